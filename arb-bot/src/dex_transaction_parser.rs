@@ -5,7 +5,18 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, debug};
 use chrono::{DateTime, Utc};
 use crate::dex_registry::{DexRegistry, DexInfo};
-use crate::protobuf_processor::ParsedTransaction;
+use crate::protobuf_processor::{InnerInstruction, ParsedTransaction, TokenBalanceChange};
+
+/// SPL Token programs - their inner transfers carry the executed swap amounts
+const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VaQ6CaxPB4D1sHbSq2pFThAQeSQgJ6";
+
+/// SPL Token instruction tags
+const TOKEN_TRANSFER: u8 = 3;
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+
+/// Confidence multiplier when only instruction data (requested / min-out) is available
+const INSTRUCTION_ONLY_CONFIDENCE_FACTOR: f64 = 0.5;
 
 /// DEX transaction parser for extracting trading information
 #[derive(Debug, Clone)]
@@ -55,6 +66,130 @@ pub struct SwapPriceInfo {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Where a swap's amounts were read from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AmountSource {
+    /// Pre/post token balances - what the trader actually spent and received
+    TokenBalances,
+    /// SPL token transfers in inner instructions
+    InnerTransfers,
+    /// Requested amount / min-out from the swap instruction (not the executed price)
+    Instruction,
+}
+
+/// Amounts a swap moved (raw token units)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutedAmounts {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub source: AmountSource,
+}
+
+impl ExecutedAmounts {
+    /// Output per unit of input, None if nothing went in
+    pub fn price(&self) -> Option<f64> {
+        if self.amount_in > 0 {
+            Some(self.amount_out as f64 / self.amount_in as f64)
+        } else {
+            None
+        }
+    }
+
+    /// Parser confidence, downgraded when only instruction-level data was available
+    pub fn confidence(&self, base_confidence: f64) -> f64 {
+        match self.source {
+            AmountSource::Instruction => base_confidence * INSTRUCTION_ONLY_CONFIDENCE_FACTOR,
+            _ => base_confidence,
+        }
+    }
+}
+
+/// Amounts a swap actually executed, falling back to the instruction's quoted amounts
+///
+/// Token balances are preferred over inner transfers (they include mints and
+/// owners); the instruction's amounts are only a bound, not the executed price.
+pub fn realized_amounts(transaction: &ParsedTransaction, requested_in: u64, quoted_out: u64) -> ExecutedAmounts {
+    executed_amounts_from_balances(&transaction.token_balances, requested_in)
+        .or_else(|| executed_amounts_from_inner_transfers(&transaction.inner_instructions, requested_in))
+        .unwrap_or(ExecutedAmounts {
+            amount_in: requested_in,
+            amount_out: quoted_out,
+            source: AmountSource::Instruction,
+        })
+}
+
+/// Executed amounts from pre/post token balances
+///
+/// The trader is the owner whose balance of one mint dropped by (closest to)
+/// `requested_in` while another mint rose; the pool vaults move the opposite way.
+pub fn executed_amounts_from_balances(balances: &[TokenBalanceChange], requested_in: u64) -> Option<ExecutedAmounts> {
+    let mut deltas: HashMap<(&str, &str), i128> = HashMap::new();
+    for balance in balances {
+        *deltas.entry((balance.owner.as_str(), balance.mint.as_str())).or_insert(0) +=
+            balance.post_amount as i128 - balance.pre_amount as i128;
+    }
+
+    deltas.iter()
+        .filter(|(_, delta)| **delta < 0)
+        .filter_map(|((owner, input_mint), delta)| {
+            let spent = u64::try_from(delta.unsigned_abs()).ok()?;
+            let received = deltas.iter()
+                .filter(|((o, m), d)| o == owner && m != input_mint && **d > 0)
+                .filter_map(|(_, d)| u64::try_from(**d).ok())
+                .max()?;
+            Some((spent, received))
+        })
+        .min_by_key(|(spent, _)| spent.abs_diff(requested_in))
+        .map(|(amount_in, amount_out)| ExecutedAmounts {
+            amount_in,
+            amount_out,
+            source: AmountSource::TokenBalances,
+        })
+}
+
+/// Executed amounts from SPL token transfers in inner instructions
+///
+/// The input leg is the transfer closest to `requested_in`; the output leg is the
+/// last transfer that neither leaves the trader's input account nor enters the
+/// pool's input vault (the final hop of a multi-hop route).
+pub fn executed_amounts_from_inner_transfers(inner_instructions: &[InnerInstruction], requested_in: u64) -> Option<ExecutedAmounts> {
+    let transfers: Vec<(&str, &str, u64)> = inner_instructions.iter()
+        .filter_map(decode_token_transfer)
+        .collect();
+
+    let (input_source, input_destination, amount_in) = *transfers.iter()
+        .min_by_key(|(_, _, amount)| amount.abs_diff(requested_in))?;
+    let (_, _, amount_out) = *transfers.iter()
+        .rev()
+        .find(|(source, destination, _)| *source != input_source && *destination != input_destination)?;
+
+    Some(ExecutedAmounts {
+        amount_in,
+        amount_out,
+        source: AmountSource::InnerTransfers,
+    })
+}
+
+/// Decode an SPL Token Transfer / TransferChecked into (source, destination, amount)
+fn decode_token_transfer(instruction: &InnerInstruction) -> Option<(&str, &str, u64)> {
+    if instruction.program_id != TOKEN_PROGRAM_ID && instruction.program_id != TOKEN_2022_PROGRAM_ID {
+        return None;
+    }
+
+    // Transfer: [source, destination, authority]
+    // TransferChecked: [source, mint, destination, authority]
+    let destination_index = match *instruction.data.first()? {
+        TOKEN_TRANSFER => 1,
+        TOKEN_TRANSFER_CHECKED => 2,
+        _ => return None,
+    };
+    let amount = u64::from_le_bytes(instruction.data.get(1..9)?.try_into().ok()?);
+    let source = instruction.accounts.first()?;
+    let destination = instruction.accounts.get(destination_index)?;
+
+    Some((source.as_str(), destination.as_str(), amount))
+}
+
 impl DexTransactionParser {
     pub fn new() -> Self {
         let mut parser = Self {
@@ -330,7 +465,7 @@ impl DexTransactionParser {
             // Check for Jupiter route discriminator
             if discriminator == [229, 23, 203, 151, 122, 227, 173, 42] {
                 let mut swap_info = self.parse_real_jupiter_route_instruction(&transaction.data, transaction, dex_info).await?;
-                swap_info.confidence = 0.75; // Lower confidence for secondary hops
+                swap_info.confidence = swap_info.confidence.min(0.75); // Lower confidence for secondary hops
                 return Ok(swap_info);
             }
         }
@@ -421,26 +556,24 @@ impl DexTransactionParser {
             .ok_or_else(|| anyhow::anyhow!("Missing token B account"))?
             .clone();
 
-        // Calculate REAL price from actual swap amounts
-        let real_price = if amount_in > 0 {
-            minimum_amount_out as f64 / amount_in as f64
-        } else {
-            return Err(anyhow::anyhow!("Invalid swap amounts"));
-        };
+        // Calculate REAL price from executed amounts (min-out is only a bound)
+        let executed = realized_amounts(transaction, amount_in, minimum_amount_out);
+        let real_price = executed.price()
+            .ok_or_else(|| anyhow::anyhow!("Invalid swap amounts"))?;
 
-        info!("🔥 REAL Raydium swap: {} {} → {} {} (price: {:.8})",
-              amount_in, token_a, minimum_amount_out, token_b, real_price);
+        info!("🔥 REAL Raydium swap: {} {} → {} {} (price: {:.8}, from {:?})",
+              executed.amount_in, token_a, executed.amount_out, token_b, real_price, executed.source);
 
         Ok(SwapPriceInfo {
             token_mint: token_a.clone(),
             base_token_mint: token_b.clone(),
-            price: real_price, // REAL price from blockchain instruction
-            volume_base: amount_in as f64,
-            volume_quote: minimum_amount_out as f64,
+            price: real_price, // REAL executed price
+            volume_base: executed.amount_in as f64,
+            volume_quote: executed.amount_out as f64,
             liquidity_before: 0, // Would need pool state parsing for accurate liquidity
             liquidity_after: 0,
             dex_name: dex_info.name.clone(),
-            confidence: 0.95, // High confidence - real data
+            confidence: executed.confidence(0.95), // High confidence - real data
             timestamp: transaction.timestamp,
         })
     }
@@ -477,26 +610,24 @@ impl DexTransactionParser {
             .ok_or_else(|| anyhow::anyhow!("Missing token B account"))?
             .clone();
 
-        // Calculate REAL price from actual swap amounts
-        let real_price = if amount > 0 {
-            other_amount_threshold as f64 / amount as f64
-        } else {
-            return Err(anyhow::anyhow!("Invalid Orca swap amounts"));
-        };
+        // Calculate REAL price from executed amounts (threshold is only a bound)
+        let executed = realized_amounts(transaction, amount, other_amount_threshold);
+        let real_price = executed.price()
+            .ok_or_else(|| anyhow::anyhow!("Invalid Orca swap amounts"))?;
 
-        info!("🌊 REAL Orca swap: {} {} → {} {} (price: {:.8})",
-              amount, token_a, other_amount_threshold, token_b, real_price);
+        info!("🌊 REAL Orca swap: {} {} → {} {} (price: {:.8}, from {:?})",
+              executed.amount_in, token_a, executed.amount_out, token_b, real_price, executed.source);
 
         Ok(SwapPriceInfo {
             token_mint: token_a.clone(),
             base_token_mint: token_b.clone(),
-            price: real_price, // REAL price from blockchain instruction
-            volume_base: amount as f64,
-            volume_quote: other_amount_threshold as f64,
+            price: real_price, // REAL executed price
+            volume_base: executed.amount_in as f64,
+            volume_quote: executed.amount_out as f64,
             liquidity_before: 0, // Would need pool state parsing for accurate liquidity
             liquidity_after: 0,
             dex_name: dex_info.name.clone(),
-            confidence: 0.95, // High confidence - real data
+            confidence: executed.confidence(0.95), // High confidence - real data
             timestamp: transaction.timestamp,
         })
     }
@@ -532,26 +663,24 @@ impl DexTransactionParser {
             .ok_or_else(|| anyhow::anyhow!("Missing destination token account"))?
             .clone();
 
-        // Calculate REAL price from actual route amounts
-        let real_price = if in_amount > 0 {
-            quoted_out_amount as f64 / in_amount as f64
-        } else {
-            return Err(anyhow::anyhow!("Invalid Jupiter route amounts"));
-        };
+        // Calculate REAL price from executed amounts (quoted out is pre-slippage)
+        let executed = realized_amounts(transaction, in_amount, quoted_out_amount);
+        let real_price = executed.price()
+            .ok_or_else(|| anyhow::anyhow!("Invalid Jupiter route amounts"))?;
 
-        info!("🚀 REAL Jupiter route: {} {} → {} {} (price: {:.8})",
-              in_amount, token_a, quoted_out_amount, token_b, real_price);
+        info!("🚀 REAL Jupiter route: {} {} → {} {} (price: {:.8}, from {:?})",
+              executed.amount_in, token_a, executed.amount_out, token_b, real_price, executed.source);
 
         Ok(SwapPriceInfo {
             token_mint: token_a.clone(),
             base_token_mint: token_b.clone(),
-            price: real_price, // REAL price from blockchain instruction
-            volume_base: in_amount as f64,
-            volume_quote: quoted_out_amount as f64,
+            price: real_price, // REAL executed price
+            volume_base: executed.amount_in as f64,
+            volume_quote: executed.amount_out as f64,
             liquidity_before: 0, // Jupiter aggregates across multiple DEXs
             liquidity_after: 0,
             dex_name: dex_info.name.clone(),
-            confidence: 0.90, // High confidence - real data from aggregator
+            confidence: executed.confidence(0.90), // High confidence - real data from aggregator
             timestamp: transaction.timestamp,
         })
     }
@@ -561,10 +690,8 @@ impl DexTransactionParser {
         // Meteora DLMM swap instruction layout (after discriminator):
         // - amount_in: u64 (8 bytes)
         // - min_amount_out: u64 (8 bytes)
-        // - active_id: i32 (4 bytes) - current active bin
-        // - max_active_id: i32 (4 bytes) - maximum active bin
 
-        if instruction_data.len() < 32 { // 8 discriminator + 8 amount_in + 8 min_out + 4 active_id + 4 max_id
+        if instruction_data.len() < 24 { // 8 discriminator + 8 amount_in + 8 min_out
             return Err(anyhow::anyhow!("Invalid Meteora instruction data length"));
         }
 
@@ -587,26 +714,24 @@ impl DexTransactionParser {
             .ok_or_else(|| anyhow::anyhow!("Missing token B account"))?
             .clone();
 
-        // Calculate REAL price from actual DLMM swap amounts
-        let real_price = if amount_in > 0 {
-            min_amount_out as f64 / amount_in as f64
-        } else {
-            return Err(anyhow::anyhow!("Invalid Meteora DLMM swap amounts"));
-        };
+        // Calculate REAL price from executed DLMM amounts (min-out is only a bound)
+        let executed = realized_amounts(transaction, amount_in, min_amount_out);
+        let real_price = executed.price()
+            .ok_or_else(|| anyhow::anyhow!("Invalid Meteora DLMM swap amounts"))?;
 
-        info!("⚡ REAL Meteora DLMM swap: {} {} → {} {} (price: {:.8})",
-              amount_in, token_a, min_amount_out, token_b, real_price);
+        info!("⚡ REAL Meteora DLMM swap: {} {} → {} {} (price: {:.8}, from {:?})",
+              executed.amount_in, token_a, executed.amount_out, token_b, real_price, executed.source);
 
         Ok(SwapPriceInfo {
             token_mint: token_a.clone(),
             base_token_mint: token_b.clone(),
-            price: real_price, // REAL price from blockchain instruction
-            volume_base: amount_in as f64,
-            volume_quote: min_amount_out as f64,
+            price: real_price, // REAL executed price
+            volume_base: executed.amount_in as f64,
+            volume_quote: executed.amount_out as f64,
             liquidity_before: 0, // Would need bin state parsing for accurate liquidity
             liquidity_after: 0,
             dex_name: dex_info.name.clone(),
-            confidence: 0.95, // High confidence - real DLMM data
+            confidence: executed.confidence(0.95), // High confidence - real DLMM data
             timestamp: transaction.timestamp,
         })
     }
@@ -772,4 +897,242 @@ impl Default for DexTransactionParser {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+    const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const TRADER: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+    const POOL: &str = "5rCf1DM8LjKTw4YqhnoLcngyZYeNnQqztScTogYHAS6";
+    const TRADER_SOL: &str = "TraderWsoLAccount11111111111111111111111111";
+    const TRADER_USDC: &str = "TraderUsdcAccount11111111111111111111111111";
+    const VAULT_SOL: &str = "PoolWsolVault111111111111111111111111111111";
+    const VAULT_USDC: &str = "PoolUsdcVault111111111111111111111111111111";
+
+    // Hand-built DLMM swap: 1.5 SOL in, min-out 200 USDC, executed 212.345678 USDC
+    const AMOUNT_IN: u64 = 1_500_000_000;
+    const MIN_OUT: u64 = 200_000_000;
+    const EXECUTED_OUT: u64 = 212_345_678;
+
+    fn dlmm_swap_data() -> Vec<u8> {
+        let mut data = vec![248, 198, 158, 145, 225, 117, 135, 200];
+        data.extend_from_slice(&AMOUNT_IN.to_le_bytes());
+        data.extend_from_slice(&MIN_OUT.to_le_bytes());
+        data
+    }
+
+    fn balance(account: &str, mint: &str, owner: &str, pre_amount: u64, post_amount: u64) -> TokenBalanceChange {
+        TokenBalanceChange {
+            account: account.to_string(),
+            mint: mint.to_string(),
+            owner: owner.to_string(),
+            pre_amount,
+            post_amount,
+        }
+    }
+
+    fn transfer(source: &str, destination: &str, amount: u64) -> InnerInstruction {
+        let mut data = vec![TOKEN_TRANSFER];
+        data.extend_from_slice(&amount.to_le_bytes());
+        InnerInstruction {
+            program_id: TOKEN_PROGRAM_ID.to_string(),
+            accounts: vec![source.to_string(), destination.to_string(), TRADER.to_string()],
+            data,
+        }
+    }
+
+    fn captured_swap() -> ParsedTransaction {
+        ParsedTransaction {
+            signature: "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn".to_string(),
+            program_id: "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo".to_string(),
+            accounts: vec![SOL_MINT.to_string(), USDC_MINT.to_string()],
+            data: dlmm_swap_data(),
+            timestamp: Utc::now(),
+            inner_instructions: vec![
+                transfer(TRADER_SOL, VAULT_SOL, AMOUNT_IN),
+                transfer(VAULT_USDC, TRADER_USDC, EXECUTED_OUT),
+            ],
+            token_balances: vec![
+                balance(TRADER_SOL, SOL_MINT, TRADER, 4_000_000_000, 2_500_000_000),
+                balance(TRADER_USDC, USDC_MINT, TRADER, 10_000_000, 10_000_000 + EXECUTED_OUT),
+                balance(VAULT_SOL, SOL_MINT, POOL, 90_000_000_000, 90_000_000_000 + AMOUNT_IN),
+                balance(VAULT_USDC, USDC_MINT, POOL, 13_000_000_000, 13_000_000_000 - EXECUTED_OUT),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_price_reflects_executed_amounts_not_min_out() {
+        let mut parser = DexTransactionParser::new();
+        let swaps = parser.parse_dex_transactions(&[captured_swap()]).await.unwrap();
+
+        assert_eq!(swaps.len(), 1);
+        let expected_price = EXECUTED_OUT as f64 / AMOUNT_IN as f64;
+        assert!((swaps[0].price - expected_price).abs() < 1e-12);
+        assert_eq!(swaps[0].volume_quote, EXECUTED_OUT as f64);
+        assert_eq!(swaps[0].confidence, 0.95);
+    }
+
+    #[test]
+    fn test_executed_amounts_from_balances_and_inner_transfers() {
+        let swap = captured_swap();
+        let expected = |source| ExecutedAmounts {
+            amount_in: AMOUNT_IN,
+            amount_out: EXECUTED_OUT,
+            source,
+        };
+
+        // Trader spent SOL and received USDC; the pool moved the opposite way
+        assert_eq!(
+            executed_amounts_from_balances(&swap.token_balances, AMOUNT_IN),
+            Some(expected(AmountSource::TokenBalances))
+        );
+        assert_eq!(
+            executed_amounts_from_inner_transfers(&swap.inner_instructions, AMOUNT_IN),
+            Some(expected(AmountSource::InnerTransfers))
+        );
+
+        // No balances: inner transfers still give the executed amounts
+        let transfers_only = ParsedTransaction { token_balances: Vec::new(), ..swap };
+        assert_eq!(
+            realized_amounts(&transfers_only, AMOUNT_IN, MIN_OUT),
+            expected(AmountSource::InnerTransfers)
+        );
+    }
+
+    #[test]
+    fn test_instruction_only_data_downgrades_confidence() {
+        let swap = ParsedTransaction {
+            inner_instructions: Vec::new(),
+            token_balances: Vec::new(),
+            ..captured_swap()
+        };
+
+        let executed = realized_amounts(&swap, AMOUNT_IN, MIN_OUT);
+        assert_eq!(executed.source, AmountSource::Instruction);
+        assert_eq!(executed.amount_out, MIN_OUT);
+        assert_eq!(executed.confidence(0.95), 0.95 * INSTRUCTION_ONLY_CONFIDENCE_FACTOR);
+    }
+
+    #[tokio::test]
+    async fn test_dlmm_swap_data_is_amounts_only() {
+        // DLMM `swap` args are amount_in and min_amount_out: 8 discriminator + 8 + 8 bytes
+        let swap = captured_swap();
+        assert_eq!(swap.data.len(), 24);
+
+        let mut parser = DexTransactionParser::new();
+        let swaps = parser.parse_dex_transactions(&[swap.clone()]).await.unwrap();
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].volume_base, AMOUNT_IN as f64);
+
+        // One byte short of min_amount_out is rejected, not read past the end
+        let truncated = ParsedTransaction { data: swap.data[..23].to_vec(), ..swap };
+        let swaps = parser.parse_dex_transactions(&[truncated]).await.unwrap();
+        assert!(swaps.is_empty());
+    }
+
+    /// Top-level DLMM swap from a raw `getTransaction` result (json encoding),
+    /// as the ShredStream path would see it, plus the transaction's fee payer
+    fn load_captured_dlmm_transaction(name: &str) -> (ParsedTransaction, String) {
+        let path = format!("{}/fixtures/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+        let raw = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        let tx: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        let message = &tx["transaction"]["message"];
+        let meta = &tx["meta"];
+
+        let mut keys: Vec<String> = Vec::new();
+        for list in [&message["accountKeys"], &meta["loadedAddresses"]["writable"], &meta["loadedAddresses"]["readonly"]] {
+            keys.extend(list.as_array().into_iter().flatten().map(|key| key.as_str().unwrap().to_string()));
+        }
+        let accounts_of = |ix: &serde_json::Value| -> Vec<String> {
+            ix["accounts"].as_array().unwrap().iter()
+                .map(|i| keys[i.as_u64().unwrap() as usize].clone())
+                .collect()
+        };
+        let data_of = |ix: &serde_json::Value| bs58::decode(ix["data"].as_str().unwrap()).into_vec().unwrap();
+
+        let dlmm_program = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+        let (index, swap_ix) = message["instructions"].as_array().unwrap().iter()
+            .enumerate()
+            .find(|(_, ix)| keys[ix["programIdIndex"].as_u64().unwrap() as usize] == dlmm_program)
+            .expect("fixture has no top-level DLMM instruction");
+
+        let inner_instructions = meta["innerInstructions"].as_array().unwrap().iter()
+            .filter(|inner| inner["index"].as_u64() == Some(index as u64))
+            .flat_map(|inner| inner["instructions"].as_array().unwrap().iter())
+            .map(|ix| InnerInstruction {
+                program_id: keys[ix["programIdIndex"].as_u64().unwrap() as usize].clone(),
+                accounts: accounts_of(ix),
+                data: data_of(ix),
+            })
+            .collect();
+
+        // Pre/post balances joined by account index; created or closed accounts count as 0
+        let mut balances: HashMap<u64, TokenBalanceChange> = HashMap::new();
+        for (field, is_post) in [("preTokenBalances", false), ("postTokenBalances", true)] {
+            for entry in meta[field].as_array().unwrap() {
+                let amount: u64 = entry["uiTokenAmount"]["amount"].as_str().unwrap().parse().unwrap();
+                let index = entry["accountIndex"].as_u64().unwrap();
+                let change = balances.entry(index).or_insert_with(|| balance(
+                    &keys[index as usize],
+                    entry["mint"].as_str().unwrap(),
+                    entry["owner"].as_str().unwrap(),
+                    0,
+                    0,
+                ));
+                if is_post {
+                    change.post_amount = amount;
+                } else {
+                    change.pre_amount = amount;
+                }
+            }
+        }
+
+        let swap = ParsedTransaction {
+            signature: tx["transaction"]["signatures"][0].as_str().unwrap().to_string(),
+            program_id: dlmm_program.to_string(),
+            accounts: accounts_of(swap_ix),
+            data: data_of(swap_ix),
+            timestamp: DateTime::from_timestamp(tx["blockTime"].as_i64().unwrap(), 0).unwrap(),
+            inner_instructions,
+            token_balances: balances.into_values().collect(),
+        };
+        (swap, keys[0].clone())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs fixtures/meteora_dlmm_swap_tx.json (see fixtures/README.md)"]
+    async fn test_captured_dlmm_swap_uses_executed_amounts() {
+        let (swap, fee_payer) = load_captured_dlmm_transaction("meteora_dlmm_swap_tx");
+        let requested_in = u64::from_le_bytes(swap.data[8..16].try_into().unwrap());
+        let min_out = u64::from_le_bytes(swap.data[16..24].try_into().unwrap());
+
+        // What the fee payer actually spent and received, per mint
+        let mut payer_deltas: HashMap<&str, i128> = HashMap::new();
+        for change in swap.token_balances.iter().filter(|change| change.owner == fee_payer) {
+            *payer_deltas.entry(change.mint.as_str()).or_insert(0) +=
+                change.post_amount as i128 - change.pre_amount as i128;
+        }
+        let spent = payer_deltas.values().copied().min().unwrap().unsigned_abs() as u64;
+        let received = payer_deltas.values().copied().max().unwrap() as u64;
+        assert!(spent > 0 && received > 0, "fee payer didn't trade in {}", swap.signature);
+
+        let executed = realized_amounts(&swap, requested_in, min_out);
+        assert_eq!(executed.source, AmountSource::TokenBalances);
+        assert_eq!((executed.amount_in, executed.amount_out), (spent, received));
+
+        // Inner transfers agree with the balance changes
+        let transfers = executed_amounts_from_inner_transfers(&swap.inner_instructions, requested_in).unwrap();
+        assert_eq!((transfers.amount_in, transfers.amount_out), (spent, received));
+
+        // Parsed price is the executed one, not the min-out bound
+        let parser = DexTransactionParser::new();
+        let dex_info = parser.dex_registry.get_dex_by_name("Meteora_DLMM").unwrap();
+        let info = parser.parse_meteora_dlmm_swap(&swap, dex_info).await.unwrap();
+        assert!((info.price - received as f64 / spent as f64).abs() < 1e-12);
+        assert_eq!(info.confidence, 0.95);
+    }
+}
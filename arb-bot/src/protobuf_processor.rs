@@ -23,6 +23,28 @@ pub struct ParsedTransaction {
     pub accounts: Vec<String>,
     pub data: Vec<u8>,
     pub timestamp: DateTime<Utc>,
+    /// CPIs made while executing the instruction (token transfers carry executed amounts)
+    pub inner_instructions: Vec<InnerInstruction>,
+    /// Pre/post token balances of the transaction's token accounts
+    pub token_balances: Vec<TokenBalanceChange>,
+}
+
+/// Inner (CPI) instruction from transaction metadata
+#[derive(Debug, Clone)]
+pub struct InnerInstruction {
+    pub program_id: String,
+    pub accounts: Vec<String>,
+    pub data: Vec<u8>,
+}
+
+/// Token account balance before and after the transaction (raw units)
+#[derive(Debug, Clone)]
+pub struct TokenBalanceChange {
+    pub account: String,
+    pub mint: String,
+    pub owner: String,
+    pub pre_amount: u64,
+    pub post_amount: u64,
 }
 
 /// Extracted price information from DEX transactions
@@ -152,6 +174,8 @@ impl ProtobufProcessor {
                 accounts: vec!["So11111111111111111111111111111111111111112".to_string()],
                 data: data[32..].to_vec(), // Use remaining data as instruction data
                 timestamp: chrono::Utc::now(),
+                inner_instructions: Vec::new(),
+                token_balances: Vec::new(),
             }];
 
             return Ok(Some(ShredInfo {
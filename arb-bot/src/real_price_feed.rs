@@ -167,6 +167,8 @@ impl RealPriceFeed {
                         accounts: vec!["So11111111111111111111111111111111111111112".to_string()],
                         data: chunk[32..].to_vec(),
                         timestamp: chrono::Utc::now(),
+                        inner_instructions: Vec::new(),
+                        token_balances: Vec::new(),
                     });
                 }
             }
@@ -0,0 +1,218 @@
+#!/usr/bin/env python3
+# Capture a mainnet pool fixture for the captured-account tests
+#
+# Snapshots the pool account (plus any extra accounts, e.g. tick arrays or a
+# bitmap extension) and the pool's token vaults, then - with --swap - waits for
+# the next swap on the pool. The swap is only kept if no other transaction
+# touched the pool between the snapshot and the swap, so the snapshot is
+# exactly the state the swap executed against.
+#
+# Everything a test asserts comes from the chain, never from our own parsers:
+# - token_accounts: vault mint/owner/amount from jsonParsed RPC output
+# - swap.amount_in / amount_out: vault balance deltas from the tx meta
+# - swap.reserve_in / reserve_out: vault balances before the swap (tx meta)
+# - swap.instruction_accounts: accounts of the swap instruction as executed
+#
+# Usage:
+#   ./capture_fixture.py --rpc https://api.mainnet-beta.solana.com \
+#       --name orca_whirlpool_swap --pool <POOL> --vault <VAULT_A> --vault <VAULT_B> \
+#       [--account <TICK_ARRAY> ...] [--swap]
+#
+# Writes fixtures/<name>.json (relative to clean_arb_bot/).
+
+import argparse
+import json
+import os
+import sys
+import time
+import urllib.request
+
+
+def rpc(url, method, params):
+    body = json.dumps({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})
+    request = urllib.request.Request(
+        url, data=body.encode(), headers={"Content-Type": "application/json"}
+    )
+    with urllib.request.urlopen(request, timeout=30) as response:
+        reply = json.load(response)
+    if "error" in reply:
+        raise RuntimeError(f"{method}: {reply['error']}")
+    return reply["result"]
+
+
+def latest_signature(url, address):
+    signatures = rpc(
+        url, "getSignaturesForAddress", [address, {"limit": 1, "commitment": "confirmed"}]
+    )
+    return signatures[0]["signature"] if signatures else None
+
+
+def program_instruction(url, signature, program):
+    """Transaction, its account keys and its first instruction calling `program`"""
+    tx = rpc(
+        url,
+        "getTransaction",
+        [signature, {"encoding": "json", "maxSupportedTransactionVersion": 0}],
+    )
+    message = tx["transaction"]["message"]
+    loaded = tx["meta"].get("loadedAddresses", {})
+    keys = message["accountKeys"] + loaded.get("writable", []) + loaded.get("readonly", [])
+    instructions = list(message["instructions"])
+    for inner in tx["meta"].get("innerInstructions") or []:
+        instructions.extend(inner["instructions"])
+    swap_ix = next(
+        (ix for ix in instructions if keys[ix["programIdIndex"]] == program),
+        None,
+    )
+    return tx, keys, swap_ix
+
+
+def snapshot(url, addresses):
+    """Raw accounts at one slot: {address: {owner, data}} plus the context slot"""
+    result = rpc(
+        url,
+        "getMultipleAccounts",
+        [addresses, {"encoding": "base64", "commitment": "confirmed"}],
+    )
+    accounts = {}
+    for address, account in zip(addresses, result["value"]):
+        if account is None:
+            print(f"⚠️ {address} does not exist - skipped", file=sys.stderr)
+            continue
+        accounts[address] = {"owner": account["owner"], "data": account["data"][0]}
+    return result["context"]["slot"], accounts
+
+
+def token_accounts(url, vaults):
+    """Vault mint/owner/amount as decoded by the RPC node"""
+    result = rpc(url, "getMultipleAccounts", [vaults, {"encoding": "jsonParsed"}])
+    parsed = {}
+    for address, account in zip(vaults, result["value"]):
+        info = account["data"]["parsed"]["info"]
+        parsed[address] = {
+            "mint": info["mint"],
+            "owner": info["owner"],
+            "amount": int(info["tokenAmount"]["amount"]),
+        }
+    return parsed
+
+
+def vault_balances(meta, keys, vaults, field):
+    balances = {}
+    for entry in meta[field]:
+        address = keys[entry["accountIndex"]]
+        if address in vaults:
+            balances[address] = int(entry["uiTokenAmount"]["amount"])
+    return balances
+
+
+def wait_for_swap(url, pool, program, vaults, after_signature, snapshot_slot, timeout_secs):
+    """First transaction on `pool` after `after_signature`, if it is the only one"""
+    deadline = time.time() + timeout_secs
+    while time.time() < deadline:
+        time.sleep(2)
+        params = {"limit": 10, "commitment": "confirmed"}
+        if after_signature:
+            params["until"] = after_signature
+        signatures = rpc(url, "getSignaturesForAddress", [pool, params])
+        if not signatures:
+            continue
+        if len(signatures) > 1:
+            print("⚠️ Pool traded more than once since the snapshot - retry", file=sys.stderr)
+            return None
+        entry = signatures[0]
+        if entry["slot"] <= snapshot_slot:
+            print("⚠️ Pool traded in the snapshot slot - retry", file=sys.stderr)
+            return None
+        if entry["err"] is not None:
+            print("⚠️ Next transaction on the pool failed - retry", file=sys.stderr)
+            return None
+
+        # The swap instruction: top level or inner (routed through an aggregator)
+        tx, keys, swap_ix = program_instruction(url, entry["signature"], program)
+        if swap_ix is None:
+            print("⚠️ Next transaction did not call the pool's program - retry", file=sys.stderr)
+            return None
+
+        pre = vault_balances(tx["meta"], keys, vaults, "preTokenBalances")
+        post = vault_balances(tx["meta"], keys, vaults, "postTokenBalances")
+        deltas = {vault: post.get(vault, 0) - pre.get(vault, 0) for vault in vaults}
+        vault_in = max(deltas, key=deltas.get)
+        vault_out = min(deltas, key=deltas.get)
+        return {
+            "signature": entry["signature"],
+            "slot": tx["slot"],
+            "instruction_accounts": [keys[i] for i in swap_ix["accounts"]],
+            "vault_in": vault_in,
+            "vault_out": vault_out,
+            "amount_in": deltas[vault_in],
+            "amount_out": -deltas[vault_out],
+            "reserve_in": pre.get(vault_in, 0),
+            "reserve_out": pre.get(vault_out, 0),
+        }
+    print("⚠️ No swap before the timeout", file=sys.stderr)
+    return None
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__)
+    parser.add_argument("--rpc", required=True)
+    parser.add_argument("--name", required=True)
+    parser.add_argument("--pool", required=True)
+    parser.add_argument("--vault", action="append", default=[])
+    parser.add_argument("--account", action="append", default=[])
+    parser.add_argument("--swap", action="store_true", help="Also capture the next swap")
+    parser.add_argument("--attempts", type=int, default=20)
+    parser.add_argument("--timeout", type=int, default=120, help="Seconds to wait per attempt")
+    args = parser.parse_args()
+
+    program = rpc(args.rpc, "getAccountInfo", [args.pool, {"encoding": "base64"}])["value"][
+        "owner"
+    ]
+
+    for _ in range(args.attempts):
+        # Snapshot the accounts the last swap used too - the next swap most
+        # likely walks the same tick arrays
+        before = latest_signature(args.rpc, args.pool)
+        addresses = [args.pool] + args.account + args.vault
+        if before:
+            _, keys, last_ix = program_instruction(args.rpc, before, program)
+            if last_ix is not None:
+                addresses += [keys[i] for i in last_ix["accounts"] if keys[i] not in addresses]
+
+        # Pool must not trade while the snapshot is taken
+        slot, accounts = snapshot(args.rpc, addresses[:100])
+        if latest_signature(args.rpc, args.pool) != before:
+            continue
+        if args.pool not in accounts:
+            sys.exit(f"Pool {args.pool} not found")
+
+        fixture = {
+            "name": args.name,
+            "program": program,
+            "pool": args.pool,
+            "slot": slot,
+            "accounts": accounts,
+            "token_accounts": token_accounts(args.rpc, args.vault) if args.vault else {},
+        }
+        if args.swap:
+            swap = wait_for_swap(
+                args.rpc, args.pool, program, args.vault, before, slot, args.timeout
+            )
+            if swap is None:
+                continue
+            fixture["swap"] = swap
+
+        path = os.path.join(os.path.dirname(__file__), "..", "fixtures", f"{args.name}.json")
+        os.makedirs(os.path.dirname(path), exist_ok=True)
+        with open(path, "w") as out:
+            json.dump(fixture, out, indent=2)
+            out.write("\n")
+        print(f"✅ Wrote {os.path.normpath(path)} (slot {slot})")
+        return
+
+    sys.exit("Gave up: no clean snapshot/swap pair captured")
+
+
+if __name__ == "__main__":
+    main()
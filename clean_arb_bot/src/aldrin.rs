@@ -0,0 +1,577 @@
+// Aldrin AMM swap instruction builder
+//
+// Aldrin AMM (AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6) is a classic
+// constant-product pool written in Anchor. The pool account stores the base and
+// quote vaults, the LP mint, the fee accounts and the fee schedule. Vaults are
+// owned by a pool signer PDA seeded by the pool address and a stored nonce.
+//
+// Swaps are expressed as an order side rather than an A→B flag: `Ask` sells
+// base for quote, `Bid` buys base with quote.
+
+use anyhow::{Context, Result};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::amm_math::{constant_product_output, fee_amount, token_account_amount};
+use crate::pool_registry::PoolRegistry;
+use crate::rpc_client::RpcApi;
+use crate::types::{SwapParams, UserSwapAccounts};
+
+/// SHA256("global:swap")[0..8]
+const SWAP_DISCRIMINATOR: [u8; 8] = [0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+
+/// `Side` enum values (Borsh u8)
+const SIDE_BID: u8 = 0;
+const SIDE_ASK: u8 = 1;
+
+/// Aldrin pool state fields needed to build and price a swap
+///
+/// Offsets follow the Anchor `Pool` account (441 bytes):
+/// - bytes 0-8: discriminator
+/// - bytes 8-40: lp_token_freeze_vault
+/// - bytes 40-72: pool_mint (LP mint)
+/// - bytes 72-104: pool_signer
+/// - byte 104: pool_signer_nonce
+/// - bytes 105-137: authority
+/// - bytes 137-169: initializer_account
+/// - bytes 169-201: fee_base_account
+/// - bytes 201-233: fee_quote_account
+/// - bytes 233-265: fee_pool_token_account
+/// - bytes 265-297: base_token_vault
+/// - bytes 297-329: base_token_mint
+/// - bytes 329-361: quote_token_vault
+/// - bytes 361-393: quote_token_mint
+/// - bytes 393-441: fees (trade, owner trade and owner withdraw numerator/denominator u64s)
+///
+/// Layout taken from the program's IDL; confirm against a live pool on Solscan
+/// before enabling real trading on Aldrin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AldrinPoolState {
+    pub pool_mint: Pubkey,
+    pub pool_signer: Pubkey,
+    pub pool_signer_nonce: u8,
+    pub fee_pool_token_account: Pubkey,
+    pub base_token_vault: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_vault: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+}
+
+impl AldrinPoolState {
+    /// Minimum account length covering all fields we read
+    const MIN_LEN: usize = 425;
+
+    /// Parse Aldrin pool account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < Self::MIN_LEN {
+            return Err(anyhow::anyhow!(
+                "Aldrin pool state too short ({} bytes). Expected at least {} bytes.",
+                data.len(),
+                Self::MIN_LEN
+            ));
+        }
+
+        let pubkey_at = |offset: usize, name: &str| -> Result<Pubkey> {
+            Pubkey::try_from(&data[offset..offset + 32])
+                .map_err(|_| anyhow::anyhow!("Failed to parse {} pubkey from pool state", name))
+        };
+        let u64_at = |offset: usize| -> Result<u64> {
+            Ok(u64::from_le_bytes(data[offset..offset + 8].try_into()?))
+        };
+
+        Ok(Self {
+            pool_mint: pubkey_at(40, "pool mint")?,
+            pool_signer: pubkey_at(72, "pool signer")?,
+            pool_signer_nonce: data[104],
+            fee_pool_token_account: pubkey_at(233, "fee pool token account")?,
+            base_token_vault: pubkey_at(265, "base vault")?,
+            base_token_mint: pubkey_at(297, "base mint")?,
+            quote_token_vault: pubkey_at(329, "quote vault")?,
+            quote_token_mint: pubkey_at(361, "quote mint")?,
+            trade_fee_numerator: u64_at(393)?,
+            trade_fee_denominator: u64_at(401)?,
+            owner_trade_fee_numerator: u64_at(409)?,
+            owner_trade_fee_denominator: u64_at(417)?,
+        })
+    }
+
+    /// Input left after trade + owner trade fees are taken
+    pub fn amount_after_fees(&self, amount_in: u64) -> u64 {
+        amount_in
+            .saturating_sub(fee_amount(
+                amount_in,
+                self.trade_fee_numerator,
+                self.trade_fee_denominator,
+            ))
+            .saturating_sub(fee_amount(
+                amount_in,
+                self.owner_trade_fee_numerator,
+                self.owner_trade_fee_denominator,
+            ))
+    }
+
+    /// Constant-product output for `amount_in` after trade + owner trade fees
+    pub fn quote(&self, amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+        constant_product_output(self.amount_after_fees(amount_in), reserve_in, reserve_out)
+    }
+}
+
+/// Aldrin swap instruction builder
+pub struct AldrinSwapBuilder {
+    /// RPC client for fetching pool state
+    rpc_client: Arc<dyn RpcApi>,
+    /// Pool registry for address resolution
+    pool_registry: Arc<PoolRegistry>,
+    /// Aldrin AMM program ID
+    program_id: Pubkey,
+}
+
+impl AldrinSwapBuilder {
+    /// Aldrin AMM program ID
+    pub const PROGRAM_ID: &'static str = "AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6";
+
+    /// Estimated compute units for one Aldrin swap (constant-product math, fee account transfer)
+    pub fn estimated_compute_units(&self) -> u32 {
+        45_000
+    }
+
+    /// Create new Aldrin swap builder
+    pub fn new(rpc_client: Arc<dyn RpcApi>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
+        let program_id = Self::PROGRAM_ID
+            .parse()
+            .context("Failed to parse Aldrin program ID")?;
+
+        info!("✅ Aldrin swap builder initialized");
+        info!("   Program ID: {}", Self::PROGRAM_ID);
+
+        Ok(Self {
+            rpc_client,
+            pool_registry,
+            program_id,
+        })
+    }
+
+    /// Build swap instruction for an Aldrin pool
+    ///
+    /// # Arguments
+    /// * `pool_short_id` - 8-char short pool ID from ShredStream
+    /// * `swap_params` - Swap parameters (amount_in, minimum_amount_out, direction;
+    ///   A is the pool's base token, B its quote token)
+    /// * `user_pubkey` - User's wallet public key
+    ///
+    /// # Returns
+    /// Solana instruction for the swap
+    pub async fn build_swap_instruction(
+        &self,
+        pool_short_id: &str,
+        swap_params: &SwapParams,
+        user_pubkey: &Pubkey,
+    ) -> Result<Instruction> {
+        debug!(
+            "Building Aldrin swap instruction for pool: {}",
+            pool_short_id
+        );
+
+        // Step 1: Resolve pool address from short ID
+        let pool_address = self
+            .pool_registry
+            .resolve_pool_address(pool_short_id, &crate::types::DexType::Aldrin)
+            .await
+            .context(format!(
+                "Failed to resolve pool address for {}",
+                pool_short_id
+            ))?;
+
+        debug!(
+            "✅ Resolved pool {} to address: {}",
+            pool_short_id, pool_address
+        );
+
+        // Safety fallback - pools are normally validated by the arbitrage engine
+        self.pool_registry.ensure_pool_valid(pool_short_id).await?;
+
+        // Step 2: Fetch and parse pool state (vaults, mints, fees)
+        let pool = self.fetch_pool_state(&pool_address)?;
+
+        debug!("Base Vault: {}", pool.base_token_vault);
+        debug!("Quote Vault: {}", pool.quote_token_vault);
+
+        // Step 3: Derive pool signer (must match the stored one) and user token accounts
+        let pool_signer = Self::derive_pool_signer(&pool_address, &pool, &self.program_id)?;
+
+        // Base → quote is an ask (A→B)
+        let (mint_in, mint_out) = if swap_params.swap_a_to_b {
+            (pool.base_token_mint, pool.quote_token_mint)
+        } else {
+            (pool.quote_token_mint, pool.base_token_mint)
+        };
+        let user = UserSwapAccounts {
+            owner: *user_pubkey,
+            token_in: spl_associated_token_account::get_associated_token_address(
+                user_pubkey,
+                &mint_in,
+            ),
+            token_out: spl_associated_token_account::get_associated_token_address(
+                user_pubkey,
+                &mint_out,
+            ),
+        };
+
+        // Step 4: Build instruction
+        let instruction = Self::build_aldrin_swap_ix(
+            &self.program_id,
+            &pool_address,
+            &pool_signer,
+            &pool,
+            &user,
+            swap_params,
+        );
+
+        info!("✅ Built Aldrin swap instruction");
+        info!("   Pool: {}", pool_address);
+        info!("   Amount in: {} lamports", swap_params.amount_in);
+        info!(
+            "   Min amount out: {} lamports",
+            swap_params.minimum_amount_out
+        );
+        info!(
+            "   Side: {}",
+            if swap_params.swap_a_to_b {
+                "Ask (base→quote)"
+            } else {
+                "Bid (quote→base)"
+            }
+        );
+
+        Ok(instruction)
+    }
+
+    /// Fetch and parse Aldrin pool account
+    fn fetch_pool_state(&self, pool_address: &Pubkey) -> Result<AldrinPoolState> {
+        let data = self
+            .rpc_client
+            .get_account_data(pool_address, None)
+            .context("Failed to fetch Aldrin pool state")?;
+
+        AldrinPoolState::parse(&data)
+    }
+
+    /// Derive the pool signer PDA (seeds: [pool, nonce]) and check it against pool state
+    pub fn derive_pool_signer(
+        pool_address: &Pubkey,
+        pool: &AldrinPoolState,
+        program_id: &Pubkey,
+    ) -> Result<Pubkey> {
+        let pool_signer = Pubkey::create_program_address(
+            &[pool_address.as_ref(), &[pool.pool_signer_nonce]],
+            program_id,
+        )
+        .context("Failed to derive Aldrin pool signer from stored nonce")?;
+
+        if pool_signer != pool.pool_signer {
+            return Err(anyhow::anyhow!(
+                "Aldrin pool signer mismatch: derived {} but pool stores {} (layout changed?)",
+                pool_signer,
+                pool.pool_signer
+            ));
+        }
+
+        Ok(pool_signer)
+    }
+
+    /// Build the raw Aldrin swap instruction
+    ///
+    /// Account order for `swap`:
+    /// 0. [readonly] pool_public_key (Pool state account)
+    /// 1. [readonly] pool_signer (Pool signer PDA)
+    /// 2. [writable] pool_mint (LP mint)
+    /// 3. [writable] base_token_vault
+    /// 4. [writable] quote_token_vault
+    /// 5. [writable] fee_pool_token_account
+    /// 6. [signer] wallet_authority (User wallet)
+    /// 7. [writable] user_base_token_account
+    /// 8. [writable] user_quote_token_account
+    /// 9. [readonly] token_program
+    fn build_aldrin_swap_ix(
+        program_id: &Pubkey,
+        pool_address: &Pubkey,
+        pool_signer: &Pubkey,
+        pool: &AldrinPoolState,
+        user: &UserSwapAccounts,
+        swap_params: &SwapParams,
+    ) -> Instruction {
+        // User accounts are listed base then quote, whichever way the swap goes
+        let (user_base, user_quote) = user.by_side(swap_params.swap_a_to_b);
+        let accounts = vec![
+            AccountMeta::new_readonly(*pool_address, false),
+            AccountMeta::new_readonly(*pool_signer, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new(pool.base_token_vault, false),
+            AccountMeta::new(pool.quote_token_vault, false),
+            AccountMeta::new(pool.fee_pool_token_account, false),
+            AccountMeta::new_readonly(user.owner, true),
+            AccountMeta::new(user_base, false),
+            AccountMeta::new(user_quote, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+
+        // Instruction data: [discriminator: 8][tokens: u64][min_tokens: u64][side: u8]
+        let side = if swap_params.swap_a_to_b {
+            SIDE_ASK
+        } else {
+            SIDE_BID
+        };
+        let mut data = Vec::with_capacity(25);
+        data.extend_from_slice(&SWAP_DISCRIMINATOR);
+        data.extend_from_slice(&swap_params.amount_in.to_le_bytes());
+        data.extend_from_slice(&swap_params.minimum_amount_out.to_le_bytes());
+        data.push(side);
+
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Estimate output amount for a swap (useful for slippage calculation)
+    ///
+    /// Constant product (x*y=k) over current vault balances, after the pool's
+    /// trade and owner fees.
+    ///
+    /// # Returns
+    /// Expected output amount (after fees)
+    pub fn estimate_swap_output(
+        &self,
+        pool_short_id: &str,
+        amount_in: u64,
+        swap_a_to_b: bool,
+    ) -> Result<u64> {
+        debug!("Estimating swap output for Aldrin pool: {}", pool_short_id);
+
+        let pool_info = self
+            .pool_registry
+            .get_pool(pool_short_id)
+            .ok_or_else(|| anyhow::anyhow!("Pool {} not found", pool_short_id))?;
+
+        let pool = self.fetch_pool_state(&pool_info.full_address)?;
+
+        let vaults = self
+            .rpc_client
+            .get_multiple_accounts(&[pool.base_token_vault, pool.quote_token_vault])?;
+        let base_reserve = token_account_amount(vaults.first().and_then(|v| v.as_deref()))
+            .context("Failed to read Aldrin base vault balance")?;
+        let quote_reserve = token_account_amount(vaults.get(1).and_then(|v| v.as_deref()))
+            .context("Failed to read Aldrin quote vault balance")?;
+
+        let (reserve_in, reserve_out) = if swap_a_to_b {
+            (base_reserve, quote_reserve)
+        } else {
+            (quote_reserve, base_reserve)
+        };
+
+        Ok(pool.quote(amount_in, reserve_in, reserve_out))
+    }
+
+    /// SOL-side reserve of a pool (liquidity depth check before position sizing)
+    ///
+    /// # Returns
+    /// WSOL vault balance in lamports, or error if neither side of the pool is WSOL
+    pub fn sol_reserve(&self, pool_address: &Pubkey) -> Result<u64> {
+        let pool = self.fetch_pool_state(pool_address)?;
+        let sol_vault = if pool.base_token_mint == spl_token::native_mint::id() {
+            pool.base_token_vault
+        } else if pool.quote_token_mint == spl_token::native_mint::id() {
+            pool.quote_token_vault
+        } else {
+            return Err(anyhow::anyhow!(
+                "Aldrin pool {} has no WSOL side",
+                pool_address
+            ));
+        };
+
+        let data = self
+            .rpc_client
+            .get_account_data(&sol_vault, None)
+            .context("Failed to fetch Aldrin SOL vault")?;
+        token_account_amount(Some(&data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::captured_fixtures::{pubkey, CapturedFixture};
+
+    /// Pool account laid out like an on-chain Aldrin pool, with the signer and
+    /// nonce taken from the canonical PDA for `pool_address`
+    fn sample_pool_data(pool_address: &Pubkey, program_id: &Pubkey) -> (Vec<u8>, Vec<Pubkey>) {
+        let mut data = vec![0u8; 441];
+        let (pool_signer, nonce) =
+            Pubkey::find_program_address(&[pool_address.as_ref()], program_id);
+        data[72..104].copy_from_slice(pool_signer.as_ref());
+        data[104] = nonce;
+
+        // pool_mint, fee_pool_token_account, base vault/mint, quote vault/mint
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        for (offset, key) in [40, 233, 265, 297, 329, 361].iter().zip(&keys) {
+            data[*offset..*offset + 32].copy_from_slice(key.as_ref());
+        }
+
+        // 0.25% trade fee + 0.05% owner fee
+        data[393..401].copy_from_slice(&25u64.to_le_bytes());
+        data[401..409].copy_from_slice(&10_000u64.to_le_bytes());
+        data[409..417].copy_from_slice(&5u64.to_le_bytes());
+        data[417..425].copy_from_slice(&10_000u64.to_le_bytes());
+
+        (data, keys)
+    }
+
+    #[test]
+    fn test_vaults_and_pool_signer_from_pool_account() {
+        let program_id: Pubkey = AldrinSwapBuilder::PROGRAM_ID.parse().unwrap();
+        let pool_address = Pubkey::new_unique();
+        let (data, keys) = sample_pool_data(&pool_address, &program_id);
+
+        let pool = AldrinPoolState::parse(&data).unwrap();
+        assert_eq!(pool.pool_mint, keys[0]);
+        assert_eq!(pool.fee_pool_token_account, keys[1]);
+        assert_eq!(pool.base_token_vault, keys[2]);
+        assert_eq!(pool.base_token_mint, keys[3]);
+        assert_eq!(pool.quote_token_vault, keys[4]);
+        assert_eq!(pool.quote_token_mint, keys[5]);
+
+        // Stored nonce reproduces the stored pool signer
+        let pool_signer =
+            AldrinSwapBuilder::derive_pool_signer(&pool_address, &pool, &program_id).unwrap();
+        assert_eq!(pool_signer, pool.pool_signer);
+
+        // A signer that doesn't match the nonce means the layout is wrong
+        let mut mismatched = pool.clone();
+        mismatched.pool_signer = Pubkey::new_unique();
+        assert!(
+            AldrinSwapBuilder::derive_pool_signer(&pool_address, &mismatched, &program_id).is_err()
+        );
+
+        let params = SwapParams {
+            amount_in: 1_000,
+            minimum_amount_out: 900,
+            expected_amount_out: Some(950),
+            swap_a_to_b: false,
+        };
+        // Bid: quote in, base out
+        let user = UserSwapAccounts {
+            owner: Pubkey::new_unique(),
+            token_in: Pubkey::new_unique(),
+            token_out: Pubkey::new_unique(),
+        };
+        let ix = AldrinSwapBuilder::build_aldrin_swap_ix(
+            &program_id,
+            &pool_address,
+            &pool_signer,
+            &pool,
+            &user,
+            &params,
+        );
+
+        assert_eq!(ix.accounts.len(), 10);
+        assert_eq!(ix.accounts[1].pubkey, pool_signer);
+        assert_eq!(ix.accounts[3].pubkey, pool.base_token_vault);
+        assert_eq!(ix.accounts[4].pubkey, pool.quote_token_vault);
+        assert!(ix.accounts[6].is_signer);
+        assert_eq!(ix.accounts[7].pubkey, user.token_out);
+        assert_eq!(ix.accounts[8].pubkey, user.token_in);
+        assert_eq!(&ix.data[..8], &SWAP_DISCRIMINATOR);
+        assert_eq!(ix.data.len(), 25);
+        // Quote → base is a bid
+        assert_eq!(ix.data[24], SIDE_BID);
+
+        assert!(AldrinPoolState::parse(&data[..400]).is_err());
+    }
+
+    #[test]
+    fn test_trade_and_owner_fees_round_down_separately() {
+        let program_id: Pubkey = AldrinSwapBuilder::PROGRAM_ID.parse().unwrap();
+        let (data, _) = sample_pool_data(&Pubkey::new_unique(), &program_id);
+        let pool = AldrinPoolState::parse(&data).unwrap();
+
+        // 0.25% of 1_000 rounds to 2, 0.05% rounds to 0: 998 reaches the curve,
+        // not the 997 a combined 0.3% fee would leave
+        assert_eq!(pool.amount_after_fees(1_000), 998);
+        assert_eq!(pool.quote(1_000, 500_000, 1_000_000), 1_992);
+        assert_eq!(pool.quote(1_000, 500_000, 0), 0);
+    }
+
+    #[test]
+    #[ignore = "needs fixtures/aldrin_pool_swap.json (scripts/capture_fixture.py --swap)"]
+    fn test_captured_aldrin_pool_and_swap() {
+        let fixture = CapturedFixture::load("aldrin_pool_swap").unwrap();
+        let pool = AldrinPoolState::parse(fixture.pool_data()).unwrap();
+
+        // Stored nonce reproduces the stored signer, and the RPC node agrees the
+        // vaults hold our mints under that signer
+        let pool_signer =
+            AldrinSwapBuilder::derive_pool_signer(&fixture.pool, &pool, &fixture.program).unwrap();
+        for (vault, mint) in [
+            (pool.base_token_vault, pool.base_token_mint),
+            (pool.quote_token_vault, pool.quote_token_mint),
+        ] {
+            let account = fixture.token_account(&vault);
+            assert_eq!(account.mint, mint.to_string());
+            assert_eq!(account.owner, pool_signer.to_string());
+        }
+
+        // Our instruction lists the accounts the real swap executed with
+        let swap = fixture.swap();
+        let executed: Vec<Pubkey> = swap
+            .instruction_accounts
+            .iter()
+            .map(|address| pubkey(address).unwrap())
+            .collect();
+        let swap_a_to_b = pubkey(&swap.vault_in).unwrap() == pool.base_token_vault;
+        let params = SwapParams {
+            amount_in: swap.amount_in,
+            minimum_amount_out: 0,
+            expected_amount_out: None,
+            swap_a_to_b,
+        };
+        let (token_in, token_out) = if swap_a_to_b {
+            (executed[7], executed[8])
+        } else {
+            (executed[8], executed[7])
+        };
+        let user = UserSwapAccounts {
+            owner: executed[6],
+            token_in,
+            token_out,
+        };
+        let ix = AldrinSwapBuilder::build_aldrin_swap_ix(
+            &fixture.program,
+            &fixture.pool,
+            &pool_signer,
+            &pool,
+            &user,
+            &params,
+        );
+        let built: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(built, executed[..built.len()]);
+
+        // Quote on the pre-swap vault balances vs the amount the vault paid out
+        let out = pool.quote(swap.amount_in, swap.reserve_in, swap.reserve_out);
+        assert!(
+            out.abs_diff(swap.amount_out) <= 1,
+            "estimated {} vs {} out on-chain ({})",
+            out,
+            swap.amount_out,
+            swap.signature
+        );
+    }
+}
@@ -0,0 +1,112 @@
+// Constant-product math shared by the classic AMM builders
+//
+// Aldrin, Lifinity, Saros, Fluxbeam and PumpSwap all quote against two vault
+// balances with x*y=k. Each DEX keeps its own fee schedule; the curve, the fee
+// helpers and the vault balance decoding live here so they can't drift apart.
+
+use anyhow::Result;
+use solana_sdk::program_pack::Pack;
+
+/// Offset of the `amount` field inside an SPL token account (same under Token-2022)
+pub const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Read the `amount` field from token account data
+///
+/// # Arguments
+/// * `data` - Account data, None if the account doesn't exist
+///
+/// # Errors
+/// Returns error if the account is missing or shorter than an SPL token account
+pub fn token_account_amount(data: Option<&[u8]>) -> Result<u64> {
+    let data = data.ok_or_else(|| anyhow::anyhow!("Token account not found"))?;
+    if data.len() < spl_token::state::Account::LEN {
+        return Err(anyhow::anyhow!(
+            "Token account too short ({} bytes)",
+            data.len()
+        ));
+    }
+    Ok(u64::from_le_bytes(
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8].try_into()?,
+    ))
+}
+
+/// Constant-product output (x*y=k) for an already fee-adjusted input
+///
+/// # Returns
+/// Output amount, or 0 if either reserve is empty
+pub fn constant_product_output(amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    if reserve_in == 0 || reserve_out == 0 {
+        return 0;
+    }
+
+    (amount_in as u128 * reserve_out as u128 / (reserve_in as u128 + amount_in as u128)) as u64
+}
+
+/// Fee charged on `amount` at `numerator / denominator`, rounded down
+///
+/// SPL token-swap forks (Aldrin, Saros) subtract each fee from the input this
+/// way; a zero denominator means the fee is disabled.
+pub fn fee_amount(amount: u64, numerator: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        return 0;
+    }
+    (amount as u128 * numerator as u128 / denominator as u128) as u64
+}
+
+/// Input left after a `numerator / denominator` fee, rounded down
+///
+/// Lifinity and PumpSwap scale the input by `(denominator - numerator)`; a
+/// zero denominator yields nothing (the pool can't be priced).
+pub fn amount_after_fee(amount_in: u64, numerator: u64, denominator: u64) -> u64 {
+    if denominator == 0 {
+        return 0;
+    }
+    let numerator = numerator.min(denominator) as u128;
+    (amount_in as u128 * (denominator as u128 - numerator) / denominator as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_account_amount() {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        data[64..72].copy_from_slice(&1_234_567u64.to_le_bytes());
+        assert_eq!(token_account_amount(Some(&data)).unwrap(), 1_234_567);
+
+        // Token-2022 accounts carry extensions after the base layout
+        data.extend_from_slice(&[0u8; 12]);
+        assert_eq!(token_account_amount(Some(&data)).unwrap(), 1_234_567);
+
+        assert!(token_account_amount(Some(&data[..100])).is_err());
+        assert!(token_account_amount(None).is_err());
+    }
+
+    #[test]
+    fn test_constant_product_output() {
+        assert_eq!(constant_product_output(9_970, 1_000_000, 1_000_000), 9_871);
+        // Skewed pool: 1 unit in buys ~2 out, minus price impact
+        assert_eq!(constant_product_output(1_000, 1_000_000, 2_000_000), 1_998);
+        assert_eq!(constant_product_output(9_970, 0, 1_000_000), 0);
+        assert_eq!(constant_product_output(9_970, 1_000_000, 0), 0);
+        // Reserves near u64::MAX don't overflow
+        assert_eq!(
+            constant_product_output(u64::MAX / 2, u64::MAX / 2, u64::MAX),
+            u64::MAX / 2
+        );
+    }
+
+    #[test]
+    fn test_fee_helpers_round_down() {
+        assert_eq!(fee_amount(10_000, 25, 10_000), 25);
+        assert_eq!(fee_amount(1_000, 25, 10_000), 2);
+        assert_eq!(fee_amount(1_000, 25, 0), 0);
+
+        assert_eq!(amount_after_fee(1_000, 20, 10_000), 998);
+        assert_eq!(amount_after_fee(999, 25, 10_000), 996);
+        assert_eq!(amount_after_fee(1_000, 20, 0), 0);
+        // A fee above 100% takes everything, never underflows
+        assert_eq!(amount_after_fee(1_000, 20_000, 10_000), 0);
+    }
+}
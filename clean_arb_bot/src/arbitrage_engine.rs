@@ -1,6 +1,11 @@
 use anyhow::{Context, Result};
-use solana_sdk::signature::{Keypair, Signer};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, Mutex};
@@ -10,27 +15,53 @@ use tracing::{debug, error, info, warn}; // CYCLE-5: Added error macro
 use crate::config::Config;
 use crate::cost_calculator::ArbitrageCosts;
 use crate::dex_registry::DexRegistry;
+use crate::errors::{ArbError, ArbResult};
 use crate::jito_bundle_client::JitoBundleClient;
-use crate::jito_submitter::JitoSubmitter;
+use crate::jito_submitter::{BlockhashResigner, BundleOutcome, BundleSink, JitoSubmitter};
 use crate::jupiter_prices::JupiterPriceClient;
+use crate::jupiter_swap::{JupiterSwapClient, JUPITER_SLIPPAGE_BPS, UNWIND_SLIPPAGE_BPS};
 use crate::jupiter_triangle::JupiterTriangleDetector;
-use crate::meteora_swap; // CYCLE-7: Meteora swap instruction building
+use crate::metrics::{LatencyBreakdown, MetricsCollector};
+use crate::opportunity_journal::{JournalEntry, OpportunityJournal};
+use crate::paper_model;
 use crate::position_tracker::PositionTracker;
-use crate::shredstream_client::{ShredStreamClient, TokenPrice};
-use crate::simple_triangle_detector::SimpleTriangleDetector;
-use crate::triangle_arbitrage::TriangleArbitrage;
+use crate::profit_currency::{self, sol_to_usd, ProfitCurrency, SolUsdPrice};
+use crate::profit_divergence::ProfitDivergenceTracker;
+use crate::real_price_feed::{self, RpcPriceFeed};
+use crate::rpc_client::{LegOutputProbe, RpcRetryPolicy};
+use crate::secure_wallet::SecureWallet;
+use crate::shredstream_client::{ShredStreamClient, ShredStreamHealth, TokenPrice};
+use crate::simple_triangle_detector::{SimpleTriangleDetector, SimpleTriangleOpportunity};
+use crate::slippage;
+use crate::spread_analyzer::SpreadAnalyzer;
+use crate::status::StatusBoard;
+use crate::structured_log;
+use crate::swap_executor::{JitoTip, MAX_SPLIT_BUNDLE_LEGS};
+use crate::tip_strategy::TipStrategy;
+use crate::token_list::TokenListWatcher;
+use crate::token_metadata::TokenMetadataRegistry;
+use crate::token_safety::TokenSafetyChecker;
+use crate::trade_log::{TradeLogger, TradeOutcome, TradeRecord};
+use crate::triangle_arbitrage::{TriangleArbitrage, TriangleOpportunity};
 use crate::{extract_pool_id, DexType, PoolRegistry, SolanaRpcClient, SwapExecutor, SwapParams};
 
 // Constants for arbitrage detection and execution
-const STALE_OPPORTUNITY_THRESHOLD_MS: u64 = 100; // Max age before considering stale
-const SHREDSTREAM_TIMEOUT_MS: u64 = 500; // Timeout for ShredStream price fetch
-const SCAN_INTERVAL_MS: u64 = 1500; // Scan interval (synced with JITO rate limit)
 const STATS_REPORT_INTERVAL_SECS: u64 = 60; // Report stats every 60 seconds
 const BALANCE_UPDATE_OPPORTUNITIES: u64 = 50; // Update balance every 50 opportunities
 const BALANCE_UPDATE_INTERVAL_SECS: u64 = 600; // Or every 10 minutes
-const MAX_REALISTIC_SPREAD_PCT: f64 = 50.0; // Max spread for volatile memecoins
-const LOG_SPREAD_THRESHOLD_PCT: f64 = 0.3; // Log spreads above this threshold
-const MIN_VOLUME_SOL: f64 = 10.0; // Minimum 24h volume to avoid illiquid tokens (increased from 0.01)
+const STATS_FILE_PATH: &str = "arbitrage_stats.json"; // Persisted stats (survive restarts)
+const EMERGENCY_STOP_FILE: &str = ".emergency_stop"; // Presence halts trading immediately
+const SUBMISSION_DEDUP_TTL_MS: u64 = 1_600; // ~4 slots: suppress re-submitting the same pair
+const SHREDSTREAM_BACKOFF_BASE_MS: u64 = 500; // First ShredStream retry delay
+const SHREDSTREAM_BACKOFF_CAP_MS: u64 = 30_000; // Longest ShredStream retry delay
+const SPREAD_ANALYSIS_INTERVAL_SECS: u64 = 300; // Log per-token min-spread advice every 5 min
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112"; // Wrapped SOL
+const SHUTDOWN_DRAIN_TIMEOUT_SECS: u64 = 30; // DRAIN_ON_SHUTDOWN: queue depth × (rate limit + landing wait)
+const JUPITER_LEG_CONFIRM_TIMEOUT: Duration = Duration::from_secs(30); // Unconfirmed after this = not landed
+const JUPITER_LEG_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const PROFIT_READ_DEADLINE: Duration = Duration::from_secs(120); // Landed but unreadable this long = book worst case
+const PROFIT_READ_RETRY_INTERVAL: Duration = Duration::from_secs(2); // getTransaction lags `confirmed` landing
+const JUPITER_LEG_FEE_LAMPORTS: u64 = 5_000; // Signature fee per Jupiter leg (no priority fee requested)
 
 /// Arbitrage opportunity
 #[derive(Debug, Clone)]
@@ -42,18 +73,100 @@ pub struct ArbitrageOpportunity {
     pub sell_price: f64,
     pub spread_percentage: f64,
     pub estimated_profit_sol: f64,
+    pub gross_profit_sol: f64, // Before JITO tip, gas and DEX fees
+    pub required_spread_percentage: f64, // Spread needed to cover all costs at this position
+    pub position_size_lamports: u64, // Capped to pool depth (MAX_PRICE_IMPACT_PCT)
 
     // GHOST POOL FIX: Full 44-char pool addresses from ShredStream
     pub buy_pool_address: String,  // Full address for buy pool
     pub sell_pool_address: String, // Full address for sell pool
 
     // NEW (2025-10-11): Timestamp for staleness detection
-    pub detected_at: Instant, // When opportunity was detected
+    pub detected_at: Instant,       // When opportunity was detected
+    pub observed_slot: Option<u64>, // Older quote's slot (None if either leg lacks one)
+}
+
+impl ArbitrageOpportunity {
+    /// The same trade as a two-leg triangle (SOL → token → SOL)
+    ///
+    /// Real trades go through the triangle bundle path, which builds any
+    /// supported DEX pair into one atomic transaction. The DEX strings carry
+    /// the pool short IDs, and the depth-capped position carries over as a cap.
+    fn as_two_leg_triangle(&self) -> TriangleOpportunity {
+        TriangleOpportunity {
+            path: vec![
+                "SOL".to_string(),
+                self.token_mint.clone(),
+                "SOL".to_string(),
+            ],
+            dexs: vec![self.buy_dex.clone(), self.sell_dex.clone()],
+            prices: vec![self.buy_price, self.sell_price],
+            estimated_profit_sol: self.gross_profit_sol, // Costs are re-validated at execution
+            profit_percentage: self.spread_percentage,
+            position_cap_lamports: Some(self.position_size_lamports),
+        }
+    }
+}
+
+/// A scan's opportunity from either detector, ranked together by net profit
+#[derive(Debug, Clone)]
+enum RankedOpportunity {
+    CrossDex(ArbitrageOpportunity),
+    Triangle(TriangleOpportunity),
+}
+
+impl RankedOpportunity {
+    /// Expected profit after costs (SOL)
+    fn net_profit_sol(&self) -> f64 {
+        match self {
+            RankedOpportunity::CrossDex(opportunity) => opportunity.estimated_profit_sol,
+            RankedOpportunity::Triangle(triangle) => triangle.estimated_profit_sol,
+        }
+    }
+}
+
+/// Merge cross-DEX and triangle opportunities, most profitable first
+fn rank_by_net_profit(
+    cross_dex: Vec<ArbitrageOpportunity>,
+    triangles: Vec<TriangleOpportunity>,
+) -> Vec<RankedOpportunity> {
+    let mut ranked: Vec<RankedOpportunity> = cross_dex
+        .into_iter()
+        .map(RankedOpportunity::CrossDex)
+        .chain(triangles.into_iter().map(RankedOpportunity::Triangle))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.net_profit_sol()
+            .partial_cmp(&a.net_profit_sol())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    ranked
+}
+
+/// Per-DEX execution outcomes (detects swap builders that always revert)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DexStats {
+    pub attempts: u64,
+    pub successes: u64,
+    pub failures: u64,
+}
+
+impl DexStats {
+    pub fn success_rate(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            (self.successes as f64 / self.attempts as f64) * 100.0
+        }
+    }
 }
 
 /// Arbitrage statistics
-#[derive(Debug, Default)]
+///
+/// Persisted to disk so daily safety limits survive restarts
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ArbitrageStats {
+    #[serde(skip)] // Per-process runtime, recomputed from start_time
     pub runtime_seconds: u64,
     pub opportunities_detected: u64,
     pub opportunities_executed: u64,
@@ -61,10 +174,227 @@ pub struct ArbitrageStats {
     pub total_profit_sol: f64,
     pub daily_trades: u64,
     pub daily_loss_sol: f64,
+    /// Net realized profit since `stats_date` began (DAILY_PROFIT_TARGET_SOL per day)
+    #[serde(default)]
+    pub daily_profit_sol: f64,
     pub consecutive_failures: u64,
+    /// JITO bundles queued but never confirmed landed (outbid, dropped, failed)
+    #[serde(default)]
+    pub opportunities_submitted_not_landed: u64,
+    /// SHADOW MODE: real transactions simulated (never submitted)
+    #[serde(default)]
+    pub shadow_simulations: u64,
+    /// SHADOW MODE: simulations that would have succeeded on-chain
+    #[serde(default)]
+    pub shadow_simulation_successes: u64,
+    /// Times the ShredStream price feed went down
+    #[serde(default)]
+    pub shredstream_outages: u64,
+    /// Partial fills flattened back to SOL after a later leg failed
+    #[serde(default)]
+    pub unwound_positions: u64,
+    /// JITO tips paid by bundles submitted since `stats_date` began (MAX_DAILY_TIP_BUDGET_SOL)
+    #[serde(default)]
+    pub daily_tips_spent_sol: f64,
+    /// Realized loss from those unwinds (also counted in daily_loss_sol)
+    #[serde(default)]
+    pub unwind_loss_sol: f64,
+    /// Unwinds that failed - the token is still held and needs a manual exit
+    #[serde(default)]
+    pub unwind_failures: u64,
+    /// Times execution paused after MAX_CONSECUTIVE_FAILURES
+    #[serde(default)]
+    pub failure_cooldowns: u64,
+    /// Seconds left in the current failure cooldown, 0 when trading (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub failure_cooldown_remaining_secs: u64,
+    /// Current adaptive sleep between scans (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub scan_interval_ms: u64,
+    /// Current ShredStream retry backoff, 0 while healthy (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub shredstream_backoff_ms: u64,
+    /// Protected SOL: base fee reserve + in-flight tips (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub fee_reserve_sol: f64,
+    /// JITO tips of queued, unresolved bundles (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub in_flight_tips_sol: f64,
+    /// Last on-chain wallet balance, None until fetched (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub wallet_balance_sol: Option<f64>,
+    /// RPC calls delayed by RPC_MAX_RPS (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub rpc_rate_limited_calls: u64,
+    /// RPC calls failed with the RPC_MAX_RPS bucket exhausted (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub rpc_rate_limit_rejections: u64,
+    /// Total time RPC calls waited on RPC_MAX_RPS (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub rpc_rate_limit_wait_ms: u64,
+    /// ShredStream endpoint prices are fetched from (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub shredstream_endpoint: Option<String>,
+    /// Block engine endpoint JITO bundles currently go to (runtime only, not restored)
+    #[serde(default, skip_deserializing)]
+    pub jito_endpoint: Option<String>,
+    /// Execution outcomes keyed by DEX name (e.g. "Orca_Whirlpools")
+    #[serde(default)]
+    pub dex_stats: HashMap<String, DexStats>,
+    /// UTC calendar date (YYYY-MM-DD) that daily_trades/daily_loss_sol belong to
+    #[serde(default)]
+    pub stats_date: String,
 }
 
 impl ArbitrageStats {
+    /// Record realized profit (negative = loss), tracking daily loss separately
+    pub fn record_profit(&mut self, profit_sol: f64) {
+        self.total_profit_sol += profit_sol;
+        self.daily_profit_sol += profit_sol;
+        if profit_sol < 0.0 {
+            self.daily_loss_sol += -profit_sol;
+        }
+    }
+
+    /// Record a partial fill sold back to SOL at `loss_sol` (counts against daily loss)
+    pub fn record_unwind(&mut self, loss_sol: f64) {
+        self.unwound_positions += 1;
+        self.unwind_loss_sol += loss_sol;
+        self.record_profit(-loss_sol);
+    }
+
+    /// Record the JITO tip of a submitted bundle against the daily tip budget
+    ///
+    /// Counted at submission: a bundle that doesn't land costs nothing on-chain,
+    /// but budgeting what was offered keeps a bidding war from outrunning the cap.
+    pub fn record_tip(&mut self, tip_lamports: u64) {
+        self.daily_tips_spent_sol += tip_lamports as f64 / 1_000_000_000.0;
+    }
+
+    /// Whether today's JITO tips have used up `max_daily_tip_budget_sol` (never true if unset)
+    pub fn tip_budget_exhausted(&self, max_daily_tip_budget_sol: Option<f64>) -> bool {
+        max_daily_tip_budget_sol.is_some_and(|budget| self.daily_tips_spent_sol >= budget)
+    }
+
+    /// Record the latest on-chain wallet balance (startup and periodic balance updates)
+    pub fn record_wallet_balance(&mut self, balance_lamports: u64) {
+        self.wallet_balance_sol = Some(balance_lamports as f64 / 1_000_000_000.0);
+    }
+
+    /// Whether the last seen wallet balance is below `min_wallet_balance_sol`
+    ///
+    /// Same rule as the production wallet manager (`balance < min`); never true
+    /// before a balance has been fetched or when no minimum is configured.
+    pub fn wallet_balance_below(&self, min_wallet_balance_sol: Option<f64>) -> bool {
+        matches!(
+            (self.wallet_balance_sol, min_wallet_balance_sol),
+            (Some(balance), Some(min)) if balance < min
+        )
+    }
+
+    /// Record the outcome of an execution attempt for every DEX leg involved
+    ///
+    /// # Arguments
+    /// * `dexs` - DEX strings for each leg (pool ID suffixes are stripped)
+    /// * `success` - Whether the execution (or simulation) succeeded
+    pub fn record_dex_result<S: AsRef<str>>(&mut self, dexs: &[S], success: bool) {
+        for dex in dexs {
+            let entry = self
+                .dex_stats
+                .entry(dex_stats_key(dex.as_ref()).to_string())
+                .or_default();
+            entry.attempts += 1;
+            if success {
+                entry.successes += 1;
+            } else {
+                entry.failures += 1;
+            }
+        }
+    }
+
+    /// Reset daily counters if `today` differs from the stats date
+    ///
+    /// # Arguments
+    /// * `today` - Current UTC date as YYYY-MM-DD
+    ///
+    /// # Returns
+    /// `true` if a new day started and daily counters were reset
+    ///
+    /// # Note
+    /// Only daily_trades, daily_loss_sol, daily_profit_sol and daily_tips_spent_sol reset -
+    /// total_profit_sol carries forward
+    pub fn roll_over_to(&mut self, today: &str) -> bool {
+        if self.stats_date == today {
+            return false;
+        }
+
+        if !self.stats_date.is_empty() {
+            info!(
+                "📅 New trading day ({} → {}) - resetting daily limits",
+                self.stats_date, today
+            );
+        }
+        self.daily_trades = 0;
+        self.daily_loss_sol = 0.0;
+        self.daily_profit_sol = 0.0;
+        self.daily_tips_spent_sol = 0.0;
+        self.stats_date = today.to_string();
+        true
+    }
+
+    /// Save stats as JSON (write to temp file then rename, so a crash never truncates)
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize stats")?;
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Load stats previously written by `save_to_file`
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse stats file {}", path.display()))
+    }
+
+    /// Load persisted stats (if any) and roll daily counters over to `today`
+    ///
+    /// A missing file starts fresh (first run).
+    ///
+    /// # Errors
+    /// Fails closed if the file exists but can't be read or parsed: starting from
+    /// zero would silently reset today's trade and loss limits
+    pub fn load_or_default(path: impl AsRef<Path>, today: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let mut stats = if path.exists() {
+            let stats = Self::load_from_file(path).with_context(|| {
+                format!(
+                    "Refusing to start: daily limits can't be restored from {} - fix or remove it",
+                    path.display()
+                )
+            })?;
+            info!(
+                "📂 Restored stats from {} (date: {}, daily trades: {}, daily loss: {:.6} SOL)",
+                path.display(),
+                stats.stats_date,
+                stats.daily_trades,
+                stats.daily_loss_sol
+            );
+            stats
+        } else {
+            Self::default()
+        };
+
+        stats.roll_over_to(today);
+        Ok(stats)
+    }
+
     pub fn success_rate(&self) -> f64 {
         if self.opportunities_detected == 0 {
             0.0
@@ -74,6 +404,704 @@ impl ArbitrageStats {
     }
 }
 
+/// Recently submitted opportunities keyed by `(token_mint, buy_pool, sell_pool)`
+///
+/// Entries expire after a few slots, or earlier once the submitter reports the
+/// bundle resolved (landed, failed or dropped), so a genuinely new opportunity on
+/// the same pair isn't suppressed.
+#[derive(Debug)]
+struct RecentSubmissions {
+    entries: HashMap<String, Instant>,
+    ttl: Duration,
+}
+
+impl RecentSubmissions {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl,
+        }
+    }
+
+    /// Dedup key for a token traded between a buy and a sell pool
+    fn key(token_mint: &str, buy_pool: &str, sell_pool: &str) -> String {
+        format!("{}:{}:{}", token_mint, buy_pool, sell_pool)
+    }
+
+    /// Check whether `key` was submitted within the TTL (prunes expired entries)
+    fn contains(&mut self, key: &str) -> bool {
+        let ttl = self.ttl;
+        self.entries
+            .retain(|_, submitted_at| submitted_at.elapsed() < ttl);
+        self.entries.contains_key(key)
+    }
+
+    fn insert(&mut self, key: String) {
+        self.entries.insert(key, Instant::now());
+    }
+
+    /// Drop keys whose bundles already resolved
+    fn release<'a>(&mut self, keys: impl IntoIterator<Item = &'a str>) {
+        for key in keys {
+            self.entries.remove(key);
+        }
+    }
+}
+
+/// Longest remaining per-token cooldown among `mints` (PER_TOKEN_COOLDOWN_MS)
+///
+/// Wrapped SOL is the base of every route, so it never cools down.
+///
+/// # Returns
+/// The mint with the most cooldown left and how long remains, or None if
+/// every mint is free to trade
+fn token_cooldown_remaining<'a>(
+    last_traded: &HashMap<String, Instant>,
+    mints: &[&'a str],
+    cooldown: Duration,
+    now: Instant,
+) -> Option<(&'a str, Duration)> {
+    mints
+        .iter()
+        .filter(|mint| **mint != SOL_MINT)
+        .filter_map(|mint| {
+            let traded_at = last_traded.get(*mint)?;
+            let remaining = cooldown.checked_sub(now.saturating_duration_since(*traded_at))?;
+            (!remaining.is_zero()).then_some((*mint, remaining))
+        })
+        .max_by_key(|(_, remaining)| *remaining)
+}
+
+/// Pending bundle that shares a token or pool with a new route (SELF_TRADE_GUARD)
+///
+/// A spread touching one of our unresolved bundles may only exist because of
+/// that bundle, or vanish once it lands - trading it competes with ourselves.
+/// Bundles leave `pending` when they land or fail, so unlike submission dedup
+/// nothing here expires on a timer. Wrapped SOL is every route's base and
+/// never counts as overlap.
+///
+/// # Arguments
+/// * `mints` - Intermediate mints of the new route
+/// * `dexs` - DEX strings of the new route (pool ID suffix, e.g. `Orca_Whirlpool_abc`)
+///
+/// # Returns
+/// Dedup key of the first overlapping pending bundle, or None
+fn in_flight_overlap<'a>(
+    pending: &'a HashMap<String, PendingBundle>,
+    mints: &[&str],
+    dexs: &[&str],
+) -> Option<&'a str> {
+    let pools: Vec<String> = dexs
+        .iter()
+        .filter_map(|dex| extract_pool_id(dex).ok())
+        .collect();
+
+    pending.iter().find_map(|(key, bundle)| {
+        let shares_mint = bundle
+            .trade
+            .token_mint
+            .split('/')
+            .any(|mint| mint != SOL_MINT && mints.contains(&mint));
+        let shares_pool = bundle
+            .dexs
+            .iter()
+            .filter_map(|dex| extract_pool_id(dex).ok())
+            .any(|pool| pools.contains(&pool));
+        (shares_mint || shares_pool).then_some(key.as_str())
+    })
+}
+
+/// Main loop sleep that adapts to opportunity frequency (AIMD)
+///
+/// Halves after a scan that found opportunities (down to the floor) and grows by
+/// a fixed step after an empty one (up to the ceiling): bursts get scanned fast,
+/// dead periods stop burning RPC calls.
+#[derive(Debug, Clone)]
+struct AdaptiveScanInterval {
+    current_ms: u64,
+    floor_ms: u64,
+    ceiling_ms: u64,
+    step_ms: u64,
+}
+
+impl AdaptiveScanInterval {
+    /// Start at `initial_ms`, clamped into `[floor_ms, ceiling_ms]`
+    fn new(initial_ms: u64, floor_ms: u64, ceiling_ms: u64, step_ms: u64) -> Self {
+        Self {
+            current_ms: initial_ms.clamp(floor_ms, ceiling_ms),
+            floor_ms,
+            ceiling_ms,
+            step_ms,
+        }
+    }
+
+    /// Adjust after a scan and return the next sleep
+    ///
+    /// # Arguments
+    /// * `found_opportunities` - Whether the scan produced executable opportunities
+    fn on_scan(&mut self, found_opportunities: bool) -> Duration {
+        self.current_ms = if found_opportunities {
+            (self.current_ms / 2).max(self.floor_ms)
+        } else {
+            self.current_ms
+                .saturating_add(self.step_ms)
+                .min(self.ceiling_ms)
+        };
+        Duration::from_millis(self.current_ms)
+    }
+}
+
+/// Add up to `max_jitter_ms` of random delay to a scan sleep (SCAN_JITTER_MS)
+///
+/// A fixed cadence makes our submission timing predictable to other searchers.
+/// Jitter is only ever added, so the sleep never drops below the adaptive
+/// interval - and with it the JITO rate-limit floor.
+///
+/// # Arguments
+/// * `interval` - Sleep chosen by the adaptive scan interval
+/// * `max_jitter_ms` - Largest delay to add (0 = no jitter)
+/// * `jitter` - Uniform random value in [0, 1)
+fn jittered_scan_delay(interval: Duration, max_jitter_ms: u64, jitter: f64) -> Duration {
+    interval + Duration::from_millis((jitter.clamp(0.0, 1.0) * max_jitter_ms as f64) as u64)
+}
+
+/// ShredStream retry delay: exponential backoff with jitter
+///
+/// Doubles per consecutive failure from `base_ms` up to `cap_ms`. Each delay is
+/// drawn from the upper half of the current step ("equal jitter") so several
+/// bot instances don't retry a recovering service in lockstep.
+#[derive(Debug, Clone)]
+struct ReconnectBackoff {
+    base_ms: u64,
+    cap_ms: u64,
+    attempt: u32,
+}
+
+impl ReconnectBackoff {
+    fn new(base_ms: u64, cap_ms: u64) -> Self {
+        Self {
+            base_ms,
+            cap_ms,
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next retry (advances the backoff)
+    ///
+    /// # Arguments
+    /// * `jitter` - Uniform random value in [0, 1)
+    fn next_delay(&mut self, jitter: f64) -> Duration {
+        let step_ms = self
+            .base_ms
+            .saturating_mul(1u64 << self.attempt.min(32))
+            .min(self.cap_ms);
+        self.attempt = self.attempt.saturating_add(1);
+        let half = step_ms / 2;
+        Duration::from_millis(half + (jitter.clamp(0.0, 1.0) * (step_ms - half) as f64) as u64)
+    }
+
+    /// Back to `base_ms` after a successful fetch
+    fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// What the failure cooldown did on this scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CooldownStatus {
+    /// Below the failure threshold - execute normally
+    Ready,
+    /// Threshold just hit - cooldown started
+    Entered,
+    /// Still cooling down - scan but don't execute
+    CoolingDown(Duration),
+    /// Window elapsed - resume and reset the failure streak
+    Resumed,
+}
+
+/// Temporary execution pause after MAX_CONSECUTIVE_FAILURES
+///
+/// A run of failures usually means a bad market moment (congestion, stale pools),
+/// not a broken bot. Instead of exiting, execution pauses for FAILURE_COOLDOWN_SECS
+/// while scanning continues, then resumes with a fresh failure streak.
+#[derive(Debug, Clone)]
+struct FailureCooldown {
+    duration: Duration,
+    until: Option<Instant>,
+}
+
+impl FailureCooldown {
+    fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            until: None,
+        }
+    }
+
+    /// Advance the cooldown for the current failure streak
+    ///
+    /// # Arguments
+    /// * `consecutive_failures` - Current failure streak
+    /// * `threshold` - MAX_CONSECUTIVE_FAILURES
+    /// * `now` - Current time
+    fn poll(&mut self, consecutive_failures: u64, threshold: u64, now: Instant) -> CooldownStatus {
+        match self.until {
+            Some(until) if now < until => CooldownStatus::CoolingDown(until - now),
+            Some(_) => {
+                self.until = None;
+                CooldownStatus::Resumed
+            }
+            None if consecutive_failures >= threshold => {
+                self.until = Some(now + self.duration);
+                CooldownStatus::Entered
+            }
+            None => CooldownStatus::Ready,
+        }
+    }
+}
+
+/// Why an opportunity was dropped before submission
+///
+/// Counted per scan/execution so `report_stats` can show where candidates die
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RejectionReason {
+    TooFewPools,
+    TooManyPools,
+    LowVolume,
+    SameDexFamily,
+    UnrealisticSpread,
+    SpreadTooLow,
+    Stale,
+    UnprofitableAfterFees,
+    CircuitBreaker,
+    InvalidOpportunity,
+    BlacklistedPool,
+    PoolResolutionFailed,
+    PoolValidationFailed,
+    GhostPool,
+    PoolNotActive,
+    NegativeProfit,
+    Duplicate,
+    EmergencyStop,
+    UnsafeMint,
+    NetProfitBelowFloor,
+    ShallowLiquidity,
+    StalePrice,
+    SimulatedUnprofitable,
+    FeeReserve,
+    TokenCooldown,
+    ReserveDrift,
+    TipBudgetExhausted,
+    SelfTrade,
+}
+
+impl std::fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            RejectionReason::TooFewPools => "too_few_pools",
+            RejectionReason::TooManyPools => "too_many_pools",
+            RejectionReason::LowVolume => "low_volume",
+            RejectionReason::SameDexFamily => "same_dex_family",
+            RejectionReason::UnrealisticSpread => "unrealistic_spread",
+            RejectionReason::SpreadTooLow => "spread_too_low",
+            RejectionReason::Stale => "stale",
+            RejectionReason::UnprofitableAfterFees => "unprofitable_after_fees",
+            RejectionReason::CircuitBreaker => "circuit_breaker",
+            RejectionReason::InvalidOpportunity => "invalid_opportunity",
+            RejectionReason::BlacklistedPool => "blacklisted_pool",
+            RejectionReason::PoolResolutionFailed => "pool_resolution_failed",
+            RejectionReason::PoolValidationFailed => "pool_validation_failed",
+            RejectionReason::GhostPool => "ghost_pool",
+            RejectionReason::PoolNotActive => "pool_not_active",
+            RejectionReason::NegativeProfit => "negative_profit",
+            RejectionReason::Duplicate => "duplicate",
+            RejectionReason::EmergencyStop => "emergency_stop",
+            RejectionReason::UnsafeMint => "unsafe_mint",
+            RejectionReason::NetProfitBelowFloor => "net_profit_below_floor",
+            RejectionReason::ShallowLiquidity => "shallow_liquidity",
+            RejectionReason::StalePrice => "stale_price",
+            RejectionReason::SimulatedUnprofitable => "simulated_unprofitable",
+            RejectionReason::FeeReserve => "fee_reserve",
+            RejectionReason::TokenCooldown => "token_cooldown",
+            RejectionReason::ReserveDrift => "reserve_drift",
+            RejectionReason::TipBudgetExhausted => "tip_budget_exhausted",
+            RejectionReason::SelfTrade => "self_trade",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Rejection histogram (interior mutability: scanning only borrows the engine)
+#[derive(Debug, Default)]
+struct RejectionCounters {
+    counts: std::sync::Mutex<HashMap<RejectionReason, u64>>,
+}
+
+impl RejectionCounters {
+    fn record(&self, reason: RejectionReason) {
+        let mut counts = match self.counts.lock() {
+            Ok(counts) => counts,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *counts.entry(reason).or_default() += 1;
+    }
+
+    /// Counts sorted by frequency (most common rejection first)
+    fn histogram(&self) -> Vec<(RejectionReason, u64)> {
+        let counts = match self.counts.lock() {
+            Ok(counts) => counts,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut histogram: Vec<_> = counts.iter().map(|(r, c)| (*r, *c)).collect();
+        histogram.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        histogram
+    }
+}
+
+/// Bundle queued for JITO, awaiting a landing outcome from the submitter
+#[derive(Debug)]
+struct PendingBundle {
+    dexs: Vec<String>,
+    expected_profit_sol: f64,
+    /// Net profit after all costs the cost calculator predicted
+    estimated_net_profit_sol: f64,
+    /// Trade log row to complete once the outcome is known
+    trade: TradeRecord,
+    /// JITO tip held in the fee reserve until the bundle resolves
+    tip_lamports: u64,
+    /// Position capital the caller reserved, held until the bundle resolves
+    position_size_lamports: u64,
+    /// Position input plus tip and fees, booked if the landed result stays unreadable
+    worst_case_loss_sol: f64,
+}
+
+impl PendingBundle {
+    /// # Arguments
+    /// * `reserved_lamports` - Capital reservation the bundle takes over from the caller
+    /// * `position_size_lamports` - Position actually traded (worst-case loss)
+    fn new(
+        opportunity: &crate::triangle_arbitrage::TriangleOpportunity,
+        trade: TradeRecord,
+        costs: &ArbitrageCosts,
+        reserved_lamports: u64,
+        position_size_lamports: u64,
+        estimated_net_profit_sol: f64,
+    ) -> Self {
+        Self {
+            dexs: opportunity.dexs.clone(),
+            expected_profit_sol: opportunity.estimated_profit_sol,
+            estimated_net_profit_sol,
+            trade,
+            tip_lamports: costs.jito_tip_lamports,
+            position_size_lamports: reserved_lamports,
+            worst_case_loss_sol: costs.worst_case_loss_lamports(position_size_lamports) as f64
+                / 1e9,
+        }
+    }
+
+    /// Return the tip and position capital to the tracker once the bundle resolved
+    ///
+    /// Landed: the tip is paid and the position is back in the wallet. Dropped:
+    /// neither was ever spent.
+    fn release(&self, position_tracker: &PositionTracker) {
+        position_tracker.release_tip(self.tip_lamports);
+        position_tracker.release_capital(self.position_size_lamports);
+    }
+}
+
+/// How a live triangle attempt that didn't fail ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TriangleExecution {
+    /// Confirmed, simulated, skipped, or nothing to execute: the attempt is over
+    Completed,
+    /// JITO bundle queued: its `PendingBundle` now holds the capital reservation
+    Queued,
+}
+
+/// Landed trade whose wallet balance change couldn't be read yet
+///
+/// `getTransaction` can lag a bundle landing at `confirmed`. The trade stays
+/// here, re-read every `PROFIT_READ_RETRY_INTERVAL`, until its realized profit
+/// is known - or `PROFIT_READ_DEADLINE` passes and the worst case is booked, so
+/// an unreadable losing trade still counts against the daily loss limit.
+#[derive(Debug)]
+struct UnsettledTrade {
+    /// Bundle ID or signature, for logs
+    label: String,
+    signatures: Vec<String>,
+    /// Trade log row to complete once settled
+    trade: TradeRecord,
+    /// Position input plus tip and fees - booked as the loss at the deadline
+    worst_case_loss_sol: f64,
+    /// DEX route and predicted net profit, for realized-vs-estimated tracking
+    route: Option<(Vec<String>, f64)>,
+    /// Jupiter round trip with a non-SOL anchor: realized profit reads the anchor token
+    jupiter: Option<SimpleTriangleOpportunity>,
+    landed_at: Instant,
+    last_read_at: Instant,
+}
+
+impl UnsettledTrade {
+    /// Landed trade, read on the next settlement pass
+    fn landed(
+        label: String,
+        signatures: Vec<String>,
+        trade: TradeRecord,
+        worst_case_loss_sol: f64,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            label,
+            signatures,
+            trade,
+            worst_case_loss_sol,
+            route: None,
+            jupiter: None,
+            landed_at: now,
+            last_read_at: now - PROFIT_READ_RETRY_INTERVAL,
+        }
+    }
+}
+
+/// How an unsettled trade was finally booked
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Settlement {
+    /// Read from the wallet balance change
+    Realized(f64),
+    /// Balance change still unreadable at the deadline - whole position, tip and fees lost
+    WorstCase(f64),
+}
+
+impl Settlement {
+    fn profit_sol(self) -> f64 {
+        match self {
+            Settlement::Realized(profit) => profit,
+            Settlement::WorstCase(loss) => -loss,
+        }
+    }
+}
+
+/// Settle landed trades whose realized profit can now be read (or whose deadline passed)
+///
+/// Trades read less than `PROFIT_READ_RETRY_INTERVAL` ago are left alone.
+///
+/// # Arguments
+/// * `unsettled` - Trades to settle; those still waiting stay in it
+/// * `now` - Current time (deadline and retry spacing)
+/// * `read` - Realized profit of a trade, None while its balance change is unavailable
+///
+/// # Returns
+/// Trades settled this pass with how they were booked
+fn settle_landed_trades(
+    unsettled: &mut Vec<UnsettledTrade>,
+    now: Instant,
+    mut read: impl FnMut(&UnsettledTrade) -> Option<f64>,
+) -> Vec<(UnsettledTrade, Settlement)> {
+    let mut settled = Vec::new();
+    for mut trade in std::mem::take(unsettled) {
+        if now.duration_since(trade.last_read_at) < PROFIT_READ_RETRY_INTERVAL {
+            unsettled.push(trade);
+            continue;
+        }
+        trade.last_read_at = now;
+        match read(&trade) {
+            Some(realized) => settled.push((trade, Settlement::Realized(realized))),
+            None if now.duration_since(trade.landed_at) >= PROFIT_READ_DEADLINE => {
+                let loss = trade.worst_case_loss_sol;
+                settled.push((trade, Settlement::WorstCase(loss)));
+            }
+            None => unsettled.push(trade),
+        }
+    }
+    settled
+}
+
+/// Realized profit of landed transactions from the wallet lamport change in each
+///
+/// Includes tip, priority and base fees, so it's the true net result.
+fn realized_profit_sol(lamport_changes: &[i64]) -> f64 {
+    lamport_changes.iter().sum::<i64>() as f64 / 1_000_000_000.0
+}
+
+/// Engine state a paper two-leg execution needs, detached from the engine so
+/// several executions can run on worker tasks at once
+///
+/// Real trades don't come through here - they are built into a JITO bundle by
+/// the triangle execution path (see `ArbitrageOpportunity::as_two_leg_triangle`).
+#[derive(Clone)]
+struct TwoLegExecutor {
+    paper_trading: bool,
+    paper_seed: u64,
+    trade_log: Option<Arc<TradeLogger>>,
+}
+
+/// Stats changes from one two-leg execution, applied by the engine once it finishes
+#[derive(Debug, Default)]
+struct TwoLegOutcome {
+    dex_results: Vec<(String, bool)>,
+    /// The trade completed (counted as executed and against the daily limit)
+    executed: bool,
+    leg_failed: bool,
+    profit_sol: Option<f64>,
+    /// Signatures of the legs that landed
+    signatures: Vec<String>,
+}
+
+impl TwoLegOutcome {
+    /// Apply to engine stats (per-DEX results, executed trade, profit or failure)
+    fn apply(&self, stats: &mut ArbitrageStats) {
+        for (dex, success) in &self.dex_results {
+            stats.record_dex_result(&[dex], *success);
+        }
+        if self.executed {
+            stats.opportunities_executed += 1;
+            stats.daily_trades += 1;
+        }
+        if self.leg_failed {
+            stats.failed_executions += 1;
+            stats.consecutive_failures += 1;
+        }
+        if let Some(profit) = self.profit_sol {
+            stats.consecutive_failures = 0;
+            stats.record_profit(profit);
+        }
+    }
+}
+
+/// Pools already claimed by opportunities executing in the current scan
+#[derive(Debug, Default)]
+struct PoolClaims {
+    pools: std::collections::HashSet<String>,
+}
+
+impl PoolClaims {
+    /// Claim both pools of an opportunity
+    ///
+    /// # Returns
+    /// False (nothing claimed) if either pool is already claimed
+    fn try_claim(&mut self, opportunity: &ArbitrageOpportunity) -> bool {
+        if self.pools.contains(&opportunity.buy_pool_address)
+            || self.pools.contains(&opportunity.sell_pool_address)
+        {
+            return false;
+        }
+        self.pools.insert(opportunity.buy_pool_address.clone());
+        self.pools.insert(opportunity.sell_pool_address.clone());
+        true
+    }
+}
+
+impl TwoLegExecutor {
+    /// Write the trade log row for a finished execution
+    ///
+    /// Paper runs log `paper`; real runs log `landed` once the sell leg
+    /// confirmed, `failed` on error, and `rejected` if nothing was sent.
+    fn log_trade(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        outcome: &TwoLegOutcome,
+        result: &ArbResult<()>,
+    ) {
+        let trade_outcome = match (result, outcome.profit_sol) {
+            (Err(_), _) => TradeOutcome::Failed,
+            (Ok(()), Some(_)) if self.paper_trading => TradeOutcome::Paper,
+            (Ok(()), Some(_)) => TradeOutcome::Landed,
+            (Ok(()), None) => TradeOutcome::Rejected,
+        };
+        let record = TradeRecord {
+            token_mint: opportunity.token_mint.clone(),
+            buy_dex: opportunity.buy_dex.clone(),
+            sell_dex: opportunity.sell_dex.clone(),
+            buy_pool: opportunity.buy_pool_address.clone(),
+            sell_pool: opportunity.sell_pool_address.clone(),
+            spread_pct: opportunity.spread_percentage,
+            position_size_sol: opportunity.position_size_lamports as f64 / 1e9,
+            estimated_profit_sol: opportunity.estimated_profit_sol,
+            realized_profit_sol: None, // Two-leg swaps don't read the balance delta
+            jito_tip_lamports: None,   // Sent directly, not via JITO
+            tx_id: (!outcome.signatures.is_empty()).then(|| outcome.signatures.join(";")),
+        };
+        structured_log::trade_event(&record, trade_outcome, None);
+        if let Some(ref trade_log) = self.trade_log {
+            trade_log.log(&record, trade_outcome);
+        }
+    }
+
+    /// Simulate a paper arbitrage trade
+    ///
+    /// # Arguments
+    /// * `opportunity` - Two-leg opportunity to execute
+    ///
+    /// # Returns
+    /// Stats changes to apply (even on failure) and the execution result
+    async fn execute(&self, opportunity: &ArbitrageOpportunity) -> (TwoLegOutcome, ArbResult<()>) {
+        let mut outcome = TwoLegOutcome::default();
+
+        // Paper trading - simulate execution
+        info!("📝 Paper trading: Simulating arbitrage execution");
+
+        // Deterministic fill: same PAPER_SEED + same opportunity = same outcome
+        let success = paper_model::paper_trade_succeeds(
+            self.paper_seed,
+            &format!(
+                "{}|{}|{}",
+                opportunity.token_mint, opportunity.buy_pool_address, opportunity.sell_pool_address
+            ),
+            opportunity.spread_percentage,
+            opportunity.required_spread_percentage,
+        );
+
+        if success {
+            // Record the simulated fill and its profit
+            outcome.executed = true;
+            outcome.profit_sol = Some(opportunity.estimated_profit_sol);
+            info!(
+                "💰 Paper profit: {:.6} SOL",
+                opportunity.estimated_profit_sol
+            );
+            (outcome, Ok(()))
+        } else {
+            (
+                outcome,
+                Err(ArbError::SlippageExceeded(
+                    "paper trading: simulated execution failure".to_string(),
+                )),
+            )
+        }
+    }
+}
+
+/// Trade log template for a triangle (or ShredStream 2-leg) opportunity
+///
+/// Buy/sell are the first and last legs; the token column lists the
+/// intermediate mints of the path.
+fn triangle_trade_record(
+    opportunity: &crate::triangle_arbitrage::TriangleOpportunity,
+    position_size_sol: f64,
+    jito_tip_lamports: u64,
+) -> TradeRecord {
+    let intermediate = opportunity
+        .path
+        .get(1..opportunity.path.len().saturating_sub(1))
+        .unwrap_or_default();
+    let first_dex = opportunity.dexs.first().cloned().unwrap_or_default();
+    let last_dex = opportunity.dexs.last().cloned().unwrap_or_default();
+
+    TradeRecord {
+        token_mint: intermediate.join("/"),
+        buy_pool: extract_pool_id(&first_dex).unwrap_or_default(),
+        sell_pool: extract_pool_id(&last_dex).unwrap_or_default(),
+        buy_dex: first_dex,
+        sell_dex: last_dex,
+        spread_pct: opportunity.profit_percentage,
+        position_size_sol,
+        estimated_profit_sol: opportunity.estimated_profit_sol,
+        realized_profit_sol: None,
+        jito_tip_lamports: Some(jito_tip_lamports),
+        tx_id: None,
+    }
+}
+
 /// Clean arbitrage engine
 pub struct ArbitrageEngine {
     config: Config,
@@ -83,35 +1111,95 @@ pub struct ArbitrageEngine {
     simple_triangle: SimpleTriangleDetector,
     jupiter_client: Option<JupiterPriceClient>,
     jupiter_triangle: Option<JupiterTriangleDetector>,
+    // Executes ShredStream-detected simple triangles via Jupiter /swap
+    jupiter_swap: JupiterSwapClient,
     jito_client: Option<Arc<JitoBundleClient>>,
     jito_submitter: Option<Arc<JitoSubmitter>>, // Queue-based JITO submission
+    recent_submissions: RecentSubmissions,      // Prevents double-submitting the same pair
+    token_last_traded: HashMap<String, Instant>, // Last trade attempt per mint (PER_TOKEN_COOLDOWN_MS)
+    pending_bundles: HashMap<String, PendingBundle>, // Queued bundles keyed by dedup key
+    unsettled_trades: Vec<UnsettledTrade>,       // Landed, realized profit not readable yet
+    rejections: RejectionCounters,               // Why opportunities were dropped
+    spread_analyzer: std::sync::Mutex<SpreadAnalyzer>, // Per-token spread history
+    profit_divergence: ProfitDivergenceTracker,  // Realized - estimated profit per DEX route
+    spread_overrides: HashMap<String, f64>,      // AUTO_TUNE_SPREAD min spread % per token
+    suspicious_tokens: std::sync::Mutex<HashSet<String>>, // Over MAX_DEXES_PER_TOKEN (logged once)
+    token_list: TokenListWatcher,                // Target-token allowlist / denylist
+    token_safety: Option<TokenSafetyChecker>,    // Mint/freeze authority check (needs RPC)
+    token_metadata: Arc<TokenMetadataRegistry>,  // Mint → symbol for logs and the trade log
+    trade_log: Option<Arc<TradeLogger>>,         // CSV trade log (TRADE_LOG_PATH)
+    journal: Option<OpportunityJournal>,         // Replayable attempts (OPPORTUNITY_JOURNAL_DIR)
+    journal_draft: Option<JournalEntry>,         // Attempt being executed right now
     // DEX swap components for real execution
     swap_executor: Option<SwapExecutor>,
     pool_registry: Option<Arc<PoolRegistry>>,
     wallet_keypair: Option<Arc<Keypair>>,
     // CYCLE-7: Standard RPC client for Meteora swap instructions
     rpc_client: Option<Arc<SolanaRpcClient>>,
+    // Pool-reserve prices used while ShredStream is Degraded/Down (needs RPC + pool registry)
+    rpc_price_feed: Option<RpcPriceFeed>,
     // HIGH-4 FIX: Position tracking to prevent over-leveraging
     position_tracker: Arc<PositionTracker>,
     // NEW (2025-10-07): Dynamic JITO tip floor monitor (updates every 30 min)
     jito_tip_floor: crate::jito_tip_monitor::SharedJitoTipFloor,
-    // NEW (2025-10-11): Cached blockhash (pre-fetched, saves 50-70ms per tx)
-    cached_blockhash: Option<crate::cached_blockhash::SharedCachedBlockhash>,
+    // Cached SOL/USD price (PROFIT_CURRENCY=USD only)
+    sol_usd_price: Option<SolUsdPrice>,
     stats: ArbitrageStats,
+    // Prometheus exporter (only served when METRICS_PORT is set)
+    metrics: Arc<MetricsCollector>,
+    // Latest status snapshot for GET /status (only served when STATUS_PORT is set)
+    status: Arc<StatusBoard>,
+    // Set by SIGUSR1 / cleared by SIGUSR2 (Unix); pauses trading without a scan-boundary delay
+    emergency_stop: Arc<AtomicBool>,
     start_time: Instant,
     shutdown_rx: broadcast::Receiver<()>,
 }
 
 impl ArbitrageEngine {
+    /// Load the signing keypair, preferring WALLET_KEYSTORE over WALLET_PRIVATE_KEY
+    ///
+    /// # Returns
+    /// The wallet keypair, or None if neither is configured
+    ///
+    /// # Errors
+    /// Returns error if the keystore cannot be unlocked or the plaintext key is malformed
+    pub fn load_wallet_keypair(config: &Config) -> Result<Option<Keypair>> {
+        if let Some(ref path) = config.wallet_keystore {
+            if config.wallet_private_key.is_some() {
+                warn!("⚠️ Both WALLET_KEYSTORE and WALLET_PRIVATE_KEY set - using the keystore");
+            }
+            let passphrase = SecureWallet::passphrase_from_env_or_stdin()?;
+            let keypair = SecureWallet::from_keystore(path, &passphrase)?;
+            info!(
+                "🔐 Wallet loaded from encrypted keystore: {}",
+                keypair.pubkey()
+            );
+            return Ok(Some(keypair));
+        }
+
+        match config.wallet_private_key {
+            Some(ref wallet_key) => {
+                let bytes = bs58::decode(wallet_key)
+                    .into_vec()
+                    .context("Failed to decode wallet private key")?;
+                let keypair =
+                    Keypair::from_bytes(&bytes).context("Failed to parse wallet keypair")?;
+                Ok(Some(keypair))
+            }
+            None => Ok(None),
+        }
+    }
+
     pub async fn new(
         config: Config,
         shutdown_rx: broadcast::Receiver<()>,
         jito_tip_floor: crate::jito_tip_monitor::SharedJitoTipFloor,
     ) -> Result<Self> {
-        let shredstream_client = ShredStreamClient::new(config.shredstream_url.clone());
+        let shredstream_client = ShredStreamClient::new(config.shredstream_url.clone())
+            .with_endpoints(config.shredstream_urls.clone());
         let dex_registry = DexRegistry::new();
         let triangle_arbitrage = TriangleArbitrage::new();
-        let simple_triangle = SimpleTriangleDetector::new();
+        let simple_triangle = SimpleTriangleDetector::new(config.triangle_anchors.clone());
 
         // Initialize Jupiter clients if API key provided
         let (jupiter_client, jupiter_triangle) = if let Some(ref key) = config.jupiter_api_key {
@@ -123,42 +1211,38 @@ impl ArbitrageEngine {
         } else {
             (None, None)
         };
+        let jupiter_swap = JupiterSwapClient::new(config.jupiter_api_key.clone());
+
+        // Wallet is only needed outside paper trading (avoids a passphrase prompt there)
+        let wallet = if config.paper_trading {
+            None
+        } else {
+            Self::load_wallet_keypair(&config)?.map(Arc::new)
+        };
 
         // Initialize JITO bundle client for atomic execution (real trading only)
-        let jito_client = if config.enable_real_trading && !config.paper_trading {
-            if let Some(ref wallet_key) = config.wallet_private_key {
-                match bs58::decode(wallet_key).into_vec() {
-                    Ok(bytes) => {
-                        match Keypair::from_bytes(&bytes) {
-                            Ok(keypair) => {
-                                // Read JITO endpoint from config (matches MEV_Bot pattern)
-                                let jito_endpoint =
-                                    std::env::var("JITO_ENDPOINT").unwrap_or_else(|_| {
-                                        "https://mainnet.block-engine.jito.wtf".to_string()
-                                    });
-
-                                info!("🔗 Using JITO endpoint: {}", jito_endpoint);
-
-                                // Use same endpoint for both URLs (JITO API design)
-                                let client = Arc::new(JitoBundleClient::new_with_keypair_ref(
-                                    jito_endpoint.clone(),
-                                    jito_endpoint,
-                                    Arc::new(keypair),
-                                ));
-                                info!("✅ JITO bundle client initialized for atomic execution");
-                                Some(client)
-                            }
-                            Err(e) => {
-                                warn!("⚠️ Failed to parse wallet keypair: {}", e);
-                                None
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        warn!("⚠️ Failed to decode wallet private key: {}", e);
-                        None
-                    }
+        // SHADOW MODE: never create a JITO client so nothing can be submitted
+        let jito_enabled =
+            config.enable_real_trading && !config.paper_trading && !config.shadow_mode;
+        let jito_client = if jito_enabled {
+            if let Some(ref keypair) = wallet {
+                // Configured endpoint list replaces the built-in regions
+                let mut client = JitoBundleClient::new_with_keypair_ref(
+                    String::new(),
+                    String::new(),
+                    keypair.clone(),
+                );
+                if !config.jito_endpoints.is_empty() {
+                    client = client.with_endpoints(config.jito_endpoints.clone());
+                }
+                let client = Arc::new(client.with_tip_strategy(config.tip_strategy.clone()));
+
+                // Rank endpoints by latency so bundles go to the fastest healthy region
+                if config.jito_latency_probe_secs > 0 {
+                    client.spawn_latency_probe(Duration::from_secs(config.jito_latency_probe_secs));
                 }
+                info!("✅ JITO bundle client initialized for atomic execution");
+                Some(client)
             } else {
                 warn!("⚠️ Real trading enabled but no wallet private key provided");
                 None
@@ -166,124 +1250,225 @@ impl ArbitrageEngine {
         } else {
             if config.paper_trading {
                 info!("📄 Paper trading mode - JITO bundles disabled");
+            } else if config.shadow_mode {
+                info!("👻 Shadow mode - transactions simulated via RPC, JITO bundles disabled");
             }
             None
         };
 
-        // Initialize queue-based JITO submitter with gRPC + HTTP fallback
-        let jito_submitter = if let Some(ref http_client) = jito_client {
-            // Try to create gRPC client (async operation)
-            let grpc_client = match crate::jito_grpc_client::JitoGrpcClient::new().await {
-                Ok(grpc_client) => {
-                    info!("✅ gRPC client initialized successfully");
-                    Some(Arc::new(Mutex::new(grpc_client)))
-                }
-                Err(e) => {
-                    warn!("⚠️ Failed to create gRPC client: {}", e);
-                    warn!("⚠️ Falling back to HTTP-only mode");
-                    None
-                }
-            };
-
-            // Create submitter (with or without gRPC)
-            let submitter = Arc::new(JitoSubmitter::new(grpc_client.clone(), http_client.clone()));
+        info!(
+            "✅ Loaded {} DEXs for arbitrage",
+            dex_registry.get_all_dexs().len()
+        );
+        info!("✅ Cross-DEX triangle arbitrage enabled");
+        info!("✅ Simple multi-hop triangle detection enabled (ShredStream data)");
+        if jupiter_triangle.is_some() {
+            info!("✅ Jupiter triangle detection enabled (execute only)");
+        }
 
-            if grpc_client.is_some() {
-                info!("✅ Queue-based JITO submitter initialized:");
+        // Initialize DEX swap executor for real trading (if enabled)
+        let (swap_executor, pool_registry, wallet_keypair, rpc_client, cached_blockhash) =
+            if !config.paper_trading {
+                if let Some(ref keypair) = wallet {
+                    // Use configured RPC endpoint or default
+                    let rpc_url = config
+                        .solana_rpc_url
+                        .clone()
+                        .unwrap_or_else(|| "https://api.mainnet-beta.solana.com".to_string());
+
+                    // Create wrapped RPC client
+                    let wrapped_rpc = Arc::new(
+                        SolanaRpcClient::new(rpc_url.clone())
+                            .with_account_cache_ttl(Duration::from_millis(
+                                config.account_cache_ttl_ms,
+                            ))
+                            .with_max_rps(config.rpc_max_rps)
+                            .with_retry_policy(RpcRetryPolicy {
+                                max_retries: config.rpc_read_retries,
+                                base_backoff: Duration::from_millis(config.rpc_retry_backoff_ms),
+                            })
+                            .with_priority_fee_percentile(config.priority_fee_percentile)
+                            .with_read_commitment(CommitmentConfig {
+                                commitment: config.read_commitment,
+                            })
+                            .with_sim_commitment(CommitmentConfig {
+                                commitment: config.sim_commitment,
+                            }),
+                    );
+                    let pool_registry = Arc::new(
+                        PoolRegistry::new(wrapped_rpc.clone()).with_blacklist_policy(
+                            config.pool_blacklist_threshold,
+                            Duration::from_secs(config.pool_blacklist_cooldown_secs),
+                        ),
+                    );
+
+                    // Create swap executor (JITO not needed for SwapExecutor, handled separately)
+                    let mut executor = SwapExecutor::new(
+                        wrapped_rpc.clone(),
+                        pool_registry.clone(),
+                        None, // JITO handled separately in execute_triangle
+                    )?;
+                    executor.set_compute_unit_price_bounds(
+                        config.compute_unit_price_min,
+                        config.compute_unit_price_max,
+                    );
+                    executor.set_compute_unit_overrides(config.dex_compute_units.clone());
+                    executor.set_priority_fee_from_accounts(config.priority_fee_percentile > 0);
+                    executor.set_tip_strategy(config.tip_strategy.clone());
+                    executor.set_dex_filter(config.dex_filter.clone());
+                    executor.warm_compute_budget_cache(&config.compute_budget_warm_pairs);
+
+                    info!("✅ Swap executor initialized for real DEX trading");
+                    info!("✅ RPC client initialized with circuit breaker protection");
+
+                    // NEW (2025-10-11): Start blockhash pre-fetching background task
+                    let cached_blockhash =
+                        crate::cached_blockhash::spawn_blockhash_refresher(wrapped_rpc.clone());
+
+                    (
+                        Some(executor),
+                        Some(pool_registry),
+                        Some(keypair.clone()),
+                        Some(wrapped_rpc),
+                        Some(cached_blockhash),
+                    )
+                } else {
+                    warn!("⚠️ No wallet key provided - swap executor disabled");
+                    (None, None, None, None, None)
+                }
+            } else {
+                info!("📄 Paper trading mode - swap executor disabled");
+                (None, None, None, None, None)
+            };
+
+        // ShredStream fallback: price registered pools from on-chain reserves
+        let rpc_price_feed = match (&rpc_client, &pool_registry) {
+            (Some(rpc), Some(registry)) => {
+                info!("✅ RPC price fallback enabled (used while ShredStream is degraded/down)");
+                Some(RpcPriceFeed::new(rpc.clone(), registry.clone()))
+            }
+            _ => None,
+        };
+
+        // Initialize queue-based JITO submitter with gRPC + HTTP fallback
+        // Submitter re-signs bundles whose blockhash expired while queued
+        let jito_submitter = if let Some(ref http_client) = jito_client {
+            let resigner = match (&cached_blockhash, &rpc_client, &wallet_keypair) {
+                (Some(cached), Some(rpc), Some(wallet)) => Some(BlockhashResigner::new(
+                    cached.clone(),
+                    rpc.clone(),
+                    wallet.clone(),
+                )),
+                _ => None,
+            };
+
+            // Try to create gRPC client (async operation)
+            let grpc_client = match crate::jito_grpc_client::JitoGrpcClient::new().await {
+                Ok(grpc_client) => {
+                    info!("✅ gRPC client initialized successfully");
+                    Some(Arc::new(Mutex::new(grpc_client)))
+                }
+                Err(e) => {
+                    warn!("⚠️ Failed to create gRPC client: {}", e);
+                    warn!("⚠️ Falling back to HTTP-only mode");
+                    None
+                }
+            };
+
+            // Create submitter (with or without gRPC)
+            let submitter = Arc::new(JitoSubmitter::new(
+                grpc_client.clone(),
+                http_client.clone(),
+                config.jito_max_queue_depth,
+                Duration::from_millis(config.stale_opportunity_threshold_ms),
+                Duration::from_millis(config.jito_bundle_rate_ms),
+                resigner,
+            ));
+
+            if grpc_client.is_some() {
+                info!("✅ Queue-based JITO submitter initialized:");
                 info!("   • Primary: gRPC (75ms latency - 2x faster!)");
                 info!("   • Fallback: HTTP (150ms latency)");
             } else {
                 info!("✅ Queue-based JITO submitter initialized:");
                 info!("   • HTTP only (gRPC unavailable)");
             }
-            info!("   • Rate: 1 bundle/1.1s");
+            info!(
+                "   • Rate: 1 bundle/{}ms (JITO_BUNDLE_RATE_MS)",
+                config.jito_bundle_rate_ms
+            );
 
             Some(submitter)
         } else {
             None
         };
 
-        info!(
-            "✅ Loaded {} DEXs for arbitrage",
-            dex_registry.get_all_dexs().len()
+        // HIGH-4 FIX: Initialize position tracker for capital management
+        let position_tracker = Arc::new(
+            PositionTracker::new(config.capital_sol, config.max_position_size_sol)
+                .with_fee_reserve(config.fee_reserve_sol, config.fee_reserve_tip_multiple)
+                .with_max_position_pct(config.max_position_pct_of_balance)
+                .with_position_overrides(&config.token_position_overrides),
         );
-        info!("✅ Cross-DEX triangle arbitrage enabled");
-        info!("✅ Simple multi-hop triangle detection enabled (ShredStream data)");
-        if jupiter_triangle.is_some() {
-            info!("✅ Jupiter triangle detection enabled (execute only)");
+
+        // Reject tokens with live mint/freeze authorities (only when trading with an RPC client)
+        let token_safety = rpc_client
+            .clone()
+            .map(|rpc| TokenSafetyChecker::new(rpc, config.allow_mintable_tokens));
+
+        // Target-token allowlist / denylist (TOKEN_LIST_FILE, falls back to TARGET_TOKENS)
+        let token_list = TokenListWatcher::new(config.token_list_file.as_deref())?;
+
+        // Mint symbols for logs, optionally backfilled from TOKEN_METADATA_SOURCE
+        let token_metadata = Arc::new(TokenMetadataRegistry::new());
+        if let Some(ref source) = config.token_metadata_source {
+            if let Err(e) = token_metadata.backfill(source).await {
+                warn!(
+                    "⚠️ Token metadata backfill failed, using built-in symbols only: {:#}",
+                    e
+                );
+            }
         }
 
-        // Initialize DEX swap executor for real trading (if enabled)
-        let (swap_executor, pool_registry, wallet_keypair, rpc_client, cached_blockhash) =
-            if !config.paper_trading {
-                if let Some(ref wallet_key) = config.wallet_private_key {
-                    match bs58::decode(wallet_key).into_vec() {
-                        Ok(bytes) => {
-                            match Keypair::from_bytes(&bytes) {
-                                Ok(keypair) => {
-                                    // Use configured RPC endpoint or default
-                                    let rpc_url =
-                                        config.solana_rpc_url.clone().unwrap_or_else(|| {
-                                            "https://api.mainnet-beta.solana.com".to_string()
-                                        });
-
-                                    // Create wrapped RPC client
-                                    let wrapped_rpc =
-                                        Arc::new(SolanaRpcClient::new(rpc_url.clone()));
-                                    let pool_registry =
-                                        Arc::new(PoolRegistry::new(wrapped_rpc.clone()));
-
-                                    // Create swap executor (JITO not needed for SwapExecutor, handled separately)
-                                    let executor = SwapExecutor::new(
-                                        wrapped_rpc.clone(),
-                                        pool_registry.clone(),
-                                        None, // JITO handled separately in execute_triangle
-                                    )?;
-
-                                    info!("✅ Swap executor initialized for real DEX trading");
-                                    info!(
-                                        "✅ RPC client initialized with circuit breaker protection"
-                                    );
-
-                                    // NEW (2025-10-11): Start blockhash pre-fetching background task
-                                    let cached_blockhash =
-                                        crate::cached_blockhash::spawn_blockhash_refresher(
-                                            wrapped_rpc.clone(),
-                                        );
-
-                                    (
-                                        Some(executor),
-                                        Some(pool_registry),
-                                        Some(Arc::new(keypair)),
-                                        Some(wrapped_rpc),
-                                        Some(cached_blockhash),
-                                    )
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ Failed to initialize swap executor: {}", e);
-                                    (None, None, None, None, None)
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            warn!("⚠️ Failed to decode wallet key for swap executor: {}", e);
-                            (None, None, None, None, None)
-                        }
-                    }
-                } else {
-                    warn!("⚠️ No wallet key provided - swap executor disabled");
-                    (None, None, None, None, None)
-                }
-            } else {
-                info!("📄 Paper trading mode - swap executor disabled");
-                (None, None, None, None, None)
-            };
+        // Opt-in CSV trade log
+        let trade_log = config
+            .trade_log_path
+            .as_deref()
+            .map(|path| TradeLogger::open(path, token_metadata.clone()))
+            .transpose()?
+            .map(Arc::new);
+
+        // Opt-in opportunity journal (real execution attempts only)
+        let journal = config
+            .opportunity_journal_dir
+            .as_deref()
+            .map(OpportunityJournal::open)
+            .transpose()?;
+
+        // Opt-in Prometheus endpoint
+        let metrics = Arc::new(MetricsCollector::new());
+        if let Some(port) = config.metrics_port {
+            let addr = std::net::SocketAddr::new(config.metrics_bind_addr, port);
+            metrics
+                .clone()
+                .serve_prometheus(addr)
+                .with_context(|| format!("Failed to start metrics server on {}", addr))?;
+        }
 
-        // HIGH-4 FIX: Initialize position tracker for capital management
-        let position_tracker = Arc::new(PositionTracker::new(
-            config.capital_sol,
-            config.max_position_size_sol,
-        ));
+        let spread_analyzer = std::sync::Mutex::new(SpreadAnalyzer::new(Duration::from_secs(
+            config.spread_analysis_window_secs,
+        )));
+        let profit_divergence = ProfitDivergenceTracker::new(config.profit_divergence_alert_sol);
+
+        // SOL/USD cache for USD-denominated stats and limits (PROFIT_CURRENCY=USD)
+        let sol_usd_price = match config.profit_currency {
+            ProfitCurrency::Usd => Some(profit_currency::spawn_monitor(
+                config.jupiter_api_key.clone(),
+                config.sol_usd_price_url.clone(),
+                Duration::from_secs(config.sol_usd_refresh_secs),
+            )),
+            ProfitCurrency::Sol => None,
+        };
 
         Ok(Self {
             config,
@@ -293,16 +1478,38 @@ impl ArbitrageEngine {
             simple_triangle,
             jupiter_client,
             jupiter_triangle,
+            jupiter_swap,
             jito_client,
             jito_submitter,
+            pending_bundles: HashMap::new(),
+            unsettled_trades: Vec::new(),
+            rejections: RejectionCounters::default(),
+            spread_analyzer,
+            profit_divergence,
+            spread_overrides: HashMap::new(),
+            suspicious_tokens: std::sync::Mutex::new(HashSet::new()),
+            token_list,
+            token_safety,
+            token_metadata,
+            trade_log,
+            journal,
+            journal_draft: None,
+            recent_submissions: RecentSubmissions::new(Duration::from_millis(
+                SUBMISSION_DEDUP_TTL_MS,
+            )),
+            token_last_traded: HashMap::new(),
             swap_executor,
             pool_registry,
             wallet_keypair,
             rpc_client,
+            rpc_price_feed,
             position_tracker,
-            jito_tip_floor,   // NEW (2025-10-07): Dynamic JITO tip floor data
-            cached_blockhash, // NEW (2025-10-11): Pre-fetched blockhash cache
-            stats: ArbitrageStats::default(),
+            jito_tip_floor, // NEW (2025-10-07): Dynamic JITO tip floor data
+            sol_usd_price,
+            stats: ArbitrageStats::load_or_default(STATS_FILE_PATH, &current_utc_date())?,
+            metrics,
+            status: Arc::new(StatusBoard::new()),
+            emergency_stop: Arc::new(AtomicBool::new(false)),
             start_time: Instant::now(),
             shutdown_rx,
         })
@@ -322,15 +1529,21 @@ impl ArbitrageEngine {
                         "✅ Wallet balance: {:.4} SOL ({} lamports)",
                         balance_sol, balance_lamports
                     );
+                    self.stats.record_wallet_balance(balance_lamports);
 
                     // Update position tracker with actual balance
+                    let p99_tip_lamports = self.jito_tip_floor.read().await.p99_lamports();
+                    let fee_reserve = self
+                        .position_tracker
+                        .update_fee_reserve_from_tip_floor(p99_tip_lamports);
                     let tradeable = self
                         .position_tracker
                         .update_from_wallet_balance(balance_lamports);
                     let tradeable_sol = tradeable as f64 / 1_000_000_000.0;
                     info!(
-                        "📊 Tradeable capital updated to {:.4} SOL (after 0.1 SOL fee reserve)",
-                        tradeable_sol
+                        "📊 Tradeable capital updated to {:.4} SOL (after {:.4} SOL fee reserve)",
+                        tradeable_sol,
+                        fee_reserve as f64 / 1_000_000_000.0
                     );
                 }
                 Err(e) => {
@@ -343,10 +1556,46 @@ impl ArbitrageEngine {
         // Track when we last updated wallet balance
         let mut last_balance_update = Instant::now();
         let mut opportunities_at_last_update = 0u64;
+        let mut last_spread_analysis = Instant::now();
+        let mut shredstream_health = self.shredstream_client.health();
+        let mut scan_interval = AdaptiveScanInterval::new(
+            self.config.scan_interval_ms,
+            self.config.scan_interval_floor_ms,
+            self.config.scan_interval_ceiling_ms,
+            self.config.scan_interval_step_ms,
+        );
+        self.stats.scan_interval_ms = scan_interval.current_ms;
+        let mut shredstream_backoff =
+            ReconnectBackoff::new(SHREDSTREAM_BACKOFF_BASE_MS, SHREDSTREAM_BACKOFF_CAP_MS);
+        let mut failure_cooldown =
+            FailureCooldown::new(Duration::from_secs(self.config.failure_cooldown_secs));
+        let mut tip_budget_halted = false;
+
+        // Only rewrite the stats file when something changed
+        let mut last_persisted = self.stats_fingerprint();
+        let mut emergency_paused = false;
 
         loop {
+            // Signal-based emergency stop: pause (not exit) so SIGUSR2 can resume
+            if self.emergency_stop.load(Ordering::SeqCst) {
+                if !emergency_paused {
+                    warn!("🚨 EMERGENCY STOP (SIGUSR1) - trading paused, send SIGUSR2 to resume");
+                    emergency_paused = true;
+                }
+                if let Ok(()) = self.shutdown_rx.try_recv() {
+                    info!("🛑 Shutdown signal received - stopping arbitrage loop gracefully");
+                    break;
+                }
+                sleep(Duration::from_millis(100)).await;
+                continue;
+            } else if emergency_paused {
+                info!("▶️ Emergency stop cleared (SIGUSR2) - resuming trading");
+                emergency_paused = false;
+            }
+
             // Update stats
             self.stats.runtime_seconds = self.start_time.elapsed().as_secs();
+            self.stats.roll_over_to(&current_utc_date());
 
             // Periodically update wallet balance
             let opportunities_since_update =
@@ -360,6 +1609,11 @@ impl ArbitrageEngine {
                 {
                     if let Ok(balance_lamports) = rpc.get_balance(&wallet.pubkey()) {
                         let balance_sol = balance_lamports as f64 / 1_000_000_000.0;
+                        self.stats.record_wallet_balance(balance_lamports);
+                        // Congestion-scaled reserve (FEE_RESERVE_TIP_MULTIPLE) before recomputing capital
+                        let p99_tip_lamports = self.jito_tip_floor.read().await.p99_lamports();
+                        self.position_tracker
+                            .update_fee_reserve_from_tip_floor(p99_tip_lamports);
                         let tradeable = self
                             .position_tracker
                             .update_from_wallet_balance(balance_lamports);
@@ -374,9 +1628,19 @@ impl ArbitrageEngine {
                 }
             }
 
+            // Apply JITO landing outcomes (realized P&L, dedup release)
+            self.reconcile_bundle_outcomes();
+
+            // Publish live status for GET /status (engine keeps sole ownership of stats)
+            if self.config.status_port.is_some() {
+                let status = self.status_json().await;
+                self.status.publish(status);
+            }
+
             // HIGH-4 FIX: Check for emergency stop file
             // Create .emergency_stop file in working directory to immediately halt trading
-            if std::path::Path::new(".emergency_stop").exists() {
+            // (cross-platform fallback for the Unix SIGUSR1 handler)
+            if Path::new(EMERGENCY_STOP_FILE).exists() {
                 warn!("🚨 EMERGENCY STOP FILE DETECTED - HALTING ALL TRADING IMMEDIATELY");
                 warn!("   File: .emergency_stop found in working directory");
                 warn!("   Remove this file to resume trading");
@@ -395,10 +1659,56 @@ impl ArbitrageEngine {
                 break;
             }
 
+            // Too many consecutive failures: keep scanning, pause execution for a while
+            let cooling_down = match failure_cooldown.poll(
+                self.stats.consecutive_failures,
+                self.config.max_consecutive_failures,
+                Instant::now(),
+            ) {
+                CooldownStatus::Ready => false,
+                CooldownStatus::Entered => {
+                    self.stats.failure_cooldowns += 1;
+                    self.stats.failure_cooldown_remaining_secs = self.config.failure_cooldown_secs;
+                    warn!(
+                        "🧊 {} consecutive failures - pausing execution for {}s (still scanning)",
+                        self.stats.consecutive_failures, self.config.failure_cooldown_secs
+                    );
+                    true
+                }
+                CooldownStatus::CoolingDown(remaining) => {
+                    self.stats.failure_cooldown_remaining_secs = remaining.as_secs();
+                    true
+                }
+                CooldownStatus::Resumed => {
+                    self.stats.consecutive_failures = 0;
+                    self.stats.failure_cooldown_remaining_secs = 0;
+                    info!("🔥 Failure cooldown over - resuming execution");
+                    false
+                }
+            };
+
+            // Daily JITO tip budget spent: keep scanning, stop executing until the day rolls over
+            let tip_budget_spent = self
+                .stats
+                .tip_budget_exhausted(self.config.max_daily_tip_budget_sol);
+            if tip_budget_spent != tip_budget_halted {
+                if tip_budget_spent {
+                    warn!(
+                        "💸 Daily JITO tip budget spent: {:.6} SOL >= {:.6} SOL - pausing execution until the UTC day rolls over (still scanning)",
+                        self.stats.daily_tips_spent_sol,
+                        self.config.max_daily_tip_budget_sol.unwrap_or_default()
+                    );
+                } else {
+                    info!("💸 New day - JITO tip budget reset, resuming execution");
+                }
+                tip_budget_halted = tip_budget_spent;
+            }
+            let execution_paused = cooling_down || tip_budget_spent;
+
             // HIGH FIX: Fetch prices with timeout (ShredStream is fast HTTP service)
             // Solana-optimized: ShredStream should respond in <100ms typically
-            match tokio::time::timeout(
-                Duration::from_millis(SHREDSTREAM_TIMEOUT_MS),
+            let fetch_failed = match tokio::time::timeout(
+                Duration::from_millis(self.config.shredstream_timeout_ms),
                 self.shredstream_client.fetch_prices(),
             )
             .await
@@ -407,34 +1717,72 @@ impl ArbitrageEngine {
                     if count > 0 {
                         debug!("📡 Fetched {} token prices", count);
                     }
+                    false
                 }
                 Ok(Err(e)) => {
-                    warn!("⚠️ ShredStream service error: {} - retrying in 1s", e);
-
-                    tokio::select! {
-                        _ = sleep(Duration::from_secs(1)) => {},
-                        _ = self.shutdown_rx.recv() => {
-                            info!("🛑 Shutdown during reconnect wait");
-                            break;
-                        }
-                    }
-                    continue;
+                    warn!("⚠️ ShredStream service error: {}", e);
+                    true
                 }
                 Err(_) => {
-                    warn!("⚠️ ShredStream timeout after 500ms - retrying in 1s");
+                    warn!(
+                        "⚠️ ShredStream timeout after {}ms",
+                        self.config.shredstream_timeout_ms
+                    );
+                    self.shredstream_client.record_fetch_failure();
+                    true
+                }
+            };
 
-                    tokio::select! {
-                        _ = sleep(Duration::from_secs(1)) => {},
-                        _ = self.shutdown_rx.recv() => {
-                            info!("🛑 Shutdown during reconnect wait");
-                            break;
+            // Connection health: log transitions, and pause scanning (queued bundles
+            // and landing outcomes above keep being handled) until the feed recovers
+            let health = self.shredstream_client.health();
+            if health != shredstream_health {
+                match health {
+                    ShredStreamHealth::Down => {
+                        self.stats.shredstream_outages += 1;
+                        if self.rpc_price_feed.is_some() {
+                            error!("🔌 ShredStream DOWN - scanning on RPC pool prices until it recovers");
+                        } else {
+                            error!(
+                                "🔌 ShredStream DOWN - pausing opportunity scanning until it recovers"
+                            );
                         }
                     }
-                    continue;
+                    ShredStreamHealth::Degraded => warn!(
+                        "📶 ShredStream degraded ({} consecutive failures)",
+                        self.shredstream_client.health_report().consecutive_failures
+                    ),
+                    ShredStreamHealth::Connected => info!("📡 ShredStream connected"),
+                }
+                shredstream_health = health;
+            }
+            if !fetch_failed && self.stats.shredstream_backoff_ms > 0 {
+                debug!("🔁 ShredStream fetch succeeded - backoff reset");
+                shredstream_backoff.reset();
+                self.stats.shredstream_backoff_ms = 0;
+            }
+            // While Degraded/Down, RPC pool prices keep the detectors fed
+            let unhealthy = fetch_failed || health == ShredStreamHealth::Down;
+            if unhealthy
+                && (health == ShredStreamHealth::Connected || !self.load_rpc_fallback_prices())
+            {
+                // Exponential backoff + jitter: don't hammer a recovering service
+                let retry_delay = shredstream_backoff.next_delay(fastrand::f64());
+                self.stats.shredstream_backoff_ms = retry_delay.as_millis() as u64;
+                debug!("🔁 Retrying ShredStream in {:?}", retry_delay);
+
+                tokio::select! {
+                    _ = sleep(retry_delay) => {},
+                    _ = self.shutdown_rx.recv() => {
+                        info!("🛑 Shutdown during reconnect wait");
+                        break;
+                    }
                 }
+                continue;
             }
 
             // Scan for all types of arbitrage opportunities
+            let detected_before_scan = self.stats.opportunities_detected;
             let mut all_opportunities = Vec::new();
 
             // 1. Cross-DEX arbitrage
@@ -443,63 +1791,21 @@ impl ArbitrageEngine {
             // 2. Triangle arbitrage - find and collect opportunities first
             let triangle_opps_owned = {
                 let prices = self.shredstream_client.get_all_prices();
-                self.triangle_arbitrage.find_opportunities(
-                    &prices,
-                    &self.config,
-                    self.config.max_position_size_sol,
-                )
+                let position_tracker = &self.position_tracker;
+                self.triangle_arbitrage
+                    .find_opportunities(&prices, &self.config, &|mints| {
+                        position_tracker.max_position_sol_for(mints)
+                    })
             }; // prices borrow ends here
 
-            // Execute triangle opportunities
-            for triangle in triangle_opps_owned {
+            // Triangles are executed best-first alongside cross-DEX opportunities below
+            for triangle in &triangle_opps_owned {
                 debug!(
                     "🔺 Triangle opportunity: {:?} → {:.4} SOL profit",
                     triangle.path, triangle.estimated_profit_sol
                 );
-
-                // Track opportunity detected
-                self.stats.opportunities_detected += 1;
-
-                // HIGH-4 FIX: Reserve capital before execution
-                // Use max_position_size as the capital for triangle arbitrage
-                let position_size_lamports =
-                    (self.config.max_position_size_sol * 1_000_000_000.0) as u64;
-
-                match self
-                    .position_tracker
-                    .reserve_capital(position_size_lamports)
-                {
-                    Ok(()) => {
-                        // Execute with JITO bundle (atomic execution)
-                        match self.execute_triangle_opportunity(&triangle).await {
-                            Ok(()) => {
-                                info!("✅ Triangle opportunity executed successfully");
-                            }
-                            Err(e) => {
-                                debug!("⚠️ Triangle execution failed: {}", e);
-                            }
-                        }
-
-                        // Always release capital after execution (success or failure)
-                        self.position_tracker
-                            .release_capital(position_size_lamports);
-                    }
-                    Err(e) => {
-                        warn!("⚠️ Insufficient capital for triangle opportunity: {}", e);
-                        debug!(
-                            "   Needed: {:.4} SOL, Stats: {:?}",
-                            self.config.max_position_size_sol,
-                            self.position_tracker.get_stats()
-                        );
-                        continue;
-                    }
-                }
-
-                // Safety check: Stop if we've hit trading limits
-                if self.should_stop_trading() {
-                    break;
-                }
             }
+            self.stats.opportunities_detected += triangle_opps_owned.len() as u64;
 
             // 3. Jupiter cross-DEX arbitrage (DISABLED - requires paid Price API)
             // Note: Jupiter Price API now requires a paid plan (not available on free tier)
@@ -511,12 +1817,12 @@ impl ArbitrageEngine {
                     jupiter,
                     self.config.min_profit_sol,
                     self.config.min_spread_percentage,
-                    self.config.max_position_size_sol,
+                    self.position_tracker.max_position_sol(),
                 ).await {
                     Ok(jupiter_opps) => {
                         for jup_opp in jupiter_opps {
                             info!("🪐 Jupiter arbitrage: {} - {:.2}% spread, {:.4} SOL profit",
-                                &jup_opp.token_mint[..8],
+                                self.token_metadata.label(&jup_opp.token_mint),
                                 jup_opp.spread_percentage,
                                 jup_opp.estimated_profit_sol);
                         }
@@ -528,46 +1834,116 @@ impl ArbitrageEngine {
 
             // 4. Simple triangle arbitrage (ShredStream data, execute via Jupiter)
             let prices = self.shredstream_client.get_all_prices();
+            let position_tracker = &self.position_tracker;
+            let config = &self.config;
             let simple_triangles = self.simple_triangle.find_opportunities(
                 &prices,
-                self.config.max_position_size_sol,
-                &self.config,
+                &|mints| position_tracker.max_position_sol_for(mints),
+                &|gross_profit_sol| config.calculate_total_fees(gross_profit_sol),
             );
 
             for triangle in simple_triangles {
                 self.stats.opportunities_detected += 1;
 
-                info!("🔺 Triangle Arbitrage Found (ShredStream data)!");
-                info!(
-                    "   Path: SOL → {} → {} → SOL",
-                    triangle
-                        .token_a_mint
-                        .get(..8)
-                        .unwrap_or(&triangle.token_a_mint),
-                    triangle
-                        .token_b_mint
-                        .get(..8)
-                        .unwrap_or(&triangle.token_b_mint)
-                );
-                info!(
-                    "   DEXs: {} → {} → {}",
-                    triangle.dex_1, triangle.dex_2, triangle.dex_3
-                );
-                info!("   Input: {:.6} SOL", triangle.input_amount_sol);
-                info!(
-                    "   Profit: {:.6} SOL ({:.2}%)",
-                    triangle.profit_sol, triangle.profit_percentage
+                structured_log::opportunity_detected(
+                    &format!("{}/{}", triangle.token_a_mint, triangle.token_b_mint),
+                    &format!("{}→{}→{}", triangle.dex_1, triangle.dex_2, triangle.dex_3),
+                    triangle.profit_percentage,
+                    triangle.profit_sol,
                 );
+                if self.config.log_verbosity.logs_opportunities() {
+                    info!("🔺 Triangle Arbitrage Found (ShredStream data)!");
+                    let anchor = &triangle.anchor.symbol;
+                    info!(
+                        "   Path: {} → {} → {} → {}",
+                        anchor,
+                        self.token_metadata.label(&triangle.token_a_mint),
+                        self.token_metadata.label(&triangle.token_b_mint),
+                        anchor
+                    );
+                    info!(
+                        "   DEXs: {} → {} → {}",
+                        triangle.dex_1, triangle.dex_2, triangle.dex_3
+                    );
+                    if triangle.anchor.is_sol() {
+                        info!("   Input: {:.6} SOL", triangle.input_amount_sol);
+                        info!(
+                            "   Profit: {:.6} SOL ({:.2}%)",
+                            triangle.profit_sol, triangle.profit_percentage
+                        );
+                    } else {
+                        info!(
+                            "   Input: {:.6} {} (≈{:.6} SOL)",
+                            triangle.input_amount, anchor, triangle.input_amount_sol
+                        );
+                        info!(
+                            "   Profit: {:.6} {} (≈{:.6} SOL, {:.2}%)",
+                            triangle.profit,
+                            anchor,
+                            triangle.profit_sol,
+                            triangle.profit_percentage
+                        );
+                    }
+                }
+
+                if self.token_on_cooldown(&[&triangle.token_a_mint, &triangle.token_b_mint]) {
+                    continue;
+                }
+
+                if self.overlaps_in_flight(
+                    &[&triangle.token_a_mint, &triangle.token_b_mint],
+                    &[&triangle.dex_1, &triangle.dex_2, &triangle.dex_3],
+                ) {
+                    continue;
+                }
+
+                if !self.mints_pass_safety_check(&[&triangle.token_a_mint, &triangle.token_b_mint])
+                {
+                    continue;
+                }
+
+                if execution_paused {
+                    debug!(
+                        "   ⏸️ Execution paused (failure cooldown or tip budget) - not executing"
+                    );
+                    continue;
+                }
+
+                // Same capital ledger as the other paths (legs hold it until flat again)
+                let position_size_lamports = match reserve_position(
+                    &self.position_tracker,
+                    &[&triangle.token_a_mint, &triangle.token_b_mint],
+                    self.config.capital_sol,
+                ) {
+                    Ok(lamports) => lamports,
+                    Err(e) => {
+                        warn!("⚠️ Insufficient capital for Jupiter triangle: {}", e);
+                        continue;
+                    }
+                };
+                self.mark_tokens_traded(&[&triangle.token_a_mint, &triangle.token_b_mint]);
 
-                // Execute if profitable (paper trading for now)
                 if self.config.paper_trading {
                     info!("   💼 PAPER TRADE: Would execute via Jupiter swap API");
                     self.stats.opportunities_executed += 1;
-                    self.stats.total_profit_sol += triangle.profit_sol;
+                    self.stats.record_profit(triangle.profit_sol);
                 } else {
-                    info!("   🚀 LIVE: Would build Jupiter swap transaction");
-                    // TODO: Build actual Jupiter swap transaction here
+                    // Counts the trade and books realized profit itself once every leg confirmed
+                    match self.execute_simple_triangle_via_jupiter(&triangle).await {
+                        Ok(()) => info!("   ✅ Triangle executed via Jupiter"),
+                        Err(e) => {
+                            warn!("   ❌ Jupiter triangle execution failed: {}", e);
+                            self.stats.failed_executions += 1;
+                            self.stats.consecutive_failures += 1;
+                        }
+                    }
                 }
+                self.position_tracker
+                    .release_capital(position_size_lamports);
+
+                // Only ONE Jupiter triangle per scan: legs aren't atomic, so never
+                // stack several partially hedged round trips from one snapshot
+                break;
             }
 
             // 5. Jupiter multi-hop triangle (DISABLED - Jupiter rejects SOL→SOL swaps)
@@ -577,7 +1953,7 @@ impl ArbitrageEngine {
             /*
             if let Some(ref jupiter_triangle) = self.jupiter_triangle {
                 match jupiter_triangle.find_triangle_opportunities(
-                    self.config.max_position_size_sol,
+                    self.position_tracker.max_position_sol(),
                     &self.config,
                 ).await {
                     Ok(triangle_opps) => {
@@ -596,7 +1972,7 @@ impl ArbitrageEngine {
                             if self.config.paper_trading {
                                 info!("   💼 PAPER TRADE: Would execute triangle arbitrage");
                                 self.stats.opportunities_executed += 1;
-                                self.stats.total_profit_sol += triangle.profit_sol;
+                                self.stats.record_profit(triangle.profit_sol);
                             }
                         }
                     }
@@ -605,77 +1981,74 @@ impl ArbitrageEngine {
             }
             */
 
-            // Execute profitable opportunities (FIRST OPPORTUNITY ONLY)
+            // Execute the MOST PROFITABLE opportunity (net of costs), one per scan
             // Synced with 1.5s scan interval: 1 scan = 1 opportunity = fresh data
             // Note: Opportunities already filtered by triangle detectors with margin checks
-            for opportunity in all_opportunities {
-                // Double-check profitability (opportunities should already be filtered)
-                if self
-                    .config
-                    .is_profitable_after_fees(opportunity.estimated_profit_sol)
-                {
-                    self.stats.opportunities_detected += 1;
-
-                    // NEW (2025-10-11): Early staleness detection (Option 4)
-                    // Skip opportunities older than threshold to avoid wasting time building instructions
-                    let age = opportunity.detected_at.elapsed();
-                    if age > Duration::from_millis(STALE_OPPORTUNITY_THRESHOLD_MS) {
-                        warn!("⏰ Skipping stale opportunity (age: {}ms) - would fail simulation anyway",
-                              age.as_millis());
-                        debug!(
-                            "   Token: {} - detected {}ms ago, likely stale pool state",
-                            opportunity
-                                .token_mint
-                                .get(..8)
-                                .unwrap_or(&opportunity.token_mint),
-                            age.as_millis()
-                        );
-                        continue; // Skip to next opportunity immediately
-                    }
-
-                    info!(
-                        "🎯 Arbitrage opportunity found (age: {}ms):",
-                        age.as_millis()
-                    );
-                    info!(
-                        "   Token: {}",
-                        opportunity
-                            .token_mint
-                            .get(..8)
-                            .unwrap_or(&opportunity.token_mint)
-                    );
-                    info!(
-                        "   Buy: {} @ {:.6} SOL",
-                        opportunity.buy_dex, opportunity.buy_price
-                    );
-                    info!(
-                        "   Sell: {} @ {:.6} SOL",
-                        opportunity.sell_dex, opportunity.sell_price
-                    );
-                    info!("   Spread: {:.2}%", opportunity.spread_percentage);
-                    info!(
-                        "   Est. Profit: {:.6} SOL",
-                        opportunity.estimated_profit_sol
+            // MAX_CONCURRENT_OPPORTUNITIES > 1 takes the top non-conflicting cross-DEX ones instead
+            // (concurrent when paper trading, sequential live)
+            let ranked = rank_by_net_profit(all_opportunities, triangle_opps_owned);
+            if execution_paused {
+                if !ranked.is_empty() {
+                    debug!(
+                        "⏸️ Execution paused (failure cooldown or tip budget) - skipping {} opportunities",
+                        ranked.len()
                     );
-
-                    // Execute the trade
-                    if let Err(e) = self.execute_arbitrage(&opportunity).await {
-                        warn!("❌ Execution failed: {}", e);
-                        self.stats.failed_executions += 1;
-                        self.stats.consecutive_failures += 1;
-                    } else {
-                        self.stats.opportunities_executed += 1;
-                        self.stats.daily_trades += 1;
-                        self.stats.consecutive_failures = 0;
-                        info!("✅ Arbitrage executed successfully");
+                }
+            } else if self.config.max_concurrent_opportunities > 1 {
+                let mut cross_dex = Vec::new();
+                let mut triangles = Vec::new();
+                for candidate in ranked {
+                    match candidate {
+                        RankedOpportunity::CrossDex(opportunity) => cross_dex.push(opportunity),
+                        triangle => triangles.push(triangle),
                     }
+                }
+                self.execute_non_conflicting_opportunities(cross_dex).await;
+                self.execute_best_opportunity(triangles).await;
+            } else {
+                self.execute_best_opportunity(ranked).await;
+            }
 
-                    // CRITICAL: Only execute FIRST opportunity per scan
-                    // This ensures fresh data every 1.5s (synced with JITO rate limit)
-                    break;
+            // Track which ShredStream endpoint prices come from (changes on failover)
+            let endpoint = self.shredstream_client.active_endpoint();
+            if self.stats.shredstream_endpoint.as_deref() != Some(endpoint) {
+                info!("📡 ShredStream endpoint: {}", endpoint);
+                self.stats.shredstream_endpoint = Some(endpoint.to_string());
+            }
+
+            // Track which JITO region bundles go to (changes on probe or failover)
+            if let Some(ref client) = self.jito_client {
+                let endpoint = client.selected_endpoint();
+                if self.stats.jito_endpoint.as_deref() != Some(endpoint.as_str()) {
+                    info!("🌐 JITO endpoint: {}", endpoint);
+                    self.stats.jito_endpoint = Some(endpoint);
                 }
             }
 
+            // Dynamic fee reserve grows with in-flight tips
+            let capital = self.position_tracker.get_stats();
+            self.stats.fee_reserve_sol = capital.fee_reserve_sol;
+            self.stats.in_flight_tips_sol = capital.in_flight_tips_sol;
+
+            // Publish latest stats to Prometheus exporter
+            self.metrics.update_from_stats(&self.stats);
+            if let Some(ref rpc) = self.rpc_client {
+                let (hits, misses) = rpc.account_cache_stats();
+                self.metrics.update_account_cache(hits, misses);
+
+                let rate_limit = rpc.rate_limit_stats();
+                self.stats.rpc_rate_limited_calls = rate_limit.throttled_calls;
+                self.stats.rpc_rate_limit_rejections = rate_limit.rejected_calls;
+                self.stats.rpc_rate_limit_wait_ms = rate_limit.wait_ms;
+            }
+
+            // Persist stats so daily limits survive restarts
+            let fingerprint = self.stats_fingerprint();
+            if fingerprint != last_persisted {
+                self.persist_stats();
+                last_persisted = fingerprint;
+            }
+
             // Report stats periodically
             if self
                 .stats
@@ -686,15 +2059,100 @@ impl ArbitrageEngine {
                 self.report_stats();
             }
 
-            // Scan interval synced with JITO rate limit
-            // This ensures each scan produces fresh data that can be submitted immediately
-            // JITO limit: 1 bundle per 1.1s, scan interval ensures fresh opportunities
-            sleep(Duration::from_millis(SCAN_INTERVAL_MS)).await;
+            // Per-token min-spread advice from the spread history
+            if last_spread_analysis.elapsed() >= Duration::from_secs(SPREAD_ANALYSIS_INTERVAL_SECS)
+            {
+                self.analyze_spreads();
+                last_spread_analysis = Instant::now();
+            }
+
+            // Adaptive scan interval, never below the JITO rate limit floor
+            // JITO limit: 1 bundle per JITO_BUNDLE_RATE_MS, so busier markets scan down to the floor
+            let found = self.stats.opportunities_detected > detected_before_scan;
+            let next_scan = scan_interval.on_scan(found);
+            if self.stats.scan_interval_ms != scan_interval.current_ms {
+                debug!(
+                    "⏱️ Scan interval {}ms → {}ms ({})",
+                    self.stats.scan_interval_ms,
+                    scan_interval.current_ms,
+                    if found {
+                        "opportunities found"
+                    } else {
+                        "empty scan"
+                    }
+                );
+                self.stats.scan_interval_ms = scan_interval.current_ms;
+            }
+            sleep(jittered_scan_delay(
+                next_scan,
+                self.config.scan_jitter_ms,
+                fastrand::f64(),
+            ))
+            .await;
         }
 
+        self.persist_stats();
+
         Ok(())
     }
 
+    /// Snapshot of the persisted fields, used to skip redundant writes
+    fn stats_fingerprint(&self) -> (u64, u64, u64, u64, u64, u64, u64, u64, String) {
+        (
+            self.stats.opportunities_detected,
+            self.stats.opportunities_executed,
+            self.stats.failed_executions,
+            self.stats.daily_trades,
+            self.stats.consecutive_failures,
+            self.stats.total_profit_sol.to_bits(),
+            self.stats.daily_loss_sol.to_bits(),
+            self.stats.daily_tips_spent_sol.to_bits(),
+            self.stats.stats_date.clone(),
+        )
+    }
+
+    /// Save stats to disk (failures are logged, never fatal)
+    fn persist_stats(&self) {
+        if let Err(e) = self.stats.save_to_file(STATS_FILE_PATH) {
+            warn!("⚠️ Failed to persist stats: {}", e);
+        }
+    }
+
+    /// Load RPC-derived pool prices into the price cache (ShredStream fallback)
+    ///
+    /// Prices target-token pools from the pool registry; detectors then read
+    /// them like ShredStream prices.
+    ///
+    /// # Returns
+    /// True if any prices were loaded (scanning can proceed)
+    fn load_rpc_fallback_prices(&self) -> bool {
+        let Some(ref feed) = self.rpc_price_feed else {
+            return false;
+        };
+
+        let token_list = self.token_list.current();
+        match feed.fetch_prices(token_list.allow.as_ref()) {
+            Ok(prices) if !prices.is_empty() => {
+                let count = self.shredstream_client.merge_prices(prices);
+                info!(
+                    "🛰️ ShredStream unavailable - using {} RPC-sourced pool prices",
+                    count
+                );
+                true
+            }
+            Ok(_) => {
+                debug!(
+                    "🛰️ RPC price fallback: no reserve-priced pools registered for target tokens"
+                );
+                false
+            }
+            Err(e) => {
+                warn!("⚠️ RPC price fallback failed: {}", e);
+                false
+            }
+        }
+    }
+
     /// Scan for arbitrage opportunities
     async fn scan_for_opportunities(&self) -> Vec<ArbitrageOpportunity> {
         // CYCLE-6: Performance benchmark timing
@@ -703,42 +2161,45 @@ impl ArbitrageEngine {
         let mut opportunities = Vec::new();
 
         // NEW: Target token filtering to avoid ghost pools
-        // Get target tokens from environment variable (comma-separated list)
-        let target_tokens = std::env::var("TARGET_TOKENS").ok().map(|s| {
-            s.split(',')
-                .map(|t| t.trim().to_string())
-                .filter(|t| !t.is_empty())
-                .collect::<Vec<_>>()
-        });
+        // Allowlist/denylist from TOKEN_LIST_FILE (hot-reloaded) or TARGET_TOKENS
+        let token_list = self.token_list.current();
 
         // Fetch all prices from ShredStream
         let all_prices_unfiltered = self.shredstream_client.get_all_prices();
+        let unfiltered_count = all_prices_unfiltered.len();
 
-        // Filter by target tokens if specified
-        let all_prices: HashMap<String, TokenPrice> = if let Some(ref tokens) = target_tokens {
-            all_prices_unfiltered
-                .into_iter()
-                .filter(|(_, price)| tokens.contains(&price.token_mint))
-                .collect()
-        } else {
-            all_prices_unfiltered
-        };
+        // Drop denylisted and non-allowlisted mints, and disabled DEXs, before grouping
+        let all_prices: HashMap<String, TokenPrice> = all_prices_unfiltered
+            .into_iter()
+            .filter(|(_, price)| token_list.is_allowed(&price.token_mint))
+            .filter(|(_, price)| self.config.dex_filter.allows_feed(&price.dex))
+            .collect();
 
         // Log filtering results
-        if let Some(ref tokens) = target_tokens {
-            info!(
-                "🎯 Target token filtering: {} prices (from {} target tokens)",
-                all_prices.len(),
-                tokens.len()
-            );
+        if let Some(ref tokens) = token_list.allow {
+            if self.config.log_verbosity.logs_opportunities() {
+                info!(
+                    "🎯 Target token filtering: {} prices (from {} target tokens)",
+                    all_prices.len(),
+                    tokens.len()
+                );
+            }
             debug!(
                 "🎯 Target tokens: {:?}",
                 tokens
                     .iter()
-                    .map(|t| t.get(..8).unwrap_or(t))
+                    .map(|t| self.token_metadata.label(t))
                     .collect::<Vec<_>>()
             );
         }
+        if !token_list.deny.is_empty() {
+            debug!(
+                "🚫 Token denylist: {} mints, {} of {} prices kept",
+                token_list.deny.len(),
+                all_prices.len(),
+                unfiltered_count
+            );
+        }
 
         // Group prices by token
         let mut token_prices: HashMap<String, Vec<&TokenPrice>> = HashMap::new();
@@ -749,113 +2210,179 @@ impl ArbitrageEngine {
                 .push(price);
         }
 
+        // Pool SOL reserves looked up this scan (None = depth unknown)
+        let mut pool_reserves: HashMap<String, Option<u64>> = HashMap::new();
+
         // Find arbitrage opportunities for each token
-        for (token_mint, prices) in token_prices {
-            if prices.len() < 2 {
+        for (token_mint, mut prices) in token_prices {
+            if prices.len() < self.config.min_dexes_per_token {
+                self.rejections.record(RejectionReason::TooFewPools);
                 continue; // Need at least 2 DEXs for arbitrage
             }
 
-            // Volume filter - FIXED decimal issue, now re-enabled
-            // Check minimum volume to avoid illiquid tokens
-            let total_volume_24h: f64 = prices.iter().map(|p| p.volume_24h).sum();
-            if total_volume_24h < MIN_VOLUME_SOL {
-                debug!(
-                    "⚠️ Skipping low volume token {}: {:.2} SOL/24h (min: {} SOL)",
-                    token_mint.get(..8).unwrap_or(&token_mint),
-                    total_volume_24h,
-                    MIN_VOLUME_SOL
-                );
-                continue;
+            // Sudden prices on 15+ pools usually means a spam/wash-traded token with fake spreads
+            if let Some(max_dexes) = self.config.max_dexes_per_token {
+                if prices.len() > max_dexes {
+                    self.rejections.record(RejectionReason::TooManyPools);
+                    self.flag_suspicious_token(&token_mint, prices.len(), max_dexes);
+                    continue;
+                }
             }
 
-            // Find lowest and highest prices
-            let mut min_price = f64::MAX;
-            let mut max_price = 0.0;
-            let mut buy_dex = String::new();
-            let mut sell_dex = String::new();
-            // GHOST POOL FIX: Track full pool addresses
-            let mut buy_pool_address = String::new();
-            let mut sell_pool_address = String::new();
-
-            for price in &prices {
-                if price.price_sol < min_price {
-                    min_price = price.price_sol;
-                    buy_dex = price.dex.clone();
-                    buy_pool_address = price.pool_address.clone(); // GHOST POOL FIX
-                }
-                if price.price_sol > max_price {
-                    max_price = price.price_sol;
-                    sell_dex = price.dex.clone();
-                    sell_pool_address = price.pool_address.clone(); // GHOST POOL FIX
+            // Per-DEX liquidity floor: strict on shallow DEXs (PumpSwap), lenient on deep AMMs
+            prices.retain(|price| {
+                let min_liquidity_sol = self.dex_registry.min_pool_liquidity_sol(&price.dex);
+                let liquid = price.volume_24h >= min_liquidity_sol;
+                if !liquid {
+                    debug!(
+                        "⚠️ Skipping low liquidity pool {} for {}: {:.2} SOL/24h (min: {} SOL)",
+                        price.dex,
+                        self.token_metadata.label(&token_mint),
+                        price.volume_24h,
+                        min_liquidity_sol
+                    );
                 }
+                liquid
+            });
+            if prices.len() < 2 {
+                self.rejections.record(RejectionReason::LowVolume);
+                continue;
             }
 
-            // Calculate spread
-            if min_price > 0.0 && max_price > 0.0 {
-                let spread_percentage = ((max_price - min_price) / min_price) * 100.0;
+            // Enumerate every executable (buy, sell) pool pairing, not just the price extremes
+            // Extreme pair is often an illiquid ghost pool - ranking by net profit avoids that
+            let pairs = candidate_pairs(&prices);
+            if pairs.is_empty() {
+                self.rejections.record(RejectionReason::SameDexFamily);
+                continue; // All pools are the same DEX family
+            }
 
-                // Sanity check: reject unrealistic spreads (likely bad price data)
-                // Grok fix: Skip same-pool-type arbitrage (not executable)
-                // Different pool types within same DEX (e.g., Meteora DAMM variants) aren't arbitrageable
-                if buy_dex.starts_with(&sell_dex[..sell_dex.find('_').unwrap_or(sell_dex.len())])
-                    && sell_dex.starts_with(&buy_dex[..buy_dex.find('_').unwrap_or(buy_dex.len())])
-                {
-                    continue; // Skip same-DEX different pools
+            // DYNAMIC PROFITABILITY CALCULATION (2025-10-11)
+            // Position size is the same for every pairing of this token (per-token override or global)
+            let position_size_lamports = self.position_lamports_for(&[&token_mint]);
+            let tip_floor = self.jito_tip_floor.read().await;
+
+            let max_price_age = Duration::from_millis(self.config.max_price_age_ms);
+
+            let mut token_opportunities = Vec::new();
+            for (buy, sell) in pairs {
+                // A fresh quote against a stale one is a false spread
+                if !is_price_pair_fresh(buy, sell, max_price_age) {
+                    debug!(
+                        "⏰ Rejecting stale pairing for {}: {} quote {}ms old, {} quote {}ms old",
+                        self.token_metadata.label(&token_mint),
+                        buy.dex,
+                        buy.age().as_millis(),
+                        sell.dex,
+                        sell.age().as_millis()
+                    );
+                    self.rejections.record(RejectionReason::StalePrice);
+                    continue;
                 }
 
+                let spread_percentage = ((sell.price_sol - buy.price_sol) / buy.price_sol) * 100.0;
+
                 // Log ALL spreads above threshold for debugging (Grok: find real opportunities)
-                if spread_percentage > LOG_SPREAD_THRESHOLD_PCT {
+                if self.config.log_verbosity.logs_spreads()
+                    && spread_percentage > self.config.log_spread_threshold_pct
+                {
+                    let source = if real_price_feed::is_rpc_sourced(buy)
+                        || real_price_feed::is_rpc_sourced(sell)
+                    {
+                        " [RPC prices]"
+                    } else {
+                        ""
+                    };
                     info!(
-                        "💡 Found spread: {:.2}% for {} | Buy: {} @ {:.6} | Sell: {} @ {:.6}",
+                        "💡 Found spread: {:.2}% for {} | Buy: {} @ {:.6} | Sell: {} @ {:.6}{}",
                         spread_percentage,
-                        token_mint.get(..8).unwrap_or(&token_mint),
-                        buy_dex,
-                        min_price,
-                        sell_dex,
-                        max_price
+                        self.token_metadata.label(&token_mint),
+                        buy.dex,
+                        buy.price_sol,
+                        sell.dex,
+                        sell.price_sol,
+                        source
                     );
                 }
 
-                // Grok fix: Raise threshold for volatile memecoins
-                if spread_percentage > MAX_REALISTIC_SPREAD_PCT {
+                // Sanity check: reject unrealistic spreads (likely bad price data)
+                // Cap depends on the token's tier: strict for stables, loose for thin memecoins
+                let max_realistic_spread_pct = self.config.spread_tiers.max_spread_pct(
+                    &token_mint,
+                    buy.volume_24h,
+                    sell.volume_24h,
+                );
+                if spread_percentage > max_realistic_spread_pct {
                     debug!(
-                        "⚠️ Rejecting unrealistic spread: {:.2}% for {} ({} @ {:.6} vs {} @ {:.6})",
+                        "⚠️ Rejecting unrealistic spread: {:.2}% for {} (tier max {}%, {} @ {:.6} vs {} @ {:.6})",
                         spread_percentage,
-                        token_mint.get(..8).unwrap_or(&token_mint),
-                        buy_dex,
-                        min_price,
-                        sell_dex,
-                        max_price
+                        self.token_metadata.label(&token_mint),
+                        max_realistic_spread_pct,
+                        buy.dex,
+                        buy.price_sol,
+                        sell.dex,
+                        sell.price_sol
                     );
+                    self.rejections.record(RejectionReason::UnrealisticSpread);
                     continue;
                 }
 
-                // DYNAMIC PROFITABILITY CALCULATION (2025-10-11)
-                // Calculate position size and expected gross profit
-                let position_size_sol = self
-                    .config
-                    .max_position_size_sol
-                    .min(self.config.capital_sol);
-                let position_size_lamports = (position_size_sol * 1_000_000_000.0) as u64;
-                let gross_profit_sol = position_size_sol * (spread_percentage / 100.0);
-                let gross_profit_lamports = (gross_profit_sol * 1_000_000_000.0) as u64;
-
-                // Calculate ALL costs FIRST (JITO tip + gas + DEX fees) using dynamic tip floor
-                let tip_floor = self.jito_tip_floor.read().await;
-                let costs = ArbitrageCosts::calculate(
-                    position_size_lamports,
-                    gross_profit_lamports,
-                    true,
-                    Some(&*tip_floor),
-                );
+                // Calculate expected gross profit and ALL costs (JITO tip + gas + DEX fees)
+                let (mut gross_profit_lamports, mut costs, mut min_required_spread_percentage) =
+                    pair_economics(
+                        position_size_lamports,
+                        spread_percentage,
+                        &tip_floor,
+                        &self.config.tip_strategy,
+                    );
+
+                // LIQUIDITY DEPTH: shrink the position so neither pool moves more than
+                // MAX_PRICE_IMPACT_PCT. Only pairs profitable at full size pay for the lookup.
+                let mut pair_position_lamports = position_size_lamports;
+                if spread_percentage >= min_required_spread_percentage {
+                    let capped = self.depth_capped_position(
+                        buy,
+                        sell,
+                        position_size_lamports,
+                        &mut pool_reserves,
+                    );
+                    if capped < position_size_lamports {
+                        pair_position_lamports = capped;
+                        (gross_profit_lamports, costs, min_required_spread_percentage) =
+                            pair_economics(
+                                capped,
+                                spread_percentage,
+                                &tip_floor,
+                                &self.config.tip_strategy,
+                            );
+                    }
+                }
+                let gross_profit_sol = gross_profit_lamports as f64 / 1_000_000_000.0;
+                let pair_position_sol = pair_position_lamports as f64 / 1_000_000_000.0;
 
-                // Calculate DYNAMIC minimum spread required
-                // Formula: min_spread = (total_costs + margin) / position_size
-                // Margin = 0.2% of gross profit for safety buffer
-                let margin_lamports = (gross_profit_lamports as f64 * 0.002) as u64; // 0.2% margin
-                let min_required_spread_lamports = costs.total_cost_lamports + margin_lamports;
-                let min_required_spread_percentage =
-                    (min_required_spread_lamports as f64 / position_size_lamports as f64) * 100.0;
+                // Spread history for per-token min-spread advice (AUTO_TUNE_SPREAD)
+                if let Ok(mut analyzer) = self.spread_analyzer.lock() {
+                    analyzer.record(
+                        &token_mint,
+                        spread_percentage,
+                        min_required_spread_percentage,
+                        Instant::now(),
+                    );
+                }
+                if let Some(&tuned_min_spread) = self.spread_overrides.get(&token_mint) {
+                    if spread_percentage < tuned_min_spread {
+                        debug!(
+                            "🎛️ Spread below tuned minimum: {} ({} → {}) - {:.2}% < {:.2}%",
+                            self.token_metadata.label(&token_mint),
+                            buy.dex,
+                            sell.dex,
+                            spread_percentage,
+                            tuned_min_spread
+                        );
+                        self.rejections.record(RejectionReason::SpreadTooLow);
+                        continue;
+                    }
+                }
 
                 // Check if spread meets DYNAMIC minimum threshold
                 if spread_percentage >= min_required_spread_percentage {
@@ -863,11 +2390,28 @@ impl ArbitrageEngine {
                     let net_profit_lamports = costs.net_profit(gross_profit_lamports);
                     let net_profit_sol = net_profit_lamports as f64 / 1_000_000_000.0;
 
+                    // Absolute floor: clearing costs by a few hundred lamports isn't worth the risk
+                    let profit_floor = self.config.net_profit_floor_lamports();
+                    if !costs.meets_net_profit_floor(gross_profit_lamports, profit_floor) {
+                        debug!(
+                            "🪙 Net profit below floor: {} ({} → {}) - {:.6} SOL < {:.6} SOL (spread {:.2}% passed)",
+                            self.token_metadata.label(&token_mint),
+                            buy.dex,
+                            sell.dex,
+                            net_profit_sol,
+                            profit_floor as f64 / 1e9,
+                            spread_percentage
+                        );
+                        self.rejections.record(RejectionReason::NetProfitBelowFloor);
+                        continue;
+                    }
+
                     // Log cost breakdown for transparency
-                    let (_gas_pct, _tip_pct) = costs.gas_tip_ratio();
                     debug!(
-                        "✅ PROFITABLE: {} - Spread {:.2}% >= {:.2}% required",
-                        token_mint.get(..8).unwrap_or(&token_mint),
+                        "✅ PROFITABLE: {} ({} → {}) - Spread {:.2}% >= {:.2}% required",
+                        self.token_metadata.label(&token_mint),
+                        buy.dex,
+                        sell.dex,
                         spread_percentage,
                         min_required_spread_percentage
                     );
@@ -885,332 +2429,724 @@ impl ArbitrageEngine {
                         (costs.base_tx_fee_lamports + costs.compute_fee_lamports) as f64 / 1e9
                     );
 
-                    opportunities.push(ArbitrageOpportunity {
-                        token_mint,
-                        buy_dex,
-                        sell_dex,
-                        buy_price: min_price,
-                        sell_price: max_price,
+                    token_opportunities.push(ArbitrageOpportunity {
+                        token_mint: token_mint.clone(),
+                        buy_dex: buy.dex.clone(),
+                        sell_dex: sell.dex.clone(),
+                        buy_price: buy.price_sol,
+                        sell_price: sell.price_sol,
                         spread_percentage,
                         estimated_profit_sol: net_profit_sol,
+                        gross_profit_sol,
+                        required_spread_percentage: min_required_spread_percentage,
+                        position_size_lamports: pair_position_lamports,
                         // GHOST POOL FIX: Pass full addresses from ShredStream
-                        buy_pool_address: buy_pool_address.clone(),
-                        sell_pool_address: sell_pool_address.clone(),
+                        buy_pool_address: buy.pool_address.clone(),
+                        sell_pool_address: sell.pool_address.clone(),
                         // NEW (2025-10-11): Record detection time for staleness check
                         detected_at: Instant::now(),
+                        observed_slot: buy.slot.min(sell.slot),
                     });
                 } else {
-                    debug!("⚠️ Spread too low: {} - {:.2}% < {:.2}% required (Position: {:.2} SOL, Costs: {:.6} SOL)",
-                           token_mint.get(..8).unwrap_or(&token_mint), spread_percentage, min_required_spread_percentage,
-                           position_size_sol, costs.total_cost_lamports as f64 / 1e9);
+                    // Expected value of taking the trade anyway (negative = guaranteed loss)
+                    let expected_value_sol =
+                        (gross_profit_lamports as f64 - costs.total_cost_lamports as f64) / 1e9;
+                    debug!("⚠️ Spread too low: {} ({} → {}) - {:.2}% < {:.2}% required (Position: {:.2} SOL, Costs: {:.6} SOL, EV: {:+.6} SOL)",
+                           self.token_metadata.label(&token_mint), buy.dex, sell.dex, spread_percentage,
+                           min_required_spread_percentage, pair_position_sol, costs.total_cost_lamports as f64 / 1e9,
+                           expected_value_sol);
+                    // Profitable at full size but not at what the pools can absorb
+                    self.rejections
+                        .record(if pair_position_lamports < position_size_lamports {
+                            RejectionReason::ShallowLiquidity
+                        } else {
+                            RejectionReason::SpreadTooLow
+                        });
                 }
             }
+
+            // Keep the top-N pairings by net profit
+            token_opportunities.sort_by(|a, b| {
+                b.estimated_profit_sol
+                    .partial_cmp(&a.estimated_profit_sol)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            token_opportunities.truncate(self.config.max_pairs_per_token);
+            opportunities.extend(token_opportunities);
         }
 
         // CYCLE-6: Log scan performance
         let scan_duration = scan_start.elapsed();
-        info!(
-            "⚡ Scan complete in {:?} ({} opportunities found)",
-            scan_duration,
-            opportunities.len()
-        );
+        self.metrics.observe_scan_duration(scan_duration);
+        if self.config.log_verbosity.logs_opportunities() {
+            info!(
+                "⚡ Scan complete in {:?} ({} opportunities found)",
+                scan_duration,
+                opportunities.len()
+            );
+        }
 
         opportunities
     }
 
+    /// Cap a pairing's position to what both pools absorb within MAX_PRICE_IMPACT_PCT
+    ///
+    /// Pools whose depth can't be read (no swap executor, DEX without reserve
+    /// parsing, RPC error) don't constrain the size.
+    ///
+    /// # Arguments
+    /// * `buy` / `sell` - Prices (pool + DEX) of the pairing
+    /// * `position_lamports` - Configured position size
+    /// * `reserves` - Per-scan cache of pool SOL reserves by pool address
+    ///
+    /// # Returns
+    /// Position size in lamports (0 if a pool is empty)
+    fn depth_capped_position(
+        &self,
+        buy: &TokenPrice,
+        sell: &TokenPrice,
+        position_lamports: u64,
+        reserves: &mut HashMap<String, Option<u64>>,
+    ) -> u64 {
+        let Some(ref executor) = self.swap_executor else {
+            return position_lamports;
+        };
+
+        let depths: Vec<u64> = [buy, sell]
+            .iter()
+            .filter_map(|price| {
+                *reserves
+                    .entry(price.pool_address.clone())
+                    .or_insert_with(|| {
+                        let dex_type = DexType::from_dex_string(&price.dex).ok()?;
+                        let pool = price.pool_address.parse().ok()?;
+                        executor
+                            .pool_sol_reserve(&dex_type, &pool)
+                            .inspect_err(|e| debug!("🌊 Depth unknown for {}: {}", price.dex, e))
+                            .ok()
+                    })
+            })
+            .collect();
+
+        let capped = slippage::depth_capped_position(
+            position_lamports,
+            &depths,
+            self.config.max_price_impact_pct,
+        );
+        if capped < position_lamports {
+            debug!(
+                "🌊 Shallow pool: {} → {} position capped {:.4} → {:.4} SOL ({:.2}% max impact)",
+                buy.dex,
+                sell.dex,
+                position_lamports as f64 / 1e9,
+                capped as f64 / 1e9,
+                self.config.max_price_impact_pct
+            );
+        }
+        capped
+    }
+
     /// Execute arbitrage trade
-    async fn execute_arbitrage(&mut self, opportunity: &ArbitrageOpportunity) -> Result<()> {
-        if self.config.paper_trading {
-            // Paper trading - simulate execution
-            info!("📝 Paper trading: Simulating arbitrage execution");
+    ///
+    /// Paper trading simulates the fill. Real trading builds both legs into one
+    /// atomic JITO bundle via the triangle path, so every DEX pair the swap
+    /// executor supports is traded (not just Meteora).
+    async fn execute_arbitrage(&mut self, opportunity: &ArbitrageOpportunity) -> ArbResult<()> {
+        // HIGH-4 FIX: Reserve capital before execution (same ledger as triangles)
+        let position_size_lamports = reserve_position(
+            &self.position_tracker,
+            &[&opportunity.token_mint],
+            self.config.capital_sol,
+        )?;
+
+        // Released when execution ends, or by the queued bundle once it resolves
+        self.execute_reserved_arbitrage(opportunity, position_size_lamports)
+            .await
+    }
 
-            // Use consistent RNG for paper trading simulation
-            use rand::Rng;
-            let success = rand::thread_rng().gen_bool(0.9); // 90% success rate
+    /// Execute a cross-DEX opportunity whose capital the caller already reserved
+    ///
+    /// Takes over the reservation (see `execute_triangle_opportunity`).
+    async fn execute_reserved_arbitrage(
+        &mut self,
+        opportunity: &ArbitrageOpportunity,
+        reserved_lamports: u64,
+    ) -> ArbResult<()> {
+        if !self.config.paper_trading {
+            return self
+                .execute_triangle_opportunity(&opportunity.as_two_leg_triangle(), reserved_lamports)
+                .await;
+        }
 
-            if success {
-                // Record profit
-                self.stats.total_profit_sol += opportunity.estimated_profit_sol;
-                info!(
-                    "💰 Paper profit: {:.6} SOL (Total: {:.6} SOL)",
-                    opportunity.estimated_profit_sol, self.stats.total_profit_sol
-                );
-                Ok(())
-            } else {
-                Err(anyhow::anyhow!(
-                    "Paper trading: Simulated execution failure"
-                ))
+        let executor = self.two_leg_executor();
+        let (outcome, result) = executor.execute(opportunity).await;
+        executor.log_trade(opportunity, &outcome, &result);
+        outcome.apply(&mut self.stats);
+        self.position_tracker.release_capital(reserved_lamports);
+        result
+    }
+
+    /// Pre-execution checks for a detected opportunity (profitability, staleness, token safety)
+    ///
+    /// # Returns
+    /// True if the opportunity should be executed (logs its details)
+    fn admit_opportunity(&mut self, opportunity: &ArbitrageOpportunity) -> bool {
+        // Double-check profitability (opportunities should already be filtered)
+        if !self
+            .config
+            .is_profitable_after_fees(opportunity.estimated_profit_sol)
+        {
+            return false;
+        }
+
+        self.stats.opportunities_detected += 1;
+
+        // NEW (2025-10-11): Early staleness detection (Option 4)
+        // Skip opportunities older than threshold to avoid wasting time building instructions.
+        // Slot age is immune to local clock drift, so it wins whenever both slots are known.
+        let age = opportunity.detected_at.elapsed();
+        let slot_age = opportunity_slot_age(
+            opportunity.observed_slot,
+            self.shredstream_client.latest_slot(),
+        );
+        let is_stale = match slot_age {
+            Some(slots) => slots > self.config.max_opportunity_slot_age,
+            None => age > Duration::from_millis(self.config.stale_opportunity_threshold_ms),
+        };
+        if is_stale {
+            match slot_age {
+                Some(slots) => warn!(
+                    "⏰ Skipping stale opportunity ({} slots behind, max {}) - would fail simulation anyway",
+                    slots, self.config.max_opportunity_slot_age
+                ),
+                None => warn!(
+                    "⏰ Skipping stale opportunity (age: {}ms) - would fail simulation anyway",
+                    age.as_millis()
+                ),
             }
-        } else {
-            // CYCLE-7: Real trading with MANDATORY simulation (Grok recommendation)
-            // Execute two-leg arbitrage: Buy low → Sell high
-            info!("💰 Executing REAL arbitrage trade");
-            info!(
-                "   Buy {} @ {:.6} SOL on {}",
-                &opportunity.token_mint[..8],
-                opportunity.buy_price,
-                opportunity.buy_dex
-            );
-            info!(
-                "   Sell {} @ {:.6} SOL on {}",
-                &opportunity.token_mint[..8],
-                opportunity.sell_price,
-                opportunity.sell_dex
+            debug!(
+                "   Token: {} - detected {}ms ago, likely stale pool state",
+                self.token_metadata.label(&opportunity.token_mint),
+                age.as_millis()
             );
+            self.rejections.record(RejectionReason::Stale);
+            return false; // Skip to next opportunity immediately
+        }
+
+        if self.token_on_cooldown(&[&opportunity.token_mint]) {
+            return false;
+        }
 
-            // Safety check: Ensure swap executor exists
-            let _swap_executor = self
-                .swap_executor
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("Swap executor not initialized for real trading"))?;
+        if self.overlaps_in_flight(
+            &[&opportunity.token_mint],
+            &[&opportunity.buy_dex, &opportunity.sell_dex],
+        ) {
+            return false;
+        }
 
-            // Safety check: Ensure wallet exists
-            let wallet = self
-                .wallet_keypair
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("Wallet not loaded for real trading"))?;
+        if !self.mints_pass_safety_check(&[&opportunity.token_mint]) {
+            return false;
+        }
 
-            warn!("⚠️ REAL MONEY TRADING - This will execute actual on-chain transactions!");
-            warn!("   Wallet: {}", wallet.pubkey());
-            warn!(
-                "   Estimated profit: {:.6} SOL",
+        structured_log::opportunity_detected(
+            &opportunity.token_mint,
+            &format!("{}→{}", opportunity.buy_dex, opportunity.sell_dex),
+            opportunity.spread_percentage,
+            opportunity.estimated_profit_sol,
+        );
+        if self.config.log_verbosity.logs_opportunities() {
+            info!(
+                "🎯 Arbitrage opportunity found (age: {}ms):",
+                age.as_millis()
+            );
+            info!(
+                "   Token: {}",
+                self.token_metadata.label(&opportunity.token_mint)
+            );
+            info!(
+                "   Buy: {} @ {:.6} SOL",
+                opportunity.buy_dex, opportunity.buy_price
+            );
+            info!(
+                "   Sell: {} @ {:.6} SOL",
+                opportunity.sell_dex, opportunity.sell_price
+            );
+            info!("   Spread: {:.2}%", opportunity.spread_percentage);
+            info!(
+                "   Est. Profit: {:.6} SOL",
                 opportunity.estimated_profit_sol
             );
+        }
 
-            // LIVE TRADING ENABLED - Pool addresses now available from ShredStream
-            // All safety systems active: slippage protection, mandatory simulation, circuit breakers
-
-            // GHOST POOL FIX: Use full addresses directly from opportunity (no need to search)
-            let buy_pool_address = &opportunity.buy_pool_address;
-            let sell_pool_address = &opportunity.sell_pool_address;
-
-            // Parse pool addresses
-            let buy_pool_pubkey = buy_pool_address
-                .parse::<solana_sdk::pubkey::Pubkey>()
-                .context("Invalid buy pool address")?;
+        true
+    }
 
-            let sell_pool_pubkey = sell_pool_address
-                .parse::<solana_sdk::pubkey::Pubkey>()
-                .context("Invalid sell pool address")?;
+    /// Snapshot of what a two-leg execution needs from the engine
+    fn two_leg_executor(&self) -> TwoLegExecutor {
+        TwoLegExecutor {
+            paper_trading: self.config.paper_trading,
+            paper_seed: self.config.paper_seed,
+            trade_log: self.trade_log.clone(),
+        }
+    }
 
-            // CRITICAL: Validate pools exist on-chain (ghost pool protection)
-            if let Some(ref rpc) = self.rpc_client {
-                debug!("🔍 Validating pool states on-chain...");
+    /// Position size for a trade through `mints`, within total capital
+    ///
+    /// GROK FIX (2025-10-07): Detection and execution size from the same value.
+    /// Per-token overrides (TOKEN_POSITION_OVERRIDES) replace the global max.
+    fn position_lamports_for(&self, mints: &[&str]) -> u64 {
+        position_size_lamports(&self.position_tracker, mints, self.config.capital_sol)
+    }
 
-                match rpc.get_account_data(&buy_pool_pubkey) {
-                    Ok(data) if data.len() > 100 => {
-                        debug!("✅ Buy pool valid: {} bytes", data.len());
-                    }
-                    Ok(data) => {
-                        warn!(
-                            "👻 GHOST POOL: Buy pool {} has only {} bytes - skipping",
-                            buy_pool_address,
-                            data.len()
-                        );
-                        return Err(anyhow::anyhow!(
-                            "Buy pool is ghost pool (insufficient data)"
-                        ));
+    /// Execute the first candidate of a best-first list that passes its checks
+    ///
+    /// Candidates rejected for staleness, token safety or capital fall through
+    /// to the next most profitable one. At most one trade is attempted.
+    async fn execute_best_opportunity(&mut self, ranked: Vec<RankedOpportunity>) {
+        for candidate in ranked {
+            match candidate {
+                RankedOpportunity::CrossDex(opportunity) => {
+                    if !self.admit_opportunity(&opportunity) {
+                        continue;
                     }
-                    Err(e) => {
-                        warn!(
-                            "👻 GHOST POOL: Buy pool {} doesn't exist: {}",
-                            buy_pool_address, e
-                        );
-                        return Err(anyhow::anyhow!("Buy pool not found on-chain"));
+
+                    // Execute the trade (reserves capital, so it can be refused)
+                    let result = self.execute_arbitrage(&opportunity).await;
+                    if let Err(ArbError::InsufficientCapital(ref e)) = result {
+                        warn!("⚠️ Insufficient capital for opportunity: {}", e);
+                        continue;
                     }
+                    self.mark_tokens_traded(&[&opportunity.token_mint]);
+                    self.record_execution_result(result);
                 }
-
-                match rpc.get_account_data(&sell_pool_pubkey) {
-                    Ok(data) if data.len() > 100 => {
-                        debug!("✅ Sell pool valid: {} bytes", data.len());
+                RankedOpportunity::Triangle(triangle) => {
+                    let path_mints: Vec<&str> = triangle.path.iter().map(String::as_str).collect();
+                    if self.token_on_cooldown(&path_mints) {
+                        continue;
                     }
-                    Ok(data) => {
-                        warn!(
-                            "👻 GHOST POOL: Sell pool {} has only {} bytes - skipping",
-                            sell_pool_address,
-                            data.len()
-                        );
-                        return Err(anyhow::anyhow!(
-                            "Sell pool is ghost pool (insufficient data)"
-                        ));
+                    let route_dexs: Vec<&str> = triangle.dexs.iter().map(String::as_str).collect();
+                    if self.overlaps_in_flight(&path_mints, &route_dexs) {
+                        continue;
                     }
-                    Err(e) => {
-                        warn!(
-                            "👻 GHOST POOL: Sell pool {} doesn't exist: {}",
-                            sell_pool_address, e
-                        );
-                        return Err(anyhow::anyhow!("Sell pool not found on-chain"));
+                    if !self.mints_pass_safety_check(&path_mints) {
+                        continue;
+                    }
+
+                    // HIGH-4 FIX: Reserve capital before execution
+                    // Use the path's effective max position (per-token override or global)
+                    let position_size_lamports = match reserve_position(
+                        &self.position_tracker,
+                        &path_mints,
+                        self.config.capital_sol,
+                    ) {
+                        Ok(lamports) => lamports,
+                        Err(e) => {
+                            warn!("⚠️ Insufficient capital for triangle opportunity: {}", e);
+                            debug!("   Stats: {:?}", self.position_tracker.get_stats());
+                            continue;
+                        }
+                    };
+                    self.mark_tokens_traded(&path_mints);
+
+                    // Execute with JITO bundle (atomic execution) - takes over the reservation
+                    match self
+                        .execute_triangle_opportunity(&triangle, position_size_lamports)
+                        .await
+                    {
+                        Ok(()) => {
+                            info!("✅ Triangle opportunity executed successfully");
+                        }
+                        Err(e) => {
+                            debug!("⚠️ Triangle execution failed ({}): {}", e.kind(), e);
+                        }
                     }
                 }
             }
 
-            info!("📍 Pool addresses validated:");
-            info!("   Buy pool: {}", buy_pool_address);
-            info!("   Sell pool: {}", sell_pool_address);
+            // CRITICAL: Only execute ONE opportunity per scan
+            // This ensures fresh data every 1.5s (synced with JITO rate limit)
+            return;
+        }
+    }
+
+    /// Execute up to `max_concurrent_opportunities` non-conflicting opportunities this scan
+    ///
+    /// Opportunities are taken in order; one is skipped if it touches a pool an
+    /// earlier pick already uses, and selection stops once the position tracker
+    /// refuses to reserve more capital. Each pick releases its reservation when done
+    /// (a queued bundle holds it until it lands or fails).
+    ///
+    /// Paper picks run concurrently, each on its own tokio task. Real picks are
+    /// NOT concurrent: they are built on the engine (shared swap executor and
+    /// JITO queue) one after another, and the submitter paces their bundles.
+    async fn execute_non_conflicting_opportunities(
+        &mut self,
+        opportunities: Vec<ArbitrageOpportunity>,
+    ) {
+        let max_concurrent = self.config.max_concurrent_opportunities;
+        let executor = self.two_leg_executor();
+        // Reserved by spawned workers and not yet released (panicked tasks never release)
+        let mut unreleased_lamports = 0u64;
+
+        let mut claims = PoolClaims::default();
+        let mut workers = tokio::task::JoinSet::new();
+        let mut picked = 0;
+
+        for opportunity in opportunities {
+            if picked >= max_concurrent {
+                break;
+            }
+            if !self.admit_opportunity(&opportunity) {
+                continue;
+            }
+            if !claims.try_claim(&opportunity) {
+                debug!(
+                    "⏭️ Skipping opportunity on {}: pool already in use this scan",
+                    self.token_metadata.label(&opportunity.token_mint)
+                );
+                self.rejections.record(RejectionReason::Duplicate);
+                continue;
+            }
+            let position_size_lamports = match reserve_position(
+                &self.position_tracker,
+                &[&opportunity.token_mint],
+                self.config.capital_sol,
+            ) {
+                Ok(lamports) => lamports,
+                Err(e) => {
+                    debug!("💼 No capital for more executions this scan: {}", e);
+                    break;
+                }
+            };
+            picked += 1;
+            // Later picks on the same token this scan are refused by admit_opportunity
+            self.mark_tokens_traded(&[&opportunity.token_mint]);
+
+            if !self.config.paper_trading {
+                // Queued bundles keep their reservation until they resolve
+                let result = self
+                    .execute_reserved_arbitrage(&opportunity, position_size_lamports)
+                    .await;
+                self.record_execution_result(result);
+                continue;
+            }
 
-            // Calculate position size in lamports
-            // GROK FIX (2025-10-07): Unify with detection path - use full capital
-            let position_size_sol = self
-                .config
-                .max_position_size_sol
-                .min(self.config.capital_sol);
-            let position_size_lamports = (position_size_sol * 1e9) as u64;
+            let executor = executor.clone();
+            let position_tracker = self.position_tracker.clone();
+            unreleased_lamports += position_size_lamports;
+            workers.spawn(async move {
+                let (outcome, result) = executor.execute(&opportunity).await;
+                executor.log_trade(&opportunity, &outcome, &result);
+                position_tracker.release_capital(position_size_lamports);
+                (outcome, result, position_size_lamports)
+            });
+        }
 
+        if workers.len() > 1 {
             info!(
-                "💰 Position size: {:.6} SOL ({} lamports)",
-                position_size_sol, position_size_lamports
+                "🧵 Paper trading {} opportunities concurrently",
+                workers.len()
             );
+        }
 
-            // CYCLE-7: Execute Meteora swap
-            if let (Some(rpc_client), Some(wallet_keypair)) =
-                (&self.rpc_client, &self.wallet_keypair)
-            {
-                // Check if both DEXs are Meteora (or compatible with lb_clmm)
-                let is_buy_meteora = opportunity.buy_dex.contains("Meteora");
-                let is_sell_meteora = opportunity.sell_dex.contains("Meteora");
+        while let Some(joined) = workers.join_next().await {
+            let (outcome, result) = match joined {
+                Ok((outcome, result, released_lamports)) => {
+                    unreleased_lamports -= released_lamports;
+                    (outcome, result)
+                }
+                Err(e) => {
+                    // Panicked task: its capital release never ran (released below)
+                    error!("❌ Execution task failed: {}", e);
+                    self.stats.failed_executions += 1;
+                    self.stats.consecutive_failures += 1;
+                    continue;
+                }
+            };
 
-                if is_buy_meteora || is_sell_meteora {
-                    info!("🚀 Executing Meteora arbitrage opportunity");
+            outcome.apply(&mut self.stats);
+            self.record_execution_result(result);
+        }
 
-                    // Execute buy swap (if Meteora)
-                    if is_buy_meteora {
-                        info!(
-                            "💰 Executing BUY on Meteora: {} @ {:.6} SOL",
-                            opportunity
-                                .token_mint
-                                .get(..8)
-                                .unwrap_or(&opportunity.token_mint),
-                            opportunity.buy_price
-                        );
+        if unreleased_lamports > 0 {
+            self.position_tracker.release_capital(unreleased_lamports);
+        }
+    }
 
-                        match meteora_swap::execute_meteora_swap(
-                            rpc_client.clone(),
-                            buy_pool_address,
-                            position_size_lamports,
-                            wallet_keypair,
-                            0.005,                          // 0.5% slippage tolerance
-                            true,                           // Swap X to Y (SOL to token)
-                            self.cached_blockhash.as_ref(), // Use pre-fetched blockhash
-                        )
-                        .await
-                        {
-                            Ok(signature) => {
-                                info!("✅ Buy executed: {}", signature);
-                                self.stats.opportunities_executed += 1;
-                            }
-                            Err(e) => {
-                                error!("❌ Buy failed: {}", e);
-                                self.stats.failed_executions += 1;
-                                self.stats.consecutive_failures += 1;
-                                return Err(e);
-                            }
-                        }
-                    }
+    /// Update failure tracking for a finished two-leg execution
+    ///
+    /// Executed trades are counted once, by the path that knows the trade
+    /// landed (paper fill, JITO reconcile or confirmed fallback), not here.
+    fn record_execution_result(&mut self, result: ArbResult<()>) {
+        match result {
+            Ok(()) => {
+                self.stats.consecutive_failures = 0;
+                info!("✅ Arbitrage execution finished");
+            }
+            // Paused/not-yet-active pool: skipped, not a failure
+            Err(ArbError::PoolNotActive(detail)) => {
+                debug!("⏸️ Execution skipped, pool not active: {}", detail);
+            }
+            Err(e) => {
+                warn!("❌ Execution failed ({}): {}", e.kind(), e);
+                self.stats.failed_executions += 1;
+                self.stats.consecutive_failures += 1;
+            }
+        }
+    }
 
-                    // Execute sell swap (if Meteora)
-                    if is_sell_meteora {
-                        info!(
-                            "💰 Executing SELL on Meteora: {} @ {:.6} SOL",
-                            opportunity
-                                .token_mint
-                                .get(..8)
-                                .unwrap_or(&opportunity.token_mint),
-                            opportunity.sell_price
-                        );
+    /// Check every mint an opportunity trades against the mint/freeze authority check
+    ///
+    /// # Returns
+    /// False (logged and recorded as a rejection) if any mint is unsafe or
+    /// could not be checked; always true when no RPC client is configured
+    fn mints_pass_safety_check(&self, mints: &[&str]) -> bool {
+        let Some(ref checker) = self.token_safety else {
+            return true;
+        };
 
-                        match meteora_swap::execute_meteora_swap(
-                            rpc_client.clone(),
-                            sell_pool_address,
-                            position_size_lamports,
-                            wallet_keypair,
-                            0.005,                          // 0.5% slippage tolerance
-                            false,                          // Swap Y to X (token to SOL)
-                            self.cached_blockhash.as_ref(), // Use pre-fetched blockhash
-                        )
-                        .await
-                        {
-                            Ok(signature) => {
-                                info!("✅ Sell executed: {}", signature);
+        for mint in mints {
+            if let Err(e) = checker.check(mint) {
+                warn!("🚫 Skipping opportunity: {}", e);
+                self.rejections.record(RejectionReason::UnsafeMint);
+                return false;
+            }
+        }
+        true
+    }
 
-                                // Reset consecutive failures on success
-                                self.stats.consecutive_failures = 0;
+    /// Check `mints` against the per-token cooldown (PER_TOKEN_COOLDOWN_MS)
+    ///
+    /// Unlike submission dedup this also blocks genuinely new opportunities,
+    /// so a volatile token isn't traded again while our last trade moves it.
+    ///
+    /// # Returns
+    /// True (logged and recorded as a rejection) if a mint is still cooling down
+    fn token_on_cooldown(&self, mints: &[&str]) -> bool {
+        let cooldown = Duration::from_millis(self.config.per_token_cooldown_ms);
+        if cooldown.is_zero() {
+            return false;
+        }
 
-                                // Track profit
-                                self.stats.total_profit_sol += opportunity.estimated_profit_sol;
+        match token_cooldown_remaining(&self.token_last_traded, mints, cooldown, Instant::now()) {
+            Some((mint, remaining)) => {
+                info!(
+                    "⏳ Skipping opportunity on {}: traded recently, {}ms cooldown remaining",
+                    self.token_metadata.label(mint),
+                    remaining.as_millis()
+                );
+                self.rejections.record(RejectionReason::TokenCooldown);
+                true
+            }
+            None => false,
+        }
+    }
 
-                                info!(
-                                    "🎉 Arbitrage complete! Estimated profit: {:.6} SOL",
-                                    opportunity.estimated_profit_sol
-                                );
-                            }
-                            Err(e) => {
-                                error!("❌ Sell failed: {}", e);
-                                self.stats.failed_executions += 1;
-                                self.stats.consecutive_failures += 1;
-                                return Err(e);
-                            }
-                        }
-                    }
+    /// Whether a route overlaps one of our in-flight bundles (SELF_TRADE_GUARD)
+    ///
+    /// # Returns
+    /// True (logged and recorded as a rejection) if a pending bundle shares a
+    /// token or pool with the route; always false when the guard is off
+    fn overlaps_in_flight(&self, mints: &[&str], dexs: &[&str]) -> bool {
+        if !self.config.self_trade_guard {
+            return false;
+        }
 
-                    info!("📊 Arbitrage execution summary:");
-                    info!("   Token: {}", opportunity.token_mint);
-                    info!(
-                        "   Buy DEX: {} (Meteora: {})",
-                        opportunity.buy_dex, is_buy_meteora
-                    );
-                    info!(
-                        "   Sell DEX: {} (Meteora: {})",
-                        opportunity.sell_dex, is_sell_meteora
-                    );
-                    info!("   Position: {:.6} SOL", position_size_sol);
-                    info!(
-                        "   Estimated profit: {:.6} SOL",
-                        opportunity.estimated_profit_sol
-                    );
-                } else {
-                    info!("📊 Non-Meteora arbitrage detected (not yet implemented):");
-                    info!("   Buy DEX: {}", opportunity.buy_dex);
-                    info!("   Sell DEX: {}", opportunity.sell_dex);
-                    warn!("⚠️ Only Meteora swaps are implemented. Skipping.");
-                }
-            } else {
-                warn!("⚠️ RPC client or wallet not available - cannot execute swaps");
+        match in_flight_overlap(&self.pending_bundles, mints, dexs) {
+            Some(key) => {
+                info!(
+                    "🪞 Skipping opportunity: overlaps our in-flight bundle {} - waiting for it to resolve",
+                    key
+                );
+                self.rejections.record(RejectionReason::SelfTrade);
+                true
             }
-
-            Ok(())
+            None => false,
         }
     }
 
-    /// Check if we should stop trading (safety limits)
-    fn should_stop_trading(&self) -> bool {
-        // Daily trade limit
-        if self.stats.daily_trades >= self.config.max_daily_trades {
-            warn!("⛔ Daily trade limit reached: {}", self.stats.daily_trades);
-            return true;
+    /// Start the per-token cooldown for `mints`
+    ///
+    /// Called when a trade is attempted - failed attempts count too, since a
+    /// revert in a volatile moment is exactly when to back off.
+    fn mark_tokens_traded(&mut self, mints: &[&str]) {
+        let cooldown = Duration::from_millis(self.config.per_token_cooldown_ms);
+        if cooldown.is_zero() {
+            return;
         }
 
-        // Daily loss limit
-        if self.stats.total_profit_sol < -self.config.daily_loss_limit_sol {
-            warn!(
-                "⛔ Daily loss limit reached: {:.6} SOL",
-                self.stats.total_profit_sol
-            );
-            return true;
+        let now = Instant::now();
+        self.token_last_traded
+            .retain(|_, traded_at| now.duration_since(*traded_at) < cooldown);
+        for mint in mints.iter().filter(|mint| **mint != SOL_MINT) {
+            self.token_last_traded.insert(mint.to_string(), now);
         }
+    }
 
-        // Consecutive failures
-        if self.stats.consecutive_failures >= self.config.max_consecutive_failures {
+    /// Log a token skipped for trading on too many pools (warn once, then debug)
+    ///
+    /// # Arguments
+    /// * `pool_count` - Pools quoting the token this scan
+    /// * `max_dexes` - MAX_DEXES_PER_TOKEN
+    fn flag_suspicious_token(&self, token_mint: &str, pool_count: usize, max_dexes: usize) {
+        let first_time = match self.suspicious_tokens.lock() {
+            Ok(mut flagged) => flagged.insert(token_mint.to_string()),
+            Err(poisoned) => poisoned.into_inner().insert(token_mint.to_string()),
+        };
+        if first_time {
             warn!(
-                "⛔ Too many consecutive failures: {}",
-                self.stats.consecutive_failures
+                "🕵️ Skipping suspicious token {}: priced on {} pools (max: {}) - review for spam/wash trading",
+                token_mint, pool_count, max_dexes
+            );
+        } else {
+            debug!(
+                "🕵️ Skipping suspicious token {}: {} pools",
+                self.token_metadata.label(token_mint),
+                pool_count
             );
-            return true;
         }
-
-        false
+    }
+
+    /// Check if we should stop trading (safety limits)
+    fn should_stop_trading(&self) -> bool {
+        // Low wallet balance (drained by fees, a bad trade or an external withdrawal)
+        if self
+            .stats
+            .wallet_balance_below(self.config.min_wallet_balance_sol)
+        {
+            warn!(
+                "⛔ Wallet balance too low: {:.6} SOL < {:.6} SOL minimum (MIN_WALLET_BALANCE_SOL) - trading stopped",
+                self.stats.wallet_balance_sol.unwrap_or_default(),
+                self.config.min_wallet_balance_sol.unwrap_or_default()
+            );
+            return true;
+        }
+
+        // Daily trade limit
+        if self.stats.daily_trades >= self.config.max_daily_trades {
+            warn!("⛔ Daily trade limit reached: {}", self.stats.daily_trades);
+            return true;
+        }
+
+        // Daily loss limit (tracked per calendar day, persisted across restarts)
+        // PROFIT_CURRENCY=USD: the USD limit applies once a SOL/USD price is known
+        let sol_usd = self.sol_usd();
+        match (sol_usd, self.config.daily_loss_limit_usd) {
+            (Some(sol_usd), Some(limit_usd)) => {
+                let loss_usd = sol_to_usd(self.stats.daily_loss_sol, sol_usd);
+                if loss_usd > limit_usd {
+                    warn!(
+                        "⛔ Daily loss limit reached: ${:.2} ({:.6} SOL @ ${:.2})",
+                        loss_usd, self.stats.daily_loss_sol, sol_usd
+                    );
+                    return true;
+                }
+            }
+            _ => {
+                if self.stats.daily_loss_sol > self.config.daily_loss_limit_sol {
+                    warn!(
+                        "⛔ Daily loss limit reached: {:.6} SOL",
+                        self.stats.daily_loss_sol
+                    );
+                    return true;
+                }
+            }
+        }
+
+        // Profit target: stop and keep the gains instead of trading them back
+        let (scope, profit) = if self.config.profit_target_reset_on_new_day {
+            ("Daily", self.stats.daily_profit_sol)
+        } else {
+            ("Total", self.stats.total_profit_sol)
+        };
+        match (sol_usd, self.config.daily_profit_target_usd) {
+            (Some(sol_usd), Some(target_usd)) => {
+                let profit_usd = sol_to_usd(profit, sol_usd);
+                if profit_usd >= target_usd {
+                    info!(
+                        "🎯 {} profit target reached: ${:.2} >= ${:.2} target",
+                        scope, profit_usd, target_usd
+                    );
+                    return true;
+                }
+            }
+            _ => {
+                if let Some(target) = self.config.daily_profit_target_sol {
+                    if profit >= target {
+                        info!(
+                            "🎯 {} profit target reached: {:.6} SOL >= {:.6} SOL target",
+                            scope, profit, target
+                        );
+                        return true;
+                    }
+                }
+            }
+        }
+
+        // Consecutive failures (otherwise handled by the failure cooldown in `run`)
+        if self.config.hard_stop_on_failures
+            && self.stats.consecutive_failures >= self.config.max_consecutive_failures
+        {
+            warn!(
+                "⛔ Too many consecutive failures: {}",
+                self.stats.consecutive_failures
+            );
+            return true;
+        }
+
+        false
     }
 
     /// Report statistics
+    /// Log a recommended min spread per token from the rolling spread history
+    ///
+    /// Read-only unless AUTO_TUNE_SPREAD=true, which replaces the per-token
+    /// minimums enforced by `scan_for_opportunities` with the recommendations.
+    fn analyze_spreads(&mut self) {
+        let recommendations = match self.spread_analyzer.lock() {
+            Ok(mut analyzer) => analyzer.recommendations(Instant::now()),
+            Err(_) => return,
+        };
+        if recommendations.is_empty() {
+            return;
+        }
+
+        info!(
+            "📐 Spread history ({} tokens, last {}s):",
+            recommendations.len(),
+            self.config.spread_analysis_window_secs
+        );
+        for rec in &recommendations {
+            let advice = rec
+                .recommended_min_spread_pct
+                .map_or("no reliable threshold".to_string(), |spread| {
+                    format!("recommend min spread {:.2}%", spread)
+                });
+            info!(
+                "  • {}: {} spreads, median {:.2}%, p90 {:.2}%, {:.0}% profitable → {}",
+                self.token_metadata.label(&rec.token_mint),
+                rec.samples,
+                rec.median_spread_pct,
+                rec.p90_spread_pct,
+                rec.profitable_fraction * 100.0,
+                advice
+            );
+        }
+
+        if self.config.auto_tune_spread {
+            self.spread_overrides = recommendations
+                .into_iter()
+                .filter_map(|rec| Some((rec.token_mint, rec.recommended_min_spread_pct?)))
+                .collect();
+            info!(
+                "🎛️ AUTO_TUNE_SPREAD: enforcing tuned min spread for {} tokens",
+                self.spread_overrides.len()
+            );
+        } else {
+            debug!("📐 Spread advice only (set AUTO_TUNE_SPREAD=true to enforce)");
+        }
+    }
+
     fn report_stats(&self) {
         info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         info!("📊 Arbitrage Statistics:");
@@ -1226,587 +3162,2192 @@ impl ArbitrageEngine {
             "  • Opportunities executed: {}",
             self.stats.opportunities_executed
         );
+        info!(
+            "  • Submitted but not landed: {}",
+            self.stats.opportunities_submitted_not_landed
+        );
         info!("  • Success rate: {:.1}%", self.stats.success_rate());
         info!("  • Total profit: {:.6} SOL", self.stats.total_profit_sol);
         info!("  • Daily trades: {}", self.stats.daily_trades);
+        info!("  • Daily profit: {:.6} SOL", self.stats.daily_profit_sol);
+        info!("  • Daily loss: {:.6} SOL", self.stats.daily_loss_sol);
+        match self.config.max_daily_tip_budget_sol {
+            Some(budget) => info!(
+                "  • Daily JITO tips: {:.6} / {:.6} SOL budget",
+                self.stats.daily_tips_spent_sol, budget
+            ),
+            None => info!(
+                "  • Daily JITO tips: {:.6} SOL",
+                self.stats.daily_tips_spent_sol
+            ),
+        }
+        if let Some(sol_usd) = self.sol_usd() {
+            info!(
+                "  • In USD @ ${:.2}/SOL: total ${:.2}, daily ${:.2}, daily loss ${:.2}",
+                sol_usd,
+                sol_to_usd(self.stats.total_profit_sol, sol_usd),
+                sol_to_usd(self.stats.daily_profit_sol, sol_usd),
+                sol_to_usd(self.stats.daily_loss_sol, sol_usd)
+            );
+        }
+        if self.config.shadow_mode {
+            info!(
+                "  • Shadow simulations: {}/{} passed",
+                self.stats.shadow_simulation_successes, self.stats.shadow_simulations
+            );
+        }
         info!(
             "  • Consecutive failures: {}",
             self.stats.consecutive_failures
         );
+        if self.stats.failure_cooldown_remaining_secs > 0 {
+            info!(
+                "  • Failure cooldown: {}s left ({} total)",
+                self.stats.failure_cooldown_remaining_secs, self.stats.failure_cooldowns
+            );
+        } else if self.stats.failure_cooldowns > 0 {
+            info!("  • Failure cooldowns: {}", self.stats.failure_cooldowns);
+        }
+        if self.stats.unwound_positions > 0 || self.stats.unwind_failures > 0 {
+            info!(
+                "  • Unwound partial fills: {} ({:.6} SOL lost, {} failed)",
+                self.stats.unwound_positions,
+                self.stats.unwind_loss_sol,
+                self.stats.unwind_failures
+            );
+        }
+        info!(
+            "  • ShredStream: {} ({} outages)",
+            self.shredstream_client.health(),
+            self.stats.shredstream_outages
+        );
+        info!("  • Scan interval: {}ms", self.stats.scan_interval_ms);
+        if self.config.scan_jitter_ms > 0 {
+            info!("  • Scan jitter: up to +{}ms", self.config.scan_jitter_ms);
+        }
+        if self.stats.shredstream_backoff_ms > 0 {
+            info!(
+                "  • ShredStream retry backoff: {}ms",
+                self.stats.shredstream_backoff_ms
+            );
+        }
+        info!(
+            "  • Fee reserve: {:.4} SOL ({:.4} SOL in-flight tips)",
+            self.stats.fee_reserve_sol, self.stats.in_flight_tips_sol
+        );
+        if let Some(ref endpoint) = self.stats.shredstream_endpoint {
+            info!("  • ShredStream endpoint: {}", endpoint);
+        }
+        if let Some(ref endpoint) = self.stats.jito_endpoint {
+            info!("  • JITO endpoint: {}", endpoint);
+        }
+        if self.config.rpc_max_rps > 0 {
+            info!(
+                "  • RPC rate limit ({}/s): {} calls delayed ({}ms total wait), {} rejected",
+                self.config.rpc_max_rps,
+                self.stats.rpc_rate_limited_calls,
+                self.stats.rpc_rate_limit_wait_ms,
+                self.stats.rpc_rate_limit_rejections
+            );
+        }
+        if !self.stats.dex_stats.is_empty() {
+            info!("  • Per-DEX execution:");
+            let mut dexs: Vec<_> = self.stats.dex_stats.iter().collect();
+            dexs.sort_by(|a, b| b.1.attempts.cmp(&a.1.attempts));
+            for (dex, dex_stats) in dexs {
+                info!(
+                    "      {}: {}/{} success ({:.0}%)",
+                    dex,
+                    dex_stats.successes,
+                    dex_stats.attempts,
+                    dex_stats.success_rate()
+                );
+            }
+        }
+        let biases = self.profit_divergence.biases();
+        if !biases.is_empty() {
+            info!("  • Realized - estimated profit (per DEX route):");
+            for bias in biases {
+                info!(
+                    "      {}: {:+.6} SOL mean, ±{:.6} SOL ({} trades){}",
+                    bias.route,
+                    bias.mean_sol,
+                    bias.stddev_sol,
+                    bias.samples,
+                    if self.profit_divergence.is_biased(&bias) {
+                        " ⚠️ estimates too optimistic"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+        let rejections = self.rejections.histogram();
+        if !rejections.is_empty() {
+            info!("  • Rejections:");
+            for (reason, count) in rejections {
+                info!("      {}: {}", reason, count);
+            }
+        }
+        let latencies = self.metrics.latency_percentiles();
+        if !latencies.is_empty() {
+            info!("  • Execution latency (p50 / p95):");
+            for latency in latencies {
+                info!(
+                    "      {}: {:.1}ms / {:.1}ms ({} samples)",
+                    latency.stage,
+                    latency.p50.as_secs_f64() * 1000.0,
+                    latency.p95.as_secs_f64() * 1000.0,
+                    latency.samples
+                );
+            }
+        }
         info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     }
 
+    /// Settle the JITO submission queue before exit
+    ///
+    /// With `DRAIN_ON_SHUTDOWN`, queued bundles are still submitted for up to
+    /// `SHUTDOWN_DRAIN_TIMEOUT_SECS`; otherwise (and after the timeout) they are
+    /// abandoned and logged. Either way the outcomes are reconciled, so tips
+    /// reserved for abandoned bundles return to the `PositionTracker`.
+    pub async fn shutdown_submissions(&mut self) {
+        let Some(submitter) = self.jito_submitter.clone() else {
+            return;
+        };
+
+        let abandoned = submitter
+            .shutdown(
+                self.config.drain_on_shutdown,
+                Duration::from_secs(SHUTDOWN_DRAIN_TIMEOUT_SECS),
+            )
+            .await;
+        self.reconcile_bundle_outcomes();
+
+        if abandoned.is_empty() {
+            info!("✅ JITO submission queue settled - no bundles abandoned");
+        } else {
+            warn!(
+                "🗑️ {} queued JITO bundle(s) abandoned at shutdown - reserved tips released",
+                abandoned.len()
+            );
+        }
+    }
+
     /// Get current statistics
     pub fn get_stats(&self) -> &ArbitrageStats {
         &self.stats
     }
 
+    /// Cached SOL/USD price (None unless PROFIT_CURRENCY=USD and a price was fetched)
+    pub fn sol_usd(&self) -> Option<f64> {
+        self.sol_usd_price.as_ref().and_then(SolUsdPrice::get)
+    }
+
+    /// Emergency stop flag (set to pause trading, clear to resume)
+    ///
+    /// Wired to SIGUSR1/SIGUSR2 on Unix by `main`. Checked at the top of every
+    /// loop iteration and again right before each JITO submission.
+    pub fn emergency_flag(&self) -> Arc<AtomicBool> {
+        self.emergency_stop.clone()
+    }
+
+    /// Shared status board the engine publishes `status_json` snapshots into
+    pub fn status_board(&self) -> Arc<StatusBoard> {
+        self.status.clone()
+    }
+
+    /// Build a JSON snapshot of live engine state for `GET /status`
+    ///
+    /// # Returns
+    /// Stats, reserved vs available capital, active JITO transport, expired-blockhash
+    /// retries, tip floor, last scan duration, ShredStream health and whether the
+    /// emergency stop file is present
+    pub async fn status_json(&self) -> serde_json::Value {
+        let (jito_transport, blockhash_retries) = match self.jito_submitter {
+            Some(ref submitter) => {
+                let stats = submitter.get_stats().await;
+                (
+                    Some(stats.active_transport.to_string()),
+                    Some(stats.blockhash_retries),
+                )
+            }
+            None => (None, None),
+        };
+        let tip_floor = {
+            let floor = self.jito_tip_floor.read().await;
+            serde_json::json!({
+                "p50_sol": floor.p50,
+                "p95_sol": floor.p95,
+                "p99_sol": floor.p99,
+                "ema_p50_sol": floor.ema_p50,
+                "age_secs": floor.last_updated.elapsed().as_secs(),
+            })
+        };
+
+        let usd = self.sol_usd().map(|sol_usd| {
+            serde_json::json!({
+                "sol_usd": sol_usd,
+                "total_profit_usd": sol_to_usd(self.stats.total_profit_sol, sol_usd),
+                "daily_profit_usd": sol_to_usd(self.stats.daily_profit_sol, sol_usd),
+                "daily_loss_usd": sol_to_usd(self.stats.daily_loss_sol, sol_usd),
+            })
+        });
+
+        serde_json::json!({
+            "status": "running",
+            "uptime_secs": self.start_time.elapsed().as_secs(),
+            "stats": self.stats,
+            "capital": self.position_tracker.get_stats(),
+            "jito_transport": jito_transport,
+            "blockhash_retries": blockhash_retries,
+            "tip_floor": tip_floor,
+            "usd": usd,
+            "last_scan_duration_ms": self.metrics.last_scan_duration().as_secs_f64() * 1000.0,
+            "shredstream": self.shredstream_client.health_report(),
+            "emergency_stop": self.emergency_stop.load(Ordering::SeqCst)
+                || Path::new(EMERGENCY_STOP_FILE).exists(),
+        })
+    }
+
     /// Get pool registry (for population)
     pub fn get_pool_registry(&self) -> &Option<Arc<PoolRegistry>> {
         &self.pool_registry
     }
 
-    /// Execute triangle arbitrage opportunity using real DEX swaps
-    async fn execute_triangle_opportunity(
-        &mut self,
-        opportunity: &crate::triangle_arbitrage::TriangleOpportunity,
-    ) -> Result<()> {
-        debug!(
-            "🔺 Executing triangle opportunity: {:?} → {:.4} SOL profit",
-            opportunity.path, opportunity.estimated_profit_sol
-        );
+    /// Apply landing outcomes from the JITO submitter, then settle landed trades
+    ///
+    /// Landed bundles count as executed with realized profit from the wallet
+    /// balance change inside the bundle's own transactions, so bundles whose
+    /// landing watches overlap are each attributed only what they moved. A
+    /// landed bundle whose balance change isn't readable yet waits in
+    /// `unsettled_trades` (see `settle_landed_trades`).
+    /// Bundles that never landed only bump `opportunities_submitted_not_landed`.
+    /// Resolved keys are also released from the dedup set so the pair can be
+    /// traded again immediately, and their tips leave the fee reserve.
+    fn reconcile_bundle_outcomes(&mut self) {
+        let outcomes: Vec<BundleOutcome> = match self.jito_submitter {
+            Some(ref submitter) => submitter.drain_outcomes(),
+            None => Vec::new(),
+        };
+        self.recent_submissions
+            .release(outcomes.iter().map(|o| o.dedup_key.as_str()));
 
-        // COST VALIDATION: Verify profitability after ALL costs before execution with dynamic tip floor
-        // Calculate position size from config (same as in triangle detection)
-        let position_size_sol = self
-            .config
-            .max_position_size_sol
-            .min(self.config.capital_sol);
-        let position_size_lamports = (position_size_sol * 1_000_000_000.0) as u64;
-        let gross_profit_lamports = (opportunity.estimated_profit_sol * 1_000_000_000.0) as u64;
-        let tip_floor = self.jito_tip_floor.read().await;
-        let costs = ArbitrageCosts::calculate(
-            position_size_lamports,
-            gross_profit_lamports,
-            true,
-            Some(&*tip_floor),
-        );
+        for outcome in outcomes {
+            let Some(mut pending) = self.pending_bundles.remove(&outcome.dedup_key) else {
+                continue;
+            };
+            // Tip and position are settled (landed) or never spent (dropped) - no longer in flight
+            pending.release(&self.position_tracker);
+            if let Some(ref bundle_id) = outcome.bundle_id {
+                pending.trade.tx_id = Some(bundle_id.clone());
+            }
 
-        if !costs.is_profitable(gross_profit_lamports) {
-            debug!("⚠️ Triangle opportunity no longer profitable after cost calculation!");
-            debug!(
-                "   Gross profit: {:.6} SOL ({} lamports)",
-                opportunity.estimated_profit_sol, gross_profit_lamports
-            );
-            debug!(
-                "   Total costs: {:.6} SOL ({} lamports)",
-                costs.total_cost_lamports as f64 / 1e9,
-                costs.total_cost_lamports
-            );
-            debug!(
-                "   Net loss: {:.6} SOL",
-                costs.net_profit(gross_profit_lamports) as f64 / 1e9
+            if !outcome.landed {
+                Self::log_trade(
+                    self.trade_log.as_deref(),
+                    &pending.trade,
+                    TradeOutcome::Failed,
+                );
+                self.stats.opportunities_submitted_not_landed += 1;
+                debug!(
+                    "📭 Bundle {} did not land (expected {:.6} SOL)",
+                    outcome.bundle_id.as_deref().unwrap_or("<not sent>"),
+                    pending.expected_profit_sol
+                );
+                continue;
+            }
+
+            self.stats.opportunities_executed += 1;
+            self.stats.daily_trades += 1;
+            self.stats.consecutive_failures = 0;
+            Self::record_triangle_outcome(
+                &mut self.stats,
+                self.pool_registry.as_deref(),
+                &pending.dexs,
+                true,
             );
-            return Err(anyhow::anyhow!(
-                "Opportunity became unprofitable after cost validation"
-            ));
+
+            self.unsettled_trades.push(UnsettledTrade {
+                route: Some((pending.dexs, pending.estimated_net_profit_sol)),
+                ..UnsettledTrade::landed(
+                    outcome.bundle_id.unwrap_or(outcome.dedup_key),
+                    outcome.signatures,
+                    pending.trade,
+                    pending.worst_case_loss_sol,
+                )
+            });
         }
 
-        let net_profit = costs.net_profit(gross_profit_lamports);
-        let (gas_pct, tip_pct) = costs.gas_tip_ratio();
-        info!("💰 Cost validation passed:");
-        info!(
-            "   Gross profit: {:.6} SOL",
-            opportunity.estimated_profit_sol
-        );
-        info!(
-            "   JITO tip: {:.6} SOL ({:.1}%)",
-            costs.jito_tip_lamports as f64 / 1e9,
-            tip_pct
-        );
-        info!(
-            "   Gas fees: {:.6} SOL ({:.1}%)",
-            (costs.base_tx_fee_lamports + costs.compute_fee_lamports) as f64 / 1e9,
-            gas_pct
-        );
-        info!(
-            "   Net profit: {:.6} SOL ({:.1}% retention)",
-            net_profit as f64 / 1e9,
-            costs.retention_percentage(gross_profit_lamports)
-        );
+        self.settle_unsettled_trades();
+    }
 
-        // Paper trading mode: Simulate execution
-        if self.config.paper_trading {
-            info!("📄 Paper trading: Simulating triangle execution...");
+    /// Book landed trades whose realized profit is now readable, or whose deadline passed
+    ///
+    /// Never leaves a landed trade unbooked: past `PROFIT_READ_DEADLINE` the
+    /// worst case (position, tip and fees lost) counts against the daily loss limit.
+    fn settle_unsettled_trades(&mut self) {
+        if self.unsettled_trades.is_empty() {
+            return;
+        }
+        let rpc_client = self.rpc_client.as_deref();
+        let wallet = self.wallet_keypair.as_deref();
+        let settled = settle_landed_trades(&mut self.unsettled_trades, Instant::now(), |trade| {
+            Self::read_unsettled_profit(rpc_client, wallet, trade)
+        });
 
-            // Simulate ~90% success rate (some opportunities will fail due to slippage, MEV, etc.)
-            use rand::Rng;
-            let success = rand::thread_rng().gen_bool(0.9);
+        for (unsettled, settlement) in settled {
+            Self::book_settlement(
+                &mut self.stats,
+                &mut self.profit_divergence,
+                self.trade_log.as_deref(),
+                unsettled,
+                settlement,
+            );
+        }
+    }
 
-            if success {
-                self.stats.opportunities_executed += 1;
-                self.stats.total_profit_sol += opportunity.estimated_profit_sol;
-                self.stats.consecutive_failures = 0;
+    /// Realized profit of a landed trade: the anchor token's change too for a Jupiter round trip
+    fn read_unsettled_profit(
+        rpc_client: Option<&SolanaRpcClient>,
+        wallet: Option<&Keypair>,
+        trade: &UnsettledTrade,
+    ) -> Option<f64> {
+        match (&trade.jupiter, rpc_client, wallet) {
+            (Some(triangle), Some(rpc), Some(wallet)) => {
+                Self::read_jupiter_realized_profit(rpc, wallet, &trade.signatures, triangle)
+            }
+            _ => Self::read_realized_profit(rpc_client, wallet, &trade.signatures),
+        }
+    }
 
-                info!("✅ Paper triangle executed successfully!");
+    /// Book a settled trade: profit (or worst-case loss) into stats, row into the trade log
+    ///
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    fn book_settlement(
+        stats: &mut ArbitrageStats,
+        profit_divergence: &mut ProfitDivergenceTracker,
+        trade_log: Option<&TradeLogger>,
+        mut unsettled: UnsettledTrade,
+        settlement: Settlement,
+    ) {
+        stats.record_profit(settlement.profit_sol());
+        match settlement {
+            Settlement::Realized(realized) => {
+                unsettled.trade.realized_profit_sol = Some(realized);
                 info!(
-                    "💰 Paper profit: {:.6} SOL (Total: {:.6} SOL)",
-                    opportunity.estimated_profit_sol, self.stats.total_profit_sol
+                    "💰 {} landed: realized {:.6} SOL (expected {:.6} SOL)",
+                    unsettled.label, realized, unsettled.trade.estimated_profit_sol
                 );
-
-                Ok(())
-            } else {
-                self.stats.failed_executions += 1;
-                self.stats.consecutive_failures += 1;
-                warn!("⚠️ Paper triangle execution failed (simulated slippage)");
-                Err(anyhow::anyhow!(
-                    "Paper trading: Simulated execution failure"
-                ))
+                if let Some((dexs, estimated_net_profit_sol)) = &unsettled.route {
+                    let route = dex_route_key(dexs);
+                    if let Some(bias) =
+                        profit_divergence.record(&route, *estimated_net_profit_sol, realized)
+                    {
+                        warn!(
+                            "📉 Realized profit on {} trails estimates by {:.6} SOL on average (±{:.6} SOL over {} trades) - cost model or slippage assumptions look optimistic",
+                            bias.route, -bias.mean_sol, bias.stddev_sol, bias.samples
+                        );
+                    }
+                }
             }
+            Settlement::WorstCase(loss) => error!(
+                "❌ {} landed but its balance change is still unreadable after {:?} - booked worst-case loss of {:.6} SOL",
+                unsettled.label, PROFIT_READ_DEADLINE, loss
+            ),
         }
-        // Real trading mode: Execute with swap executor
-        else if let (Some(ref mut executor), Some(ref wallet)) =
-            (&mut self.swap_executor, &self.wallet_keypair)
-        {
-            // CYCLE-5 FIX: Check RPC circuit breaker before trading
-            if let Err(e) = executor.check_circuit_breaker() {
-                error!("🚨 Cannot execute trade: {}", e);
-                return Err(e);
-            }
-
-            info!("💎 REAL TRADING: Building triangle swap with DEX instructions");
+        Self::log_trade(trade_log, &unsettled.trade, TradeOutcome::Landed);
+    }
 
-            // Extract pool IDs from DEX strings (e.g., "Meteora_DAMM_V2_81vA2wJx" → "81vA2wJx")
-            let pool_ids: Result<Vec<String>> = opportunity
-                .dexs
-                .iter()
-                .map(|dex| extract_pool_id(dex))
-                .collect();
+    /// Realized profit of a Jupiter round trip (legs plus any unwind exits), in SOL
+    ///
+    /// SOL anchors read the wallet's lamport change across the transactions.
+    /// USDC/USDT anchors read the anchor token's net change, converted to SOL at
+    /// the opportunity's anchor price, plus the lamports the transactions cost.
+    ///
+    /// # Returns
+    /// None (logged) if any balance change is unavailable
+    fn read_jupiter_realized_profit(
+        rpc_client: &SolanaRpcClient,
+        wallet: &Keypair,
+        signatures: &[String],
+        triangle: &SimpleTriangleOpportunity,
+    ) -> Option<f64> {
+        let lamports_sol = Self::read_realized_profit(Some(rpc_client), Some(wallet), signatures)?;
+        if triangle.anchor.is_sol() {
+            return Some(lamports_sol);
+        }
 
-            let pool_ids = match pool_ids {
-                Ok(ids) => ids,
+        let mint: solana_sdk::pubkey::Pubkey = triangle.anchor.mint.parse().ok()?;
+        let mut anchor_change = 0i128;
+        for signature in signatures {
+            let change = signature
+                .parse::<Signature>()
+                .map_err(anyhow::Error::from)
+                .and_then(|signature| {
+                    rpc_client.get_transaction_token_change(&signature, &wallet.pubkey(), &mint)
+                });
+            match change {
+                Ok(change) => anchor_change += change,
                 Err(e) => {
-                    warn!("⚠️ Failed to extract pool IDs: {}", e);
-                    return Err(e);
+                    warn!(
+                        "⚠️ {} balance change of {} unavailable: {}",
+                        triangle.anchor.symbol, signature, e
+                    );
+                    return None;
                 }
-            };
-
-            // CRITICAL FIX: Validate all pool addresses can be resolved BEFORE execution
-            // This prevents wasting time building transactions for pools that don't exist
-            if let Some(ref pool_registry) = self.pool_registry {
-                debug!("🔍 Pre-validating {} pool addresses...", pool_ids.len());
+            }
+        }
+        Some(triangle.base_units_to_sol(anchor_change) + lamports_sol)
+    }
 
-                for (i, pool_id) in pool_ids.iter().enumerate() {
-                    let dex_type = DexType::from_dex_string(&opportunity.dexs[i])?;
+    /// Book a directly sent (non-JITO) trade whose transaction(s) confirmed
+    ///
+    /// Counts it as executed and books the realized profit from the
+    /// transactions' wallet balance change. If that can't be read yet the trade
+    /// stays in `unsettled` and is booked once it can, or at its worst case past
+    /// `PROFIT_READ_DEADLINE`. Takes fields explicitly so it can run while the
+    /// swap executor is borrowed.
+    ///
+    /// # Returns
+    /// Realized profit in SOL, if it could be read now
+    fn record_confirmed_trade(
+        stats: &mut ArbitrageStats,
+        profit_divergence: &mut ProfitDivergenceTracker,
+        unsettled: &mut Vec<UnsettledTrade>,
+        rpc_client: Option<&SolanaRpcClient>,
+        wallet: &Keypair,
+        trade_log: Option<&TradeLogger>,
+        landed: UnsettledTrade,
+    ) -> Option<f64> {
+        stats.opportunities_executed += 1;
+        stats.daily_trades += 1;
+        stats.consecutive_failures = 0;
+
+        let mut waiting = vec![landed];
+        let settled = settle_landed_trades(&mut waiting, Instant::now(), |trade| {
+            Self::read_unsettled_profit(rpc_client, Some(wallet), trade)
+        });
+        let mut realized = None;
+        for (trade, settlement) in settled {
+            realized = Some(settlement.profit_sol());
+            Self::book_settlement(stats, profit_divergence, trade_log, trade, settlement);
+        }
+        for trade in &waiting {
+            warn!(
+                "⏳ Transaction {} confirmed, balance change not readable yet - retrying for up to {:?}",
+                trade.label, PROFIT_READ_DEADLINE
+            );
+        }
+        unsettled.append(&mut waiting);
+        realized
+    }
 
-                    match pool_registry.resolve_pool_address(pool_id, &dex_type).await {
-                        Ok(pool_address) => {
-                            debug!(
-                                "  ✅ Pool {} resolved: {} ({})",
-                                i + 1,
-                                pool_id,
-                                pool_address
-                            );
-                        }
-                        Err(e) => {
-                            warn!(
-                                "⚠️ Cannot resolve pool address for {} ({:?}): {}",
-                                pool_id, dex_type, e
-                            );
-                            warn!("   Skipping opportunity - pool lookup failed");
-                            return Err(anyhow::anyhow!(
-                                "Pool address resolution failed for {}: {}",
-                                pool_id,
-                                e
-                            ));
-                        }
-                    }
+    /// Realized profit of landed transactions from the wallet balance change in each
+    ///
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    ///
+    /// # Returns
+    /// None (logged) if there is nothing to read or any transaction's balance
+    /// change is unavailable - never falls back to the estimate
+    fn read_realized_profit(
+        rpc_client: Option<&SolanaRpcClient>,
+        wallet: Option<&Keypair>,
+        signatures: &[String],
+    ) -> Option<f64> {
+        let (Some(rpc), Some(wallet)) = (rpc_client, wallet) else {
+            return None;
+        };
+        if signatures.is_empty() {
+            return None;
+        }
+        let mut lamport_changes = Vec::with_capacity(signatures.len());
+        for signature in signatures {
+            let change = signature
+                .parse::<Signature>()
+                .map_err(anyhow::Error::from)
+                .and_then(|signature| {
+                    rpc.get_transaction_balance_change(&signature, &wallet.pubkey())
+                });
+            match change {
+                Ok(lamports) => lamport_changes.push(lamports),
+                Err(e) => {
+                    warn!("⚠️ Balance change of {} unavailable: {}", signature, e);
+                    return None;
                 }
-
-                debug!(
-                    "✅ All {} pool addresses resolved successfully",
-                    pool_ids.len()
-                );
             }
+        }
+        Some(realized_profit_sol(&lamport_changes))
+    }
 
-            // GROK GHOST POOL SOLUTION - STEP 2: Validate pools before execution
-            // Check cache for each pool, batch-validate uncached pools
+    /// Emit the structured trade event and append a row to the trade log, if enabled
+    ///
+    /// Takes the logger explicitly so it can run while the swap executor is borrowed.
+    fn log_trade(trade_log: Option<&TradeLogger>, trade: &TradeRecord, outcome: TradeOutcome) {
+        structured_log::trade_event(trade, outcome, None);
+        if let Some(trade_log) = trade_log {
+            trade_log.log(trade, outcome);
+        }
+    }
 
-            // MARKET CHAOS MODE - Skip ghost pool validation for speed
-            let skip_ghost_pool_check = std::env::var("SKIP_GHOST_POOL_CHECK")
-                .unwrap_or_else(|_| "false".to_string())
-                .to_lowercase()
-                == "true";
+    /// Count a pre-execution rejection and log the trade as `rejected` with its reason
+    ///
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    fn reject_trade(
+        trade_log: Option<&TradeLogger>,
+        rejections: &RejectionCounters,
+        trade: &TradeRecord,
+        reason: RejectionReason,
+    ) {
+        rejections.record(reason);
+        structured_log::trade_event(trade, TradeOutcome::Rejected, Some(&reason.to_string()));
+        if let Some(trade_log) = trade_log {
+            trade_log.log(trade, TradeOutcome::Rejected);
+        }
+    }
 
-            // PumpSwap pools don't have traditional pool accounts - skip ghost pool validation
-            let has_pumpswap = opportunity
-                .dexs
-                .iter()
-                .any(|dex| dex.contains("PumpSwap") || dex.contains("PumpFun"));
+    /// Record a triangle execution outcome per DEX (stats) and per pool (blacklist)
+    ///
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    fn record_triangle_outcome(
+        stats: &mut ArbitrageStats,
+        pool_registry: Option<&PoolRegistry>,
+        dexs: &[String],
+        success: bool,
+    ) {
+        stats.record_dex_result(dexs, success);
+
+        if let Some(pool_registry) = pool_registry {
+            for pool_id in dexs.iter().filter_map(|dex| extract_pool_id(dex).ok()) {
+                if success {
+                    pool_registry.record_pool_success(&pool_id);
+                } else {
+                    pool_registry.record_pool_failure(&pool_id);
+                }
+            }
+        }
+    }
 
-            if skip_ghost_pool_check {
-                info!(
-                    "⚡ MARKET CHAOS MODE: Skipping ghost pool validation for ultra-fast execution"
-                );
-            } else if has_pumpswap {
-                debug!("🪙 PumpSwap pools detected - skipping ghost pool validation (uses bonding curve, not traditional pools)");
-            } else if let Some(ref pool_registry) = self.pool_registry {
-                debug!(
-                    "🔍 Validating {} pools for ghost pool check",
-                    pool_ids.len()
-                );
+    /// RESERVE_RECHECK: re-read pool reserves and re-estimate the route before building
+    ///
+    /// Reserves move between detection and execution. Each leg is re-quoted with
+    /// the builders' `estimate_swap_output` (fresh on-chain reads), chained from
+    /// the first leg's input. Unlike the disabled final simulation this only
+    /// rejects when the drift pushes net profit below the floor, not on any change.
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    ///
+    /// # Arguments
+    /// * `executor` - Re-quotes each leg against current reserves
+    /// * `legs` - (DEX, short pool ID, swap params) per leg, in route order
+    /// * `costs` - Validated costs (tip and gas come off the re-estimated return)
+    /// * `profit_floor_lamports` - Minimum acceptable net profit
+    ///
+    /// # Errors
+    /// `ArbError::Unprofitable` if re-estimated net profit is below the floor,
+    /// `ArbError::RpcError` if a leg can't be re-quoted
+    fn recheck_reserves(
+        executor: &SwapExecutor,
+        legs: &[(&DexType, &str, &SwapParams)],
+        costs: &ArbitrageCosts,
+        profit_floor_lamports: u64,
+    ) -> ArbResult<()> {
+        let Some((_, _, first)) = legs.first() else {
+            return Ok(());
+        };
+        let stage_start = Instant::now();
+        let amount_out =
+            reestimated_route_output(first.amount_in, legs, |dex, pool, amount, a_to_b| {
+                executor.estimate_swap_output(dex, pool, amount, a_to_b)
+            })
+            .map_err(|e| ArbError::RpcError(format!("reserve re-check: {}", e)))?;
+
+        let expected_out = legs
+            .last()
+            .and_then(|(_, _, swap)| swap.expected_amount_out)
+            .unwrap_or(amount_out);
+        let net_profit = reestimated_net_profit(first.amount_in, amount_out, costs);
+        debug!(
+            "🔁 Reserve re-check in {:?}: return {} vs {} expected ({:+.3}%), net {} lamports",
+            stage_start.elapsed(),
+            amount_out,
+            expected_out,
+            (amount_out as f64 / expected_out.max(1) as f64 - 1.0) * 100.0,
+            net_profit
+        );
 
-                let mut needs_validation = Vec::new();
-                for pool_id in &pool_ids {
-                    if pool_registry.is_pool_valid_cached(pool_id).await != Some(true) {
-                        needs_validation.push(pool_id.clone());
-                    }
-                }
+        if net_profit < profit_floor_lamports as i64 {
+            warn!(
+                "🔁 Aborting: reserves drifted, re-estimated net profit {} lamports below floor {}",
+                net_profit, profit_floor_lamports
+            );
+            return Err(ArbError::Unprofitable(format!(
+                "re-estimated net profit {} lamports below floor {} after reserve drift",
+                net_profit, profit_floor_lamports
+            )));
+        }
+        Ok(())
+    }
 
-                // Batch-validate uncached pools
-                if !needs_validation.is_empty() {
-                    debug!(
-                        "🔍 Batch-validating {} uncached pools",
-                        needs_validation.len()
-                    );
-                    if let Err(e) = pool_registry.validate_pools_batch(&needs_validation).await {
-                        warn!("⚠️ Pool validation failed: {}", e);
-                        return Err(anyhow::anyhow!("Pool validation error: {}", e));
-                    }
-                }
+    /// Record a failed triangle build
+    ///
+    /// A paused or not-yet-activated pool (`ArbError::PoolNotActive`) is a skip:
+    /// counted as a rejection, never against the pools' blacklist or DEX stats.
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    fn record_build_failure(
+        stats: &mut ArbitrageStats,
+        pool_registry: Option<&PoolRegistry>,
+        rejections: &RejectionCounters,
+        trade_log: Option<&TradeLogger>,
+        dexs: &[String],
+        trade: &TradeRecord,
+        error: &anyhow::Error,
+    ) {
+        if let Some(ArbError::PoolNotActive(detail)) = ArbError::find(error) {
+            info!("⏸️ Skipping opportunity: pool not active ({})", detail);
+            Self::reject_trade(trade_log, rejections, trade, RejectionReason::PoolNotActive);
+            return;
+        }
+        Self::record_triangle_outcome(stats, pool_registry, dexs, false);
+        Self::log_trade(trade_log, trade, TradeOutcome::Failed);
+    }
 
-                // Re-check: Reject if ANY pool is invalid (ghost pool)
-                for pool_id in &pool_ids {
-                    if pool_registry.is_pool_valid_cached(pool_id).await != Some(true) {
-                        debug!(
-                            "⚠️ Ghost pool detected: {} (pool doesn't exist on-chain)",
-                            pool_id
-                        );
-                        debug!(
-                            "   Rejected opportunity: token {} on {:?}",
-                            opportunity.path[1], opportunity.dexs
-                        );
-                        return Err(anyhow::anyhow!("Ghost pool detected: {}", pool_id));
-                    }
-                }
+    /// PROFIT_VERIFY_SIM: Simulate the built transaction and check realized profit
+    ///
+    /// Decodes the actual output of every swap leg from post-simulation token
+    /// balances, logs drift against `expected_amount_out`, and requires the final
+    /// SOL output to beat the input plus JITO tip and gas. Fails closed: a leg
+    /// that cannot be measured rejects the trade. Takes fields explicitly so it
+    /// can run while the swap executor is borrowed.
+    ///
+    /// # Arguments
+    /// * `legs` - Pool short ID and swap parameters per leg, in execution order
+    ///
+    /// # Returns
+    /// * `Ok(net_lamports)` - Simulated profit after tip and gas (positive)
+    /// * `Err` if the simulation failed, a leg could not be decoded, or the
+    ///   simulated round trip is not profitable
+    fn verify_simulated_profit(
+        rpc_client: Option<&SolanaRpcClient>,
+        pool_registry: Option<&PoolRegistry>,
+        wallet: &solana_sdk::pubkey::Pubkey,
+        transaction: &Transaction,
+        legs: &[(&str, &SwapParams)],
+        costs: &ArbitrageCosts,
+    ) -> ArbResult<i64> {
+        let rpc_client = rpc_client
+            .ok_or_else(|| anyhow::anyhow!("PROFIT_VERIFY_SIM requires an RPC client"))?;
+        let pool_registry = pool_registry
+            .ok_or_else(|| anyhow::anyhow!("PROFIT_VERIFY_SIM requires a pool registry"))?;
+
+        let mut leg_mints = Vec::with_capacity(legs.len());
+        for (pool_id, swap) in legs {
+            let pool = pool_registry.get_pool(pool_id).ok_or_else(|| {
+                anyhow::anyhow!("Pool {} not registered - cannot verify", pool_id)
+            })?;
+            let (input_mint, output_mint) = if swap.swap_a_to_b {
+                (pool.token_a_mint, pool.token_b_mint)
+            } else {
+                (pool.token_b_mint, pool.token_a_mint)
+            };
+            leg_mints.push((input_mint, output_mint, swap.amount_in));
+        }
+        let probes = leg_output_probes(wallet, &leg_mints);
 
-                debug!("✅ All {} pools validated successfully", pool_ids.len());
-            }
+        info!(
+            "🧪 Verifying profit via simulation ({} legs)...",
+            legs.len()
+        );
+        let simulation = rpc_client.simulate_transaction_with_amounts(transaction, &probes)?;
+        if !simulation.outcome.success {
+            return Err(ArbError::SimulationFailed(format!(
+                "profit verification: {}",
+                simulation
+                    .outcome
+                    .error
+                    .as_deref()
+                    .unwrap_or("unknown error")
+            )));
+        }
 
-            // Validate we have 2 or 3 DEXs (2-leg arbitrage or 3-leg triangle)
-            if pool_ids.len() < 2 || pool_ids.len() > 3 {
-                return Err(anyhow::anyhow!(
-                    "Invalid opportunity: expected 2-3 DEXs, got {}",
-                    pool_ids.len()
-                ));
+        for (i, ((_, swap), actual)) in legs.iter().zip(&simulation.leg_outputs).enumerate() {
+            let actual = actual
+                .ok_or_else(|| anyhow::anyhow!("Could not decode output of leg {}", i + 1))?;
+            match swap.expected_amount_out {
+                Some(expected) if expected > 0 => debug!(
+                    "   Leg {}: simulated {} vs expected {} ({:+.2}%)",
+                    i + 1,
+                    actual,
+                    expected,
+                    (actual as f64 / expected as f64 - 1.0) * 100.0
+                ),
+                _ => debug!("   Leg {}: simulated {}", i + 1, actual),
             }
+        }
 
-            // Determine DEX types
-            let dex_types: Result<Vec<DexType>> = opportunity
-                .dexs
-                .iter()
-                .map(|dex| DexType::from_dex_string(dex))
-                .collect();
+        let amount_in = legs.first().map(|(_, swap)| swap.amount_in).unwrap_or(0);
+        let final_out = simulation
+            .leg_outputs
+            .last()
+            .copied()
+            .flatten()
+            .unwrap_or(0);
+        let execution_costs =
+            costs.jito_tip_lamports + costs.base_tx_fee_lamports + costs.compute_fee_lamports;
+        let net = final_out as i64 - amount_in as i64 - execution_costs as i64;
+
+        if net <= 0 {
+            return Err(ArbError::Unprofitable(format!(
+                "simulated round trip: {} in, {} out, {} tip+gas ({} lamports net)",
+                amount_in, final_out, execution_costs, net
+            )));
+        }
 
-            let dex_types = match dex_types {
-                Ok(types) => types,
-                Err(e) => {
-                    warn!("⚠️ Failed to parse DEX types: {}", e);
-                    return Err(e);
-                }
-            };
+        info!(
+            "✅ Simulated net profit: {:.6} SOL after tip and gas",
+            net as f64 / 1e9
+        );
+        Ok(net)
+    }
 
-            // CRITICAL FIX: Reserve SOL for fees before calculating position size
-            // Can't spend all capital - need to keep SOL for JITO tips + gas + DEX fees
-            let gross_capital_lamports =
-                (self.config.max_position_size_sol * 1_000_000_000.0) as u64;
+    /// SHADOW MODE: Simulate a fully built transaction (swaps + tip) without submitting
+    ///
+    /// Validates swap builders against live pool state without risking capital.
+    /// Takes fields explicitly so it can run while the swap executor is borrowed.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the transaction would succeed on-chain
+    /// * `Err` if simulation failed (program error) or the RPC call failed
+    fn shadow_simulate(
+        rpc_client: Option<&SolanaRpcClient>,
+        pool_registry: Option<&PoolRegistry>,
+        stats: &mut ArbitrageStats,
+        transaction: &Transaction,
+        dexs: &[String],
+        label: &str,
+        expected_profit_sol: f64,
+    ) -> ArbResult<()> {
+        let rpc_client =
+            rpc_client.ok_or_else(|| anyhow::anyhow!("Shadow mode requires an RPC client"))?;
 
-            // Subtract all costs to get actual tradeable capital
-            let capital_lamports = gross_capital_lamports.saturating_sub(costs.total_cost_lamports);
+        info!(
+            "👻 SHADOW: Simulating {} transaction (not submitting)...",
+            label
+        );
+        let outcome = rpc_client.simulate_transaction_detailed(transaction)?;
+        stats.shadow_simulations += 1;
+        Self::record_triangle_outcome(stats, pool_registry, dexs, outcome.success);
 
-            info!("💰 Position sizing:");
-            info!(
-                "   Gross capital: {:.6} SOL",
-                gross_capital_lamports as f64 / 1e9
-            );
+        let units = outcome
+            .units_consumed
+            .map(|cu| cu.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        if outcome.success {
+            stats.shadow_simulation_successes += 1;
             info!(
-                "   Reserved for fees: {:.6} SOL",
-                costs.total_cost_lamports as f64 / 1e9
+                "✅ SHADOW: {} simulation passed ({} CU, would have earned ~{:.6} SOL)",
+                label, units, expected_profit_sol
             );
-            info!(
-                "   Tradeable capital: {:.6} SOL",
-                capital_lamports as f64 / 1e9
+            Ok(())
+        } else {
+            let error = outcome.error.unwrap_or_else(|| "unknown error".to_string());
+            warn!(
+                "❌ SHADOW: {} simulation failed ({} CU): {}",
+                label, units, error
             );
+            Err(ArbError::SimulationFailed(format!(
+                "shadow mode ({} CU consumed): {}",
+                units, error
+            )))
+        }
+    }
 
-            // Handle 2-leg arbitrage (SOL → Token → SOL via different DEXs)
-            if pool_ids.len() == 2 {
-                info!("💱 Executing 2-leg arbitrage (cross-DEX same token):");
+    /// Execute a ShredStream-detected simple triangle via Jupiter swaps
+    ///
+    /// Jupiter rejects SOL → SOL routes, so each leg (Anchor → A → B → Anchor) is its
+    /// own swap transaction. The round trip is quoted up front (chaining each leg's
+    /// minimum output into the next) and dropped if it no longer pays. Legs are then
+    /// sent one at a time: each waits for the previous leg to confirm and is re-quoted
+    /// from the amount that leg actually delivered. The emergency stop is re-checked
+    /// right before every send.
+    ///
+    /// Once the last leg confirms, the trade is counted and its realized profit (the
+    /// wallet balance change across the legs) is booked - never the estimate. If
+    /// that change isn't readable yet it's retried, and booked as the worst case
+    /// (whole input plus leg fees lost) past `PROFIT_READ_DEADLINE`.
+    ///
+    /// NOTE: Legs are NOT atomic - a later leg that fails leaves the wallet holding
+    /// the intermediate token, which is then unwound back to the anchor.
+    async fn execute_simple_triangle_via_jupiter(
+        &mut self,
+        triangle: &SimpleTriangleOpportunity,
+    ) -> Result<()> {
+        let (rpc_client, wallet) = match (&self.rpc_client, &self.wallet_keypair) {
+            (Some(rpc_client), Some(wallet)) => (rpc_client.clone(), wallet.clone()),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "Jupiter execution requires WALLET_PRIVATE_KEY and RPC client"
+                ))
+            }
+        };
 
-                // GROK FIX: Correct profit calculation matching detection logic
-                // Prices are in SOL/token, so we DIVIDE (not multiply) for SOL→Token
-                const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
-                const SWAP_FEE: f64 = 0.0025; // 0.25% per leg
+        let anchor = &triangle.anchor;
+        let mints = [
+            anchor.mint.as_str(),
+            triangle.token_a_mint.as_str(),
+            triangle.token_b_mint.as_str(),
+            anchor.mint.as_str(),
+        ];
+        // Anchor base units (lamports for SOL, micro-USDC for USDC)
+        let input_amount = triangle.input_base_units();
+
+        // Quote the round trip up front (leg N+1 input = leg N guaranteed minimum output)
+        let mut routes = Vec::with_capacity(3);
+        let mut amount = input_amount;
+        for leg in mints.windows(2) {
+            let route = self
+                .jupiter_swap
+                .get_quote(leg[0], leg[1], amount, JUPITER_SLIPPAGE_BPS)
+                .await?;
+            amount = route.other_amount_threshold;
+            routes.push(route);
+        }
 
-                // Leg 1: SOL → Token (buy on DEX A)
-                let amount_in_1 = capital_lamports;
-                let capital_sol = amount_in_1 as f64 / LAMPORTS_PER_SOL as f64;
+        if amount <= input_amount {
+            return Err(anyhow::anyhow!(
+                "Jupiter quotes no longer profitable: {} → {} {} base units (after slippage)",
+                input_amount,
+                amount,
+                anchor.symbol
+            ));
+        }
+        info!(
+            "   🪐 Jupiter round trip: {} → {} {} base units (min, after slippage)",
+            input_amount, amount, anchor.symbol
+        );
 
-                // CORRECT: SOL / (SOL/token) = tokens (with fee)
-                let tokens_received = (capital_sol / opportunity.prices[0]) * (1.0 - SWAP_FEE);
-                let expected_out_1 = (tokens_received * 1_000_000_000.0) as u64; // Convert to token lamports
-                let min_out_1 =
-                    SwapExecutor::calculate_min_output_with_slippage(expected_out_1, 100);
+        // The wallet must already hold the anchor it starts from (USDC/USDT aren't wrapped)
+        let anchor_balance = wallet_mint_balance(&rpc_client, &wallet.pubkey(), &anchor.mint)
+            .context(format!("Failed to read wallet {} balance", anchor.symbol))?;
+        if anchor_balance < input_amount {
+            return Err(anyhow::anyhow!(
+                "Insufficient {}: wallet holds {} base units, trade needs {}",
+                anchor.symbol,
+                anchor_balance,
+                input_amount
+            ));
+        }
 
-                // Leg 2: Token → SOL (sell on DEX B)
-                let amount_in_2 = expected_out_1;
+        let shadow_mode = self.config.shadow_mode;
+        // Intermediate balances before leg 1 - an unwind only sells what the trade added
+        let held_before = if shadow_mode {
+            Vec::new()
+        } else {
+            let mut held_before = Vec::with_capacity(2);
+            for mint in &mints[1..3] {
+                let balance = wallet_mint_balance(&rpc_client, &wallet.pubkey(), mint)
+                    .context(format!("Failed to read wallet balance of {}", mint))?;
+                held_before.push((*mint, balance));
+            }
+            held_before
+        };
+        let mut signatures = Vec::with_capacity(routes.len());
+        // Sent leg whose status stayed unknown - it may still land
+        let mut unconfirmed_leg = None;
+        // Amount of the current leg's input mint the trade holds (confirmed output of the last leg)
+        let mut held_amount = input_amount;
+        for i in 0..routes.len() {
+            let leg = async {
+                // Later legs sell what the previous leg actually delivered
+                if i > 0 && !shadow_mode {
+                    routes[i] = self
+                        .jupiter_swap
+                        .get_quote(mints[i], mints[i + 1], held_amount, JUPITER_SLIPPAGE_BPS)
+                        .await
+                        .context(format!("Failed to re-quote Jupiter leg {}", i + 1))?;
+                }
+                let route = &routes[i];
+                let blockhash = rpc_client.get_latest_blockhash()?;
+                let transaction = self
+                    .jupiter_swap
+                    .build_swap_transaction(route, &wallet, blockhash)
+                    .await
+                    .context(format!("Failed to build Jupiter leg {}", i + 1))?;
 
-                // CORRECT: tokens * (SOL/token) = SOL (with fee)
-                let tokens_sol = amount_in_2 as f64 / 1_000_000_000.0;
-                let sol_received = (tokens_sol * opportunity.prices[1]) * (1.0 - SWAP_FEE);
-                let expected_out_2 = (sol_received * LAMPORTS_PER_SOL as f64) as u64;
-                let min_out_2 =
-                    SwapExecutor::calculate_min_output_with_slippage(expected_out_2, 100);
+                // SHADOW MODE: transactions are built and signed but never submitted
+                if shadow_mode {
+                    return Ok(None);
+                }
 
-                info!(
-                    "   Leg 1: {} SOL → {} tokens on {} (min {})",
-                    capital_lamports as f64 / 1e9,
-                    expected_out_1,
-                    opportunity.dexs[0],
-                    min_out_1
-                );
-                info!(
-                    "   Leg 2: {} tokens → {} SOL on {} (min {})",
-                    amount_in_2,
-                    expected_out_2 as f64 / 1e9,
-                    opportunity.dexs[1],
-                    min_out_2
+                if self.emergency_stop.load(Ordering::SeqCst) {
+                    return Err(anyhow::anyhow!(
+                        "emergency stop active - Jupiter leg {} not sent",
+                        i + 1
+                    ));
+                }
+                let signature = rpc_client
+                    .send_versioned_transaction(&transaction)
+                    .context(format!("Failed to send Jupiter leg {}", i + 1))?;
+
+                match wait_for_leg_status(&rpc_client, &signature).await {
+                    Some(true) => {}
+                    Some(false) => {
+                        return Err(anyhow::anyhow!(
+                            "Jupiter leg {} failed on-chain: {}",
+                            i + 1,
+                            signature
+                        ))
+                    }
+                    None => {
+                        unconfirmed_leg = Some(signature);
+                        return Err(anyhow::anyhow!(
+                            "Jupiter leg {} not confirmed within {:?}: {}",
+                            i + 1,
+                            JUPITER_LEG_CONFIRM_TIMEOUT,
+                            signature
+                        ));
+                    }
+                }
+                let delivered =
+                    jupiter_leg_output(&rpc_client, &wallet.pubkey(), &signature, mints[i + 1])
+                        .context(format!("Jupiter leg {} output unreadable", i + 1))?;
+                Ok(Some((signature, delivered)))
+            }
+            .await;
+
+            match leg {
+                Ok(Some((signature, delivered))) => {
+                    info!(
+                        "   ✅ Jupiter leg {} confirmed: {} ({} base units out)",
+                        i + 1,
+                        signature,
+                        delivered
+                    );
+                    signatures.push(signature.to_string());
+                    held_amount = delivered;
+                }
+                Ok(None) => {
+                    info!("   👻 SHADOW: Built Jupiter leg {}, not submitting", i + 1);
+                    held_amount = routes[i].other_amount_threshold;
+                }
+                Err(e) => {
+                    // Earlier legs confirmed as their own transactions (or this one
+                    // may still land) - flatten whatever the wallet actually holds
+                    if (i > 0 || unconfirmed_leg.is_some()) && !shadow_mode {
+                        if let Some(signature) = unconfirmed_leg {
+                            warn!(
+                                "⚠️ Jupiter leg {} ({}) may still land after the unwind - check the wallet",
+                                i + 1,
+                                signature
+                            );
+                        }
+                        let _ = self
+                            .unwind_jupiter_position(
+                                &rpc_client,
+                                &wallet,
+                                &held_before,
+                                &signatures,
+                                triangle,
+                            )
+                            .await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        if shadow_mode {
+            return Ok(());
+        }
+
+        // Worst case if the balance change stays unreadable: the whole input plus leg fees
+        let worst_case_loss_sol = triangle.input_amount_sol
+            + (signatures.len() as u64 * JUPITER_LEG_FEE_LAMPORTS) as f64 / 1e9;
+        let trade = TradeRecord {
+            token_mint: triangle.token_a_mint.clone(),
+            buy_dex: triangle.dex_1.clone(),
+            sell_dex: triangle.dex_3.clone(),
+            buy_pool: triangle.pool_1_address.clone(),
+            sell_pool: triangle.pool_3_address.clone(),
+            spread_pct: triangle.profit_percentage,
+            position_size_sol: triangle.input_amount_sol,
+            estimated_profit_sol: triangle.profit_sol,
+            realized_profit_sol: None,
+            jito_tip_lamports: None, // Legs sent directly, not via JITO
+            tx_id: Some(signatures.join(";")),
+        };
+        Self::record_confirmed_trade(
+            &mut self.stats,
+            &mut self.profit_divergence,
+            &mut self.unsettled_trades,
+            Some(rpc_client.as_ref()),
+            &wallet,
+            self.trade_log.as_deref(),
+            UnsettledTrade {
+                jupiter: Some(triangle.clone()),
+                ..UnsettledTrade::landed(
+                    "Jupiter triangle".to_string(),
+                    signatures,
+                    trade,
+                    worst_case_loss_sol,
+                )
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Sell the intermediate tokens a partial Jupiter fill left back to the triangle's anchor
+    ///
+    /// Jupiter legs land as separate transactions (not one bundle), so a failed
+    /// later leg leaves the wallet holding the token bought by the earlier ones.
+    /// Direct DEX trades don't need this: all legs share one transaction/bundle.
+    ///
+    /// Each exit is sized from the wallet's actual balance - what the mint grew by
+    /// since before leg 1, so unrelated holdings are never sold - and counts only
+    /// once its transaction confirmed.
+    ///
+    /// # Arguments
+    /// * `held_before` - Each intermediate mint with its wallet balance before leg 1
+    /// * `leg_signatures` - Legs of the trade that confirmed (their spend and fees are part of the loss)
+    /// * `triangle` - The trade: anchor to exit into, and its input to compute the realized loss
+    ///
+    /// # Returns
+    /// Error if an exit could not be quoted, built, sent or confirmed (position still open)
+    async fn unwind_jupiter_position(
+        &mut self,
+        rpc_client: &SolanaRpcClient,
+        wallet: &Keypair,
+        held_before: &[(&str, u64)],
+        leg_signatures: &[String],
+        triangle: &SimpleTriangleOpportunity,
+    ) -> Result<()> {
+        let anchor = &triangle.anchor;
+        let mut exit_signatures = Vec::new();
+        let mut recovered = 0u64;
+
+        for &(held_mint, balance_before) in held_before {
+            let exit = async {
+                let balance = wallet_mint_balance(rpc_client, &wallet.pubkey(), held_mint)?;
+                let held_amount = balance.saturating_sub(balance_before);
+                if held_amount == 0 {
+                    return Ok(None);
+                }
+                warn!(
+                    "🔄 Partial fill - unwinding {} of {} back to {}",
+                    held_amount,
+                    self.token_metadata.label(held_mint),
+                    anchor.symbol
                 );
-                // FIX 1: Reject negative profit trades
-                let expected_profit_lamports = expected_out_2 as i64 - capital_lamports as i64;
-                if expected_profit_lamports <= 0 {
-                    warn!("⚠️ REJECTING trade with negative expected profit!");
+
+                let route = self
+                    .jupiter_swap
+                    .get_quote(held_mint, &anchor.mint, held_amount, UNWIND_SLIPPAGE_BPS)
+                    .await?;
+                let blockhash = rpc_client.get_latest_blockhash()?;
+                let transaction = self
+                    .jupiter_swap
+                    .build_swap_transaction(&route, wallet, blockhash)
+                    .await?;
+                let signature = rpc_client.send_versioned_transaction(&transaction)?;
+                if wait_for_leg_status(rpc_client, &signature).await != Some(true) {
+                    return Err(anyhow::anyhow!(
+                        "exit {} did not confirm ({} of {} may still be held)",
+                        signature,
+                        held_amount,
+                        held_mint
+                    ));
+                }
+                let out =
+                    jupiter_leg_output(rpc_client, &wallet.pubkey(), &signature, &anchor.mint)?;
+                Ok(Some((signature, out)))
+            }
+            .await;
+
+            match exit {
+                Ok(Some((signature, out))) => {
                     warn!(
-                        "   Initial capital: {:.6} SOL",
-                        capital_lamports as f64 / 1e9
+                        "   ↩️ Unwound {} ({}): recovered {} {} base units",
+                        self.token_metadata.label(held_mint),
+                        signature,
+                        out,
+                        anchor.symbol
                     );
-                    warn!("   Expected return: {:.6} SOL", expected_out_2 as f64 / 1e9);
-                    warn!(
-                        "   Expected profit: {:.6} SOL (LOSS!)",
-                        expected_profit_lamports as f64 / 1e9
+                    exit_signatures.push(signature.to_string());
+                    recovered = recovered.saturating_add(out);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.stats.unwind_failures += 1;
+                    error!(
+                        "🚨 UNWIND FAILED - still holding {}, manual exit required: {}",
+                        held_mint, e
                     );
-                    return Err(anyhow::anyhow!("Trade would result in a loss - rejecting"));
+                    return Err(e);
                 }
+            }
+        }
 
-                info!(
-                    "   Expected profit: {:.6} SOL",
-                    expected_profit_lamports as f64 / 1e9
-                );
+        if exit_signatures.is_empty() && leg_signatures.is_empty() {
+            // Nothing landed - nothing was spent
+            return Ok(());
+        }
 
-                let swap1 = SwapParams {
-                    amount_in: amount_in_1,
-                    minimum_amount_out: min_out_1,
-                    expected_amount_out: Some(expected_out_1),
-                    swap_a_to_b: true,
-                };
+        // Exact loss from the wallet's balance changes across legs and exits
+        let all_signatures: Vec<String> = leg_signatures
+            .iter()
+            .chain(&exit_signatures)
+            .cloned()
+            .collect();
+        let realized =
+            Self::read_jupiter_realized_profit(rpc_client, wallet, &all_signatures, triangle);
+        let loss_sol = match realized {
+            Some(realized) => -realized,
+            // Input spent minus confirmed exits (excludes fees)
+            None => triangle
+                .base_units_to_sol(i128::from(triangle.input_base_units()) - i128::from(recovered)),
+        };
+        self.stats.record_unwind(loss_sol);
+        warn!(
+            "   ↩️ Position unwound: recovered {} {} base units, realized loss {:.6} SOL",
+            recovered, anchor.symbol, loss_sol
+        );
+        Ok(())
+    }
 
-                let swap2 = SwapParams {
-                    amount_in: amount_in_2,
-                    minimum_amount_out: min_out_2,
-                    expected_amount_out: Some(expected_out_2),
-                    swap_a_to_b: false,
-                };
-
-                // For 2-leg, we use the same token as "middle leg" placeholder
-                let swap3 = SwapParams {
-                    amount_in: 0,
-                    minimum_amount_out: 0,
-                    expected_amount_out: None,
-                    swap_a_to_b: false,
-                };
-
-                // SECURITY FIX (2025-10-08): Build transaction with tip INSIDE (not as separate tx)
-                // Get random JITO tip account for load balancing
-                let tip_account = if let Some(ref client) = self.jito_client {
-                    client.get_random_tip_account()
-                } else {
-                    // Fallback to default if no JITO client (shouldn't happen)
-                    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"
-                        .parse()
-                        .unwrap()
-                };
+    /// Execute triangle arbitrage opportunity using real DEX swaps
+    ///
+    /// Takes over the caller's capital reservation: released once the attempt
+    /// ends (including build or submit errors), except for a queued JITO bundle,
+    /// whose `PendingBundle` holds it until `reconcile_bundle_outcomes` sees the
+    /// bundle land or fail.
+    ///
+    /// # Arguments
+    /// * `reserved_lamports` - Capital the caller reserved for this trade
+    async fn execute_triangle_opportunity(
+        &mut self,
+        opportunity: &crate::triangle_arbitrage::TriangleOpportunity,
+        reserved_lamports: u64,
+    ) -> ArbResult<()> {
+        // Journal real attempts: the body fills in pools, costs and the built transaction
+        if self.journal.is_some() && !self.config.paper_trading {
+            self.journal_draft = Some(JournalEntry::new(opportunity));
+        }
 
-                // Build transaction with tip INSIDE (SECURE method)
-                let transaction = executor
-                    .build_triangle_with_tip(
-                        (&dex_types[0], &pool_ids[0], &swap1),
-                        (&dex_types[1], &pool_ids[1], &swap2),
-                        (&dex_types[0], &pool_ids[0], &swap3), // Dummy third leg
-                        wallet.as_ref(),
-                        costs.jito_tip_lamports, // Tip included INSIDE transaction
-                        &tip_account,
-                    )
-                    .await?;
+        let result = self
+            .execute_triangle_attempt(opportunity, reserved_lamports)
+            .await;
+        if !matches!(result, Ok(TriangleExecution::Queued)) {
+            self.position_tracker.release_capital(reserved_lamports);
+        }
+        let result = result.map(|_| ());
 
-                info!(
-                    "🔒 SECURE: JITO tip ({} lamports) included INSIDE transaction",
-                    costs.jito_tip_lamports
+        if let (Some(journal), Some(mut entry)) = (&self.journal, self.journal_draft.take()) {
+            entry.finish(&result);
+            if let Some(path) = journal.record(&mut entry) {
+                debug!(
+                    "📓 Journaled attempt ({}): {}",
+                    entry.outcome,
+                    path.display()
                 );
-
-                // PERFORMANCE OPTIMIZATION (2025-10-12): Final simulation disabled
-                //
-                // Analysis: 2,043 final simulation rejections vs 0 staleness rejections
-                // Problem: Pool state changes in the 5-10ms between initial and final simulation
-                // Result: 0% JITO submission rate (everything rejected at final sim)
-                //
-                // Safety mechanisms still active:
-                // 1. ✅ 100ms staleness check (prevents old queued opportunities)
-                // 2. ✅ Initial simulation after building (validates instructions)
-                // 3. ✅ Cost validation (rejects unprofitable trades)
-                // 4. ✅ JITO's own validation (will reject bad bundles)
-                //
-                // Benefit: 5-10ms faster execution = less time for pool state to change
-                //
-                // /* COMMENTED OUT - Restore if JITO rejection rate > 30%
-                // if let Some(ref rpc) = self.rpc_client {
-                //     info!("🧪 Simulating transaction before JITO submission...");
-                //     let sim_result = match rpc.simulate_transaction(&transaction) {
-                //         Ok(success) => success,
-                //         Err(e) => {
-                //             warn!("Failed to simulate: {}", e);
-                //             false
-                //         }
-                //     };
-                //
-                //     if !sim_result {
-                //         warn!("❌ Transaction simulation failed - skipping JITO submission");
-                //         warn!("   This would have been a wasted submission slot");
-                //         return Ok(());
-                //     }
-                //     info!("✅ Simulation successful - proceeding with JITO submission");
-                // }
-                // */
-                // Submit via queue-based JITO submitter (non-blocking, rate-controlled)
-                if let Some(ref submitter) = self.jito_submitter {
-                    info!("💎 Submitting 2-leg arbitrage via queue-based JITO...");
-                    submitter
-                        .submit(
-                            vec![transaction],
-                            format!(
-                                "2-leg: {} → {} → {}",
-                                opportunity.path.first().unwrap_or(&"SOL".to_string()),
-                                opportunity.path.get(1).unwrap_or(&"?".to_string()),
-                                opportunity.path.first().unwrap_or(&"SOL".to_string())
-                            ),
-                            opportunity.estimated_profit_sol,
-                        )
-                        .await?;
-
-                    self.stats.opportunities_executed += 1;
-                    self.stats.total_profit_sol += opportunity.estimated_profit_sol;
-                    self.stats.consecutive_failures = 0;
-                    info!("✅ 2-leg arbitrage queued for JITO submission!");
-                    info!(
-                        "💵 Expected profit: {:.6} SOL",
-                        opportunity.estimated_profit_sol
-                    );
-                    return Ok(());
-                } else {
-                    // Fallback: execute directly (paper trading or no JITO)
-                    match executor
-                        .execute_triangle(
-                            (&dex_types[0], &pool_ids[0], &swap1),
-                            (&dex_types[1], &pool_ids[1], &swap2),
-                            (&dex_types[0], &pool_ids[0], &swap3),
-                            wallet.as_ref(),
-                            false,
-                        )
-                        .await
-                    {
-                        Ok(signature) => {
-                            self.stats.opportunities_executed += 1;
-                            self.stats.total_profit_sol += opportunity.estimated_profit_sol;
-                            self.stats.consecutive_failures = 0;
-                            info!("✅ 2-leg arbitrage executed successfully!");
-                            info!("💰 Transaction: {}", signature);
-                            return Ok(());
-                        }
-                        Err(e) => {
-                            self.stats.failed_executions += 1;
-                            self.stats.consecutive_failures += 1;
-                            warn!("⚠️ 2-leg arbitrage execution failed: {}", e);
-                            return Err(e);
-                        }
-                    }
-                }
             }
+        }
+        result
+    }
 
-            // Handle 3-leg triangle (SOL → TokenA → TokenB → SOL)
-            // Leg 1: SOL → TokenA
-            let amount_in_1 = capital_lamports;
-            let expected_out_1 = (amount_in_1 as f64 * opportunity.prices[0]) as u64;
-            let min_out_1 = SwapExecutor::calculate_min_output_with_slippage(expected_out_1, 100); // 1% slippage
+    /// One execution attempt of `execute_triangle_opportunity` (journaled by the caller)
+    ///
+    /// # Returns
+    /// `TriangleExecution::Queued` once a bundle holding `reserved_lamports` is pending
+    async fn execute_triangle_attempt(
+        &mut self,
+        opportunity: &crate::triangle_arbitrage::TriangleOpportunity,
+        reserved_lamports: u64,
+    ) -> ArbResult<TriangleExecution> {
+        debug!(
+            "🔺 Executing triangle opportunity: {:?} → {:.4} SOL profit",
+            opportunity.path, opportunity.estimated_profit_sol
+        );
 
-            // Leg 2: TokenA → TokenB
-            let amount_in_2 = expected_out_1;
-            let expected_out_2 = (amount_in_2 as f64 * opportunity.prices[1]) as u64;
-            let min_out_2 = SwapExecutor::calculate_min_output_with_slippage(expected_out_2, 100);
+        // COST VALIDATION: Verify profitability after ALL costs before execution with dynamic tip floor
+        // Calculate position size from config (same as in triangle detection)
+        // Two-leg pairs keep the position they were capped to at detection (pool depth)
+        let path_mints: Vec<&str> = opportunity.path.iter().map(String::as_str).collect();
+        let full_position_lamports = self.position_lamports_for(&path_mints);
+        let position_size_lamports = opportunity
+            .position_cap_lamports
+            .map_or(full_position_lamports, |cap| {
+                cap.min(full_position_lamports)
+            });
+        let position_size_sol = position_size_lamports as f64 / 1_000_000_000.0;
+        let gross_profit_lamports = (opportunity.estimated_profit_sol * 1_000_000_000.0) as u64;
+        let tip_floor = self.jito_tip_floor.read().await;
+        let costs = ArbitrageCosts::calculate(
+            position_size_lamports,
+            gross_profit_lamports,
+            true,
+            Some(&*tip_floor),
+            &self.config.tip_strategy,
+        );
+        if let Some(ref mut draft) = self.journal_draft {
+            draft.costs = Some(costs.clone());
+        }
 
-            // Leg 3: TokenB → SOL
-            let amount_in_3 = expected_out_2;
-            let expected_out_3 = (amount_in_3 as f64 * opportunity.prices[2]) as u64;
-            let min_out_3 = SwapExecutor::calculate_min_output_with_slippage(expected_out_3, 100);
+        let mut trade =
+            triangle_trade_record(opportunity, position_size_sol, costs.jito_tip_lamports);
 
-            // Build swap parameters for each leg
-            let swap1 = SwapParams {
-                amount_in: amount_in_1,
-                minimum_amount_out: min_out_1,
-                expected_amount_out: Some(expected_out_1),
-                swap_a_to_b: true, // SOL → TokenA
-            };
+        // Scale priority fee with current JITO congestion before building transactions
+        if let Some(ref mut executor) = self.swap_executor {
+            executor.set_compute_unit_price_from_congestion(&tip_floor);
+        }
 
-            let swap2 = SwapParams {
-                amount_in: amount_in_2,
-                minimum_amount_out: min_out_2,
-                expected_amount_out: Some(expected_out_2),
-                swap_a_to_b: true, // TokenA → TokenB
-            };
+        if !costs.is_profitable(gross_profit_lamports) {
+            debug!("⚠️ Triangle opportunity no longer profitable after cost calculation!");
+            debug!(
+                "   Gross profit: {:.6} SOL ({} lamports)",
+                opportunity.estimated_profit_sol, gross_profit_lamports
+            );
+            debug!(
+                "   Total costs: {:.6} SOL ({} lamports)",
+                costs.total_cost_lamports as f64 / 1e9,
+                costs.total_cost_lamports
+            );
+            debug!(
+                "   Net loss: {:.6} SOL",
+                costs.net_profit(gross_profit_lamports) as f64 / 1e9
+            );
+            Self::reject_trade(
+                self.trade_log.as_deref(),
+                &self.rejections,
+                &trade,
+                RejectionReason::UnprofitableAfterFees,
+            );
+            return Err(ArbError::Unprofitable(
+                "opportunity became unprofitable after cost validation".to_string(),
+            ));
+        }
 
-            let swap3 = SwapParams {
-                amount_in: amount_in_3,
-                minimum_amount_out: min_out_3,
-                expected_amount_out: Some(expected_out_3),
-                swap_a_to_b: false, // TokenB → SOL
-            };
+        // Tip ceiling guard: the dynamic tip can eat nearly all the margin under congestion
+        let net_profit = costs.net_profit(gross_profit_lamports);
+        let profit_floor = self.config.net_profit_floor_lamports();
+        if !costs.meets_net_profit_floor(gross_profit_lamports, profit_floor) {
+            warn!(
+                "🪙 Aborting triangle: net profit {} lamports below floor {} after JITO tip {} lamports",
+                net_profit, profit_floor, costs.jito_tip_lamports
+            );
+            debug!(
+                "   Gross: {} lamports, tip: {:.1}% of gross, gas: {} lamports",
+                gross_profit_lamports,
+                costs.jito_tip_lamports as f64 / gross_profit_lamports.max(1) as f64 * 100.0,
+                costs.base_tx_fee_lamports + costs.compute_fee_lamports
+            );
+            Self::reject_trade(
+                self.trade_log.as_deref(),
+                &self.rejections,
+                &trade,
+                RejectionReason::NetProfitBelowFloor,
+            );
+            return Err(ArbError::Unprofitable(format!(
+                "net profit {} lamports after JITO tip below floor {}",
+                net_profit, profit_floor
+            )));
+        }
 
-            // Execute triangle using swap executor
-            info!("🔺 Executing 3-leg triangle:");
+        let (gas_pct, tip_pct) = costs.gas_tip_ratio();
+        if self.config.log_verbosity.logs_cost_breakdowns() {
+            info!("💰 Cost validation passed:");
             info!(
-                "   Leg 1: {} lamports → {} (min {})",
-                amount_in_1, expected_out_1, min_out_1
+                "   Gross profit: {:.6} SOL",
+                opportunity.estimated_profit_sol
             );
             info!(
-                "   Leg 2: {} → {} (min {})",
-                amount_in_2, expected_out_2, min_out_2
+                "   JITO tip: {:.6} SOL ({:.1}%)",
+                costs.jito_tip_lamports as f64 / 1e9,
+                tip_pct
             );
             info!(
-                "   Leg 3: {} → {} SOL (min {})",
-                amount_in_3, expected_out_3, min_out_3
+                "   Gas fees: {:.6} SOL ({:.1}%)",
+                (costs.base_tx_fee_lamports + costs.compute_fee_lamports) as f64 / 1e9,
+                gas_pct
+            );
+            info!(
+                "   Net profit: {:.6} SOL ({:.1}% retention)",
+                net_profit as f64 / 1e9,
+                costs.retention_percentage(gross_profit_lamports)
             );
+        }
 
-            // SECURITY FIX (2025-10-08): Build transaction with tip INSIDE (not as separate tx)
-            // Get random JITO tip account for load balancing
-            let tip_account = if let Some(ref client) = self.jito_client {
-                client.get_random_tip_account()
+        // Paper trading mode: Simulate execution
+        if self.config.paper_trading {
+            info!("📄 Paper trading: Simulating triangle execution...");
+
+            // Deterministic fill, less likely the thinner the margin over costs
+            // (some opportunities will fail due to slippage, MEV, etc.)
+            let position = position_size_lamports.max(1) as f64;
+            let success = paper_model::paper_trade_succeeds(
+                self.config.paper_seed,
+                &format!(
+                    "{}|{}",
+                    opportunity.path.join(">"),
+                    opportunity.dexs.join(">")
+                ),
+                gross_profit_lamports as f64 / position * 100.0,
+                costs.total_cost_lamports as f64 / position * 100.0,
+            );
+
+            if success {
+                self.stats.opportunities_executed += 1;
+                self.stats.record_profit(opportunity.estimated_profit_sol);
+                self.stats.consecutive_failures = 0;
+                Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Paper);
+
+                info!("✅ Paper triangle executed successfully!");
+                info!(
+                    "💰 Paper profit: {:.6} SOL (Total: {:.6} SOL)",
+                    opportunity.estimated_profit_sol, self.stats.total_profit_sol
+                );
+
+                Ok(TriangleExecution::Completed)
             } else {
-                // Fallback to default if no JITO client (shouldn't happen)
-                "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"
-                    .parse()
-                    .unwrap()
-            };
+                self.stats.failed_executions += 1;
+                self.stats.consecutive_failures += 1;
+                Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Failed);
+                warn!("⚠️ Paper triangle execution failed (simulated slippage)");
+                Err(ArbError::SlippageExceeded(
+                    "paper trading: simulated execution failure".to_string(),
+                ))
+            }
+        }
+        // Real trading mode: Execute with swap executor
+        else if let (Some(ref mut executor), Some(ref wallet)) =
+            (&mut self.swap_executor, &self.wallet_keypair)
+        {
+            // CYCLE-5 FIX: Check RPC circuit breaker before trading
+            if let Err(e) = executor.check_circuit_breaker() {
+                error!("🚨 Cannot execute trade: {}", e);
+                self.rejections.record(RejectionReason::CircuitBreaker);
+                return Err(e.into());
+            }
 
-            // Build transaction with tip INSIDE (SECURE method)
-            let transaction = executor
-                .build_triangle_with_tip(
-                    (&dex_types[0], &pool_ids[0], &swap1),
-                    (&dex_types[1], &pool_ids[1], &swap2),
-                    (&dex_types[2], &pool_ids[2], &swap3),
-                    wallet.as_ref(),
-                    costs.jito_tip_lamports, // Tip included INSIDE transaction
-                    &tip_account,
-                )
-                .await?;
+            info!("💎 REAL TRADING: Building triangle swap with DEX instructions");
+            let mut latency = LatencyBreakdown::default();
 
-            info!(
-                "🔒 SECURE: JITO tip ({} lamports) included INSIDE transaction",
-                costs.jito_tip_lamports
-            );
+            // Extract pool IDs from DEX strings (e.g., "Meteora_DAMM_V2_81vA2wJx" → "81vA2wJx")
+            let pool_ids: Result<Vec<String>> = opportunity
+                .dexs
+                .iter()
+                .map(|dex| extract_pool_id(dex))
+                .collect();
 
-            // PERFORMANCE OPTIMIZATION (2025-10-12): Final simulation disabled
-            //
-            // Analysis: 2,043 final simulation rejections vs 0 staleness rejections
-            // Problem: Pool state changes in the 5-10ms between initial and final simulation
-            // Result: 0% JITO submission rate (everything rejected at final sim)
-            //
+            let pool_ids = match pool_ids {
+                Ok(ids) => ids,
+                Err(e) => {
+                    warn!("⚠️ Failed to extract pool IDs: {}", e);
+                    self.rejections.record(RejectionReason::InvalidOpportunity);
+                    return Err(ArbError::Rejected(format!("invalid pool IDs: {}", e)));
+                }
+            };
+
+            // Skip pools blacklisted after repeated simulation/execution failures
+            if let Some(ref pool_registry) = self.pool_registry {
+                if let Some(pool_id) = pool_ids
+                    .iter()
+                    .find(|pool_id| pool_registry.is_pool_blacklisted(pool_id))
+                {
+                    debug!("⛔ Skipping opportunity - pool {} is blacklisted", pool_id);
+                    self.rejections.record(RejectionReason::BlacklistedPool);
+                    return Err(ArbError::Rejected(format!(
+                        "pool {} is blacklisted",
+                        pool_id
+                    )));
+                }
+            }
+
+            // CRITICAL FIX: Validate all pool addresses can be resolved BEFORE execution
+            // This prevents wasting time building transactions for pools that don't exist
+            if let Some(ref pool_registry) = self.pool_registry {
+                debug!("🔍 Pre-validating {} pool addresses...", pool_ids.len());
+                let stage_start = Instant::now();
+
+                for (i, pool_id) in pool_ids.iter().enumerate() {
+                    let dex_type = DexType::from_dex_string(&opportunity.dexs[i])?;
+
+                    match pool_registry.resolve_pool_address(pool_id, &dex_type).await {
+                        Ok(pool_address) => {
+                            debug!(
+                                "  ✅ Pool {} resolved: {} ({})",
+                                i + 1,
+                                pool_id,
+                                pool_address
+                            );
+                            if let Some(ref mut draft) = self.journal_draft {
+                                draft.pool_addresses.push(pool_address.to_string());
+                            }
+                        }
+                        Err(e) => {
+                            warn!(
+                                "⚠️ Cannot resolve pool address for {} ({:?}): {}",
+                                pool_id, dex_type, e
+                            );
+                            warn!("   Skipping opportunity - pool lookup failed");
+                            self.rejections
+                                .record(RejectionReason::PoolResolutionFailed);
+                            if let Some(ArbError::PoolTypeMismatch(detail)) = ArbError::find(&e) {
+                                return Err(ArbError::PoolTypeMismatch(detail.clone()));
+                            }
+                            return Err(ArbError::PoolResolution(format!("{}: {}", pool_id, e)));
+                        }
+                    }
+                }
+
+                latency.pool_resolution = Some(stage_start.elapsed());
+                debug!(
+                    "✅ All {} pool addresses resolved successfully",
+                    pool_ids.len()
+                );
+            }
+
+            // GROK GHOST POOL SOLUTION - STEP 2: Validate pools before execution
+            // Check cache for each pool, batch-validate uncached pools
+
+            // MARKET CHAOS MODE - Skip ghost pool validation for speed
+            let skip_ghost_pool_check = crate::pool_registry::ghost_pool_check_disabled();
+
+            // PumpSwap pools don't have traditional pool accounts - skip ghost pool validation
+            let has_pumpswap = opportunity
+                .dexs
+                .iter()
+                .any(|dex| dex.contains("PumpSwap") || dex.contains("PumpFun"));
+
+            if skip_ghost_pool_check {
+                info!(
+                    "⚡ MARKET CHAOS MODE: Skipping ghost pool validation for ultra-fast execution"
+                );
+            } else if has_pumpswap {
+                debug!("🪙 PumpSwap pools detected - skipping ghost pool validation (uses bonding curve, not traditional pools)");
+            } else if let Some(ref pool_registry) = self.pool_registry {
+                debug!(
+                    "🔍 Validating {} pools for ghost pool check",
+                    pool_ids.len()
+                );
+                let stage_start = Instant::now();
+
+                let mut needs_validation = Vec::new();
+                for pool_id in &pool_ids {
+                    if pool_registry.is_pool_valid_cached(pool_id).await != Some(true) {
+                        needs_validation.push(pool_id.clone());
+                    }
+                }
+
+                // Batch-validate uncached pools
+                if !needs_validation.is_empty() {
+                    debug!(
+                        "🔍 Batch-validating {} uncached pools",
+                        needs_validation.len()
+                    );
+                    if let Err(e) = pool_registry.validate_pools_batch(&needs_validation).await {
+                        warn!("⚠️ Pool validation failed: {}", e);
+                        self.rejections
+                            .record(RejectionReason::PoolValidationFailed);
+                        return Err(ArbError::RpcError(format!("pool validation: {}", e)));
+                    }
+                }
+
+                // Re-check: Reject if ANY pool is invalid (ghost pool)
+                for pool_id in &pool_ids {
+                    if pool_registry.is_pool_valid_cached(pool_id).await != Some(true) {
+                        debug!(
+                            "⚠️ Ghost pool detected: {} (pool doesn't exist on-chain)",
+                            pool_id
+                        );
+                        debug!(
+                            "   Rejected opportunity: token {} on {:?}",
+                            opportunity.path[1], opportunity.dexs
+                        );
+                        self.rejections.record(RejectionReason::GhostPool);
+                        return Err(ArbError::GhostPool(pool_id.clone()));
+                    }
+                }
+
+                latency.pool_validation = Some(stage_start.elapsed());
+                debug!("✅ All {} pools validated successfully", pool_ids.len());
+            }
+
+            // Validate we have 2 or 3 DEXs (2-leg arbitrage or 3-leg triangle)
+            if pool_ids.len() < 2 || pool_ids.len() > 3 {
+                self.rejections.record(RejectionReason::InvalidOpportunity);
+                return Err(ArbError::Rejected(format!(
+                    "invalid opportunity: expected 2-3 DEXs, got {}",
+                    pool_ids.len()
+                )));
+            }
+
+            // Determine DEX types
+            let dex_types: Result<Vec<DexType>> = opportunity
+                .dexs
+                .iter()
+                .map(|dex| DexType::from_dex_string(dex))
+                .collect();
+
+            let dex_types = match dex_types {
+                Ok(types) => types,
+                Err(e) => {
+                    warn!("⚠️ Failed to parse DEX types: {}", e);
+                    return Err(e.into());
+                }
+            };
+
+            // CRITICAL FIX: Reserve SOL for fees before calculating position size
+            // Can't spend all capital - need to keep SOL for JITO tips + gas + DEX fees
+            // Same per-token, depth-capped size the costs were validated at
+            let gross_capital_lamports = position_size_lamports;
+
+            // Subtract all costs to get actual tradeable capital
+            let capital_lamports = gross_capital_lamports.saturating_sub(costs.total_cost_lamports);
+
+            if self.config.log_verbosity.logs_cost_breakdowns() {
+                info!("💰 Position sizing:");
+                info!(
+                    "   Gross capital: {:.6} SOL",
+                    gross_capital_lamports as f64 / 1e9
+                );
+                info!(
+                    "   Reserved for fees: {:.6} SOL",
+                    costs.total_cost_lamports as f64 / 1e9
+                );
+                info!(
+                    "   Tradeable capital: {:.6} SOL",
+                    capital_lamports as f64 / 1e9
+                );
+            }
+
+            // Only a native SOL input can be wrapped into the first leg
+            let input_mint = route_input_mint(&opportunity.path)?;
+            if input_mint != spl_token::native_mint::id() {
+                return Err(ArbError::Rejected(format!(
+                    "route starts from {} - only SOL-funded routes execute",
+                    input_mint
+                )));
+            }
+
+            // Handle 2-leg arbitrage (SOL → Token → SOL via different DEXs)
+            if pool_ids.len() == 2 {
+                info!("💱 Executing 2-leg arbitrage (cross-DEX same token):");
+
+                // GROK FIX: Correct profit calculation matching detection logic
+                const SWAP_FEE: f64 = 0.0025; // 0.25% per leg
+
+                // Token base units depend on the mint (USDC = 6, most memecoins = 6/9)
+                let token_mint = opportunity
+                    .path
+                    .get(1)
+                    .ok_or_else(|| anyhow::anyhow!("2-leg path missing token mint"))?;
+                let token_decimals = match self.rpc_client {
+                    Some(ref rpc) => {
+                        let mint = token_mint
+                            .parse()
+                            .context(format!("Invalid token mint: {}", token_mint))?;
+                        rpc.get_mint(&mint)?.decimals
+                    }
+                    None => {
+                        return Err(ArbError::RpcError(
+                            "RPC client required to fetch token decimals".to_string(),
+                        ))
+                    }
+                };
+
+                // Leg 1: SOL → Token (buy on DEX A), Leg 2: Token → SOL (sell on DEX B)
+                let amount_in_1 = capital_lamports;
+                let (expected_out_1, expected_out_2) = two_leg_expected_outputs(
+                    amount_in_1,
+                    opportunity.prices[0],
+                    opportunity.prices[1],
+                    token_decimals,
+                    SWAP_FEE,
+                );
+                // Dynamic slippage from cross-DEX price dispersion + spread
+                // Falls back to DEFAULT_SLIPPAGE_BPS when there's no volatility data
+                let token_prices: Vec<f64> = self
+                    .shredstream_client
+                    .get_token_prices(token_mint)
+                    .into_iter()
+                    .map(|(_, price)| price)
+                    .collect();
+                let slippage_bps = (slippage::dynamic_slippage_tolerance(
+                    slippage::price_dispersion_pct(&token_prices),
+                    opportunity.profit_percentage,
+                    self.config.default_slippage_bps,
+                ) * 10_000.0)
+                    .round() as u64;
+                // Each leg then widens by its own pool's price impact
+                let leg_slippage = per_leg_slippage_bps(
+                    executor,
+                    self.pool_registry.as_deref(),
+                    &pool_ids,
+                    slippage_bps,
+                    capital_lamports,
+                    self.config.max_leg_slippage_bps as u64,
+                );
+                info!("   🎚️ Slippage tolerance: {:?} bps per leg", leg_slippage);
+                let min_out_1 = SwapExecutor::calculate_min_output_with_slippage(
+                    expected_out_1,
+                    leg_slippage[0],
+                );
+                let amount_in_2 = expected_out_1;
+                let min_out_2 = SwapExecutor::calculate_min_output_with_slippage(
+                    expected_out_2,
+                    leg_slippage[1],
+                );
+
+                info!(
+                    "   Leg 1: {} SOL → {} tokens on {} (min {})",
+                    capital_lamports as f64 / 1e9,
+                    expected_out_1,
+                    opportunity.dexs[0],
+                    min_out_1
+                );
+                info!(
+                    "   Leg 2: {} tokens → {} SOL on {} (min {})",
+                    amount_in_2,
+                    expected_out_2 as f64 / 1e9,
+                    opportunity.dexs[1],
+                    min_out_2
+                );
+                // FIX 1: Reject negative profit trades
+                let expected_profit_lamports =
+                    match checked_expected_profit(capital_lamports, expected_out_2) {
+                        Ok(profit) => profit,
+                        Err(e) => {
+                            self.rejections.record(RejectionReason::NegativeProfit);
+                            return Err(e);
+                        }
+                    };
+
+                info!(
+                    "   Expected profit: {:.6} SOL",
+                    expected_profit_lamports as f64 / 1e9
+                );
+
+                let swap1 = SwapParams {
+                    amount_in: amount_in_1,
+                    minimum_amount_out: min_out_1,
+                    expected_amount_out: Some(expected_out_1),
+                    swap_a_to_b: true,
+                };
+
+                let swap2 = SwapParams {
+                    amount_in: amount_in_2,
+                    minimum_amount_out: min_out_2,
+                    expected_amount_out: Some(expected_out_2),
+                    swap_a_to_b: false,
+                };
+
+                // For 2-leg, we use the same token as "middle leg" placeholder
+                let swap3 = SwapParams {
+                    amount_in: 0,
+                    minimum_amount_out: 0,
+                    expected_amount_out: None,
+                    swap_a_to_b: false,
+                };
+
+                if self.config.reserve_recheck {
+                    if let Err(e) = Self::recheck_reserves(
+                        executor,
+                        &[
+                            (&dex_types[0], pool_ids[0].as_str(), &swap1),
+                            (&dex_types[1], pool_ids[1].as_str(), &swap2),
+                        ],
+                        &costs,
+                        self.config.net_profit_floor_lamports(),
+                    ) {
+                        Self::reject_trade(
+                            self.trade_log.as_deref(),
+                            &self.rejections,
+                            &trade,
+                            RejectionReason::ReserveDrift,
+                        );
+                        return Err(e);
+                    }
+                }
+
+                // SECURITY FIX (2025-10-08): Build transaction with tip INSIDE (not as separate tx)
+                // Get random JITO tip account for load balancing
+                let tip_account = if let Some(ref client) = self.jito_client {
+                    client.get_random_tip_account()
+                } else {
+                    // Fallback to default if no JITO client (shouldn't happen)
+                    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"
+                        .parse()
+                        .unwrap()
+                };
+
+                // Build transaction with tip INSIDE (SECURE method)
+                let transaction = executor
+                    .build_triangle_with_tip_timed(
+                        &input_mint,
+                        [
+                            (&dex_types[0], &pool_ids[0], &swap1),
+                            (&dex_types[1], &pool_ids[1], &swap2),
+                            (&dex_types[0], &pool_ids[0], &swap3), // Dummy third leg
+                        ],
+                        wallet.as_ref(),
+                        JitoTip {
+                            // Tip included INSIDE transaction
+                            lamports: costs.jito_tip_lamports,
+                            account: tip_account,
+                        },
+                        &mut latency,
+                    )
+                    .await
+                    .inspect_err(|e| {
+                        Self::record_build_failure(
+                            &mut self.stats,
+                            self.pool_registry.as_deref(),
+                            &self.rejections,
+                            self.trade_log.as_deref(),
+                            &opportunity.dexs,
+                            &trade,
+                            e,
+                        );
+                    })?;
+
+                info!(
+                    "🔒 SECURE: JITO tip ({} lamports) included INSIDE transaction",
+                    costs.jito_tip_lamports
+                );
+                if let Some(ref mut draft) = self.journal_draft {
+                    draft.record_build(
+                        &[
+                            (&dex_types[0], pool_ids[0].as_str(), &swap1),
+                            (&dex_types[1], pool_ids[1].as_str(), &swap2),
+                        ],
+                        &tip_account,
+                        &transaction,
+                    );
+                }
+
+                // PERFORMANCE OPTIMIZATION (2025-10-12): Final simulation disabled
+                //
+                // Analysis: 2,043 final simulation rejections vs 0 staleness rejections
+                // Problem: Pool state changes in the 5-10ms between initial and final simulation
+                // Result: 0% JITO submission rate (everything rejected at final sim)
+                //
+                // Safety mechanisms still active:
+                // 1. ✅ 100ms staleness check (prevents old queued opportunities)
+                // 2. ✅ Initial simulation after building (validates instructions)
+                // 3. ✅ Cost validation (rejects unprofitable trades)
+                // 4. ✅ JITO's own validation (will reject bad bundles)
+                //
+                // Benefit: 5-10ms faster execution = less time for pool state to change
+                //
+                // /* COMMENTED OUT - Restore if JITO rejection rate > 30%
+                // if let Some(ref rpc) = self.rpc_client {
+                //     info!("🧪 Simulating transaction before JITO submission...");
+                //     let sim_result = match rpc.simulate_transaction(&transaction) {
+                //         Ok(success) => success,
+                //         Err(e) => {
+                //             warn!("Failed to simulate: {}", e);
+                //             false
+                //         }
+                //     };
+                //
+                //     if !sim_result {
+                //         warn!("❌ Transaction simulation failed - skipping JITO submission");
+                //         warn!("   This would have been a wasted submission slot");
+                //         return Ok(());
+                //     }
+                //     info!("✅ Simulation successful - proceeding with JITO submission");
+                // }
+                // */
+                // PROFIT_VERIFY_SIM: re-check profit against simulated leg outputs
+                if self.config.profit_verify_sim {
+                    if let Err(e) = Self::verify_simulated_profit(
+                        self.rpc_client.as_deref(),
+                        self.pool_registry.as_deref(),
+                        &wallet.pubkey(),
+                        &transaction,
+                        &[
+                            (pool_ids[0].as_str(), &swap1),
+                            (pool_ids[1].as_str(), &swap2),
+                        ],
+                        &costs,
+                    ) {
+                        warn!("❌ 2-leg profit verification rejected trade: {}", e);
+                        Self::reject_trade(
+                            self.trade_log.as_deref(),
+                            &self.rejections,
+                            &trade,
+                            RejectionReason::SimulatedUnprofitable,
+                        );
+                        return Err(e);
+                    }
+                }
+
+                // SHADOW MODE: simulate the real transaction and stop before submission
+                if self.config.shadow_mode {
+                    return Self::shadow_simulate(
+                        self.rpc_client.as_deref(),
+                        self.pool_registry.as_deref(),
+                        &mut self.stats,
+                        &transaction,
+                        &opportunity.dexs,
+                        "2-leg",
+                        opportunity.estimated_profit_sol,
+                    );
+                }
+
+                // Submit via queue-based JITO submitter (non-blocking, rate-controlled)
+                if let Some(ref submitter) = self.jito_submitter {
+                    // Skip if this token/pool pair is already in flight
+                    let dedup_key = RecentSubmissions::key(
+                        opportunity.path.get(1).map(String::as_str).unwrap_or("?"),
+                        &pool_ids[0],
+                        &pool_ids[1],
+                    );
+                    if self.recent_submissions.contains(&dedup_key) {
+                        debug!("🔁 Skipping duplicate 2-leg submission: {}", dedup_key);
+                        self.rejections.record(RejectionReason::Duplicate);
+                        return Err(ArbError::Rejected(format!(
+                            "duplicate submission suppressed: {}",
+                            dedup_key
+                        )));
+                    }
+
+                    if self.emergency_stop.load(Ordering::SeqCst) {
+                        warn!("🚨 Emergency stop active - 2-leg submission aborted");
+                        self.rejections.record(RejectionReason::EmergencyStop);
+                        return Err(ArbError::Rejected("emergency stop active".to_string()));
+                    }
+
+                    // SPLIT_LEG_BUNDLES: one transaction per leg, tip shared across them
+                    // (the combined transaction above is what passed verification)
+                    let bundle: Vec<VersionedTransaction> = if self.config.split_leg_bundles {
+                        executor
+                            .build_leg_bundle_with_tip(
+                                &input_mint,
+                                &[
+                                    (&dex_types[0], pool_ids[0].as_str(), &swap1),
+                                    (&dex_types[1], pool_ids[1].as_str(), &swap2),
+                                ],
+                                wallet.as_ref(),
+                                costs.jito_tip_lamports,
+                                &tip_account,
+                            )
+                            .await
+                            .inspect_err(|e| {
+                                Self::record_build_failure(
+                                    &mut self.stats,
+                                    self.pool_registry.as_deref(),
+                                    &self.rejections,
+                                    self.trade_log.as_deref(),
+                                    &opportunity.dexs,
+                                    &trade,
+                                    e,
+                                );
+                            })?
+                            .into_iter()
+                            .map(Into::into)
+                            .collect()
+                    } else {
+                        vec![transaction.into()]
+                    };
+
+                    // Daily tip budget may run out mid-scan (concurrent or back-to-back executions)
+                    if self
+                        .stats
+                        .tip_budget_exhausted(self.config.max_daily_tip_budget_sol)
+                    {
+                        warn!(
+                            "💸 2-leg submission blocked: daily JITO tip budget spent ({:.6} SOL)",
+                            self.stats.daily_tips_spent_sol
+                        );
+                        Self::reject_trade(
+                            self.trade_log.as_deref(),
+                            &self.rejections,
+                            &trade,
+                            RejectionReason::TipBudgetExhausted,
+                        );
+                        return Err(ArbError::Rejected(
+                            "daily JITO tip budget exhausted".to_string(),
+                        ));
+                    }
+
+                    // Wallet must keep in-flight tips + base fee reserve after this bundle pays
+                    if let Err(e) = self.position_tracker.reserve_tip(costs.jito_tip_lamports) {
+                        warn!("⛽ 2-leg submission blocked: {}", e);
+                        self.rejections.record(RejectionReason::FeeReserve);
+                        return Err(e);
+                    }
+
+                    info!("💎 Submitting 2-leg arbitrage via queue-based JITO...");
+                    let stage_start = Instant::now();
+                    submit_leg_bundle(
+                        submitter.as_ref(),
+                        bundle,
+                        format!(
+                            "2-leg: {} → {} → {}",
+                            opportunity.path.first().unwrap_or(&"SOL".to_string()),
+                            opportunity.path.get(1).unwrap_or(&"?".to_string()),
+                            opportunity.path.first().unwrap_or(&"SOL".to_string())
+                        ),
+                        opportunity.estimated_profit_sol,
+                        dedup_key.clone(),
+                    )
+                    .await
+                    .inspect_err(|_| {
+                        self.position_tracker.release_tip(costs.jito_tip_lamports);
+                        Self::record_triangle_outcome(
+                            &mut self.stats,
+                            self.pool_registry.as_deref(),
+                            &opportunity.dexs,
+                            false,
+                        );
+                        Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Failed);
+                    })?;
+                    latency.queueing = Some(stage_start.elapsed());
+                    self.metrics.observe_latency_breakdown(&latency);
+                    debug!("⏱️ 2-leg latency: {}", latency.summary());
+                    self.recent_submissions.insert(dedup_key.clone());
+                    self.stats.record_tip(costs.jito_tip_lamports);
+
+                    // Profit/executed counters update only once the bundle lands
+                    trade.tx_id = Some(dedup_key.clone());
+                    Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Submitted);
+                    self.pending_bundles.insert(
+                        dedup_key,
+                        PendingBundle::new(
+                            opportunity,
+                            trade,
+                            &costs,
+                            reserved_lamports,
+                            position_size_lamports,
+                            net_profit as f64 / 1e9,
+                        ),
+                    );
+                    info!("✅ 2-leg arbitrage queued for JITO submission!");
+                    info!(
+                        "💵 Expected profit: {:.6} SOL",
+                        opportunity.estimated_profit_sol
+                    );
+                    return Ok(TriangleExecution::Queued);
+                } else {
+                    // Fallback: execute directly (paper trading or no JITO)
+                    match executor
+                        .execute_triangle(
+                            &input_mint,
+                            [
+                                (&dex_types[0], &pool_ids[0], &swap1),
+                                (&dex_types[1], &pool_ids[1], &swap2),
+                                (&dex_types[0], &pool_ids[0], &swap3),
+                            ],
+                            wallet.as_ref(),
+                            false,
+                        )
+                        .await
+                    {
+                        Ok(signature) => {
+                            // Returned only once the transaction confirmed
+                            Self::record_triangle_outcome(
+                                &mut self.stats,
+                                self.pool_registry.as_deref(),
+                                &opportunity.dexs,
+                                true,
+                            );
+                            trade.tx_id = Some(signature.to_string());
+                            Self::record_confirmed_trade(
+                                &mut self.stats,
+                                &mut self.profit_divergence,
+                                &mut self.unsettled_trades,
+                                self.rpc_client.as_deref(),
+                                wallet.as_ref(),
+                                self.trade_log.as_deref(),
+                                UnsettledTrade::landed(
+                                    signature.to_string(),
+                                    vec![signature.to_string()],
+                                    trade,
+                                    costs.worst_case_loss_lamports(position_size_lamports) as f64
+                                        / 1e9,
+                                ),
+                            );
+                            info!("✅ 2-leg arbitrage executed successfully!");
+                            info!("💰 Transaction: {}", signature);
+                            return Ok(TriangleExecution::Completed);
+                        }
+                        Err(e) => {
+                            self.stats.failed_executions += 1;
+                            self.stats.consecutive_failures += 1;
+                            Self::record_triangle_outcome(
+                                &mut self.stats,
+                                self.pool_registry.as_deref(),
+                                &opportunity.dexs,
+                                false,
+                            );
+                            Self::log_trade(
+                                self.trade_log.as_deref(),
+                                &trade,
+                                TradeOutcome::Failed,
+                            );
+                            warn!("⚠️ 2-leg arbitrage execution failed: {}", e);
+                            return Err(e.into());
+                        }
+                    }
+                }
+            }
+
+            // Handle 3-leg triangle (SOL → TokenA → TokenB → SOL)
+            // LEG_SLIPPAGE_BPS per leg, widened by each pool's depth (thin middle legs get more room)
+            let leg_slippage = per_leg_slippage_bps(
+                executor,
+                self.pool_registry.as_deref(),
+                &pool_ids,
+                self.config.leg_slippage_bps as u64,
+                capital_lamports,
+                self.config.max_leg_slippage_bps as u64,
+            );
+            info!("   🎚️ Slippage tolerance: {:?} bps per leg", leg_slippage);
+
+            // Leg 1: SOL → TokenA, Leg 2: TokenA → TokenB, Leg 3: TokenB → SOL
+            let amount_in_1 = capital_lamports;
+            let (expected_out_1, expected_out_2, expected_out_3) =
+                three_leg_expected_outputs(amount_in_1, &opportunity.prices);
+            let amount_in_2 = expected_out_1;
+            let amount_in_3 = expected_out_2;
+
+            // Same final check as the 2-leg path: detection-time profit may have
+            // evaporated by now, and a triangle must never be submitted at a loss
+            if let Err(e) = checked_expected_profit(capital_lamports, expected_out_3) {
+                self.rejections.record(RejectionReason::NegativeProfit);
+                return Err(e);
+            }
+
+            let min_out_1 =
+                SwapExecutor::calculate_min_output_with_slippage(expected_out_1, leg_slippage[0]);
+            let min_out_2 =
+                SwapExecutor::calculate_min_output_with_slippage(expected_out_2, leg_slippage[1]);
+            let min_out_3 =
+                SwapExecutor::calculate_min_output_with_slippage(expected_out_3, leg_slippage[2]);
+
+            // Build swap parameters for each leg
+            let swap1 = SwapParams {
+                amount_in: amount_in_1,
+                minimum_amount_out: min_out_1,
+                expected_amount_out: Some(expected_out_1),
+                swap_a_to_b: true, // SOL → TokenA
+            };
+
+            let swap2 = SwapParams {
+                amount_in: amount_in_2,
+                minimum_amount_out: min_out_2,
+                expected_amount_out: Some(expected_out_2),
+                swap_a_to_b: true, // TokenA → TokenB
+            };
+
+            let swap3 = SwapParams {
+                amount_in: amount_in_3,
+                minimum_amount_out: min_out_3,
+                expected_amount_out: Some(expected_out_3),
+                swap_a_to_b: false, // TokenB → SOL
+            };
+
+            // Execute triangle using swap executor
+            info!("🔺 Executing 3-leg triangle:");
+            info!(
+                "   Leg 1: {} lamports → {} (min {})",
+                amount_in_1, expected_out_1, min_out_1
+            );
+            info!(
+                "   Leg 2: {} → {} (min {})",
+                amount_in_2, expected_out_2, min_out_2
+            );
+            info!(
+                "   Leg 3: {} → {} SOL (min {})",
+                amount_in_3, expected_out_3, min_out_3
+            );
+
+            if self.config.reserve_recheck {
+                if let Err(e) = Self::recheck_reserves(
+                    executor,
+                    &[
+                        (&dex_types[0], pool_ids[0].as_str(), &swap1),
+                        (&dex_types[1], pool_ids[1].as_str(), &swap2),
+                        (&dex_types[2], pool_ids[2].as_str(), &swap3),
+                    ],
+                    &costs,
+                    self.config.net_profit_floor_lamports(),
+                ) {
+                    Self::reject_trade(
+                        self.trade_log.as_deref(),
+                        &self.rejections,
+                        &trade,
+                        RejectionReason::ReserveDrift,
+                    );
+                    return Err(e);
+                }
+            }
+
+            // SECURITY FIX (2025-10-08): Build transaction with tip INSIDE (not as separate tx)
+            // Get random JITO tip account for load balancing
+            let tip_account = if let Some(ref client) = self.jito_client {
+                client.get_random_tip_account()
+            } else {
+                // Fallback to default if no JITO client (shouldn't happen)
+                "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"
+                    .parse()
+                    .unwrap()
+            };
+
+            // Build transaction with tip INSIDE (SECURE method)
+            let transaction = executor
+                .build_triangle_with_tip_timed(
+                    &input_mint,
+                    [
+                        (&dex_types[0], &pool_ids[0], &swap1),
+                        (&dex_types[1], &pool_ids[1], &swap2),
+                        (&dex_types[2], &pool_ids[2], &swap3),
+                    ],
+                    wallet.as_ref(),
+                    JitoTip {
+                        // Tip included INSIDE transaction
+                        lamports: costs.jito_tip_lamports,
+                        account: tip_account,
+                    },
+                    &mut latency,
+                )
+                .await
+                .inspect_err(|e| {
+                    Self::record_build_failure(
+                        &mut self.stats,
+                        self.pool_registry.as_deref(),
+                        &self.rejections,
+                        self.trade_log.as_deref(),
+                        &opportunity.dexs,
+                        &trade,
+                        e,
+                    );
+                })?;
+
+            info!(
+                "🔒 SECURE: JITO tip ({} lamports) included INSIDE transaction",
+                costs.jito_tip_lamports
+            );
+            if let Some(ref mut draft) = self.journal_draft {
+                draft.record_build(
+                    &[
+                        (&dex_types[0], pool_ids[0].as_str(), &swap1),
+                        (&dex_types[1], pool_ids[1].as_str(), &swap2),
+                        (&dex_types[2], pool_ids[2].as_str(), &swap3),
+                    ],
+                    &tip_account,
+                    &transaction,
+                );
+            }
+
+            // PERFORMANCE OPTIMIZATION (2025-10-12): Final simulation disabled
+            //
+            // Analysis: 2,043 final simulation rejections vs 0 staleness rejections
+            // Problem: Pool state changes in the 5-10ms between initial and final simulation
+            // Result: 0% JITO submission rate (everything rejected at final sim)
+            //
             // Safety mechanisms still active:
             // 1. ✅ 100ms staleness check (prevents old queued opportunities)
             // 2. ✅ Initial simulation after building (validates instructions)
@@ -1834,71 +5375,1699 @@ impl ArbitrageEngine {
             //     info!("✅ Triangle simulation successful - proceeding with JITO submission");
             // }
             // */
+            // PROFIT_VERIFY_SIM: re-check profit against simulated leg outputs
+            if self.config.profit_verify_sim {
+                if let Err(e) = Self::verify_simulated_profit(
+                    self.rpc_client.as_deref(),
+                    self.pool_registry.as_deref(),
+                    &wallet.pubkey(),
+                    &transaction,
+                    &[
+                        (pool_ids[0].as_str(), &swap1),
+                        (pool_ids[1].as_str(), &swap2),
+                        (pool_ids[2].as_str(), &swap3),
+                    ],
+                    &costs,
+                ) {
+                    warn!("❌ 3-leg profit verification rejected trade: {}", e);
+                    Self::reject_trade(
+                        self.trade_log.as_deref(),
+                        &self.rejections,
+                        &trade,
+                        RejectionReason::SimulatedUnprofitable,
+                    );
+                    return Err(e);
+                }
+            }
+
+            // SHADOW MODE: simulate the real transaction and stop before submission
+            if self.config.shadow_mode {
+                return Self::shadow_simulate(
+                    self.rpc_client.as_deref(),
+                    self.pool_registry.as_deref(),
+                    &mut self.stats,
+                    &transaction,
+                    &opportunity.dexs,
+                    "3-leg",
+                    opportunity.estimated_profit_sol,
+                );
+            }
+
             // Submit via queue-based JITO submitter (non-blocking, rate-controlled)
             if let Some(ref submitter) = self.jito_submitter {
-                info!("💎 Submitting 3-leg triangle via queue-based JITO...");
-                submitter
-                    .submit(
-                        vec![transaction],
-                        format!(
-                            "Triangle: {} → {} → {} → {}",
-                            opportunity.path.first().unwrap_or(&"SOL".to_string()),
-                            opportunity.path.get(1).unwrap_or(&"?".to_string()),
-                            opportunity.path.get(2).unwrap_or(&"?".to_string()),
-                            "SOL"
-                        ),
-                        opportunity.estimated_profit_sol,
-                    )
-                    .await?;
+                // Skip if this route is already in flight (intermediate mints/pools joined)
+                let dedup_key = RecentSubmissions::key(
+                    &format!(
+                        "{}/{}",
+                        opportunity.path.get(1).map(String::as_str).unwrap_or("?"),
+                        opportunity.path.get(2).map(String::as_str).unwrap_or("?")
+                    ),
+                    &pool_ids[0],
+                    &pool_ids[1..].join("/"),
+                );
+                if self.recent_submissions.contains(&dedup_key) {
+                    debug!("🔁 Skipping duplicate 3-leg submission: {}", dedup_key);
+                    self.rejections.record(RejectionReason::Duplicate);
+                    return Err(ArbError::Rejected(format!(
+                        "duplicate submission suppressed: {}",
+                        dedup_key
+                    )));
+                }
 
-                self.stats.opportunities_executed += 1;
-                self.stats.total_profit_sol += opportunity.estimated_profit_sol;
-                self.stats.consecutive_failures = 0;
+                if self.emergency_stop.load(Ordering::SeqCst) {
+                    warn!("🚨 Emergency stop active - 3-leg submission aborted");
+                    self.rejections.record(RejectionReason::EmergencyStop);
+                    return Err(ArbError::Rejected("emergency stop active".to_string()));
+                }
+
+                // SPLIT_LEG_BUNDLES: one transaction per leg, tip shared across them
+                // (the combined transaction above is what passed verification)
+                let bundle: Vec<VersionedTransaction> = if self.config.split_leg_bundles {
+                    executor
+                        .build_leg_bundle_with_tip(
+                            &input_mint,
+                            &[
+                                (&dex_types[0], pool_ids[0].as_str(), &swap1),
+                                (&dex_types[1], pool_ids[1].as_str(), &swap2),
+                                (&dex_types[2], pool_ids[2].as_str(), &swap3),
+                            ],
+                            wallet.as_ref(),
+                            costs.jito_tip_lamports,
+                            &tip_account,
+                        )
+                        .await
+                        .inspect_err(|e| {
+                            Self::record_build_failure(
+                                &mut self.stats,
+                                self.pool_registry.as_deref(),
+                                &self.rejections,
+                                self.trade_log.as_deref(),
+                                &opportunity.dexs,
+                                &trade,
+                                e,
+                            );
+                        })?
+                        .into_iter()
+                        .map(Into::into)
+                        .collect()
+                } else {
+                    vec![transaction.into()]
+                };
+
+                // Daily tip budget may run out mid-scan (concurrent or back-to-back executions)
+                if self
+                    .stats
+                    .tip_budget_exhausted(self.config.max_daily_tip_budget_sol)
+                {
+                    warn!(
+                        "💸 3-leg submission blocked: daily JITO tip budget spent ({:.6} SOL)",
+                        self.stats.daily_tips_spent_sol
+                    );
+                    Self::reject_trade(
+                        self.trade_log.as_deref(),
+                        &self.rejections,
+                        &trade,
+                        RejectionReason::TipBudgetExhausted,
+                    );
+                    return Err(ArbError::Rejected(
+                        "daily JITO tip budget exhausted".to_string(),
+                    ));
+                }
+
+                // Wallet must keep in-flight tips + base fee reserve after this bundle pays
+                if let Err(e) = self.position_tracker.reserve_tip(costs.jito_tip_lamports) {
+                    warn!("⛽ 3-leg submission blocked: {}", e);
+                    self.rejections.record(RejectionReason::FeeReserve);
+                    return Err(e);
+                }
+
+                info!("💎 Submitting 3-leg triangle via queue-based JITO...");
+                let stage_start = Instant::now();
+                submit_leg_bundle(
+                    submitter.as_ref(),
+                    bundle,
+                    format!(
+                        "Triangle: {} → {} → {} → {}",
+                        opportunity.path.first().unwrap_or(&"SOL".to_string()),
+                        opportunity.path.get(1).unwrap_or(&"?".to_string()),
+                        opportunity.path.get(2).unwrap_or(&"?".to_string()),
+                        "SOL"
+                    ),
+                    opportunity.estimated_profit_sol,
+                    dedup_key.clone(),
+                )
+                .await
+                .inspect_err(|_| {
+                    self.position_tracker.release_tip(costs.jito_tip_lamports);
+                    Self::record_triangle_outcome(
+                        &mut self.stats,
+                        self.pool_registry.as_deref(),
+                        &opportunity.dexs,
+                        false,
+                    );
+                    Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Failed);
+                })?;
+                latency.queueing = Some(stage_start.elapsed());
+                self.metrics.observe_latency_breakdown(&latency);
+                debug!("⏱️ 3-leg latency: {}", latency.summary());
+                self.recent_submissions.insert(dedup_key.clone());
+                self.stats.record_tip(costs.jito_tip_lamports);
+
+                // Profit/executed counters update only once the bundle lands
+                trade.tx_id = Some(dedup_key.clone());
+                Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Submitted);
+                self.pending_bundles.insert(
+                    dedup_key,
+                    PendingBundle::new(
+                        opportunity,
+                        trade,
+                        &costs,
+                        reserved_lamports,
+                        position_size_lamports,
+                        net_profit as f64 / 1e9,
+                    ),
+                );
 
                 info!("✅ 3-leg triangle queued for JITO submission!");
                 info!(
-                    "💰 Expected profit: {:.6} SOL (Total: {:.6} SOL)",
+                    "💰 Expected profit: {:.6} SOL (Total realized: {:.6} SOL)",
                     opportunity.estimated_profit_sol, self.stats.total_profit_sol
                 );
 
-                Ok(())
+                Ok(TriangleExecution::Queued)
             } else {
                 // Fallback: execute directly (paper trading or no JITO)
                 match executor
                     .execute_triangle(
-                        (&dex_types[0], &pool_ids[0], &swap1),
-                        (&dex_types[1], &pool_ids[1], &swap2),
-                        (&dex_types[2], &pool_ids[2], &swap3),
+                        &input_mint,
+                        [
+                            (&dex_types[0], &pool_ids[0], &swap1),
+                            (&dex_types[1], &pool_ids[1], &swap2),
+                            (&dex_types[2], &pool_ids[2], &swap3),
+                        ],
                         wallet.as_ref(),
                         false,
                     )
                     .await
                 {
                     Ok(signature) => {
-                        self.stats.opportunities_executed += 1;
-                        self.stats.total_profit_sol += opportunity.estimated_profit_sol;
-                        self.stats.consecutive_failures = 0;
+                        // Returned only once the transaction confirmed
+                        Self::record_triangle_outcome(
+                            &mut self.stats,
+                            self.pool_registry.as_deref(),
+                            &opportunity.dexs,
+                            true,
+                        );
+                        trade.tx_id = Some(signature.to_string());
+                        let realized = Self::record_confirmed_trade(
+                            &mut self.stats,
+                            &mut self.profit_divergence,
+                            &mut self.unsettled_trades,
+                            self.rpc_client.as_deref(),
+                            wallet.as_ref(),
+                            self.trade_log.as_deref(),
+                            UnsettledTrade::landed(
+                                signature.to_string(),
+                                vec![signature.to_string()],
+                                trade,
+                                costs.worst_case_loss_lamports(position_size_lamports) as f64 / 1e9,
+                            ),
+                        );
 
                         info!("✅ Triangle executed successfully!");
                         info!("💰 Transaction: {}", signature);
-                        info!(
-                            "💰 Estimated profit: {:.6} SOL (Total: {:.6} SOL)",
-                            opportunity.estimated_profit_sol, self.stats.total_profit_sol
-                        );
+                        if let Some(realized) = realized {
+                            info!(
+                                "💰 Realized profit: {:.6} SOL (estimated {:.6} SOL, Total: {:.6} SOL)",
+                                realized, opportunity.estimated_profit_sol, self.stats.total_profit_sol
+                            );
+                        }
 
-                        Ok(())
+                        Ok(TriangleExecution::Completed)
                     }
                     Err(e) => {
                         self.stats.failed_executions += 1;
                         self.stats.consecutive_failures += 1;
+                        Self::record_triangle_outcome(
+                            &mut self.stats,
+                            self.pool_registry.as_deref(),
+                            &opportunity.dexs,
+                            false,
+                        );
+                        Self::log_trade(self.trade_log.as_deref(), &trade, TradeOutcome::Failed);
                         warn!("⚠️ Triangle execution failed: {}", e);
-                        Err(e)
+                        Err(e.into())
                     }
                 }
             }
         } else {
             warn!("⚠️ Real trading enabled but swap executor or wallet not initialized");
-            Ok(())
+            Ok(TriangleExecution::Completed)
+        }
+    }
+}
+
+/// Grok fix: Same-pool-type arbitrage isn't executable
+/// Different pool types within same DEX (e.g., Meteora DAMM variants) aren't arbitrageable
+/// Gross profit, costs and minimum required spread of a pairing at a position size
+///
+/// # Returns
+/// `(gross_profit_lamports, costs, min_required_spread_percentage)`; the
+/// required spread is infinite for an empty position
+fn pair_economics(
+    position_lamports: u64,
+    spread_percentage: f64,
+    tip_floor: &crate::jito_tip_monitor::JitoTipFloor,
+    tip_strategy: &TipStrategy,
+) -> (u64, ArbitrageCosts, f64) {
+    let gross_profit_lamports = (position_lamports as f64 * (spread_percentage / 100.0)) as u64;
+    let costs = ArbitrageCosts::calculate(
+        position_lamports,
+        gross_profit_lamports,
+        true,
+        Some(tip_floor),
+        tip_strategy,
+    );
+
+    // Calculate DYNAMIC minimum spread required
+    // Formula: min_spread = (total_costs + margin) / position_size
+    // Margin = 0.2% of gross profit for safety buffer
+    let margin_lamports = (gross_profit_lamports as f64 * 0.002) as u64; // 0.2% margin
+    let min_required_spread_lamports = costs.total_cost_lamports + margin_lamports;
+    let min_required_spread_percentage = if position_lamports == 0 {
+        f64::INFINITY
+    } else {
+        (min_required_spread_lamports as f64 / position_lamports as f64) * 100.0
+    };
+
+    (gross_profit_lamports, costs, min_required_spread_percentage)
+}
+
+/// Whether both legs of a pairing were quoted within `max_age`
+fn is_price_pair_fresh(buy: &TokenPrice, sell: &TokenPrice, max_age: Duration) -> bool {
+    buy.age() <= max_age && sell.age() <= max_age
+}
+
+/// Position size for a trade through `mints`, within total capital
+fn position_size_lamports(
+    position_tracker: &PositionTracker,
+    mints: &[&str],
+    capital_sol: f64,
+) -> u64 {
+    let capital_lamports = (capital_sol * 1_000_000_000.0) as u64;
+    position_tracker
+        .max_position_lamports_for(mints)
+        .min(capital_lamports)
+}
+
+/// Reserve a trade's position from the shared capital ledger
+///
+/// Every execution path (cross-DEX, triangle, concurrent) reserves through
+/// here, so one busy scan can't commit more than the available capital.
+///
+/// # Returns
+/// Lamports reserved (release with `PositionTracker::release_capital`), or
+/// `ArbError::InsufficientCapital`
+fn reserve_position(
+    position_tracker: &PositionTracker,
+    mints: &[&str],
+    capital_sol: f64,
+) -> ArbResult<u64> {
+    let lamports = position_size_lamports(position_tracker, mints, capital_sol);
+    position_tracker.reserve_capital(mints, lamports)?;
+    Ok(lamports)
+}
+
+/// Queue a trade's transactions through `sink` as ONE JITO bundle
+///
+/// Accepts the single combined transaction or a split bundle of up to
+/// `MAX_SPLIT_BUNDLE_LEGS` per-leg transactions (JITO caps bundles at 5).
+///
+/// # Returns
+/// `ArbError::Rejected` for an empty or oversized bundle, otherwise the sink's result
+async fn submit_leg_bundle(
+    sink: &dyn BundleSink,
+    bundle: Vec<VersionedTransaction>,
+    description: String,
+    expected_profit_sol: f64,
+    dedup_key: String,
+) -> ArbResult<()> {
+    if bundle.is_empty() || bundle.len() > MAX_SPLIT_BUNDLE_LEGS {
+        return Err(ArbError::Rejected(format!(
+            "bundle must contain 1-{} transactions (got {})",
+            MAX_SPLIT_BUNDLE_LEGS,
+            bundle.len()
+        )));
+    }
+    if bundle.len() > 1 {
+        info!("📦 Bundling {} transactions atomically", bundle.len());
+    }
+
+    sink.submit_bundle(bundle, description, expected_profit_sol, dedup_key)
+        .await?;
+    Ok(())
+}
+
+/// Poll a sent Jupiter leg until it confirms, fails on-chain or times out
+///
+/// # Returns
+/// `Some(true)` once confirmed, `Some(false)` if it failed on-chain, or `None`
+/// if its status is still unknown after `JUPITER_LEG_CONFIRM_TIMEOUT`
+async fn wait_for_leg_status(rpc_client: &SolanaRpcClient, signature: &Signature) -> Option<bool> {
+    let deadline = Instant::now() + JUPITER_LEG_CONFIRM_TIMEOUT;
+    loop {
+        match rpc_client.get_transaction_status(signature) {
+            Ok(Some(confirmed)) => return Some(confirmed),
+            Ok(None) => {}
+            Err(e) => debug!("Status read for Jupiter leg {} failed: {}", signature, e),
+        }
+        if Instant::now() >= deadline {
+            return None;
+        }
+        sleep(JUPITER_LEG_POLL_INTERVAL).await;
+    }
+}
+
+/// Mint a route's first leg spends
+///
+/// Detector paths name native SOL either "SOL" or by its mint.
+pub(crate) fn route_input_mint(path: &[String]) -> ArbResult<solana_sdk::pubkey::Pubkey> {
+    match path.first().map(String::as_str) {
+        Some("SOL") | Some(SOL_MINT) => Ok(spl_token::native_mint::id()),
+        Some(mint) => mint
+            .parse()
+            .map_err(|_| ArbError::Rejected(format!("invalid route input mint: {}", mint))),
+        None => Err(ArbError::Rejected("route has no input mint".to_string())),
+    }
+}
+
+/// Wallet balance of `mint` in base units
+///
+/// Native SOL is read as lamports, since Jupiter unwraps SOL output.
+fn wallet_mint_balance(
+    rpc_client: &SolanaRpcClient,
+    wallet: &solana_sdk::pubkey::Pubkey,
+    mint: &str,
+) -> Result<u64> {
+    if mint == SOL_MINT {
+        return rpc_client.get_balance(wallet);
+    }
+    let mint: solana_sdk::pubkey::Pubkey = mint
+        .parse()
+        .context(format!("Invalid mint address: {}", mint))?;
+    rpc_client.get_wallet_token_balance(wallet, &mint)
+}
+
+/// Amount of `mint` a confirmed Jupiter leg delivered to the wallet
+///
+/// Jupiter unwraps native SOL output (`wrapAndUnwrapSol`), so a SOL leg is read
+/// as the wallet's lamport change - net of the fee, slightly under what the
+/// swap returned.
+///
+/// # Errors
+/// Returns error if the transaction can't be read or delivered nothing
+fn jupiter_leg_output(
+    rpc_client: &SolanaRpcClient,
+    wallet: &solana_sdk::pubkey::Pubkey,
+    signature: &Signature,
+    mint: &str,
+) -> Result<u64> {
+    let change = if mint == SOL_MINT {
+        i128::from(rpc_client.get_transaction_balance_change(signature, wallet)?)
+    } else {
+        let mint: solana_sdk::pubkey::Pubkey = mint
+            .parse()
+            .context(format!("Invalid mint address: {}", mint))?;
+        rpc_client.get_transaction_token_change(signature, wallet, &mint)?
+    };
+    u64::try_from(change)
+        .ok()
+        .filter(|&amount| amount > 0)
+        .ok_or_else(|| anyhow::anyhow!("{} delivered no {} ({})", signature, mint, change))
+}
+
+/// Slots an opportunity's quotes lag behind the current slot
+///
+/// # Returns
+/// `None` when either slot is unknown, so callers fall back to wall-clock age
+fn opportunity_slot_age(observed_slot: Option<u64>, current_slot: Option<u64>) -> Option<u64> {
+    Some(current_slot?.saturating_sub(observed_slot?))
+}
+
+fn is_same_dex_family(buy_dex: &str, sell_dex: &str) -> bool {
+    buy_dex.starts_with(&sell_dex[..sell_dex.find('_').unwrap_or(sell_dex.len())])
+        && sell_dex.starts_with(&buy_dex[..buy_dex.find('_').unwrap_or(buy_dex.len())])
+}
+
+/// All executable (buy, sell) pool pairings for one token
+///
+/// Only pairs where the buy pool is cheaper than the sell pool and the two
+/// pools belong to different DEX families are returned
+fn candidate_pairs<'a>(prices: &[&'a TokenPrice]) -> Vec<(&'a TokenPrice, &'a TokenPrice)> {
+    let mut pairs = Vec::new();
+    for buy in prices {
+        for sell in prices {
+            if buy.price_sol > 0.0
+                && sell.price_sol > buy.price_sol
+                && !is_same_dex_family(&buy.dex, &sell.dex)
+            {
+                pairs.push((*buy, *sell));
+            }
+        }
+    }
+    pairs
+}
+
+/// Slippage tolerance for each leg of a route, widened by that leg's pool depth
+///
+/// Depth is the pool's SOL-side reserve; pools that aren't registered or whose
+/// DEX can't report depth keep `base_bps`.
+///
+/// # Arguments
+/// * `executor` - Reads pool reserves
+/// * `pool_registry` - Resolves short pool IDs to DEX type + address
+/// * `pool_ids` - Short pool ID of each leg, in order
+/// * `base_bps` - Tolerance for a deep pool
+/// * `trade_lamports` - Trade size in SOL terms (same notional on every leg)
+/// * `max_bps` - Cap on a widened leg
+///
+/// # Returns
+/// One tolerance (bps) per entry in `pool_ids`
+fn per_leg_slippage_bps(
+    executor: &SwapExecutor,
+    pool_registry: Option<&PoolRegistry>,
+    pool_ids: &[String],
+    base_bps: u64,
+    trade_lamports: u64,
+    max_bps: u64,
+) -> Vec<u64> {
+    pool_ids
+        .iter()
+        .map(|pool_id| {
+            let reserve = pool_registry
+                .and_then(|registry| registry.get_pool(pool_id))
+                .and_then(|pool| {
+                    executor
+                        .pool_sol_reserve(&pool.dex_type, &pool.full_address)
+                        .inspect_err(|e| debug!("🌊 Depth unknown for {}: {}", pool_id, e))
+                        .ok()
+                });
+            slippage::leg_slippage_bps(base_bps, trade_lamports, reserve, max_bps)
+        })
+        .collect()
+}
+
+/// Chain a route's input through a fresh output estimate for every leg
+///
+/// # Arguments
+/// * `amount_in` - Input of the first leg
+/// * `legs` - (DEX, short pool ID, swap params) per leg; only the direction is used
+/// * `estimate` - Quotes one leg: (DEX, pool, amount in, a→b) → amount out
+///
+/// # Returns
+/// Output of the last leg
+fn reestimated_route_output<F>(
+    amount_in: u64,
+    legs: &[(&DexType, &str, &SwapParams)],
+    mut estimate: F,
+) -> Result<u64>
+where
+    F: FnMut(&DexType, &str, u64, bool) -> Result<u64>,
+{
+    legs.iter()
+        .try_fold(amount_in, |amount, (dex_type, pool_id, swap)| {
+            estimate(dex_type, pool_id, amount, swap.swap_a_to_b)
+        })
+}
+
+/// Net profit of a re-estimated route
+///
+/// Pool curves already charge the DEX fees, so only tip and gas come off.
+fn reestimated_net_profit(amount_in: u64, amount_out: u64, costs: &ArbitrageCosts) -> i64 {
+    let execution_costs = costs
+        .total_cost_lamports
+        .saturating_sub(costs.dex_fee_lamports);
+    amount_out as i64 - amount_in as i64 - execution_costs as i64
+}
+
+/// Expected outputs of a 3-leg SOL → TokenA → TokenB → SOL triangle
+///
+/// # Arguments
+/// * `capital_lamports` - SOL input of leg 1
+/// * `prices` - Output per unit input of each leg, in order (at least 3)
+///
+/// # Returns
+/// (TokenA from leg 1, TokenB from leg 2, lamports from leg 3)
+fn three_leg_expected_outputs(capital_lamports: u64, prices: &[f64]) -> (u64, u64, u64) {
+    let out_1 = (capital_lamports as f64 * prices[0]) as u64;
+    let out_2 = (out_1 as f64 * prices[1]) as u64;
+    let out_3 = (out_2 as f64 * prices[2]) as u64;
+    (out_1, out_2, out_3)
+}
+
+/// Reject a route whose expected SOL return doesn't exceed the capital put in
+///
+/// # Arguments
+/// * `capital_lamports` - SOL input of the first leg
+/// * `expected_return_lamports` - SOL output of the last leg
+///
+/// # Returns
+/// Expected profit in lamports (always > 0)
+///
+/// # Errors
+/// `ArbError::Unprofitable` (after logging the expected loss) if profit <= 0
+fn checked_expected_profit(capital_lamports: u64, expected_return_lamports: u64) -> ArbResult<i64> {
+    let expected_profit_lamports = expected_return_lamports as i64 - capital_lamports as i64;
+    if expected_profit_lamports <= 0 {
+        warn!("⚠️ REJECTING trade with negative expected profit!");
+        warn!(
+            "   Initial capital: {:.6} SOL",
+            capital_lamports as f64 / 1e9
+        );
+        warn!(
+            "   Expected return: {:.6} SOL",
+            expected_return_lamports as f64 / 1e9
+        );
+        warn!(
+            "   Expected profit: {:.6} SOL (LOSS!)",
+            expected_profit_lamports as f64 / 1e9
+        );
+        return Err(ArbError::Unprofitable(format!(
+            "trade would lose {:.6} SOL",
+            -(expected_profit_lamports as f64) / 1e9
+        )));
+    }
+    Ok(expected_profit_lamports)
+}
+
+/// Expected base-unit outputs of a 2-leg SOL → Token → SOL trade
+///
+/// Prices are in SOL per whole token, so SOL→Token divides and Token→SOL multiplies.
+///
+/// # Arguments
+/// * `capital_lamports` - SOL input of leg 1
+/// * `buy_price` / `sell_price` - SOL per token on the buy/sell pool
+/// * `token_decimals` - Mint decimals (converts whole tokens ↔ base units)
+/// * `swap_fee` - Fee fraction per leg (e.g. 0.0025)
+///
+/// # Returns
+/// (token base units from leg 1, lamports from leg 2)
+fn two_leg_expected_outputs(
+    capital_lamports: u64,
+    buy_price: f64,
+    sell_price: f64,
+    token_decimals: u8,
+    swap_fee: f64,
+) -> (u64, u64) {
+    const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+    let token_factor = 10f64.powi(token_decimals as i32);
+
+    // SOL / (SOL/token) = tokens (with fee)
+    let tokens_received =
+        (capital_lamports as f64 / LAMPORTS_PER_SOL / buy_price) * (1.0 - swap_fee);
+    let token_units = (tokens_received * token_factor) as u64;
+
+    // tokens * (SOL/token) = SOL (with fee)
+    let sol_received = (token_units as f64 / token_factor * sell_price) * (1.0 - swap_fee);
+    (token_units, (sol_received * LAMPORTS_PER_SOL) as u64)
+}
+
+/// Token accounts to measure for PROFIT_VERIFY_SIM, one per leg
+///
+/// Every leg is exact-input, so what other legs spend from a leg's output
+/// account is known up front and added back to its balance delta.
+///
+/// # Arguments
+/// * `wallet` - Signer whose associated token accounts the swaps use
+/// * `legs` - (input mint, output mint, amount_in) per leg, in execution order
+///
+/// # Returns
+/// One probe per leg, in leg order
+fn leg_output_probes(
+    wallet: &solana_sdk::pubkey::Pubkey,
+    legs: &[(solana_sdk::pubkey::Pubkey, solana_sdk::pubkey::Pubkey, u64)],
+) -> Vec<LegOutputProbe> {
+    legs.iter()
+        .map(|(_, output_mint, _)| LegOutputProbe {
+            output_account: spl_associated_token_account::get_associated_token_address(
+                wallet,
+                output_mint,
+            ),
+            spent_elsewhere: legs
+                .iter()
+                .filter(|(input_mint, _, _)| input_mint == output_mint)
+                .map(|(_, _, amount_in)| amount_in)
+                .sum(),
+        })
+        .collect()
+}
+
+/// DEX name used as the per-DEX stats key
+///
+/// Strips the trailing 8-char pool ID ShredStream appends
+/// (e.g. "Meteora_DAMM_V2_81vA2wJx" → "Meteora_DAMM_V2")
+fn dex_stats_key(dex: &str) -> &str {
+    match dex.rsplit_once('_') {
+        Some((name, pool_id))
+            if pool_id.len() == 8 && pool_id.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            name
+        }
+        _ => dex,
+    }
+}
+
+/// Route key for realized-vs-estimated tracking (e.g. "Orca_Whirlpools/Meteora_DLMM")
+fn dex_route_key(dexs: &[String]) -> String {
+    dexs.iter()
+        .map(|dex| dex_stats_key(dex))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Current UTC date as YYYY-MM-DD (daily limit boundary)
+fn current_utc_date() -> String {
+    chrono::Utc::now().date_naive().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::{MockJitoSubmitter, MockRpcClient};
+
+    #[test]
+    fn test_stats_persist_and_daily_reset() {
+        let path =
+            std::env::temp_dir().join(format!("arbitrage_stats_test_{}.json", std::process::id()));
+
+        let mut stats = ArbitrageStats::default();
+        stats.roll_over_to("2025-10-14");
+        stats.opportunities_executed = 7;
+        stats.daily_trades = 7;
+        stats.record_profit(0.5);
+        stats.record_profit(-0.2);
+        stats.save_to_file(&path).unwrap();
+
+        // Same day: everything restored as-is
+        let same_day = ArbitrageStats::load_or_default(&path, "2025-10-14").unwrap();
+        assert_eq!(same_day.stats_date, "2025-10-14");
+        assert_eq!(same_day.opportunities_executed, 7);
+        assert_eq!(same_day.daily_trades, 7);
+        assert!((same_day.daily_loss_sol - 0.2).abs() < 1e-9);
+        assert!((same_day.daily_profit_sol - 0.3).abs() < 1e-9);
+        assert!((same_day.total_profit_sol - 0.3).abs() < 1e-9);
+
+        // Next day: daily counters reset, lifetime profit carries forward
+        let next_day = ArbitrageStats::load_or_default(&path, "2025-10-15").unwrap();
+        assert_eq!(next_day.stats_date, "2025-10-15");
+        assert_eq!(next_day.daily_trades, 0);
+        assert_eq!(next_day.daily_loss_sol, 0.0);
+        assert_eq!(next_day.daily_profit_sol, 0.0);
+        assert_eq!(next_day.opportunities_executed, 7);
+        assert!((next_day.total_profit_sol - 0.3).abs() < 1e-9);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_tip_budget_exhaustion_blocks_execution() {
+        let mut stats = ArbitrageStats::default();
+        stats.roll_over_to("2025-10-14");
+        let budget = Some(0.003);
+
+        // No budget configured: never blocks
+        stats.record_tip(5_000_000);
+        assert!(!stats.tip_budget_exhausted(None));
+        stats.daily_tips_spent_sol = 0.0;
+
+        stats.record_tip(1_000_000);
+        stats.record_tip(1_000_000);
+        assert!(!stats.tip_budget_exhausted(budget));
+        stats.record_tip(1_000_000);
+        assert!((stats.daily_tips_spent_sol - 0.003).abs() < 1e-9);
+        assert!(stats.tip_budget_exhausted(budget));
+        // Every further execution stays blocked for the rest of the day
+        stats.record_tip(1_000_000);
+        assert!(stats.tip_budget_exhausted(budget));
+        assert!(!stats.roll_over_to("2025-10-14"));
+        assert!(stats.tip_budget_exhausted(budget));
+
+        // New UTC day: budget resets alongside the other daily counters
+        assert!(stats.roll_over_to("2025-10-15"));
+        assert_eq!(stats.daily_tips_spent_sol, 0.0);
+        assert!(!stats.tip_budget_exhausted(budget));
+    }
+
+    #[test]
+    fn test_balance_drop_below_minimum_halts_trading() {
+        let mut stats = ArbitrageStats::default();
+        let min_wallet_balance_sol = Some(0.2);
+
+        // Nothing fetched yet, or no minimum configured: never halt
+        assert!(!stats.wallet_balance_below(min_wallet_balance_sol));
+        stats.record_wallet_balance(1_500_000_000);
+        assert!(!stats.wallet_balance_below(min_wallet_balance_sol));
+        assert!(!stats.wallet_balance_below(None));
+
+        // External withdrawal drains the wallet to 0.05 SOL
+        stats.record_wallet_balance(50_000_000);
+        assert_eq!(stats.wallet_balance_sol, Some(0.05));
+        assert!(stats.wallet_balance_below(min_wallet_balance_sol));
+
+        // Exactly at the minimum still trades (balance < min halts)
+        stats.record_wallet_balance(200_000_000);
+        assert!(!stats.wallet_balance_below(min_wallet_balance_sol));
+    }
+
+    #[test]
+    fn test_stats_load_missing_file_starts_fresh() {
+        let path = std::env::temp_dir().join("arbitrage_stats_does_not_exist.json");
+        let stats = ArbitrageStats::load_or_default(&path, "2025-10-14").unwrap();
+
+        assert_eq!(stats.stats_date, "2025-10-14");
+        assert_eq!(stats.daily_trades, 0);
+        assert_eq!(stats.total_profit_sol, 0.0);
+    }
+
+    #[test]
+    fn test_stats_load_corrupt_file_refuses_to_start() {
+        let path = std::env::temp_dir().join(format!(
+            "arbitrage_stats_corrupt_{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, "{ \"daily_trades\": 9").unwrap();
+
+        let result = ArbitrageStats::load_or_default(&path, "2025-10-14");
+        std::fs::remove_file(&path).ok();
+        let message = format!("{:#}", result.unwrap_err());
+        assert!(message.contains("Refusing to start"), "{}", message);
+    }
+
+    fn price(dex: &str, pool: &str, price_sol: f64) -> TokenPrice {
+        TokenPrice {
+            token_mint: "MINT".to_string(),
+            dex: dex.to_string(),
+            price_sol,
+            last_update: String::new(),
+            volume_24h: 100.0,
+            pool_address: pool.to_string(),
+            last_updated: Instant::now(),
+            slot: None,
+        }
+    }
+
+    #[test]
+    fn test_stale_quote_rejects_pair() {
+        let max_age = Duration::from_millis(2000);
+        let fresh = price("Orca_Whirlpool", "orca", 1.00);
+        let mut stale = price("Raydium_CPMM", "ray", 1.05);
+        stale.last_updated = Instant::now() - Duration::from_secs(5);
+
+        assert!(is_price_pair_fresh(&fresh, &fresh, max_age));
+        assert!(!is_price_pair_fresh(&fresh, &stale, max_age));
+        assert!(!is_price_pair_fresh(&stale, &fresh, max_age));
+    }
+
+    #[test]
+    fn test_opportunity_slot_age_falls_back_when_unknown() {
+        assert_eq!(opportunity_slot_age(Some(100), Some(103)), Some(3));
+        // Feed slot behind the quote (reordered updates) counts as fresh
+        assert_eq!(opportunity_slot_age(Some(105), Some(103)), Some(0));
+        // Either side missing defers to the wall-clock threshold
+        assert_eq!(opportunity_slot_age(None, Some(103)), None);
+        assert_eq!(opportunity_slot_age(Some(100), None), None);
+    }
+
+    #[test]
+    fn test_leg_output_probes_add_back_later_spends() {
+        use solana_sdk::pubkey::Pubkey;
+
+        let wallet = Pubkey::new_unique();
+        let (sol, token_a, token_b) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let legs = [
+            (sol, token_a, 1_000_000_000),
+            (token_a, token_b, 5_000),
+            (token_b, sol, 7_000),
+        ];
+
+        let probes = leg_output_probes(&wallet, &legs);
+        assert_eq!(probes.len(), 3);
+        assert_eq!(
+            probes[0].output_account,
+            spl_associated_token_account::get_associated_token_address(&wallet, &token_a)
+        );
+        // Each output account is drained by the following leg's exact input
+        assert_eq!(probes[0].spent_elsewhere, 5_000);
+        assert_eq!(probes[1].spent_elsewhere, 7_000);
+        // The SOL leg's account funded leg 1
+        assert_eq!(probes[2].spent_elsewhere, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_adaptive_scan_interval_aimd() {
+        let mut interval = AdaptiveScanInterval::new(1500, 1100, 2000, 250);
+
+        // Empty scans grow additively up to the ceiling
+        assert_eq!(interval.on_scan(false), Duration::from_millis(1750));
+        assert_eq!(interval.on_scan(false), Duration::from_millis(2000));
+        assert_eq!(interval.on_scan(false), Duration::from_millis(2000));
+
+        // Busy scans halve down to the floor
+        assert_eq!(interval.on_scan(true), Duration::from_millis(1100));
+        assert_eq!(interval.on_scan(true), Duration::from_millis(1100));
+
+        // Initial value outside the bounds is clamped
+        assert_eq!(
+            AdaptiveScanInterval::new(500, 1100, 2000, 250).current_ms,
+            1100
+        );
+    }
+
+    #[test]
+    fn test_scan_jitter_never_shortens_interval() {
+        let floor = Duration::from_millis(1100);
+        assert_eq!(jittered_scan_delay(floor, 0, 0.9), floor);
+        assert_eq!(jittered_scan_delay(floor, 400, 0.0), floor);
+        assert_eq!(
+            jittered_scan_delay(floor, 400, 0.5),
+            Duration::from_millis(1300)
+        );
+        for _ in 0..100 {
+            let delay = jittered_scan_delay(floor, 400, fastrand::f64());
+            assert!(delay >= floor && delay <= Duration::from_millis(1500));
+        }
+    }
+
+    #[test]
+    fn test_reserve_drift_rejects_below_floor() {
+        // Constant product with a 0.25% fee taken from the input
+        fn quote(reserve_in: u64, reserve_out: u64, amount_in: u64) -> u64 {
+            let amount_in = amount_in as u128 * 9975 / 10_000;
+            (reserve_out as u128 * amount_in / (reserve_in as u128 + amount_in)) as u64
+        }
+        let swap = |swap_a_to_b| SwapParams {
+            amount_in: 1_000_000_000,
+            minimum_amount_out: 0,
+            expected_amount_out: None,
+            swap_a_to_b,
+        };
+        let (buy, sell) = (swap(true), swap(false));
+        let legs = [
+            (&DexType::PumpSwap, "buyPool1", &buy),
+            (&DexType::Fluxbeam, "sellPool", &sell),
+        ];
+        // Buy pool: 1000 SOL / 1e14 tokens; sell pool's SOL side is what drifts
+        let route_output = |sell_sol_reserve: u64| {
+            reestimated_route_output(1_000_000_000, &legs, |_, pool, amount, a_to_b| {
+                Ok(match (pool, a_to_b) {
+                    ("buyPool1", true) => quote(1_000_000_000_000, 100_000_000_000_000, amount),
+                    ("sellPool", false) => quote(100_000_000_000_000, sell_sol_reserve, amount),
+                    _ => unreachable!(),
+                })
+            })
+            .unwrap()
+        };
+        let costs = ArbitrageCosts::calculate(
+            1_000_000_000,
+            40_000_000,
+            true,
+            None,
+            &TipStrategy::Fixed(1_000_000),
+        );
+        let floor = 10_000;
+
+        // At detection the sell pool paid ~5% more
+        let detected =
+            reestimated_net_profit(1_000_000_000, route_output(1_050_000_000_000), &costs);
+        assert!(detected > 30_000_000, "net {}", detected);
+
+        // Its SOL side has since drained to parity: now a loss, caught before building
+        let drifted =
+            reestimated_net_profit(1_000_000_000, route_output(1_000_000_000_000), &costs);
+        assert!(drifted < floor as i64, "net {}", drifted);
+
+        // A re-quote failure aborts the route
+        assert!(
+            reestimated_route_output(1, &legs, |_, _, _, _| Err(anyhow::anyhow!("rpc"))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_negative_three_leg_triangle_rejected() {
+        let capital = 1_000_000_000;
+
+        // 1 SOL → 100 A → 50 B → 0.995 SOL: profit evaporated since detection
+        let (_, _, losing_out) = three_leg_expected_outputs(capital, &[100.0, 0.5, 0.0199]);
+        assert!(losing_out < capital);
+        assert!(matches!(
+            checked_expected_profit(capital, losing_out),
+            Err(ArbError::Unprofitable(_))
+        ));
+        // Break-even is rejected too
+        assert!(checked_expected_profit(capital, capital).is_err());
+
+        let (_, _, winning_out) = three_leg_expected_outputs(capital, &[100.0, 0.5, 0.0201]);
+        assert!(checked_expected_profit(capital, winning_out).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_failure_cooldown_pauses_then_resumes() {
+        let start = Instant::now();
+        let mut cooldown = FailureCooldown::new(Duration::from_secs(300));
+
+        assert_eq!(cooldown.poll(2, 3, start), CooldownStatus::Ready);
+        assert_eq!(cooldown.poll(3, 3, start), CooldownStatus::Entered);
+        assert_eq!(
+            cooldown.poll(3, 3, start + Duration::from_secs(100)),
+            CooldownStatus::CoolingDown(Duration::from_secs(200))
+        );
+
+        // Window over: resume even though the streak is still at the threshold
+        let end = start + Duration::from_secs(300);
+        assert_eq!(cooldown.poll(3, 3, end), CooldownStatus::Resumed);
+        // Engine resets the streak on resume
+        assert_eq!(cooldown.poll(0, 3, end), CooldownStatus::Ready);
+    }
+
+    #[test]
+    fn test_shredstream_backoff_grows_caps_and_resets() {
+        let mut backoff = ReconnectBackoff::new(500, 30_000);
+
+        // Jitter 1.0 = top of each step: 500, 1000, 2000, ...
+        assert_eq!(backoff.next_delay(1.0), Duration::from_millis(500));
+        assert_eq!(backoff.next_delay(1.0), Duration::from_millis(1000));
+        // Jitter 0.0 = bottom half of the step (2000 / 2)
+        assert_eq!(backoff.next_delay(0.0), Duration::from_millis(1000));
+
+        // Capped at 30s no matter how many failures
+        for _ in 0..100 {
+            assert!(backoff.next_delay(1.0) <= Duration::from_millis(30_000));
+        }
+        assert_eq!(backoff.next_delay(1.0), Duration::from_millis(30_000));
+
+        // First success drops back to the base delay
+        backoff.reset();
+        assert_eq!(backoff.next_delay(1.0), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_same_dex_family_filter() {
+        assert!(is_same_dex_family("Meteora_DAMM", "Meteora_DLMM"));
+        assert!(is_same_dex_family("Orca", "Orca"));
+        assert!(!is_same_dex_family("Meteora_DLMM", "Orca_Whirlpool"));
+        assert!(!is_same_dex_family("Raydium_CPMM", "PumpSwap"));
+    }
+
+    #[test]
+    fn test_candidate_pairs_beyond_extremes() {
+        let prices = [
+            price("Orca_Whirlpool", "orca", 1.00),
+            price("Raydium_CPMM", "ray", 1.01),
+            price("Meteora_DLMM", "met1", 1.02),
+            price("Meteora_DAMM", "met2", 1.05),
+        ];
+        let refs: Vec<&TokenPrice> = prices.iter().collect();
+        let pairs: Vec<(String, String)> = candidate_pairs(&refs)
+            .into_iter()
+            .map(|(b, s)| (b.pool_address.clone(), s.pool_address.clone()))
+            .collect();
+
+        // Every cheaper → pricier pairing across DEX families
+        assert!(pairs.contains(&("orca".to_string(), "met2".to_string())));
+        assert!(pairs.contains(&("orca".to_string(), "ray".to_string())));
+        assert!(pairs.contains(&("ray".to_string(), "met1".to_string())));
+        // Same-DEX filter applies to non-extreme pairs too
+        assert!(!pairs.contains(&("met1".to_string(), "met2".to_string())));
+        // Never sell into a cheaper pool
+        assert!(!pairs.contains(&("met2".to_string(), "orca".to_string())));
+        assert_eq!(pairs.len(), 5);
+    }
+
+    #[test]
+    fn test_dex_stats_keyed_by_dex_name() {
+        let mut stats = ArbitrageStats::default();
+        let legs = vec![
+            "Orca_Whirlpools_AbCd1234".to_string(),
+            "Meteora_DAMM_V2_81vA2wJx".to_string(),
+        ];
+        stats.record_dex_result(&legs, false);
+        stats.record_dex_result(&legs, false);
+        stats.record_dex_result(&["Meteora_DAMM_V2_9xYz7777"], true);
+
+        let orca = &stats.dex_stats["Orca_Whirlpools"];
+        assert_eq!((orca.attempts, orca.successes, orca.failures), (2, 0, 2));
+        assert_eq!(orca.success_rate(), 0.0);
+
+        let meteora = &stats.dex_stats["Meteora_DAMM_V2"];
+        assert_eq!((meteora.attempts, meteora.successes), (3, 1));
+        assert_eq!(stats.dex_stats.len(), 2);
+
+        // Names without a pool suffix are used as-is
+        assert_eq!(dex_stats_key("PumpSwap"), "PumpSwap");
+        assert_eq!(
+            dex_route_key(&[
+                "Orca_Whirlpools_7qbRF6Ys".to_string(),
+                "Meteora_DLMM_5rCf1DM8".to_string()
+            ]),
+            "Orca_Whirlpools/Meteora_DLMM"
+        );
+        assert_eq!(dex_stats_key("Raydium_CPMM"), "Raydium_CPMM");
+    }
+
+    #[test]
+    fn test_recent_submissions_dedup_and_release() {
+        let mut recent = RecentSubmissions::new(Duration::from_millis(SUBMISSION_DEDUP_TTL_MS));
+        let key = RecentSubmissions::key("mintA", "buyPool", "sellPool");
+        assert!(!recent.contains(&key));
+
+        recent.insert(key.clone());
+        assert!(recent.contains(&key));
+        // Different sell pool is a different opportunity
+        assert!(!recent.contains(&RecentSubmissions::key("mintA", "buyPool", "otherPool")));
+
+        // Submitter reported the bundle resolved → key re-arms immediately
+        recent.release([key.as_str()]);
+        assert!(!recent.contains(&key));
+
+        // Unresolved entries still expire after the TTL
+        let mut short = RecentSubmissions::new(Duration::ZERO);
+        short.insert(key.clone());
+        assert!(!short.contains(&key));
+    }
+
+    #[test]
+    fn test_self_trade_guard_overlap_until_resolved() {
+        let mut pending = HashMap::new();
+        pending.insert(
+            "key1".to_string(),
+            PendingBundle {
+                dexs: vec![
+                    "Orca_Whirlpool_PoolAAAA".to_string(),
+                    "Raydium_CLMM_PoolBBBB".to_string(),
+                ],
+                expected_profit_sol: 0.01,
+                estimated_net_profit_sol: 0.008,
+                trade: TradeRecord {
+                    token_mint: "TokenA".to_string(),
+                    ..Default::default()
+                },
+                tip_lamports: 10_000,
+                position_size_lamports: 500_000_000,
+                worst_case_loss_sol: 0.5,
+            },
+        );
+
+        // Same token on other pools, or another token through our pool
+        assert_eq!(
+            in_flight_overlap(&pending, &["TokenA"], &["Meteora_DLMM_PoolCCCC"]),
+            Some("key1")
+        );
+        assert_eq!(
+            in_flight_overlap(&pending, &["TokenB"], &["Raydium_CLMM_PoolBBBB"]),
+            Some("key1")
+        );
+        // Disjoint route, and SOL alone is never overlap
+        assert_eq!(
+            in_flight_overlap(&pending, &["TokenB", SOL_MINT], &["Meteora_DLMM_PoolCCCC"]),
+            None
+        );
+
+        // Bundle landed or failed - reconciliation removes it
+        pending.remove("key1");
+        assert_eq!(
+            in_flight_overlap(&pending, &["TokenA"], &["Orca_Whirlpool_PoolAAAA"]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_landed_trade_retries_then_books_worst_case() {
+        let readable = UnsettledTrade::landed(
+            "readable".to_string(),
+            vec!["sigA".to_string()],
+            TradeRecord::default(),
+            0.5,
+        );
+        let unreadable = UnsettledTrade::landed(
+            "unreadable".to_string(),
+            vec!["sigB".to_string()],
+            TradeRecord::default(),
+            0.5,
+        );
+        let start = readable.landed_at.max(unreadable.landed_at);
+        let mut unsettled = vec![readable, unreadable];
+
+        // First balance read returns None - both stay pending, nothing booked
+        let settled = settle_landed_trades(&mut unsettled, start, |_| None);
+        assert!(settled.is_empty());
+        assert_eq!(unsettled.len(), 2);
+
+        // Re-read before the retry interval is skipped
+        let settled = settle_landed_trades(&mut unsettled, start, |_| Some(1.0));
+        assert!(settled.is_empty());
+
+        // Next read succeeds for one trade
+        let retry = start + PROFIT_READ_RETRY_INTERVAL;
+        let settled = settle_landed_trades(&mut unsettled, retry, |trade| {
+            (trade.label == "readable").then_some(0.002)
+        });
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].0.label, "readable");
+        assert_eq!(settled[0].1, Settlement::Realized(0.002));
+        assert_eq!(unsettled.len(), 1);
+
+        // Still unreadable at the deadline - worst case booked as a loss
+        let deadline = start + PROFIT_READ_DEADLINE;
+        let settled = settle_landed_trades(&mut unsettled, deadline, |_| None);
+        assert!(unsettled.is_empty());
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].1, Settlement::WorstCase(0.5));
+
+        let mut stats = ArbitrageStats::default();
+        stats.record_profit(settled[0].1.profit_sol());
+        assert!((stats.daily_loss_sol - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_token_cooldown_remaining() {
+        let start = Instant::now();
+        let cooldown = Duration::from_millis(5_000);
+        let mut last_traded = HashMap::new();
+        last_traded.insert("TokenA".to_string(), start);
+        last_traded.insert(SOL_MINT.to_string(), start);
+
+        // 2s after trading TokenA: 3s left; untraded TokenB is free
+        let now = start + Duration::from_millis(2_000);
+        assert_eq!(
+            token_cooldown_remaining(&last_traded, &["TokenB", "TokenA"], cooldown, now),
+            Some(("TokenA", Duration::from_millis(3_000)))
+        );
+        assert_eq!(
+            token_cooldown_remaining(&last_traded, &["TokenB"], cooldown, now),
+            None
+        );
+        // SOL is every route's base - never cooled down
+        assert_eq!(
+            token_cooldown_remaining(&last_traded, &[SOL_MINT], cooldown, now),
+            None
+        );
+
+        // Cooldown elapsed
+        let later = start + cooldown;
+        assert_eq!(
+            token_cooldown_remaining(&last_traded, &["TokenA"], cooldown, later),
+            None
+        );
+    }
+
+    #[test]
+    fn test_route_input_mint() {
+        let path = |first: &str| vec![first.to_string(), "TokenA".to_string()];
+        let wsol = spl_token::native_mint::id();
+        let usdc = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+        assert_eq!(route_input_mint(&path("SOL")).unwrap(), wsol);
+        assert_eq!(route_input_mint(&path(SOL_MINT)).unwrap(), wsol);
+        assert_eq!(
+            route_input_mint(&path(usdc)).unwrap(),
+            usdc.parse().unwrap()
+        );
+        assert!(route_input_mint(&path("not-a-mint")).is_err());
+        assert!(route_input_mint(&[]).is_err());
+    }
+
+    #[test]
+    fn test_two_leg_min_output_respects_token_decimals() {
+        // 1 SOL into a token priced at 0.01 SOL, sold at 0.0102 SOL, no fees
+        let capital = 1_000_000_000;
+
+        // 6-decimal token (USDC-style): 100 tokens = 100_000_000 base units
+        let (out_1, out_2) = two_leg_expected_outputs(capital, 0.01, 0.0102, 6, 0.0);
+        assert_eq!(out_1, 100_000_000);
+        assert_eq!(
+            SwapExecutor::calculate_min_output_with_slippage(out_1, 100),
+            99_000_000
+        );
+        assert!((out_2 as i64 - 1_020_000_000).abs() <= 1);
+
+        // 9-decimal token: same trade, 1000x more base units
+        let (out_1, out_2) = two_leg_expected_outputs(capital, 0.01, 0.0102, 9, 0.0);
+        assert_eq!(out_1, 100_000_000_000);
+        assert_eq!(
+            SwapExecutor::calculate_min_output_with_slippage(out_1, 100),
+            99_000_000_000
+        );
+        assert!((out_2 as i64 - 1_020_000_000).abs() <= 1);
+    }
+
+    #[test]
+    fn test_realized_profit_from_balance_delta() {
+        // Landed bundle: +0.012 SOL net of tip and fees
+        assert!((realized_profit_sol(&[12_000_000]) - 0.012).abs() < 1e-12);
+        // Split bundle: leg 1 spends, leg 2 returns more
+        assert!((realized_profit_sol(&[-1_000_005_000, 1_012_005_000]) - 0.012).abs() < 1e-12);
+        // Landed but unprofitable (price moved): realized loss, not the estimate
+        assert!((realized_profit_sol(&[-2_500_000]) + 0.0025).abs() < 1e-12);
+
+        let mut stats = ArbitrageStats::default();
+        stats.record_profit(realized_profit_sol(&[-2_500_000]));
+        assert!((stats.daily_loss_sol - 0.0025).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejection_histogram_sorted_by_count() {
+        let rejections = RejectionCounters::default();
+        rejections.record(RejectionReason::GhostPool);
+        for _ in 0..3 {
+            rejections.record(RejectionReason::SpreadTooLow);
+        }
+        rejections.record(RejectionReason::Stale);
+        rejections.record(RejectionReason::Stale);
+
+        let histogram = rejections.histogram();
+        assert_eq!(
+            histogram,
+            vec![
+                (RejectionReason::SpreadTooLow, 3),
+                (RejectionReason::Stale, 2),
+                (RejectionReason::GhostPool, 1),
+            ]
+        );
+        assert_eq!(RejectionReason::SpreadTooLow.to_string(), "spread_too_low");
+    }
+
+    fn opportunity(buy_pool: &str, sell_pool: &str) -> ArbitrageOpportunity {
+        ArbitrageOpportunity {
+            token_mint: "TokenMint1111".to_string(),
+            buy_dex: "Meteora_DLMM".to_string(),
+            sell_dex: "Orca_Whirlpools".to_string(),
+            buy_price: 1.0,
+            sell_price: 1.01,
+            spread_percentage: 1.0,
+            estimated_profit_sol: 0.01,
+            gross_profit_sol: 0.012,
+            required_spread_percentage: 0.8,
+            position_size_lamports: 500_000_000,
+            buy_pool_address: buy_pool.to_string(),
+            sell_pool_address: sell_pool.to_string(),
+            detected_at: Instant::now(),
+            observed_slot: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_by_net_profit_merges_triangles() {
+        let mut small = opportunity("PoolA", "PoolB");
+        small.estimated_profit_sol = 0.002;
+        let mut large = opportunity("PoolC", "PoolD");
+        large.estimated_profit_sol = 0.02;
+        let mut triangle = small.as_two_leg_triangle();
+        triangle.estimated_profit_sol = 0.01;
+
+        let profits: Vec<f64> = rank_by_net_profit(vec![small, large], vec![triangle])
+            .iter()
+            .map(RankedOpportunity::net_profit_sol)
+            .collect();
+        assert_eq!(profits, vec![0.02, 0.01, 0.002]);
+    }
+
+    #[test]
+    fn test_mixed_opportunities_share_one_capital_ledger() {
+        // 1 SOL capital, 0.5 SOL positions: room for exactly two trades
+        let tracker = PositionTracker::new(1.0, 0.5);
+        let cross_dex = opportunity("PoolA", "PoolB");
+        let triangle = opportunity("PoolC", "PoolD").as_two_leg_triangle();
+        let cross_mints = [cross_dex.token_mint.as_str()];
+        let triangle_mints: Vec<&str> = triangle.path.iter().map(String::as_str).collect();
+
+        assert_eq!(
+            reserve_position(&tracker, &cross_mints, 1.0).unwrap(),
+            500_000_000
+        );
+        assert_eq!(
+            reserve_position(&tracker, &triangle_mints, 1.0).unwrap(),
+            500_000_000
+        );
+
+        // Later opportunities in the same scan are refused, whichever path they take
+        assert!(matches!(
+            reserve_position(&tracker, &cross_mints, 1.0),
+            Err(ArbError::InsufficientCapital(_))
+        ));
+        assert!(matches!(
+            reserve_position(&tracker, &triangle_mints, 1.0),
+            Err(ArbError::InsufficientCapital(_))
+        ));
+
+        // Released capital is available again
+        tracker.release_capital(500_000_000);
+        assert!(reserve_position(&tracker, &cross_mints, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_queued_bundle_holds_capital_until_resolved() {
+        // 0.6 SOL capital, 0.5 SOL positions: room for one bundle in flight
+        let tracker = PositionTracker::new(0.6, 0.5);
+        let first = opportunity("PoolA", "PoolB");
+        let second = opportunity("PoolC", "PoolD");
+        let costs = ArbitrageCosts::calculate(
+            500_000_000,
+            10_000_000,
+            true,
+            None,
+            &TipStrategy::Fixed(10_000),
+        );
+
+        // First bundle queued: the pending bundle keeps its reservation and tip
+        let reserved = reserve_position(&tracker, &[&first.token_mint], 0.6).unwrap();
+        tracker.reserve_tip(costs.jito_tip_lamports).unwrap();
+        let pending = PendingBundle::new(
+            &first.as_two_leg_triangle(),
+            TradeRecord::default(),
+            &costs,
+            reserved,
+            reserved,
+            0.005,
+        );
+
+        // Second bundle's reservation is refused while the first is in flight
+        assert!(matches!(
+            reserve_position(&tracker, &[&second.token_mint], 0.6),
+            Err(ArbError::InsufficientCapital(_))
+        ));
+
+        // First bundle resolves - its capital and tip come back
+        pending.release(&tracker);
+        let reserved = reserve_position(&tracker, &[&second.token_mint], 0.6).unwrap();
+        assert_eq!(reserved, 500_000_000);
+        assert!(tracker.reserve_tip(costs.jito_tip_lamports).is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_opportunities_never_share_pools() {
+        let mut claims = PoolClaims::default();
+        assert!(claims.try_claim(&opportunity("PoolA", "PoolB")));
+        // Either leg overlapping a claimed pool conflicts
+        assert!(!claims.try_claim(&opportunity("PoolB", "PoolC")));
+        assert!(!claims.try_claim(&opportunity("PoolC", "PoolA")));
+        // A rejected claim reserves nothing
+        assert!(claims.try_claim(&opportunity("PoolC", "PoolD")));
+    }
+
+    #[test]
+    fn test_non_meteora_pair_routes_through_bundle_path() {
+        let mut two_leg = opportunity("PoolA", "PoolB");
+        two_leg.buy_dex = "Orca_Whirlpools_7qbRF6Ys".to_string();
+        two_leg.sell_dex = "Raydium_AMM_V4_58oQChx4".to_string();
+
+        let triangle = two_leg.as_two_leg_triangle();
+        assert_eq!(triangle.path, ["SOL", "TokenMint1111", "SOL"]);
+
+        // Same pool IDs and DEX types the triangle path resolves and builds with
+        let pool_ids: Vec<String> = triangle
+            .dexs
+            .iter()
+            .map(|dex| extract_pool_id(dex).unwrap())
+            .collect();
+        assert_eq!(pool_ids, ["7qbRF6Ys", "58oQChx4"]);
+        assert_eq!(
+            DexType::from_dex_string(&triangle.dexs[0]).unwrap(),
+            DexType::OrcaWhirlpools
+        );
+        assert_eq!(
+            DexType::from_dex_string(&triangle.dexs[1]).unwrap(),
+            DexType::RaydiumAmmV4
+        );
+
+        // Gross profit is re-validated against costs; depth cap carries over
+        assert_eq!(triangle.estimated_profit_sol, 0.012);
+        assert_eq!(triangle.prices, [1.0, 1.01]);
+        assert_eq!(triangle.position_cap_lamports, Some(500_000_000));
+    }
+
+    #[test]
+    fn test_two_leg_outcome_applies_to_stats() {
+        let mut stats = ArbitrageStats {
+            consecutive_failures: 2,
+            ..Default::default()
+        };
+
+        TwoLegOutcome {
+            dex_results: vec![
+                ("Meteora_DLMM".to_string(), true),
+                ("Orca_Whirlpools".to_string(), true),
+            ],
+            executed: true,
+            leg_failed: false,
+            profit_sol: Some(0.01),
+            signatures: vec!["sig".to_string()],
+        }
+        .apply(&mut stats);
+        assert_eq!(stats.opportunities_executed, 1);
+        assert_eq!(stats.daily_trades, 1);
+        assert_eq!(stats.consecutive_failures, 0);
+        assert!((stats.total_profit_sol - 0.01).abs() < 1e-12);
+
+        TwoLegOutcome {
+            dex_results: vec![("Meteora_DLMM".to_string(), false)],
+            leg_failed: true,
+            ..Default::default()
+        }
+        .apply(&mut stats);
+        assert_eq!(stats.failed_executions, 1);
+        assert_eq!(stats.consecutive_failures, 1);
+        assert_eq!(stats.dex_stats["Meteora_DLMM"].attempts, 2);
+    }
+
+    #[test]
+    fn test_partial_fill_unwind_accounting() {
+        let mut stats = ArbitrageStats::default();
+        stats.record_unwind(0.004);
+        assert_eq!(stats.unwound_positions, 1);
+        assert!((stats.unwind_loss_sol - 0.004).abs() < 1e-12);
+        assert!((stats.daily_loss_sol - 0.004).abs() < 1e-12);
+        assert!((stats.total_profit_sol + 0.004).abs() < 1e-12);
+    }
+
+    #[tokio::test]
+    async fn test_split_legs_submit_as_one_bundle() {
+        use solana_sdk::hash::Hash;
+        use solana_sdk::system_instruction;
+
+        let wallet = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let leg_bundle = |legs: u64| -> Vec<VersionedTransaction> {
+            (1..=legs)
+                .map(|lamports| {
+                    let ix =
+                        system_instruction::transfer(&wallet.pubkey(), &wallet.pubkey(), lamports);
+                    Transaction::new_signed_with_payer(
+                        &[ix],
+                        Some(&wallet.pubkey()),
+                        &[&wallet],
+                        blockhash,
+                    )
+                    .into()
+                })
+                .collect()
+        };
+
+        let sink = MockJitoSubmitter::default();
+        let triangle = leg_bundle(3);
+        submit_leg_bundle(&sink, triangle.clone(), "3-leg".into(), 0.01, "k1".into())
+            .await
+            .unwrap();
+        submit_leg_bundle(&sink, leg_bundle(2), "2-leg".into(), 0.01, "k2".into())
+            .await
+            .unwrap();
+
+        // Oversized and empty bundles never reach the submitter
+        assert!(
+            submit_leg_bundle(&sink, leg_bundle(5), "too big".into(), 0.01, "k3".into())
+                .await
+                .is_err()
+        );
+        assert!(
+            submit_leg_bundle(&sink, Vec::new(), "empty".into(), 0.01, "k4".into())
+                .await
+                .is_err()
+        );
+
+        let bundles = sink.bundles();
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[0].transactions.len(), 3);
+        assert_eq!(bundles[1].transactions.len(), 2);
+        for (queued, built) in bundles[0].transactions.iter().zip(&triangle) {
+            assert_eq!(queued.signatures, built.signatures);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_price_map_to_submitted_bundle() {
+        use crate::pool_registry::expected_program_id;
+        use crate::PoolInfo;
+        use solana_sdk::pubkey::Pubkey;
+
+        let rpc = Arc::new(MockRpcClient::default());
+        let registry = Arc::new(PoolRegistry::new(rpc.clone()));
+        let wallet = Keypair::new();
+        let tip_account = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        let wsol = spl_token::native_mint::id();
+        rpc.set_mint(token);
+        rpc.set_mint(wsol);
+
+        // PumpSwap pool: base = token, quote = WSOL, 1M tokens / 10k SOL
+        let pump_pool = Pubkey::new_unique();
+        let (pump_base_vault, pump_quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut pump_data = vec![0u8; 300];
+        pump_data[43..75].copy_from_slice(token.as_ref());
+        pump_data[75..107].copy_from_slice(wsol.as_ref());
+        pump_data[139..171].copy_from_slice(pump_base_vault.as_ref());
+        pump_data[171..203].copy_from_slice(pump_quote_vault.as_ref());
+        let pump_program = expected_program_id(&DexType::PumpSwap).unwrap();
+        rpc.set_account(pump_pool, pump_program, pump_data);
+        rpc.set_token_account(pump_base_vault, 1_000_000_000_000);
+        rpc.set_token_account(pump_quote_vault, 10_000_000_000_000);
+
+        // Fluxbeam pool: token A = WSOL, token B = token (SwapV1, padded past the ghost check)
+        let flux_pool = Pubkey::new_unique();
+        let flux_program = expected_program_id(&DexType::Fluxbeam).unwrap();
+        let (_, bump) = Pubkey::find_program_address(&[flux_pool.as_ref()], &flux_program);
+        let (flux_vault_a, flux_vault_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut flux_data = vec![0u8; 1000];
+        flux_data[1] = 1;
+        flux_data[2] = bump;
+        flux_data[3..35].copy_from_slice(spl_token::id().as_ref());
+        flux_data[35..67].copy_from_slice(flux_vault_a.as_ref());
+        flux_data[67..99].copy_from_slice(flux_vault_b.as_ref());
+        flux_data[99..131].copy_from_slice(Pubkey::new_unique().as_ref());
+        flux_data[131..163].copy_from_slice(wsol.as_ref());
+        flux_data[163..195].copy_from_slice(token.as_ref());
+        flux_data[195..227].copy_from_slice(Pubkey::new_unique().as_ref());
+        rpc.set_account(flux_pool, flux_program, flux_data);
+
+        for (short_id, pool, dex_type) in [
+            ("PumpPool", pump_pool, DexType::PumpSwap),
+            ("FluxPool", flux_pool, DexType::Fluxbeam),
+        ] {
+            let pool_info = PoolInfo {
+                full_address: pool,
+                dex_type,
+                token_a_mint: Pubkey::default(),
+                token_b_mint: Pubkey::default(),
+                reserve_a: Pubkey::default(),
+                reserve_b: Pubkey::default(),
+            };
+            registry
+                .register_pool(short_id.to_string(), pool_info)
+                .unwrap();
+        }
+
+        // Known price map: buy on PumpSwap, sell 3% higher on Fluxbeam
+        let prices = [
+            price("PumpSwap_PumpPool", &pump_pool.to_string(), 0.0100),
+            price("Fluxbeam_FluxPool", &flux_pool.to_string(), 0.0103),
+        ];
+        let refs: Vec<&TokenPrice> = prices.iter().collect();
+        let pairs = candidate_pairs(&refs);
+        assert_eq!(pairs.len(), 1);
+        let (buy, sell) = pairs[0];
+        let buy_dex = DexType::from_dex_string(&buy.dex).unwrap();
+        let sell_dex = DexType::from_dex_string(&sell.dex).unwrap();
+        let buy_pool = extract_pool_id(&buy.dex).unwrap();
+        let sell_pool = extract_pool_id(&sell.dex).unwrap();
+
+        let capital = 1_000_000_000;
+        let (out_1, out_2) =
+            two_leg_expected_outputs(capital, buy.price_sol, sell.price_sol, 6, 0.0025);
+        let swap1 = SwapParams {
+            amount_in: capital,
+            minimum_amount_out: SwapExecutor::calculate_min_output_with_slippage(out_1, 50),
+            expected_amount_out: Some(out_1),
+            swap_a_to_b: true,
+        };
+        let swap2 = SwapParams {
+            amount_in: out_1,
+            minimum_amount_out: SwapExecutor::calculate_min_output_with_slippage(out_2, 50),
+            expected_amount_out: Some(out_2),
+            swap_a_to_b: false,
+        };
+
+        let executor = SwapExecutor::new(rpc.clone(), registry, None).unwrap();
+        let bundle = executor
+            .build_leg_bundle_with_tip(
+                &wsol,
+                &[
+                    (&buy_dex, buy_pool.as_str(), &swap1),
+                    (&sell_dex, sell_pool.as_str(), &swap2),
+                ],
+                &wallet,
+                10_000,
+                &tip_account,
+            )
+            .await
+            .unwrap();
+        let jito = MockJitoSubmitter::default();
+        submit_leg_bundle(
+            &jito,
+            bundle.into_iter().map(Into::into).collect(),
+            "2-leg".into(),
+            0.02,
+            "pump-flux".into(),
+        )
+        .await
+        .unwrap();
+
+        let bundles = jito.bundles();
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].dedup_key, "pump-flux");
+        assert_eq!(bundles[0].description, "2-leg");
+        assert_eq!(bundles[0].expected_profit_sol, 0.02);
+        let [buy_tx, sell_tx] = bundles[0].transactions.as_slice() else {
+            panic!("expected one transaction per leg");
+        };
+
+        // Swap instruction of `program` in a queued transaction: (accounts, data)
+        let swap_ix = |tx: &VersionedTransaction, program: Pubkey| -> (Vec<Pubkey>, Vec<u8>) {
+            let keys = tx.message.static_account_keys();
+            let ix = tx
+                .message
+                .instructions()
+                .iter()
+                .find(|ix| keys[ix.program_id_index as usize] == program)
+                .expect("swap instruction missing");
+            let accounts = ix.accounts.iter().map(|&i| keys[i as usize]).collect();
+            (accounts, ix.data.clone())
+        };
+
+        // Leg 1: PumpSwap BUY for at least min_out_1 tokens, spending at most the capital
+        assert_eq!(*buy_tx.message.recent_blockhash(), rpc.blockhash());
+        let (accounts, data) = swap_ix(buy_tx, pump_program);
+        assert_eq!(accounts[0], wallet.pubkey());
+        assert_eq!(accounts[7], pump_pool);
+        assert_eq!(data[8..16], swap1.minimum_amount_out.to_le_bytes());
+        assert_eq!(data[16..24], capital.to_le_bytes());
+
+        // Leg 2: Fluxbeam token → WSOL for the leg-1 output
+        assert_eq!(*sell_tx.message.recent_blockhash(), rpc.blockhash());
+        let (accounts, data) = swap_ix(sell_tx, flux_program);
+        assert_eq!(accounts[0], flux_pool);
+        assert_eq!((accounts[4], accounts[5]), (flux_vault_b, flux_vault_a));
+        assert_eq!(data[0], 1);
+        assert_eq!(data[1..9], out_1.to_le_bytes());
+        assert_eq!(data[9..17], swap2.minimum_amount_out.to_le_bytes());
+
+        // Each leg ends with its half of the tip
+        for tx in [buy_tx, sell_tx] {
+            let keys = tx.message.static_account_keys();
+            let tip = tx.message.instructions().last().unwrap();
+            assert_eq!(keys[tip.accounts[1] as usize], tip_account);
+            assert_eq!(tip.data[4..12], 5_000u64.to_le_bytes());
         }
     }
 }
@@ -12,6 +12,9 @@ use tracing::{debug, info, warn};
 
 use crate::rpc_client::SolanaRpcClient;
 
+/// Blockhashes live ~150 slots (60-90s); past this age a transaction may not land
+pub const BLOCKHASH_NEAR_EXPIRY: Duration = Duration::from_secs(45);
+
 /// Cached blockhash with timestamp
 #[derive(Clone)]
 pub struct CachedBlockhash {
@@ -19,6 +22,18 @@ pub struct CachedBlockhash {
     pub fetched_at: Instant,
 }
 
+impl CachedBlockhash {
+    /// Time since this blockhash was fetched
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+
+    /// Whether transactions signed with this blockhash risk expiring before landing
+    pub fn is_near_expiry(&self) -> bool {
+        self.age() >= BLOCKHASH_NEAR_EXPIRY
+    }
+}
+
 /// Shared cached blockhash wrapped in Arc<RwLock> for thread-safe access
 pub type SharedCachedBlockhash = Arc<RwLock<Option<CachedBlockhash>>>;
 
@@ -111,9 +126,19 @@ pub async fn get_blockhash(
     drop(cache); // Release read lock before fetching
 
     debug!("🔄 Cache miss - fetching blockhash from RPC");
+    refresh_blockhash(cached, rpc_client).await
+}
+
+/// Fetch a blockhash from RPC, bypassing the cache, and store it
+///
+/// Used when a transaction was rejected for an expired blockhash - the cached
+/// one may be the very hash the cluster no longer recognizes.
+pub async fn refresh_blockhash(
+    cached: &SharedCachedBlockhash,
+    rpc_client: &SolanaRpcClient,
+) -> Result<Hash> {
     let hash = rpc_client.get_latest_blockhash()?;
 
-    // Update cache
     let mut cache = cached.write().await;
     *cache = Some(CachedBlockhash {
         hash,
@@ -123,6 +148,20 @@ pub async fn get_blockhash(
     Ok(hash)
 }
 
+/// Age of the cached blockhash (None before the first successful fetch)
+pub async fn blockhash_age(cached: &SharedCachedBlockhash) -> Option<Duration> {
+    cached.read().await.as_ref().map(CachedBlockhash::age)
+}
+
+/// Whether an RPC/JITO error means the transaction's blockhash has expired
+pub fn is_blockhash_expired_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("blockhash not found")
+        || error.contains("blockhashnotfound")
+        || error.contains("block height exceeded")
+        || error.contains("expired blockhash")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,5 +176,21 @@ mod tests {
 
         assert_eq!(cached.hash, hash);
         assert!(cached.fetched_at.elapsed() < Duration::from_millis(10));
+        assert!(!cached.is_near_expiry());
+    }
+
+    #[test]
+    fn test_blockhash_expired_error_detection() {
+        assert!(is_blockhash_expired_error(
+            "RPC response error -32002: Transaction simulation failed: Blockhash not found"
+        ));
+        assert!(is_blockhash_expired_error(
+            "bundle rejected: BlockhashNotFound"
+        ));
+        assert!(is_blockhash_expired_error(
+            "transaction expired: block height exceeded"
+        ));
+        assert!(!is_blockhash_expired_error("429 Too Many Requests"));
+        assert!(!is_blockhash_expired_error("insufficient funds for fee"));
     }
 }
@@ -0,0 +1,146 @@
+// Captured mainnet accounts and swaps for parser/quoter tests
+//
+// Fixtures are written by `scripts/capture_fixture.py` into `fixtures/<name>.json`:
+// raw pool (and tick array / extension) accounts at one slot, the pool vaults as
+// decoded by the RPC node, and optionally the next swap on the pool with its
+// amounts taken from the transaction's token balance deltas.
+//
+// Tests built on these compare our parsers and quoters against values the chain
+// produced, never against bytes written at our own offsets. They are
+// `#[ignore]`d until the fixture is captured; run them with
+// `cargo test -- --ignored` once `fixtures/` is populated.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Raw account as returned by `getMultipleAccounts` (base64 data)
+#[derive(Debug, Deserialize)]
+struct RawAccount {
+    data: String,
+}
+
+/// SPL token account as decoded by the RPC node (`jsonParsed`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CapturedTokenAccount {
+    pub mint: String,
+    pub owner: String,
+    pub amount: u64,
+}
+
+/// First swap on the pool after the snapshot (nothing else touched the pool in between)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CapturedSwap {
+    pub signature: String,
+    pub instruction_accounts: Vec<String>,
+    pub vault_in: String,
+    pub vault_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Vault balances right before the swap (from the transaction meta)
+    pub reserve_in: u64,
+    pub reserve_out: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FixtureFile {
+    program: String,
+    pool: String,
+    accounts: HashMap<String, RawAccount>,
+    #[serde(default)]
+    token_accounts: HashMap<String, CapturedTokenAccount>,
+    swap: Option<CapturedSwap>,
+}
+
+/// A captured pool snapshot
+#[derive(Debug)]
+pub struct CapturedFixture {
+    pub program: Pubkey,
+    pub pool: Pubkey,
+    accounts: HashMap<Pubkey, Vec<u8>>,
+    token_accounts: HashMap<Pubkey, CapturedTokenAccount>,
+    swap: Option<CapturedSwap>,
+}
+
+impl CapturedFixture {
+    /// Load `fixtures/<name>.json`
+    ///
+    /// # Errors
+    /// Returns error if the fixture hasn't been captured or doesn't parse
+    pub fn load(name: &str) -> Result<Self> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join(format!("{}.json", name));
+        let json = std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "Fixture {} not captured - run scripts/capture_fixture.py --name {}",
+                path.display(),
+                name
+            )
+        })?;
+        let file: FixtureFile = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse fixture {}", path.display()))?;
+
+        let mut accounts = HashMap::new();
+        for (address, raw) in file.accounts {
+            let data = BASE64
+                .decode(&raw.data)
+                .with_context(|| format!("Invalid base64 for {}", address))?;
+            accounts.insert(pubkey(&address)?, data);
+        }
+        let token_accounts = file
+            .token_accounts
+            .into_iter()
+            .map(|(address, account)| Ok((pubkey(&address)?, account)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            program: pubkey(&file.program)?,
+            pool: pubkey(&file.pool)?,
+            accounts,
+            token_accounts,
+            swap: file.swap,
+        })
+    }
+
+    /// Raw data of a captured account
+    pub fn data(&self, address: &Pubkey) -> &[u8] {
+        &self
+            .accounts
+            .get(address)
+            .unwrap_or_else(|| panic!("{} not in fixture", address))
+    }
+
+    /// Raw data of the pool account
+    pub fn pool_data(&self) -> &[u8] {
+        self.data(&self.pool)
+    }
+
+    /// Data of a captured account, or None if it wasn't snapshotted
+    pub fn try_data(&self, address: &Pubkey) -> Option<&[u8]> {
+        self.accounts.get(address).map(Vec::as_slice)
+    }
+
+    /// RPC-decoded token account (pool vaults)
+    pub fn token_account(&self, address: &Pubkey) -> &CapturedTokenAccount {
+        self.token_accounts
+            .get(address)
+            .unwrap_or_else(|| panic!("Token account {} not in fixture", address))
+    }
+
+    /// The captured swap (panics if the fixture was taken without `--swap`)
+    pub fn swap(&self) -> &CapturedSwap {
+        self.swap
+            .as_ref()
+            .expect("Fixture has no swap - recapture with --swap")
+    }
+}
+
+/// Parse a base58 address from a fixture
+pub fn pubkey(address: &str) -> Result<Pubkey> {
+    Pubkey::from_str(address).map_err(|e| anyhow!("Invalid pubkey {}: {}", address, e))
+}
@@ -1,6 +1,22 @@
 use anyhow::{Context, Result};
+use solana_sdk::commitment_config::CommitmentLevel;
+use std::collections::HashMap;
 use std::env;
 
+use crate::dex_filter::DexFilter;
+use crate::jito_submitter::{DEFAULT_BUNDLE_INTERVAL_MS, MIN_BUNDLE_INTERVAL_MS};
+use crate::log_verbosity::{LogVerbosity, DEFAULT_LOG_SPREAD_THRESHOLD_PCT};
+use crate::profit_currency::ProfitCurrency;
+use crate::rpc_client::{
+    DEFAULT_RPC_READ_RETRIES, DEFAULT_RPC_RETRY_BACKOFF_MS, MAX_RPC_READ_RETRIES,
+};
+use crate::simple_triangle_detector::{TriangleAnchor, DEFAULT_TRIANGLE_ANCHORS};
+use crate::slippage::MAX_DYNAMIC_SLIPPAGE_BPS;
+use crate::spread_tiers::{SpreadTiers, DEFAULT_SPREAD_TIERS};
+use crate::swap_executor::MAX_TRANSACTION_COMPUTE_UNITS;
+use crate::tip_strategy::{TipStrategy, DEFAULT_TIP_STRATEGY};
+use crate::types::DexType;
+
 /// Configuration for the arbitrage bot
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,45 +24,115 @@ pub struct Config {
     pub solana_rpc_url: Option<String>,
     pub capital_sol: f64,
     pub max_position_size_sol: f64,
-    pub min_profit_margin_multiplier: f64, // Replaced min_profit_sol with margin multiplier
+    pub max_position_pct_of_balance: Option<f64>, // Scale max position with live balance
+    pub token_position_overrides: HashMap<String, f64>, // Token mint -> max position SOL
+    pub min_profit_margin_multiplier: f64,        // Replaced min_profit_sol with margin multiplier
     pub min_spread_percentage: f64,
     pub max_daily_trades: u64,
     pub daily_loss_limit_sol: f64,
+    pub daily_profit_target_sol: Option<f64>, // Stop trading once realized profit reaches this
+    pub profit_target_reset_on_new_day: bool, // Target counts today's profit, not lifetime
     pub max_consecutive_failures: u64,
+    pub failure_cooldown_secs: u64, // Pause execution this long after max_consecutive_failures
+    pub hard_stop_on_failures: bool, // Exit instead of cooling down after max_consecutive_failures
     pub enable_real_trading: bool,
     pub paper_trading: bool,
+    pub shadow_mode: bool, // Build + simulate real transactions, never submit
+    pub profit_verify_sim: bool, // Simulate per-leg outputs and require simulated profit
+    pub split_leg_bundles: bool, // Submit one transaction per leg in a single JITO bundle
     pub wallet_private_key: Option<String>,
+    pub wallet_keystore: Option<String>, // Encrypted keystore, preferred over the plaintext key
     pub jupiter_api_key: Option<String>,
+    pub scan_interval_ms: u64, // Main loop sleep between scans (>= JITO_BUNDLE_RATE_MS)
+    pub scan_interval_floor_ms: u64, // Adaptive interval lower bound (busy markets)
+    pub scan_interval_ceiling_ms: u64, // Adaptive interval upper bound (quiet markets)
+    pub scan_interval_step_ms: u64, // Added to the interval after each empty scan
+    pub stale_opportunity_threshold_ms: u64, // Max opportunity age before skipping
+    pub max_opportunity_slot_age: u64, // Max slots between quote observation and submission
+    pub max_price_age_ms: u64, // Max quote age on either leg of a pair
+    pub shredstream_timeout_ms: u64, // Timeout for ShredStream price fetch
+    pub pool_blacklist_threshold: u32, // Consecutive pool failures before blacklisting
+    pub pool_blacklist_cooldown_secs: u64, // How long a blacklisted pool is skipped
+    pub compute_unit_price_min: u64, // Priority fee at calm JITO tip floor (micro-lamports/CU)
+    pub compute_unit_price_max: u64, // Priority fee at congested JITO tip floor (micro-lamports/CU)
+    pub priority_fee_percentile: u8, // Percentile of recent fees on our pools (0 = congestion scaling only)
+    pub dex_compute_units: HashMap<DexType, u32>, // Per-DEX swap CU estimate overrides
+    pub default_slippage_bps: u16,   // Swap slippage fallback when volatility data is unavailable
+    pub account_cache_ttl_ms: u64,   // RPC account data cache TTL (validation paths)
+    pub sim_commitment: CommitmentLevel, // Commitment transactions are simulated against
+    pub read_commitment: CommitmentLevel, // Commitment for account/balance reads
+    pub rpc_max_rps: u32,            // Token-bucket cap on outbound RPC calls (0 = unlimited)
+    pub jito_max_queue_depth: usize, // Max bundles queued for JITO (oldest dropped when full)
+    pub jito_endpoints: Vec<String>, // Block engine failover list (empty = built-in regions)
+    pub jito_latency_probe_secs: u64, // Interval between endpoint latency probes (0 = off)
+    pub tip_strategy: TipStrategy,   // How every JITO tip is sized
+    pub paper_seed: u64,             // Seed for deterministic paper-trading fills
+    pub max_pairs_per_token: usize,  // Top-N buy/sell pool pairings kept per token
+    pub min_dexes_per_token: usize,  // Pools a token needs before it's scanned
+    pub max_dexes_per_token: Option<usize>, // Skip tokens priced on more pools (None = unlimited)
+    pub auto_tune_spread: bool, // Apply spread-history min spread per token (advisory otherwise)
+    pub spread_analysis_window_secs: u64, // Rolling spread history kept per token
+    pub spread_tiers: SpreadTiers, // Max realistic spread per token tier (stable / volume)
+    pub max_concurrent_opportunities: usize, // Non-conflicting executions per scan (1 = first only)
+    pub per_token_cooldown_ms: u64, // Min time between trades on the same token (0 = off)
+    pub min_net_profit_lamports: u64, // Min triangle net profit after JITO tip + gas
+    pub profit_divergence_alert_sol: f64, // Warn when realized trails estimated profit by more on average
+    pub max_price_impact_pct: f64,        // Position capped so per-pool impact stays below this
+    pub metrics_port: Option<u16>,        // Prometheus /metrics port (disabled if unset)
+    pub status_port: Option<u16>,         // JSON GET /status port (disabled if unset)
+    pub token_list_file: Option<String>,  // JSON/TOML allowlist + denylist (hot-reloaded)
+    pub allow_mintable_tokens: bool,      // Trade mints with live mint/freeze authority
+    pub trade_log_path: Option<String>,   // CSV log of every trade attempt
+    pub token_metadata_source: Option<String>, // Token list file/URL backfilling mint symbols
+    pub opportunity_journal_dir: Option<String>, // JSON file per real execution attempt (--replay)
+    pub drain_on_shutdown: bool, // Submit queued JITO bundles before exiting (abandoned otherwise)
+    pub fee_reserve_sol: f64,    // Wallet balance never traded (gas + tips buffer)
+    pub fee_reserve_tip_multiple: Option<f64>, // Grow the reserve to this × p99 landed tip (None = fixed)
+    pub dex_filter: DexFilter, // DEXs scanned and traded (ENABLED_DEXES / DISABLED_DEXES)
+    pub profit_currency: ProfitCurrency, // Currency profit stats and limits are shown/enforced in
+    pub daily_loss_limit_usd: Option<f64>, // USD loss limit (PROFIT_CURRENCY=USD, SOL limit if unset)
+    pub daily_profit_target_usd: Option<f64>, // USD profit target (PROFIT_CURRENCY=USD, SOL target if unset)
+    pub sol_usd_price_url: Option<String>, // SOL/USD oracle (None = Jupiter), optional `#<JSON pointer>`
+    pub sol_usd_refresh_secs: u64,         // SOL/USD cache refresh interval
+    pub min_wallet_balance_sol: Option<f64>, // Halt trading when the wallet drops below this
+    pub jito_bundle_rate_ms: u64,          // Minimum time between JITO bundle submissions
+    pub triangle_anchors: Vec<TriangleAnchor>, // Tokens triangle cycles start and end in
+    pub log_verbosity: LogVerbosity,       // Which scan/opportunity info logs are emitted
+    pub log_spread_threshold_pct: f64,     // Spreads above this are logged (normal/verbose)
+    pub rpc_read_retries: u32,             // Retries of an RPC read after a transient failure
+    pub rpc_retry_backoff_ms: u64,         // Backoff before the first retry, doubled per retry
+    pub scan_jitter_ms: u64,               // Max random delay added to each scan sleep (0 = off)
+    pub leg_slippage_bps: u16,             // Per-leg triangle slippage before depth widening
+    pub max_leg_slippage_bps: u16,         // Cap on a leg's depth-widened slippage
+    pub min_net_profit_sol: f64,           // Absolute net profit floor after costs (0 = off)
+    pub reserve_recheck: bool,             // Re-quote each leg on fresh reserves before building
+    pub max_daily_tip_budget_sol: Option<f64>, // JITO tips per UTC day before execution halts
+    pub shredstream_urls: Vec<String>, // Price feed failover list, primary (= shredstream_url) first
+    pub self_trade_guard: bool,        // Skip routes overlapping our unresolved bundles
+    pub compute_budget_warm_pairs: Vec<(u64, u32)>, // (CU price, CU limit) pre-built at startup
+    pub metrics_bind_addr: std::net::IpAddr, // Interface the metrics/status servers listen on
 }
 
 impl Config {
-    /// Calculate JITO tip based on profit (3-10% of profit, capped at 0.001 SOL)
+    /// Calculate JITO tip for a profit with the configured `TipStrategy`
+    ///
+    /// Floor-following components use the conservative default tip floor, as
+    /// the detectors screening with this have no live floor data.
     ///
     /// # Arguments
     /// * `gross_profit_sol` - Expected gross profit in SOL
     ///
     /// # Returns
-    /// JITO tip amount in SOL (0.0001-0.001 SOL range)
-    ///
-    /// # Strategy
-    /// - Small profits (<0.1 SOL): 3% tip
-    /// - Medium profits (0.1-1.0 SOL): 5% tip
-    /// - Large profits (>1.0 SOL): 7% tip
-    /// - Capped at 0.001 SOL maximum, minimum 0.0001 SOL
+    /// JITO tip amount in SOL
     pub fn calculate_jito_tip(&self, gross_profit_sol: f64) -> f64 {
-        if gross_profit_sol < 0.1 {
-            // Small profits: 3-5% tip
-            let tip = gross_profit_sol * 0.03;
-            tip.min(0.001).max(0.0001) // Cap at 0.001 SOL, min 0.0001 SOL
-        } else if gross_profit_sol < 1.0 {
-            // Medium profits: 5-7% tip
-            let tip = gross_profit_sol * 0.05;
-            tip.min(0.001).max(0.0001)
-        } else {
-            // Large profits: 7-10% tip
-            let tip = gross_profit_sol * 0.07;
-            tip.min(0.001).max(0.0001)
-        }
+        let gross_profit_lamports = (gross_profit_sol.max(0.0) * 1_000_000_000.0) as u64;
+        self.tip_strategy.tip_lamports(gross_profit_lamports, None) as f64 / 1_000_000_000.0
+    }
+
+    /// Net profit floor in lamports: the stricter of MIN_NET_PROFIT_SOL and MIN_NET_PROFIT_LAMPORTS
+    pub fn net_profit_floor_lamports(&self) -> u64 {
+        let sol_floor = (self.min_net_profit_sol.max(0.0) * 1_000_000_000.0).round() as u64;
+        sol_floor.max(self.min_net_profit_lamports)
     }
 
     /// Calculate total fees for a trade (JITO tip + gas + compute)
@@ -164,6 +250,15 @@ impl Config {
         Ok(())
     }
 
+    /// Decode a base58 wallet private key into a keypair
+    fn decode_keypair(key: &str) -> Result<solana_sdk::signature::Keypair> {
+        let bytes = bs58::decode(key)
+            .into_vec()
+            .context("Invalid wallet private key: not valid base58")?;
+        solana_sdk::signature::Keypair::from_bytes(&bytes)
+            .context("Invalid wallet private key: does not decode to a valid keypair")
+    }
+
     /// Load configuration from environment variables
     ///
     /// # Returns
@@ -172,18 +267,98 @@ impl Config {
     ///
     /// # Environment Variables
     /// - `SHREDSTREAM_SERVICE_URL`: ShredStream price feed URL (default: http://localhost:8080)
+    /// - `SHREDSTREAM_URLS`: Comma-separated ShredStream URLs in failover order; the primary is retried every 30s while failed over (default: `SHREDSTREAM_SERVICE_URL`)
     /// - `SOLANA_RPC_URL`: Solana RPC endpoint (optional)
     /// - `WALLET_PRIVATE_KEY`: Base58-encoded private key (optional)
+    /// - `WALLET_KEYSTORE`: Path to a scrypt + AES-GCM encrypted keystore (create one with `create-keystore <path>`), used instead of `WALLET_PRIVATE_KEY` when set (optional)
+    /// - `WALLET_PASSPHRASE`: Keystore passphrase (optional, prompted for without echo if unset)
     /// - `CAPITAL_SOL`: Total trading capital (default: 2.0 SOL)
     /// - `MAX_POSITION_SIZE_SOL`: Max position per trade (default: 0.5 SOL)
+    /// - `MAX_POSITION_PCT_OF_BALANCE`: Max position as a fraction of tradeable wallet balance, capped by `MAX_POSITION_SIZE_SOL` (optional, e.g. 0.25)
+    /// - `FEE_RESERVE_SOL`: Wallet balance kept out of trading for gas and tips (default: 0.1)
+    /// - `FEE_RESERVE_TIP_MULTIPLE`: Raise the fee reserve to this many p99 landed JITO tips when congestion makes that larger than `FEE_RESERVE_SOL` (optional, fixed reserve if unset)
+    /// - `TOKEN_POSITION_OVERRIDES`: Per-token max position in SOL, as inline JSON (`{"<mint>": 0.05}`) or a path to a JSON file; replaces `MAX_POSITION_SIZE_SOL` for those tokens (optional)
     /// - `MIN_PROFIT_MARGIN_MULTIPLIER`: Profit margin multiplier (default: 2.0)
     /// - `MIN_SPREAD_PERCENTAGE`: Minimum spread to consider (default: 0.3%)
     /// - `MAX_DAILY_TRADES`: Daily trade limit (default: 200)
     /// - `DAILY_LOSS_LIMIT_SOL`: Max daily loss (default: 0.5 SOL)
+    /// - `DAILY_PROFIT_TARGET_SOL`: Stop the bot once realized profit reaches this (optional, disabled if unset)
+    /// - `MIN_WALLET_BALANCE_SOL`: Stop trading (like the daily loss limit) once the wallet balance falls below this (optional, disabled if unset)
+    /// - `PROFIT_CURRENCY`: `SOL` or `USD`; USD also reports profit in USD and enforces the USD limits below, the ledger stays in SOL (default: SOL)
+    /// - `DAILY_LOSS_LIMIT_USD`: Max daily loss in USD when `PROFIT_CURRENCY=USD`; `DAILY_LOSS_LIMIT_SOL` applies until a SOL/USD price is known (optional)
+    /// - `DAILY_PROFIT_TARGET_USD`: Profit target in USD when `PROFIT_CURRENCY=USD`, replacing `DAILY_PROFIT_TARGET_SOL` (optional)
+    /// - `SOL_USD_PRICE_URL`: JSON endpoint for SOL/USD, suffix `#<JSON pointer>` to select the field (optional, default: Jupiter price API, pointer `/price`)
+    /// - `SOL_USD_REFRESH_SECS`: SOL/USD price refresh interval (default: 60)
+    /// - `PROFIT_TARGET_RESET_ON_NEW_DAY`: Measure the profit target against today's profit; false measures it against the persisted lifetime total, which stays reached across restarts. The `--reset-on-new-day` flag forces it on (default: true)
     /// - `MAX_CONSECUTIVE_FAILURES`: Failure threshold (default: 100)
+    /// - `FAILURE_COOLDOWN_SECS`: Pause execution (scanning continues) this long after hitting the failure threshold, then reset the streak (default: 300)
+    /// - `HARD_STOP_ON_FAILURES`: Stop the bot at the failure threshold instead of cooling down (default: false)
     /// - `ENABLE_REAL_TRADING`: Enable live trading (default: false)
     /// - `PAPER_TRADING`: Paper trading mode (default: true)
+    /// - `SHADOW_MODE`: Build and simulate real transactions without submitting (default: false, requires PAPER_TRADING=false)
+    /// - `PROFIT_VERIFY_SIM`: Simulate each transaction, decode actual per-leg outputs and only submit if still profitable after costs (default: false)
+    /// - `SPLIT_LEG_BUNDLES`: Submit each swap leg as its own transaction inside one JITO bundle, tip split across the legs (default: false)
     /// - `JUPITER_API_KEY`: Jupiter API key (optional)
+    /// - `JITO_BUNDLE_RATE_MS`: Minimum time between JITO bundle submissions; lower only with a higher-tier block engine key (default: 1100ms, min: 200ms)
+    /// - `SCAN_INTERVAL_MS`: Initial main loop scan interval (default: 1500ms, min: `JITO_BUNDLE_RATE_MS`)
+    /// - `SCAN_INTERVAL_FLOOR_MS`: Adaptive interval floor, halved towards after scans with opportunities (default and min: `JITO_BUNDLE_RATE_MS`)
+    /// - `SCAN_INTERVAL_CEILING_MS`: Adaptive interval ceiling, grown towards after empty scans (default: 5000ms)
+    /// - `SCAN_INTERVAL_STEP_MS`: Interval increase per empty scan (default: 250ms)
+    /// - `SCAN_JITTER_MS`: Max random delay added to each scan sleep so submission timing isn't periodic; only ever added, so never below the JITO rate limit, but opportunities wait ~half of it longer on average (default: 0, off)
+    /// - `LEG_SLIPPAGE_BPS`: Per-leg triangle slippage for deep pools; each leg is widened by its own pool's price impact (default: 100)
+    /// - `MAX_LEG_SLIPPAGE_BPS`: Cap on a leg's depth-widened slippage (default: 300)
+    /// - `MIN_NET_PROFIT_SOL`: Absolute net profit floor after all costs, enforced at detection and again before execution even when the dynamic spread check passes; the stricter of this and MIN_NET_PROFIT_LAMPORTS applies (default: 0, off)
+    /// - `RESERVE_RECHECK`: Re-read pool reserves and re-estimate every leg right before building; abort if the re-estimated net profit fell below the floor (default: true)
+    /// - `MAX_DAILY_TIP_BUDGET_SOL`: JITO tips spent on submitted bundles per UTC day; once used up, execution halts (scanning continues) until the day rolls over (optional, disabled if unset)
+    /// - `STALE_OPPORTUNITY_THRESHOLD_MS`: Max opportunity age before skipping (default: 100ms)
+    /// - `MAX_OPPORTUNITY_SLOT_AGE`: Max slots an opportunity's quotes may lag the feed's latest slot; wall-clock threshold applies when slots are unknown (default: 2)
+    /// - `MAX_PRICE_AGE_MS`: Reject buy/sell pairs where either quote is older than this (default: 2000ms)
+    /// - `SHREDSTREAM_TIMEOUT_MS`: ShredStream price fetch timeout (default: 500ms)
+    /// - `POOL_BLACKLIST_THRESHOLD`: Consecutive failures before a pool is blacklisted (default: 5)
+    /// - `POOL_BLACKLIST_COOLDOWN_SECS`: Pool blacklist duration (default: 600s)
+    /// - `COMPUTE_UNIT_PRICE_MIN`: Priority fee floor in micro-lamports/CU (default: 1000)
+    /// - `COMPUTE_UNIT_PRICE_MAX`: Priority fee ceiling under congestion (default: 100000)
+    /// - `PRIORITY_FEE_PERCENTILE`: Price each transaction at this percentile of recent prioritization fees on the pools it writes, within the min/max bounds (default: 75, 0 = JITO congestion scaling only)
+    /// - `DEX_COMPUTE_UNITS`: Per-DEX compute units for one swap, as inline JSON (`{"MeteoraDlmm": 180000}`); replaces the builder estimate used to size the CU limit (optional)
+    /// - `COMPUTE_BUDGET_WARM_PAIRS`: Compute budget pairs built into the executor's cache at startup, `price:limit` comma-separated (default: the executor's default pair)
+    /// - `ENABLED_DEXES`: Comma-separated DEXs to scan and trade, feed prefix or DexType name (`PumpSwap,Meteora_DLMM`) (optional, all DEXs if unset)
+    /// - `DISABLED_DEXES`: Comma-separated DEXs dropped from the scan and refused by the swap executor, applied after `ENABLED_DEXES` (optional)
+    /// - `TRIANGLE_ANCHORS`: Comma-separated tokens triangles start and end in, sized and costed in that token's units: SOL, USDC, USDT (default: SOL). Cross-DEX pairs stay SOL-anchored
+    /// - `DEFAULT_SLIPPAGE_BPS`: Fallback swap slippage in basis points (default: 50 = 0.5%)
+    /// - `ACCOUNT_CACHE_TTL_MS`: RPC account data cache TTL (default: 400ms, one slot)
+    /// - `SIM_COMMITMENT`: Commitment simulations run against: processed, confirmed or finalized (default: processed)
+    /// - `READ_COMMITMENT`: Commitment for account and balance reads (default: confirmed)
+    /// - `RPC_MAX_RPS`: Max outbound RPC requests per second; calls queue briefly then fail when exceeded (default: 0, unlimited)
+    /// - `RPC_READ_RETRIES`: Retries of an RPC read after a timeout, 429 or 5xx; the circuit breaker counts a failure only once they are exhausted (default: 2, max 10)
+    /// - `RPC_RETRY_BACKOFF_MS`: Wait before the first read retry, doubled for each further retry (default: 100)
+    /// - `JITO_MAX_QUEUE_DEPTH`: Max bundles waiting for JITO submission (default: 3)
+    /// - `DRAIN_ON_SHUTDOWN`: On Ctrl+C, keep submitting queued JITO bundles (rate-limited, bounded wait) instead of abandoning them (default: false)
+    /// - `JITO_ENDPOINTS`: Comma-separated block engine URLs, lowest-latency healthy one is used (default: `JITO_ENDPOINT`, else ny/amsterdam/frankfurt/tokyo)
+    /// - `JITO_LATENCY_PROBE_SECS`: Seconds between `/health` latency probes of the JITO endpoints (default: 30, 0 disables)
+    /// - `TIP_STRATEGY`: How JITO tips are sized - `fixed:<lamports>`, `percent:<bps of profit>` or `floor:<p99 multiplier>`; comma-separate to pay the largest (default: floor:1.1,percent:1000)
+    /// - `PAPER_SEED`: Seed for paper-trading fills; same seed + same opportunities = same results (default: 0)
+    /// - `MAX_PAIRS_PER_TOKEN`: Top-N profitable pool pairings per token (default: 3)
+    /// - `MIN_DEXES_PER_TOKEN`: Pools a token must be priced on before it's scanned (default: 2)
+    /// - `MAX_DEXES_PER_TOKEN`: Skip (and log for review) tokens priced on more pools than this, typically spam/wash-traded tokens (optional, unlimited if unset)
+    /// - `AUTO_TUNE_SPREAD`: Enforce the per-token min spread recommended from spread history instead of only logging it (default: false)
+    /// - `SPREAD_ANALYSIS_WINDOW_SECS`: Rolling window of observed spreads per token (default: 3600)
+    /// - `SPREAD_TIERS`: Max realistic spread % per tier - `stable:<pct>` for stablecoins/LSTs, `<min 24h volume SOL>:<pct>` by thinner pool volume (default: stable:2,1000:10,100:25,0:50)
+    /// - `MAX_CONCURRENT_OPPORTUNITIES`: Non-conflicting cross-DEX opportunities executed per scan - in parallel when paper trading, one after another when live (default: 1)
+    /// - `PER_TOKEN_COOLDOWN_MS`: Minimum time between trades on the same token, even for new opportunities (default: 0, disabled)
+    /// - `SELF_TRADE_GUARD`: Skip opportunities sharing a token or pool with one of our submitted bundles until it lands or fails (default: false)
+    /// - `MIN_NET_PROFIT_LAMPORTS`: Minimum triangle net profit after JITO tip and gas (default: 10000)
+    /// - `PROFIT_DIVERGENCE_ALERT_SOL`: Warn when a DEX route's realized profit trails the cost model's estimate by more than this on average (default: 0.001)
+    /// - `MAX_PRICE_IMPACT_PCT`: Max price impact per pool; positions are shrunk to fit pool depth (default: 1.0%)
+    /// - `METRICS_PORT`: Port for Prometheus `GET /metrics` endpoint (optional, disabled if unset)
+    /// - `STATUS_PORT`: Port for JSON `GET /status` endpoint (optional, disabled if unset)
+    /// - `METRICS_BIND_ADDR`: Interface the metrics and status endpoints listen on; they expose P&L and wallet data, so only bind a public interface behind a firewall (default: 127.0.0.1)
+    /// - `TOKEN_LIST_FILE`: JSON/TOML file with `allow`/`deny` mint lists, reloaded on change (optional, falls back to `TARGET_TOKENS`)
+    /// - `ALLOW_MINTABLE_TOKENS`: Trade tokens whose mint/freeze authority is still live (default: false)
+    /// - `TRADE_LOG_PATH`: Append a CSV row per executed/attempted trade to this file (optional)
+    /// - `LOG_VERBOSITY`: quiet (executions and errors only), normal (plus spreads and opportunities) or verbose (plus cost breakdowns) (default: normal)
+    /// - `LOG_SPREAD_THRESHOLD_PCT`: Spreads above this percentage are logged (default: 0.3)
+    /// - `TOKEN_METADATA_SOURCE`: Token list file or URL (e.g. Jupiter token API) backfilling mint symbols/decimals for logs (optional)
+    /// - `OPPORTUNITY_JOURNAL_DIR`: Write a replayable JSON file (opportunity, pools, costs, transaction, outcome) per real execution attempt to this directory; replay one with `--replay <file>` (optional)
     ///
     /// # Security
     /// - All URLs are validated for proper format
@@ -193,66 +368,144 @@ impl Config {
         // Load .env file
         dotenvy::dotenv().ok();
 
-        // Load and validate ShredStream URL
-        let shredstream_url = env::var("SHREDSTREAM_SERVICE_URL")
-            .unwrap_or_else(|_| "http://localhost:8080".to_string());
-        Self::validate_url(&shredstream_url, "SHREDSTREAM_SERVICE_URL")?;
+        // Unparseable vars fall back to their defaults and are reported together
+        // with the validation problems, so one run lists every mistake
+        let mut problems = EnvProblems::default();
 
-        // Load and validate Solana RPC URL if provided
-        let solana_rpc_url = if let Ok(url) = env::var("SOLANA_RPC_URL") {
-            Self::validate_url(&url, "SOLANA_RPC_URL")?;
-            Some(url)
-        } else {
-            None
+        // Load ShredStream URLs, primary first (all URLs are checked in validate())
+        // SHREDSTREAM_SERVICE_URL is kept as a single-entry list
+        let shredstream_urls: Vec<String> = env::var("SHREDSTREAM_URLS")
+            .or_else(|_| env::var("SHREDSTREAM_SERVICE_URL"))
+            .unwrap_or_else(|_| "http://localhost:8080".to_string())
+            .split(',')
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+        let shredstream_url = shredstream_urls
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "http://localhost:8080".to_string());
+
+        // Load Solana RPC URL and wallet private key if provided (checked in validate())
+        let solana_rpc_url = env::var("SOLANA_RPC_URL").ok();
+        let wallet_private_key = env::var("WALLET_PRIVATE_KEY").ok();
+        let wallet_keystore = env::var("WALLET_KEYSTORE").ok().filter(|p| !p.is_empty());
+
+        // Load balance-proportional position cap if provided
+        let max_position_pct_of_balance =
+            problems.opt_var("MAX_POSITION_PCT_OF_BALANCE", "a valid number");
+
+        // Load congestion-scaled fee reserve multiple if provided
+        let fee_reserve_tip_multiple =
+            problems.opt_var("FEE_RESERVE_TIP_MULTIPLE", "a valid number");
+
+        // Load profit target if provided
+        let daily_profit_target_sol = problems.opt_var("DAILY_PROFIT_TARGET_SOL", "a valid number");
+
+        // Load daily JITO tip budget if provided
+        let max_daily_tip_budget_sol =
+            problems.opt_var("MAX_DAILY_TIP_BUDGET_SOL", "a valid number");
+
+        // Scanning faster than bundles can be sent yields unsubmittable opportunities
+        let jito_bundle_rate_ms = problems.var_or(
+            "JITO_BUNDLE_RATE_MS",
+            DEFAULT_BUNDLE_INTERVAL_MS,
+            "a valid integer",
+        );
+
+        // Load minimum wallet balance if provided (low-balance safety halt)
+        let min_wallet_balance_sol = problems.opt_var("MIN_WALLET_BALANCE_SOL", "a valid number");
+
+        // Load USD limits if provided (PROFIT_CURRENCY=USD)
+        let daily_loss_limit_usd = problems.opt_var("DAILY_LOSS_LIMIT_USD", "a valid number");
+        let daily_profit_target_usd = problems.opt_var("DAILY_PROFIT_TARGET_USD", "a valid number");
+
+        // Load pool-count ceiling if provided (spam token filter)
+        let max_dexes_per_token = problems.opt_var("MAX_DEXES_PER_TOKEN", "a valid integer");
+
+        // Load per-token position overrides (inline JSON object or JSON file path)
+        let token_position_overrides = match env::var("TOKEN_POSITION_OVERRIDES") {
+            Ok(value) if !value.trim().is_empty() => problems.check(
+                Self::parse_token_position_overrides(&value),
+                Default::default,
+            ),
+            _ => HashMap::new(),
         };
 
-        // Load and validate wallet private key if provided
-        let wallet_private_key = if let Ok(key) = env::var("WALLET_PRIVATE_KEY") {
-            Self::validate_private_key(&key)?;
-            Some(key)
-        } else {
-            None
+        // Load per-DEX compute unit overrides (inline JSON object)
+        let dex_compute_units = match env::var("DEX_COMPUTE_UNITS") {
+            Ok(value) if !value.trim().is_empty() => {
+                problems.check(Self::parse_dex_compute_units(&value), Default::default)
+            }
+            _ => HashMap::new(),
+        };
+
+        // Load compute budget pairs to pre-build at startup (price:limit,...)
+        let compute_budget_warm_pairs = match env::var("COMPUTE_BUDGET_WARM_PAIRS") {
+            Ok(value) if !value.trim().is_empty() => {
+                problems.check(Self::parse_compute_budget_pairs(&value), Default::default)
+            }
+            _ => Vec::new(),
         };
 
+        // Load JITO block engine endpoints (JITO_ENDPOINT kept as a single-entry list)
+        let jito_endpoints: Vec<String> = env::var("JITO_ENDPOINTS")
+            .or_else(|_| env::var("JITO_ENDPOINT"))
+            .unwrap_or_default()
+            .split(',')
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+
+        // Load metrics port if provided (opt-in Prometheus endpoint)
+        let metrics_port = problems.opt_var("METRICS_PORT", "a valid port (1-65535)");
+
+        // Load status port if provided (opt-in live status endpoint)
+        let status_port = problems.opt_var("STATUS_PORT", "a valid port (1-65535)");
+
         let config = Self {
             shredstream_url,
 
             solana_rpc_url,
 
-            capital_sol: env::var("CAPITAL_SOL")
-                .unwrap_or_else(|_| "2.0".to_string())
-                .parse()
-                .context("Failed to parse CAPITAL_SOL: must be a valid number")?,
-
-            max_position_size_sol: env::var("MAX_POSITION_SIZE_SOL")
-                .unwrap_or_else(|_| "0.5".to_string())
-                .parse()
-                .context("Failed to parse MAX_POSITION_SIZE_SOL: must be a valid number")?,
-
-            min_profit_margin_multiplier: env::var("MIN_PROFIT_MARGIN_MULTIPLIER")
-                .unwrap_or_else(|_| "2.0".to_string()) // Default: 2x fees (100% margin)
-                .parse()
-                .context("Failed to parse MIN_PROFIT_MARGIN_MULTIPLIER: must be a valid number")?,
-
-            min_spread_percentage: env::var("MIN_SPREAD_PERCENTAGE")
-                .unwrap_or_else(|_| "0.3".to_string()) // HIGH FIX: 0.3% - realistic for cross-DEX arbitrage
-                .parse()
-                .context("Failed to parse MIN_SPREAD_PERCENTAGE: must be a valid number")?,
-
-            max_daily_trades: env::var("MAX_DAILY_TRADES")
-                .unwrap_or_else(|_| "200".to_string())
-                .parse()
-                .context("Failed to parse MAX_DAILY_TRADES: must be a valid integer")?,
-
-            daily_loss_limit_sol: env::var("DAILY_LOSS_LIMIT_SOL")
-                .unwrap_or_else(|_| "0.5".to_string())
-                .parse()
-                .context("Failed to parse DAILY_LOSS_LIMIT_SOL: must be a valid number")?,
-
-            max_consecutive_failures: env::var("MAX_CONSECUTIVE_FAILURES")
-                .unwrap_or_else(|_| "100".to_string()) // Increased for market chaos - keep running!
-                .parse()
-                .context("Failed to parse MAX_CONSECUTIVE_FAILURES: must be a valid integer")?,
+            capital_sol: problems.var_or("CAPITAL_SOL", 2.0, "a valid number"),
+
+            max_position_size_sol: problems.var_or("MAX_POSITION_SIZE_SOL", 0.5, "a valid number"),
+            max_position_pct_of_balance,
+            token_position_overrides,
+
+            min_profit_margin_multiplier: problems.var_or(
+                "MIN_PROFIT_MARGIN_MULTIPLIER",
+                2.0,
+                "a valid number",
+            ), // Default: 2x fees (100% margin)
+
+            min_spread_percentage: problems.var_or("MIN_SPREAD_PERCENTAGE", 0.3, "a valid number"), // HIGH FIX: 0.3% - realistic for cross-DEX arbitrage
+
+            max_daily_trades: problems.var_or("MAX_DAILY_TRADES", 200, "a valid integer"),
+
+            daily_loss_limit_sol: problems.var_or("DAILY_LOSS_LIMIT_SOL", 0.5, "a valid number"),
+
+            daily_profit_target_sol,
+
+            profit_target_reset_on_new_day: env::var("PROFIT_TARGET_RESET_ON_NEW_DAY")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase()
+                == "true"
+                || env::args().any(|arg| arg == "--reset-on-new-day"),
+
+            max_consecutive_failures: problems.var_or(
+                "MAX_CONSECUTIVE_FAILURES",
+                100,
+                "a valid integer",
+            ), // Increased for market chaos - keep running!
+
+            failure_cooldown_secs: problems.var_or("FAILURE_COOLDOWN_SECS", 300, "a valid integer"),
+
+            hard_stop_on_failures: env::var("HARD_STOP_ON_FAILURES")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
 
             enable_real_trading: env::var("ENABLE_REAL_TRADING")
                 .unwrap_or_else(|_| "false".to_string())
@@ -264,46 +517,475 @@ impl Config {
                 .to_lowercase()
                 == "true",
 
+            shadow_mode: env::var("SHADOW_MODE")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+
+            profit_verify_sim: env::var("PROFIT_VERIFY_SIM")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+
+            split_leg_bundles: env::var("SPLIT_LEG_BUNDLES")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+
             wallet_private_key,
+            wallet_keystore,
 
             jupiter_api_key: env::var("JUPITER_API_KEY").ok(),
+
+            scan_interval_ms: problems.var_or("SCAN_INTERVAL_MS", 1500, "a valid integer"), // Synced with JITO rate limit
+
+            scan_interval_floor_ms: problems.var_or(
+                "SCAN_INTERVAL_FLOOR_MS",
+                jito_bundle_rate_ms,
+                "a valid integer",
+            ),
+
+            scan_interval_ceiling_ms: problems.var_or(
+                "SCAN_INTERVAL_CEILING_MS",
+                5000,
+                "a valid integer",
+            ),
+
+            scan_interval_step_ms: problems.var_or("SCAN_INTERVAL_STEP_MS", 250, "a valid integer"),
+
+            stale_opportunity_threshold_ms: problems.var_or(
+                "STALE_OPPORTUNITY_THRESHOLD_MS",
+                100,
+                "a valid integer",
+            ),
+
+            max_opportunity_slot_age: problems.var_or(
+                "MAX_OPPORTUNITY_SLOT_AGE",
+                2,
+                "a valid integer",
+            ),
+
+            max_price_age_ms: problems.var_or("MAX_PRICE_AGE_MS", 2000, "a valid integer"),
+
+            shredstream_timeout_ms: problems.var_or(
+                "SHREDSTREAM_TIMEOUT_MS",
+                500,
+                "a valid integer",
+            ),
+
+            pool_blacklist_threshold: problems.var_or(
+                "POOL_BLACKLIST_THRESHOLD",
+                5,
+                "a valid integer",
+            ),
+
+            pool_blacklist_cooldown_secs: problems.var_or(
+                "POOL_BLACKLIST_COOLDOWN_SECS",
+                600,
+                "a valid integer",
+            ),
+
+            compute_unit_price_min: problems.var_or(
+                "COMPUTE_UNIT_PRICE_MIN",
+                1000,
+                "a valid integer",
+            ),
+
+            compute_unit_price_max: problems.var_or(
+                "COMPUTE_UNIT_PRICE_MAX",
+                100000,
+                "a valid integer",
+            ),
+
+            priority_fee_percentile: problems.var_or(
+                "PRIORITY_FEE_PERCENTILE",
+                75,
+                "an integer 0-100",
+            ),
+            dex_compute_units,
+
+            default_slippage_bps: problems.var_or("DEFAULT_SLIPPAGE_BPS", 50, "a valid integer"),
+
+            account_cache_ttl_ms: problems.var_or("ACCOUNT_CACHE_TTL_MS", 400, "a valid integer"),
+
+            sim_commitment: problems.check(
+                Self::parse_commitment(
+                    "SIM_COMMITMENT",
+                    &env::var("SIM_COMMITMENT").unwrap_or_else(|_| "processed".to_string()),
+                ),
+                || CommitmentLevel::Processed,
+            ),
+
+            read_commitment: problems.check(
+                Self::parse_commitment(
+                    "READ_COMMITMENT",
+                    &env::var("READ_COMMITMENT").unwrap_or_else(|_| "confirmed".to_string()),
+                ),
+                || CommitmentLevel::Confirmed,
+            ),
+
+            rpc_max_rps: problems.var_or("RPC_MAX_RPS", 0, "a valid integer"),
+
+            jito_max_queue_depth: problems.var_or("JITO_MAX_QUEUE_DEPTH", 3, "a valid integer"),
+
+            drain_on_shutdown: env::var("DRAIN_ON_SHUTDOWN")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+
+            fee_reserve_sol: problems.var_or("FEE_RESERVE_SOL", 0.1, "a valid number"),
+            fee_reserve_tip_multiple,
+
+            dex_filter: problems.check(
+                DexFilter::parse(
+                    env::var("ENABLED_DEXES").ok().as_deref(),
+                    env::var("DISABLED_DEXES").ok().as_deref(),
+                ),
+                DexFilter::default,
+            ),
+
+            profit_currency: problems.check(
+                ProfitCurrency::parse(
+                    &env::var("PROFIT_CURRENCY").unwrap_or_else(|_| "SOL".to_string()),
+                ),
+                ProfitCurrency::default,
+            ),
+            daily_loss_limit_usd,
+            daily_profit_target_usd,
+            sol_usd_price_url: env::var("SOL_USD_PRICE_URL").ok(),
+
+            sol_usd_refresh_secs: problems.var_or("SOL_USD_REFRESH_SECS", 60, "a valid integer"),
+            min_wallet_balance_sol,
+            jito_bundle_rate_ms,
+            triangle_anchors: problems.check(
+                TriangleAnchor::parse_list(
+                    &env::var("TRIANGLE_ANCHORS")
+                        .unwrap_or_else(|_| DEFAULT_TRIANGLE_ANCHORS.to_string()),
+                )
+                .context("Failed to parse TRIANGLE_ANCHORS"),
+                || TriangleAnchor::parse_list(DEFAULT_TRIANGLE_ANCHORS).unwrap_or_default(),
+            ),
+            log_verbosity: problems.check(
+                LogVerbosity::parse(
+                    &env::var("LOG_VERBOSITY").unwrap_or_else(|_| "normal".to_string()),
+                ),
+                LogVerbosity::default,
+            ),
+            log_spread_threshold_pct: problems.var_or(
+                "LOG_SPREAD_THRESHOLD_PCT",
+                DEFAULT_LOG_SPREAD_THRESHOLD_PCT,
+                "a valid number",
+            ),
+            rpc_read_retries: problems.var_or(
+                "RPC_READ_RETRIES",
+                DEFAULT_RPC_READ_RETRIES,
+                "a valid integer",
+            ),
+            rpc_retry_backoff_ms: problems.var_or(
+                "RPC_RETRY_BACKOFF_MS",
+                DEFAULT_RPC_RETRY_BACKOFF_MS,
+                "a valid integer",
+            ),
+            scan_jitter_ms: problems.var_or("SCAN_JITTER_MS", 0, "a valid integer"),
+            leg_slippage_bps: problems.var_or("LEG_SLIPPAGE_BPS", 100, "a valid integer"),
+            max_leg_slippage_bps: problems.var_or("MAX_LEG_SLIPPAGE_BPS", 300, "a valid integer"),
+            min_net_profit_sol: problems.var_or("MIN_NET_PROFIT_SOL", 0.0, "a valid number"),
+            reserve_recheck: env::var("RESERVE_RECHECK")
+                .unwrap_or_else(|_| "true".to_string())
+                .to_lowercase()
+                == "true",
+            max_daily_tip_budget_sol,
+            shredstream_urls,
+            self_trade_guard: env::var("SELF_TRADE_GUARD")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+            compute_budget_warm_pairs,
+            metrics_bind_addr: problems.var_or(
+                "METRICS_BIND_ADDR",
+                std::net::IpAddr::from([127, 0, 0, 1]),
+                "an IP address",
+            ),
+
+            jito_endpoints,
+
+            jito_latency_probe_secs: problems.var_or(
+                "JITO_LATENCY_PROBE_SECS",
+                30,
+                "a valid integer",
+            ),
+
+            tip_strategy: problems.check(
+                TipStrategy::parse(
+                    &env::var("TIP_STRATEGY").unwrap_or_else(|_| DEFAULT_TIP_STRATEGY.to_string()),
+                )
+                .context("Failed to parse TIP_STRATEGY"),
+                TipStrategy::default,
+            ),
+
+            paper_seed: problems.var_or("PAPER_SEED", 0, "a valid integer"),
+
+            max_pairs_per_token: problems.var_or("MAX_PAIRS_PER_TOKEN", 3, "a valid integer"),
+
+            min_dexes_per_token: problems.var_or("MIN_DEXES_PER_TOKEN", 2, "a valid integer"),
+
+            max_dexes_per_token,
+
+            auto_tune_spread: env::var("AUTO_TUNE_SPREAD")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+
+            spread_analysis_window_secs: problems.var_or(
+                "SPREAD_ANALYSIS_WINDOW_SECS",
+                3600,
+                "a valid integer",
+            ),
+
+            spread_tiers: problems.check(
+                SpreadTiers::parse(
+                    &env::var("SPREAD_TIERS").unwrap_or_else(|_| DEFAULT_SPREAD_TIERS.to_string()),
+                )
+                .context("Failed to parse SPREAD_TIERS"),
+                SpreadTiers::default,
+            ),
+
+            max_concurrent_opportunities: problems.var_or(
+                "MAX_CONCURRENT_OPPORTUNITIES",
+                1,
+                "a valid integer",
+            ),
+
+            per_token_cooldown_ms: problems.var_or("PER_TOKEN_COOLDOWN_MS", 0, "a valid integer"),
+
+            min_net_profit_lamports: problems.var_or(
+                "MIN_NET_PROFIT_LAMPORTS",
+                10000,
+                "a valid integer",
+            ),
+
+            profit_divergence_alert_sol: problems.var_or(
+                "PROFIT_DIVERGENCE_ALERT_SOL",
+                0.001,
+                "a valid number",
+            ),
+
+            max_price_impact_pct: problems.var_or("MAX_PRICE_IMPACT_PCT", 1.0, "a valid number"),
+
+            metrics_port,
+            status_port,
+
+            token_list_file: env::var("TOKEN_LIST_FILE").ok().filter(|p| !p.is_empty()),
+
+            allow_mintable_tokens: env::var("ALLOW_MINTABLE_TOKENS")
+                .unwrap_or_else(|_| "false".to_string())
+                .to_lowercase()
+                == "true",
+
+            trade_log_path: env::var("TRADE_LOG_PATH").ok().filter(|p| !p.is_empty()),
+            token_metadata_source: env::var("TOKEN_METADATA_SOURCE")
+                .ok()
+                .filter(|s| !s.is_empty()),
+
+            opportunity_journal_dir: env::var("OPPORTUNITY_JOURNAL_DIR")
+                .ok()
+                .filter(|p| !p.is_empty()),
         };
 
-        // MEDIUM FIX: Validate config parameters
-        config.validate()?;
+        // MEDIUM FIX: Validate config parameters (parse failures reported alongside)
+        config.validate(problems.into_inner())?;
 
         Ok(config)
     }
 
+    /// Parse TOKEN_POSITION_OVERRIDES
+    ///
+    /// # Arguments
+    /// * `value` - Inline JSON object (starts with `{`) or path to a JSON file
+    ///
+    /// # Returns
+    /// Token mint -> max position in SOL
+    fn parse_token_position_overrides(value: &str) -> Result<HashMap<String, f64>> {
+        let json = if value.trim_start().starts_with('{') {
+            value.to_string()
+        } else {
+            std::fs::read_to_string(value.trim()).context(format!(
+                "Failed to read TOKEN_POSITION_OVERRIDES file: {}",
+                value
+            ))?
+        };
+
+        let overrides: HashMap<String, f64> = serde_json::from_str(&json).context(
+            "Failed to parse TOKEN_POSITION_OVERRIDES: must be a JSON object of mint -> SOL",
+        )?;
+        for mint in overrides.keys() {
+            mint.parse::<solana_sdk::pubkey::Pubkey>().context(format!(
+                "Invalid mint in TOKEN_POSITION_OVERRIDES: {}",
+                mint
+            ))?;
+        }
+        Ok(overrides)
+    }
+
+    /// Parse a commitment level env var (processed, confirmed or finalized)
+    fn parse_commitment(name: &str, value: &str) -> Result<CommitmentLevel> {
+        match value.trim().to_lowercase().as_str() {
+            "processed" => Ok(CommitmentLevel::Processed),
+            "confirmed" => Ok(CommitmentLevel::Confirmed),
+            "finalized" => Ok(CommitmentLevel::Finalized),
+            other => Err(anyhow::anyhow!(
+                "Failed to parse {}: '{}' (must be processed, confirmed or finalized)",
+                name,
+                other
+            )),
+        }
+    }
+
+    /// Parse DEX_COMPUTE_UNITS
+    ///
+    /// # Arguments
+    /// * `value` - Inline JSON object keyed by DEX type name (e.g. `MeteoraDlmm`)
+    ///
+    /// # Returns
+    /// DEX type -> compute units for one swap
+    fn parse_dex_compute_units(value: &str) -> Result<HashMap<DexType, u32>> {
+        serde_json::from_str(value).context(
+            "Failed to parse DEX_COMPUTE_UNITS: must be a JSON object of DEX type -> compute units",
+        )
+    }
+
+    /// Parse COMPUTE_BUDGET_WARM_PAIRS
+    ///
+    /// # Arguments
+    /// * `value` - Comma-separated `price:limit` pairs (e.g. `1000:240000,5000:480000`)
+    ///
+    /// # Returns
+    /// (micro-lamports per CU, compute unit limit) pairs
+    fn parse_compute_budget_pairs(value: &str) -> Result<Vec<(u64, u32)>> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (price, limit) = pair.split_once(':').with_context(|| {
+                    format!(
+                        "Invalid COMPUTE_BUDGET_WARM_PAIRS entry '{}': expected price:limit",
+                        pair
+                    )
+                })?;
+                Ok((
+                    price.trim().parse().with_context(|| {
+                        format!("Invalid COMPUTE_BUDGET_WARM_PAIRS price '{}'", price)
+                    })?,
+                    limit.trim().parse().with_context(|| {
+                        format!("Invalid COMPUTE_BUDGET_WARM_PAIRS limit '{}'", limit)
+                    })?,
+                ))
+            })
+            .collect()
+    }
+
     /// Validate configuration parameters
     /// MEDIUM FIX: Ensure all config values are sensible
-    fn validate(&self) -> Result<()> {
+    ///
+    /// # Arguments
+    /// * `parse_problems` - Env vars `from_env` could not parse, listed first
+    ///
+    /// # Returns
+    /// * `Ok(())` if the config is usable
+    /// * `Err` listing every problem (numbered), not just the first
+    fn validate(&self, parse_problems: Vec<String>) -> Result<()> {
+        let mut problems = parse_problems;
+        problems.extend(self.validation_errors());
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        let list: Vec<String> = problems
+            .iter()
+            .enumerate()
+            .map(|(i, problem)| format!("  {}. {}", i + 1, problem))
+            .collect();
+        Err(anyhow::anyhow!(
+            "Found {} configuration problem(s):\n{}",
+            problems.len(),
+            list.join("\n")
+        ))
+    }
+
+    /// Every problem with the config, in check order (empty if valid)
+    fn validation_errors(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        // Validate endpoint URLs parse (scheme + no injected characters checked first)
+        let urls = std::iter::once(("SHREDSTREAM_SERVICE_URL", &self.shredstream_url))
+            .chain(
+                self.shredstream_urls
+                    .iter()
+                    .skip(1)
+                    .map(|url| ("SHREDSTREAM_URLS", url)),
+            )
+            .chain(
+                self.solana_rpc_url
+                    .iter()
+                    .map(|url| ("SOLANA_RPC_URL", url)),
+            )
+            .chain(
+                self.jito_endpoints
+                    .iter()
+                    .map(|url| ("JITO_ENDPOINTS", url)),
+            );
+        for (name, url) in urls {
+            if let Err(e) = Self::validate_url(url, name) {
+                problems.push(e.to_string());
+            } else if let Err(e) = reqwest::Url::parse(url) {
+                problems.push(format!(
+                    "Invalid {}: {} is not a valid URL ({})",
+                    name, url, e
+                ));
+            }
+        }
+
+        // Validate the wallet key decodes to a usable keypair (fails late otherwise)
+        if let Some(ref key) = self.wallet_private_key {
+            if let Err(e) = Self::validate_private_key(key) {
+                problems.push(e.to_string());
+            } else if let Err(e) = Self::decode_keypair(key) {
+                problems.push(format!("{:#}", e));
+            }
+        }
+
         // Validate capital is positive
         if self.capital_sol <= 0.0 {
-            return Err(anyhow::anyhow!(
+            problems.push(format!(
                 "Invalid capital_sol: {} (must be > 0)",
                 self.capital_sol
             ));
         }
 
-        // Validate max position size doesn't exceed capital
+        // Validate max position size is positive and doesn't exceed capital
+        if self.max_position_size_sol <= 0.0 {
+            problems.push(format!(
+                "Invalid max_position_size_sol: {} (must be > 0)",
+                self.max_position_size_sol
+            ));
+        }
         if self.max_position_size_sol > self.capital_sol {
-            return Err(anyhow::anyhow!(
+            problems.push(format!(
                 "Invalid max_position_size_sol: {} exceeds capital_sol: {}",
-                self.max_position_size_sol,
-                self.capital_sol
+                self.max_position_size_sol, self.capital_sol
             ));
         }
 
         // Validate profit margin multiplier is reasonable
         if self.min_profit_margin_multiplier < 1.0 {
-            return Err(anyhow::anyhow!(
+            problems.push(format!(
                 "Invalid min_profit_margin_multiplier: {} (must be >= 1.0 for positive margin)",
                 self.min_profit_margin_multiplier
             ));
         }
         if self.min_profit_margin_multiplier > 10.0 {
-            return Err(anyhow::anyhow!(
+            problems.push(format!(
                 "Invalid min_profit_margin_multiplier: {} (> 10.0 is too conservative, bot won't find trades)",
                 self.min_profit_margin_multiplier
             ));
@@ -313,7 +995,7 @@ impl Config {
         // NOTE: min_spread_percentage is DEPRECATED - now calculated dynamically
         // Keeping field for backward compatibility, but 0 is allowed
         if self.min_spread_percentage < 0.0 {
-            return Err(anyhow::anyhow!(
+            problems.push(format!(
                 "Invalid min_spread_percentage: {} (must be >= 0, or 0 for dynamic)",
                 self.min_spread_percentage
             ));
@@ -321,30 +1003,666 @@ impl Config {
 
         // Validate max daily trades is reasonable
         if self.max_daily_trades == 0 {
-            return Err(anyhow::anyhow!(
-                "Invalid max_daily_trades: 0 (bot would do nothing)"
+            problems.push("Invalid max_daily_trades: 0 (bot would do nothing)".to_string());
+        }
+
+        // A 0s cooldown would resume on the next scan, ignoring the failure threshold
+        if self.failure_cooldown_secs == 0 && !self.hard_stop_on_failures {
+            problems.push(
+                "Invalid failure_cooldown_secs: 0 (must be >= 1 unless HARD_STOP_ON_FAILURES=true)"
+                    .to_string(),
+            );
+        }
+
+        // Validate daily loss limit (0 halts before trading, above capital never halts)
+        if !(self.daily_loss_limit_sol > 0.0 && self.daily_loss_limit_sol <= self.capital_sol) {
+            problems.push(format!(
+                "Invalid daily_loss_limit_sol: {} (must be > 0 and <= capital_sol: {})",
+                self.daily_loss_limit_sol, self.capital_sol
+            ));
+        }
+
+        // Shadow mode needs the real swap executor, which paper trading doesn't build
+        if self.shadow_mode && self.paper_trading {
+            problems.push(
+                "Invalid config: SHADOW_MODE=true requires PAPER_TRADING=false (shadow mode builds real transactions)"
+                    .to_string(),
+            );
+        }
+        if self.shadow_mode && self.wallet_private_key.is_none() && self.wallet_keystore.is_none() {
+            problems.push(
+                "Invalid config: SHADOW_MODE=true requires WALLET_PRIVATE_KEY or WALLET_KEYSTORE (transactions are built for simulation)"
+                    .to_string(),
+            );
+        }
+        if let Some(ref path) = self.wallet_keystore {
+            if !std::path::Path::new(path).is_file() {
+                problems.push(format!(
+                    "Invalid config: WALLET_KEYSTORE={} does not exist",
+                    path
+                ));
+            }
+        }
+
+        // Validate JITO bundle rate (too fast gets the key rate-limited or banned)
+        if self.jito_bundle_rate_ms < MIN_BUNDLE_INTERVAL_MS {
+            problems.push(format!(
+                "Invalid jito_bundle_rate_ms: {} (must be >= {} to avoid block engine rate-limit bans)",
+                self.jito_bundle_rate_ms, MIN_BUNDLE_INTERVAL_MS
+            ));
+        }
+
+        // Validate scan interval respects JITO rate limit (1 bundle per jito_bundle_rate_ms)
+        if self.scan_interval_ms < self.jito_bundle_rate_ms {
+            problems.push(format!(
+                "Invalid scan_interval_ms: {} (must be >= {} to respect JITO rate limit of 1 bundle per {}ms)",
+                self.scan_interval_ms,
+                self.jito_bundle_rate_ms,
+                self.jito_bundle_rate_ms
+            ));
+        }
+        if self.scan_interval_floor_ms < self.jito_bundle_rate_ms {
+            problems.push(format!(
+                "Invalid scan_interval_floor_ms: {} (must be >= {} to respect JITO rate limit)",
+                self.scan_interval_floor_ms, self.jito_bundle_rate_ms
+            ));
+        }
+        if self.scan_interval_ceiling_ms < self.scan_interval_floor_ms {
+            problems.push(format!(
+                "Invalid scan_interval_ceiling_ms: {} (must be >= scan_interval_floor_ms {})",
+                self.scan_interval_ceiling_ms, self.scan_interval_floor_ms
+            ));
+        }
+
+        // Validate timeouts are non-zero (0 would skip every opportunity / price fetch)
+        if self.stale_opportunity_threshold_ms == 0 {
+            problems.push(
+                "Invalid stale_opportunity_threshold_ms: 0 (every opportunity would be stale)"
+                    .to_string(),
+            );
+        }
+        if self.max_opportunity_slot_age == 0 {
+            problems.push(
+                "Invalid max_opportunity_slot_age: 0 (any quote behind the latest slot would be stale)"
+                    .to_string(),
+            );
+        }
+        if self.max_price_age_ms == 0 {
+            problems.push("Invalid max_price_age_ms: 0 (every price would be stale)".to_string());
+        }
+        if self.shredstream_timeout_ms == 0 {
+            problems.push(
+                "Invalid shredstream_timeout_ms: 0 (every price fetch would time out)".to_string(),
+            );
+        }
+
+        // Validate pool blacklist threshold (0 would blacklist pools before any attempt)
+        if self.pool_blacklist_threshold == 0 {
+            problems.push("Invalid pool_blacklist_threshold: 0 (must be >= 1)".to_string());
+        }
+
+        // Validate compute unit price bounds
+        if self.compute_unit_price_max < self.compute_unit_price_min {
+            problems.push(format!(
+                "Invalid compute_unit_price_max: {} (must be >= compute_unit_price_min {})",
+                self.compute_unit_price_max, self.compute_unit_price_min
+            ));
+        }
+        if self.priority_fee_percentile > 100 {
+            problems.push(format!(
+                "Invalid priority_fee_percentile: {} (must be 0-100)",
+                self.priority_fee_percentile
+            ));
+        }
+
+        // Validate per-DEX compute units (one swap must fit a transaction)
+        for (dex_type, units) in &self.dex_compute_units {
+            if *units == 0 || *units > MAX_TRANSACTION_COMPUTE_UNITS {
+                problems.push(format!(
+                    "Invalid DEX_COMPUTE_UNITS for {:?}: {} (must be 1-{})",
+                    dex_type, units, MAX_TRANSACTION_COMPUTE_UNITS
+                ));
+            }
+        }
+        for (price, limit) in &self.compute_budget_warm_pairs {
+            if *limit == 0 || *limit > MAX_TRANSACTION_COMPUTE_UNITS {
+                problems.push(format!(
+                    "Invalid COMPUTE_BUDGET_WARM_PAIRS limit for price {}: {} (must be 1-{})",
+                    price, limit, MAX_TRANSACTION_COMPUTE_UNITS
+                ));
+            }
+        }
+
+        // Validate default slippage (0 would make every swap revert)
+        if self.default_slippage_bps == 0 || self.default_slippage_bps > 10_000 {
+            problems.push(format!(
+                "Invalid default_slippage_bps: {} (must be 1-10000)",
+                self.default_slippage_bps
+            ));
+        }
+
+        // Validate JITO queue depth (0 would drop every bundle)
+        if self.jito_max_queue_depth == 0 {
+            problems.push("Invalid jito_max_queue_depth: 0 (must be >= 1)".to_string());
+        }
+
+        // Validate tip strategy (a profit share above 100% tips away more than the trade makes)
+        if self.tip_strategy.profit_share() > 1.0 {
+            problems.push(format!(
+                "Invalid tip_strategy: {} (percent must be <= 10000 bps)",
+                self.tip_strategy
+            ));
+        }
+
+        // Validate pair limit (0 would discard every opportunity)
+        if self.max_pairs_per_token == 0 {
+            problems.push("Invalid max_pairs_per_token: 0 (must be >= 1)".to_string());
+        }
+
+        // Validate pool-count bounds (arbitrage needs 2 pools; max below min skips everything)
+        if self.min_dexes_per_token < 2 {
+            problems.push(format!(
+                "Invalid min_dexes_per_token: {} (must be >= 2)",
+                self.min_dexes_per_token
+            ));
+        }
+        if let Some(max_dexes) = self.max_dexes_per_token {
+            if max_dexes < self.min_dexes_per_token {
+                problems.push(format!(
+                    "Invalid max_dexes_per_token: {} (must be >= min_dexes_per_token: {})",
+                    max_dexes, self.min_dexes_per_token
+                ));
+            }
+        }
+
+        // Validate divergence alert threshold (negative would flag accurate estimates)
+        if !(self.profit_divergence_alert_sol.is_finite()
+            && self.profit_divergence_alert_sol >= 0.0)
+        {
+            problems.push(format!(
+                "Invalid profit_divergence_alert_sol: {} (must be >= 0)",
+                self.profit_divergence_alert_sol
+            ));
+        }
+
+        // Validate spread history window (0 would keep no samples to analyze)
+        if self.spread_analysis_window_secs == 0 {
+            problems.push("Invalid spread_analysis_window_secs: 0 (must be >= 1)".to_string());
+        }
+
+        // Validate execution concurrency (0 would never execute anything)
+        if self.max_concurrent_opportunities == 0 {
+            problems.push("Invalid max_concurrent_opportunities: 0 (must be >= 1)".to_string());
+        }
+
+        // Validate price impact cap (0 rejects everything, 100+ is unbounded)
+        if !(self.max_price_impact_pct > 0.0 && self.max_price_impact_pct < 100.0) {
+            problems.push(format!(
+                "Invalid max_price_impact_pct: {} (must be between 0 and 100)",
+                self.max_price_impact_pct
+            ));
+        }
+
+        // Validate fee reserve (negative would let trades spend the gas buffer)
+        if !(self.fee_reserve_sol.is_finite() && self.fee_reserve_sol >= 0.0) {
+            problems.push(format!(
+                "Invalid fee_reserve_sol: {} (must be >= 0)",
+                self.fee_reserve_sol
+            ));
+        }
+        if let Some(multiple) = self.fee_reserve_tip_multiple {
+            if !(multiple.is_finite() && multiple > 0.0) {
+                problems.push(format!(
+                    "Invalid fee_reserve_tip_multiple: {} (must be > 0)",
+                    multiple
+                ));
+            }
+        }
+
+        // Validate balance fraction (0 would never trade, >1 exceeds the balance)
+        if let Some(pct) = self.max_position_pct_of_balance {
+            if !(pct > 0.0 && pct <= 1.0) {
+                problems.push(format!(
+                    "Invalid max_position_pct_of_balance: {} (must be in (0, 1])",
+                    pct
+                ));
+            }
+        }
+
+        // Validate profit target (<= 0 would stop the bot before its first trade)
+        if let Some(target) = self.daily_profit_target_sol {
+            if !(target.is_finite() && target > 0.0) {
+                problems.push(format!(
+                    "Invalid daily_profit_target_sol: {} (must be > 0)",
+                    target
+                ));
+            }
+        }
+
+        // Validate tip budget (<= 0 would halt before the first bundle)
+        if let Some(budget) = self.max_daily_tip_budget_sol {
+            if !(budget.is_finite() && budget > 0.0) {
+                problems.push(format!(
+                    "Invalid max_daily_tip_budget_sol: {} (must be > 0)",
+                    budget
+                ));
+            }
+        }
+
+        // Validate minimum wallet balance (0 would never halt)
+        if let Some(min) = self.min_wallet_balance_sol {
+            if !(min.is_finite() && min > 0.0) {
+                problems.push(format!(
+                    "Invalid min_wallet_balance_sol: {} (must be > 0)",
+                    min
+                ));
+            }
+        }
+
+        // Validate USD limits (same bounds as their SOL counterparts)
+        for (name, limit) in [
+            ("daily_loss_limit_usd", self.daily_loss_limit_usd),
+            ("daily_profit_target_usd", self.daily_profit_target_usd),
+        ] {
+            if let Some(limit) = limit {
+                if !(limit.is_finite() && limit > 0.0) {
+                    problems.push(format!("Invalid {}: {} (must be > 0)", name, limit));
+                }
+            }
+        }
+        if self.sol_usd_refresh_secs == 0 {
+            problems.push("Invalid sol_usd_refresh_secs: 0 (must be > 0)".to_string());
+        }
+
+        // Validate spread log threshold (negative would log every pairing)
+        if !(self.log_spread_threshold_pct.is_finite() && self.log_spread_threshold_pct >= 0.0) {
+            problems.push(format!(
+                "Invalid log_spread_threshold_pct: {} (must be >= 0)",
+                self.log_spread_threshold_pct
+            ));
+        }
+
+        // Validate RPC read retries (backoff doubles per retry)
+        if self.rpc_read_retries > MAX_RPC_READ_RETRIES {
+            problems.push(format!(
+                "Invalid rpc_read_retries: {} (must be <= {})",
+                self.rpc_read_retries, MAX_RPC_READ_RETRIES
+            ));
+        }
+
+        if !(self.min_net_profit_sol.is_finite() && self.min_net_profit_sol >= 0.0) {
+            problems.push(format!(
+                "Invalid min_net_profit_sol: {} (must be >= 0)",
+                self.min_net_profit_sol
+            ));
+        }
+
+        // Validate per-leg slippage (above MAX_DYNAMIC_SLIPPAGE_BPS it isn't arbitrage)
+        if self.leg_slippage_bps == 0 || self.leg_slippage_bps > MAX_DYNAMIC_SLIPPAGE_BPS {
+            problems.push(format!(
+                "Invalid leg_slippage_bps: {} (must be 1-{})",
+                self.leg_slippage_bps, MAX_DYNAMIC_SLIPPAGE_BPS
+            ));
+        }
+        if self.max_leg_slippage_bps < self.leg_slippage_bps
+            || self.max_leg_slippage_bps > MAX_DYNAMIC_SLIPPAGE_BPS
+        {
+            problems.push(format!(
+                "Invalid max_leg_slippage_bps: {} (must be between leg_slippage_bps {} and {})",
+                self.max_leg_slippage_bps, self.leg_slippage_bps, MAX_DYNAMIC_SLIPPAGE_BPS
             ));
         }
 
+        // Validate per-token position overrides (same bounds as max_position_size_sol)
+        for (mint, size) in &self.token_position_overrides {
+            if !(size.is_finite() && *size > 0.0 && *size <= self.capital_sol) {
+                problems.push(format!(
+                    "Invalid position override for {}: {} SOL (must be > 0 and <= capital_sol: {})",
+                    mint, size, self.capital_sol
+                ));
+            }
+        }
+
+        // Validate metrics port (0 would bind a random port nobody can scrape)
+        if self.metrics_port == Some(0) {
+            problems.push("Invalid metrics_port: 0 (must be 1-65535)".to_string());
+        }
+
+        // Validate status port (same reasoning as metrics_port)
+        if self.status_port == Some(0) {
+            problems.push("Invalid status_port: 0 (must be 1-65535)".to_string());
+        }
+        if self.status_port.is_some() && self.status_port == self.metrics_port {
+            problems.push("Invalid status_port: must differ from metrics_port".to_string());
+        }
+
         // Validate all float values are finite
         if !self.capital_sol.is_finite() {
-            return Err(anyhow::anyhow!("capital_sol must be finite"));
+            problems.push("capital_sol must be finite".to_string());
         }
         if !self.max_position_size_sol.is_finite() {
-            return Err(anyhow::anyhow!("max_position_size_sol must be finite"));
+            problems.push("max_position_size_sol must be finite".to_string());
         }
         if !self.min_profit_margin_multiplier.is_finite() {
-            return Err(anyhow::anyhow!(
-                "min_profit_margin_multiplier must be finite"
-            ));
+            problems.push("min_profit_margin_multiplier must be finite".to_string());
         }
         if !self.min_spread_percentage.is_finite() {
-            return Err(anyhow::anyhow!("min_spread_percentage must be finite"));
+            problems.push("min_spread_percentage must be finite".to_string());
         }
         if !self.daily_loss_limit_sol.is_finite() {
-            return Err(anyhow::anyhow!("daily_loss_limit_sol must be finite"));
+            problems.push("daily_loss_limit_sol must be finite".to_string());
         }
 
-        Ok(())
+        problems
+    }
+}
+
+/// Env var parse failures collected by `Config::from_env`
+#[derive(Debug, Default)]
+struct EnvProblems(Vec<String>);
+
+impl EnvProblems {
+    /// Parse `name`, or `default` when unset
+    ///
+    /// An unparseable value is recorded and `default` returned in its place.
+    fn var_or<T: std::str::FromStr>(&mut self, name: &str, default: T, expected: &str) -> T {
+        match env::var(name) {
+            Ok(value) => match value.parse() {
+                Ok(parsed) => parsed,
+                Err(_) => {
+                    self.record_parse_failure(name, expected);
+                    default
+                }
+            },
+            Err(_) => default,
+        }
+    }
+
+    /// Parse `name` if set (None when unset or unparseable; failures are recorded)
+    fn opt_var<T: std::str::FromStr>(&mut self, name: &str, expected: &str) -> Option<T> {
+        let value = env::var(name).ok()?;
+        let parsed = value.parse().ok();
+        if parsed.is_none() {
+            self.record_parse_failure(name, expected);
+        }
+        parsed
+    }
+
+    /// Value of a custom parser, or `fallback()` with the error recorded
+    fn check<T>(&mut self, result: Result<T>, fallback: impl FnOnce() -> T) -> T {
+        result.unwrap_or_else(|e| {
+            self.0.push(format!("{:#}", e));
+            fallback()
+        })
+    }
+
+    fn record_parse_failure(&mut self, name: &str, expected: &str) {
+        self.0
+            .push(format!("Failed to parse {}: must be {}", name, expected));
+    }
+
+    fn into_inner(self) -> Vec<String> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    fn valid_config() -> Config {
+        Config {
+            shredstream_url: "http://localhost:8080".to_string(),
+            solana_rpc_url: Some("https://api.mainnet-beta.solana.com".to_string()),
+            capital_sol: 2.0,
+            max_position_size_sol: 0.5,
+            max_position_pct_of_balance: None,
+            token_position_overrides: HashMap::new(),
+            min_profit_margin_multiplier: 2.0,
+            min_spread_percentage: 0.0,
+            max_daily_trades: 100,
+            daily_loss_limit_sol: 0.5,
+            daily_profit_target_sol: None,
+            profit_target_reset_on_new_day: true,
+            max_consecutive_failures: 5,
+            failure_cooldown_secs: 300,
+            hard_stop_on_failures: false,
+            enable_real_trading: false,
+            paper_trading: true,
+            shadow_mode: false,
+            profit_verify_sim: false,
+            split_leg_bundles: false,
+            wallet_private_key: Some(Keypair::new().to_base58_string()),
+            wallet_keystore: None,
+            jupiter_api_key: None,
+            scan_interval_ms: 1500,
+            scan_interval_floor_ms: 1100,
+            scan_interval_ceiling_ms: 5000,
+            scan_interval_step_ms: 250,
+            stale_opportunity_threshold_ms: 100,
+            max_opportunity_slot_age: 2,
+            max_price_age_ms: 2000,
+            shredstream_timeout_ms: 500,
+            pool_blacklist_threshold: 5,
+            pool_blacklist_cooldown_secs: 600,
+            compute_unit_price_min: 1000,
+            compute_unit_price_max: 100_000,
+            priority_fee_percentile: 75,
+            dex_compute_units: HashMap::new(),
+            default_slippage_bps: 100,
+            account_cache_ttl_ms: 2000,
+            sim_commitment: CommitmentLevel::Processed,
+            read_commitment: CommitmentLevel::Confirmed,
+            rpc_max_rps: 0,
+            jito_max_queue_depth: 10,
+            drain_on_shutdown: false,
+            fee_reserve_sol: 0.1,
+            fee_reserve_tip_multiple: None,
+            dex_filter: DexFilter::default(),
+            profit_currency: ProfitCurrency::Sol,
+            daily_loss_limit_usd: None,
+            daily_profit_target_usd: None,
+            sol_usd_price_url: None,
+            sol_usd_refresh_secs: 60,
+            min_wallet_balance_sol: None,
+            jito_bundle_rate_ms: 1100,
+            triangle_anchors: TriangleAnchor::parse_list(DEFAULT_TRIANGLE_ANCHORS).unwrap(),
+            log_verbosity: LogVerbosity::Normal,
+            log_spread_threshold_pct: DEFAULT_LOG_SPREAD_THRESHOLD_PCT,
+            rpc_read_retries: DEFAULT_RPC_READ_RETRIES,
+            rpc_retry_backoff_ms: DEFAULT_RPC_RETRY_BACKOFF_MS,
+            scan_jitter_ms: 0,
+            leg_slippage_bps: 100,
+            max_leg_slippage_bps: 300,
+            min_net_profit_sol: 0.0,
+            reserve_recheck: true,
+            max_daily_tip_budget_sol: None,
+            shredstream_urls: vec!["http://localhost:8080".to_string()],
+            self_trade_guard: false,
+            compute_budget_warm_pairs: Vec::new(),
+            metrics_bind_addr: std::net::IpAddr::from([127, 0, 0, 1]),
+            jito_endpoints: vec!["https://mainnet.block-engine.jito.wtf".to_string()],
+            jito_latency_probe_secs: 60,
+            tip_strategy: TipStrategy::default(),
+            paper_seed: 42,
+            max_pairs_per_token: 3,
+            min_dexes_per_token: 2,
+            max_dexes_per_token: None,
+            auto_tune_spread: false,
+            spread_analysis_window_secs: 3600,
+            spread_tiers: SpreadTiers::default(),
+            max_concurrent_opportunities: 1,
+            per_token_cooldown_ms: 0,
+            min_net_profit_lamports: 10_000,
+            profit_divergence_alert_sol: 0.001,
+            max_price_impact_pct: 1.0,
+            metrics_port: None,
+            status_port: None,
+            token_list_file: None,
+            allow_mintable_tokens: false,
+            trade_log_path: None,
+            token_metadata_source: None,
+            opportunity_journal_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_valid_config_has_no_problems() {
+        assert!(valid_config().validation_errors().is_empty());
+        assert!(valid_config().validate().is_ok());
+    }
+
+    #[test]
+    fn test_wallet_key_must_decode_to_keypair() {
+        // Right length and alphabet, but decodes to 88 zero bytes
+        let mut config = valid_config();
+        config.wallet_private_key = Some("1".repeat(88));
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("valid keypair"), "{}", problems[0]);
+    }
+
+    #[test]
+    fn test_urls_must_parse() {
+        let mut config = valid_config();
+        config.solana_rpc_url = Some("https://".to_string());
+        config.jito_endpoints = vec!["block-engine.jito.wtf".to_string()];
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("SOLANA_RPC_URL"));
+        assert!(problems[1].contains("JITO_ENDPOINTS"));
+    }
+
+    #[test]
+    fn test_dex_compute_units_parse_and_bounds() {
+        let overrides =
+            Config::parse_dex_compute_units(r#"{"MeteoraDlmm": 180000, "OrcaWhirlpools": 0}"#)
+                .unwrap();
+        assert_eq!(overrides[&DexType::MeteoraDlmm], 180_000);
+        assert!(Config::parse_dex_compute_units(r#"{"NotADex": 1}"#).is_err());
+
+        let mut config = valid_config();
+        config.dex_compute_units = overrides;
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("OrcaWhirlpools"), "{}", problems[0]);
+    }
+
+    #[test]
+    fn test_jito_bundle_rate_bounds_scan_interval() {
+        // Higher-tier key: faster bundles allow a faster scan floor
+        let mut config = valid_config();
+        config.jito_bundle_rate_ms = 500;
+        config.scan_interval_floor_ms = 500;
+        assert!(config.validation_errors().is_empty());
+
+        // Too fast for any block engine tier
+        config.jito_bundle_rate_ms = MIN_BUNDLE_INTERVAL_MS - 1;
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("jito_bundle_rate_ms"),
+            "{}",
+            problems[0]
+        );
+
+        // Scan floor can't outpace the bundle rate
+        config.jito_bundle_rate_ms = DEFAULT_BUNDLE_INTERVAL_MS;
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 1);
+        assert!(
+            problems[0].contains("scan_interval_floor_ms"),
+            "{}",
+            problems[0]
+        );
+    }
+
+    #[test]
+    fn test_dexes_per_token_bounds() {
+        let mut config = valid_config();
+        config.max_dexes_per_token = Some(15);
+        assert!(config.validation_errors().is_empty());
+
+        config.min_dexes_per_token = 1;
+        config.max_dexes_per_token = Some(0);
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 2);
+        assert!(
+            problems[0].contains("min_dexes_per_token"),
+            "{}",
+            problems[0]
+        );
+        assert!(
+            problems[1].contains("max_dexes_per_token"),
+            "{}",
+            problems[1]
+        );
+    }
+
+    #[test]
+    fn test_tip_strategy_profit_share_bound() {
+        let mut config = valid_config();
+        config.tip_strategy = TipStrategy::parse("floor:1.1,percent:10000").unwrap();
+        assert!(config.validation_errors().is_empty());
+        // 0.1 SOL profit → 0.1 SOL tip at 100%
+        assert!((config.calculate_jito_tip(0.1) - 0.1).abs() < 1e-9);
+
+        config.tip_strategy = TipStrategy::parse("percent:12000").unwrap();
+        let problems = config.validation_errors();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("tip_strategy"), "{}", problems[0]);
+    }
+
+    #[test]
+    fn test_all_problems_reported_numbered() {
+        let mut config = valid_config();
+        config.capital_sol = 1.0;
+        config.max_position_size_sol = 1.5;
+        config.daily_loss_limit_sol = 0.0;
+        config.max_daily_trades = 0;
+
+        let message = config.validate(Vec::new()).unwrap_err().to_string();
+        assert!(message.starts_with("Found 3 configuration problem(s):"));
+        assert!(message.contains("  1. Invalid max_position_size_sol"));
+        assert!(message.contains("  2. Invalid max_daily_trades"));
+        assert!(message.contains("  3. Invalid daily_loss_limit_sol"));
+    }
+
+    #[test]
+    fn test_parse_failures_listed_with_validation_problems() {
+        // Names nothing else reads, so the test can't race real config loading
+        std::env::set_var("CONFIG_TEST_BAD_NUMBER", "two");
+        std::env::set_var("CONFIG_TEST_BAD_PORT", "99999");
+
+        let mut problems = EnvProblems::default();
+        let number: f64 = problems.var_or("CONFIG_TEST_BAD_NUMBER", 2.0, "a valid number");
+        let port: Option<u16> = problems.opt_var("CONFIG_TEST_BAD_PORT", "a valid port");
+        let unset: u64 = problems.var_or("CONFIG_TEST_UNSET", 7, "a valid integer");
+        let tiers = problems.check(
+            SpreadTiers::parse("not tiers").context("Failed to parse SPREAD_TIERS"),
+            SpreadTiers::default,
+        );
+        assert_eq!(number, 2.0);
+        assert_eq!(port, None);
+        assert_eq!(unset, 7);
+        assert_eq!(tiers, SpreadTiers::default());
+
+        let mut config = valid_config();
+        config.max_daily_trades = 0;
+        let message = config
+            .validate(problems.into_inner())
+            .unwrap_err()
+            .to_string();
+        assert!(message.starts_with("Found 4 configuration problem(s):"));
+        assert!(
+            message.contains("  1. Failed to parse CONFIG_TEST_BAD_NUMBER: must be a valid number")
+        );
+        assert!(message.contains("  2. Failed to parse CONFIG_TEST_BAD_PORT: must be a valid port"));
+        assert!(message.contains("  3. Failed to parse SPREAD_TIERS"));
+        assert!(message.contains("  4. Invalid max_daily_trades"));
+
+        std::env::remove_var("CONFIG_TEST_BAD_NUMBER");
+        std::env::remove_var("CONFIG_TEST_BAD_PORT");
     }
 }
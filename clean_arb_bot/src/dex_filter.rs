@@ -0,0 +1,155 @@
+// Runtime DEX enable/disable switches
+//
+// ENABLED_DEXES and DISABLED_DEXES take comma-separated DEX names - either the
+// price feed prefix (`PumpSwap`, `Meteora_DLMM`) or the DexType name
+// (`MeteoraDlmm`). With neither set every DEX is enabled. ENABLED_DEXES limits
+// trading to the listed DEXs; DISABLED_DEXES then removes DEXs from that set.
+//
+// Disabled DEXs are dropped from the scan (their prices never pair up) and
+// refused by the swap executor before any pool resolution or RPC work.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::types::DexType;
+
+/// Which DEXs may be scanned and traded (ENABLED_DEXES / DISABLED_DEXES)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DexFilter {
+    enabled: Option<HashSet<DexType>>, // None = all DEXs
+    disabled: HashSet<DexType>,
+}
+
+impl DexFilter {
+    /// Build the filter from ENABLED_DEXES / DISABLED_DEXES values
+    ///
+    /// # Arguments
+    /// * `enabled` - Comma-separated allowlist (None or empty = all DEXs)
+    /// * `disabled` - Comma-separated denylist, applied after the allowlist
+    ///
+    /// # Errors
+    /// Returns error if any name is not a known DEX
+    pub fn parse(enabled: Option<&str>, disabled: Option<&str>) -> Result<Self> {
+        let enabled = match enabled.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => Some(parse_dex_list(value).context("Failed to parse ENABLED_DEXES")?),
+            None => None,
+        };
+        let disabled = match disabled {
+            Some(value) => parse_dex_list(value).context("Failed to parse DISABLED_DEXES")?,
+            None => HashSet::new(),
+        };
+
+        Ok(Self { enabled, disabled })
+    }
+
+    /// Whether swaps on this DEX may be built
+    pub fn is_enabled(&self, dex_type: &DexType) -> bool {
+        !self.disabled.contains(dex_type)
+            && self
+                .enabled
+                .as_ref()
+                .map_or(true, |enabled| enabled.contains(dex_type))
+    }
+
+    /// Whether a price feed entry (e.g. "PumpSwap_8sLbNZoA") may be scanned
+    ///
+    /// Unrecognized feed names pass unless an allowlist is set.
+    pub fn allows_feed(&self, dex: &str) -> bool {
+        match DexType::from_dex_string(dex) {
+            Ok(dex_type) => self.is_enabled(&dex_type),
+            Err(_) => self.enabled.is_none(),
+        }
+    }
+
+    /// Whether any DEX is switched off
+    pub fn is_restricted(&self) -> bool {
+        self.enabled.is_some() || !self.disabled.is_empty()
+    }
+
+    /// Effective set of enabled DEXs, in declaration order
+    pub fn enabled_dexes(&self) -> Vec<DexType> {
+        DexType::ALL
+            .iter()
+            .filter(|dex_type| self.is_enabled(dex_type))
+            .cloned()
+            .collect()
+    }
+}
+
+impl fmt::Display for DexFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_restricted() {
+            return write!(f, "all");
+        }
+        let names: Vec<&str> = self
+            .enabled_dexes()
+            .iter()
+            .map(DexType::feed_name)
+            .collect();
+        write!(f, "{}", names.join(","))
+    }
+}
+
+/// Parse a comma-separated DEX list
+///
+/// # Errors
+/// Returns error naming the first entry that is not a known DEX
+fn parse_dex_list(value: &str) -> Result<HashSet<DexType>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            DexType::ALL
+                .iter()
+                .find(|dex_type| format!("{:?}", dex_type).eq_ignore_ascii_case(name))
+                .cloned()
+                .map_or_else(|| DexType::from_dex_string(name), Ok)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_dexes_are_filtered_and_refused() {
+        let all = DexFilter::default();
+        assert!(!all.is_restricted());
+        assert!(all.allows_feed("PumpSwap_8sLbNZoA"));
+        assert_eq!(all.enabled_dexes().len(), DexType::ALL.len());
+        assert_eq!(all.to_string(), "all");
+
+        let no_pumpswap = DexFilter::parse(None, Some("PumpSwap")).unwrap();
+        assert!(!no_pumpswap.is_enabled(&DexType::PumpSwap));
+        assert!(!no_pumpswap.allows_feed("PumpSwap_8sLbNZoA"));
+        assert!(no_pumpswap.allows_feed("Meteora_DLMM_5rCf1DM8"));
+        assert!(no_pumpswap.allows_feed("SomeNewDex_abc"));
+        assert_eq!(no_pumpswap.enabled_dexes().len(), DexType::ALL.len() - 1);
+    }
+
+    #[test]
+    fn test_enabled_dexes_allowlist_then_denylist() {
+        let filter = DexFilter::parse(
+            Some("MeteoraDlmm, Raydium_AMM_V4, Orca_Whirlpools"),
+            Some("orcawhirlpools"),
+        )
+        .unwrap();
+        assert_eq!(
+            filter.enabled_dexes(),
+            vec![DexType::MeteoraDlmm, DexType::RaydiumAmmV4]
+        );
+        assert_eq!(filter.to_string(), "Meteora_DLMM,Raydium_AMM_V4");
+        // Allowlist set: unknown feeds are dropped too
+        assert!(!filter.allows_feed("SomeNewDex_abc"));
+
+        // Empty values mean "all enabled"
+        assert_eq!(
+            DexFilter::parse(Some(" "), Some("")).unwrap(),
+            DexFilter::default()
+        );
+        assert!(DexFilter::parse(None, Some("NotADex")).is_err());
+    }
+}
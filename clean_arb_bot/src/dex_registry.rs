@@ -7,9 +7,12 @@ pub struct DexInfo {
     pub program_id: String,
     pub fee_rate: f64,
     pub supports_arbitrage: bool,
-    pub min_liquidity_threshold: u64,
+    pub min_pool_liquidity_sol: f64, // Pools below this 24h SOL volume are skipped in detection
 }
 
+/// Pool liquidity floor for DEXs missing from the registry (SOL of 24h volume)
+pub const DEFAULT_MIN_POOL_LIQUIDITY_SOL: f64 = 10.0;
+
 /// Registry of all supported DEXs
 #[derive(Debug, Clone)]
 pub struct DexRegistry {
@@ -20,7 +23,8 @@ impl DexRegistry {
     pub fn new() -> Self {
         let mut dexs = HashMap::new();
 
-        // DEX configurations: (name, program_id, fee_rate, supports_arb, min_liquidity)
+        // DEX configurations: (name, program_id, fee_rate, supports_arb, min_pool_liquidity_sol)
+        // Deep AMMs accept smaller pools; shallow bonding-curve DEXs need more volume
         let configs = vec![
             // Raydium
             (
@@ -28,21 +32,21 @@ impl DexRegistry {
                 "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
                 0.0025,
                 true,
-                1_000_000,
+                2.0,
             ),
             (
                 "Raydium_CLMM",
                 "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK",
                 0.0025,
                 true,
-                1_000_000,
+                2.0,
             ),
             (
                 "Raydium_CPMM",
                 "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
                 0.0025,
                 true,
-                1_000_000,
+                3.0,
             ),
             // Orca
             (
@@ -50,7 +54,7 @@ impl DexRegistry {
                 "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
                 0.003,
                 true,
-                5_000_000,
+                2.0,
             ),
             // Jupiter
             (
@@ -58,7 +62,7 @@ impl DexRegistry {
                 "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
                 0.001,
                 true,
-                0,
+                0.0,
             ),
             // Meteora
             (
@@ -66,7 +70,7 @@ impl DexRegistry {
                 "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
                 0.003,
                 true,
-                2_000_000,
+                3.0,
             ),
             // Serum
             (
@@ -74,7 +78,7 @@ impl DexRegistry {
                 "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin",
                 0.0022,
                 true,
-                10_000_000,
+                5.0,
             ),
             // PumpSwap (migrated tokens)
             (
@@ -82,7 +86,7 @@ impl DexRegistry {
                 "GMk6j2defJhS7F194toqmJNFNhAkbDXhYJo5oR3Rpump",
                 0.003,
                 true,
-                100_000,
+                25.0,
             ),
             // Others
             (
@@ -90,25 +94,25 @@ impl DexRegistry {
                 "AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6",
                 0.003,
                 true,
-                1_000_000,
+                5.0,
             ),
             (
                 "Lifinity",
                 "EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S",
                 0.0025,
                 true,
-                2_000_000,
+                2.0,
             ),
             (
                 "Crema",
-                "6MLxLqiXaaSUpkgMnWDTuejNZEz3kE7k2woyHGVFw319",
+                "CLMM9tUoggJu2wagPkkqs9eFG4BWhVBZWkP1qv3Sp7tR",
                 0.003,
                 true,
-                1_000_000,
+                5.0,
             ),
         ];
 
-        for (name, program_id, fee_rate, supports_arb, min_liquidity) in configs {
+        for (name, program_id, fee_rate, supports_arb, min_pool_liquidity_sol) in configs {
             if supports_arb {
                 dexs.insert(
                     name.to_string(),
@@ -117,7 +121,7 @@ impl DexRegistry {
                         program_id: program_id.to_string(),
                         fee_rate,
                         supports_arbitrage: supports_arb,
-                        min_liquidity_threshold: min_liquidity,
+                        min_pool_liquidity_sol,
                     },
                 );
             }
@@ -134,6 +138,27 @@ impl DexRegistry {
         self.dexs.get(name)
     }
 
+    /// Registry entry for a price feed DEX string (e.g. "Raydium_CLMM_3ucNos4N")
+    ///
+    /// Feed strings carry a variant and pool suffix, so the longest registered
+    /// name the string starts with wins ("Raydium_CLMM" over "Raydium").
+    pub fn for_dex_string(&self, dex_str: &str) -> Option<&DexInfo> {
+        self.dexs
+            .values()
+            .filter(|dex| dex_str.starts_with(dex.name.as_str()))
+            .max_by_key(|dex| dex.name.len())
+    }
+
+    /// Minimum pool liquidity (SOL of 24h volume) for a price feed DEX string
+    ///
+    /// # Returns
+    /// The DEX's floor, or `DEFAULT_MIN_POOL_LIQUIDITY_SOL` for unregistered DEXs
+    pub fn min_pool_liquidity_sol(&self, dex_str: &str) -> f64 {
+        self.for_dex_string(dex_str)
+            .map(|dex| dex.min_pool_liquidity_sol)
+            .unwrap_or(DEFAULT_MIN_POOL_LIQUIDITY_SOL)
+    }
+
     /// Get all DEX pairs for arbitrage scanning
     pub fn get_arbitrage_pairs(&self) -> Vec<(&DexInfo, &DexInfo)> {
         let dex_list: Vec<&DexInfo> = self.dexs.values().collect();
@@ -148,3 +173,42 @@ impl DexRegistry {
         pairs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_pool_liquidity_per_dex() {
+        let registry = DexRegistry::new();
+
+        // Longest prefix wins: CLMM has its own entry, AMM V4 falls back to "Raydium"
+        assert_eq!(
+            registry
+                .for_dex_string("Raydium_CLMM_3ucNos4N")
+                .unwrap()
+                .name,
+            "Raydium_CLMM"
+        );
+        assert_eq!(
+            registry
+                .for_dex_string("Raydium_AMM_V4_58oQChx4")
+                .unwrap()
+                .name,
+            "Raydium"
+        );
+
+        // Shallow bonding-curve DEX is stricter than a deep AMM
+        assert!(
+            registry.min_pool_liquidity_sol("PumpSwap_9xQeWvG8")
+                > registry.min_pool_liquidity_sol("Orca_Whirlpools_7qbRF6Ys")
+        );
+
+        // Unregistered DEX gets the default floor
+        assert!(registry.for_dex_string("HumidiFi_5kLmNoPq").is_none());
+        assert_eq!(
+            registry.min_pool_liquidity_sol("HumidiFi_5kLmNoPq"),
+            DEFAULT_MIN_POOL_LIQUIDITY_SOL
+        );
+    }
+}
@@ -0,0 +1,167 @@
+// Structured execution errors
+//
+// Execution paths used to fail with free-form anyhow strings, so stats, retry
+// logic and circuit breakers could only classify a failure by matching on its
+// message. `ArbError` gives each failure class its own variant. It implements
+// `std::error::Error`, so `?` still lifts it into `anyhow::Error` at the top
+// level, and `From<anyhow::Error>` recovers an `ArbError` raised deeper in the
+// call stack (through any `.context()` layers) instead of flattening it.
+
+use std::fmt;
+
+use crate::cached_blockhash::is_blockhash_expired_error;
+
+/// Result alias for execution paths that report an `ArbError`
+pub type ArbResult<T> = std::result::Result<T, ArbError>;
+
+/// Why an execution attempt failed
+#[derive(Debug)]
+pub enum ArbError {
+    /// Pool account missing on-chain or too small to be a real pool
+    GhostPool(String),
+    /// Short pool ID could not be resolved to an on-chain address
+    PoolResolution(String),
+    /// Resolved pool account is owned by a different DEX program than claimed
+    PoolTypeMismatch(String),
+    /// Pool is paused or not yet activated - skip it, it's not a failure
+    PoolNotActive(String),
+    /// Price moved past the minimum output we accept
+    SlippageExceeded(String),
+    /// Transaction simulation reported a program error
+    SimulationFailed(String),
+    /// Transaction was signed with a blockhash that is no longer valid
+    BlockhashExpired(String),
+    /// JITO did not accept (or never answered for) the bundle
+    JitoRejected(String),
+    /// Not enough free capital to open the position
+    InsufficientCapital(String),
+    /// Trade no longer clears fees, tip or the profit floor
+    Unprofitable(String),
+    /// RPC request failed or the RPC circuit breaker is open
+    RpcError(String),
+    /// Dropped by a guard (blacklist, duplicate, emergency stop, malformed opportunity)
+    Rejected(String),
+    /// Not classified yet
+    Other(anyhow::Error),
+}
+
+impl ArbError {
+    /// Short stable label for stats and logs
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ArbError::GhostPool(_) => "ghost_pool",
+            ArbError::PoolResolution(_) => "pool_resolution",
+            ArbError::PoolTypeMismatch(_) => "pool_type_mismatch",
+            ArbError::PoolNotActive(_) => "pool_not_active",
+            ArbError::SlippageExceeded(_) => "slippage_exceeded",
+            ArbError::SimulationFailed(_) => "simulation_failed",
+            ArbError::BlockhashExpired(_) => "blockhash_expired",
+            ArbError::JitoRejected(_) => "jito_rejected",
+            ArbError::InsufficientCapital(_) => "insufficient_capital",
+            ArbError::Unprofitable(_) => "unprofitable",
+            ArbError::RpcError(_) => "rpc_error",
+            ArbError::Rejected(_) => "rejected",
+            ArbError::Other(_) => "other",
+        }
+    }
+
+    /// The `ArbError` carried by an `anyhow::Error`, if any (looks through context layers)
+    pub fn find(error: &anyhow::Error) -> Option<&ArbError> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ArbError>())
+    }
+
+    /// Classify a failed JITO submission
+    ///
+    /// JITO reports an expired blockhash as a plain rejection message; that one
+    /// case is worth retrying with a fresh signature, everything else is not.
+    pub fn from_jito_submission(error: anyhow::Error) -> Self {
+        match ArbError::from(error) {
+            ArbError::Other(e) if is_blockhash_expired_error(&e.to_string()) => {
+                ArbError::BlockhashExpired(e.to_string())
+            }
+            ArbError::Other(e) => ArbError::JitoRejected(e.to_string()),
+            classified => classified,
+        }
+    }
+}
+
+impl fmt::Display for ArbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArbError::GhostPool(detail) => write!(f, "Ghost pool: {}", detail),
+            ArbError::PoolResolution(detail) => write!(f, "Pool resolution failed: {}", detail),
+            ArbError::PoolTypeMismatch(detail) => write!(f, "Pool type mismatch: {}", detail),
+            ArbError::PoolNotActive(detail) => write!(f, "Pool not active: {}", detail),
+            ArbError::SlippageExceeded(detail) => write!(f, "Slippage exceeded: {}", detail),
+            ArbError::SimulationFailed(detail) => write!(f, "Simulation failed: {}", detail),
+            ArbError::BlockhashExpired(detail) => write!(f, "Blockhash expired: {}", detail),
+            ArbError::JitoRejected(detail) => write!(f, "JITO rejected bundle: {}", detail),
+            ArbError::InsufficientCapital(detail) => {
+                write!(f, "Insufficient capital: {}", detail)
+            }
+            ArbError::Unprofitable(detail) => write!(f, "Unprofitable: {}", detail),
+            ArbError::RpcError(detail) => write!(f, "RPC error: {}", detail),
+            ArbError::Rejected(detail) => write!(f, "Rejected: {}", detail),
+            ArbError::Other(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
+impl std::error::Error for ArbError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArbError::Other(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ArbError {
+    fn from(error: anyhow::Error) -> Self {
+        // Recover a classified error that was lifted into anyhow further down
+        match error.downcast::<ArbError>() {
+            Ok(classified) => classified,
+            Err(other) => ArbError::Other(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_round_trip_through_anyhow_keeps_variant() {
+        let lifted: anyhow::Error = ArbError::GhostPool("81vA2wJx".to_string()).into();
+        let wrapped = Err::<(), _>(lifted)
+            .context("Failed to build swap instruction")
+            .unwrap_err();
+
+        assert_eq!(
+            ArbError::find(&wrapped).map(ArbError::kind),
+            Some("ghost_pool")
+        );
+        assert!(matches!(ArbError::from(wrapped), ArbError::GhostPool(ref id) if id == "81vA2wJx"));
+
+        let plain = ArbError::from(anyhow::anyhow!("something else"));
+        assert_eq!(plain.kind(), "other");
+        assert_eq!(plain.to_string(), "something else");
+    }
+
+    #[test]
+    fn test_jito_submission_classification() {
+        let expired =
+            ArbError::from_jito_submission(anyhow::anyhow!("Blockhash not found (bundle dropped)"));
+        assert!(matches!(expired, ArbError::BlockhashExpired(_)));
+
+        let rejected = ArbError::from_jito_submission(anyhow::anyhow!("429 Too Many Requests"));
+        assert!(matches!(rejected, ArbError::JitoRejected(_)));
+
+        // Already-classified errors pass through unchanged
+        let rpc = ArbError::from_jito_submission(ArbError::RpcError("down".to_string()).into());
+        assert!(matches!(rpc, ArbError::RpcError(_)));
+    }
+}
@@ -0,0 +1,570 @@
+// Fluxbeam swap instruction builder
+//
+// Fluxbeam (FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X) is a Token-2022-native
+// fork of the SPL token-swap program: a constant-product pool with the same
+// `SwapV1` state as Saros, but whose swap instruction also takes both mints and
+// a token program per side, so either vault may belong to Token-2022. The pool
+// authority is a PDA seeded by the pool address and the stored bump.
+//
+// Transfer fees are not part of the pool curve: `SwapExecutor::estimate_swap_output`
+// deducts the input mint's fee before `estimate_swap_output` here and the output
+// mint's fee after it.
+
+use anyhow::{Context, Result};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::amm_math::{constant_product_output, fee_amount, token_account_amount};
+use crate::pool_registry::PoolRegistry;
+use crate::rpc_client::RpcApi;
+use crate::token_program::{associated_token_address, TokenProgram};
+use crate::types::SwapParams;
+
+/// Token-swap instruction tag for `Swap`
+const SWAP_INSTRUCTION_TAG: u8 = 1;
+
+/// Fluxbeam pool state fields needed to build and price a swap
+///
+/// Offsets follow the SPL token-swap `SwapV1` layout (324 bytes):
+/// - byte 0: version
+/// - byte 1: is_initialized
+/// - byte 2: bump_seed (authority PDA bump)
+/// - bytes 3-35: token_program_id (LP mint's token program)
+/// - bytes 35-67: token_a (vault A)
+/// - bytes 67-99: token_b (vault B)
+/// - bytes 99-131: pool_mint (LP mint)
+/// - bytes 131-163: token_a_mint
+/// - bytes 163-195: token_b_mint
+/// - bytes 195-227: pool_fee_account
+/// - bytes 227-259: trade_fee_numerator/denominator, owner_trade_fee_numerator/denominator (u64s)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluxbeamPoolState {
+    pub bump_seed: u8,
+    pub pool_token_program: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub pool_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub fee_account: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+}
+
+impl FluxbeamPoolState {
+    /// Minimum account length covering all fields we read
+    const MIN_LEN: usize = 259;
+
+    /// Parse Fluxbeam pool account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < Self::MIN_LEN {
+            return Err(anyhow::anyhow!(
+                "Fluxbeam pool state too short ({} bytes). Expected at least {} bytes.",
+                data.len(),
+                Self::MIN_LEN
+            ));
+        }
+
+        if data[1] == 0 {
+            return Err(anyhow::anyhow!("Fluxbeam pool account is not initialized"));
+        }
+
+        let pubkey_at = |offset: usize, name: &str| -> Result<Pubkey> {
+            Pubkey::try_from(&data[offset..offset + 32])
+                .map_err(|_| anyhow::anyhow!("Failed to parse {} pubkey from pool state", name))
+        };
+        let u64_at = |offset: usize| -> Result<u64> {
+            Ok(u64::from_le_bytes(data[offset..offset + 8].try_into()?))
+        };
+
+        Ok(Self {
+            bump_seed: data[2],
+            pool_token_program: pubkey_at(3, "pool token program")?,
+            token_a_vault: pubkey_at(35, "token A vault")?,
+            token_b_vault: pubkey_at(67, "token B vault")?,
+            pool_mint: pubkey_at(99, "pool mint")?,
+            token_a_mint: pubkey_at(131, "token A mint")?,
+            token_b_mint: pubkey_at(163, "token B mint")?,
+            fee_account: pubkey_at(195, "pool fee account")?,
+            trade_fee_numerator: u64_at(227)?,
+            trade_fee_denominator: u64_at(235)?,
+            owner_trade_fee_numerator: u64_at(243)?,
+            owner_trade_fee_denominator: u64_at(251)?,
+        })
+    }
+
+    /// Input left after trade + owner trade fees are taken
+    pub fn amount_after_fees(&self, amount_in: u64) -> u64 {
+        amount_in
+            .saturating_sub(fee_amount(
+                amount_in,
+                self.trade_fee_numerator,
+                self.trade_fee_denominator,
+            ))
+            .saturating_sub(fee_amount(
+                amount_in,
+                self.owner_trade_fee_numerator,
+                self.owner_trade_fee_denominator,
+            ))
+    }
+
+    /// Constant-product output for `amount_in` that has reached the pool vault
+    pub fn quote(&self, amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+        constant_product_output(self.amount_after_fees(amount_in), reserve_in, reserve_out)
+    }
+}
+
+/// Input and output side of one Fluxbeam swap
+///
+/// Each side carries its own token program: a Token-2022 mint needs its ATA
+/// derived under Token-2022 and its program passed to the swap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FluxbeamSwapSide {
+    pub mint: Pubkey,
+    pub token_program: TokenProgram,
+    pub user_account: Pubkey,
+    pub vault: Pubkey,
+}
+
+/// Fluxbeam swap instruction builder
+pub struct FluxbeamSwapBuilder {
+    /// RPC client for fetching pool state
+    rpc_client: Arc<dyn RpcApi>,
+    /// Pool registry for address resolution
+    pool_registry: Arc<PoolRegistry>,
+    /// Fluxbeam AMM program ID
+    program_id: Pubkey,
+}
+
+impl FluxbeamSwapBuilder {
+    /// Fluxbeam AMM program ID
+    pub const PROGRAM_ID: &'static str = "FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X";
+
+    /// Estimated compute units for one Fluxbeam swap
+    ///
+    /// Constant-product math plus Token-2022 transfers, which cost more than
+    /// classic SPL transfers when extensions (transfer fee) are present.
+    pub fn estimated_compute_units(&self) -> u32 {
+        60_000
+    }
+
+    /// Create new Fluxbeam swap builder
+    pub fn new(rpc_client: Arc<dyn RpcApi>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
+        let program_id = Self::PROGRAM_ID
+            .parse()
+            .context("Failed to parse Fluxbeam program ID")?;
+
+        info!("✅ Fluxbeam swap builder initialized");
+        info!("   Program ID: {}", Self::PROGRAM_ID);
+
+        Ok(Self {
+            rpc_client,
+            pool_registry,
+            program_id,
+        })
+    }
+
+    /// Build swap instruction for a Fluxbeam pool
+    ///
+    /// # Arguments
+    /// * `pool_short_id` - 8-char short pool ID from ShredStream
+    /// * `swap_params` - Swap parameters (amount_in, minimum_amount_out, direction)
+    /// * `user_pubkey` - User's wallet public key
+    ///
+    /// # Returns
+    /// Solana instruction for the swap
+    pub async fn build_swap_instruction(
+        &self,
+        pool_short_id: &str,
+        swap_params: &SwapParams,
+        user_pubkey: &Pubkey,
+    ) -> Result<Instruction> {
+        debug!(
+            "Building Fluxbeam swap instruction for pool: {}",
+            pool_short_id
+        );
+
+        // Step 1: Resolve pool address from short ID
+        let pool_address = self
+            .pool_registry
+            .resolve_pool_address(pool_short_id, &crate::types::DexType::Fluxbeam)
+            .await
+            .context(format!(
+                "Failed to resolve pool address for {}",
+                pool_short_id
+            ))?;
+
+        debug!(
+            "✅ Resolved pool {} to address: {}",
+            pool_short_id, pool_address
+        );
+
+        // Safety fallback - pools are normally validated by the arbitrage engine
+        self.pool_registry.ensure_pool_valid(pool_short_id).await?;
+
+        // Step 2: Fetch and parse pool state (vaults, mints, fees)
+        let pool = self.fetch_pool_state(&pool_address)?;
+
+        debug!("Token Vault A: {}", pool.token_a_vault);
+        debug!("Token Vault B: {}", pool.token_b_vault);
+
+        // Step 3: Per-side token programs / user accounts
+        let ((mint_in, vault_in), (mint_out, vault_out)) = if swap_params.swap_a_to_b {
+            (
+                (pool.token_a_mint, pool.token_a_vault),
+                (pool.token_b_mint, pool.token_b_vault),
+            )
+        } else {
+            (
+                (pool.token_b_mint, pool.token_b_vault),
+                (pool.token_a_mint, pool.token_a_vault),
+            )
+        };
+        let source = self.swap_side(user_pubkey, mint_in, vault_in)?;
+        let destination = self.swap_side(user_pubkey, mint_out, vault_out)?;
+
+        // Step 4: Build instruction
+        let instruction = Self::build_fluxbeam_swap_ix(
+            &self.program_id,
+            &pool_address,
+            &pool,
+            user_pubkey,
+            &source,
+            &destination,
+            swap_params,
+        )?;
+
+        info!("✅ Built Fluxbeam swap instruction");
+        info!("   Pool: {}", pool_address);
+        info!("   Amount in: {} lamports", swap_params.amount_in);
+        info!(
+            "   Min amount out: {} lamports",
+            swap_params.minimum_amount_out
+        );
+        info!(
+            "   Direction: {} ({:?} → {:?})",
+            if swap_params.swap_a_to_b {
+                "A→B"
+            } else {
+                "B→A"
+            },
+            source.token_program,
+            destination.token_program
+        );
+
+        Ok(instruction)
+    }
+
+    /// Fetch and parse Fluxbeam pool account
+    fn fetch_pool_state(&self, pool_address: &Pubkey) -> Result<FluxbeamPoolState> {
+        let data = self
+            .rpc_client
+            .get_account_data(pool_address, None)
+            .context("Failed to fetch Fluxbeam pool state")?;
+
+        FluxbeamPoolState::parse(&data)
+    }
+
+    /// Token program of `mint` and the user's ATA under it
+    fn swap_side(
+        &self,
+        user_pubkey: &Pubkey,
+        mint: Pubkey,
+        vault: Pubkey,
+    ) -> Result<FluxbeamSwapSide> {
+        let token_program = self
+            .rpc_client
+            .get_mint_token_program(&mint)
+            .with_context(|| format!("Failed to detect token program of Fluxbeam mint {}", mint))?
+            .program;
+
+        Ok(FluxbeamSwapSide {
+            mint,
+            token_program,
+            user_account: associated_token_address(user_pubkey, &mint, token_program),
+            vault,
+        })
+    }
+
+    /// Derive the pool authority PDA (seeds: [pool, bump_seed])
+    pub fn derive_authority(pool: &Pubkey, bump_seed: u8, program_id: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_program_address(&[pool.as_ref(), &[bump_seed]], program_id)
+            .context("Failed to derive Fluxbeam pool authority from stored bump seed")
+    }
+
+    /// Build the raw Fluxbeam swap instruction
+    ///
+    /// Account order for Token-2022-aware token-swap `Swap`:
+    /// 0. [readonly] swap (Pool state account)
+    /// 1. [readonly] authority (Pool authority PDA)
+    /// 2. [signer] user_transfer_authority (User wallet)
+    /// 3. [writable] source (User's input token account)
+    /// 4. [writable] swap_source (Pool vault receiving input)
+    /// 5. [writable] swap_destination (Pool vault sending output)
+    /// 6. [writable] destination (User's output token account)
+    /// 7. [writable] pool_mint (LP mint)
+    /// 8. [writable] pool_fee_account
+    /// 9. [readonly] source_mint
+    /// 10. [readonly] destination_mint
+    /// 11. [readonly] source_token_program
+    /// 12. [readonly] destination_token_program
+    /// 13. [readonly] pool_token_program
+    ///
+    /// # Errors
+    /// Returns error if the stored bump seed doesn't give a valid authority PDA
+    fn build_fluxbeam_swap_ix(
+        program_id: &Pubkey,
+        pool_address: &Pubkey,
+        pool: &FluxbeamPoolState,
+        user_pubkey: &Pubkey,
+        source: &FluxbeamSwapSide,
+        destination: &FluxbeamSwapSide,
+        swap_params: &SwapParams,
+    ) -> Result<Instruction> {
+        let authority = Self::derive_authority(pool_address, pool.bump_seed, program_id)?;
+        let accounts = vec![
+            AccountMeta::new_readonly(*pool_address, false),
+            AccountMeta::new_readonly(authority, false),
+            AccountMeta::new_readonly(*user_pubkey, true),
+            AccountMeta::new(source.user_account, false),
+            AccountMeta::new(source.vault, false),
+            AccountMeta::new(destination.vault, false),
+            AccountMeta::new(destination.user_account, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new(pool.fee_account, false),
+            AccountMeta::new_readonly(source.mint, false),
+            AccountMeta::new_readonly(destination.mint, false),
+            AccountMeta::new_readonly(source.token_program.id(), false),
+            AccountMeta::new_readonly(destination.token_program.id(), false),
+            AccountMeta::new_readonly(pool.pool_token_program, false),
+        ];
+
+        // Instruction data: [tag: 1][amount_in: u64][minimum_amount_out: u64]
+        let mut data = Vec::with_capacity(17);
+        data.push(SWAP_INSTRUCTION_TAG);
+        data.extend_from_slice(&swap_params.amount_in.to_le_bytes());
+        data.extend_from_slice(&swap_params.minimum_amount_out.to_le_bytes());
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// Estimate pool output for an input that has already reached the vault
+    ///
+    /// Constant product (x*y=k) over current vault balances, after the pool's
+    /// trade and owner fees. Token-2022 transfer fees are applied around this by
+    /// `SwapExecutor::estimate_swap_output`.
+    ///
+    /// # Returns
+    /// Expected output amount leaving the pool (after pool fees)
+    pub fn estimate_swap_output(
+        &self,
+        pool_short_id: &str,
+        amount_in: u64,
+        swap_a_to_b: bool,
+    ) -> Result<u64> {
+        debug!(
+            "Estimating swap output for Fluxbeam pool: {}",
+            pool_short_id
+        );
+
+        let pool_info = self
+            .pool_registry
+            .get_pool(pool_short_id)
+            .ok_or_else(|| anyhow::anyhow!("Pool {} not found", pool_short_id))?;
+
+        let pool = self.fetch_pool_state(&pool_info.full_address)?;
+        let (reserve_a, reserve_b) = self.vault_balances(&pool)?;
+
+        let (reserve_in, reserve_out) = if swap_a_to_b {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+
+        Ok(pool.quote(amount_in, reserve_in, reserve_out))
+    }
+
+    /// SOL-side reserve of a pool (liquidity depth check before position sizing)
+    ///
+    /// # Returns
+    /// WSOL vault balance in lamports, or error if neither side of the pool is WSOL
+    pub fn sol_reserve(&self, pool_address: &Pubkey) -> Result<u64> {
+        let pool = self.fetch_pool_state(pool_address)?;
+        let (reserve_a, reserve_b) = self.vault_balances(&pool)?;
+        if pool.token_a_mint == spl_token::native_mint::id() {
+            Ok(reserve_a)
+        } else if pool.token_b_mint == spl_token::native_mint::id() {
+            Ok(reserve_b)
+        } else {
+            Err(anyhow::anyhow!(
+                "Fluxbeam pool {} has no WSOL side",
+                pool_address
+            ))
+        }
+    }
+
+    /// Current balances of vault A and vault B
+    fn vault_balances(&self, pool: &FluxbeamPoolState) -> Result<(u64, u64)> {
+        let vaults = self
+            .rpc_client
+            .get_multiple_accounts(&[pool.token_a_vault, pool.token_b_vault])?;
+        let reserve_a = token_account_amount(vaults.first().and_then(|v| v.as_deref()))
+            .context("Failed to read Fluxbeam vault A balance")?;
+        let reserve_b = token_account_amount(vaults.get(1).and_then(|v| v.as_deref()))
+            .context("Failed to read Fluxbeam vault B balance")?;
+        Ok((reserve_a, reserve_b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token_program::{
+        MintTokenProgram, TransferFee, TransferFeeConfig, TOKEN_2022_PROGRAM_ID,
+    };
+
+    /// SwapV1 pool account laid out like an on-chain Fluxbeam pool, with the
+    /// bump seed taken from the canonical authority PDA for `pool_address`
+    fn sample_pool_data(pool_address: &Pubkey, program_id: &Pubkey) -> (Vec<u8>, Vec<Pubkey>) {
+        let mut data = vec![0u8; 324];
+        data[0] = 1; // version
+        data[1] = 1; // is_initialized
+        data[2] = Pubkey::find_program_address(&[pool_address.as_ref()], program_id).1;
+        data[3..35].copy_from_slice(TOKEN_2022_PROGRAM_ID.as_ref());
+
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        for (i, key) in keys.iter().enumerate() {
+            let offset = 35 + i * 32;
+            data[offset..offset + 32].copy_from_slice(key.as_ref());
+        }
+
+        // 0.25% trade fee + 0.05% owner fee
+        data[227..235].copy_from_slice(&25u64.to_le_bytes());
+        data[235..243].copy_from_slice(&10_000u64.to_le_bytes());
+        data[243..251].copy_from_slice(&5u64.to_le_bytes());
+        data[251..259].copy_from_slice(&10_000u64.to_le_bytes());
+
+        (data, keys)
+    }
+
+    #[test]
+    fn test_authority_ata_and_account_derivation() {
+        let program_id: Pubkey = FluxbeamSwapBuilder::PROGRAM_ID.parse().unwrap();
+        let pool_address = Pubkey::new_unique();
+        let (data, keys) = sample_pool_data(&pool_address, &program_id);
+
+        let pool = FluxbeamPoolState::parse(&data).unwrap();
+        assert_eq!(pool.pool_token_program, TOKEN_2022_PROGRAM_ID);
+        assert_eq!(pool.token_a_vault, keys[0]);
+        assert_eq!(pool.token_b_vault, keys[1]);
+        assert_eq!(pool.pool_mint, keys[2]);
+        assert_eq!(pool.token_a_mint, keys[3]);
+        assert_eq!(pool.token_b_mint, keys[4]);
+        assert_eq!(pool.fee_account, keys[5]);
+
+        // Stored bump reproduces the canonical authority PDA
+        let authority =
+            FluxbeamSwapBuilder::derive_authority(&pool_address, pool.bump_seed, &program_id)
+                .unwrap();
+        let (expected, _) = Pubkey::find_program_address(&[pool_address.as_ref()], &program_id);
+        assert_eq!(authority, expected);
+
+        // WSOL (SPL) in, Token-2022 token out: each ATA under its own program
+        let user = Pubkey::new_unique();
+        let source = FluxbeamSwapSide {
+            mint: pool.token_a_mint,
+            token_program: TokenProgram::Spl,
+            user_account: associated_token_address(&user, &pool.token_a_mint, TokenProgram::Spl),
+            vault: pool.token_a_vault,
+        };
+        let destination = FluxbeamSwapSide {
+            mint: pool.token_b_mint,
+            token_program: TokenProgram::Token2022,
+            user_account: associated_token_address(
+                &user,
+                &pool.token_b_mint,
+                TokenProgram::Token2022,
+            ),
+            vault: pool.token_b_vault,
+        };
+        assert_ne!(
+            destination.user_account,
+            spl_associated_token_account::get_associated_token_address(&user, &pool.token_b_mint)
+        );
+
+        let params = SwapParams {
+            amount_in: 1_000,
+            minimum_amount_out: 900,
+            expected_amount_out: Some(950),
+            swap_a_to_b: true,
+        };
+        let ix = FluxbeamSwapBuilder::build_fluxbeam_swap_ix(
+            &program_id,
+            &pool_address,
+            &pool,
+            &user,
+            &source,
+            &destination,
+            &params,
+        )
+        .unwrap();
+
+        assert_eq!(ix.accounts.len(), 14);
+        assert_eq!(ix.accounts[1].pubkey, authority);
+        assert!(ix.accounts[2].is_signer);
+        assert_eq!(ix.accounts[3].pubkey, source.user_account);
+        assert_eq!(ix.accounts[4].pubkey, pool.token_a_vault);
+        assert_eq!(ix.accounts[5].pubkey, pool.token_b_vault);
+        assert_eq!(ix.accounts[6].pubkey, destination.user_account);
+        assert_eq!(ix.accounts[11].pubkey, spl_token::id());
+        assert_eq!(ix.accounts[12].pubkey, TOKEN_2022_PROGRAM_ID);
+        assert_eq!(ix.accounts[13].pubkey, TOKEN_2022_PROGRAM_ID);
+        assert_eq!(ix.data[0], SWAP_INSTRUCTION_TAG);
+        assert_eq!(ix.data.len(), 17);
+
+        // Truncated and uninitialized accounts are rejected
+        assert!(FluxbeamPoolState::parse(&data[..200]).is_err());
+        let mut uninitialized = data.clone();
+        uninitialized[1] = 0;
+        assert!(FluxbeamPoolState::parse(&uninitialized).is_err());
+    }
+
+    #[test]
+    fn test_quote_with_transfer_fees() {
+        let program_id: Pubkey = FluxbeamSwapBuilder::PROGRAM_ID.parse().unwrap();
+        let (data, _) = sample_pool_data(&Pubkey::new_unique(), &program_id);
+        let pool = FluxbeamPoolState::parse(&data).unwrap();
+
+        // 0.3% pool fee: 9_970 into a balanced 1M/1M pool
+        assert_eq!(pool.quote(10_000, 1_000_000, 1_000_000), 9_871);
+        assert_eq!(pool.quote(10_000, 0, 1_000_000), 0);
+
+        // 1% Token-2022 transfer fee on the output mint (as SwapExecutor applies it)
+        let fee = TransferFee {
+            epoch: 0,
+            maximum_fee: u64::MAX,
+            basis_points: 100,
+        };
+        let token_2022_out = MintTokenProgram {
+            program: TokenProgram::Token2022,
+            transfer_fee: Some(TransferFeeConfig {
+                older: fee,
+                newer: fee,
+            }),
+        };
+        let amount_in = MintTokenProgram::SPL.amount_after_transfer_fee(10_000);
+        let pool_out = pool.quote(amount_in, 1_000_000, 1_000_000);
+        // 9_871 - ceil(98.71) = 9_772
+        assert_eq!(token_2022_out.amount_after_transfer_fee(pool_out), 9_772);
+    }
+}
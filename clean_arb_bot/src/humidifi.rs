@@ -40,6 +40,11 @@ pub struct HumidiFiSwapBuilder {
 }
 
 impl HumidiFiSwapBuilder {
+    /// Estimated compute units for one HumidiFi swap (proprietary oracle-priced AMM)
+    pub fn estimated_compute_units(&self) -> u32 {
+        60_000
+    }
+
     pub fn new() -> Result<Self> {
         let program_id =
             Pubkey::from_str(HUMIDIFI_PROGRAM_ID).context("Invalid HumidiFi program ID")?;
@@ -11,6 +11,8 @@ use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::tip_strategy::TipStrategy;
+
 /// Token bucket rate limiter for JITO bundle submissions
 #[derive(Debug)]
 struct RateLimiter {
@@ -53,18 +55,122 @@ impl RateLimiter {
     }
 }
 
+/// Built-in block engine regions (used when no endpoints are configured)
+const DEFAULT_ENDPOINTS: [&str; 4] = [
+    "https://mainnet.block-engine.jito.wtf", // US (primary)
+    "https://amsterdam.mainnet.block-engine.jito.wtf", // EU
+    "https://frankfurt.mainnet.block-engine.jito.wtf", // EU
+    "https://tokyo.mainnet.block-engine.jito.wtf", // Asia
+];
+
+/// Per-endpoint submission timeout - short enough to leave time to fail over
+const ENDPOINT_SUBMIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timeout for one `/health` latency probe
+const LATENCY_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Weight of the newest probe in the smoothed endpoint latency
+const LATENCY_EWMA_ALPHA: f64 = 0.3;
+
+/// Health and latency of one block engine endpoint
+#[derive(Debug, Clone)]
+struct EndpointHealth {
+    url: String,
+    latency_ms: Option<f64>, // Smoothed probe round-trip (None until first probe)
+    healthy: bool,
+    consecutive_failures: u32,
+}
+
+/// Block engine endpoints ranked by health, then latency
+#[derive(Debug)]
+struct EndpointPool {
+    endpoints: Vec<EndpointHealth>,
+}
+
+impl EndpointPool {
+    fn new(urls: Vec<String>) -> Self {
+        Self {
+            endpoints: urls
+                .into_iter()
+                .map(|url| EndpointHealth {
+                    url,
+                    latency_ms: None,
+                    healthy: true,
+                    consecutive_failures: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Endpoint indices, best first
+    ///
+    /// Healthy endpoints come first, fastest first; unprobed endpoints keep
+    /// their configured order after probed ones. Unhealthy endpoints stay at
+    /// the back (fewest failures first) so a submission is never left with
+    /// nowhere to go.
+    fn ranked(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.endpoints[a], &self.endpoints[b]);
+            b.healthy
+                .cmp(&a.healthy)
+                .then(a.consecutive_failures.cmp(&b.consecutive_failures))
+                .then(
+                    a.latency_ms
+                        .unwrap_or(f64::INFINITY)
+                        .total_cmp(&b.latency_ms.unwrap_or(f64::INFINITY)),
+                )
+        });
+        order
+    }
+
+    /// URL of the endpoint submissions currently go to
+    fn selected(&self) -> &str {
+        &self.endpoints[self.ranked()[0]].url
+    }
+
+    /// Endpoint answered (submission or probe) - back in rotation
+    fn record_success(&mut self, index: usize, latency: Option<Duration>) {
+        let endpoint = &mut self.endpoints[index];
+        endpoint.healthy = true;
+        endpoint.consecutive_failures = 0;
+        if let Some(latency) = latency {
+            let sample = latency.as_secs_f64() * 1000.0;
+            endpoint.latency_ms = Some(match endpoint.latency_ms {
+                Some(avg) => avg + LATENCY_EWMA_ALPHA * (sample - avg),
+                None => sample,
+            });
+        }
+    }
+
+    /// Endpoint errored or timed out - skip it until it answers again
+    fn record_failure(&mut self, index: usize) {
+        let endpoint = &mut self.endpoints[index];
+        endpoint.healthy = false;
+        endpoint.consecutive_failures += 1;
+    }
+}
+
+/// Why a submission to one endpoint failed
+enum SubmitError {
+    /// Endpoint unreachable, timed out, rate limited or erroring - try another region
+    Endpoint(anyhow::Error),
+    /// Block engine answered and refused the bundle - other regions would too
+    Rejected(anyhow::Error),
+}
+
 /// Production-ready Jito bundle client with HTTP submission and rate limiting
 #[derive(Debug)]
 pub struct JitoBundleClient {
     client: Client,
-    endpoints: Arc<Mutex<Vec<String>>>, // Multiple JITO endpoints with rotation
-    current_endpoint_index: Arc<Mutex<usize>>, // Current endpoint for round-robin
+    endpoints: Arc<Mutex<EndpointPool>>, // Block engine regions, ranked by health and latency
     auth_keypair: Option<Arc<solana_sdk::signature::Keypair>>, // SECURITY: Use Arc<Keypair> instead of owned Keypair
     tip_accounts: Vec<Pubkey>,
     bundle_timeout: Duration,
     max_retries: usize,
     metrics: Arc<Mutex<JitoMetrics>>,
     rate_limiter: Arc<RateLimiter>, // JITO rate limiting (30 bundles/minute)
+    tip_strategy: TipStrategy,      // Base tip before success/latency adjustment
 }
 
 #[derive(Debug, Clone)]
@@ -112,9 +218,32 @@ pub struct BundleStatus {
     pub bundle_id: String,
     pub status: String,
     pub landed_slot: Option<u64>,
+    #[serde(default)]
     pub transactions: Vec<BundleTransaction>,
 }
 
+/// Bundle was accepted by JITO but did not land (failed, invalid, or timed out)
+///
+/// Distinct from a submission error so callers can tell "submitted but not landed"
+/// apart from "rejected by the block engine" (downcast from `anyhow::Error`)
+#[derive(Debug, Clone)]
+pub struct BundleNotLandedError {
+    pub bundle_id: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for BundleNotLandedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Bundle {} submitted but not landed: {}",
+            self.bundle_id, self.reason
+        )
+    }
+}
+
+impl std::error::Error for BundleNotLandedError {}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BundleTransaction {
     pub signature: String,
@@ -129,13 +258,8 @@ impl JitoBundleClient {
         _relayer_url: String,      // Deprecated - using multiple endpoints
         auth_keypair: Arc<solana_sdk::signature::Keypair>,
     ) -> Self {
-        // Multiple JITO endpoints for rotation (Grok recommendation)
-        let endpoints = vec![
-            "https://mainnet.block-engine.jito.wtf".to_string(), // US (primary)
-            "https://amsterdam.mainnet.block-engine.jito.wtf".to_string(), // EU
-            "https://frankfurt.mainnet.block-engine.jito.wtf".to_string(), // EU
-            "https://tokyo.mainnet.block-engine.jito.wtf".to_string(), // Asia
-        ];
+        // Multiple JITO endpoints for failover (Grok recommendation)
+        let endpoints: Vec<String> = DEFAULT_ENDPOINTS.iter().map(|e| e.to_string()).collect();
 
         info!("🌐 JITO endpoints configured:");
         for (i, endpoint) in endpoints.iter().enumerate() {
@@ -180,17 +304,109 @@ impl JitoBundleClient {
                 .timeout(Duration::from_secs(30))
                 .build()
                 .unwrap(),
-            endpoints: Arc::new(Mutex::new(endpoints)),
-            current_endpoint_index: Arc::new(Mutex::new(0)),
+            endpoints: Arc::new(Mutex::new(EndpointPool::new(endpoints))),
             auth_keypair: Some(auth_keypair), // Store Arc<Keypair> securely
             tip_accounts,
             bundle_timeout: Duration::from_secs(60),
             max_retries: 1, // No retries - fail fast and move to next opportunity
             metrics: Arc::new(Mutex::new(JitoMetrics::default())),
             rate_limiter,
+            tip_strategy: TipStrategy::default(),
+        }
+    }
+
+    /// Set the tip policy for bundles submitted without an explicit tip
+    pub fn with_tip_strategy(mut self, tip_strategy: TipStrategy) -> Self {
+        info!("💸 JITO tip strategy: {}", tip_strategy);
+        self.tip_strategy = tip_strategy;
+        self
+    }
+
+    /// Override the block engine endpoints (replaces the built-in region list)
+    ///
+    /// An empty list is ignored so there is always an endpoint to submit to
+    pub fn with_endpoints(self, endpoints: Vec<String>) -> Self {
+        if endpoints.is_empty() {
+            warn!("⚠️ Empty JITO endpoint list ignored - keeping defaults");
+            return self;
+        }
+        info!("🌐 JITO endpoints overridden:");
+        for (i, endpoint) in endpoints.iter().enumerate() {
+            info!("   {}. {}", i + 1, endpoint);
+        }
+        if let Ok(mut current) = self.endpoints.lock() {
+            *current = EndpointPool::new(endpoints);
+        }
+        self
+    }
+
+    /// Block engine endpoint submissions currently go to (lowest-latency healthy one)
+    pub fn selected_endpoint(&self) -> String {
+        self.endpoints.lock().unwrap().selected().to_string()
+    }
+
+    /// Ping every endpoint's `/health` and re-rank them by round-trip latency
+    ///
+    /// Any HTTP response counts as reachable - the round-trip is what ranks
+    /// regions. Connection errors and timeouts mark the endpoint unhealthy.
+    pub async fn probe_endpoint_latency(&self) {
+        let urls: Vec<String> = self
+            .endpoints
+            .lock()
+            .unwrap()
+            .endpoints
+            .iter()
+            .map(|e| e.url.clone())
+            .collect();
+        let before = self.selected_endpoint();
+
+        for (index, url) in urls.iter().enumerate() {
+            let start = Instant::now();
+            let probe = timeout(
+                LATENCY_PROBE_TIMEOUT,
+                self.client.get(format!("{}/health", url)).send(),
+            )
+            .await;
+            let latency = start.elapsed();
+
+            let mut pool = self.endpoints.lock().unwrap();
+            match probe {
+                Ok(Ok(_)) => {
+                    pool.record_success(index, Some(latency));
+                    debug!("📡 JITO {} latency: {}ms", url, latency.as_millis());
+                }
+                Ok(Err(e)) => {
+                    pool.record_failure(index);
+                    debug!("📡 JITO {} unreachable: {}", url, e);
+                }
+                Err(_) => {
+                    pool.record_failure(index);
+                    debug!("📡 JITO {} probe timed out", url);
+                }
+            }
+        }
+
+        let after = self.selected_endpoint();
+        if after != before {
+            info!("🌐 JITO endpoint switched: {} → {}", before, after);
         }
     }
 
+    /// Probe endpoint latency now and then every `interval` in the background
+    pub fn spawn_latency_probe(self: &Arc<Self>, interval: Duration) {
+        let client = self.clone();
+        tokio::spawn(async move {
+            info!(
+                "📡 Starting JITO endpoint latency probe (every {}s)",
+                interval.as_secs()
+            );
+            loop {
+                client.probe_endpoint_latency().await;
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
     /// Get a random JITO tip account for load balancing
     ///
     /// Returns one of the 8 official Jito tip accounts at random
@@ -232,13 +448,15 @@ impl JitoBundleClient {
     /// ```ignore
     /// // Build transaction with tip INSIDE
     /// let tx = swap_executor.build_triangle_with_tip(
-    ///     leg1, leg2, leg3, wallet, tip_lamports, &tip_account
+    ///     &input_mint, [leg1, leg2, leg3], wallet, tip_lamports, &tip_account
     /// ).await?;
     ///
     /// // Submit securely (tip already in transaction)
     /// let bundle_id = jito_client.submit_bundle_safe(vec![tx]).await?;
     /// ```
-    pub async fn submit_bundle_safe(&self, transactions: Vec<Transaction>) -> Result<String> {
+    ///
+    /// Accepts legacy `Transaction` or `VersionedTransaction` (v0 with lookup tables)
+    pub async fn submit_bundle_safe<T: Serialize>(&self, transactions: Vec<T>) -> Result<String> {
         let start_time = Instant::now();
 
         // RATE LIMITING: Acquire token before proceeding
@@ -256,15 +474,7 @@ impl JitoBundleClient {
         );
 
         // Convert to base58 encoded strings
-        let encoded_transactions: Result<Vec<String>> = transactions
-            .iter()
-            .map(|tx| {
-                let serialized = bincode::serialize(tx)?;
-                Ok(bs58::encode(serialized).into_string())
-            })
-            .collect();
-
-        let encoded_transactions = encoded_transactions?;
+        let encoded_transactions = encode_bundle_transactions(&transactions)?;
 
         // Create bundle (no separate tip - already in transactions)
         let bundle = JitoBundle {
@@ -351,15 +561,7 @@ impl JitoBundleClient {
         transactions.push(tip_tx);
 
         // Convert to base58 encoded strings
-        let encoded_transactions: Result<Vec<String>> = transactions
-            .iter()
-            .map(|tx| {
-                let serialized = bincode::serialize(tx)?;
-                Ok(bs58::encode(serialized).into_string())
-            })
-            .collect();
-
-        let encoded_transactions = encoded_transactions?;
+        let encoded_transactions = encode_bundle_transactions(&transactions)?;
 
         // Create bundle
         let bundle = JitoBundle {
@@ -391,55 +593,58 @@ impl JitoBundleClient {
     }
 
     /// Submit bundle with retry logic
+    ///
+    /// Each attempt walks the endpoints best-first and fails over to the next
+    /// region when one errors or times out. A bundle the block engine refuses
+    /// is not resent to other regions.
     async fn submit_with_retries(&self, bundle: &JitoBundle) -> Result<String> {
         let mut last_error = None;
 
         for attempt in 1..=self.max_retries {
-            match self.submit_bundle_once(bundle).await {
-                Ok(bundle_id) => {
-                    if attempt > 1 {
-                        info!("✅ Bundle submitted successfully on attempt {}", attempt);
+            let ranked = self.endpoints.lock().unwrap().ranked();
+            for index in ranked {
+                let endpoint = self.endpoints.lock().unwrap().endpoints[index].url.clone();
+                match self.submit_bundle_once(bundle, &endpoint).await {
+                    Ok(bundle_id) => {
+                        self.endpoints.lock().unwrap().record_success(index, None);
+                        if attempt > 1 {
+                            info!("✅ Bundle submitted successfully on attempt {}", attempt);
+                        }
+                        return Ok(bundle_id);
                     }
-                    return Ok(bundle_id);
-                }
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    warn!(
-                        "❌ Bundle submission attempt {} failed: {}",
-                        attempt, error_msg
-                    );
-
-                    // Rotate endpoint on 429 errors (Grok recommendation)
-                    if error_msg.contains("429") {
-                        let mut index = self.current_endpoint_index.lock().unwrap();
-                        *index = (*index + 1) % self.endpoints.lock().unwrap().len();
-                        debug!("🔄 Rotating to endpoint #{} due to 429", *index + 1);
+                    Err(SubmitError::Endpoint(e)) => {
+                        warn!(
+                            "❌ JITO endpoint {} failed (attempt {}): {} - failing over",
+                            endpoint, attempt, e
+                        );
+                        self.endpoints.lock().unwrap().record_failure(index);
+                        last_error = Some(e);
                     }
-
-                    last_error = Some(e);
-
-                    if attempt < self.max_retries {
-                        let delay = Duration::from_millis(100 * attempt as u64);
-                        tokio::time::sleep(delay).await;
+                    Err(SubmitError::Rejected(e)) => {
+                        warn!("❌ Bundle submission attempt {} failed: {}", attempt, e);
+                        last_error = Some(e);
+                        break;
                     }
                 }
             }
+
+            if attempt < self.max_retries {
+                let delay = Duration::from_millis(100 * attempt as u64);
+                tokio::time::sleep(delay).await;
+            }
         }
 
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All bundle submission attempts failed")))
     }
 
-    /// Single bundle submission attempt
-    async fn submit_bundle_once(&self, bundle: &JitoBundle) -> Result<String> {
+    /// Single bundle submission attempt against one endpoint
+    async fn submit_bundle_once(
+        &self,
+        bundle: &JitoBundle,
+        current_endpoint: &str,
+    ) -> std::result::Result<String, SubmitError> {
         use rand::Rng;
 
-        // Get current endpoint (round-robin)
-        let current_endpoint = {
-            let index = *self.current_endpoint_index.lock().unwrap();
-            let endpoints = self.endpoints.lock().unwrap();
-            endpoints[index].clone()
-        };
-
         let request = BundleSubmissionRequest {
             jsonrpc: "2.0".to_string(),
             id: rand::thread_rng().gen::<u64>(),
@@ -450,36 +655,52 @@ impl JitoBundleClient {
         debug!("🌐 Submitting to: {}", current_endpoint);
 
         let response = timeout(
-            Duration::from_secs(30),
+            ENDPOINT_SUBMIT_TIMEOUT,
             self.client
                 .post(format!("{}/api/v1/bundles", current_endpoint))
                 .header("Content-Type", "application/json")
                 .json(&request)
                 .send(),
         )
-        .await??;
-
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "HTTP error {}: {}",
-                response.status(),
-                response.text().await?
-            ));
+        .await
+        .map_err(|_| {
+            SubmitError::Endpoint(anyhow::anyhow!(
+                "timed out after {}ms",
+                ENDPOINT_SUBMIT_TIMEOUT.as_millis()
+            ))
+        })?
+        .map_err(|e| SubmitError::Endpoint(e.into()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let error = anyhow::anyhow!("HTTP error {}: {}", status, body);
+            // Rate limits are per region, so 429s fail over like outages
+            return Err(
+                if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                    SubmitError::Endpoint(error)
+                } else {
+                    SubmitError::Rejected(error)
+                },
+            );
         }
 
-        let bundle_response: BundleSubmissionResponse = response.json().await?;
+        let bundle_response: BundleSubmissionResponse = response
+            .json()
+            .await
+            .map_err(|e| SubmitError::Endpoint(e.into()))?;
 
         if let Some(error) = bundle_response.error {
-            return Err(anyhow::anyhow!(
+            return Err(SubmitError::Rejected(anyhow::anyhow!(
                 "Jito error {}: {}",
                 error.code,
                 error.message
-            ));
+            )));
         }
 
         bundle_response
             .result
-            .ok_or_else(|| anyhow::anyhow!("No bundle ID returned"))
+            .ok_or_else(|| SubmitError::Rejected(anyhow::anyhow!("No bundle ID returned")))
     }
 
     /// Create tip transaction to Jito validators (LEGACY - SECURITY RISK)
@@ -555,11 +776,8 @@ impl JitoBundleClient {
 
     /// Calculate optimal tip based on expected profit and network conditions
     ///
-    /// **PROFIT-BASED TIP STRATEGY**:
-    /// - Minimum: 100,000 lamports (0.0001 SOL) - 95th percentile per Jito dashboard
-    /// - Base: 10% of expected profit
-    /// - Adjusted: Based on success rate and confirmation times
-    /// - Maximum: 20% of expected profit (prevents overtipping)
+    /// Base tip comes from the configured `TipStrategy` (default landed-tip
+    /// floor), then is adjusted by recent success rate and confirmation times.
     ///
     /// # Arguments
     /// * `expected_profit_lamports` - Expected profit from the arbitrage (optional)
@@ -567,27 +785,9 @@ impl JitoBundleClient {
     /// # Returns
     /// Optimal tip amount in lamports
     pub fn calculate_optimal_tip_with_profit(&self, expected_profit_lamports: Option<u64>) -> u64 {
-        // Minimum tip: 100,000 lamports (0.0001 SOL) - 95th percentile
-        const MIN_TIP_LAMPORTS: u64 = 100_000;
-
-        // Maximum tip as percentage of profit
-        const MAX_TIP_PERCENTAGE: f64 = 0.20; // 20% max
-
-        // Base tip as percentage of profit
-        const BASE_TIP_PERCENTAGE: f64 = 0.10; // 10% base
-
-        // Calculate base tip from expected profit
-        let base_tip = if let Some(profit) = expected_profit_lamports {
-            if profit > 0 {
-                // 10% of expected profit, but at least minimum
-                let profit_based_tip = (profit as f64 * BASE_TIP_PERCENTAGE) as u64;
-                profit_based_tip.max(MIN_TIP_LAMPORTS)
-            } else {
-                MIN_TIP_LAMPORTS
-            }
-        } else {
-            MIN_TIP_LAMPORTS
-        };
+        let base_tip = self
+            .tip_strategy
+            .tip_lamports(expected_profit_lamports.unwrap_or(0), None);
 
         // Adjust based on recent success rate and confirmation times
         let (success_rate_multiplier, latency_multiplier) = if let Ok(metrics) = self.metrics.lock()
@@ -613,22 +813,7 @@ impl JitoBundleClient {
             (1.0, 1.0) // Default multipliers if mutex is poisoned
         };
 
-        let adjusted_tip = (base_tip as f64 * success_rate_multiplier * latency_multiplier) as u64;
-
-        // Cap tip at maximum percentage of profit (if profit provided)
-        let capped_tip = if let Some(profit) = expected_profit_lamports {
-            if profit > 0 {
-                let max_tip = (profit as f64 * MAX_TIP_PERCENTAGE) as u64;
-                adjusted_tip.min(max_tip)
-            } else {
-                adjusted_tip
-            }
-        } else {
-            adjusted_tip
-        };
-
-        // Ensure minimum tip (95th percentile)
-        let final_tip = capped_tip.max(MIN_TIP_LAMPORTS);
+        let final_tip = (base_tip as f64 * success_rate_multiplier * latency_multiplier) as u64;
 
         debug!(
             "💰 Calculated optimal tip: {} lamports (0.{:06} SOL)",
@@ -652,55 +837,75 @@ impl JitoBundleClient {
     /// **DEPRECATED**: Use `calculate_optimal_tip_with_profit()` instead for profit-based tipping
     #[deprecated(note = "Use calculate_optimal_tip_with_profit() for better tip strategy")]
     fn calculate_optimal_tip(&self) -> u64 {
-        // Minimum tip: 100,000 lamports (0.0001 SOL) - 95th percentile
         self.calculate_optimal_tip_with_profit(None)
     }
 
-    /// Monitor bundle status and update metrics
-    async fn monitor_bundle_status(&self, bundle_id: String) -> Result<()> {
+    /// Poll bundle status until it lands, fails, or `max_wait` elapses
+    ///
+    /// # Returns
+    /// * `Ok(landed_slot)` if the bundle landed
+    /// * `Err(BundleNotLandedError)` if the bundle failed, was invalid, or timed out
+    pub async fn wait_for_landing(
+        &self,
+        bundle_id: &str,
+        max_wait: Duration,
+    ) -> Result<Option<u64>> {
         let start_time = Instant::now();
         let mut check_interval = tokio::time::interval(Duration::from_millis(500));
 
-        for _ in 0..120 {
-            // Monitor for up to 60 seconds
+        while start_time.elapsed() < max_wait {
             check_interval.tick().await;
 
-            match self.get_bundle_status(&bundle_id).await {
-                Ok(status) => {
-                    match status.status.as_str() {
-                        "Landed" => {
-                            let confirmation_time = start_time.elapsed().as_millis() as f64;
-                            info!("✅ Bundle landed in {}ms: {}", confirmation_time, bundle_id);
-
-                            // Update metrics (would need mutable access)
-                            // self.metrics.bundles_landed += 1;
-                            // self.update_average_confirmation_time(confirmation_time);
-                            return Ok(());
-                        }
-                        "Failed" | "Rejected" => {
-                            error!("❌ Bundle failed: {}", bundle_id);
-                            // self.metrics.bundles_failed += 1;
-                            return Err(anyhow::anyhow!("Bundle failed: {}", status.status));
-                        }
-                        "Pending" | "Processing" => {
-                            debug!("⏳ Bundle pending: {}", bundle_id);
-                            continue;
+            match self.get_bundle_status(bundle_id).await {
+                Ok(status) => match status.status.as_str() {
+                    "Landed" => {
+                        let confirmation_time = start_time.elapsed().as_millis() as f64;
+                        info!("✅ Bundle landed in {}ms: {}", confirmation_time, bundle_id);
+
+                        if let Ok(mut metrics) = self.metrics.lock() {
+                            metrics.bundles_landed += 1;
+                            metrics.update_average_confirmation_time(confirmation_time);
+                            metrics.calculate_success_rate();
                         }
-                        _ => {
-                            warn!("Unknown bundle status: {}", status.status);
-                            continue;
+                        return Ok(status.landed_slot);
+                    }
+                    "Failed" | "Rejected" | "Invalid" => {
+                        error!("❌ Bundle {}: {}", status.status, bundle_id);
+                        self.record_bundle_failure();
+                        return Err(BundleNotLandedError {
+                            bundle_id: bundle_id.to_string(),
+                            reason: status.status,
                         }
+                        .into());
                     }
-                }
+                    "Pending" | "Processing" => {
+                        debug!("⏳ Bundle pending: {}", bundle_id);
+                    }
+                    _ => {
+                        warn!("Unknown bundle status: {}", status.status);
+                    }
+                },
                 Err(e) => {
                     debug!("Error checking bundle status: {}", e);
-                    continue;
                 }
             }
         }
 
-        warn!("⏰ Bundle monitoring timeout: {}", bundle_id);
-        Ok(())
+        warn!("⏰ Bundle landing timeout: {}", bundle_id);
+        self.record_bundle_failure();
+        Err(BundleNotLandedError {
+            bundle_id: bundle_id.to_string(),
+            reason: format!("not landed within {}ms", max_wait.as_millis()),
+        }
+        .into())
+    }
+
+    /// Count a submitted bundle that did not land
+    fn record_bundle_failure(&self) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.bundles_failed += 1;
+            metrics.calculate_success_rate();
+        }
     }
 
     /// Get bundle status from Jito
@@ -709,16 +914,12 @@ impl JitoBundleClient {
         let request = serde_json::json!({
             "jsonrpc": "2.0",
             "id": rand::thread_rng().gen::<u64>(),
-            "method": "getBundleStatuses",
+            "method": "getInflightBundleStatuses",
             "params": [vec![bundle_id]]
         });
 
         // Get current endpoint
-        let current_endpoint = {
-            let index = *self.current_endpoint_index.lock().unwrap();
-            let endpoints = self.endpoints.lock().unwrap();
-            endpoints[index].clone()
-        };
+        let current_endpoint = self.selected_endpoint();
 
         let response = timeout(
             Duration::from_secs(10),
@@ -775,11 +976,7 @@ impl JitoBundleClient {
         });
 
         // Get current endpoint
-        let current_endpoint = {
-            let index = *self.current_endpoint_index.lock().unwrap();
-            let endpoints = self.endpoints.lock().unwrap();
-            endpoints[index].clone()
-        };
+        let current_endpoint = self.selected_endpoint();
 
         let response = timeout(
             Duration::from_secs(5),
@@ -841,6 +1038,23 @@ impl JitoMetrics {
     }
 }
 
+/// Encode bundle transactions for `sendBundle`, preserving their order
+///
+/// # Arguments
+/// * `transactions` - Signed transactions, executed by JITO in this order
+///
+/// # Returns
+/// One base58 string per transaction (bincode wire format)
+fn encode_bundle_transactions<T: Serialize>(transactions: &[T]) -> Result<Vec<String>> {
+    transactions
+        .iter()
+        .map(|tx| {
+            let serialized = bincode::serialize(tx)?;
+            Ok(bs58::encode(serialized).into_string())
+        })
+        .collect()
+}
+
 /// Helper function to create MEV bundle for front-running protection
 pub fn create_mev_bundle(
     user_transactions: Vec<Transaction>,
@@ -854,3 +1068,76 @@ pub fn create_mev_bundle(
 
     user_transactions // Simplified for now
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_pool_ranks_by_health_then_latency() {
+        let mut pool = EndpointPool::new(vec![
+            "https://ny".to_string(),
+            "https://frankfurt".to_string(),
+            "https://tokyo".to_string(),
+        ]);
+
+        // Unprobed endpoints keep configured order
+        assert_eq!(pool.selected(), "https://ny");
+
+        pool.record_success(0, Some(Duration::from_millis(120)));
+        pool.record_success(1, Some(Duration::from_millis(15)));
+        pool.record_success(2, Some(Duration::from_millis(180)));
+        assert_eq!(pool.ranked(), vec![1, 0, 2]);
+
+        // Failover: an erroring endpoint drops behind every healthy one
+        pool.record_failure(1);
+        assert_eq!(pool.selected(), "https://ny");
+        assert_eq!(pool.ranked(), vec![0, 2, 1]);
+
+        // Smoothed latency: one slow probe moves the average, not replaces it
+        pool.record_success(0, Some(Duration::from_millis(420)));
+        assert!((pool.endpoints[0].latency_ms.unwrap() - 210.0).abs() < 1e-9);
+        assert_eq!(pool.selected(), "https://tokyo");
+
+        // Recovered endpoint is preferred again
+        pool.record_success(1, Some(Duration::from_millis(15)));
+        assert_eq!(pool.selected(), "https://frankfurt");
+
+        // All down: still returns the least-failing endpoint
+        for index in 0..3 {
+            pool.record_failure(index);
+        }
+        pool.record_failure(2);
+        assert_eq!(pool.ranked()[2], 2);
+    }
+
+    #[test]
+    fn test_multi_transaction_bundle_encodes_in_order() {
+        use solana_sdk::hash::Hash;
+        use solana_sdk::signature::Keypair;
+        use solana_sdk::transaction::VersionedTransaction;
+
+        let wallet = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let bundle: Vec<VersionedTransaction> = (1..=3)
+            .map(|lamports| {
+                let ix = system_instruction::transfer(&wallet.pubkey(), &wallet.pubkey(), lamports);
+                Transaction::new_signed_with_payer(
+                    &[ix],
+                    Some(&wallet.pubkey()),
+                    &[&wallet],
+                    blockhash,
+                )
+                .into()
+            })
+            .collect();
+
+        let encoded = encode_bundle_transactions(&bundle).unwrap();
+        assert_eq!(encoded.len(), 3);
+        for (tx, entry) in bundle.iter().zip(&encoded) {
+            let bytes = bs58::decode(entry).into_vec().unwrap();
+            let decoded: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(decoded.signatures, tx.signatures);
+        }
+    }
+}
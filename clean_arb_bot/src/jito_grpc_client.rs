@@ -7,8 +7,9 @@
 
 use anyhow::Result;
 use prost_types::Timestamp;
-use solana_sdk::transaction::Transaction;
+use serde::Serialize;
 use std::time::SystemTime;
+use tokio::time::{Duration, Instant};
 use tonic::transport::{Channel, ClientTlsConfig};
 use tonic::Request;
 use tracing::{debug, error, info, warn};
@@ -32,6 +33,46 @@ pub mod shared {
 
 use searcher::searcher_service_client::SearcherServiceClient;
 
+/// Exponential backoff schedule for gRPC reconnect attempts
+///
+/// Long runs eventually drop the gRPC stream; retrying every bundle would add
+/// connect latency to the hot path, so attempts are spaced out and doubled on failure.
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+    next_attempt_at: Option<Instant>,
+}
+
+impl ReconnectBackoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            current: initial,
+            next_attempt_at: None,
+        }
+    }
+
+    /// Whether a reconnect attempt is allowed at `now`
+    pub fn ready(&self, now: Instant) -> bool {
+        self.next_attempt_at.map_or(true, |at| now >= at)
+    }
+
+    /// Schedule the next attempt after the current delay, then double it (capped)
+    pub fn on_failure(&mut self, now: Instant) {
+        self.next_attempt_at = Some(now + self.current);
+        self.current = (self.current * 2).min(self.max);
+    }
+
+    /// Connection healthy again - start over from the initial delay
+    pub fn reset(&mut self) {
+        self.current = self.initial;
+        self.next_attempt_at = None;
+    }
+}
+
 /// gRPC client for JITO bundle submission
 pub struct JitoGrpcClient {
     client: SearcherServiceClient<Channel>,
@@ -97,14 +138,40 @@ impl JitoGrpcClient {
         Ok(())
     }
 
+    /// Re-establish the gRPC channel after a disconnect
+    ///
+    /// Tries every endpoint once, starting with the current one.
+    /// Callers space out attempts with `ReconnectBackoff`.
+    pub async fn reconnect(&mut self) -> Result<()> {
+        let mut last_error = None;
+
+        for offset in 0..self.endpoints.len() {
+            let idx = (self.current_endpoint_idx + offset) % self.endpoints.len();
+            match Self::connect_to_endpoint(&self.endpoints[idx]).await {
+                Ok(channel) => {
+                    self.client = SearcherServiceClient::new(channel);
+                    self.current_endpoint_idx = idx;
+                    info!("🔌 gRPC reconnected: {}", self.endpoints[idx]);
+                    return Ok(());
+                }
+                Err(e) => {
+                    debug!("gRPC reconnect to {} failed: {}", self.endpoints[idx], e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No gRPC endpoints configured")))
+    }
+
     /// Submit bundle via gRPC (FAST!)
     ///
     /// # Arguments
-    /// * `transactions` - Transactions with JITO tips ALREADY included
+    /// * `transactions` - Transactions with JITO tips ALREADY included (legacy or versioned)
     ///
     /// # Returns
     /// Bundle UUID from JITO
-    pub async fn send_bundle(&mut self, transactions: Vec<Transaction>) -> Result<String> {
+    pub async fn send_bundle<T: Serialize>(&mut self, transactions: Vec<T>) -> Result<String> {
         // Convert Solana transactions to JITO Packets
         let packets: Vec<packet::Packet> = transactions
             .iter()
@@ -279,4 +346,27 @@ mod tests {
         let result = JitoGrpcClient::new().await;
         assert!(result.is_ok() || result.is_err()); // Either works for CI
     }
+
+    #[test]
+    fn test_reconnect_backoff_doubles_and_resets() {
+        let start = Instant::now();
+        let mut backoff = ReconnectBackoff::new(Duration::from_secs(5), Duration::from_secs(12));
+        assert!(backoff.ready(start));
+
+        backoff.on_failure(start);
+        assert!(!backoff.ready(start + Duration::from_secs(4)));
+        assert!(backoff.ready(start + Duration::from_secs(5)));
+
+        // Second failure waits 10s, third is capped at 12s
+        backoff.on_failure(start);
+        assert!(!backoff.ready(start + Duration::from_secs(9)));
+        backoff.on_failure(start);
+        assert!(!backoff.ready(start + Duration::from_secs(11)));
+        assert!(backoff.ready(start + Duration::from_secs(12)));
+
+        backoff.reset();
+        assert!(backoff.ready(start));
+        backoff.on_failure(start);
+        assert!(backoff.ready(start + Duration::from_secs(5)));
+    }
 }
@@ -4,39 +4,289 @@
 // - gRPC for 2x faster submission (75ms vs 150ms)
 // - HTTP fallback for reliability
 // - Non-blocking queue (detects opportunities without delay)
-// - Precise rate control (1 bundle per 1.1 seconds)
+// - Precise rate control (1 bundle per JITO_BUNDLE_RATE_MS, default 1.1 seconds)
 // - Client reuse (10-50ms performance boost)
 // - Exponential backoff on 429 errors
 // - Support for batching up to 5 transactions per bundle
 
-use anyhow::Result;
-use solana_sdk::transaction::Transaction;
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::Keypair;
+use solana_sdk::transaction::VersionedTransaction;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
 use tokio::time::{self, Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+use crate::cached_blockhash::{self, SharedCachedBlockhash, BLOCKHASH_NEAR_EXPIRY};
+use crate::errors::ArbError;
 use crate::jito_bundle_client::JitoBundleClient;
-use crate::jito_grpc_client::JitoGrpcClient;
+use crate::jito_grpc_client::{JitoGrpcClient, ReconnectBackoff};
+use crate::rpc_client::SolanaRpcClient;
+
+/// gRPC restore attempts while on HTTP fallback: 5s, 10s, 20s ... capped at 5 minutes
+const GRPC_RESTORE_INITIAL_SECS: u64 = 5;
+const GRPC_RESTORE_MAX_SECS: u64 = 300;
+
+/// Default minimum time between bundle submissions (JITO_BUNDLE_RATE_MS, free tier)
+pub const DEFAULT_BUNDLE_INTERVAL_MS: u64 = 1100;
+
+/// Fastest allowed submission rate - below this block engines rate-limit or ban the key
+pub const MIN_BUNDLE_INTERVAL_MS: u64 = 200;
+
+/// Default cap on queued (not yet submitted) bundles - older ones are stale anyway
+pub const DEFAULT_MAX_QUEUE_DEPTH: usize = 3;
+
+/// How long to poll JITO for a submitted bundle before counting it as not landed
+const BUNDLE_LANDING_TIMEOUT_SECS: u64 = 10;
+
+/// JITO block engine rejects bundles with more transactions than this
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
 
 /// Bundle submission request
 #[derive(Debug, Clone)]
 pub struct BundleRequest {
-    pub transactions: Vec<Transaction>, // Transactions with tips ALREADY included
-    pub description: String,            // For logging (e.g., "SOL→TokenA→SOL arbitrage")
+    pub transactions: Vec<VersionedTransaction>, // Transactions with tips ALREADY included
+    pub description: String,                     // For logging (e.g., "SOL→TokenA→SOL arbitrage")
     pub expected_profit_sol: f64,
     pub attempt: u32,
-    pub queued_at: Instant, // Timestamp when bundle was queued
+    pub queued_at: Instant,        // Timestamp when bundle was queued
+    pub dedup_key: Option<String>, // Reported back via `drain_outcomes` once resolved
+}
+
+/// Destination for signed bundles, implemented by `JitoSubmitter`
+///
+/// Lets the engine hand over multi-transaction bundles without depending on the
+/// concrete queue, so bundle assembly can be exercised against a mock in tests.
+pub trait BundleSink: Send + Sync {
+    /// Queue `transactions` as ONE bundle tagged with `dedup_key`
+    fn submit_bundle(
+        &self,
+        transactions: Vec<VersionedTransaction>,
+        description: String,
+        expected_profit_sol: f64,
+        dedup_key: String,
+    ) -> BoxFuture<'_, Result<()>>;
+}
+
+/// Final state of a keyed bundle (landed, not landed, or dropped before sending)
+#[derive(Debug, Clone)]
+pub struct BundleOutcome {
+    pub dedup_key: String,
+    pub bundle_id: Option<String>, // None if dropped before submission
+    pub landed: bool,
+    /// Signature of each transaction as sent (after any re-sign); empty if never sent
+    pub signatures: Vec<String>,
+}
+
+/// Re-signs queued bundles with a fresh blockhash
+///
+/// Bundles are signed at build time with the cached blockhash; one that waited
+/// in the queue can reach the cluster after that blockhash expired. Only works
+/// for transactions whose sole signer is the wallet.
+pub struct BlockhashResigner {
+    cached: SharedCachedBlockhash,
+    rpc_client: Arc<SolanaRpcClient>,
+    signer: Arc<Keypair>,
+}
+
+impl BlockhashResigner {
+    /// # Arguments
+    /// * `cached` - Blockhash cache shared with the refresher task
+    /// * `rpc_client` - Used to fetch a fresh blockhash on expiry
+    /// * `signer` - Wallet that signed the original transactions
+    pub fn new(
+        cached: SharedCachedBlockhash,
+        rpc_client: Arc<SolanaRpcClient>,
+        signer: Arc<Keypair>,
+    ) -> Self {
+        Self {
+            cached,
+            rpc_client,
+            signer,
+        }
+    }
+
+    /// Whether the cached blockhash (what bundles are built with) is about to expire
+    async fn cached_near_expiry(&self) -> bool {
+        cached_blockhash::blockhash_age(&self.cached)
+            .await
+            .is_some_and(|age| age >= BLOCKHASH_NEAR_EXPIRY)
+    }
+
+    /// Fetch a fresh blockhash from RPC and re-sign every transaction with it
+    async fn resign_fresh(
+        &self,
+        transactions: &[VersionedTransaction],
+    ) -> Result<Vec<VersionedTransaction>> {
+        let blockhash = cached_blockhash::refresh_blockhash(&self.cached, &self.rpc_client)
+            .await
+            .context("Failed to fetch fresh blockhash")?;
+        transactions
+            .iter()
+            .map(|tx| resign_transaction(tx, blockhash, &self.signer))
+            .collect()
+    }
+}
+
+/// Bounded drop-oldest queue shared with the submission task
+///
+/// A full queue evicts its oldest entry: during a burst the newest opportunity is
+/// the one most likely to still be valid when the rate limit opens.
+struct BundleQueue {
+    items: std::sync::Mutex<VecDeque<BundleRequest>>,
+    notify: Notify,
+    max_depth: usize,
+    closed: AtomicBool,
+}
+
+impl BundleQueue {
+    fn new(max_depth: usize) -> Self {
+        Self {
+            items: std::sync::Mutex::new(VecDeque::with_capacity(max_depth)),
+            notify: Notify::new(),
+            max_depth: max_depth.max(1),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<BundleRequest>> {
+        match self.items.lock() {
+            Ok(items) => items,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Enqueue `request`, returning the evicted oldest entry if the queue was full
+    fn push(&self, request: BundleRequest) -> Option<BundleRequest> {
+        let evicted = {
+            let mut items = self.lock();
+            let evicted = if items.len() >= self.max_depth {
+                items.pop_front()
+            } else {
+                None
+            };
+            items.push_back(request);
+            evicted
+        };
+        self.notify.notify_one();
+        evicted
+    }
+
+    /// Remove every queued entry
+    fn drain(&self) -> Vec<BundleRequest> {
+        self.lock().drain(..).collect()
+    }
+
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Wait for the next entry (`None` once the submitter is dropped)
+    async fn recv(&self) -> Option<BundleRequest> {
+        loop {
+            if let Some(request) = self.lock().pop_front() {
+                return Some(request);
+            }
+            if self.closed.load(Ordering::SeqCst) {
+                return None;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.notify.notify_one();
+    }
 }
 
 /// Queue-based JITO bundle submitter with optional gRPC + HTTP fallback
 ///
 /// Ensures exactly 1 bundle per 1.1 seconds to avoid 429 errors
 pub struct JitoSubmitter {
-    queue_tx: mpsc::Sender<BundleRequest>, // CRITICAL FIX: Bounded channel (was unbounded)
+    queue: Arc<BundleQueue>, // Drop-oldest, capped at max_queue_depth
     stats: Arc<Mutex<SubmitterStats>>,
     grpc_client: Option<Arc<Mutex<JitoGrpcClient>>>, // Optional: gRPC (75ms latency)
     http_client: Arc<JitoBundleClient>,              // Always available: HTTP (150ms latency)
+    outcomes: Arc<std::sync::Mutex<Vec<BundleOutcome>>>, // Resolved keyed bundles (drained by engine)
+    task: std::sync::Mutex<Option<JoinHandle<()>>>,      // Submission task (awaited by `shutdown`)
+    landing_watches: Arc<std::sync::Mutex<Vec<JoinHandle<()>>>>, // Sent bundles still being polled
+}
+
+/// Transport currently used for bundle submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JitoTransport {
+    #[default]
+    Grpc,
+    Http,
+}
+
+impl std::fmt::Display for JitoTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JitoTransport::Grpc => write!(f, "gRPC"),
+            JitoTransport::Http => write!(f, "HTTP"),
+        }
+    }
+}
+
+/// Tracks which transport is active and when to try restoring gRPC
+///
+/// A gRPC failure flips submission to HTTP; restore attempts then follow an
+/// exponential backoff until a reconnect succeeds and gRPC is primary again.
+#[derive(Debug)]
+struct TransportSelector {
+    active: JitoTransport,
+    backoff: ReconnectBackoff,
+}
+
+impl TransportSelector {
+    fn new(grpc_available: bool) -> Self {
+        Self {
+            active: if grpc_available {
+                JitoTransport::Grpc
+            } else {
+                JitoTransport::Http
+            },
+            backoff: ReconnectBackoff::new(
+                Duration::from_secs(GRPC_RESTORE_INITIAL_SECS),
+                Duration::from_secs(GRPC_RESTORE_MAX_SECS),
+            ),
+        }
+    }
+
+    /// gRPC submission failed - fall back to HTTP and schedule a restore attempt
+    fn on_grpc_failure(&mut self, now: Instant) {
+        if self.active == JitoTransport::Grpc {
+            warn!(
+                "⚠️ gRPC transport down - using HTTP until reconnect (retry in {}s)",
+                GRPC_RESTORE_INITIAL_SECS
+            );
+            self.active = JitoTransport::Http;
+            self.backoff.reset();
+            self.backoff.on_failure(now);
+        }
+    }
+
+    /// On HTTP fallback and backoff elapsed
+    fn should_try_restore(&self, now: Instant) -> bool {
+        self.active == JitoTransport::Http && self.backoff.ready(now)
+    }
+
+    /// Record the outcome of a gRPC reconnect attempt
+    fn on_restore_attempt(&mut self, success: bool, now: Instant) {
+        if success {
+            self.active = JitoTransport::Grpc;
+            self.backoff.reset();
+        } else {
+            self.backoff.on_failure(now);
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -47,36 +297,65 @@ pub struct SubmitterStats {
     pub rate_limited_429: u64,
     pub queue_depth: usize,
     pub queue_full_drops: u64, // Track dropped bundles due to full queue
+    pub active_transport: JitoTransport,
+    pub grpc_reconnects: u64,
+    pub blockhash_retries: u64, // Resubmitted once after an expired-blockhash rejection
+    pub proactive_resigns: u64, // Re-signed before sending (cached blockhash near expiry)
 }
 
 impl JitoSubmitter {
     /// Create new JITO submitter with optional gRPC + HTTP fallback
-    /// CRITICAL FIX: Uses bounded channel (capacity 100) to prevent memory leaks
+    ///
+    /// # Arguments
+    /// * `grpc_client` - Optional gRPC client (preferred transport)
+    /// * `http_client` - HTTP client (fallback, always available)
+    /// * `max_queue_depth` - Max queued bundles; the oldest is dropped when full
+    ///   (see `DEFAULT_MAX_QUEUE_DEPTH`)
+    /// * `stale_threshold` - Queued bundles older than this are discarded, never sent
+    /// * `bundle_interval` - Minimum time between submissions (see `DEFAULT_BUNDLE_INTERVAL_MS`)
+    /// * `resigner` - Re-signs bundles whose blockhash expired (None = no retry)
     pub fn new(
         grpc_client: Option<Arc<Mutex<JitoGrpcClient>>>,
         http_client: Arc<JitoBundleClient>,
+        max_queue_depth: usize,
+        stale_threshold: Duration,
+        bundle_interval: Duration,
+        resigner: Option<BlockhashResigner>,
     ) -> Self {
-        let (queue_tx, mut queue_rx) = mpsc::channel::<BundleRequest>(100); // Bounded capacity
-        let stats = Arc::new(Mutex::new(SubmitterStats::default()));
+        let queue = Arc::new(BundleQueue::new(max_queue_depth));
+        let queue_rx = queue.clone();
+        let mut transport = TransportSelector::new(grpc_client.is_some());
+        let stats = Arc::new(Mutex::new(SubmitterStats {
+            active_transport: transport.active,
+            ..Default::default()
+        }));
         let stats_clone = stats.clone();
         let grpc_clone = grpc_client.clone();
         let http_clone = http_client.clone();
+        let outcomes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let outcomes_clone = outcomes.clone();
+        let landing_watches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let watches_clone = landing_watches.clone();
 
         // Spawn dedicated submission task
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             let mut last_submit = Instant::now();
 
             info!("🚀 JITO submission queue started (WAIT-FOR-FRESH)");
-            info!("   Rate: 1 bundle per 1.5 seconds");
+            info!("   Rate: 1 bundle per {:?}", bundle_interval);
             info!("   Strategy: DISCARD ALL stale, WAIT for fresh opportunities");
             info!("   User requirement: '0ms when we start the process'");
             info!("   Implementation: Drop everything, wait 100ms for NEW opportunity");
+            info!(
+                "   Max queue depth: {} (drop oldest), stale after {:?}",
+                queue_rx.max_depth, stale_threshold
+            );
 
             loop {
                 // Check if rate limit requires waiting
                 let elapsed = last_submit.elapsed();
-                if elapsed < Duration::from_millis(1500) {
-                    let wait_time = Duration::from_millis(1500) - elapsed;
+                if elapsed < bundle_interval {
+                    let wait_time = bundle_interval - elapsed;
                     debug!(
                         "⏱️ Rate limiting: waiting {:?} before next submission",
                         wait_time
@@ -85,10 +364,19 @@ impl JitoSubmitter {
                     // Sleep for most of the wait time
                     time::sleep(wait_time).await;
 
+                    // Shutting down: what is still queued is being drained, not replaced
+                    let draining = queue_rx.closed.load(Ordering::SeqCst);
+
                     // NOW clear ALL stale bundles from queue
                     let mut drained_count = 0;
-                    while queue_rx.try_recv().is_ok() {
+                    let stale_bundles = if draining {
+                        Vec::new()
+                    } else {
+                        queue_rx.drain()
+                    };
+                    for stale in stale_bundles {
                         drained_count += 1;
+                        record_outcome(&outcomes_clone, &stale, None, false);
                     }
                     if drained_count > 0 {
                         debug!(
@@ -124,10 +412,13 @@ impl JitoSubmitter {
                 };
 
                 // We have a fresh opportunity! Verify freshness one more time
-                let age_ms = request.queued_at.elapsed().as_millis();
-                if age_ms > 150 {
-                    // Should be impossible, but safety check
-                    warn!("⏰ Unexpected: bundle age {}ms > 150ms - dropping", age_ms);
+                let age = request.queued_at.elapsed();
+                if age > stale_threshold {
+                    warn!(
+                        "⏰ Bundle age {:?} > {:?} stale threshold - discarding: {}",
+                        age, stale_threshold, request.description
+                    );
+                    record_outcome(&outcomes_clone, &request, None, false);
                     let mut s = stats_clone.lock().await;
                     s.total_failed += 1;
                     continue;
@@ -139,82 +430,68 @@ impl JitoSubmitter {
                     s.queue_depth = queue_rx.len();
                 }
 
-                // Try gRPC first (if available), otherwise use HTTP
-                let bundle_id = if let Some(ref grpc_mutex) = grpc_clone {
-                    // gRPC available - try it first (2x faster!)
-                    let mut grpc = grpc_mutex.lock().await;
-                    match tokio::time::timeout(
-                        Duration::from_secs(5),
-                        grpc.send_bundle(request.transactions.clone()),
-                    )
-                    .await
-                    {
-                        Ok(Ok(uuid)) => {
-                            info!("🚀 JITO bundle submitted via gRPC (FAST!): {}", uuid);
-                            Ok(uuid)
-                        }
-                        Ok(Err(e)) => {
-                            warn!("⚠️ gRPC submission failed: {} - falling back to HTTP", e);
-                            // Release lock before HTTP call
-                            drop(grpc);
-
-                            // Fallback to HTTP
-                            match tokio::time::timeout(
-                                Duration::from_secs(10),
-                                http_clone.submit_bundle_safe(request.transactions.clone()),
-                            )
-                            .await
-                            {
-                                Ok(Ok(uuid)) => {
-                                    info!("📤 JITO bundle submitted via HTTP (fallback): {}", uuid);
-                                    Ok(uuid)
-                                }
-                                Ok(Err(e2)) => Err(anyhow::anyhow!(
-                                    "Both gRPC and HTTP failed: gRPC={}, HTTP={}",
-                                    e,
-                                    e2
-                                )),
-                                Err(_) => {
-                                    Err(anyhow::anyhow!("HTTP fallback timeout after gRPC failure"))
-                                }
-                            }
+                // On HTTP fallback: periodically try to bring gRPC back
+                if let Some(ref grpc_mutex) = grpc_clone {
+                    if transport.should_try_restore(Instant::now()) {
+                        let restored = grpc_mutex.lock().await.reconnect().await.is_ok();
+                        transport.on_restore_attempt(restored, Instant::now());
+                        if restored {
+                            info!("✅ gRPC transport restored - switching back from HTTP");
+                            stats_clone.lock().await.grpc_reconnects += 1;
+                        } else {
+                            debug!("gRPC still unavailable - staying on HTTP");
                         }
-                        Err(_) => {
-                            warn!("⚠️ gRPC timeout - falling back to HTTP");
-                            drop(grpc);
-
-                            // Fallback to HTTP
-                            match tokio::time::timeout(
-                                Duration::from_secs(10),
-                                http_clone.submit_bundle_safe(request.transactions.clone()),
-                            )
-                            .await
-                            {
-                                Ok(Ok(uuid)) => {
-                                    info!("📤 JITO bundle submitted via HTTP (fallback): {}", uuid);
-                                    Ok(uuid)
-                                }
-                                Ok(Err(e)) => Err(e),
-                                Err(_) => Err(anyhow::anyhow!("HTTP fallback timeout")),
+                    }
+                }
+                stats_clone.lock().await.active_transport = transport.active;
+
+                let mut transactions = request.transactions.clone();
+
+                // Proactively re-sign if the cached blockhash the bundle was built with
+                // is close to expiry (refresher stalled) - it would likely be rejected
+                if let Some(ref resigner) = resigner {
+                    if resigner.cached_near_expiry().await {
+                        match resigner.resign_fresh(&transactions).await {
+                            Ok(fresh) => {
+                                debug!("🔏 Cached blockhash near expiry - re-signed bundle");
+                                transactions = fresh;
+                                stats_clone.lock().await.proactive_resigns += 1;
                             }
+                            Err(e) => warn!("⚠️ Proactive blockhash re-sign failed: {}", e),
                         }
                     }
-                } else {
-                    // No gRPC - use HTTP only
-                    match tokio::time::timeout(
-                        Duration::from_secs(10),
-                        http_clone.submit_bundle_safe(request.transactions.clone()),
-                    )
-                    .await
-                    {
-                        Ok(Ok(uuid)) => {
-                            info!("📤 JITO bundle submitted via HTTP: {}", uuid);
-                            Ok(uuid)
+                }
+
+                let mut bundle_id = send_via_transport(
+                    grpc_clone.as_ref(),
+                    &http_clone,
+                    &mut transport,
+                    transactions.clone(),
+                )
+                .await
+                .map_err(ArbError::from_jito_submission);
+
+                // Expired blockhash: fetch a fresh one, re-sign and retry exactly once
+                if matches!(bundle_id, Err(ArbError::BlockhashExpired(_))) {
+                    if let Some(ref resigner) = resigner {
+                        warn!("⏰ Bundle rejected for expired blockhash - re-signing and retrying once");
+                        match resigner.resign_fresh(&transactions).await {
+                            Ok(fresh) => {
+                                stats_clone.lock().await.blockhash_retries += 1;
+                                transactions = fresh;
+                                bundle_id = send_via_transport(
+                                    grpc_clone.as_ref(),
+                                    &http_clone,
+                                    &mut transport,
+                                    transactions.clone(),
+                                )
+                                .await
+                                .map_err(ArbError::from_jito_submission);
+                            }
+                            Err(e) => warn!("⚠️ Could not re-sign with fresh blockhash: {}", e),
                         }
-                        Ok(Err(e)) => Err(e),
-                        Err(_) => Err(anyhow::anyhow!("HTTP timeout")),
                     }
-                };
+                }
 
                 match bundle_id {
                     Ok(bundle_id) => {
@@ -222,36 +499,19 @@ impl JitoSubmitter {
                         info!("   Expected profit: {:.6} SOL", request.expected_profit_sol);
                         info!("   🔒 Tip included INSIDE transaction (prevents unbundling)");
 
-                        // HIGH FIX: Wait for bundle confirmation with 10s timeout
-                        // Solana-optimized: Most bundles confirm within 5-10 seconds
-                        // Check if bundle actually landed on-chain
-                        match tokio::time::timeout(
-                            Duration::from_secs(10),
-                            check_bundle_status(&http_clone, &bundle_id),
-                        )
-                        .await
-                        {
-                            Ok(Ok(true)) => {
-                                info!("✅ Bundle landed successfully!");
-                                let mut s = stats_clone.lock().await;
-                                s.total_submitted += 1;
-                            }
-                            Ok(Ok(false)) => {
-                                warn!("⚠️ Bundle submitted but NOT landed on-chain");
-                                let mut s = stats_clone.lock().await;
-                                s.total_failed += 1;
-                            }
-                            Ok(Err(e)) => {
-                                warn!("⚠️ Failed to check bundle status: {}", e);
-                                // Count as submitted since we don't know status
-                                let mut s = stats_clone.lock().await;
-                                s.total_submitted += 1;
-                            }
-                            Err(_) => {
-                                warn!("⚠️ Bundle status check timeout (10s)");
-                                let mut s = stats_clone.lock().await;
-                                s.total_submitted += 1;
-                            }
+                        // Landing is polled off this loop, so the next bundle can go
+                        // out while this one is still in flight
+                        let watch = spawn_landing_watch(
+                            http_clone.clone(),
+                            stats_clone.clone(),
+                            outcomes_clone.clone(),
+                            request,
+                            bundle_id,
+                            sent_signatures(&transactions),
+                        );
+                        if let Ok(mut watches) = watches_clone.lock() {
+                            watches.retain(|watch| !watch.is_finished());
+                            watches.push(watch);
                         }
 
                         last_submit = Instant::now();
@@ -274,6 +534,7 @@ impl JitoSubmitter {
                             let mut s = stats_clone.lock().await;
                             s.total_failed += 1;
                         }
+                        record_outcome(&outcomes_clone, &request, None, false);
                     }
                 }
             }
@@ -282,57 +543,172 @@ impl JitoSubmitter {
         });
 
         Self {
-            queue_tx,
+            queue,
             stats,
             grpc_client,
             http_client,
+            outcomes,
+            task: std::sync::Mutex::new(Some(task)),
+            landing_watches,
         }
     }
 
+    /// Stop accepting bundles and settle the queue before exit
+    ///
+    /// With `drain`, the submission task keeps sending queued bundles (rate limit
+    /// and stale threshold still apply) until the queue is empty or `timeout`
+    /// elapses. Whatever is still queued afterwards - everything, without `drain` -
+    /// is abandoned and reported as not landed through `drain_outcomes`, so the
+    /// caller can release what it reserved for those bundles.
+    ///
+    /// # Returns
+    /// Descriptions of the abandoned bundles
+    pub async fn shutdown(&self, drain: bool, timeout: Duration) -> Vec<String> {
+        self.queue.close();
+
+        if drain {
+            let task = match self.task.lock() {
+                Ok(mut task) => task.take(),
+                Err(poisoned) => poisoned.into_inner().take(),
+            };
+            if let Some(task) = task {
+                info!(
+                    "⏳ Draining {} queued JITO bundles (timeout {:?})",
+                    self.queue.len(),
+                    timeout
+                );
+                if time::timeout(timeout, task).await.is_err() {
+                    warn!("⏰ JITO queue drain timed out after {:?}", timeout);
+                }
+            }
+
+            // Let sent bundles resolve so their outcomes (and tips) are settled
+            let watches = match self.landing_watches.lock() {
+                Ok(mut watches) => std::mem::take(&mut *watches),
+                Err(poisoned) => std::mem::take(&mut *poisoned.into_inner()),
+            };
+            if !watches.is_empty() {
+                info!("⏳ Waiting for {} sent bundle(s) to resolve", watches.len());
+                let all = futures::future::join_all(watches);
+                if time::timeout(timeout, all).await.is_err() {
+                    warn!("⏰ Bundle landing checks timed out after {:?}", timeout);
+                }
+            }
+        }
+
+        let abandoned = self.queue.drain();
+        for request in &abandoned {
+            warn!(
+                "🗑️ Abandoned queued bundle: {} (expected {:.6} SOL, queued {}ms ago)",
+                request.description,
+                request.expected_profit_sol,
+                request.queued_at.elapsed().as_millis()
+            );
+            record_outcome(&self.outcomes, request, None, false);
+        }
+
+        let mut stats = self.stats.lock().await;
+        stats.total_failed += abandoned.len() as u64;
+        stats.queue_depth = 0;
+        abandoned
+            .into_iter()
+            .map(|request| request.description)
+            .collect()
+    }
+
     /// Submit bundle to queue (non-blocking)
     ///
     /// **SECURITY**: Transactions must have JITO tip ALREADY included inside them!
     /// Use `SwapExecutor::build_triangle_with_tip()` to build transactions properly.
     ///
+    /// Accepts legacy `Transaction` (converted losslessly - same wire format) or
+    /// `VersionedTransaction` built with address lookup tables.
+    ///
     /// Returns immediately, bundle will be submitted at next available slot
-    pub async fn submit(
+    pub async fn submit<T: Into<VersionedTransaction>>(
         &self,
-        transactions: Vec<Transaction>, // Must have tips INSIDE
+        transactions: Vec<T>, // Must have tips INSIDE
         description: String,
         expected_profit_sol: f64,
     ) -> Result<()> {
-        let request = BundleRequest {
+        self.enqueue(transactions, description, expected_profit_sol, None)
+            .await
+    }
+
+    /// Submit bundle tagged with a dedup key (non-blocking)
+    ///
+    /// Same as `submit`, but a `BundleOutcome` carrying `dedup_key` is reported
+    /// through `drain_outcomes` as soon as the bundle lands, fails or is dropped,
+    /// so the caller can reconcile P&L and stop suppressing that opportunity.
+    pub async fn submit_keyed<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>, // Must have tips INSIDE
+        description: String,
+        expected_profit_sol: f64,
+        dedup_key: String,
+    ) -> Result<()> {
+        self.enqueue(
             transactions,
+            description,
+            expected_profit_sol,
+            Some(dedup_key),
+        )
+        .await
+    }
+
+    /// Take outcomes of keyed bundles resolved since the last call
+    pub fn drain_outcomes(&self) -> Vec<BundleOutcome> {
+        match self.outcomes.lock() {
+            Ok(mut outcomes) => std::mem::take(&mut *outcomes),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    async fn enqueue<T: Into<VersionedTransaction>>(
+        &self,
+        transactions: Vec<T>,
+        description: String,
+        expected_profit_sol: f64,
+        dedup_key: Option<String>,
+    ) -> Result<()> {
+        if transactions.is_empty() || transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(ArbError::JitoRejected(format!(
+                "bundle must contain 1-{} transactions (got {})",
+                MAX_BUNDLE_TRANSACTIONS,
+                transactions.len()
+            ))
+            .into());
+        }
+
+        let request = BundleRequest {
+            transactions: transactions.into_iter().map(Into::into).collect(),
             description: description.clone(),
             expected_profit_sol,
             attempt: 0,
             queued_at: Instant::now(), // Timestamp for stale detection
+            dedup_key,
         };
 
-        // Update stats
-        {
-            let mut stats = self.stats.lock().await;
-            stats.total_queued += 1;
-            // Note: UnboundedSender doesn't expose queue length
-            // Queue depth is tracked in the receiver task
+        if self.queue.closed.load(Ordering::SeqCst) {
+            return Err(ArbError::JitoRejected("submission queue closed".to_string()).into());
         }
 
-        // CRITICAL FIX: Use try_send for bounded channel (not async)
-        match self.queue_tx.try_send(request) {
-            Ok(_) => {
-                debug!("📥 Bundle queued: {}", description);
-                Ok(())
-            }
-            Err(mpsc::error::TrySendError::Full(_)) => {
-                warn!("⚠️ Queue FULL - bundle dropped. System overloaded!");
-                let mut stats = self.stats.lock().await;
-                stats.queue_full_drops += 1;
-                Err(anyhow::anyhow!("JITO queue full - bundle dropped"))
-            }
-            Err(mpsc::error::TrySendError::Closed(_)) => {
-                Err(anyhow::anyhow!("JITO submission queue closed"))
-            }
+        let evicted = self.queue.push(request);
+        debug!("📥 Bundle queued: {}", description);
+
+        let mut stats = self.stats.lock().await;
+        stats.total_queued += 1;
+        stats.queue_depth = self.queue.len();
+        if let Some(oldest) = evicted {
+            warn!(
+                "⚠️ Queue FULL ({} max) - dropped oldest bundle: {}",
+                self.queue.max_depth, oldest.description
+            );
+            record_outcome(&self.outcomes, &oldest, None, false);
+            stats.queue_full_drops += 1;
+            stats.total_failed += 1;
         }
+        Ok(())
     }
 
     /// Get submission statistics
@@ -345,6 +721,10 @@ impl JitoSubmitter {
             rate_limited_429: stats.rate_limited_429,
             queue_depth: stats.queue_depth,
             queue_full_drops: stats.queue_full_drops,
+            active_transport: stats.active_transport,
+            grpc_reconnects: stats.grpc_reconnects,
+            blockhash_retries: stats.blockhash_retries,
+            proactive_resigns: stats.proactive_resigns,
         }
     }
 
@@ -359,6 +739,13 @@ impl JitoSubmitter {
         info!("  • Failed permanently: {}", stats.total_failed);
         info!("  • 429 rate limits: {}", stats.rate_limited_429);
         info!("  • Current queue depth: {}", stats.queue_depth);
+        info!("  • Dropped (queue full): {}", stats.queue_full_drops);
+        info!("  • Active transport: {}", stats.active_transport);
+        info!("  • gRPC reconnects: {}", stats.grpc_reconnects);
+        info!(
+            "  • Blockhash retries: {} (proactive re-signs: {})",
+            stats.blockhash_retries, stats.proactive_resigns
+        );
 
         if stats.total_queued > 0 {
             let success_rate = (stats.total_submitted as f64 / stats.total_queued as f64) * 100.0;
@@ -369,35 +756,317 @@ impl JitoSubmitter {
     }
 }
 
-/// Helper function to check if JITO bundle landed on-chain
-///
-/// IMPLEMENTATION NOTE: JITO bundle status checking is removed in favor of
-/// transaction confirmation checking. Instead of checking bundle status,
-/// we rely on swap_executor's transaction confirmation logic which is more reliable.
+impl BundleSink for JitoSubmitter {
+    fn submit_bundle(
+        &self,
+        transactions: Vec<VersionedTransaction>,
+        description: String,
+        expected_profit_sol: f64,
+        dedup_key: String,
+    ) -> BoxFuture<'_, Result<()>> {
+        Box::pin(self.submit_keyed(transactions, description, expected_profit_sol, dedup_key))
+    }
+}
+
+impl Drop for JitoSubmitter {
+    fn drop(&mut self) {
+        // Lets the submission task exit (replaces channel-closed semantics)
+        self.queue.close();
+    }
+}
+
+/// Swap a new blockhash into a transaction and re-sign it with the wallet
 ///
-/// This function now returns Ok(false) to be conservative and not report
-/// false successes. The actual success/failure is determined by checking
-/// if the transaction signature confirms on-chain.
+/// # Errors
+/// Returns error if the transaction requires signers other than `signer`
+fn resign_transaction(
+    transaction: &VersionedTransaction,
+    blockhash: Hash,
+    signer: &Keypair,
+) -> Result<VersionedTransaction> {
+    let mut message = transaction.message.clone();
+    message.set_recent_blockhash(blockhash);
+    VersionedTransaction::try_new(message, &[signer])
+        .context("Failed to re-sign transaction (requires signers besides the wallet?)")
+}
+
+/// Send a bundle over the active transport, falling back from gRPC to HTTP
+async fn send_via_transport(
+    grpc: Option<&Arc<Mutex<JitoGrpcClient>>>,
+    http: &JitoBundleClient,
+    transport: &mut TransportSelector,
+    transactions: Vec<VersionedTransaction>,
+) -> Result<String> {
+    // Try gRPC first (if available and healthy), otherwise use HTTP
+    let grpc_active = transport.active == JitoTransport::Grpc;
+    if let Some(grpc_mutex) = grpc.filter(|_| grpc_active) {
+        // gRPC available - try it first (2x faster!)
+        let mut grpc = grpc_mutex.lock().await;
+        match tokio::time::timeout(
+            Duration::from_secs(5),
+            grpc.send_bundle(transactions.clone()),
+        )
+        .await
+        {
+            Ok(Ok(uuid)) => {
+                info!("🚀 JITO bundle submitted via gRPC (FAST!): {}", uuid);
+                Ok(uuid)
+            }
+            Ok(Err(e)) => {
+                warn!("⚠️ gRPC submission failed: {} - falling back to HTTP", e);
+                // Release lock before HTTP call
+                drop(grpc);
+                transport.on_grpc_failure(Instant::now());
+
+                // Fallback to HTTP
+                match tokio::time::timeout(
+                    Duration::from_secs(10),
+                    http.submit_bundle_safe(transactions.clone()),
+                )
+                .await
+                {
+                    Ok(Ok(uuid)) => {
+                        info!("📤 JITO bundle submitted via HTTP (fallback): {}", uuid);
+                        Ok(uuid)
+                    }
+                    Ok(Err(e2)) => Err(anyhow::anyhow!(
+                        "Both gRPC and HTTP failed: gRPC={}, HTTP={}",
+                        e,
+                        e2
+                    )),
+                    Err(_) => Err(anyhow::anyhow!("HTTP fallback timeout after gRPC failure")),
+                }
+            }
+            Err(_) => {
+                warn!("⚠️ gRPC timeout - falling back to HTTP");
+                drop(grpc);
+                transport.on_grpc_failure(Instant::now());
+
+                // Fallback to HTTP
+                match tokio::time::timeout(
+                    Duration::from_secs(10),
+                    http.submit_bundle_safe(transactions.clone()),
+                )
+                .await
+                {
+                    Ok(Ok(uuid)) => {
+                        info!("📤 JITO bundle submitted via HTTP (fallback): {}", uuid);
+                        Ok(uuid)
+                    }
+                    Ok(Err(e)) => Err(e),
+                    Err(_) => Err(anyhow::anyhow!("HTTP fallback timeout")),
+                }
+            }
+        }
+    } else {
+        // No gRPC - use HTTP only
+        match tokio::time::timeout(
+            Duration::from_secs(10),
+            http.submit_bundle_safe(transactions.clone()),
+        )
+        .await
+        {
+            Ok(Ok(uuid)) => {
+                info!("📤 JITO bundle submitted via HTTP: {}", uuid);
+                Ok(uuid)
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(anyhow::anyhow!("HTTP timeout")),
+        }
+    }
+}
+
+/// Poll a sent bundle until it lands, fails or times out, then report the outcome
 ///
-/// Future enhancement: Implement proper JITO bundle status API if needed:
-/// - Use JITO's get_bundle_statuses RPC method
-/// - Check bundle.landed status
-/// - This would provide earlier failure detection before full confirmation
-async fn check_bundle_status(
-    _jito_client: &Arc<JitoBundleClient>,
-    bundle_id: &str,
-) -> Result<bool> {
-    // REMOVED: Fake OK(true) return that was causing false success reports
-    //
-    // Instead, we return Ok(false) to be conservative.
-    // Real success/failure is determined by transaction confirmation,
-    // not bundle status (which we don't have API for yet).
-
-    warn!("⚠️ JITO bundle status check not implemented - relying on transaction confirmation");
-    warn!("   Bundle ID: {}", bundle_id);
-    warn!("   This is expected - transaction confirmation provides actual success status");
-
-    // Conservative: return false since we cannot verify bundle landing
-    // Transaction confirmation will provide the actual success/failure status
-    Ok(false)
+/// Runs as its own task so the submission loop never blocks on confirmation.
+/// Unknown status counts as NOT landed - never report fictional profit.
+fn spawn_landing_watch(
+    http: Arc<JitoBundleClient>,
+    stats: Arc<Mutex<SubmitterStats>>,
+    outcomes: Arc<std::sync::Mutex<Vec<BundleOutcome>>>,
+    request: BundleRequest,
+    bundle_id: String,
+    signatures: Vec<String>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let landed = match http
+            .wait_for_landing(&bundle_id, Duration::from_secs(BUNDLE_LANDING_TIMEOUT_SECS))
+            .await
+        {
+            Ok(slot) => {
+                info!("✅ Bundle landed successfully! (slot: {:?})", slot);
+                stats.lock().await.total_submitted += 1;
+                true
+            }
+            Err(e) => {
+                warn!("⚠️ Bundle submitted but NOT landed on-chain: {}", e);
+                stats.lock().await.total_failed += 1;
+                false
+            }
+        };
+        let sent = Some((bundle_id.as_str(), signatures));
+        record_outcome(&outcomes, &request, sent, landed);
+    })
+}
+
+/// First signature of each transaction - identifies what a landed bundle did on-chain
+fn sent_signatures(transactions: &[VersionedTransaction]) -> Vec<String> {
+    transactions
+        .iter()
+        .filter_map(|tx| tx.signatures.first().map(ToString::to_string))
+        .collect()
+}
+
+/// Report a keyed bundle's final state back to the submitter's owner
+fn record_outcome(
+    outcomes: &std::sync::Mutex<Vec<BundleOutcome>>,
+    request: &BundleRequest,
+    sent: Option<(&str, Vec<String>)>,
+    landed: bool,
+) {
+    if let (Some(key), Ok(mut outcomes)) = (request.dedup_key.as_ref(), outcomes.lock()) {
+        let (bundle_id, signatures) = match sent {
+            Some((bundle_id, signatures)) => (Some(bundle_id.to_string()), signatures),
+            None => (None, Vec::new()),
+        };
+        outcomes.push(BundleOutcome {
+            dedup_key: key.clone(),
+            bundle_id,
+            landed,
+            signatures,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transport_flips_back_to_grpc_after_recovery() {
+        let start = Instant::now();
+        let mut transport = TransportSelector::new(true);
+        assert_eq!(transport.active, JitoTransport::Grpc);
+        assert!(!transport.should_try_restore(start));
+
+        // gRPC drops mid-run → HTTP fallback, restore not attempted immediately
+        transport.on_grpc_failure(start);
+        assert_eq!(transport.active, JitoTransport::Http);
+        assert!(!transport.should_try_restore(start));
+
+        // First restore attempt fails → backoff doubles (5s → 10s)
+        let first_try = start + Duration::from_secs(GRPC_RESTORE_INITIAL_SECS);
+        assert!(transport.should_try_restore(first_try));
+        transport.on_restore_attempt(false, first_try);
+        assert_eq!(transport.active, JitoTransport::Http);
+        assert!(!transport.should_try_restore(first_try + Duration::from_secs(9)));
+
+        // gRPC recovers → submitter flips back
+        let second_try = first_try + Duration::from_secs(10);
+        assert!(transport.should_try_restore(second_try));
+        transport.on_restore_attempt(true, second_try);
+        assert_eq!(transport.active, JitoTransport::Grpc);
+        assert!(!transport.should_try_restore(second_try + Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_http_only_when_grpc_unavailable_at_startup() {
+        let transport = TransportSelector::new(false);
+        assert_eq!(transport.active, JitoTransport::Http);
+    }
+
+    #[test]
+    fn test_resign_swaps_blockhash_and_signature() {
+        use solana_sdk::signature::Signer;
+        use solana_sdk::system_instruction;
+        use solana_sdk::transaction::Transaction;
+
+        let wallet = Keypair::new();
+        let ix = system_instruction::transfer(&wallet.pubkey(), &Keypair::new().pubkey(), 1);
+        let stale = Hash::new_unique();
+        let original: VersionedTransaction =
+            Transaction::new_signed_with_payer(&[ix], Some(&wallet.pubkey()), &[&wallet], stale)
+                .into();
+
+        let fresh = Hash::new_unique();
+        let resigned = resign_transaction(&original, fresh, &wallet).unwrap();
+        assert_eq!(*resigned.message.recent_blockhash(), fresh);
+        assert_ne!(resigned.signatures[0], original.signatures[0]);
+        assert!(resigned.verify_with_results().iter().all(|ok| *ok));
+
+        // A different wallet cannot re-sign the transaction
+        assert!(resign_transaction(&original, fresh, &Keypair::new()).is_err());
+    }
+
+    fn test_request(description: &str) -> BundleRequest {
+        BundleRequest {
+            transactions: Vec::new(),
+            description: description.to_string(),
+            expected_profit_sol: 0.0,
+            attempt: 0,
+            queued_at: Instant::now(),
+            dedup_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queue_drops_oldest_when_full() {
+        let queue = BundleQueue::new(DEFAULT_MAX_QUEUE_DEPTH);
+        for i in 0..DEFAULT_MAX_QUEUE_DEPTH {
+            assert!(queue.push(test_request(&i.to_string())).is_none());
+        }
+
+        let evicted = queue.push(test_request("newest")).unwrap();
+        assert_eq!(evicted.description, "0");
+        assert_eq!(queue.len(), DEFAULT_MAX_QUEUE_DEPTH);
+        assert_eq!(queue.recv().await.unwrap().description, "1");
+
+        queue.drain();
+        queue.close();
+        assert!(queue.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_without_drain_abandons_queued_bundles() {
+        let http_client = Arc::new(JitoBundleClient::new(String::new(), String::new(), None));
+        let submitter = JitoSubmitter::new(
+            None,
+            http_client,
+            DEFAULT_MAX_QUEUE_DEPTH,
+            Duration::from_secs(5),
+            Duration::from_millis(DEFAULT_BUNDLE_INTERVAL_MS),
+            None,
+        );
+
+        // Submission task holds the rate limit for its first 1.1s: both stay queued
+        for key in ["pair-a", "pair-b"] {
+            submitter
+                .submit_keyed(
+                    vec![VersionedTransaction::default()],
+                    key.to_string(),
+                    0.01,
+                    key.to_string(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let abandoned = submitter.shutdown(false, Duration::from_secs(1)).await;
+        assert_eq!(abandoned, vec!["pair-a".to_string(), "pair-b".to_string()]);
+
+        let outcomes = submitter.drain_outcomes();
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|o| !o.landed && o.bundle_id.is_none()));
+        assert_eq!(submitter.get_stats().await.total_failed, 2);
+
+        // Closed queue refuses new bundles
+        assert!(submitter
+            .submit(
+                vec![VersionedTransaction::default()],
+                "late".to_string(),
+                0.01
+            )
+            .await
+            .is_err());
+    }
 }
@@ -0,0 +1,255 @@
+// Jupiter Swap API client (quote + signed transaction building)
+//
+// Jupiter returns v0 transactions that reference address lookup tables, which our
+// legacy `Transaction` type cannot represent. The lookups are already compiled into
+// the message, so we only swap in a fresh blockhash and sign - no ALT resolution needed.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::{
+    hash::Hash,
+    signature::{Keypair, Signer},
+    transaction::VersionedTransaction,
+};
+use tracing::debug;
+
+/// Default slippage for Jupiter swaps (1%, matches direct DEX builders)
+pub const JUPITER_SLIPPAGE_BPS: u16 = 100;
+
+/// Slippage for unwinding a stranded position (5% - getting out beats the price)
+pub const UNWIND_SLIPPAGE_BPS: u16 = 500;
+
+/// Jupiter quote for a single input → output swap
+///
+/// Keeps the raw quote JSON because `/swap` expects it back verbatim.
+#[derive(Debug, Clone)]
+pub struct JupiterRoute {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    /// Minimum output after slippage (what the swap guarantees)
+    pub other_amount_threshold: u64,
+    raw: Value,
+}
+
+impl JupiterRoute {
+    /// Parse a `/quote` response
+    pub fn from_quote_json(raw: Value) -> Result<Self> {
+        let field = |name: &str| -> Result<String> {
+            raw.get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("Jupiter quote missing field: {}", name))
+        };
+        let amount = |name: &str| -> Result<u64> {
+            let value = field(name)?;
+            value
+                .parse()
+                .context(format!("Failed to parse Jupiter quote {}: {}", name, value))
+        };
+
+        Ok(Self {
+            input_mint: field("inputMint")?,
+            output_mint: field("outputMint")?,
+            in_amount: amount("inAmount")?,
+            out_amount: amount("outAmount")?,
+            other_amount_threshold: amount("otherAmountThreshold")?,
+            raw,
+        })
+    }
+}
+
+/// Jupiter `/swap` response
+#[derive(Debug, Deserialize)]
+struct JupiterSwapResponse {
+    #[serde(rename = "swapTransaction")]
+    swap_transaction: String,
+}
+
+/// Jupiter Swap API client
+pub struct JupiterSwapClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl JupiterSwapClient {
+    /// Create new Jupiter Swap API client (keyed endpoint if API key provided)
+    pub fn new(api_key: Option<String>) -> Self {
+        let base_url = if api_key.is_some() {
+            "https://api.jup.ag/swap/v1".to_string()
+        } else {
+            "https://lite-api.jup.ag/swap/v1".to_string()
+        };
+
+        Self {
+            client: reqwest::Client::new(),
+            base_url,
+            api_key,
+        }
+    }
+
+    /// Fetch a quote for `amount` base units of `input_mint` → `output_mint`
+    pub async fn get_quote(
+        &self,
+        input_mint: &str,
+        output_mint: &str,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> Result<JupiterRoute> {
+        let url = format!(
+            "{}/quote?inputMint={}&outputMint={}&amount={}&slippageBps={}",
+            self.base_url, input_mint, output_mint, amount, slippage_bps
+        );
+
+        let mut request = self.client.get(&url);
+        if let Some(ref key) = self.api_key {
+            request = request.header("X-API-Key", key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Jupiter quote request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Jupiter quote error {}: {}", status, text));
+        }
+
+        let route = JupiterRoute::from_quote_json(response.json().await?)?;
+        debug!(
+            "🪐 Jupiter quote: {} {} → {} {}",
+            route.in_amount,
+            &route.input_mint[..8.min(route.input_mint.len())],
+            route.out_amount,
+            &route.output_mint[..8.min(route.output_mint.len())]
+        );
+        Ok(route)
+    }
+
+    /// Build a signed swap transaction for `route` via Jupiter's `/swap` endpoint
+    ///
+    /// # Arguments
+    /// * `route` - Quote from `get_quote`
+    /// * `wallet` - Signer and fee payer
+    /// * `blockhash` - Recent blockhash (replaces the one Jupiter embedded)
+    ///
+    /// # Returns
+    /// Signed `VersionedTransaction` (v0 with address lookup tables when Jupiter uses them)
+    pub async fn build_swap_transaction(
+        &self,
+        route: &JupiterRoute,
+        wallet: &Keypair,
+        blockhash: Hash,
+    ) -> Result<VersionedTransaction> {
+        let body = json!({
+            "quoteResponse": route.raw,
+            "userPublicKey": wallet.pubkey().to_string(),
+            "wrapAndUnwrapSol": true,
+            "dynamicComputeUnitLimit": true,
+        });
+
+        let mut request = self
+            .client
+            .post(format!("{}/swap", self.base_url))
+            .json(&body);
+        if let Some(ref key) = self.api_key {
+            request = request.header("X-API-Key", key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Jupiter swap request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Jupiter swap error {}: {}", status, text));
+        }
+
+        let swap: JupiterSwapResponse = response.json().await?;
+        sign_swap_transaction(&swap.swap_transaction, wallet, blockhash)
+    }
+}
+
+/// Decode Jupiter's base64 transaction, set `blockhash` and sign with `wallet`
+fn sign_swap_transaction(
+    encoded: &str,
+    wallet: &Keypair,
+    blockhash: Hash,
+) -> Result<VersionedTransaction> {
+    let bytes = BASE64
+        .decode(encoded)
+        .context("Failed to decode Jupiter swap transaction")?;
+    let unsigned: VersionedTransaction =
+        bincode::deserialize(&bytes).context("Failed to deserialize Jupiter swap transaction")?;
+
+    let mut message = unsigned.message;
+    message.set_recent_blockhash(blockhash);
+
+    VersionedTransaction::try_new(message, &[wallet])
+        .context("Failed to sign Jupiter swap transaction")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        address_lookup_table::AddressLookupTableAccount,
+        message::{v0, VersionedMessage},
+        pubkey::Pubkey,
+        system_instruction,
+    };
+
+    #[test]
+    fn test_parse_quote_json() {
+        let raw = json!({
+            "inputMint": "So11111111111111111111111111111111111111112",
+            "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "inAmount": "1000000000",
+            "outAmount": "150000000",
+            "otherAmountThreshold": "148500000",
+            "routePlan": []
+        });
+
+        let route = JupiterRoute::from_quote_json(raw).unwrap();
+        assert_eq!(route.in_amount, 1_000_000_000);
+        assert_eq!(route.out_amount, 150_000_000);
+        assert_eq!(route.other_amount_threshold, 148_500_000);
+        assert!(JupiterRoute::from_quote_json(json!({"inAmount": "1"})).is_err());
+    }
+
+    #[test]
+    fn test_sign_v0_transaction_with_lookup_table() {
+        let wallet = Keypair::new();
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique()],
+        };
+        let ix = system_instruction::transfer(&wallet.pubkey(), &lookup_table.addresses[0], 1);
+        let message =
+            v0::Message::try_compile(&wallet.pubkey(), &[ix], &[lookup_table], Hash::default())
+                .unwrap();
+
+        // Jupiter returns the transaction unsigned (default signatures)
+        let unsigned = VersionedTransaction {
+            signatures: vec![Default::default()],
+            message: VersionedMessage::V0(message),
+        };
+        let encoded = BASE64.encode(bincode::serialize(&unsigned).unwrap());
+
+        let blockhash = Hash::new_unique();
+        let signed = sign_swap_transaction(&encoded, &wallet, blockhash).unwrap();
+
+        assert_eq!(*signed.message.recent_blockhash(), blockhash);
+        assert!(signed
+            .message
+            .address_table_lookups()
+            .is_some_and(|l| l.len() == 1));
+        assert!(signed.verify_with_results().iter().all(|ok| *ok));
+    }
+}
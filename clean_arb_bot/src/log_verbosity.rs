@@ -0,0 +1,88 @@
+// Opportunity-log verbosity (LOG_VERBOSITY)
+//
+// RUST_LOG filters by level; this decides which info-level scan chatter the
+// engine emits in the first place:
+// - quiet:   executions, landings and errors only
+// - normal:  plus spreads above LOG_SPREAD_THRESHOLD_PCT and opportunity summaries
+// - verbose: plus per-trade cost breakdowns and position sizing
+
+use anyhow::{bail, Result};
+use std::fmt;
+
+/// Default LOG_SPREAD_THRESHOLD_PCT: spreads above this are logged
+pub const DEFAULT_LOG_SPREAD_THRESHOLD_PCT: f64 = 0.3;
+
+/// How much per-scan detail the engine logs at info level
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogVerbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+impl LogVerbosity {
+    /// Parse a LOG_VERBOSITY value (`quiet`, `normal` or `verbose`, case-insensitive)
+    ///
+    /// # Errors
+    /// Returns error for any other value
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "quiet" => Ok(Self::Quiet),
+            "normal" => Ok(Self::Normal),
+            "verbose" => Ok(Self::Verbose),
+            other => bail!(
+                "Invalid LOG_VERBOSITY '{}': expected quiet, normal or verbose",
+                other
+            ),
+        }
+    }
+
+    /// Log each spread above the threshold as it is found
+    pub fn logs_spreads(&self) -> bool {
+        *self >= Self::Normal
+    }
+
+    /// Log opportunity summaries and per-scan totals
+    pub fn logs_opportunities(&self) -> bool {
+        *self >= Self::Normal
+    }
+
+    /// Log cost breakdowns and position sizing for each trade
+    pub fn logs_cost_breakdowns(&self) -> bool {
+        *self >= Self::Verbose
+    }
+}
+
+impl fmt::Display for LogVerbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Quiet => write!(f, "quiet"),
+            Self::Normal => write!(f, "normal"),
+            Self::Verbose => write!(f, "verbose"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_gates() {
+        let quiet = LogVerbosity::parse("QUIET").unwrap();
+        assert!(!quiet.logs_spreads());
+        assert!(!quiet.logs_opportunities());
+        assert!(!quiet.logs_cost_breakdowns());
+
+        let normal = LogVerbosity::parse(" normal ").unwrap();
+        assert_eq!(normal, LogVerbosity::default());
+        assert!(normal.logs_spreads() && normal.logs_opportunities());
+        assert!(!normal.logs_cost_breakdowns());
+
+        assert!(LogVerbosity::parse("verbose")
+            .unwrap()
+            .logs_cost_breakdowns());
+        assert!(LogVerbosity::parse("loud").is_err());
+    }
+}
@@ -1,81 +1,228 @@
 //! Clean Arbitrage Bot - Production MEV Trading System
 //! CYCLE-7: Grok-approved production system (9/10 → 10/10 in progress)
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use tokio::signal;
 use tokio::sync::broadcast;
 use tracing::{error, info};
 
 mod arbitrage_engine;
 mod config;
+mod dex_filter; // ENABLED_DEXES / DISABLED_DEXES runtime switches
 mod dex_registry;
+mod errors; // ArbError: structured execution failures
 mod jito_bundle_client;
 mod jito_grpc_client; // NEW (2025-10-12): gRPC for 75ms faster submission!
 mod jito_submitter;
 mod jito_tip_monitor;
 mod jupiter_prices;
+mod jupiter_swap; // Jupiter /quote + /swap execution (v0 transactions)
 mod jupiter_triangle;
+mod log_verbosity; // Which scan/opportunity logs are emitted (LOG_VERBOSITY)
+mod metrics; // Prometheus /metrics endpoint (opt-in via METRICS_PORT)
+mod opportunity_journal; // Replayable JSON per execution attempt (OPPORTUNITY_JOURNAL_DIR)
+mod paper_model; // Deterministic paper-trading fills (PAPER_SEED)
+mod profit_currency; // USD overlay for profit stats and limits (PROFIT_CURRENCY)
+mod profit_divergence; // Realized vs estimated profit per DEX route (PROFIT_DIVERGENCE_ALERT_SOL)
+mod real_price_feed; // RPC pool-reserve prices when ShredStream is down
+mod secure_wallet; // Encrypted wallet keystore (WALLET_KEYSTORE)
 mod shredstream_client;
 mod simple_triangle_detector;
+mod spread_analyzer; // Per-token min-spread advice from spread history (AUTO_TUNE_SPREAD)
+mod spread_tiers; // Max realistic spread per token tier (SPREAD_TIERS)
+mod status; // JSON GET /status endpoint (opt-in via STATUS_PORT)
+mod structured_log; // LOG_FORMAT=json output + typed execution events
+mod tip_strategy; // JITO tip policy shared by every tip computation (TIP_STRATEGY)
+mod token_list; // TOKEN_LIST_FILE allowlist/denylist (hot-reloaded)
+mod token_metadata; // Mint → symbol/decimals for logs (TOKEN_METADATA_SOURCE backfill)
+mod token_program; // SPL Token vs Token-2022 detection, transfer fees
+mod token_safety; // Mint/freeze authority check (ALLOW_MINTABLE_TOKENS)
+mod trade_log; // CSV log of trade attempts (TRADE_LOG_PATH)
 mod triangle_arbitrage; // NEW: Dynamic JITO tip adjustment (every 30 min)
                         // DEX swap modules (flattened from dex_swap/ directory)
+mod aldrin;
+mod amm_math; // Constant-product curve, fee helpers, vault balance decoding
+mod crema;
+mod fluxbeam;
 mod humidifi;
+mod lifinity;
 mod meteora;
 mod orca;
 mod pool_registry;
 mod pumpswap;
 mod raydium;
 mod rpc_client;
+mod saros;
 mod swap_executor;
 mod types;
 
 mod cached_blockhash;
+#[cfg(test)]
+mod captured_fixtures; // Captured mainnet pool accounts/swaps (fixtures/, scripts/capture_fixture.py)
 mod cost_calculator; // Cost calculation and profitability filtering
-mod meteora_swap; // CYCLE-7: Meteora DAMM V2 swap instructions (90% of opportunities)
 mod pool_population;
 mod position_tracker; // HIGH-4 FIX: Position tracking module
 mod slippage; // CYCLE-7: Dynamic slippage protection // NEW (2025-10-11): Pre-fetched blockhash (saves 50-70ms per tx)
+#[cfg(test)]
+mod test_harness; // MockRpcClient + MockJitoSubmitter for end-to-end pipeline tests
 
 // Public re-exports for convenience (previously in dex_swap/mod.rs)
 use pool_registry::PoolRegistry;
-use rpc_client::SolanaRpcClient;
+use rpc_client::{RpcRetryPolicy, SolanaRpcClient};
+use secure_wallet::SecureWallet;
+use structured_log::LogFormat;
 use swap_executor::SwapExecutor;
 use types::{extract_pool_id, DexType, PoolInfo, SwapParams};
 
-use arbitrage_engine::ArbitrageEngine;
+use arbitrage_engine::{route_input_mint, ArbitrageEngine};
 use config::Config;
+use solana_sdk::commitment_config::CommitmentConfig;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter("info,clean_arb_bot=debug")
-        .init();
+    // Initialize logging (LOG_FORMAT=json for log pipelines)
+    let log_format = LogFormat::from_env()?;
+    structured_log::init(log_format);
 
     info!("💰 Starting Clean Arbitrage Bot");
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
+    let args: Vec<String> = std::env::args().collect();
+
+    // create-keystore <path>: encrypt a wallet into a WALLET_KEYSTORE file, then exit
+    // (runs before config loading - it needs no RPC or trading settings)
+    if let Some(index) = args
+        .iter()
+        .position(|arg| arg == "create-keystore" || arg == "--create-keystore")
+    {
+        let Some(path) = args.get(index + 1) else {
+            anyhow::bail!("usage: create-keystore <path>");
+        };
+        let pubkey = SecureWallet::create_keystore(std::path::Path::new(path))?;
+        info!("🔐 Wallet keystore written to {} ({})", path, pubkey);
+        info!(
+            "   Set WALLET_KEYSTORE={} and remove WALLET_PRIVATE_KEY",
+            path
+        );
+        return Ok(());
+    }
+
     // Load configuration
     let config = Config::from_env()?;
 
+    // --validate-pools: check pool seed data on-chain, then exit without trading
+    if args.iter().any(|arg| arg == "--validate-pools") {
+        return validate_pools(&config).await;
+    }
+
+    // --replay <file>: rebuild and simulate a journaled opportunity, then exit
+    if let Some(index) = args.iter().position(|arg| arg == "--replay") {
+        let path = args
+            .get(index + 1)
+            .context("--replay requires a journal file path")?;
+        return replay_opportunity(&config, path).await;
+    }
+
+    // resolve-pool <short_id> <dex_type>: debug one short pool ID, then exit
+    if let Some(index) = args
+        .iter()
+        .position(|arg| arg == "resolve-pool" || arg == "--resolve-pool")
+    {
+        let (Some(short_id), Some(dex)) = (args.get(index + 1), args.get(index + 2)) else {
+            anyhow::bail!("usage: resolve-pool <short_id> <dex_type>");
+        };
+        return resolve_pool(&config, short_id, dex).await;
+    }
+
     info!("✅ Configuration loaded:");
-    info!("  • ShredStream service: {}", config.shredstream_url);
+    info!(
+        "  • ShredStream service: {}",
+        config.shredstream_urls.join(" → ")
+    );
     info!("  • Capital: {:.2} SOL", config.capital_sol);
     info!(
         "  • Max position: {:.2} SOL ({:.0}% of tradable capital)",
         config.max_position_size_sol,
         (config.max_position_size_sol / config.capital_sol) * 100.0
     );
+    if let Some(pct) = config.max_position_pct_of_balance {
+        info!(
+            "  • Max position scales to {:.0}% of tradeable balance",
+            pct * 100.0
+        );
+    }
+    if let Some(target) = config.daily_profit_target_sol {
+        info!(
+            "  • Profit target: {:.4} SOL ({}), bot stops when reached",
+            target,
+            if config.profit_target_reset_on_new_day {
+                "per day"
+            } else {
+                "lifetime total"
+            }
+        );
+    }
+    info!(
+        "  • JITO bundle rate: 1 bundle per {}ms",
+        config.jito_bundle_rate_ms
+    );
+    if let Some(min) = config.min_wallet_balance_sol {
+        info!("  • Min wallet balance: {:.4} SOL, bot stops below it", min);
+    }
+    if config.profit_currency == profit_currency::ProfitCurrency::Usd {
+        let usd_limit = |limit: Option<f64>| match limit {
+            Some(limit) => format!("${:.2}", limit),
+            None => "SOL limit".to_string(),
+        };
+        info!(
+            "  • Profit currency: USD (loss limit {}, profit target {}; ledger stays in SOL)",
+            usd_limit(config.daily_loss_limit_usd),
+            usd_limit(config.daily_profit_target_usd)
+        );
+    }
     info!("  • Profit requirement: Dynamic (costs + 0.2% margin calculated per opportunity)");
     info!("  • Min spread: DYNAMIC (calculated per opportunity: [total_costs + margin] / position_size)");
     info!(
         "  • Trading mode: {}",
         if config.paper_trading {
             "PAPER"
+        } else if config.shadow_mode {
+            "SHADOW (simulate only)"
         } else {
             "LIVE"
         }
     );
+    info!("  • Enabled DEXs: {}", config.dex_filter);
+    let anchors: Vec<&str> = config
+        .triangle_anchors
+        .iter()
+        .map(|anchor| anchor.symbol.as_str())
+        .collect();
+    info!(
+        "  • Triangle anchors: {} (cross-DEX pairs: SOL only)",
+        anchors.join(", ")
+    );
+    if config.max_concurrent_opportunities > 1 {
+        // Only paper picks run on worker tasks; live picks share the engine's executor
+        info!(
+            "  • Opportunities per scan: {} ({})",
+            config.max_concurrent_opportunities,
+            if config.paper_trading {
+                "concurrent"
+            } else {
+                "live: built and queued one after another"
+            }
+        );
+    }
+    info!(
+        "  • Log verbosity: {} (spreads above {}%)",
+        config.log_verbosity, config.log_spread_threshold_pct
+    );
+    info!("  • Log format: {}", log_format);
+    info!(
+        "  • RPC read retries: {} ({}ms backoff, doubling)",
+        config.rpc_read_retries, config.rpc_retry_backoff_ms
+    );
 
     // Create shutdown channel (Grok recommendation: explicit shutdown signaling)
     let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
@@ -90,14 +237,35 @@ async fn main() -> Result<()> {
     let mut engine = ArbitrageEngine::new(config.clone(), shutdown_rx, jito_tip_floor).await?;
     info!("✅ Arbitrage engine ready");
 
+    // Opt-in live status endpoint (reads snapshots the engine publishes each loop)
+    if let Some(port) = config.status_port {
+        let status = engine.status_board();
+        status.publish(engine.status_json().await);
+        let addr = std::net::SocketAddr::new(config.metrics_bind_addr, port);
+        status
+            .serve(addr)
+            .with_context(|| format!("Failed to start status server on {}", addr))?;
+    }
+
     // Populate pool registry if real trading is enabled
     if !config.paper_trading && config.enable_real_trading {
         if let Some(ref pool_registry) = engine.get_pool_registry() {
             info!("📋 Populating pool registry for real trading...");
             pool_population::populate_known_pools(pool_registry.clone())?;
+
+            let report = pool_registry.prefetch_all().await;
+            info!(
+                "🔥 Prefetched {} pools ({} ghost, {} unfetched)",
+                report.prefetched, report.ghost, report.unfetched
+            );
         }
     }
 
+    // Unix: SIGUSR1 pauses trading immediately, SIGUSR2 resumes
+    // (other platforms rely on the .emergency_stop file checked each scan)
+    #[cfg(unix)]
+    spawn_emergency_signal_handler(engine.emergency_flag())?;
+
     // Set up graceful shutdown handler (Grok recommendation: explicit error handling)
     let shutdown_handle = tokio::spawn(async move {
         match signal::ctrl_c().await {
@@ -153,6 +321,9 @@ async fn main() -> Result<()> {
     // Allow engine to finish cleanup before accessing stats
     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
+    // Submit (DRAIN_ON_SHUTDOWN) or abandon queued JITO bundles, releasing their reserved tips
+    engine.shutdown_submissions().await;
+
     // Final statistics (Grok recommendation: ensure thread-safe access post-cancellation)
     let stats = engine.get_stats();
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -169,11 +340,367 @@ async fn main() -> Result<()> {
         "  • Opportunities executed: {}",
         stats.opportunities_executed
     );
+    info!(
+        "  • Submitted but not landed: {}",
+        stats.opportunities_submitted_not_landed
+    );
     info!("  • Success rate: {:.1}%", stats.success_rate());
     info!("  • Total profit: {:.6} SOL", stats.total_profit_sol);
+    info!("  • Daily profit: {:.6} SOL", stats.daily_profit_sol);
+    if let Some(sol_usd) = engine.sol_usd() {
+        info!(
+            "  • Total profit: ${:.2} (daily ${:.2}) @ ${:.2}/SOL",
+            profit_currency::sol_to_usd(stats.total_profit_sol, sol_usd),
+            profit_currency::sol_to_usd(stats.daily_profit_sol, sol_usd),
+            sol_usd
+        );
+    }
     info!("  • Failed executions: {}", stats.failed_executions);
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     info!("👋 Arbitrage Bot shutdown complete");
 
     engine_result
 }
+
+/// Resolve and fetch every seeded pool, print a report and exit (`--validate-pools`)
+///
+/// # Errors
+/// Returns error (non-zero exit) if no RPC URL is configured or any critical
+/// (WSOL-paired) pool is a ghost or fails to resolve
+async fn validate_pools(config: &Config) -> Result<()> {
+    use pool_population::PoolCheck;
+    use std::sync::Arc;
+    use tracing::warn;
+
+    let rpc_url = config
+        .solana_rpc_url
+        .clone()
+        .context("SOLANA_RPC_URL is required for --validate-pools")?;
+    let pool_registry = Arc::new(PoolRegistry::new(Arc::new(
+        SolanaRpcClient::new(rpc_url)
+            .with_max_rps(config.rpc_max_rps)
+            .with_retry_policy(RpcRetryPolicy {
+                max_retries: config.rpc_read_retries,
+                base_backoff: std::time::Duration::from_millis(config.rpc_retry_backoff_ms),
+            })
+            .with_read_commitment(CommitmentConfig {
+                commitment: config.read_commitment,
+            }),
+    )));
+    pool_population::populate_known_pools(pool_registry.clone())?;
+
+    info!(
+        "🔍 Validating {} registered pools...",
+        pool_registry.pool_count()
+    );
+    let results = pool_population::validate_registered_pools(&pool_registry).await;
+
+    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    info!("📋 Pool Validation Report:");
+    for result in &results {
+        let critical = if result.is_critical() {
+            " [critical]"
+        } else {
+            ""
+        };
+        match &result.check {
+            PoolCheck::Ok(address) => info!(
+                "  ✅ {} ({:?}) → {}{}",
+                result.short_id, result.pool.dex_type, address, critical
+            ),
+            PoolCheck::Ghost(address, e) => warn!(
+                "  👻 {} ({:?}) → {} GHOST: {}{}",
+                result.short_id, result.pool.dex_type, address, e, critical
+            ),
+            PoolCheck::Unresolved(e) => warn!(
+                "  ❌ {} ({:?}) UNRESOLVED: {}{}",
+                result.short_id, result.pool.dex_type, e, critical
+            ),
+        }
+    }
+    let ok = results.iter().filter(|result| result.is_ok()).count();
+    let ghosts = results
+        .iter()
+        .filter(|result| matches!(result.check, PoolCheck::Ghost(..)))
+        .count();
+    info!(
+        "  • {} ok, {} ghost, {} unresolved",
+        ok,
+        ghosts,
+        results.len() - ok - ghosts
+    );
+    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    let missing = pool_population::missing_critical_pools(&results);
+    if missing > 0 {
+        anyhow::bail!(
+            "{} critical pool(s) missing - fix pool_population seed data",
+            missing
+        );
+    }
+    info!("✅ All critical pools resolve and exist on-chain");
+    Ok(())
+}
+
+/// Resolve one short pool ID, print the full address and account size, and exit (`resolve-pool`)
+///
+/// Runs the same registry resolution the engine uses, without starting the
+/// engine, so a single failing pool can be debugged in isolation.
+///
+/// # Arguments
+/// * `short_id` - Short pool ID as it appears in ShredStream events
+/// * `dex` - DEX type string, parsed with `DexType::from_dex_string`
+///
+/// # Errors
+/// Returns error (non-zero exit) if the DEX type is unknown, no RPC URL is
+/// configured, resolution fails, or the account is missing or too small to be a pool
+async fn resolve_pool(config: &Config, short_id: &str, dex: &str) -> Result<()> {
+    use std::sync::Arc;
+    use tracing::warn;
+
+    let dex_type =
+        DexType::from_dex_string(dex).with_context(|| format!("Unknown DEX type '{}'", dex))?;
+    let rpc_url = config
+        .solana_rpc_url
+        .clone()
+        .context("SOLANA_RPC_URL is required for resolve-pool")?;
+    let rpc_client = Arc::new(
+        SolanaRpcClient::new(rpc_url)
+            .with_max_rps(config.rpc_max_rps)
+            .with_retry_policy(RpcRetryPolicy {
+                max_retries: config.rpc_read_retries,
+                base_backoff: std::time::Duration::from_millis(config.rpc_retry_backoff_ms),
+            })
+            .with_read_commitment(CommitmentConfig {
+                commitment: config.read_commitment,
+            }),
+    );
+    let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+    pool_population::populate_known_pools(pool_registry.clone())?;
+
+    info!("🔍 Resolving {} ({:?})...", short_id, dex_type);
+    let address = match pool_registry
+        .resolve_pool_address(short_id, &dex_type)
+        .await
+    {
+        Ok(address) => address,
+        Err(e) => {
+            warn!("  ❌ {} ({:?}) UNRESOLVED: {:#}", short_id, dex_type, e);
+            return Err(e.context(format!("Failed to resolve pool {}", short_id)));
+        }
+    };
+    let (l1, l2, l3, l4, _, latency_ms) = pool_registry.get_resolution_stats();
+    let layer = if l1 > 0 {
+        "in-memory registry"
+    } else if l2 > 0 {
+        "ShredStream API"
+    } else if l3 > 0 {
+        "SQLite cache"
+    } else if l4 > 0 {
+        "on-chain RPC"
+    } else {
+        "unknown"
+    };
+    info!("  ✅ Resolved → {}", address);
+    info!("  • Layer: {} ({:.0}ms)", layer, latency_ms);
+
+    let data = rpc_client
+        .get_account_data(&address, None)
+        .with_context(|| format!("Resolved address {} is not on-chain", address))?;
+    let owner = rpc_client
+        .get_account_owner(&address)
+        .with_context(|| format!("Failed to fetch owner of {}", address))?;
+    info!("  • Account size: {} bytes", data.len());
+    info!("  • Owner: {}", owner);
+
+    if !pool_registry::is_valid_pool_data(Some(&data)) {
+        anyhow::bail!(
+            "Resolved address {} is a ghost: {} bytes is too small to be a pool",
+            address,
+            data.len()
+        );
+    }
+    info!("✅ {} resolves to a live pool", short_id);
+    Ok(())
+}
+
+/// Rebuild a journaled opportunity against current chain state and simulate it (`--replay`)
+///
+/// Uses the journaled swap legs and tip as-is, so the result shows whether the
+/// same transaction would succeed now (pools, reserves and ATAs as they are today).
+///
+/// # Errors
+/// Returns error (non-zero exit) if the journal file is unreadable, no RPC URL or
+/// wallet is configured, the transaction cannot be rebuilt, or the simulation fails
+async fn replay_opportunity(config: &Config, path: &str) -> Result<()> {
+    use opportunity_journal::OpportunityJournal;
+    use std::sync::Arc;
+    use tracing::warn;
+
+    let entry = OpportunityJournal::load(std::path::Path::new(path))?;
+
+    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    info!("📓 Replaying journaled opportunity: {}", path);
+    info!("  • Recorded: {}", entry.recorded_at);
+    info!("  • Path: {}", entry.opportunity.path.join(" → "));
+    info!("  • DEXs: {}", entry.opportunity.dexs.join(" → "));
+    info!(
+        "  • Estimated profit: {:.6} SOL ({:.2}%)",
+        entry.opportunity.estimated_profit_sol, entry.opportunity.profit_percentage
+    );
+    if !entry.pool_addresses.is_empty() {
+        info!("  • Pools: {}", entry.pool_addresses.join(", "));
+    }
+    if let Some(ref costs) = entry.costs {
+        info!(
+            "  • Costs: {} lamports total (tip {}, DEX fees {})",
+            costs.total_cost_lamports, costs.jito_tip_lamports, costs.dex_fee_lamports
+        );
+    }
+    info!(
+        "  • Original outcome: {}{}",
+        entry.outcome,
+        entry
+            .error
+            .as_deref()
+            .map(|e| format!(" - {}", e))
+            .unwrap_or_default()
+    );
+    if let Some(original) = entry.transaction()? {
+        info!(
+            "  • Original transaction: {} instructions, signature {}",
+            original.message.instructions.len(),
+            original.signatures.first().copied().unwrap_or_default()
+        );
+    }
+
+    if !(2..=3).contains(&entry.legs.len()) {
+        anyhow::bail!(
+            "Journaled attempt has {} swap legs - it stopped before a transaction was built",
+            entry.legs.len()
+        );
+    }
+
+    let rpc_url = config
+        .solana_rpc_url
+        .clone()
+        .context("SOLANA_RPC_URL is required for --replay")?;
+    let wallet = ArbitrageEngine::load_wallet_keypair(config)?
+        .context("WALLET_KEYSTORE or WALLET_PRIVATE_KEY is required for --replay")?;
+    let rpc_client = Arc::new(
+        SolanaRpcClient::new(rpc_url)
+            .with_max_rps(config.rpc_max_rps)
+            .with_retry_policy(RpcRetryPolicy {
+                max_retries: config.rpc_read_retries,
+                base_backoff: std::time::Duration::from_millis(config.rpc_retry_backoff_ms),
+            })
+            .with_read_commitment(CommitmentConfig {
+                commitment: config.read_commitment,
+            })
+            .with_sim_commitment(CommitmentConfig {
+                commitment: config.sim_commitment,
+            }),
+    );
+    let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+    pool_population::populate_known_pools(pool_registry.clone())?;
+    let mut executor = SwapExecutor::new(rpc_client.clone(), pool_registry, None)?;
+    executor.set_compute_unit_overrides(config.dex_compute_units.clone());
+
+    let tip_account = entry
+        .tip_account
+        .as_deref()
+        .context("Journaled attempt has no tip account")?
+        .parse()
+        .context("Invalid journaled tip account")?;
+    let tip_lamports = entry
+        .costs
+        .as_ref()
+        .map_or(0, |costs| costs.jito_tip_lamports);
+
+    // 2-leg attempts were built with a zero-amount placeholder third leg
+    let legs = &entry.legs;
+    let placeholder = SwapParams {
+        amount_in: 0,
+        minimum_amount_out: 0,
+        expected_amount_out: None,
+        swap_a_to_b: false,
+    };
+    let leg3 = match legs.get(2) {
+        Some(leg) => (&leg.dex_type, leg.pool_id.as_str(), &leg.params),
+        None => (&legs[0].dex_type, legs[0].pool_id.as_str(), &placeholder),
+    };
+    let input_mint = route_input_mint(&entry.opportunity.path)?;
+    let transaction = executor
+        .build_triangle_with_tip(
+            &input_mint,
+            [
+                (&legs[0].dex_type, legs[0].pool_id.as_str(), &legs[0].params),
+                (&legs[1].dex_type, legs[1].pool_id.as_str(), &legs[1].params),
+                leg3,
+            ],
+            &wallet,
+            tip_lamports,
+            &tip_account,
+        )
+        .await
+        .context("Failed to rebuild journaled transaction")?;
+
+    info!("🧪 Simulating rebuilt transaction against current chain state...");
+    let outcome = rpc_client.simulate_transaction_detailed(&transaction)?;
+    let units = outcome
+        .units_consumed
+        .map(|cu| cu.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    for log in &outcome.logs {
+        info!("    {}", log);
+    }
+    info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    if outcome.success {
+        info!("✅ Replay simulation passed ({} CU)", units);
+        Ok(())
+    } else {
+        let error = outcome.error.unwrap_or_else(|| "unknown error".to_string());
+        warn!("❌ Replay simulation failed ({} CU): {}", units, error);
+        anyhow::bail!("replay simulation failed: {}", error)
+    }
+}
+
+/// Spawn SIGUSR1/SIGUSR2 listener toggling the engine's emergency stop flag
+///
+/// Unix-only. `kill -USR1 <pid>` pauses trading (checked at the top of the loop and
+/// before every JITO submission), `kill -USR2 <pid>` resumes. The `.emergency_stop`
+/// file remains the cross-platform fallback.
+///
+/// # Errors
+/// Returns error if the signal handlers cannot be registered
+#[cfg(unix)]
+fn spawn_emergency_signal_handler(
+    flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> Result<()> {
+    use std::sync::atomic::Ordering;
+    use tokio::signal::unix::{signal, SignalKind};
+    use tracing::warn;
+
+    let mut stop = signal(SignalKind::user_defined1()).context("Failed to register SIGUSR1")?;
+    let mut resume = signal(SignalKind::user_defined2()).context("Failed to register SIGUSR2")?;
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                Some(()) = stop.recv() => {
+                    flag.store(true, Ordering::SeqCst);
+                    warn!("🚨 SIGUSR1 received - EMERGENCY STOP engaged");
+                }
+                Some(()) = resume.recv() => {
+                    flag.store(false, Ordering::SeqCst);
+                    info!("▶️ SIGUSR2 received - emergency stop cleared");
+                }
+                else => break,
+            }
+        }
+    });
+
+    info!("🛡️ Emergency stop signals armed (SIGUSR1 = stop, SIGUSR2 = resume)");
+    Ok(())
+}
@@ -8,14 +8,16 @@ use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
-use crate::pool_registry::PoolRegistry;
-use crate::rpc_client::SolanaRpcClient;
+use crate::errors::{ArbError, ArbResult};
+use crate::pool_registry::{ghost_pool_check_disabled, PoolRegistry};
+use crate::rpc_client::RpcApi;
+use crate::token_program::{associated_token_address, TokenProgram};
 use crate::types::SwapParams;
 
 /// Meteora DLMM swap instruction builder
 pub struct MeteoraSwapBuilder {
     /// RPC client for fetching pool state
-    rpc_client: Arc<SolanaRpcClient>,
+    rpc_client: Arc<dyn RpcApi>,
     /// Pool registry for address resolution
     pool_registry: Arc<PoolRegistry>,
     /// Meteora DLMM program ID
@@ -32,8 +34,20 @@ impl MeteoraSwapBuilder {
     /// Meteora DAMM V2 program ID on mainnet (Dynamic Automated Market Maker V2)
     pub const DAMM_V2_PROGRAM_ID: &'static str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
 
+    /// Estimated compute units for one Meteora swap
+    ///
+    /// DLMM swaps walk liquidity bins and crossing several can cost well over 100k CU;
+    /// the DAMM pools are plain AMM curves.
+    pub fn estimated_compute_units(&self, dex_type: &crate::types::DexType) -> u32 {
+        match dex_type {
+            crate::types::DexType::MeteoraDlmm => 150_000,
+            crate::types::DexType::MeteoraDammV1 => 90_000,
+            _ => 70_000,
+        }
+    }
+
     /// Create new Meteora swap builder
-    pub fn new(rpc_client: Arc<SolanaRpcClient>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
+    pub fn new(rpc_client: Arc<dyn RpcApi>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
         let program_id = Self::PROGRAM_ID
             .parse()
             .context("Failed to parse Meteora program ID")?;
@@ -64,7 +78,7 @@ impl MeteoraSwapBuilder {
         pool_short_id: &str,
         swap_params: &SwapParams,
         user_pubkey: &Pubkey,
-    ) -> Result<Instruction> {
+    ) -> ArbResult<Instruction> {
         debug!(
             "Building Meteora swap instruction for pool: {}",
             pool_short_id
@@ -94,10 +108,12 @@ impl MeteoraSwapBuilder {
                                 &crate::types::DexType::MeteoraDammV2,
                             )
                             .await
-                            .context(format!(
-                                "Failed to resolve pool address for {} (tried DLMM, V1, V2)",
-                                pool_short_id
-                            ))?
+                            .map_err(|e| {
+                                ArbError::PoolResolution(format!(
+                                    "{} (tried DLMM, V1, V2): {}",
+                                    pool_short_id, e
+                                ))
+                            })?
                     }
                 }
             }
@@ -110,38 +126,15 @@ impl MeteoraSwapBuilder {
 
         // GROK GHOST POOL SOLUTION - STEP 3: Early validation check (should be cached from arbitrage engine)
         // This is a safety fallback - normally pools are validated before execution
-
-        // MARKET CHAOS MODE - Skip ghost pool validation for speed
-        let skip_ghost_pool_check = std::env::var("SKIP_GHOST_POOL_CHECK")
-            .unwrap_or_else(|_| "false".to_string())
-            .to_lowercase()
-            == "true";
-
-        if !skip_ghost_pool_check
-            && self.pool_registry.is_pool_valid_cached(pool_short_id).await != Some(true)
-        {
-            // Rare case: validate on-demand if not cached
-            warn!(
-                "⚠️ Pool {} not in cache, validating on-demand",
-                pool_short_id
-            );
-            self.pool_registry
-                .validate_pools_batch(&[pool_short_id.to_string()])
-                .await?;
-
-            // Double-check after validation
-            if self.pool_registry.is_pool_valid_cached(pool_short_id).await != Some(true) {
-                return Err(anyhow::anyhow!(
-                    "⚠️ Ghost pool detected: {} (failed validation)",
-                    pool_short_id
-                ));
-            }
-        }
+        // (skipped in market chaos mode, SKIP_GHOST_POOL_CHECK=true)
+        let skip_ghost_pool_check = ghost_pool_check_disabled();
+        self.pool_registry.ensure_pool_valid(pool_short_id).await?;
 
         debug!("✅ Pool validated (cached), proceeding to ownership check");
 
         // CRITICAL: Validate this is actually a Meteora pool (DLMM, DAMM V1, or DAMM V2)
         // SKIP if SKIP_GHOST_POOL_CHECK is enabled (market chaos mode)
+        let mut is_dlmm = false;
         if !skip_ghost_pool_check {
             let account_owner =
                 self.rpc_client
@@ -159,13 +152,14 @@ impl MeteoraSwapBuilder {
                 && account_owner != damm_v1_program_id
                 && account_owner != damm_v2_program_id
             {
-                return Err(anyhow::anyhow!(
-                    "Pool {} not owned by Meteora DLMM, DAMM V1, or DAMM V2. Owner: {}, Expected: {} or {} or {}",
+                return Err(ArbError::GhostPool(format!(
+                    "{} not owned by Meteora DLMM, DAMM V1, or DAMM V2. Owner: {}, Expected: {} or {} or {}",
                     pool_address, account_owner, self.program_id, damm_v1_program_id, damm_v2_program_id
-                ));
+                )));
             }
 
-            let pool_type = if account_owner == self.program_id {
+            is_dlmm = account_owner == self.program_id;
+            let pool_type = if is_dlmm {
                 "DLMM"
             } else if account_owner == damm_v1_program_id {
                 "DAMM V1"
@@ -195,18 +189,34 @@ impl MeteoraSwapBuilder {
 
         debug!("✅ Got pool state ({} bytes)", pool_state.len());
 
+        // Disabled or not-yet-activated DLMM pairs revert every swap
+        if is_dlmm {
+            let activation = DlmmActivation::parse(&pool_state)?;
+            let current = if activation.by_slot && activation.activation_point > 0 {
+                self.rpc_client.get_slot()?
+            } else {
+                unix_timestamp()
+            };
+            if let Some(reason) = activation.inactive_reason(current) {
+                return Err(ArbError::PoolNotActive(format!(
+                    "Meteora DLMM {} {}",
+                    pool_short_id, reason
+                )));
+            }
+        }
+
         // Step 3: Determine token accounts
         let (user_token_in, user_token_out) = if swap_params.swap_a_to_b {
             // Swapping token A to token B
             (
-                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint),
-                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint),
+                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint)?,
+                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint)?,
             )
         } else {
             // Swapping token B to token A
             (
-                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint),
-                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint),
+                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint)?,
+                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint)?,
             )
         };
 
@@ -248,10 +258,10 @@ impl MeteoraSwapBuilder {
             // Create ATA instruction
             let create_ata_ix =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    user_pubkey,      // Payer
-                    user_pubkey,      // Owner of new account
-                    token_mint,       // Token mint
-                    &spl_token::id(), // Token program ID
+                    user_pubkey,                               // Payer
+                    user_pubkey,                               // Owner of new account
+                    token_mint,                                // Token mint
+                    &self.token_program_for(token_mint)?.id(), // Token program ID
                 );
 
             setup_instructions.push(create_ata_ix);
@@ -290,10 +300,10 @@ impl MeteoraSwapBuilder {
             // Create ATA instruction
             let create_ata_ix =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    user_pubkey,      // Payer
-                    user_pubkey,      // Owner of new account
-                    token_mint,       // Token mint
-                    &spl_token::id(), // Token program ID
+                    user_pubkey,                               // Payer
+                    user_pubkey,                               // Owner of new account
+                    token_mint,                                // Token mint
+                    &self.token_program_for(token_mint)?.id(), // Token program ID
                 );
 
             setup_instructions.push(create_ata_ix);
@@ -364,13 +374,26 @@ impl MeteoraSwapBuilder {
     /// Fetch pool state from blockchain
     fn fetch_pool_state(&self, pool_address: &Pubkey) -> Result<Vec<u8>> {
         self.rpc_client
-            .get_account_data(pool_address)
+            .get_account_data(pool_address, None)
             .context("Failed to fetch Meteora pool state")
     }
 
-    /// Get associated token account address for user
-    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
-        spl_associated_token_account::get_associated_token_address(wallet, mint)
+    /// Token program owning `mint` (SPL Token or Token-2022, cached per mint)
+    fn token_program_for(&self, mint: &Pubkey) -> Result<TokenProgram> {
+        // Native SOL placeholder mint has no mint account
+        if *mint == solana_sdk::system_program::ID {
+            return Ok(TokenProgram::Spl);
+        }
+        Ok(self.rpc_client.get_mint_token_program(mint)?.program)
+    }
+
+    /// Get associated token account address for user under the mint's token program
+    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+        Ok(associated_token_address(
+            wallet,
+            mint,
+            self.token_program_for(mint)?,
+        ))
     }
 
     /// Build the actual Meteora swap instruction
@@ -404,6 +427,7 @@ impl MeteoraSwapBuilder {
         // 9. [] token_x_program
         // 10. [] token_y_program
         // Note: bin_array_bitmap_extension and host_fee_in are optional, skipping
+        // token_x_program / token_y_program are per mint (SPL Token or Token-2022)
 
         // Determine which reserve is X and which is Y based on swap direction
         let (reserve_x, reserve_y) = if swap_params.swap_a_to_b {
@@ -421,6 +445,9 @@ impl MeteoraSwapBuilder {
             .parse()
             .expect("Valid event authority pubkey");
 
+        let token_x_program = self.token_program_for(token_mint_a)?.id();
+        let token_y_program = self.token_program_for(token_mint_b)?.id();
+
         let accounts = vec![
             solana_sdk::instruction::AccountMeta::new(*pool, false), // 0. lb_pair
             // Note: bin_array_bitmap_extension is optional, using None (skipping)
@@ -433,8 +460,8 @@ impl MeteoraSwapBuilder {
             solana_sdk::instruction::AccountMeta::new(oracle, false),                 // 7. oracle
             // Note: host_fee_in is optional, using None (skipping)
             solana_sdk::instruction::AccountMeta::new_readonly(*user, true), // 8. user (signer)
-            solana_sdk::instruction::AccountMeta::new_readonly(spl_token::id(), false), // 9. token_x_program
-            solana_sdk::instruction::AccountMeta::new_readonly(spl_token::id(), false), // 10. token_y_program
+            solana_sdk::instruction::AccountMeta::new_readonly(token_x_program, false), // 9. token_x_program
+            solana_sdk::instruction::AccountMeta::new_readonly(token_y_program, false), // 10. token_y_program
             solana_sdk::instruction::AccountMeta::new_readonly(event_authority, false), // 11. event_authority (CRITICAL!)
             solana_sdk::instruction::AccountMeta::new_readonly(self.program_id, false), // 12. program (CRITICAL!)
         ];
@@ -537,9 +564,103 @@ impl MeteoraSwapBuilder {
     }
 }
 
+/// DLMM `LbPair` fields that gate swapping (lb_clmm account layout)
+///
+/// - byte 82: status (0 = enabled, anything else = disabled by the pool admin)
+/// - byte 86: activation_type (0 = slot, 1 = unix timestamp)
+/// - bytes 816-824: activation_point (u64, 0 = active since creation)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DlmmActivation {
+    pub enabled: bool,
+    pub by_slot: bool,
+    pub activation_point: u64,
+}
+
+impl DlmmActivation {
+    const STATUS_OFFSET: usize = 82;
+    const ACTIVATION_TYPE_OFFSET: usize = 86;
+    const ACTIVATION_POINT_OFFSET: usize = 816;
+
+    /// Parse LbPair account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let point = data
+            .get(Self::ACTIVATION_POINT_OFFSET..Self::ACTIVATION_POINT_OFFSET + 8)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "DLMM pair state too short ({} bytes). Expected at least {} bytes.",
+                    data.len(),
+                    Self::ACTIVATION_POINT_OFFSET + 8
+                )
+            })?;
+
+        Ok(Self {
+            enabled: data[Self::STATUS_OFFSET] == 0,
+            by_slot: data[Self::ACTIVATION_TYPE_OFFSET] == 0,
+            activation_point: u64::from_le_bytes(point.try_into()?),
+        })
+    }
+
+    /// Why the pair can't be swapped right now, if it can't
+    ///
+    /// # Arguments
+    /// * `current` - Current slot or unix timestamp, matching the activation type
+    pub fn inactive_reason(&self, current: u64) -> Option<String> {
+        if !self.enabled {
+            return Some("is disabled".to_string());
+        }
+        if current < self.activation_point {
+            return Some(format!(
+                "activates at {} {} (now {})",
+                if self.by_slot { "slot" } else { "timestamp" },
+                self.activation_point,
+                current
+            ));
+        }
+        None
+    }
+}
+
+/// Seconds since the unix epoch (0 if the clock is before it)
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rpc_client::SolanaRpcClient;
+
+    #[test]
+    fn test_dlmm_activation_paused_and_active() {
+        // LbPair account bytes laid out as on-chain (904 bytes)
+        let mut data = vec![0u8; 904];
+        let active = DlmmActivation::parse(&data).unwrap();
+        assert_eq!(active.inactive_reason(300_000_000), None);
+
+        // Disabled by the admin
+        data[82] = 1;
+        let paused = DlmmActivation::parse(&data).unwrap();
+        assert_eq!(
+            paused.inactive_reason(300_000_000).as_deref(),
+            Some("is disabled")
+        );
+
+        // Enabled, but only tradable from a future timestamp
+        data[82] = 0;
+        data[86] = 1;
+        data[816..824].copy_from_slice(&1_800_000_000u64.to_le_bytes());
+        let pending = DlmmActivation::parse(&data).unwrap();
+        assert!(!pending.by_slot);
+        assert!(pending
+            .inactive_reason(1_700_000_000)
+            .unwrap()
+            .contains("timestamp 1800000000"));
+        assert_eq!(pending.inactive_reason(1_800_000_000), None);
+
+        assert!(DlmmActivation::parse(&data[..820]).is_err());
+    }
 
     #[test]
     fn test_slippage_calculation() {
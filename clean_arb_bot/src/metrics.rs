@@ -0,0 +1,494 @@
+// Prometheus metrics exporter
+//
+// Mirrors ArbitrageStats into lock-free counters so the engine loop never
+// blocks on a scrape, and records scan latency as a histogram. Per-stage
+// execution latencies (resolve -> validate -> build -> sign -> queue) are kept
+// in a rolling window and exported as p50/p95.
+// Opt-in via METRICS_PORT - serves Prometheus text format on GET /metrics
+
+use anyhow::Result;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::arbitrage_engine::ArbitrageStats;
+
+/// Scan duration histogram bucket upper bounds (seconds)
+/// Scans typically complete in 1-50ms; slow tail covers RPC stalls
+const SCAN_DURATION_BUCKETS_SECS: [f64; 10] =
+    [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Execution pipeline stages, in pipeline order (label values for stage metrics)
+pub const EXECUTION_STAGES: [&str; 6] = [
+    "pool_resolution",
+    "pool_validation",
+    "instruction_build",
+    "signing",
+    "queueing",
+    "total",
+];
+
+/// Samples kept per stage for percentile calculation
+const LATENCY_WINDOW_SIZE: usize = 500;
+
+/// Stage timings for one real execution attempt
+///
+/// Stages that were skipped (e.g. ghost-pool validation in chaos mode) stay None.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyBreakdown {
+    /// Resolving short pool IDs to on-chain addresses
+    pub pool_resolution: Option<Duration>,
+    /// Ghost-pool validation (cache check + batch RPC validation)
+    pub pool_validation: Option<Duration>,
+    /// Building swap + tip instructions
+    pub instruction_build: Option<Duration>,
+    /// Blockhash fetch, compute budget and transaction signing
+    pub signing: Option<Duration>,
+    /// Handing the bundle to the JITO submitter queue
+    pub queueing: Option<Duration>,
+}
+
+impl LatencyBreakdown {
+    /// Sum of all recorded stages
+    pub fn total(&self) -> Duration {
+        self.stage_durations()[..EXECUTION_STAGES.len() - 1]
+            .iter()
+            .flatten()
+            .sum()
+    }
+
+    /// Durations in `EXECUTION_STAGES` order (last entry = total)
+    fn stage_durations(&self) -> [Option<Duration>; EXECUTION_STAGES.len()] {
+        [
+            self.pool_resolution,
+            self.pool_validation,
+            self.instruction_build,
+            self.signing,
+            self.queueing,
+            None,
+        ]
+    }
+
+    /// One-line summary for logs (e.g. "resolve 1.2ms, build 8.0ms, ...")
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = EXECUTION_STAGES
+            .iter()
+            .zip(self.stage_durations())
+            .filter_map(|(stage, duration)| {
+                duration.map(|d| format!("{} {:.1}ms", stage, d.as_secs_f64() * 1000.0))
+            })
+            .collect();
+        parts.push(format!(
+            "total {:.1}ms",
+            self.total().as_secs_f64() * 1000.0
+        ));
+        parts.join(", ")
+    }
+}
+
+/// Rolling-window percentiles for one execution stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageLatency {
+    pub stage: &'static str,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub samples: usize,
+}
+
+/// Nearest-rank percentile of an ascending-sorted slice
+fn percentile(sorted: &[Duration], quantile: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Cumulative histogram state (Prometheus semantics)
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Count of observations <= each bucket bound (same order as SCAN_DURATION_BUCKETS_SECS)
+    bucket_counts: [u64; SCAN_DURATION_BUCKETS_SECS.len()],
+    sum_secs: f64,
+    count: u64,
+}
+
+/// Collects engine metrics and exports them in Prometheus text format
+#[derive(Debug, Default)]
+pub struct MetricsCollector {
+    opportunities_detected: AtomicU64,
+    opportunities_executed: AtomicU64,
+    opportunities_failed: AtomicU64,
+    consecutive_failures: AtomicU64,
+    /// f64 stored as bits (no AtomicF64 in std)
+    total_profit_sol_bits: AtomicU64,
+    account_cache_hits: AtomicU64,
+    account_cache_misses: AtomicU64,
+    scan_duration: Mutex<Histogram>,
+    last_scan_duration_us: AtomicU64,
+    /// Most recent samples per stage (same order as EXECUTION_STAGES)
+    stage_latencies: Mutex<[VecDeque<Duration>; EXECUTION_STAGES.len()]>,
+}
+
+impl MetricsCollector {
+    /// Create new metrics collector with all counters at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mirror the engine's stats into the exported gauges
+    pub fn update_from_stats(&self, stats: &ArbitrageStats) {
+        self.opportunities_detected
+            .store(stats.opportunities_detected, Ordering::Relaxed);
+        self.opportunities_executed
+            .store(stats.opportunities_executed, Ordering::Relaxed);
+        self.opportunities_failed
+            .store(stats.failed_executions, Ordering::Relaxed);
+        self.consecutive_failures
+            .store(stats.consecutive_failures, Ordering::Relaxed);
+        self.total_profit_sol_bits
+            .store(stats.total_profit_sol.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Mirror SolanaRpcClient account cache counters
+    pub fn update_account_cache(&self, hits: u64, misses: u64) {
+        self.account_cache_hits.store(hits, Ordering::Relaxed);
+        self.account_cache_misses.store(misses, Ordering::Relaxed);
+    }
+
+    /// Record one scan_for_opportunities duration
+    pub fn observe_scan_duration(&self, duration: Duration) {
+        self.last_scan_duration_us
+            .store(duration.as_micros() as u64, Ordering::Relaxed);
+        let secs = duration.as_secs_f64();
+        let mut histogram = match self.scan_duration.lock() {
+            Ok(histogram) => histogram,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        for (bound, count) in SCAN_DURATION_BUCKETS_SECS
+            .iter()
+            .zip(histogram.bucket_counts.iter_mut())
+        {
+            if secs <= *bound {
+                *count += 1;
+            }
+        }
+        histogram.sum_secs += secs;
+        histogram.count += 1;
+    }
+
+    /// Record the stage timings of one execution attempt
+    pub fn observe_latency_breakdown(&self, breakdown: &LatencyBreakdown) {
+        let mut durations = breakdown.stage_durations();
+        durations[EXECUTION_STAGES.len() - 1] = Some(breakdown.total());
+        let mut windows = match self.stage_latencies.lock() {
+            Ok(windows) => windows,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        for (window, duration) in windows.iter_mut().zip(durations) {
+            if let Some(duration) = duration {
+                if window.len() == LATENCY_WINDOW_SIZE {
+                    window.pop_front();
+                }
+                window.push_back(duration);
+            }
+        }
+    }
+
+    /// p50/p95 per stage over the rolling window (stages with no samples omitted)
+    pub fn latency_percentiles(&self) -> Vec<StageLatency> {
+        let windows = match self.stage_latencies.lock() {
+            Ok(windows) => windows,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        EXECUTION_STAGES
+            .iter()
+            .zip(windows.iter())
+            .filter(|(_, window)| !window.is_empty())
+            .map(|(stage, window)| {
+                let mut sorted: Vec<Duration> = window.iter().copied().collect();
+                sorted.sort_unstable();
+                StageLatency {
+                    stage: *stage,
+                    p50: percentile(&sorted, 0.5),
+                    p95: percentile(&sorted, 0.95),
+                    samples: sorted.len(),
+                }
+            })
+            .collect()
+    }
+
+    /// Most recent scan duration (zero before the first scan)
+    pub fn last_scan_duration(&self) -> Duration {
+        Duration::from_micros(self.last_scan_duration_us.load(Ordering::Relaxed))
+    }
+
+    /// Render all metrics in Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        let counters = [
+            (
+                "arb_opportunities_detected_total",
+                "Profitable opportunities detected",
+                self.opportunities_detected.load(Ordering::Relaxed),
+            ),
+            (
+                "arb_opportunities_executed_total",
+                "Opportunities executed successfully",
+                self.opportunities_executed.load(Ordering::Relaxed),
+            ),
+            (
+                "arb_opportunities_failed_total",
+                "Opportunities that failed to execute",
+                self.opportunities_failed.load(Ordering::Relaxed),
+            ),
+            (
+                "arb_rpc_account_cache_hits_total",
+                "Account data reads served from the RPC cache",
+                self.account_cache_hits.load(Ordering::Relaxed),
+            ),
+            (
+                "arb_rpc_account_cache_misses_total",
+                "Account data reads that went to RPC",
+                self.account_cache_misses.load(Ordering::Relaxed),
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP arb_consecutive_failures Current consecutive execution failures"
+        );
+        let _ = writeln!(out, "# TYPE arb_consecutive_failures gauge");
+        let _ = writeln!(
+            out,
+            "arb_consecutive_failures {}",
+            self.consecutive_failures.load(Ordering::Relaxed)
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP arb_total_profit_sol Cumulative realized profit in SOL"
+        );
+        let _ = writeln!(out, "# TYPE arb_total_profit_sol gauge");
+        let _ = writeln!(
+            out,
+            "arb_total_profit_sol {}",
+            f64::from_bits(self.total_profit_sol_bits.load(Ordering::Relaxed))
+        );
+
+        let histogram = match self.scan_duration.lock() {
+            Ok(histogram) => histogram,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let _ = writeln!(
+            out,
+            "# HELP arb_scan_duration_seconds Duration of opportunity scans"
+        );
+        let _ = writeln!(out, "# TYPE arb_scan_duration_seconds histogram");
+        for (bound, count) in SCAN_DURATION_BUCKETS_SECS
+            .iter()
+            .zip(histogram.bucket_counts.iter())
+        {
+            let _ = writeln!(
+                out,
+                "arb_scan_duration_seconds_bucket{{le=\"{}\"}} {}",
+                bound, count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "arb_scan_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            histogram.count
+        );
+        let _ = writeln!(out, "arb_scan_duration_seconds_sum {}", histogram.sum_secs);
+        let _ = writeln!(out, "arb_scan_duration_seconds_count {}", histogram.count);
+        drop(histogram);
+
+        let latencies = self.latency_percentiles();
+        if !latencies.is_empty() {
+            let _ = writeln!(
+                out,
+                "# HELP arb_execution_stage_latency_seconds Execution stage latency over the last {} attempts",
+                LATENCY_WINDOW_SIZE
+            );
+            let _ = writeln!(out, "# TYPE arb_execution_stage_latency_seconds summary");
+            for latency in &latencies {
+                for (quantile, value) in [("0.5", latency.p50), ("0.95", latency.p95)] {
+                    let _ = writeln!(
+                        out,
+                        "arb_execution_stage_latency_seconds{{stage=\"{}\",quantile=\"{}\"}} {}",
+                        latency.stage,
+                        quantile,
+                        value.as_secs_f64()
+                    );
+                }
+                let _ = writeln!(
+                    out,
+                    "arb_execution_stage_latency_seconds_count{{stage=\"{}\"}} {}",
+                    latency.stage, latency.samples
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Spawn HTTP server exposing `GET /metrics` on `addr`
+    ///
+    /// # Arguments
+    /// * `addr` - Socket address to bind (e.g. 127.0.0.1:9090)
+    ///
+    /// # Returns
+    /// Handle to the server task (runs until the runtime shuts down)
+    ///
+    /// # Errors
+    /// Returns error if the address cannot be bound
+    pub fn serve_prometheus(self: Arc<Self>, addr: SocketAddr) -> Result<JoinHandle<()>> {
+        let make_service = make_service_fn(move |_conn| {
+            let collector = self.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let collector = collector.clone();
+                    async move { Ok::<_, Infallible>(collector.handle_request(&req)) }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&addr)?.serve(make_service);
+        info!("📈 Prometheus metrics available at http://{}/metrics", addr);
+
+        Ok(tokio::spawn(async move {
+            if let Err(e) = server.await {
+                error!("❌ Metrics server failed: {}", e);
+            }
+        }))
+    }
+
+    /// Route a scrape request (only GET /metrics is served)
+    fn handle_request(&self, req: &Request<Body>) -> Response<Body> {
+        if req.method() == Method::GET && req.uri().path() == "/metrics" {
+            Response::builder()
+                .header("Content-Type", "text/plain; version=0.0.4")
+                .body(Body::from(self.render()))
+                .unwrap_or_else(|_| Response::new(Body::empty()))
+        } else {
+            let mut response = Response::new(Body::from("Not Found"));
+            *response.status_mut() = StatusCode::NOT_FOUND;
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_stats() {
+        let collector = MetricsCollector::new();
+        let stats = ArbitrageStats {
+            opportunities_detected: 12,
+            opportunities_executed: 5,
+            failed_executions: 3,
+            total_profit_sol: 0.25,
+            consecutive_failures: 2,
+            ..Default::default()
+        };
+        collector.update_from_stats(&stats);
+
+        let output = collector.render();
+        assert!(output.contains("arb_opportunities_detected_total 12"));
+        assert!(output.contains("arb_opportunities_executed_total 5"));
+        assert!(output.contains("arb_opportunities_failed_total 3"));
+        assert!(output.contains("arb_consecutive_failures 2"));
+        assert!(output.contains("arb_total_profit_sol 0.25"));
+    }
+
+    #[test]
+    fn test_render_includes_account_cache_counters() {
+        let collector = MetricsCollector::new();
+        collector.update_account_cache(40, 8);
+
+        let output = collector.render();
+        assert!(output.contains("arb_rpc_account_cache_hits_total 40"));
+        assert!(output.contains("arb_rpc_account_cache_misses_total 8"));
+    }
+
+    #[test]
+    fn test_scan_duration_histogram_is_cumulative() {
+        let collector = MetricsCollector::new();
+        collector.observe_scan_duration(Duration::from_millis(3));
+        collector.observe_scan_duration(Duration::from_millis(40));
+        collector.observe_scan_duration(Duration::from_secs(5));
+
+        let output = collector.render();
+        assert!(output.contains("arb_scan_duration_seconds_bucket{le=\"0.001\"} 0"));
+        assert!(output.contains("arb_scan_duration_seconds_bucket{le=\"0.005\"} 1"));
+        assert!(output.contains("arb_scan_duration_seconds_bucket{le=\"0.05\"} 2"));
+        assert!(output.contains("arb_scan_duration_seconds_bucket{le=\"2.5\"} 2"));
+        assert!(output.contains("arb_scan_duration_seconds_bucket{le=\"+Inf\"} 3"));
+        assert!(output.contains("arb_scan_duration_seconds_count 3"));
+    }
+
+    #[test]
+    fn test_latency_percentiles_over_rolling_window() {
+        let collector = MetricsCollector::new();
+        // No attempts yet - nothing exported
+        assert!(collector.latency_percentiles().is_empty());
+
+        for ms in 1..=100 {
+            collector.observe_latency_breakdown(&LatencyBreakdown {
+                pool_resolution: Some(Duration::from_millis(ms)),
+                instruction_build: Some(Duration::from_millis(10)),
+                ..Default::default()
+            });
+        }
+
+        let latencies = collector.latency_percentiles();
+        let stage = |name: &str| latencies.iter().find(|l| l.stage == name).cloned();
+        let resolution = stage("pool_resolution").unwrap();
+        assert_eq!(resolution.p50, Duration::from_millis(50));
+        assert_eq!(resolution.p95, Duration::from_millis(95));
+        assert_eq!(resolution.samples, 100);
+        assert_eq!(stage("total").unwrap().p95, Duration::from_millis(105));
+        // Skipped stages have no samples
+        assert!(stage("pool_validation").is_none());
+
+        // Window keeps only the most recent samples
+        for _ in 0..LATENCY_WINDOW_SIZE {
+            collector.observe_latency_breakdown(&LatencyBreakdown {
+                pool_resolution: Some(Duration::from_millis(2)),
+                ..Default::default()
+            });
+        }
+        let resolution = collector
+            .latency_percentiles()
+            .into_iter()
+            .find(|l| l.stage == "pool_resolution")
+            .unwrap();
+        assert_eq!(resolution.p95, Duration::from_millis(2));
+        assert_eq!(resolution.samples, LATENCY_WINDOW_SIZE);
+
+        let output = collector.render();
+        assert!(output.contains(
+            "arb_execution_stage_latency_seconds{stage=\"pool_resolution\",quantile=\"0.95\"} 0.002"
+        ));
+    }
+}
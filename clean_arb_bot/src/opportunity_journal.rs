@@ -0,0 +1,262 @@
+// Replayable opportunity journal
+//
+// Writes one JSON file per real execution attempt to OPPORTUNITY_JOURNAL_DIR:
+// the opportunity as executed (cross-DEX pairs in their two-leg triangle form),
+// the resolved pool addresses, the ArbitrageCosts, every swap leg's parameters,
+// the built transaction (base64) and the outcome. Attempts rejected before the
+// build simply have fewer fields filled in.
+//
+// `--replay <file>` rebuilds a journaled opportunity's transaction from the
+// recorded legs and simulates it against current chain state.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, transaction::Transaction};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{info, warn};
+
+use crate::cost_calculator::ArbitrageCosts;
+use crate::errors::ArbResult;
+use crate::triangle_arbitrage::TriangleOpportunity;
+use crate::types::{DexType, SwapParams};
+
+/// One swap leg exactly as handed to the swap executor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournaledLeg {
+    pub dex_type: DexType,
+    pub pool_id: String,
+    pub params: SwapParams,
+}
+
+/// Everything known about one execution attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub recorded_at: String, // RFC 3339, set when written
+    pub opportunity: TriangleOpportunity,
+    pub pool_addresses: Vec<String>, // Resolved full addresses, in leg order
+    pub costs: Option<ArbitrageCosts>,
+    pub legs: Vec<JournaledLeg>, // Empty if the attempt stopped before the build
+    pub tip_account: Option<String>,
+    pub transaction_base64: Option<String>, // Combined transaction (bincode)
+    pub outcome: String,                    // "ok" or "error"
+    pub error: Option<String>,              // Why the attempt failed
+}
+
+impl JournalEntry {
+    /// Start an entry for an attempt on `opportunity`
+    pub fn new(opportunity: &TriangleOpportunity) -> Self {
+        Self {
+            recorded_at: String::new(),
+            opportunity: opportunity.clone(),
+            pool_addresses: Vec::new(),
+            costs: None,
+            legs: Vec::new(),
+            tip_account: None,
+            transaction_base64: None,
+            outcome: "pending".to_string(),
+            error: None,
+        }
+    }
+
+    /// Record the legs and the transaction built from them
+    ///
+    /// # Arguments
+    /// * `legs` - Real swap legs in order (no 2-leg placeholder)
+    /// * `tip_account` - JITO tip account the transaction pays
+    /// * `transaction` - Signed combined transaction
+    pub fn record_build(
+        &mut self,
+        legs: &[(&DexType, &str, &SwapParams)],
+        tip_account: &Pubkey,
+        transaction: &Transaction,
+    ) {
+        self.legs = legs
+            .iter()
+            .map(|(dex_type, pool_id, params)| JournaledLeg {
+                dex_type: (*dex_type).clone(),
+                pool_id: pool_id.to_string(),
+                params: (*params).clone(),
+            })
+            .collect();
+        self.tip_account = Some(tip_account.to_string());
+        self.transaction_base64 = match bincode::serialize(transaction) {
+            Ok(bytes) => Some(BASE64.encode(bytes)),
+            Err(e) => {
+                warn!("⚠️ Failed to serialize journaled transaction: {}", e);
+                None
+            }
+        };
+    }
+
+    /// Record how the attempt ended
+    pub fn finish(&mut self, result: &ArbResult<()>) {
+        match result {
+            Ok(()) => self.outcome = "ok".to_string(),
+            Err(e) => {
+                self.outcome = "error".to_string();
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Decode the journaled transaction, if one was built
+    pub fn transaction(&self) -> Result<Option<Transaction>> {
+        self.transaction_base64
+            .as_deref()
+            .map(|encoded| {
+                let bytes = BASE64
+                    .decode(encoded)
+                    .context("Invalid base64 in journaled transaction")?;
+                bincode::deserialize(&bytes).context("Invalid journaled transaction bytes")
+            })
+            .transpose()
+    }
+}
+
+/// Directory of journal files, one per attempt
+pub struct OpportunityJournal {
+    dir: PathBuf,
+    sequence: AtomicU64, // Keeps same-millisecond file names unique
+}
+
+impl OpportunityJournal {
+    /// Use (creating if needed) `dir` for journal files
+    ///
+    /// # Errors
+    /// Returns error if the directory cannot be created
+    pub fn open(dir: &str) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create opportunity journal dir {}", dir))?;
+        info!("📓 Opportunity journal: {}", dir);
+        Ok(Self {
+            dir: PathBuf::from(dir),
+            sequence: AtomicU64::new(0),
+        })
+    }
+
+    /// Write `entry` to a new timestamped file
+    ///
+    /// Write failures are logged, never returned - journaling must not
+    /// interfere with execution.
+    ///
+    /// # Returns
+    /// Path of the written file, or None if the write failed
+    pub fn record(&self, entry: &mut JournalEntry) -> Option<PathBuf> {
+        let now = chrono::Utc::now();
+        entry.recorded_at = now.to_rfc3339();
+        let token = entry
+            .opportunity
+            .path
+            .get(1)
+            .map(|mint| mint.get(..8).unwrap_or(mint))
+            .unwrap_or("unknown");
+        let path = self.dir.join(format!(
+            "opportunity_{}_{}_{}.json",
+            now.format("%Y%m%dT%H%M%S%.3fZ"),
+            self.sequence.fetch_add(1, Ordering::Relaxed),
+            token
+        ));
+
+        let written = serde_json::to_vec_pretty(entry)
+            .context("Failed to serialize journal entry")
+            .and_then(|json| {
+                std::fs::write(&path, json)
+                    .with_context(|| format!("Failed to write {}", path.display()))
+            });
+        match written {
+            Ok(()) => Some(path),
+            Err(e) => {
+                warn!("⚠️ Opportunity journal write failed: {:#}", e);
+                None
+            }
+        }
+    }
+
+    /// Read a journal file back (for `--replay`)
+    pub fn load(path: &Path) -> Result<JournalEntry> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read journal file {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid journal file {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ArbError;
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    #[test]
+    fn test_entry_round_trips_through_file() {
+        let dir = std::env::temp_dir().join(format!("journal_test_{}", std::process::id()));
+        let journal = OpportunityJournal::open(dir.to_str().unwrap()).unwrap();
+
+        let opportunity = TriangleOpportunity {
+            path: vec![
+                "SOL".to_string(),
+                "MintAAAAAAAAA".to_string(),
+                "SOL".to_string(),
+            ],
+            dexs: vec![
+                "Raydium_AMM_V4_abcd1234".to_string(),
+                "Orca_Whirlpools_efgh5678".to_string(),
+            ],
+            prices: vec![1.0, 1.02],
+            estimated_profit_sol: 0.01,
+            profit_percentage: 2.0,
+            position_cap_lamports: Some(500_000_000),
+        };
+        let mut entry = JournalEntry::new(&opportunity);
+        entry.pool_addresses = vec![Pubkey::new_unique().to_string()];
+
+        let wallet = Keypair::new();
+        let tip_account = Pubkey::new_unique();
+        let transaction = Transaction::new_signed_with_payer(
+            &[solana_sdk::system_instruction::transfer(
+                &wallet.pubkey(),
+                &tip_account,
+                100_000,
+            )],
+            Some(&wallet.pubkey()),
+            &[&wallet],
+            solana_sdk::hash::Hash::default(),
+        );
+        let swap = SwapParams {
+            amount_in: 500_000_000,
+            minimum_amount_out: 990,
+            expected_amount_out: Some(1_000),
+            swap_a_to_b: true,
+        };
+        entry.record_build(
+            &[(&DexType::RaydiumAmmV4, "abcd1234", &swap)],
+            &tip_account,
+            &transaction,
+        );
+        entry.finish(&Err(ArbError::SimulationFailed("slippage".to_string())));
+
+        let path = journal.record(&mut entry).unwrap();
+        let loaded = OpportunityJournal::load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(loaded.outcome, "error");
+        assert!(loaded.error.unwrap().contains("slippage"));
+        assert_eq!(loaded.opportunity.dexs, opportunity.dexs);
+        assert_eq!(loaded.legs.len(), 1);
+        assert_eq!(loaded.legs[0].dex_type, DexType::RaydiumAmmV4);
+        assert_eq!(loaded.legs[0].params.minimum_amount_out, 990);
+        assert_eq!(loaded.tip_account, Some(tip_account.to_string()));
+        assert_eq!(
+            entry.transaction().unwrap().unwrap().signatures,
+            transaction.signatures
+        );
+        assert!(path
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .ends_with("_MintAAAA.json"));
+    }
+}
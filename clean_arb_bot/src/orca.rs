@@ -12,14 +12,16 @@ use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::errors::{ArbError, ArbResult};
 use crate::pool_registry::PoolRegistry;
-use crate::rpc_client::SolanaRpcClient;
+use crate::rpc_client::RpcApi;
+use crate::token_program::{associated_token_address, require_spl_token_program, TokenProgram};
 use crate::types::SwapParams;
 
 /// Orca swap instruction builder (supports Whirlpools + Legacy)
 pub struct OrcaSwapBuilder {
     /// RPC client for fetching pool state
-    rpc_client: Arc<SolanaRpcClient>,
+    rpc_client: Arc<dyn RpcApi>,
     /// Pool registry for address resolution
     pool_registry: Arc<PoolRegistry>,
     /// Orca Whirlpools program ID (default)
@@ -33,8 +35,19 @@ impl OrcaSwapBuilder {
     /// Orca Legacy program ID (older AMM)
     pub const LEGACY_PROGRAM_ID: &'static str = "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP";
 
+    /// Estimated compute units for one Orca swap
+    ///
+    /// Whirlpool swaps that cross initialized ticks load up to three tick arrays;
+    /// legacy pools are a single constant-product step.
+    pub fn estimated_compute_units(&self, dex_type: &crate::types::DexType) -> u32 {
+        match dex_type {
+            crate::types::DexType::OrcaWhirlpools => 130_000,
+            _ => 50_000,
+        }
+    }
+
     /// Create new Orca swap builder
-    pub fn new(rpc_client: Arc<SolanaRpcClient>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
+    pub fn new(rpc_client: Arc<dyn RpcApi>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
         let program_id = Self::WHIRLPOOLS_PROGRAM_ID
             .parse()
             .context("Failed to parse Orca Whirlpools program ID")?;
@@ -64,7 +77,7 @@ impl OrcaSwapBuilder {
         pool_short_id: &str,
         swap_params: &SwapParams,
         user_pubkey: &Pubkey,
-    ) -> Result<Instruction> {
+    ) -> ArbResult<Instruction> {
         debug!(
             "Building Orca Whirlpool swap instruction for pool: {}",
             pool_short_id
@@ -75,10 +88,7 @@ impl OrcaSwapBuilder {
             .pool_registry
             .resolve_pool_address(pool_short_id, &crate::types::DexType::OrcaWhirlpools)
             .await
-            .context(format!(
-                "Failed to resolve pool address for {}",
-                pool_short_id
-            ))?;
+            .map_err(|e| ArbError::PoolResolution(format!("{}: {}", pool_short_id, e)))?;
 
         debug!(
             "✅ Resolved pool {} to address: {}",
@@ -87,33 +97,8 @@ impl OrcaSwapBuilder {
 
         // GROK GHOST POOL SOLUTION - STEP 3: Early validation check (should be cached from arbitrage engine)
         // This is a safety fallback - normally pools are validated before execution
-
-        // MARKET CHAOS MODE - Skip ghost pool validation for speed
-        let skip_ghost_pool_check = std::env::var("SKIP_GHOST_POOL_CHECK")
-            .unwrap_or_else(|_| "false".to_string())
-            .to_lowercase()
-            == "true";
-
-        if !skip_ghost_pool_check
-            && self.pool_registry.is_pool_valid_cached(pool_short_id).await != Some(true)
-        {
-            // Rare case: validate on-demand if not cached
-            warn!(
-                "⚠️ Pool {} not in cache, validating on-demand",
-                pool_short_id
-            );
-            self.pool_registry
-                .validate_pools_batch(&[pool_short_id.to_string()])
-                .await?;
-
-            // Double-check after validation
-            if self.pool_registry.is_pool_valid_cached(pool_short_id).await != Some(true) {
-                return Err(anyhow::anyhow!(
-                    "⚠️ Ghost pool detected: {} (failed validation)",
-                    pool_short_id
-                ));
-            }
-        }
+        // (skipped in market chaos mode, SKIP_GHOST_POOL_CHECK=true)
+        self.pool_registry.ensure_pool_valid(pool_short_id).await?;
 
         debug!("✅ Pool validated (cached), proceeding to fetch state");
 
@@ -132,6 +117,23 @@ impl OrcaSwapBuilder {
 
         debug!("✅ Got pool state ({} bytes)", pool_state.len());
 
+        // Adaptive-fee pools can open for trading in the future (no oracle = always open)
+        let oracle_pda = Self::derive_oracle_pda(&pool_address, &self.program_id);
+        if let Some(Some(oracle_data)) = self
+            .rpc_client
+            .get_multiple_accounts(&[oracle_pda])?
+            .into_iter()
+            .next()
+        {
+            let trading = WhirlpoolTrading::parse(&oracle_data)?;
+            if let Some(reason) = trading.inactive_reason(unix_timestamp()) {
+                return Err(ArbError::PoolNotActive(format!(
+                    "Orca Whirlpool {} {}",
+                    pool_short_id, reason
+                )));
+            }
+        }
+
         // Step 3: Parse Orca Whirlpool state for critical data
         // Orca Whirlpool state structure (from Whirlpools program):
         // - bytes 0-8: discriminator
@@ -146,10 +148,11 @@ impl OrcaSwapBuilder {
         // - bytes 234-238: tick_current_index (i32) ← Need this for tick arrays
 
         if pool_state.len() < 238 {
-            return Err(anyhow::anyhow!(
-                "Pool state too short ({} bytes). Expected at least 238 bytes for Orca Whirlpool.",
+            return Err(ArbError::GhostPool(format!(
+                "{} state too short ({} bytes). Expected at least 238 bytes for Orca Whirlpool.",
+                pool_short_id,
                 pool_state.len()
-            ));
+            )));
         }
 
         // Extract critical data from pool state
@@ -179,18 +182,31 @@ impl OrcaSwapBuilder {
         debug!("Token Vault B: {}", token_vault_b);
         debug!("Oracle: {}", oracle);
 
+        // This swap instruction only takes the classic token program - reject
+        // Token-2022 pools here instead of reverting on-chain
+        let token_program = require_spl_token_program(
+            (
+                &pool_info.token_a_mint,
+                self.token_program_for(&pool_info.token_a_mint)?,
+            ),
+            (
+                &pool_info.token_b_mint,
+                self.token_program_for(&pool_info.token_b_mint)?,
+            ),
+        )?;
+
         // Step 4: Determine user token accounts
         let (user_token_in, user_token_out) = if swap_params.swap_a_to_b {
             // Swapping token A to token B
             (
-                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint),
-                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint),
+                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint)?,
+                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint)?,
             )
         } else {
             // Swapping token B to token A
             (
-                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint),
-                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint),
+                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint)?,
+                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint)?,
             )
         };
 
@@ -214,10 +230,10 @@ impl OrcaSwapBuilder {
 
             let create_ata_ix =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    user_pubkey,      // Payer
-                    user_pubkey,      // Owner of new account
-                    token_mint,       // Token mint
-                    &spl_token::id(), // Token program ID
+                    user_pubkey,                               // Payer
+                    user_pubkey,                               // Owner of new account
+                    token_mint,                                // Token mint
+                    &self.token_program_for(token_mint)?.id(), // Token program ID
                 );
 
             setup_instructions.push(create_ata_ix);
@@ -238,10 +254,10 @@ impl OrcaSwapBuilder {
 
             let create_ata_ix =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    user_pubkey,      // Payer
-                    user_pubkey,      // Owner of new account
-                    token_mint,       // Token mint
-                    &spl_token::id(), // Token program ID
+                    user_pubkey,                               // Payer
+                    user_pubkey,                               // Owner of new account
+                    token_mint,                                // Token mint
+                    &self.token_program_for(token_mint)?.id(), // Token program ID
                 );
 
             setup_instructions.push(create_ata_ix);
@@ -272,6 +288,7 @@ impl OrcaSwapBuilder {
             &token_vault_b,
             &oracle,
             &tick_arrays,
+            &token_program,
             swap_params,
         )?;
 
@@ -321,13 +338,26 @@ impl OrcaSwapBuilder {
     /// Fetch pool state from blockchain
     fn fetch_pool_state(&self, pool_address: &Pubkey) -> Result<Vec<u8>> {
         self.rpc_client
-            .get_account_data(pool_address)
+            .get_account_data(pool_address, None)
             .context("Failed to fetch Orca Whirlpool state")
     }
 
-    /// Get associated token account address for user
-    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
-        spl_associated_token_account::get_associated_token_address(wallet, mint)
+    /// Token program owning `mint` (SPL Token or Token-2022, cached per mint)
+    fn token_program_for(&self, mint: &Pubkey) -> Result<TokenProgram> {
+        // Native SOL placeholder mint has no mint account
+        if *mint == solana_sdk::system_program::ID {
+            return Ok(TokenProgram::Spl);
+        }
+        Ok(self.rpc_client.get_mint_token_program(mint)?.program)
+    }
+
+    /// Get associated token account address for user under the mint's token program
+    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+        Ok(associated_token_address(
+            wallet,
+            mint,
+            self.token_program_for(mint)?,
+        ))
     }
 
     /// Derive tick array addresses for Orca Whirlpool swap
@@ -380,7 +410,7 @@ impl OrcaSwapBuilder {
 
     /// Derive a single tick array PDA
     ///
-    /// PDA derivation: ["tick_array", whirlpool, start_tick_index (i32 bytes)]
+    /// PDA derivation: ["tick_array", whirlpool, start_tick_index (decimal string)]
     fn derive_tick_array_pda(
         whirlpool: &Pubkey,
         start_tick_index: i32,
@@ -390,13 +420,20 @@ impl OrcaSwapBuilder {
             &[
                 b"tick_array",
                 whirlpool.as_ref(),
-                &start_tick_index.to_le_bytes(),
+                start_tick_index.to_string().as_bytes(),
             ],
             program_id,
         );
         pda
     }
 
+    /// Derive a whirlpool's adaptive-fee Oracle PDA: ["oracle", whirlpool]
+    fn derive_oracle_pda(whirlpool: &Pubkey, program_id: &Pubkey) -> Pubkey {
+        let (pda, _bump) =
+            Pubkey::find_program_address(&[b"oracle", whirlpool.as_ref()], program_id);
+        pda
+    }
+
     /// Build the actual Orca Whirlpool swap instruction
     ///
     /// Reference: Orca Whirlpools program instruction structure
@@ -413,6 +450,7 @@ impl OrcaSwapBuilder {
         token_vault_b: &Pubkey,
         oracle: &Pubkey,
         tick_arrays: &[Pubkey; 3],
+        token_program: &Pubkey,
         swap_params: &SwapParams,
     ) -> Result<Instruction> {
         // Orca Whirlpool swap instruction accounts
@@ -433,7 +471,7 @@ impl OrcaSwapBuilder {
         // 10. [readonly] oracle (Price oracle account)
 
         let accounts = vec![
-            solana_sdk::instruction::AccountMeta::new_readonly(spl_token::id(), false),
+            solana_sdk::instruction::AccountMeta::new_readonly(*token_program, false),
             solana_sdk::instruction::AccountMeta::new_readonly(*token_authority, true),
             solana_sdk::instruction::AccountMeta::new(*whirlpool, false),
             solana_sdk::instruction::AccountMeta::new(*token_owner_account_a, false),
@@ -511,11 +549,19 @@ impl OrcaSwapBuilder {
     }
 
     /// Estimate output amount for a swap (useful for slippage calculation)
+    ///
+    /// Reads the whirlpool account (liquidity, sqrt_price, tick spacing, fee rate)
+    /// plus the 3 tick arrays the swap would traverse, then runs the concentrated
+    /// liquidity swap math including liquidity changes at initialized ticks.
+    ///
+    /// # Returns
+    /// Expected output amount (after LP fee), or error if the swap would run past
+    /// the 3 tick arrays a single Whirlpool swap instruction can touch
     pub fn estimate_swap_output(
         &self,
         pool_short_id: &str,
         amount_in: u64,
-        _swap_a_to_b: bool,
+        swap_a_to_b: bool,
     ) -> Result<u64> {
         debug!("Estimating swap output for Orca pool: {}", pool_short_id);
 
@@ -525,21 +571,74 @@ impl OrcaSwapBuilder {
             .get_pool(pool_short_id)
             .ok_or_else(|| anyhow::anyhow!("Pool {} not found", pool_short_id))?;
 
-        // Fetch pool state
-        let _pool_state = self.fetch_pool_state(&pool_info.full_address)?;
+        // Fetch and parse whirlpool state
+        let pool_state = self.fetch_pool_state(&pool_info.full_address)?;
+        let whirlpool = WhirlpoolState::parse(&pool_state)?;
+
+        // Fetch the 3 tick arrays in swap direction (current + next 2)
+        let start_indexes = Self::swap_tick_array_start_indexes(&whirlpool, swap_a_to_b);
+        let tick_array_addresses: Vec<Pubkey> = start_indexes
+            .iter()
+            .map(|start| {
+                Self::derive_tick_array_pda(&pool_info.full_address, *start, &self.program_id)
+            })
+            .collect();
+        let tick_array_data = self
+            .rpc_client
+            .get_multiple_accounts(&tick_array_addresses)
+            .context("Failed to fetch Orca tick arrays")?;
+
+        // Uninitialized tick arrays have no liquidity changes - skip them
+        let mut initialized_ticks = Vec::new();
+        for data in tick_array_data.iter().flatten() {
+            initialized_ticks.extend(parse_tick_array(data, whirlpool.tick_spacing)?);
+        }
 
-        // Parse pool state to get current sqrt_price and liquidity
-        // This would use Orca's concentrated liquidity math
+        // Swap cannot move past the far edge of the last tick array
+        let ticks_in_array = whirlpool.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        let boundary_tick = if swap_a_to_b {
+            start_indexes[2]
+        } else {
+            start_indexes[2] + ticks_in_array
+        };
 
-        // For now, return a conservative estimate
-        let estimated_output = amount_in * 99 / 100; // Assume 1% slippage
+        let estimated_output = compute_swap_output(
+            &whirlpool,
+            &initialized_ticks,
+            boundary_tick,
+            amount_in,
+            swap_a_to_b,
+        )?;
 
-        warn!("⚠️ Using conservative estimate (1% slippage)");
-        warn!("   Production should use Orca's concentrated liquidity curve calculation");
+        debug!(
+            "Orca estimate: {} in → {} out ({} initialized ticks in range)",
+            amount_in,
+            estimated_output,
+            initialized_ticks.len()
+        );
 
         Ok(estimated_output)
     }
 
+    /// Start tick indexes of the 3 tick arrays a swap traverses, in swap order
+    ///
+    /// A→B moves price down (current, lower, lower), B→A moves price up
+    fn swap_tick_array_start_indexes(whirlpool: &WhirlpoolState, swap_a_to_b: bool) -> [i32; 3] {
+        let ticks_in_array = whirlpool.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        let current_start =
+            whirlpool.tick_current_index.div_euclid(ticks_in_array) * ticks_in_array;
+        let step = if swap_a_to_b {
+            -ticks_in_array
+        } else {
+            ticks_in_array
+        };
+        [
+            current_start,
+            current_start + step,
+            current_start + 2 * step,
+        ]
+    }
+
     /// Calculate slippage percentage
     pub fn calculate_slippage(expected: u64, minimum: u64) -> f64 {
         if expected == 0 {
@@ -575,9 +674,274 @@ impl OrcaSwapBuilder {
     }
 }
 
+/// Orca Whirlpools constant: each tick array covers 88 ticks
+const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Whirlpool fee rate denominator (fee_rate is in hundredths of a basis point)
+const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// Size of a single Tick entry inside a TickArray account
+/// (initialized: 1 + liquidity_net: 16 + liquidity_gross: 16 + fee growths: 32 + rewards: 48)
+const TICK_SIZE: usize = 113;
+
+/// Offset of the ticks array inside a TickArray account (discriminator + start_tick_index)
+const TICK_ARRAY_TICKS_OFFSET: usize = 12;
+
+/// Whirlpool state fields needed for swap output estimation
+///
+/// Offsets follow the on-chain Whirlpool account layout (653 bytes):
+/// - bytes 0-8: discriminator
+/// - bytes 8-40: whirlpools_config (pubkey)
+/// - byte 40: whirlpool_bump
+/// - bytes 41-43: tick_spacing (u16)
+/// - bytes 43-45: tick_spacing_seed
+/// - bytes 45-47: fee_rate (u16, hundredths of a bip)
+/// - bytes 47-49: protocol_fee_rate (u16)
+/// - bytes 49-65: liquidity (u128)
+/// - bytes 65-81: sqrt_price (u128, Q64.64)
+/// - bytes 81-85: tick_current_index (i32)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhirlpoolState {
+    pub tick_spacing: u16,
+    pub fee_rate: u16,
+    pub liquidity: u128,
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+}
+
+impl WhirlpoolState {
+    /// Minimum account length covering all fields we read
+    const MIN_LEN: usize = 85;
+
+    /// Parse whirlpool account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < Self::MIN_LEN {
+            return Err(anyhow::anyhow!(
+                "Whirlpool state too short ({} bytes). Expected at least {} bytes.",
+                data.len(),
+                Self::MIN_LEN
+            ));
+        }
+
+        let tick_spacing = u16::from_le_bytes([data[41], data[42]]);
+        if tick_spacing == 0 {
+            return Err(anyhow::anyhow!("Invalid whirlpool tick spacing: 0"));
+        }
+
+        Ok(Self {
+            tick_spacing,
+            fee_rate: u16::from_le_bytes([data[45], data[46]]),
+            liquidity: u128::from_le_bytes(data[49..65].try_into()?),
+            sqrt_price: u128::from_le_bytes(data[65..81].try_into()?),
+            tick_current_index: i32::from_le_bytes(data[81..85].try_into()?),
+        })
+    }
+}
+
+/// Adaptive-fee `Oracle` field that gates trading (Whirlpools account layout)
+///
+/// - bytes 0-8: discriminator
+/// - bytes 8-40: whirlpool (pubkey)
+/// - bytes 40-48: trade_enable_timestamp (u64 unix seconds, 0 = always open)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WhirlpoolTrading {
+    pub trade_enable_timestamp: u64,
+}
+
+impl WhirlpoolTrading {
+    const TRADE_ENABLE_TIMESTAMP_OFFSET: usize = 40;
+
+    /// Parse Oracle account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let bytes = data
+            .get(Self::TRADE_ENABLE_TIMESTAMP_OFFSET..Self::TRADE_ENABLE_TIMESTAMP_OFFSET + 8)
+            .ok_or_else(|| anyhow::anyhow!("Whirlpool oracle too short ({} bytes)", data.len()))?;
+        Ok(Self {
+            trade_enable_timestamp: u64::from_le_bytes(bytes.try_into()?),
+        })
+    }
+
+    /// Why the pool can't be swapped at `unix_timestamp`, if it can't
+    pub fn inactive_reason(&self, unix_timestamp: u64) -> Option<String> {
+        (unix_timestamp < self.trade_enable_timestamp).then(|| {
+            format!(
+                "opens for trading at timestamp {} (now {})",
+                self.trade_enable_timestamp, unix_timestamp
+            )
+        })
+    }
+}
+
+/// Seconds since the unix epoch (0 if the clock is before it)
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Parse initialized ticks from a TickArray account
+///
+/// # Returns
+/// Vec of (tick_index, liquidity_net) for every initialized tick in the array
+pub fn parse_tick_array(data: &[u8], tick_spacing: u16) -> Result<Vec<(i32, i128)>> {
+    let expected_len = TICK_ARRAY_TICKS_OFFSET + TICK_ARRAY_SIZE as usize * TICK_SIZE;
+    if data.len() < expected_len {
+        return Err(anyhow::anyhow!(
+            "Tick array too short ({} bytes). Expected at least {} bytes.",
+            data.len(),
+            expected_len
+        ));
+    }
+
+    let start_tick_index = i32::from_le_bytes(data[8..12].try_into()?);
+
+    let mut ticks = Vec::new();
+    for i in 0..TICK_ARRAY_SIZE as usize {
+        let offset = TICK_ARRAY_TICKS_OFFSET + i * TICK_SIZE;
+        if data[offset] == 0 {
+            continue; // Not initialized
+        }
+        let liquidity_net = i128::from_le_bytes(data[offset + 1..offset + 17].try_into()?);
+        let tick_index = start_tick_index + i as i32 * tick_spacing as i32;
+        ticks.push((tick_index, liquidity_net));
+    }
+
+    Ok(ticks)
+}
+
+/// Convert a tick index to its sqrt price (sqrt(1.0001^tick), not Q64.64 scaled)
+fn sqrt_price_at_tick(tick: i32) -> f64 {
+    1.0001_f64.powf(tick as f64 / 2.0)
+}
+
+/// Concentrated liquidity swap math (exact input)
+///
+/// Walks from the current price toward `boundary_tick`, consuming input until it
+/// runs out. Each initialized tick crossed adjusts active liquidity by its
+/// liquidity_net (subtracted when moving down, added when moving up).
+///
+/// # Arguments
+/// * `whirlpool` - Parsed whirlpool state
+/// * `initialized_ticks` - (tick_index, liquidity_net) for initialized ticks in range
+/// * `boundary_tick` - Furthest tick the swap may reach (end of loaded tick arrays)
+/// * `amount_in` - Input amount (before fee)
+/// * `swap_a_to_b` - true = A→B (price decreases), false = B→A (price increases)
+///
+/// # Returns
+/// Output amount, or error if input cannot be filled before `boundary_tick`
+pub fn compute_swap_output(
+    whirlpool: &WhirlpoolState,
+    initialized_ticks: &[(i32, i128)],
+    boundary_tick: i32,
+    amount_in: u64,
+    swap_a_to_b: bool,
+) -> Result<u64> {
+    const Q64: f64 = 18_446_744_073_709_551_616.0; // 2^64
+
+    let fee_rate = whirlpool.fee_rate as u64;
+    if fee_rate >= FEE_RATE_DENOMINATOR {
+        return Err(anyhow::anyhow!("Invalid whirlpool fee rate: {}", fee_rate));
+    }
+
+    let mut remaining =
+        amount_in as f64 * (FEE_RATE_DENOMINATOR - fee_rate) as f64 / FEE_RATE_DENOMINATOR as f64;
+    let mut sqrt_price = whirlpool.sqrt_price as f64 / Q64;
+    let mut liquidity = whirlpool.liquidity as f64;
+    let mut amount_out = 0.0;
+
+    // Ticks in the order the swap will cross them
+    let mut ticks: Vec<(i32, i128)> = initialized_ticks
+        .iter()
+        .copied()
+        .filter(|(tick, _)| {
+            if swap_a_to_b {
+                *tick <= whirlpool.tick_current_index && *tick > boundary_tick
+            } else {
+                *tick > whirlpool.tick_current_index && *tick < boundary_tick
+            }
+        })
+        .collect();
+    if swap_a_to_b {
+        ticks.sort_by(|a, b| b.0.cmp(&a.0));
+    } else {
+        ticks.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    // Final step stops at the boundary with no liquidity change
+    let steps = ticks
+        .into_iter()
+        .map(|(tick, net)| (tick, Some(net)))
+        .chain(std::iter::once((boundary_tick, None)));
+
+    for (tick, liquidity_net) in steps {
+        let target_sqrt_price = sqrt_price_at_tick(tick);
+
+        if swap_a_to_b {
+            // Input is token A: Δx = L * (1/√P_target - 1/√P)
+            let max_in = if liquidity > 0.0 {
+                liquidity * (1.0 / target_sqrt_price - 1.0 / sqrt_price)
+            } else {
+                0.0
+            };
+
+            if remaining < max_in {
+                let next_sqrt_price = liquidity * sqrt_price / (liquidity + remaining * sqrt_price);
+                amount_out += liquidity * (sqrt_price - next_sqrt_price);
+                remaining = 0.0;
+                break;
+            }
+
+            amount_out += liquidity * (sqrt_price - target_sqrt_price);
+            remaining -= max_in;
+            sqrt_price = target_sqrt_price;
+
+            if let Some(net) = liquidity_net {
+                liquidity -= net as f64;
+            }
+        } else {
+            // Input is token B: Δy = L * (√P_target - √P)
+            let max_in = liquidity * (target_sqrt_price - sqrt_price);
+
+            if remaining < max_in {
+                let next_sqrt_price = sqrt_price + remaining / liquidity;
+                amount_out += liquidity * (1.0 / sqrt_price - 1.0 / next_sqrt_price);
+                remaining = 0.0;
+                break;
+            }
+
+            amount_out += liquidity * (1.0 / sqrt_price - 1.0 / target_sqrt_price);
+            remaining -= max_in;
+            sqrt_price = target_sqrt_price;
+
+            if let Some(net) = liquidity_net {
+                liquidity += net as f64;
+            }
+        }
+
+        if liquidity < 0.0 {
+            return Err(anyhow::anyhow!(
+                "Negative liquidity after crossing tick {} - corrupt tick array data",
+                tick
+            ));
+        }
+    }
+
+    if remaining > 0.0 {
+        return Err(anyhow::anyhow!(
+            "Swap of {} exceeds liquidity in the 3 loaded tick arrays (stopped at tick {})",
+            amount_in,
+            boundary_tick
+        ));
+    }
+
+    Ok(amount_out.max(0.0) as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::captured_fixtures::{pubkey, CapturedFixture};
+    use crate::rpc_client::SolanaRpcClient;
 
     #[test]
     fn test_slippage_calculation() {
@@ -620,4 +984,201 @@ mod tests {
         };
         assert!(builder.validate_swap_params(&zero_out).is_err());
     }
+
+    /// Whirlpool with price 1.0 (tick 0), 1e12 liquidity, 0.3% fee, tick spacing 64
+    fn sample_whirlpool() -> WhirlpoolState {
+        WhirlpoolState {
+            tick_spacing: 64,
+            fee_rate: 3000,
+            liquidity: 1_000_000_000_000,
+            sqrt_price: 1u128 << 64,
+            tick_current_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_whirlpool_state_parse() {
+        // Whirlpool account bytes laid out as on-chain (653 bytes)
+        let mut data = vec![0u8; 653];
+        data[41..43].copy_from_slice(&64u16.to_le_bytes());
+        data[45..47].copy_from_slice(&3000u16.to_le_bytes());
+        data[49..65].copy_from_slice(&1_000_000_000_000u128.to_le_bytes());
+        data[65..81].copy_from_slice(&(1u128 << 64).to_le_bytes());
+        data[81..85].copy_from_slice(&0i32.to_le_bytes());
+
+        assert_eq!(WhirlpoolState::parse(&data).unwrap(), sample_whirlpool());
+        assert!(WhirlpoolState::parse(&data[..80]).is_err());
+    }
+
+    #[test]
+    fn test_whirlpool_trading_paused_and_active() {
+        // Oracle account: discriminator, whirlpool, trade_enable_timestamp, ...
+        let mut data = vec![0u8; 254];
+        let always_open = WhirlpoolTrading::parse(&data).unwrap();
+        assert_eq!(always_open.inactive_reason(1_700_000_000), None);
+
+        data[40..48].copy_from_slice(&1_800_000_000u64.to_le_bytes());
+        let pending = WhirlpoolTrading::parse(&data).unwrap();
+        assert!(pending
+            .inactive_reason(1_700_000_000)
+            .unwrap()
+            .contains("timestamp 1800000000"));
+        assert_eq!(pending.inactive_reason(1_800_000_000), None);
+
+        assert!(WhirlpoolTrading::parse(&data[..44]).is_err());
+    }
+
+    #[test]
+    fn test_parse_tick_array() {
+        let mut data =
+            vec![0u8; TICK_ARRAY_TICKS_OFFSET + TICK_ARRAY_SIZE as usize * TICK_SIZE + 32];
+        data[8..12].copy_from_slice(&(-5632i32).to_le_bytes()); // -64 * 88
+
+        // Initialize tick #3 with liquidity_net = -500
+        let offset = TICK_ARRAY_TICKS_OFFSET + 3 * TICK_SIZE;
+        data[offset] = 1;
+        data[offset + 1..offset + 17].copy_from_slice(&(-500i128).to_le_bytes());
+
+        let ticks = parse_tick_array(&data, 64).unwrap();
+        assert_eq!(ticks, vec![(-5632 + 3 * 64, -500)]);
+    }
+
+    #[test]
+    fn test_swap_output_within_single_tick_range() {
+        let whirlpool = sample_whirlpool();
+
+        // Closed form at price 1.0: out = L * Δx / (L + Δx), Δx after 0.3% fee
+        let amount_in = 1_000_000u64;
+        let dx = 997_000.0;
+        let l = 1_000_000_000_000.0;
+        let expected_a_to_b = l * dx / (l + dx);
+
+        let out = compute_swap_output(&whirlpool, &[], -5632 * 3, amount_in, true).unwrap();
+        assert!((out as f64 - expected_a_to_b).abs() / expected_a_to_b < 0.001);
+
+        // Symmetric pool at price 1.0 gives the same output in the other direction
+        let out = compute_swap_output(&whirlpool, &[], 5632 * 3, amount_in, false).unwrap();
+        assert!((out as f64 - expected_a_to_b).abs() / expected_a_to_b < 0.001);
+    }
+
+    #[test]
+    fn test_swap_output_crosses_initialized_tick() {
+        let whirlpool = sample_whirlpool();
+        let amount_in = 20_000_000_000u64; // Large enough to move well past tick -64
+
+        let no_cross = compute_swap_output(&whirlpool, &[], -16896, amount_in, true).unwrap();
+
+        // Half the liquidity exits at tick -64 (liquidity_net is subtracted moving down)
+        let ticks = [(-64, 500_000_000_000i128)];
+        let with_cross = compute_swap_output(&whirlpool, &ticks, -16896, amount_in, true).unwrap();
+
+        assert!(with_cross < no_cross);
+
+        // Same swap, crossing never reached for a tiny amount
+        let small_no_cross = compute_swap_output(&whirlpool, &[], -16896, 1_000, true).unwrap();
+        let small_cross = compute_swap_output(&whirlpool, &ticks, -16896, 1_000, true).unwrap();
+        assert_eq!(small_no_cross, small_cross);
+    }
+
+    #[test]
+    fn test_swap_output_exceeds_tick_arrays() {
+        let whirlpool = sample_whirlpool();
+
+        // All liquidity exits at tick -64 - nothing left to fill the rest of the swap
+        let ticks = [(-64, 1_000_000_000_000i128)];
+        let result = compute_swap_output(&whirlpool, &ticks, -16896, 20_000_000_000, true);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[ignore = "needs fixtures/orca_whirlpool_swap.json (scripts/capture_fixture.py --swap)"]
+    fn test_swap_output_matches_captured_whirlpool_swap() {
+        let fixture = CapturedFixture::load("orca_whirlpool_swap").unwrap();
+        let swap = fixture.swap();
+        let whirlpool = WhirlpoolState::parse(fixture.pool_data()).unwrap();
+
+        // Whirlpool layout: token_vault_a at bytes 133-165
+        let vault_a = Pubkey::try_from(&fixture.pool_data()[133..165]).unwrap();
+        let swap_a_to_b = pubkey(&swap.vault_in).unwrap() == vault_a;
+
+        // The tick arrays we derive are the ones the real swap was given
+        let start_indexes = OrcaSwapBuilder::swap_tick_array_start_indexes(&whirlpool, swap_a_to_b);
+        let first_array = OrcaSwapBuilder::derive_tick_array_pda(
+            &fixture.pool,
+            start_indexes[0],
+            &fixture.program,
+        );
+        assert!(swap.instruction_accounts.contains(&first_array.to_string()));
+
+        let mut ticks = Vec::new();
+        for start in start_indexes {
+            let address =
+                OrcaSwapBuilder::derive_tick_array_pda(&fixture.pool, start, &fixture.program);
+            if let Some(data) = fixture.try_data(&address) {
+                ticks.extend(parse_tick_array(data, whirlpool.tick_spacing).unwrap());
+            }
+        }
+        let ticks_in_array = whirlpool.tick_spacing as i32 * TICK_ARRAY_SIZE;
+        let boundary_tick = if swap_a_to_b {
+            start_indexes[2]
+        } else {
+            start_indexes[2] + ticks_in_array
+        };
+
+        let out = compute_swap_output(
+            &whirlpool,
+            &ticks,
+            boundary_tick,
+            swap.amount_in,
+            swap_a_to_b,
+        )
+        .unwrap();
+
+        // f64 sqrt-price math vs the program's Q64.64 integers: within 1 bps
+        let error = (out as f64 - swap.amount_out as f64).abs() / swap.amount_out as f64;
+        assert!(
+            error < 0.0001,
+            "estimated {} vs {} out on-chain ({})",
+            out,
+            swap.amount_out,
+            swap.signature
+        );
+    }
+
+    #[test]
+    fn test_tick_array_pda_uses_decimal_start_index() {
+        let program_id: Pubkey = OrcaSwapBuilder::WHIRLPOOLS_PROGRAM_ID.parse().unwrap();
+        let whirlpool = Pubkey::new_unique();
+
+        // The Whirlpools program seeds tick arrays with the start index as a
+        // decimal string ("-5632"), not as little-endian bytes
+        let expected = Pubkey::find_program_address(
+            &[b"tick_array", whirlpool.as_ref(), b"-5632"],
+            &program_id,
+        )
+        .0;
+        let le_bytes = Pubkey::find_program_address(
+            &[b"tick_array", whirlpool.as_ref(), &(-5632i32).to_le_bytes()],
+            &program_id,
+        )
+        .0;
+        let derived = OrcaSwapBuilder::derive_tick_array_pda(&whirlpool, -5632, &program_id);
+        assert_eq!(derived, expected);
+        assert_ne!(derived, le_bytes);
+    }
+
+    #[test]
+    fn test_swap_tick_array_start_indexes() {
+        let mut whirlpool = sample_whirlpool();
+        whirlpool.tick_current_index = -1; // Negative ticks round down, not toward zero
+
+        assert_eq!(
+            OrcaSwapBuilder::swap_tick_array_start_indexes(&whirlpool, true),
+            [-5632, -11264, -16896]
+        );
+        assert_eq!(
+            OrcaSwapBuilder::swap_tick_array_start_indexes(&whirlpool, false),
+            [-5632, 0, 5632]
+        );
+    }
 }
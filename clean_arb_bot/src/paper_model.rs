@@ -0,0 +1,98 @@
+// Deterministic paper-trading outcomes
+//
+// Paper fills are decided by hashing the opportunity (token, pools, spread)
+// with PAPER_SEED instead of a coin flip, so replaying the same opportunity
+// stream under two configs gives comparable results. Thin margins over the
+// required spread fail more often than fat ones (slippage, MEV, racing bots).
+
+use solana_sdk::hash::hashv;
+
+/// Success probability when the spread only just covers costs
+const MIN_SUCCESS_PROBABILITY: f64 = 0.5;
+/// Success probability approached as the margin over costs grows
+const MAX_SUCCESS_PROBABILITY: f64 = 0.95;
+/// Relative margin (spread / required - 1) at which success is halfway between min and max
+const HALF_SUCCESS_MARGIN: f64 = 0.5;
+
+/// Simulated fill probability for a spread vs. the spread needed to cover costs
+///
+/// # Arguments
+/// * `spread_pct` - Opportunity spread (%)
+/// * `required_spread_pct` - Spread needed to break even after all costs (%)
+///
+/// # Returns
+/// Probability in [MIN_SUCCESS_PROBABILITY, MAX_SUCCESS_PROBABILITY]
+pub fn success_probability(spread_pct: f64, required_spread_pct: f64) -> f64 {
+    if required_spread_pct.is_nan() || required_spread_pct <= 0.0 {
+        // No cost baseline (zero-cost or unknown) - treat as a fat margin
+        return MAX_SUCCESS_PROBABILITY;
+    }
+    let margin = (spread_pct / required_spread_pct - 1.0).max(0.0);
+    MIN_SUCCESS_PROBABILITY
+        + (MAX_SUCCESS_PROBABILITY - MIN_SUCCESS_PROBABILITY) * margin
+            / (margin + HALF_SUCCESS_MARGIN)
+}
+
+/// Deterministic uniform draw in [0, 1) for an opportunity
+///
+/// # Arguments
+/// * `seed` - PAPER_SEED
+/// * `key` - Opportunity identity (token + pools/DEXs)
+/// * `spread_pct` - Opportunity spread (%), part of the identity
+fn opportunity_draw(seed: u64, key: &str, spread_pct: f64) -> f64 {
+    let hash = hashv(&[
+        &seed.to_le_bytes(),
+        key.as_bytes(),
+        &spread_pct.to_bits().to_le_bytes(),
+    ]);
+    let mut head = [0u8; 8];
+    head.copy_from_slice(&hash.as_ref()[..8]);
+    // Top 53 bits fill an f64 mantissa exactly
+    (u64::from_le_bytes(head) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Whether a paper trade of this opportunity fills
+///
+/// Same seed + same opportunity always gives the same answer.
+///
+/// # Arguments
+/// * `seed` - PAPER_SEED
+/// * `key` - Opportunity identity (token + pools/DEXs)
+/// * `spread_pct` - Opportunity spread (%)
+/// * `required_spread_pct` - Spread needed to break even after all costs (%)
+pub fn paper_trade_succeeds(
+    seed: u64,
+    key: &str,
+    spread_pct: f64,
+    required_spread_pct: f64,
+) -> bool {
+    opportunity_draw(seed, key, spread_pct) < success_probability(spread_pct, required_spread_pct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_probability_grows_with_margin() {
+        let at_cost = success_probability(1.0, 1.0);
+        let thin = success_probability(1.1, 1.0);
+        let fat = success_probability(3.0, 1.0);
+        assert_eq!(at_cost, MIN_SUCCESS_PROBABILITY);
+        assert!(at_cost < thin && thin < fat && fat < MAX_SUCCESS_PROBABILITY);
+        assert_eq!(success_probability(1.0, 0.0), MAX_SUCCESS_PROBABILITY);
+    }
+
+    #[test]
+    fn test_paper_outcomes_are_reproducible() {
+        let key = "TokenMint1111|PoolA|PoolB";
+        let outcomes = |seed| -> Vec<bool> {
+            (0..64)
+                .map(|i| paper_trade_succeeds(seed, key, 1.0 + i as f64 * 0.01, 0.9))
+                .collect()
+        };
+        assert_eq!(outcomes(7), outcomes(7));
+        // A different seed reshuffles which opportunities fill
+        assert_ne!(outcomes(7), outcomes(8));
+    }
+}
@@ -10,7 +10,7 @@
 use anyhow::Result;
 use solana_sdk::pubkey::Pubkey;
 use std::sync::Arc;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 use crate::{DexType, PoolInfo, PoolRegistry};
 
@@ -177,6 +177,84 @@ pub fn populate_known_pools(pool_registry: Arc<PoolRegistry>) -> Result<()> {
     Ok(())
 }
 
+/// Outcome of checking one registered pool against the chain
+#[derive(Debug, Clone, PartialEq)]
+pub enum PoolCheck {
+    /// Resolves and the pool account exists on-chain
+    Ok(Pubkey),
+    /// Resolves, but the account can't be fetched (closed/renamed pool)
+    Ghost(Pubkey, String),
+    /// Short ID doesn't resolve to an address
+    Unresolved(String),
+}
+
+/// Validation result for one registered pool
+#[derive(Debug, Clone)]
+pub struct PoolCheckResult {
+    pub short_id: String,
+    pub pool: PoolInfo,
+    pub check: PoolCheck,
+}
+
+impl PoolCheckResult {
+    /// WSOL-paired pools are critical: every 2-leg route starts and ends in SOL
+    pub fn is_critical(&self) -> bool {
+        let wsol = spl_token::native_mint::id();
+        self.pool.token_a_mint == wsol || self.pool.token_b_mint == wsol
+    }
+
+    /// Whether the pool is usable for trading
+    pub fn is_ok(&self) -> bool {
+        matches!(self.check, PoolCheck::Ok(_))
+    }
+}
+
+/// Number of critical pools that are ghost or unresolved
+pub fn missing_critical_pools(results: &[PoolCheckResult]) -> usize {
+    results
+        .iter()
+        .filter(|result| result.is_critical() && !result.is_ok())
+        .count()
+}
+
+/// Check every registered pool resolves and exists on-chain (`--validate-pools`)
+///
+/// Runs `resolve_pool_address` then fetches the pool account, one pool at a
+/// time, so stale or renamed seed entries show up before a live session
+/// instead of as runtime rejections.
+///
+/// # Arguments
+/// * `pool_registry` - Registry populated via `populate_known_pools`
+///
+/// # Returns
+/// One result per registered pool, sorted by short ID
+pub async fn validate_registered_pools(pool_registry: &PoolRegistry) -> Vec<PoolCheckResult> {
+    let mut results = Vec::new();
+
+    for (short_id, pool) in pool_registry.registered_pools() {
+        let check = match pool_registry
+            .resolve_pool_address(&short_id, &pool.dex_type)
+            .await
+        {
+            Ok(address) => match pool_registry.fetch_pool_state(&address) {
+                Ok(data) => {
+                    debug!("✅ {} → {} ({} bytes)", short_id, address, data.len());
+                    PoolCheck::Ok(address)
+                }
+                Err(e) => PoolCheck::Ghost(address, format!("{:#}", e)),
+            },
+            Err(e) => PoolCheck::Unresolved(e.to_string()),
+        };
+        results.push(PoolCheckResult {
+            short_id,
+            pool,
+            check,
+        });
+    }
+
+    results
+}
+
 /// Attempt to resolve pool address from ShredStream service
 ///
 /// This makes an HTTP request to the ShredStream service asking for the full address.
@@ -252,4 +330,36 @@ mod tests {
         // Just verify the function compiles
         // Can't actually test without real pool data
     }
+
+    #[test]
+    fn test_missing_critical_pools_counts_wsol_pairs_only() {
+        let wsol = spl_token::native_mint::id();
+        let result = |token_a_mint: Pubkey, check: PoolCheck| PoolCheckResult {
+            short_id: "pool".to_string(),
+            pool: PoolInfo {
+                full_address: Pubkey::new_unique(),
+                dex_type: DexType::MeteoraDammV2,
+                token_a_mint,
+                token_b_mint: Pubkey::new_unique(),
+                reserve_a: Pubkey::new_unique(),
+                reserve_b: Pubkey::new_unique(),
+            },
+            check,
+        };
+
+        let results = vec![
+            result(wsol, PoolCheck::Ok(Pubkey::new_unique())),
+            result(wsol, PoolCheck::Unresolved("not found".to_string())),
+            result(
+                wsol,
+                PoolCheck::Ghost(Pubkey::new_unique(), "AccountNotFound".to_string()),
+            ),
+            // Missing non-SOL pool is reported but not critical
+            result(
+                Pubkey::new_unique(),
+                PoolCheck::Unresolved("not found".to_string()),
+            ),
+        ];
+        assert_eq!(missing_critical_pools(&results), 2);
+    }
 }
@@ -20,13 +20,90 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock as TokioRwLock;
 use tracing::{debug, info, warn}; // For async validation cache
 
-use crate::rpc_client::SolanaRpcClient;
+use crate::errors::{ArbError, ArbResult};
+use crate::rpc_client::RpcApi;
 use crate::types::{DexType, PoolInfo};
 
 // Pool validation constants (Grok's ghost pool solution)
 const MIN_POOL_SIZE: usize = 1000; // Minimum bytes for valid pool (DEX-specific)
 const VALIDATION_TTL_SECS: u64 = 300; // 5 minutes cache TTL
 const BACKGROUND_INTERVAL_SECS: u64 = 120; // 2 minutes background validation
+const PREFETCH_BATCH_SIZE: usize = 100; // getMultipleAccounts limit per call
+
+// Per-pool circuit breaker defaults (overridable via with_blacklist_policy)
+const DEFAULT_BLACKLIST_THRESHOLD: u32 = 5; // Consecutive failures before blacklisting
+const DEFAULT_BLACKLIST_COOLDOWN_SECS: u64 = 600; // 10 minutes
+
+/// Old Pump.fun bonding curve program (pre-migration, not arbitrageable)
+const PUMP_FUN_BONDING_CURVE_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+/// Program that owns pool accounts of this DEX type
+///
+/// Returns None for Jupiter, which routes through other DEXs' pools.
+pub fn expected_program_id(dex_type: &DexType) -> Option<Pubkey> {
+    let program_id = match dex_type {
+        // Meteora variants
+        DexType::MeteoraDammV1 => "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB",
+        DexType::MeteoraDammV2 => "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG",
+        DexType::MeteoraDlmm => "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+
+        // Orca variants
+        DexType::OrcaWhirlpools => "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+        DexType::OrcaLegacy => "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP",
+
+        // Raydium variants
+        DexType::RaydiumAmmV4 => "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+        DexType::RaydiumClmm => "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK",
+        DexType::RaydiumCpmm => "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
+        DexType::RaydiumStable => "5quBtoiQqxF9Jv6KYKctB59NT3gtJD2Y65kdnB1Uev3h",
+
+        // Other DEXes
+        DexType::PumpSwap => "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA",
+        DexType::Jupiter => return None,
+        DexType::Serum => "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin",
+        DexType::Aldrin => "AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6",
+        DexType::Saros => "SSwpkEEWHvCXCNWnMYXVW7gCYDXkF4aQMxKdpEqrZks",
+        DexType::Crema => "CLMM9tUoggJu2wagPkkqs9eFG4BWhVBZWkP1qv3Sp7tR",
+        DexType::Cropper => "CTMAxxk34HjKWxQ3QLZQA1EQdxtjbYGP4Qjrw7nTn8bM",
+        DexType::Lifinity => "EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S",
+        DexType::Fluxbeam => "FLUXubRmkEi2q6K3Y9kBPg9248ggaZVsoSFhtJHSrm1X",
+        DexType::HumidiFi => "9H6tuB8C3VnXcBLKFJGPqpFu1F2Bwsa7eJvbw8Tq6Rp",
+    };
+    program_id.parse().ok()
+}
+
+/// Check a resolved pool account is owned by its DEX type's program
+///
+/// A short ID that collides with another DEX's account (e.g. a "Meteora" ID
+/// resolving to an Orca-owned pool) would otherwise build a doomed instruction.
+///
+/// # Arguments
+/// * `pool_address` - Resolved pool address
+/// * `dex_type` - DEX the price feed attributed the pool to
+/// * `owner` - Program that owns the account on-chain
+///
+/// # Errors
+/// Returns `ArbError::PoolTypeMismatch` if the owner is not the DEX's program
+pub fn check_pool_owner(
+    pool_address: &Pubkey,
+    dex_type: &DexType,
+    owner: &Pubkey,
+) -> ArbResult<()> {
+    match expected_program_id(dex_type) {
+        Some(expected) if expected != *owner => Err(ArbError::PoolTypeMismatch(format!(
+            "{} claimed as {:?} but owned by {} (expected {})",
+            pool_address, dex_type, owner, expected
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Per-pool failure tracking for the blacklist circuit breaker
+#[derive(Debug, Default)]
+struct PoolFailureState {
+    consecutive_failures: u32,
+    blacklisted_until: Option<Instant>,
+}
 
 /// Cache entry for resolved pool addresses
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,7 +128,7 @@ pub struct PoolRegistry {
     /// Map of full_address -> short_id (reverse lookup)
     address_to_id: Arc<RwLock<HashMap<Pubkey, String>>>,
     /// RPC client for fetching pool data (Layer 4: On-chain fallback)
-    rpc_client: Arc<SolanaRpcClient>,
+    rpc_client: Arc<dyn RpcApi>,
     /// HTTP client for ShredStream service queries (Layer 2: ShredStream API)
     http_client: reqwest::Client,
     /// ShredStream service URL
@@ -61,6 +138,28 @@ pub struct PoolRegistry {
     /// Pool validation cache (pool_short_id -> (is_valid, last_checked))
     /// Grok's ghost pool solution: 5-minute TTL cache
     validation_cache: Arc<TokioRwLock<HashMap<String, (bool, Instant)>>>,
+    /// Per-pool consecutive simulation/execution failures (pool_short_id -> state)
+    pool_failures: Arc<RwLock<HashMap<String, PoolFailureState>>>,
+    /// Consecutive failures before a pool is blacklisted
+    blacklist_threshold: u32,
+    /// How long a blacklisted pool is skipped
+    blacklist_cooldown: Duration,
+}
+
+/// Outcome of the startup pool prefetch
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrefetchReport {
+    /// Pools whose accounts were fetched (valid + ghost)
+    pub prefetched: usize,
+    /// Fetched pools missing on-chain or too small to be a pool
+    pub ghost: usize,
+    /// Pools in batches whose RPC call failed (validated on demand later)
+    pub unfetched: usize,
+}
+
+/// Whether account data looks like a live pool (exists and is big enough)
+pub fn is_valid_pool_data(data: Option<&[u8]>) -> bool {
+    data.is_some_and(|data| data.len() >= MIN_POOL_SIZE)
 }
 
 /// Statistics for pool resolution performance
@@ -76,7 +175,7 @@ struct ResolutionStats {
 
 impl PoolRegistry {
     /// Create new pool registry
-    pub fn new(rpc_client: Arc<SolanaRpcClient>) -> Self {
+    pub fn new(rpc_client: Arc<dyn RpcApi>) -> Self {
         let shredstream_url = std::env::var("SHREDSTREAM_SERVICE_URL")
             .unwrap_or_else(|_| "http://localhost:8080".to_string());
 
@@ -96,9 +195,23 @@ impl PoolRegistry {
             shredstream_url,
             resolution_stats: Arc::new(RwLock::new(ResolutionStats::default())),
             validation_cache: Arc::new(TokioRwLock::new(HashMap::new())), // Grok's ghost pool solution
+            pool_failures: Arc::new(RwLock::new(HashMap::new())),
+            blacklist_threshold: DEFAULT_BLACKLIST_THRESHOLD,
+            blacklist_cooldown: Duration::from_secs(DEFAULT_BLACKLIST_COOLDOWN_SECS),
         }
     }
 
+    /// Override the per-pool blacklist policy
+    ///
+    /// # Arguments
+    /// * `threshold` - Consecutive failures before a pool is blacklisted
+    /// * `cooldown` - How long the pool stays blacklisted
+    pub fn with_blacklist_policy(mut self, threshold: u32, cooldown: Duration) -> Self {
+        self.blacklist_threshold = threshold.max(1);
+        self.blacklist_cooldown = cooldown;
+        self
+    }
+
     /// Register a pool manually (for pre-population)
     pub fn register_pool(&self, short_id: String, pool_info: PoolInfo) -> Result<()> {
         let full_address = pool_info.full_address;
@@ -145,6 +258,30 @@ impl PoolRegistry {
         pools.contains_key(short_id)
     }
 
+    /// Registered pools with `mint` on either side
+    ///
+    /// # Returns
+    /// (short_id, pool) pairs, e.g. every WSOL-paired pool for RPC price fallback
+    pub fn pools_paired_with(&self, mint: &Pubkey) -> Vec<(String, PoolInfo)> {
+        let pools = self.pools.read().unwrap();
+        pools
+            .iter()
+            .filter(|(_, pool)| pool.token_a_mint == *mint || pool.token_b_mint == *mint)
+            .map(|(short_id, pool)| (short_id.clone(), pool.clone()))
+            .collect()
+    }
+
+    /// All registered pools, sorted by short ID
+    pub fn registered_pools(&self) -> Vec<(String, PoolInfo)> {
+        let pools = self.pools.read().unwrap();
+        let mut registered: Vec<(String, PoolInfo)> = pools
+            .iter()
+            .map(|(short_id, pool)| (short_id.clone(), pool.clone()))
+            .collect();
+        registered.sort_by(|a, b| a.0.cmp(&b.0));
+        registered
+    }
+
     /// Get number of registered pools
     pub fn pool_count(&self) -> usize {
         let pools = self.pools.read().unwrap();
@@ -157,7 +294,7 @@ impl PoolRegistry {
 
         let data = self
             .rpc_client
-            .get_account_data(pool_address)
+            .get_account_data(pool_address, None)
             .context("Failed to fetch pool state")?;
 
         Ok(data)
@@ -196,6 +333,11 @@ impl PoolRegistry {
                 stats.total_latency_ms += latency;
             } // Lock released immediately
 
+            // Registered under another DEX's program: same mismatch, no RPC needed
+            if let Some(registered) = expected_program_id(&pool_info.dex_type) {
+                check_pool_owner(&pool_info.full_address, dex_type, &registered)?;
+            }
+
             debug!("✅ Layer 1 HIT: Found in memory cache ({}ms)", latency);
             return Ok(pool_info.full_address);
         }
@@ -223,6 +365,12 @@ impl PoolRegistry {
 
                 debug!("✅ Layer 2 HIT: Found via ShredStream API ({}ms)", latency);
 
+                // Don't cache a resolution that points at another DEX's account
+                if let Err(e) = self.verify_pool_owner(&full_address, dex_type) {
+                    warn!("❌ Layer 2 rejected {}: {}", short_id, e);
+                    return Err(e);
+                }
+
                 // Cache in memory for future lookups
                 let pool_info = PoolInfo {
                     full_address,
@@ -265,40 +413,11 @@ impl PoolRegistry {
                     latency
                 );
 
-                // FILTER: Reject old Pump.fun bonding curve (NOT arbitrageable)
+                // FILTER: Reject old Pump.fun bonding curves and accounts owned by another DEX
                 // Allow PumpSwap AMM (post-migration, standard AMM like Raydium)
-                match self.rpc_client.get_account_owner(&full_address) {
-                    Ok(owner) => {
-                        // OLD Pump.fun bonding curve program (pre-migration)
-                        let old_pump_fun_bonding: Pubkey =
-                            "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"
-                                .parse()
-                                .unwrap();
-
-                        // PumpSwap AMM program (post-migration) - ALLOW
-                        let pumpswap_amm: Pubkey = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA"
-                            .parse()
-                            .unwrap();
-
-                        if owner == old_pump_fun_bonding {
-                            debug!("⚠️ Skipping old Pump.fun bonding curve (pre-migration, not arbitrageable): {}", full_address);
-                            return Err(anyhow::anyhow!(
-                                "Pool {} is on old Pump.fun bonding curve (unsupported - use PumpSwap AMM instead)",
-                                full_address
-                            ));
-                        }
-
-                        if owner == pumpswap_amm {
-                            debug!(
-                                "✅ PumpSwap AMM pool detected (post-migration, arbitrageable): {}",
-                                full_address
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        debug!("⚠️ Could not verify pool owner: {}", e);
-                        // Continue anyway - owner check failure shouldn't block
-                    }
+                if let Err(e) = self.verify_pool_owner(&full_address, dex_type) {
+                    warn!("❌ Layer 4 rejected {}: {}", short_id, e);
+                    return Err(e);
                 }
 
                 // Cache in memory for future lookups
@@ -339,6 +458,38 @@ impl PoolRegistry {
         ))
     }
 
+    /// Verify a freshly resolved pool's on-chain owner matches its DEX type
+    ///
+    /// Runs once per short ID (the result is cached in Layer 1). An owner
+    /// lookup failure is let through - the ghost pool check catches missing accounts.
+    ///
+    /// # Errors
+    /// Returns `ArbError::PoolTypeMismatch` if another program owns the account,
+    /// or an error if it is an old Pump.fun bonding curve
+    fn verify_pool_owner(&self, pool_address: &Pubkey, dex_type: &DexType) -> Result<()> {
+        let owner = match self.rpc_client.get_account_owner(pool_address) {
+            Ok(owner) => owner,
+            Err(e) => {
+                debug!("⚠️ Could not verify pool owner: {}", e);
+                return Ok(());
+            }
+        };
+
+        if owner.to_string() == PUMP_FUN_BONDING_CURVE_PROGRAM_ID {
+            debug!(
+                "⚠️ Skipping old Pump.fun bonding curve (pre-migration, not arbitrageable): {}",
+                pool_address
+            );
+            return Err(anyhow::anyhow!(
+                "Pool {} is on old Pump.fun bonding curve (unsupported - use PumpSwap AMM instead)",
+                pool_address
+            ));
+        }
+
+        check_pool_owner(pool_address, dex_type, &owner)?;
+        Ok(())
+    }
+
     /// Query ShredStream service API for full pool address (Layer 2)
     async fn query_shredstream_api(&self, short_id: &str) -> Result<Pubkey> {
         let url = format!("{}/api/pool/{}", self.shredstream_url, short_id);
@@ -383,57 +534,8 @@ impl PoolRegistry {
             short_id, dex_type
         );
 
-        // Get the program ID for this DEX type (reserved for future validation)
-        let _program_id = match dex_type {
-            // Meteora variants
-            DexType::MeteoraDammV1 => {
-                "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB".parse::<Pubkey>()?
-            }
-            DexType::MeteoraDammV2 => {
-                "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG".parse::<Pubkey>()?
-            }
-            DexType::MeteoraDlmm => {
-                "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo".parse::<Pubkey>()?
-            }
-
-            // Orca variants
-            DexType::OrcaWhirlpools => {
-                "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".parse::<Pubkey>()?
-            }
-            DexType::OrcaLegacy => {
-                "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP".parse::<Pubkey>()?
-            }
-
-            // Raydium variants (note: AMM V4 and CPMM share same program ID)
-            DexType::RaydiumAmmV4 => {
-                "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8".parse::<Pubkey>()?
-            }
-            DexType::RaydiumClmm => {
-                "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".parse::<Pubkey>()?
-            }
-            DexType::RaydiumCpmm => {
-                "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C".parse::<Pubkey>()?
-            }
-            DexType::RaydiumStable => {
-                "5quBtoiQqxF9Jv6KYKctB59NT3gtJD2Y65kdnB1Uev3h".parse::<Pubkey>()?
-            }
-
-            // Other DEXes
-            DexType::PumpSwap => "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA".parse::<Pubkey>()?,
-            DexType::Jupiter => "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".parse::<Pubkey>()?,
-            DexType::Serum => "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin".parse::<Pubkey>()?,
-            DexType::Aldrin => "AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6".parse::<Pubkey>()?,
-            DexType::Saros => "SSwpkEEWHvCXCNWnMYXVW7gCYDXkF4aQMxKdpEqrZks".parse::<Pubkey>()?,
-            DexType::Crema => "6MLxLqiXaaSUpkgMnWDTuejNZEz3kE7k2woyHGVFw319".parse::<Pubkey>()?,
-            DexType::Cropper => "CTMAxxk34HjKWxQ3QLZQA1EQdxtjbYGP4Qjrw7nTn8bM".parse::<Pubkey>()?,
-            DexType::Lifinity => {
-                "EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S".parse::<Pubkey>()?
-            }
-            DexType::Fluxbeam => {
-                "FLUXBmPhT3Fd1EDVFdg46YREqHBeNypn1h4EbnTzWERX".parse::<Pubkey>()?
-            }
-            DexType::HumidiFi => "9H6tuB8C3VnXcBLKFJGPqpFu1F2Bwsa7eJvbw8Tq6Rp".parse::<Pubkey>()?,
-        };
+        // Get the program ID for this DEX type (reserved for the prefix-filtered scan)
+        let _program_id = expected_program_id(dex_type);
 
         // Query all program accounts (VERY SLOW - avoid if possible)
         warn!("⚠️ Using slow getProgramAccounts - this will take 200-400ms!");
@@ -546,11 +648,12 @@ impl PoolRegistry {
         let mut valid_ids = Vec::new();
 
         for short_id in pool_short_ids {
-            // Try to resolve using any DEX type (we just need the address)
-            match self
-                .resolve_pool_address(short_id, &DexType::OrcaWhirlpools)
-                .await
-            {
+            // We just need the address: registered pools resolve under their own
+            // DEX type so the Layer 1 owner check passes, others under any type
+            let dex_type = self
+                .get_pool(short_id)
+                .map_or(DexType::OrcaWhirlpools, |pool| pool.dex_type);
+            match self.resolve_pool_address(short_id, &dex_type).await {
                 Ok(addr) => {
                     addresses.push(addr);
                     valid_ids.push(short_id.clone());
@@ -579,9 +682,9 @@ impl PoolRegistry {
             let short_id = &valid_ids[i];
 
             // Check if account exists and has minimum size
-            let is_valid = match self.rpc_client.get_account_data(addr) {
+            let is_valid = match self.rpc_client.get_account_data_cached(addr) {
                 Ok(data) => {
-                    let valid = !data.is_empty() && data.len() >= MIN_POOL_SIZE;
+                    let valid = is_valid_pool_data(Some(&data));
                     if !valid {
                         debug!(
                             "⚠️ Pool {} exists but too small ({} bytes < {} min)",
@@ -610,6 +713,160 @@ impl PoolRegistry {
         Ok(())
     }
 
+    /// Require that `pool_short_id` passed ghost-pool validation
+    ///
+    /// Safety fallback for the swap builders - the arbitrage engine normally
+    /// validates every pool of an opportunity first, so this only hits RPC for
+    /// a pool that isn't cached. Does nothing when SKIP_GHOST_POOL_CHECK=true.
+    ///
+    /// # Errors
+    /// `ArbError::GhostPool` if the pool fails validation
+    pub async fn ensure_pool_valid(&self, pool_short_id: &str) -> ArbResult<()> {
+        if ghost_pool_check_disabled()
+            || self.is_pool_valid_cached(pool_short_id).await == Some(true)
+        {
+            return Ok(());
+        }
+
+        warn!(
+            "⚠️ Pool {} not in cache, validating on-demand",
+            pool_short_id
+        );
+        self.validate_pools_batch(&[pool_short_id.to_string()])
+            .await?;
+
+        if self.is_pool_valid_cached(pool_short_id).await != Some(true) {
+            return Err(ArbError::GhostPool(format!(
+                "{} (failed validation)",
+                pool_short_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Warm the caches for every registered pool before the first scan
+    ///
+    /// Batches `getMultipleAccounts` over all registered pools, caches each
+    /// account's data and its ghost-pool validity, so the first opportunity on
+    /// a pool skips the on-demand validation round trip. Registered pools
+    /// already resolve from Layer 1; their lookups are counted so the
+    /// resolution stats reflect the warm-up.
+    ///
+    /// # Returns
+    /// How many pools were prefetched, how many were ghost pools and how many
+    /// could not be fetched
+    pub async fn prefetch_all(&self) -> PrefetchReport {
+        let pools = self.registered_pools();
+        let mut report = PrefetchReport::default();
+        if pools.is_empty() {
+            return report;
+        }
+
+        let start = Instant::now();
+        for batch in pools.chunks(PREFETCH_BATCH_SIZE) {
+            let addresses: Vec<Pubkey> = batch.iter().map(|(_, info)| info.full_address).collect();
+            let accounts = match self.rpc_client.get_multiple_accounts(&addresses) {
+                Ok(accounts) => accounts,
+                Err(e) => {
+                    warn!(
+                        "⚠️ Pool prefetch batch of {} failed: {} - validating on demand",
+                        batch.len(),
+                        e
+                    );
+                    report.unfetched += batch.len();
+                    continue;
+                }
+            };
+
+            let mut cache = self.validation_cache.write().await;
+            for ((short_id, info), data) in batch.iter().zip(accounts) {
+                let is_valid = is_valid_pool_data(data.as_deref());
+                cache.insert(short_id.clone(), (is_valid, Instant::now()));
+                report.prefetched += 1;
+
+                if is_valid {
+                    if let Some(data) = data {
+                        self.rpc_client.prime_account_cache(info.full_address, data);
+                    }
+                } else {
+                    report.ghost += 1;
+                    debug!(
+                        "👻 Prefetch: pool {} ({}) is a ghost pool",
+                        short_id, info.full_address
+                    );
+                }
+            }
+        }
+
+        {
+            let mut stats = self.resolution_stats.write().unwrap();
+            stats.total_lookups += report.prefetched as u64;
+            stats.layer1_hits += report.prefetched as u64;
+        }
+
+        debug!(
+            "🔥 Pool prefetch took {}ms ({} batches)",
+            start.elapsed().as_millis(),
+            pools.len().div_ceil(PREFETCH_BATCH_SIZE)
+        );
+        report
+    }
+
+    // ========================================
+    // Per-pool circuit breaker (blacklist after repeated failures)
+    // ========================================
+
+    /// Record a simulation/execution failure for a pool
+    ///
+    /// # Returns
+    /// `true` if this failure tripped the blacklist
+    pub fn record_pool_failure(&self, pool_short_id: &str) -> bool {
+        let mut failures = self.pool_failures.write().unwrap();
+        let state = failures.entry(pool_short_id.to_string()).or_default();
+
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.blacklist_threshold
+            && state.blacklisted_until.is_none()
+        {
+            state.blacklisted_until = Some(Instant::now() + self.blacklist_cooldown);
+            warn!(
+                "⛔ Pool {} blacklisted for {}s after {} consecutive failures",
+                pool_short_id,
+                self.blacklist_cooldown.as_secs(),
+                state.consecutive_failures
+            );
+            return true;
+        }
+
+        false
+    }
+
+    /// Record a successful simulation/execution (resets the failure streak)
+    pub fn record_pool_success(&self, pool_short_id: &str) {
+        let mut failures = self.pool_failures.write().unwrap();
+        failures.remove(pool_short_id);
+    }
+
+    /// Check if a pool is currently blacklisted
+    ///
+    /// Expired entries are cleared automatically, giving the pool a fresh streak
+    pub fn is_pool_blacklisted(&self, pool_short_id: &str) -> bool {
+        let mut failures = self.pool_failures.write().unwrap();
+
+        match failures
+            .get(pool_short_id)
+            .and_then(|s| s.blacklisted_until)
+        {
+            Some(until) if Instant::now() < until => true,
+            Some(_) => {
+                failures.remove(pool_short_id);
+                info!("✅ Pool {} blacklist cooldown expired", pool_short_id);
+                false
+            }
+            None => false,
+        }
+    }
+
     /// Start background task to periodically validate top pools
     /// Runs async without blocking main flow
     pub fn start_background_validation(self: Arc<Self>, top_pools: Vec<String>) {
@@ -636,9 +893,17 @@ impl PoolRegistry {
     }
 }
 
+/// SKIP_GHOST_POOL_CHECK=true turns ghost-pool validation off (market chaos mode)
+pub fn ghost_pool_check_disabled() -> bool {
+    std::env::var("SKIP_GHOST_POOL_CHECK")
+        .map(|value| value.to_lowercase() == "true")
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rpc_client::SolanaRpcClient;
 
     #[test]
     fn test_pool_registry_creation() {
@@ -649,6 +914,19 @@ mod tests {
         assert_eq!(registry.pool_count(), 0);
     }
 
+    #[tokio::test]
+    async fn test_prefetch_validity_and_empty_registry() {
+        assert!(is_valid_pool_data(Some(&[0u8; MIN_POOL_SIZE])));
+        assert!(!is_valid_pool_data(Some(&[0u8; 165]))); // Token account, not a pool
+        assert!(!is_valid_pool_data(Some(&[])));
+        assert!(!is_valid_pool_data(None)); // Closed / never existed
+
+        // Nothing registered: no RPC call, nothing prefetched
+        let rpc_client = Arc::new(SolanaRpcClient::new("http://127.0.0.1:1".to_string()));
+        let registry = PoolRegistry::new(rpc_client);
+        assert_eq!(registry.prefetch_all().await, PrefetchReport::default());
+    }
+
     #[test]
     fn test_pool_registration() {
         let rpc_url = "https://api.mainnet-beta.solana.com".to_string();
@@ -685,4 +963,119 @@ mod tests {
         let short_id = registry.get_short_id(&pool_address).unwrap();
         assert_eq!(short_id, "81vA2wJx");
     }
+
+    #[tokio::test]
+    async fn test_pool_owner_mismatch_is_rejected() {
+        let pool_address: Pubkey = "81vA2wJxKyUE8RHKXxT5VfEQnJGYvJ9FTBwJQhRZHvqX"
+            .parse()
+            .unwrap();
+        let orca = expected_program_id(&DexType::OrcaWhirlpools).unwrap();
+        let dlmm = expected_program_id(&DexType::MeteoraDlmm).unwrap();
+
+        // "Meteora" short ID resolving to an Orca-owned account
+        let mismatch = check_pool_owner(&pool_address, &DexType::MeteoraDlmm, &orca).unwrap_err();
+        assert!(matches!(mismatch, ArbError::PoolTypeMismatch(_)));
+        assert!(check_pool_owner(&pool_address, &DexType::MeteoraDlmm, &dlmm).is_ok());
+        // Jupiter has no pool program of its own
+        assert!(check_pool_owner(&pool_address, &DexType::Jupiter, &orca).is_ok());
+
+        // Registered as Orca, requested as Meteora: rejected from the cache, no RPC
+        let rpc_client = Arc::new(SolanaRpcClient::new("http://127.0.0.1:1".to_string()));
+        let registry = PoolRegistry::new(rpc_client);
+        let pool_info = PoolInfo {
+            full_address: pool_address,
+            dex_type: DexType::OrcaWhirlpools,
+            token_a_mint: Pubkey::default(),
+            token_b_mint: Pubkey::default(),
+            reserve_a: Pubkey::default(),
+            reserve_b: Pubkey::default(),
+        };
+        registry
+            .register_pool("81vA2wJx".to_string(), pool_info)
+            .unwrap();
+
+        let err = registry
+            .resolve_pool_address("81vA2wJx", &DexType::MeteoraDlmm)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            ArbError::find(&err).map(ArbError::kind),
+            Some("pool_type_mismatch")
+        );
+        assert_eq!(
+            registry
+                .resolve_pool_address("81vA2wJx", &DexType::OrcaWhirlpools)
+                .await
+                .unwrap(),
+            pool_address
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_pool_valid_flags_ghost_pools() {
+        let rpc_client = Arc::new(SolanaRpcClient::new("http://127.0.0.1:1".to_string()));
+        let registry = PoolRegistry::new(rpc_client);
+
+        // Validated by the engine: served from the cache, no RPC
+        registry
+            .validation_cache
+            .write()
+            .await
+            .insert("cachedOk".to_string(), (true, Instant::now()));
+        assert!(registry.ensure_pool_valid("cachedOk").await.is_ok());
+
+        // Uncached and its account can't be fetched: validated on demand, rejected
+        let pool_info = PoolInfo {
+            full_address: Pubkey::new_unique(),
+            dex_type: DexType::Saros,
+            token_a_mint: Pubkey::default(),
+            token_b_mint: Pubkey::default(),
+            reserve_a: Pubkey::default(),
+            reserve_b: Pubkey::default(),
+        };
+        registry
+            .register_pool("ghost123".to_string(), pool_info)
+            .unwrap();
+        let err = registry.ensure_pool_valid("ghost123").await.unwrap_err();
+        assert!(matches!(err, ArbError::GhostPool(_)));
+        assert_eq!(registry.is_pool_valid_cached("ghost123").await, Some(false));
+    }
+
+    #[test]
+    fn test_pool_blacklist_after_consecutive_failures() {
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let registry =
+            PoolRegistry::new(rpc_client).with_blacklist_policy(3, Duration::from_secs(600));
+
+        assert!(!registry.record_pool_failure("81vA2wJx"));
+        assert!(!registry.record_pool_failure("81vA2wJx"));
+        // Success resets the streak
+        registry.record_pool_success("81vA2wJx");
+        assert!(!registry.record_pool_failure("81vA2wJx"));
+        assert!(!registry.record_pool_failure("81vA2wJx"));
+        assert!(!registry.is_pool_blacklisted("81vA2wJx"));
+
+        assert!(registry.record_pool_failure("81vA2wJx"));
+        assert!(registry.is_pool_blacklisted("81vA2wJx"));
+        assert!(!registry.is_pool_blacklisted("Other123"));
+    }
+
+    #[test]
+    fn test_pool_blacklist_clears_after_cooldown() {
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let registry =
+            PoolRegistry::new(rpc_client).with_blacklist_policy(1, Duration::from_millis(20));
+
+        assert!(registry.record_pool_failure("81vA2wJx"));
+        assert!(registry.is_pool_blacklisted("81vA2wJx"));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(!registry.is_pool_blacklisted("81vA2wJx"));
+        // Entry cleared - next failure starts a fresh streak
+        assert!(registry.record_pool_failure("81vA2wJx"));
+    }
 }
@@ -5,10 +5,16 @@
 //
 // Grok Cycle 3 Critical Fix: Atomic position tracking with lock-free design
 
-use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{debug, info, warn};
 
+use crate::errors::{ArbError, ArbResult};
+
+/// Default base fee reserve (FEE_RESERVE_SOL)
+pub const DEFAULT_FEE_RESERVE_SOL: f64 = 0.1;
+
 /// Lock-free position tracker using atomic operations
 ///
 /// Thread-safe capital management for concurrent arbitrage opportunities
@@ -20,11 +26,32 @@ pub struct PositionTracker {
     /// Capital currently committed to in-flight trades (atomic for thread-safety)
     in_flight_lamports: AtomicU64,
 
-    /// Maximum allowed position size (in lamports)
-    max_position_lamports: u64,
+    /// Maximum allowed position size (in lamports) - rescaled with balance when
+    /// `max_position_pct` is set
+    max_position_lamports: AtomicU64,
+
+    /// Absolute position ceiling from MAX_POSITION_SIZE_SOL (in lamports)
+    max_position_cap_lamports: u64,
+
+    /// Fraction of tradeable balance allowed per position (None = fixed cap)
+    max_position_pct: Option<f64>,
+
+    /// Per-token-mint position ceilings (in lamports), used instead of the global max
+    position_overrides: HashMap<String, u64>,
+
+    /// Base fee reserve (always protected, never tradeable) - FEE_RESERVE_SOL, or
+    /// tip-floor multiple when that is larger
+    fee_reserve_lamports: AtomicU64,
+
+    /// Configured FEE_RESERVE_SOL (lamports) - floor of the dynamic reserve
+    min_fee_reserve_lamports: u64,
 
-    /// Fee reserve (always protected, never tradeable) - DEFAULT: 0.1 SOL
-    fee_reserve_lamports: u64,
+    /// Scale the base reserve to this × p99 landed tip (None = fixed reserve)
+    fee_reserve_tip_multiple: Option<f64>,
+
+    /// JITO tips of queued bundles not yet resolved - reserved on top of the base
+    /// fee reserve so concurrent submissions can't spend the gas buffer
+    in_flight_tip_lamports: AtomicU64,
 }
 
 impl PositionTracker {
@@ -35,12 +62,11 @@ impl PositionTracker {
     /// * `max_position_sol` - Maximum position size per trade in SOL
     ///
     /// # Fee Reserve
-    /// - 0.1 SOL is ALWAYS protected for transaction fees
-    /// - Tradeable balance = wallet_balance - 0.1 SOL
+    /// - 0.1 SOL is ALWAYS protected for transaction fees (see `with_fee_reserve`)
+    /// - Tradeable balance = wallet_balance - fee reserve
     /// - This reserve is never used for trades
     pub fn new(capital_sol: f64, max_position_sol: f64) -> Self {
-        const FEE_RESERVE_SOL: f64 = 0.1;
-        let fee_reserve_lamports = (FEE_RESERVE_SOL * 1_000_000_000.0) as u64;
+        let fee_reserve_lamports = (DEFAULT_FEE_RESERVE_SOL * 1_000_000_000.0) as u64;
 
         // Initial capital (will be updated dynamically from wallet balance)
         let total_capital_lamports = (capital_sol * 1_000_000_000.0) as u64;
@@ -57,15 +83,158 @@ impl PositionTracker {
         );
         info!(
             "   Fee reserve: {:.4} SOL ({} lamports) - PROTECTED",
-            FEE_RESERVE_SOL, fee_reserve_lamports
+            DEFAULT_FEE_RESERVE_SOL, fee_reserve_lamports
         );
         info!("   Tradeable balance will update based on actual wallet balance");
 
         Self {
             total_capital_lamports: AtomicU64::new(total_capital_lamports),
             in_flight_lamports: AtomicU64::new(0),
-            max_position_lamports,
-            fee_reserve_lamports,
+            max_position_lamports: AtomicU64::new(max_position_lamports),
+            max_position_cap_lamports: max_position_lamports,
+            max_position_pct: None,
+            position_overrides: HashMap::new(),
+            fee_reserve_lamports: AtomicU64::new(fee_reserve_lamports),
+            min_fee_reserve_lamports: fee_reserve_lamports,
+            fee_reserve_tip_multiple: None,
+            in_flight_tip_lamports: AtomicU64::new(0),
+        }
+    }
+
+    /// Configure the base fee reserve
+    ///
+    /// # Arguments
+    /// * `fee_reserve_sol` - Balance never traded (FEE_RESERVE_SOL)
+    /// * `tip_multiple` - Grow the reserve to this × p99 landed tip when larger,
+    ///   applied by `update_fee_reserve_from_tip_floor` (None = fixed reserve)
+    ///
+    /// # Returns
+    /// Self, with the new reserve taken out of the initial capital on the next
+    /// wallet balance update
+    pub fn with_fee_reserve(mut self, fee_reserve_sol: f64, tip_multiple: Option<f64>) -> Self {
+        let lamports = (fee_reserve_sol * 1_000_000_000.0) as u64;
+        self.min_fee_reserve_lamports = lamports;
+        self.fee_reserve_lamports = AtomicU64::new(lamports);
+        self.fee_reserve_tip_multiple = tip_multiple;
+        info!(
+            "   Fee reserve: {:.4} SOL{}",
+            fee_reserve_sol,
+            match tip_multiple {
+                Some(multiple) =>
+                    format!(" (or {}x p99 landed tip, whichever is larger)", multiple),
+                None => String::new(),
+            }
+        );
+        self
+    }
+
+    /// Rescale the base fee reserve from the current JITO tip floor
+    ///
+    /// No-op for a fixed reserve. Takes effect on the next
+    /// `update_from_wallet_balance`.
+    ///
+    /// # Arguments
+    /// * `p99_tip_lamports` - 99th percentile landed tip
+    ///
+    /// # Returns
+    /// Base fee reserve now in effect (lamports)
+    pub fn update_fee_reserve_from_tip_floor(&self, p99_tip_lamports: u64) -> u64 {
+        let Some(multiple) = self.fee_reserve_tip_multiple else {
+            return self.fee_reserve_lamports.load(Ordering::Relaxed);
+        };
+
+        let reserve =
+            dynamic_fee_reserve_lamports(self.min_fee_reserve_lamports, multiple, p99_tip_lamports);
+        let previous = self.fee_reserve_lamports.swap(reserve, Ordering::Release);
+        if previous != reserve {
+            debug!(
+                "🛡️ Fee reserve {:.4} → {:.4} SOL ({}x p99 tip of {} lamports)",
+                previous as f64 / 1_000_000_000.0,
+                reserve as f64 / 1_000_000_000.0,
+                multiple,
+                p99_tip_lamports
+            );
+        }
+        reserve
+    }
+
+    /// Scale the max position with the tradeable balance
+    ///
+    /// # Arguments
+    /// * `pct` - Fraction of tradeable balance (after fee reserve) per position,
+    ///   still capped by `max_position_sol`; None keeps the fixed cap
+    ///
+    /// # Returns
+    /// Self, with the max position recomputed from the current capital
+    pub fn with_max_position_pct(mut self, pct: Option<f64>) -> Self {
+        self.max_position_pct = pct;
+        if let Some(pct) = pct {
+            info!(
+                "   Max position: {:.0}% of tradeable balance (capped at {:.4} SOL)",
+                pct * 100.0,
+                self.max_position_cap_lamports as f64 / 1_000_000_000.0
+            );
+            let capital = self.total_capital_lamports.load(Ordering::Relaxed);
+            self.rescale_max_position(capital);
+        }
+        self
+    }
+
+    /// Per-token position size overrides
+    ///
+    /// # Arguments
+    /// * `overrides` - Token mint -> max position in SOL (TOKEN_POSITION_OVERRIDES)
+    ///
+    /// # Returns
+    /// Self, with overridden tokens sized by their override instead of the global max
+    pub fn with_position_overrides(mut self, overrides: &HashMap<String, f64>) -> Self {
+        if !overrides.is_empty() {
+            info!("   Per-token position overrides: {}", overrides.len());
+        }
+        self.position_overrides = overrides
+            .iter()
+            .map(|(mint, sol)| (mint.clone(), (sol * 1_000_000_000.0) as u64))
+            .collect();
+        self
+    }
+
+    /// Current maximum position size in SOL
+    pub fn max_position_sol(&self) -> f64 {
+        self.max_position_lamports.load(Ordering::Relaxed) as f64 / 1_000_000_000.0
+    }
+
+    /// Maximum position size (in lamports) for a trade through `mints`
+    ///
+    /// The smallest override among the mints wins; without any override the
+    /// global (possibly balance-scaled) max applies.
+    pub fn max_position_lamports_for(&self, mints: &[&str]) -> u64 {
+        mints
+            .iter()
+            .filter_map(|mint| self.position_overrides.get(*mint).copied())
+            .min()
+            .unwrap_or_else(|| self.max_position_lamports.load(Ordering::Relaxed))
+    }
+
+    /// Maximum position size in SOL for a trade through `mints`
+    pub fn max_position_sol_for(&self, mints: &[&str]) -> f64 {
+        self.max_position_lamports_for(mints) as f64 / 1_000_000_000.0
+    }
+
+    /// Recompute the max position from tradeable capital (no-op without a pct)
+    fn rescale_max_position(&self, tradeable_lamports: u64) {
+        let Some(pct) = self.max_position_pct else {
+            return;
+        };
+        let scaled = ((tradeable_lamports as f64 * pct) as u64).min(self.max_position_cap_lamports);
+        let old = self.max_position_lamports.swap(scaled, Ordering::Release);
+        if scaled != old {
+            info!(
+                "📐 Max position rescaled to {:.6} SOL ({:.0}% of {:.6} SOL tradeable, was {:.6} SOL)",
+                scaled as f64 / 1_000_000_000.0,
+                pct * 100.0,
+                tradeable_lamports as f64 / 1_000_000_000.0,
+                old as f64 / 1_000_000_000.0
+            );
         }
     }
 
@@ -78,20 +247,98 @@ impl PositionTracker {
     /// true if capital is available, false otherwise
     pub fn can_open_position(&self, size_lamports: u64) -> bool {
         // Check against max position size limit
-        if size_lamports > self.max_position_lamports {
+        let max_position = self.max_position_lamports.load(Ordering::Relaxed);
+        if size_lamports > max_position {
             debug!(
                 "Position size {} exceeds max {} lamports",
-                size_lamports, self.max_position_lamports
+                size_lamports, max_position
             );
             return false;
         }
 
-        // Check against available capital (using atomic load)
-        let current_in_flight = self.in_flight_lamports.load(Ordering::Relaxed);
-        let total_capital = self.total_capital_lamports.load(Ordering::Relaxed);
-        let available = total_capital.saturating_sub(current_in_flight);
+        size_lamports <= self.available_lamports()
+    }
+
+    /// Tradeable capital not committed to positions or in-flight tips
+    fn available_lamports(&self) -> u64 {
+        self.total_capital_lamports
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.in_flight_lamports.load(Ordering::Relaxed))
+            .saturating_sub(self.in_flight_tip_lamports.load(Ordering::Relaxed))
+    }
 
-        size_lamports <= available
+    /// Current fee reserve: base reserve + tips of in-flight bundles (lamports)
+    pub fn fee_reserve_lamports(&self) -> u64 {
+        self.fee_reserve_lamports.load(Ordering::Relaxed)
+            + self.in_flight_tip_lamports.load(Ordering::Relaxed)
+    }
+
+    /// JITO tips of queued, unresolved bundles (lamports)
+    pub fn in_flight_tip_lamports(&self) -> u64 {
+        self.in_flight_tip_lamports.load(Ordering::Relaxed)
+    }
+
+    /// Reserve a bundle's JITO tip before queueing it (atomic operation)
+    ///
+    /// The wallet must keep `in_flight_tips + base_reserve` after every queued
+    /// bundle pays out, so a tip is only accepted if it fits in the capital not
+    /// already committed to positions or other tips.
+    ///
+    /// # Arguments
+    /// * `tip_lamports` - Tip paid by the bundle about to be queued
+    ///
+    /// # Returns
+    /// Ok(()) if reserved, Err if the tip would eat into the base fee reserve
+    pub fn reserve_tip(&self, tip_lamports: u64) -> ArbResult<()> {
+        loop {
+            let current_tips = self.in_flight_tip_lamports.load(Ordering::Acquire);
+            let committed = self.in_flight_lamports.load(Ordering::Relaxed) + current_tips;
+            let total_capital = self.total_capital_lamports.load(Ordering::Relaxed);
+
+            if committed + tip_lamports > total_capital {
+                return Err(ArbError::InsufficientCapital(format!(
+                    "tip of {} lamports would breach the fee reserve ({:.4} SOL in-flight tips + {:.4} SOL base)",
+                    tip_lamports,
+                    current_tips as f64 / 1_000_000_000.0,
+                    self.fee_reserve_lamports.load(Ordering::Relaxed) as f64 / 1_000_000_000.0
+                )));
+            }
+
+            if self
+                .in_flight_tip_lamports
+                .compare_exchange(
+                    current_tips,
+                    current_tips + tip_lamports,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                debug!(
+                    "✅ Reserved {} lamport tip. In-flight tips: {} lamports",
+                    tip_lamports,
+                    current_tips + tip_lamports
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    /// Release a tip once its bundle resolved (landed, dropped or failed to queue)
+    pub fn release_tip(&self, tip_lamports: u64) {
+        let result = self.in_flight_tip_lamports.fetch_update(
+            Ordering::Release,
+            Ordering::Relaxed,
+            |tips| Some(tips.saturating_sub(tip_lamports)),
+        );
+        if let Ok(previous) = result {
+            if previous < tip_lamports {
+                warn!(
+                    "⚠️ Released {} lamport tip but only {} were in-flight",
+                    tip_lamports, previous
+                );
+            }
+        }
     }
 
     /// Update total capital based on actual wallet balance
@@ -110,7 +357,8 @@ impl PositionTracker {
     /// ```
     pub fn update_from_wallet_balance(&self, wallet_balance_lamports: u64) -> u64 {
         // Calculate tradeable balance (wallet - fee reserve)
-        let tradeable = wallet_balance_lamports.saturating_sub(self.fee_reserve_lamports);
+        let tradeable = wallet_balance_lamports
+            .saturating_sub(self.fee_reserve_lamports.load(Ordering::Acquire));
 
         // Update total capital atomically
         let old_capital = self
@@ -124,10 +372,16 @@ impl PositionTracker {
 
             info!("💰 Capital updated from wallet balance:");
             info!("   Wallet balance: {:.6} SOL", wallet_sol);
-            info!("   Fee reserve: 0.1 SOL (protected)");
+            info!(
+                "   Fee reserve: {:.4} SOL (protected, incl. {:.4} SOL in-flight tips)",
+                self.fee_reserve_lamports() as f64 / 1_000_000_000.0,
+                self.in_flight_tip_lamports() as f64 / 1_000_000_000.0
+            );
             info!("   Tradeable: {:.6} SOL (was {:.6} SOL)", new_sol, old_sol);
         }
 
+        self.rescale_max_position(tradeable);
+
         tradeable
     }
 
@@ -144,14 +398,11 @@ impl PositionTracker {
     /// - Cap at max_position_lamports for risk management
     /// - Cap at opportunity size (don't trade more than needed)
     pub fn get_dynamic_position_size(&self, opportunity_size_lamports: u64) -> u64 {
-        let total_capital = self.total_capital_lamports.load(Ordering::Relaxed);
-        let in_flight = self.in_flight_lamports.load(Ordering::Relaxed);
-        let available = total_capital.saturating_sub(in_flight);
+        let available = self.available_lamports();
+        let max_position = self.max_position_lamports.load(Ordering::Relaxed);
 
         // Use minimum of: opportunity size, available capital, max position
-        let position_size = opportunity_size_lamports
-            .min(available)
-            .min(self.max_position_lamports);
+        let position_size = opportunity_size_lamports.min(available).min(max_position);
 
         debug!("📊 Dynamic position sizing:");
         debug!(
@@ -159,10 +410,7 @@ impl PositionTracker {
             opportunity_size_lamports as f64 / 1e9
         );
         debug!("   Available capital: {:.6} SOL", available as f64 / 1e9);
-        debug!(
-            "   Max position: {:.6} SOL",
-            self.max_position_lamports as f64 / 1e9
-        );
+        debug!("   Max position: {:.6} SOL", max_position as f64 / 1e9);
         debug!("   Position size: {:.6} SOL", position_size as f64 / 1e9);
 
         position_size
@@ -171,20 +419,22 @@ impl PositionTracker {
     /// Reserve capital for a new position (atomic operation)
     ///
     /// # Arguments
+    /// * `mints` - Token mints the trade goes through (per-token overrides apply)
     /// * `amount_lamports` - Amount to reserve in lamports
     ///
     /// # Returns
     /// Ok(()) if reservation successful, Err if insufficient capital
-    pub fn reserve_capital(&self, amount_lamports: u64) -> Result<()> {
-        // Validate against max position size
-        if amount_lamports > self.max_position_lamports {
-            return Err(anyhow!(
+    pub fn reserve_capital(&self, mints: &[&str], amount_lamports: u64) -> ArbResult<()> {
+        // Validate against the per-token (or global) max position size
+        let max_position = self.max_position_lamports_for(mints);
+        if amount_lamports > max_position {
+            return Err(ArbError::InsufficientCapital(format!(
                 "Position size {} lamports exceeds max {} lamports ({:.4} SOL > {:.4} SOL)",
                 amount_lamports,
-                self.max_position_lamports,
+                max_position,
                 amount_lamports as f64 / 1_000_000_000.0,
-                self.max_position_lamports as f64 / 1_000_000_000.0
-            ));
+                max_position as f64 / 1_000_000_000.0
+            )));
         }
 
         // Atomic compare-and-swap loop
@@ -192,18 +442,22 @@ impl PositionTracker {
         loop {
             let current = self.in_flight_lamports.load(Ordering::Acquire);
             let new_total = current + amount_lamports;
-            let total_capital = self.total_capital_lamports.load(Ordering::Relaxed);
+            // In-flight tips are part of the fee reserve, not tradeable
+            let total_capital = self
+                .total_capital_lamports
+                .load(Ordering::Relaxed)
+                .saturating_sub(self.in_flight_tip_lamports.load(Ordering::Relaxed));
 
             // Check if we have enough capital
             if new_total > total_capital {
-                let available = total_capital - current;
-                return Err(anyhow!(
-                    "Insufficient capital: {} lamports needed, {} lamports available ({:.4} SOL needed, {:.4} SOL available)",
+                let available = total_capital.saturating_sub(current);
+                return Err(ArbError::InsufficientCapital(format!(
+                    "{} lamports needed, {} lamports available ({:.4} SOL needed, {:.4} SOL available)",
                     amount_lamports,
                     available,
                     amount_lamports as f64 / 1_000_000_000.0,
                     available as f64 / 1_000_000_000.0
-                ));
+                )));
             }
 
             // Try to atomically update in_flight amount
@@ -267,7 +521,7 @@ impl PositionTracker {
     pub fn get_stats(&self) -> PositionStats {
         let in_flight = self.in_flight_lamports.load(Ordering::Relaxed);
         let total_capital = self.total_capital_lamports.load(Ordering::Relaxed);
-        let available = total_capital.saturating_sub(in_flight);
+        let available = self.available_lamports();
         let utilization_pct = (in_flight as f64 / total_capital as f64) * 100.0;
 
         PositionStats {
@@ -275,7 +529,9 @@ impl PositionTracker {
             in_flight_sol: in_flight as f64 / 1_000_000_000.0,
             available_sol: available as f64 / 1_000_000_000.0,
             utilization_pct,
-            max_position_sol: self.max_position_lamports as f64 / 1_000_000_000.0,
+            max_position_sol: self.max_position_sol(),
+            fee_reserve_sol: self.fee_reserve_lamports() as f64 / 1_000_000_000.0,
+            in_flight_tips_sol: self.in_flight_tip_lamports() as f64 / 1_000_000_000.0,
         }
     }
 
@@ -302,13 +558,21 @@ impl PositionTracker {
 }
 
 /// Position tracker statistics
-#[derive(Debug, Clone)]
+/// Base fee reserve scaled with congestion: `multiple` × p99 tip, never below `min_lamports`
+fn dynamic_fee_reserve_lamports(min_lamports: u64, multiple: f64, p99_tip_lamports: u64) -> u64 {
+    ((p99_tip_lamports as f64 * multiple) as u64).max(min_lamports)
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PositionStats {
     pub total_capital_sol: f64,
     pub in_flight_sol: f64,
     pub available_sol: f64,
     pub utilization_pct: f64,
     pub max_position_sol: f64,
+    /// Base reserve + in-flight tips
+    pub fee_reserve_sol: f64,
+    pub in_flight_tips_sol: f64,
 }
 
 #[cfg(test)]
@@ -335,14 +599,14 @@ mod tests {
         let tracker = PositionTracker::new(2.0, 0.5);
 
         // Reserve first position
-        assert!(tracker.reserve_capital(500_000_000).is_ok()); // 0.5 SOL
+        assert!(tracker.reserve_capital(&[], 500_000_000).is_ok()); // 0.5 SOL
 
         // Check available reduced
         assert!(tracker.can_open_position(500_000_000)); // Still have 1.5 SOL
         assert!(!tracker.can_open_position(2_000_000_000)); // But not 2 SOL
 
         // Reserve second position
-        assert!(tracker.reserve_capital(500_000_000).is_ok()); // 0.5 SOL more
+        assert!(tracker.reserve_capital(&[], 500_000_000).is_ok()); // 0.5 SOL more
 
         // Now only 1 SOL left
         assert!(tracker.can_open_position(500_000_000));
@@ -361,13 +625,13 @@ mod tests {
         let tracker = PositionTracker::new(1.0, 0.5);
 
         // Reserve 0.5 SOL
-        assert!(tracker.reserve_capital(500_000_000).is_ok());
+        assert!(tracker.reserve_capital(&[], 500_000_000).is_ok());
 
         // Reserve another 0.5 SOL
-        assert!(tracker.reserve_capital(500_000_000).is_ok());
+        assert!(tracker.reserve_capital(&[], 500_000_000).is_ok());
 
         // Try to reserve more - should fail (only 1 SOL total)
-        let result = tracker.reserve_capital(100_000_000); // 0.1 SOL
+        let result = tracker.reserve_capital(&[], 100_000_000); // 0.1 SOL
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -380,11 +644,98 @@ mod tests {
         let tracker = PositionTracker::new(2.0, 0.5);
 
         // Try to reserve 0.6 SOL (exceeds max 0.5)
-        let result = tracker.reserve_capital(600_000_000);
+        let result = tracker.reserve_capital(&[], 600_000_000);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("exceeds max"));
     }
 
+    #[test]
+    fn test_position_overrides_per_token() {
+        let overrides = HashMap::from([
+            ("MemeMint".to_string(), 0.05),
+            ("UsdcMint".to_string(), 1.5),
+        ]);
+        let tracker = PositionTracker::new(2.0, 0.5).with_position_overrides(&overrides);
+
+        // Override replaces the global max in both directions
+        assert_eq!(tracker.max_position_sol_for(&["MemeMint"]), 0.05);
+        assert_eq!(tracker.max_position_sol_for(&["UsdcMint"]), 1.5);
+        assert_eq!(tracker.max_position_sol_for(&["SOL", "OtherMint"]), 0.5);
+        // A path through several tokens takes the tightest override
+        assert_eq!(
+            tracker.max_position_sol_for(&["UsdcMint", "MemeMint"]),
+            0.05
+        );
+
+        // Reservation is checked against the token's effective size
+        assert!(tracker.reserve_capital(&["MemeMint"], 60_000_000).is_err());
+        assert!(tracker
+            .reserve_capital(&["UsdcMint"], 1_000_000_000)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_in_flight_tips_grow_fee_reserve() {
+        let tracker = PositionTracker::new(1.0, 0.5);
+        assert_eq!(tracker.fee_reserve_lamports(), 100_000_000);
+
+        // Two queued bundles: their tips join the protected reserve
+        assert!(tracker.reserve_capital(&[], 500_000_000).is_ok());
+        assert!(tracker.reserve_tip(200_000_000).is_ok());
+        assert!(tracker.reserve_tip(200_000_000).is_ok());
+        assert_eq!(tracker.in_flight_tip_lamports(), 400_000_000);
+        assert_eq!(tracker.fee_reserve_lamports(), 500_000_000);
+
+        // Another tip would leave less than the base reserve after all bundles pay
+        assert!(tracker.reserve_tip(200_000_000).is_err());
+        // Tips also shrink the capital available for new positions
+        assert!(!tracker.can_open_position(200_000_000));
+        assert!(tracker.reserve_capital(&[], 200_000_000).is_err());
+
+        // Resolved bundles free their tips
+        tracker.release_tip(200_000_000);
+        assert!(tracker.reserve_tip(200_000_000).is_ok());
+        tracker.release_tip(400_000_000);
+        assert_eq!(tracker.in_flight_tip_lamports(), 0);
+        assert_eq!(tracker.get_stats().in_flight_tips_sol, 0.0);
+    }
+
+    #[test]
+    fn test_tradeable_is_balance_minus_fixed_or_dynamic_reserve() {
+        // Fixed 0.05 SOL reserve: tip floor changes are ignored
+        let fixed = PositionTracker::new(2.0, 0.5).with_fee_reserve(0.05, None);
+        assert_eq!(
+            fixed.update_fee_reserve_from_tip_floor(10_000_000),
+            50_000_000
+        );
+        assert_eq!(fixed.update_from_wallet_balance(1_000_000_000), 950_000_000);
+
+        // Dynamic: 50x p99 tip, never below 0.05 SOL
+        let dynamic = PositionTracker::new(2.0, 0.5).with_fee_reserve(0.05, Some(50.0));
+        // Calm (p99 = 0.0001 SOL): 50x = 0.005 SOL, the 0.05 SOL floor applies
+        assert_eq!(
+            dynamic.update_fee_reserve_from_tip_floor(100_000),
+            50_000_000
+        );
+        assert_eq!(
+            dynamic.update_from_wallet_balance(1_000_000_000),
+            950_000_000
+        );
+        // Congested (p99 = 0.003 SOL): reserve grows to 0.15 SOL
+        assert_eq!(
+            dynamic.update_fee_reserve_from_tip_floor(3_000_000),
+            150_000_000
+        );
+        assert_eq!(
+            dynamic.update_from_wallet_balance(1_000_000_000),
+            850_000_000
+        );
+        assert_eq!(dynamic.fee_reserve_lamports(), 150_000_000);
+
+        // Reserve larger than the wallet leaves nothing tradeable
+        assert_eq!(dynamic.update_from_wallet_balance(100_000_000), 0);
+    }
+
     #[test]
     fn test_stats() {
         let tracker = PositionTracker::new(2.0, 0.5);
@@ -396,7 +747,7 @@ mod tests {
         assert_eq!(stats.utilization_pct, 0.0);
 
         // Reserve some capital
-        tracker.reserve_capital(1_000_000_000).unwrap(); // 1 SOL
+        tracker.reserve_capital(&[], 1_000_000_000).unwrap(); // 1 SOL
 
         let stats = tracker.get_stats();
         assert_eq!(stats.in_flight_sol, 1.0);
@@ -404,6 +755,28 @@ mod tests {
         assert_eq!(stats.utilization_pct, 50.0);
     }
 
+    #[test]
+    fn test_max_position_scales_with_balance() {
+        let tracker = PositionTracker::new(2.0, 0.5).with_max_position_pct(Some(0.25));
+        // 25% of the initial 2 SOL capital is exactly the 0.5 SOL cap
+        assert_eq!(tracker.max_position_sol(), 0.5);
+
+        // 1.1 SOL wallet -> 1.0 SOL tradeable after reserve -> 0.25 SOL max
+        tracker.update_from_wallet_balance(1_100_000_000);
+        assert_eq!(tracker.max_position_sol(), 0.25);
+        assert!(tracker.can_open_position(250_000_000));
+        assert!(!tracker.can_open_position(250_000_001));
+
+        // Balance growth is clamped by the absolute cap
+        tracker.update_from_wallet_balance(10_100_000_000);
+        assert_eq!(tracker.max_position_sol(), 0.5);
+
+        // Without a pct the cap stays fixed
+        let fixed = PositionTracker::new(2.0, 0.5);
+        fixed.update_from_wallet_balance(1_100_000_000);
+        assert_eq!(fixed.max_position_sol(), 0.5);
+    }
+
     #[test]
     fn test_concurrent_reservations() {
         use std::sync::Arc;
@@ -416,7 +789,7 @@ mod tests {
         for i in 0..20 {
             let tracker_clone = tracker.clone();
             let handle = thread::spawn(move || {
-                tracker_clone.reserve_capital(500_000_000) // 0.5 SOL
+                tracker_clone.reserve_capital(&[], 500_000_000) // 0.5 SOL
             });
             handles.push(handle);
         }
@@ -0,0 +1,211 @@
+// USD overlay for profit stats and loss/profit limits
+//
+// The ledger stays in lamports/SOL. With PROFIT_CURRENCY=USD the bot also
+// reports profit in USD and enforces DAILY_LOSS_LIMIT_USD and
+// DAILY_PROFIT_TARGET_USD against a cached SOL/USD price, refreshed every
+// SOL_USD_REFRESH_SECS from Jupiter or SOL_USD_PRICE_URL. Until the first
+// price arrives (or when a USD limit is unset) the SOL limits apply.
+
+use anyhow::{bail, Context, Result};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::jupiter_prices::JupiterPriceClient;
+
+/// Wrapped SOL mint (Jupiter prices it in USD)
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// JSON pointer read from SOL_USD_PRICE_URL responses without a `#<pointer>` suffix
+const DEFAULT_PRICE_POINTER: &str = "/price";
+
+/// Oracle request timeout
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Currency profit stats and limits are expressed in (PROFIT_CURRENCY)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProfitCurrency {
+    #[default]
+    Sol,
+    Usd,
+}
+
+impl ProfitCurrency {
+    /// Parse a PROFIT_CURRENCY value (`SOL` or `USD`, case-insensitive)
+    ///
+    /// # Errors
+    /// Returns error for any other value
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "SOL" => Ok(Self::Sol),
+            "USD" => Ok(Self::Usd),
+            other => bail!("Invalid PROFIT_CURRENCY '{}': expected SOL or USD", other),
+        }
+    }
+}
+
+impl fmt::Display for ProfitCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sol => write!(f, "SOL"),
+            Self::Usd => write!(f, "USD"),
+        }
+    }
+}
+
+/// Convert a SOL amount (profit, loss, limit) to USD
+pub fn sol_to_usd(amount_sol: f64, sol_usd: f64) -> f64 {
+    amount_sol * sol_usd
+}
+
+/// Cached SOL/USD price shared with the refresh task (None until first fetch)
+#[derive(Debug, Clone, Default)]
+pub struct SolUsdPrice(Arc<RwLock<Option<f64>>>);
+
+impl SolUsdPrice {
+    /// Latest SOL/USD price, None if never fetched
+    pub fn get(&self) -> Option<f64> {
+        match self.0.read() {
+            Ok(price) => *price,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    /// Replace the cached price
+    pub fn set(&self, sol_usd: f64) {
+        match self.0.write() {
+            Ok(mut price) => *price = Some(sol_usd),
+            Err(poisoned) => *poisoned.into_inner() = Some(sol_usd),
+        }
+    }
+}
+
+/// Spawn the SOL/USD refresh task
+///
+/// # Arguments
+/// * `jupiter_api_key` - Jupiter API key (used when no oracle URL is set)
+/// * `price_url` - Oracle endpoint, optionally suffixed `#<JSON pointer>` (SOL_USD_PRICE_URL)
+/// * `refresh` - Interval between fetches (SOL_USD_REFRESH_SECS)
+///
+/// # Returns
+/// Shared price, updated in the background; a failed fetch keeps the last price
+pub fn spawn_monitor(
+    jupiter_api_key: Option<String>,
+    price_url: Option<String>,
+    refresh: Duration,
+) -> SolUsdPrice {
+    let price = SolUsdPrice::default();
+    let shared = price.clone();
+
+    tokio::spawn(async move {
+        let jupiter = JupiterPriceClient::new(jupiter_api_key);
+        let client = reqwest::Client::new();
+        let source = price_url.as_deref().unwrap_or("Jupiter");
+        info!(
+            "💵 SOL/USD monitor started ({}, every {}s)",
+            source,
+            refresh.as_secs()
+        );
+
+        loop {
+            match fetch_sol_usd(&client, &jupiter, price_url.as_deref()).await {
+                Ok(sol_usd) => {
+                    debug!("💵 SOL/USD: ${:.2}", sol_usd);
+                    shared.set(sol_usd);
+                }
+                Err(e) => match shared.get() {
+                    Some(last) => warn!("⚠️ SOL/USD fetch failed, keeping ${:.2}: {:#}", last, e),
+                    None => warn!("⚠️ SOL/USD fetch failed, SOL limits apply: {:#}", e),
+                },
+            }
+            tokio::time::sleep(refresh).await;
+        }
+    });
+
+    price
+}
+
+/// Fetch SOL/USD from the configured oracle, or Jupiter
+///
+/// # Errors
+/// Returns error if the request fails or the response carries no positive price
+async fn fetch_sol_usd(
+    client: &reqwest::Client,
+    jupiter: &JupiterPriceClient,
+    price_url: Option<&str>,
+) -> Result<f64> {
+    let sol_usd = match price_url {
+        Some(price_url) => {
+            let (url, pointer) = match price_url.split_once('#') {
+                Some((url, pointer)) => (url, pointer),
+                None => (price_url, DEFAULT_PRICE_POINTER),
+            };
+            let body: serde_json::Value = client
+                .get(url)
+                .timeout(FETCH_TIMEOUT)
+                .send()
+                .await
+                .with_context(|| format!("SOL/USD request to {} failed", url))?
+                .json()
+                .await
+                .context("SOL/USD response is not JSON")?;
+            parse_oracle_price(&body, pointer)?
+        }
+        None => jupiter.fetch_price(SOL_MINT).await?,
+    };
+
+    if !sol_usd.is_finite() || sol_usd <= 0.0 {
+        bail!("Invalid SOL/USD price: {}", sol_usd);
+    }
+    Ok(sol_usd)
+}
+
+/// Read the price at `pointer` from an oracle response (number or numeric string)
+///
+/// # Errors
+/// Returns error if the pointer is missing or not a number
+fn parse_oracle_price(body: &serde_json::Value, pointer: &str) -> Result<f64> {
+    let value = body
+        .pointer(pointer)
+        .with_context(|| format!("SOL/USD response has no '{}'", pointer))?;
+    let price = match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(text) => text.parse().ok(),
+        _ => None,
+    };
+    price.with_context(|| format!("SOL/USD '{}' is not a number: {}", pointer, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sol_pnl_converts_to_usd_at_fixed_rate() {
+        let price = SolUsdPrice::default();
+        assert_eq!(price.get(), None);
+        price.set(150.0);
+        let sol_usd = price.get().unwrap();
+
+        // +0.25 SOL profit, -0.1 SOL loss at $150/SOL
+        assert!((sol_to_usd(0.25, sol_usd) - 37.5).abs() < 1e-9);
+        assert!((sol_to_usd(-0.1, sol_usd) + 15.0).abs() < 1e-9);
+        // Same SOL loss breaches a $20 limit only once SOL rallies past $200
+        assert!(sol_to_usd(0.1, sol_usd) < 20.0);
+        assert!(sol_to_usd(0.1, 210.0) > 20.0);
+    }
+
+    #[test]
+    fn test_parse_currency_and_oracle_price() {
+        assert_eq!(ProfitCurrency::parse("usd").unwrap(), ProfitCurrency::Usd);
+        assert_eq!(ProfitCurrency::parse(" SOL ").unwrap(), ProfitCurrency::Sol);
+        assert!(ProfitCurrency::parse("EUR").is_err());
+
+        let body = serde_json::json!({"price": 151.25, "data": {"amount": "149.80"}});
+        assert_eq!(parse_oracle_price(&body, "/price").unwrap(), 151.25);
+        assert_eq!(parse_oracle_price(&body, "/data/amount").unwrap(), 149.80);
+        assert!(parse_oracle_price(&body, "/missing").is_err());
+        assert!(parse_oracle_price(&body, "/data").is_err());
+    }
+}
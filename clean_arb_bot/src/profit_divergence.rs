@@ -0,0 +1,152 @@
+// Realized vs estimated profit tracker
+//
+// Every landed bundle whose wallet balance delta is known records
+// (realized - estimated) net profit under its DEX route (e.g.
+// "Orca_Whirlpools/Meteora_DLMM"). The estimate is the cost calculator's net
+// profit at execution time, so a route whose rolling mean sits more than
+// PROFIT_DIVERGENCE_ALERT_SOL below zero means the cost model or slippage
+// assumptions are too optimistic for it.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Most recent samples kept per route
+const DIVERGENCE_WINDOW: usize = 50;
+
+/// Samples a route needs before it can be flagged as biased
+const MIN_SAMPLES_FOR_ALERT: usize = 5;
+
+/// Rolling (realized - estimated) profit statistics for one DEX route
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteBias {
+    pub route: String,
+    pub samples: usize,
+    pub mean_sol: f64,   // Negative = realized trails the estimate
+    pub stddev_sol: f64, // Population standard deviation
+}
+
+/// Per-route rolling window of realized minus estimated profit
+pub struct ProfitDivergenceTracker {
+    alert_threshold_sol: f64,
+    samples: HashMap<String, VecDeque<f64>>,
+}
+
+impl ProfitDivergenceTracker {
+    /// Create a tracker flagging routes whose mean shortfall exceeds `alert_threshold_sol`
+    pub fn new(alert_threshold_sol: f64) -> Self {
+        Self {
+            alert_threshold_sol,
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Record one landed trade
+    ///
+    /// # Arguments
+    /// * `route` - DEX route key the trade went through
+    /// * `estimated_sol` - Net profit the cost calculator predicted
+    /// * `realized_sol` - Net profit from the wallet balance delta
+    ///
+    /// # Returns
+    /// The route's statistics if realized profit now trails the estimate by
+    /// more than the alert threshold on average
+    pub fn record(
+        &mut self,
+        route: &str,
+        estimated_sol: f64,
+        realized_sol: f64,
+    ) -> Option<RouteBias> {
+        let divergence = realized_sol - estimated_sol;
+        if !divergence.is_finite() {
+            return None;
+        }
+
+        let samples = self.samples.entry(route.to_string()).or_default();
+        samples.push_back(divergence);
+        while samples.len() > DIVERGENCE_WINDOW {
+            samples.pop_front();
+        }
+
+        let bias = route_bias(route, samples);
+        self.is_biased(&bias).then_some(bias)
+    }
+
+    /// Whether a route's realized profit consistently trails its estimates
+    pub fn is_biased(&self, bias: &RouteBias) -> bool {
+        bias.samples >= MIN_SAMPLES_FOR_ALERT && bias.mean_sol < -self.alert_threshold_sol
+    }
+
+    /// Statistics for every route, most negative mean first
+    pub fn biases(&self) -> Vec<RouteBias> {
+        let mut biases: Vec<RouteBias> = self
+            .samples
+            .iter()
+            .map(|(route, samples)| route_bias(route, samples))
+            .collect();
+        biases.sort_by(|a, b| {
+            a.mean_sol
+                .partial_cmp(&b.mean_sol)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        biases
+    }
+}
+
+/// Mean and standard deviation of a route's samples
+fn route_bias(route: &str, samples: &VecDeque<f64>) -> RouteBias {
+    let n = samples.len().max(1) as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+    RouteBias {
+        route: route.to_string(),
+        samples: samples.len(),
+        mean_sol: mean,
+        stddev_sol: variance.sqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consistent_shortfall_is_flagged_after_min_samples() {
+        let mut tracker = ProfitDivergenceTracker::new(0.001);
+
+        // Realized 0.002 SOL short of estimate every time
+        for _ in 0..MIN_SAMPLES_FOR_ALERT - 1 {
+            assert!(tracker.record("Orca/Raydium", 0.010, 0.008).is_none());
+        }
+        let bias = tracker.record("Orca/Raydium", 0.010, 0.008).unwrap();
+        assert_eq!(bias.samples, MIN_SAMPLES_FOR_ALERT);
+        assert!((bias.mean_sol + 0.002).abs() < 1e-12);
+        assert!(bias.stddev_sol < 1e-12);
+
+        // Noisy but unbiased route: never flagged
+        for i in 0..20 {
+            let noise = if i % 2 == 0 { 0.003 } else { -0.003 };
+            assert!(tracker
+                .record("Meteora/Orca", 0.010, 0.010 + noise)
+                .is_none());
+        }
+
+        let biases = tracker.biases();
+        assert_eq!(biases[0].route, "Orca/Raydium"); // Most negative first
+        assert!((biases[1].stddev_sol - 0.003).abs() < 1e-12);
+        assert!(!tracker.is_biased(&biases[1]));
+    }
+
+    #[test]
+    fn test_window_drops_old_samples() {
+        let mut tracker = ProfitDivergenceTracker::new(0.001);
+        for _ in 0..DIVERGENCE_WINDOW {
+            tracker.record("Orca/Raydium", 0.010, 0.005);
+        }
+        // Estimator fixed: a full window of accurate trades clears the bias
+        for _ in 0..DIVERGENCE_WINDOW {
+            tracker.record("Orca/Raydium", 0.010, 0.010);
+        }
+        let bias = &tracker.biases()[0];
+        assert_eq!(bias.samples, DIVERGENCE_WINDOW);
+        assert!(bias.mean_sol.abs() < 1e-12);
+    }
+}
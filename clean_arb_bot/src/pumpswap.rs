@@ -22,14 +22,14 @@ use solana_sdk::{
 use std::str::FromStr;
 use tracing::{debug, info};
 
-use crate::rpc_client::SolanaRpcClient;
+use crate::amm_math::{amount_after_fee, constant_product_output, token_account_amount};
+use crate::errors::ArbResult;
+use crate::rpc_client::RpcApi;
+use crate::token_program::associated_token_address;
 
 /// PumpSwap program ID
 pub const PUMPSWAP_PROGRAM_ID: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
 
-/// SPL Token program ID
-const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
-
 /// Associated Token Account program ID
 const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
 
@@ -42,6 +42,15 @@ const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
 /// SELL instruction discriminator (from ShredStream parser)
 const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
 
+/// Total swap fee in basis points (0.2% LP + 0.05% protocol)
+const SWAP_FEE_BPS: u64 = 25;
+
+/// Share of the output vault that must remain after a swap (bps)
+///
+/// x * y = k never pays out the whole reserve, but a swap taking most of it
+/// moves price so far that the quote is meaningless by the time it lands.
+const MIN_REMAINING_RESERVE_BPS: u128 = 1_000;
+
 /// PumpSwap pool accounts structure
 /// Parsed from 300-byte pool account (PDA owned by PumpSwap program)
 ///
@@ -54,17 +63,24 @@ pub struct PumpSwapPool {
     pub quote_mint: Pubkey, // SOL/WSOL mint (offset 75, +8)
     pub pool_base_account: Pubkey, // Pool's token vault (offset 139, +8) - NOT USED IN SWAPS
     pub pool_quote_account: Pubkey, // Pool's SOL vault (offset 171, +8) - NOT USED IN SWAPS
+    pub base_reserve: u64, // Token balance held in pool_base_account
+    pub quote_reserve: u64, // SOL balance held in pool_quote_account
 }
 
 /// PumpSwap swap builder
 pub struct PumpSwapSwapBuilder {
     program_id: Pubkey,
-    rpc_client: std::sync::Arc<SolanaRpcClient>,
+    rpc_client: std::sync::Arc<dyn RpcApi>,
 }
 
 impl PumpSwapSwapBuilder {
+    /// Estimated compute units for one PumpSwap swap (curve math + protocol fee transfers)
+    pub fn estimated_compute_units(&self) -> u32 {
+        80_000
+    }
+
     /// Create new PumpSwap swap builder
-    pub fn new(rpc_client: std::sync::Arc<SolanaRpcClient>) -> Result<Self> {
+    pub fn new(rpc_client: std::sync::Arc<dyn RpcApi>) -> Result<Self> {
         let program_id =
             Pubkey::from_str(PUMPSWAP_PROGRAM_ID).context("Invalid PumpSwap program ID")?;
 
@@ -88,7 +104,7 @@ impl PumpSwapSwapBuilder {
         amount_in: u64,
         minimum_amount_out: u64,
         swap_a_to_b: bool, // true = SOL -> Token (BUY), false = Token -> SOL (SELL)
-    ) -> Result<Instruction> {
+    ) -> ArbResult<Instruction> {
         debug!("🔨 Building PumpSwap swap instruction (CORRECT 12-ACCOUNT STRUCTURE)");
         debug!("   Pool: {}", pool.pool_address);
         debug!("   Amount in: {}", amount_in);
@@ -102,15 +118,21 @@ impl PumpSwapSwapBuilder {
             }
         );
 
-        // Get user's token accounts
-        let user_base_account = spl_associated_token_account::get_associated_token_address(
-            user_wallet,
-            &pool.base_mint,
-        );
-        let user_quote_account = spl_associated_token_account::get_associated_token_address(
-            user_wallet,
-            &pool.quote_mint,
-        );
+        // Newer pump tokens are Token-2022 mints; WSOL is always classic SPL Token
+        let base_token_program = self
+            .rpc_client
+            .get_mint_token_program(&pool.base_mint)?
+            .program;
+        let quote_token_program = self
+            .rpc_client
+            .get_mint_token_program(&pool.quote_mint)?
+            .program;
+
+        // Get user's token accounts (derived under each mint's token program)
+        let user_base_account =
+            associated_token_address(user_wallet, &pool.base_mint, base_token_program);
+        let user_quote_account =
+            associated_token_address(user_wallet, &pool.quote_mint, quote_token_program);
 
         // Build instruction data
         let mut data = Vec::with_capacity(24);
@@ -204,8 +226,8 @@ impl PumpSwapSwapBuilder {
                 AccountMeta::new_readonly(global_config, false),
                 // 9: event_authority (read-only, PDA)
                 AccountMeta::new_readonly(event_authority, false),
-                // 10: token_program (read-only)
-                AccountMeta::new_readonly(Pubkey::from_str(SPL_TOKEN_PROGRAM_ID).unwrap(), false),
+                // 10: token_program (read-only) - the base token's program (quote is WSOL)
+                AccountMeta::new_readonly(base_token_program.id(), false),
                 // 11: associated_token_program (read-only)
                 AccountMeta::new_readonly(
                     Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).unwrap(),
@@ -238,7 +260,7 @@ impl PumpSwapSwapBuilder {
         // Fetch pool account data
         let pool_data = self
             .rpc_client
-            .get_account_data(pool_address)
+            .get_account_data(pool_address, None)
             .context("Failed to fetch PumpSwap pool data")?;
 
         if pool_data.len() < 203 {
@@ -258,11 +280,27 @@ impl PumpSwapSwapBuilder {
         let pool_quote_account = Pubkey::try_from(&pool_data[171..203])
             .map_err(|_| anyhow::anyhow!("Invalid pool quote account pubkey"))?;
 
+        // Fetch both vault balances in one call (curve reserves for output estimation)
+        let vault_data = self
+            .rpc_client
+            .get_multiple_accounts(&[pool_base_account, pool_quote_account])
+            .context("Failed to fetch PumpSwap pool vaults")?;
+        let base_reserve = token_account_amount(vault_data[0].as_deref())
+            .context("Invalid PumpSwap base vault")?;
+        let quote_reserve = token_account_amount(vault_data[1].as_deref())
+            .context("Invalid PumpSwap quote vault")?;
+
         debug!("✅ PumpSwap pool info parsed");
         debug!("   Base mint (token): {}", base_mint);
         debug!("   Quote mint (SOL): {}", quote_mint);
-        debug!("   Pool base vault: {}", pool_base_account);
-        debug!("   Pool quote vault: {}", pool_quote_account);
+        debug!(
+            "   Pool base vault: {} ({} reserve)",
+            pool_base_account, base_reserve
+        );
+        debug!(
+            "   Pool quote vault: {} ({} reserve)",
+            pool_quote_account, quote_reserve
+        );
 
         Ok(PumpSwapPool {
             pool_address: *pool_address,
@@ -270,9 +308,88 @@ impl PumpSwapSwapBuilder {
             quote_mint,
             pool_base_account,
             pool_quote_account,
+            base_reserve,
+            quote_reserve,
         })
     }
 
+    /// Estimate swap output using the constant-product curve (x * y = k)
+    ///
+    /// PumpSwap AMM pools are post-migration, so the reserves are the real vault
+    /// balances (the virtual reserves only exist on the pre-migration bonding curve).
+    /// Price impact grows nonlinearly with trade size, unlike a flat % estimate.
+    ///
+    /// # Arguments
+    /// * `pool` - Pool info with reserves from `fetch_pool_info`
+    /// * `amount_in` - Input amount (SOL lamports for BUY, token units for SELL)
+    /// * `swap_a_to_b` - true = SOL -> Token (BUY), false = Token -> SOL (SELL)
+    ///
+    /// # Returns
+    /// Expected output after the 0.25% fee, or error if the trade would leave less
+    /// than 10% of the output reserve (caller should skip the opportunity)
+    pub fn estimate_swap_output(
+        &self,
+        pool: &PumpSwapPool,
+        amount_in: u64,
+        swap_a_to_b: bool,
+    ) -> Result<u64> {
+        let (reserve_in, reserve_out) = if swap_a_to_b {
+            (pool.quote_reserve, pool.base_reserve)
+        } else {
+            (pool.base_reserve, pool.quote_reserve)
+        };
+
+        let amount_out = Self::checked_output(amount_in, reserve_in, reserve_out)?;
+
+        debug!(
+            "PumpSwap estimate: {} in → {} out (reserves in: {}, out: {})",
+            amount_in, amount_out, reserve_in, reserve_out
+        );
+
+        Ok(amount_out)
+    }
+
+    /// Constant-product output after the swap fee, rejecting unfillable swaps
+    ///
+    /// # Errors
+    /// Returns error on empty reserves, dust output, or a swap that would leave
+    /// less than `MIN_REMAINING_RESERVE_BPS` of the output vault
+    fn checked_output(amount_in: u64, reserve_in: u64, reserve_out: u64) -> Result<u64> {
+        if reserve_in == 0 || reserve_out == 0 {
+            return Err(anyhow::anyhow!(
+                "PumpSwap pool has empty reserves (in: {}, out: {})",
+                reserve_in,
+                reserve_out
+            ));
+        }
+
+        let amount_out = constant_product_output(
+            amount_after_fee(amount_in, SWAP_FEE_BPS, 10_000),
+            reserve_in,
+            reserve_out,
+        ) as u128;
+
+        if amount_out == 0 {
+            return Err(anyhow::anyhow!(
+                "Swap of {} too small to produce PumpSwap output",
+                amount_in
+            ));
+        }
+
+        // Output must leave a minimum share of the vault - otherwise the pool is drained
+        let min_remaining = reserve_out as u128 * MIN_REMAINING_RESERVE_BPS / 10_000;
+        if reserve_out as u128 - amount_out < min_remaining {
+            return Err(anyhow::anyhow!(
+                "Swap of {} would drain PumpSwap reserve ({} out of {} available)",
+                amount_in,
+                amount_out,
+                reserve_out
+            ));
+        }
+
+        Ok(amount_out as u64)
+    }
+
     /// Validate that pool is PumpSwap AMM
     pub fn is_pumpswap_pool(&self, pool_address: &Pubkey) -> Result<bool> {
         match self.rpc_client.get_account_owner(pool_address) {
@@ -284,3 +401,41 @@ impl PumpSwapSwapBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_output() {
+        // 100 SOL / 1,000,000 tokens, buy with 1 SOL
+        let out =
+            PumpSwapSwapBuilder::checked_output(1_000_000_000, 100_000_000_000, 1_000_000).unwrap();
+        // 1,000,000 * 0.9975 / 100.9975 = 9876
+        assert_eq!(out, 9876);
+    }
+
+    #[test]
+    fn test_price_impact_is_nonlinear() {
+        let small = PumpSwapSwapBuilder::checked_output(10_000, 1_000_000, 1_000_000).unwrap();
+        let large = PumpSwapSwapBuilder::checked_output(500_000, 1_000_000, 1_000_000).unwrap();
+
+        // 50x the input yields far less than 50x the output
+        assert!(large < small * 50);
+        assert!(large < 1_000_000);
+    }
+
+    #[test]
+    fn test_unfillable_swaps_rejected() {
+        // Dust input produces nothing
+        assert!(PumpSwapSwapBuilder::checked_output(10, 1_000_000, 1_000).is_err());
+
+        // Empty reserves
+        assert!(PumpSwapSwapBuilder::checked_output(100, 0, 1_000).is_err());
+        assert!(PumpSwapSwapBuilder::checked_output(100, 1_000, 0).is_err());
+
+        // 10x the input reserve would take ~91% of the output vault
+        assert!(PumpSwapSwapBuilder::checked_output(10_000_000, 1_000_000, 1_000_000).is_err());
+        assert!(PumpSwapSwapBuilder::checked_output(5_000_000, 1_000_000, 1_000_000).is_ok());
+    }
+}
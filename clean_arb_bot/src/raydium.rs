@@ -17,14 +17,123 @@ use solana_sdk::{
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::errors::{ArbError, ArbResult};
 use crate::pool_registry::PoolRegistry;
-use crate::rpc_client::SolanaRpcClient;
-use crate::types::SwapParams;
+use crate::rpc_client::RpcApi;
+use crate::token_program::{associated_token_address, require_spl_token_program, TokenProgram};
+use crate::types::{DexType, SwapParams};
+
+/// Raydium CLMM ticks per tick array account
+const CLMM_TICK_ARRAY_SIZE: i32 = 60;
+
+/// Tick arrays passed to a CLMM swap (current + next two in swap direction)
+const CLMM_SWAP_TICK_ARRAY_COUNT: i32 = 3;
+
+/// Raydium CLMM PoolState field offsets (after 8-byte Anchor discriminator)
+const CLMM_AMM_CONFIG_OFFSET: usize = 9;
+const CLMM_TOKEN_MINT_0_OFFSET: usize = 73;
+const CLMM_TOKEN_MINT_1_OFFSET: usize = 105;
+const CLMM_TOKEN_VAULT_0_OFFSET: usize = 137;
+const CLMM_TOKEN_VAULT_1_OFFSET: usize = 169;
+const CLMM_OBSERVATION_KEY_OFFSET: usize = 201;
+const CLMM_TICK_SPACING_OFFSET: usize = 235;
+const CLMM_TICK_CURRENT_OFFSET: usize = 269;
+
+/// Raydium CLMM pool fields needed to build a swap
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClmmPoolState {
+    pub amm_config: Pubkey,
+    pub token_mint_0: Pubkey,
+    pub token_mint_1: Pubkey,
+    pub token_vault_0: Pubkey,
+    pub token_vault_1: Pubkey,
+    pub observation_key: Pubkey,
+    pub tick_spacing: u16,
+    pub tick_current: i32,
+}
+
+impl ClmmPoolState {
+    /// Parse Raydium CLMM PoolState account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < CLMM_TICK_CURRENT_OFFSET + 4 {
+            return Err(anyhow::anyhow!(
+                "CLMM pool state too short ({} bytes, need at least {})",
+                data.len(),
+                CLMM_TICK_CURRENT_OFFSET + 4
+            ));
+        }
+
+        let pubkey_at = |offset: usize| -> Result<Pubkey> {
+            Pubkey::try_from(&data[offset..offset + 32])
+                .map_err(|_| anyhow::anyhow!("Invalid pubkey at CLMM offset {}", offset))
+        };
+
+        let tick_spacing = u16::from_le_bytes(
+            data[CLMM_TICK_SPACING_OFFSET..CLMM_TICK_SPACING_OFFSET + 2].try_into()?,
+        );
+        if tick_spacing == 0 {
+            return Err(anyhow::anyhow!("Invalid CLMM pool: tick_spacing is 0"));
+        }
+
+        Ok(Self {
+            amm_config: pubkey_at(CLMM_AMM_CONFIG_OFFSET)?,
+            token_mint_0: pubkey_at(CLMM_TOKEN_MINT_0_OFFSET)?,
+            token_mint_1: pubkey_at(CLMM_TOKEN_MINT_1_OFFSET)?,
+            token_vault_0: pubkey_at(CLMM_TOKEN_VAULT_0_OFFSET)?,
+            token_vault_1: pubkey_at(CLMM_TOKEN_VAULT_1_OFFSET)?,
+            observation_key: pubkey_at(CLMM_OBSERVATION_KEY_OFFSET)?,
+            tick_spacing,
+            tick_current: i32::from_le_bytes(
+                data[CLMM_TICK_CURRENT_OFFSET..CLMM_TICK_CURRENT_OFFSET + 4].try_into()?,
+            ),
+        })
+    }
+}
+
+/// Start tick index of the CLMM tick array containing `tick`
+///
+/// Arrays cover `tick_spacing * 60` ticks; negative ticks round toward -inf
+pub fn clmm_tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_per_array = tick_spacing as i32 * CLMM_TICK_ARRAY_SIZE;
+    tick.div_euclid(ticks_per_array) * ticks_per_array
+}
+
+/// Start indexes of the tick arrays a CLMM swap traverses
+///
+/// `zero_for_one` (token0 → token1) moves price down, so arrays step toward lower ticks
+pub fn clmm_swap_tick_array_start_indexes(
+    tick_current: i32,
+    tick_spacing: u16,
+    zero_for_one: bool,
+) -> Vec<i32> {
+    let ticks_per_array = tick_spacing as i32 * CLMM_TICK_ARRAY_SIZE;
+    let start = clmm_tick_array_start_index(tick_current, tick_spacing);
+    let step = if zero_for_one {
+        -ticks_per_array
+    } else {
+        ticks_per_array
+    };
+
+    (0..CLMM_SWAP_TICK_ARRAY_COUNT)
+        .map(|i| start + step * i)
+        .collect()
+}
+
+/// Derive a CLMM tick array PDA
+///
+/// Seeds: ["tick_array", pool, start_index (i32 big-endian)]
+pub fn derive_clmm_tick_array(program_id: &Pubkey, pool: &Pubkey, start_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"tick_array", pool.as_ref(), &start_index.to_be_bytes()],
+        program_id,
+    )
+    .0
+}
 
 /// Raydium swap instruction builder (supports all variants)
 pub struct RaydiumSwapBuilder {
     /// RPC client for fetching pool state
-    rpc_client: Arc<SolanaRpcClient>,
+    rpc_client: Arc<dyn RpcApi>,
     /// Pool registry for address resolution
     pool_registry: Arc<PoolRegistry>,
     /// Raydium AMM V4 program ID (default)
@@ -44,8 +153,20 @@ impl RaydiumSwapBuilder {
     /// Raydium Stable program ID (stable swap)
     pub const STABLE_PROGRAM_ID: &'static str = "5quBtoiQqxF9Jv6KYKctB59NT3gtJD2Y65kdnB1Uev3h";
 
+    /// Estimated compute units for one Raydium swap
+    ///
+    /// AMM V4 and CPMM are cheap curve math; CLMM crosses tick arrays like Whirlpools.
+    pub fn estimated_compute_units(&self, dex_type: &DexType) -> u32 {
+        match dex_type {
+            DexType::RaydiumClmm => 120_000,
+            DexType::RaydiumStable => 80_000,
+            DexType::RaydiumCpmm => 50_000,
+            _ => 45_000,
+        }
+    }
+
     /// Create new Raydium swap builder
-    pub fn new(rpc_client: Arc<SolanaRpcClient>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
+    pub fn new(rpc_client: Arc<dyn RpcApi>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
         let program_id = Self::AMM_V4_PROGRAM_ID
             .parse()
             .context("Failed to parse Raydium AMM V4 program ID")?;
@@ -77,7 +198,7 @@ impl RaydiumSwapBuilder {
         pool_short_id: &str,
         swap_params: &SwapParams,
         user_pubkey: &Pubkey,
-    ) -> Result<Instruction> {
+    ) -> ArbResult<Instruction> {
         debug!(
             "Building Raydium swap instruction for pool: {}",
             pool_short_id
@@ -113,9 +234,17 @@ impl RaydiumSwapBuilder {
                             Err(_) => {
                                 // Try Stable if CLMM fails
                                 self.pool_registry
-                                    .resolve_pool_address(pool_short_id, &crate::types::DexType::RaydiumStable)
+                                    .resolve_pool_address(
+                                        pool_short_id,
+                                        &crate::types::DexType::RaydiumStable,
+                                    )
                                     .await
-                                    .context(format!("Failed to resolve pool address for {} (tried AMM V4, CPMM, CLMM, Stable)", pool_short_id))?
+                                    .map_err(|e| {
+                                        ArbError::PoolResolution(format!(
+                                            "{} (tried AMM V4, CPMM, CLMM, Stable): {}",
+                                            pool_short_id, e
+                                        ))
+                                    })?
                             }
                         }
                     }
@@ -142,10 +271,10 @@ impl RaydiumSwapBuilder {
 
             // Double-check after validation
             if self.pool_registry.is_pool_valid_cached(pool_short_id).await != Some(true) {
-                return Err(anyhow::anyhow!(
-                    "⚠️ Ghost pool detected: {} (failed validation)",
+                return Err(ArbError::GhostPool(format!(
+                    "{} (failed validation)",
                     pool_short_id
-                ));
+                )));
             }
         }
 
@@ -176,10 +305,11 @@ impl RaydiumSwapBuilder {
         // For basic CPMM, we can derive the authority PDA and assume vault positions
 
         if pool_state.len() < 300 {
-            return Err(anyhow::anyhow!(
-                "Pool state too short ({} bytes). Expected at least 300 bytes for Raydium CPMM.",
+            return Err(ArbError::GhostPool(format!(
+                "{} state too short ({} bytes). Expected at least 300 bytes for Raydium CPMM.",
+                pool_short_id,
                 pool_state.len()
-            ));
+            )));
         }
 
         // Parse critical addresses from pool state
@@ -207,18 +337,31 @@ impl RaydiumSwapBuilder {
         debug!("Pool Coin Vault: {}", pool_coin_vault);
         debug!("Pool PC Vault: {}", pool_pc_vault);
 
+        // This swap instruction only takes the classic token program - reject
+        // Token-2022 pools here instead of reverting on-chain
+        let token_program = require_spl_token_program(
+            (
+                &pool_info.token_a_mint,
+                self.token_program_for(&pool_info.token_a_mint)?,
+            ),
+            (
+                &pool_info.token_b_mint,
+                self.token_program_for(&pool_info.token_b_mint)?,
+            ),
+        )?;
+
         // Step 4: Determine user token accounts
         let (user_token_in, user_token_out) = if swap_params.swap_a_to_b {
             // Swapping token A (coin) to token B (pc)
             (
-                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint),
-                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint),
+                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint)?,
+                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint)?,
             )
         } else {
             // Swapping token B (pc) to token A (coin)
             (
-                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint),
-                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint),
+                self.get_associated_token_address(user_pubkey, &pool_info.token_b_mint)?,
+                self.get_associated_token_address(user_pubkey, &pool_info.token_a_mint)?,
             )
         };
 
@@ -242,10 +385,10 @@ impl RaydiumSwapBuilder {
 
             let create_ata_ix =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    user_pubkey,      // Payer
-                    user_pubkey,      // Owner of new account
-                    token_mint,       // Token mint
-                    &spl_token::id(), // Token program ID
+                    user_pubkey,                               // Payer
+                    user_pubkey,                               // Owner of new account
+                    token_mint,                                // Token mint
+                    &self.token_program_for(token_mint)?.id(), // Token program ID
                 );
 
             setup_instructions.push(create_ata_ix);
@@ -266,10 +409,10 @@ impl RaydiumSwapBuilder {
 
             let create_ata_ix =
                 spl_associated_token_account::instruction::create_associated_token_account(
-                    user_pubkey,      // Payer
-                    user_pubkey,      // Owner of new account
-                    token_mint,       // Token mint
-                    &spl_token::id(), // Token program ID
+                    user_pubkey,                               // Payer
+                    user_pubkey,                               // Owner of new account
+                    token_mint,                                // Token mint
+                    &self.token_program_for(token_mint)?.id(), // Token program ID
                 );
 
             setup_instructions.push(create_ata_ix);
@@ -285,6 +428,7 @@ impl RaydiumSwapBuilder {
             &pool_coin_vault,
             &pool_pc_vault,
             &pool_authority,
+            &token_program,
             swap_params,
         )?;
 
@@ -331,16 +475,194 @@ impl RaydiumSwapBuilder {
         Ok(all_instructions.into_iter().last().unwrap())
     }
 
+    /// Build swap instruction for Raydium CLMM pool
+    ///
+    /// Derives the three tick arrays around the current price in the swap
+    /// direction and passes them to the CLMM `swap` instruction.
+    ///
+    /// # Arguments
+    /// * `pool_short_id` - 8-char short pool ID from ShredStream
+    /// * `swap_params` - Swap parameters (swap_a_to_b = token0 → token1)
+    /// * `user_pubkey` - User's wallet public key
+    ///
+    /// # Returns
+    /// Solana instruction for the swap
+    pub async fn build_clmm_swap_instruction(
+        &self,
+        pool_short_id: &str,
+        swap_params: &SwapParams,
+        user_pubkey: &Pubkey,
+    ) -> ArbResult<Instruction> {
+        debug!(
+            "Building Raydium CLMM swap instruction for pool: {}",
+            pool_short_id
+        );
+
+        let pool_address = self
+            .pool_registry
+            .resolve_pool_address(pool_short_id, &DexType::RaydiumClmm)
+            .await
+            .map_err(|e| ArbError::PoolResolution(format!("{} (CLMM): {}", pool_short_id, e)))?;
+
+        let pool_state = self
+            .fetch_pool_state(&pool_address)
+            .context("Failed to fetch CLMM pool state")?;
+        let pool = ClmmPoolState::parse(&pool_state)?;
+
+        debug!(
+            "CLMM pool {}: tick_current={}, tick_spacing={}",
+            pool_address, pool.tick_current, pool.tick_spacing
+        );
+
+        let program_id: Pubkey = Self::CLMM_PROGRAM_ID
+            .parse()
+            .context("Failed to parse Raydium CLMM program ID")?;
+
+        // CLMM `swap` only takes the classic token program (Token-2022 needs `swap_v2`)
+        let token_program = require_spl_token_program(
+            (
+                &pool.token_mint_0,
+                self.token_program_for(&pool.token_mint_0)?,
+            ),
+            (
+                &pool.token_mint_1,
+                self.token_program_for(&pool.token_mint_1)?,
+            ),
+        )?;
+
+        let instruction = Self::build_clmm_swap_ix(
+            &program_id,
+            &pool_address,
+            &pool,
+            user_pubkey,
+            &token_program,
+            swap_params,
+        );
+
+        info!("✅ Built Raydium CLMM swap instruction");
+        info!("   Pool: {}", pool_address);
+        info!("   Amount in: {} lamports", swap_params.amount_in);
+        info!(
+            "   Min amount out: {} lamports",
+            swap_params.minimum_amount_out
+        );
+
+        Ok(instruction)
+    }
+
+    /// Assemble the Raydium CLMM `swap` instruction (accounts + data)
+    ///
+    /// Account order follows the CLMM program's `SwapSingle` context;
+    /// the first tick array is a named account, the rest are remaining accounts.
+    fn build_clmm_swap_ix(
+        program_id: &Pubkey,
+        pool_address: &Pubkey,
+        pool: &ClmmPoolState,
+        user_pubkey: &Pubkey,
+        token_program: &Pubkey,
+        swap_params: &SwapParams,
+    ) -> Instruction {
+        let zero_for_one = swap_params.swap_a_to_b;
+
+        let (input_mint, output_mint, input_vault, output_vault) = if zero_for_one {
+            (
+                &pool.token_mint_0,
+                &pool.token_mint_1,
+                &pool.token_vault_0,
+                &pool.token_vault_1,
+            )
+        } else {
+            (
+                &pool.token_mint_1,
+                &pool.token_mint_0,
+                &pool.token_vault_1,
+                &pool.token_vault_0,
+            )
+        };
+
+        let user_token_in =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                user_pubkey,
+                input_mint,
+                token_program,
+            );
+        let user_token_out =
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                user_pubkey,
+                output_mint,
+                token_program,
+            );
+
+        let mut accounts = vec![
+            // 0. Payer (signer)
+            AccountMeta::new_readonly(*user_pubkey, true),
+            // 1. AMM config
+            AccountMeta::new_readonly(pool.amm_config, false),
+            // 2. Pool state
+            AccountMeta::new(*pool_address, false),
+            // 3. User input token account
+            AccountMeta::new(user_token_in, false),
+            // 4. User output token account
+            AccountMeta::new(user_token_out, false),
+            // 5. Input vault
+            AccountMeta::new(*input_vault, false),
+            // 6. Output vault
+            AccountMeta::new(*output_vault, false),
+            // 7. Observation state
+            AccountMeta::new(pool.observation_key, false),
+            // 8. Token program
+            AccountMeta::new_readonly(*token_program, false),
+        ];
+
+        // 9+. Tick arrays (current first, then in swap direction)
+        for start_index in
+            clmm_swap_tick_array_start_indexes(pool.tick_current, pool.tick_spacing, zero_for_one)
+        {
+            let tick_array = derive_clmm_tick_array(program_id, pool_address, start_index);
+            debug!("   Tick array {}: {}", start_index, tick_array);
+            accounts.push(AccountMeta::new(tick_array, false));
+        }
+
+        // Instruction data:
+        // [discriminator: 8][amount: u64][other_amount_threshold: u64]
+        // [sqrt_price_limit_x64: u128][is_base_input: bool]
+        // SHA256("global:swap")[0..8]
+        let mut data = vec![0xf8, 0xc6, 0x9e, 0x91, 0xe1, 0x75, 0x87, 0xc8];
+        data.extend_from_slice(&swap_params.amount_in.to_le_bytes());
+        data.extend_from_slice(&swap_params.minimum_amount_out.to_le_bytes());
+        data.extend_from_slice(&0u128.to_le_bytes()); // 0 = no price limit
+        data.push(1); // is_base_input: amount is exact input
+
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }
+    }
+
     /// Fetch pool state from blockchain
     fn fetch_pool_state(&self, pool_address: &Pubkey) -> Result<Vec<u8>> {
         self.rpc_client
-            .get_account_data(pool_address)
+            .get_account_data(pool_address, None)
             .context("Failed to fetch Raydium pool state")
     }
 
-    /// Get associated token account address for user
-    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
-        spl_associated_token_account::get_associated_token_address(wallet, mint)
+    /// Token program owning `mint` (SPL Token or Token-2022, cached per mint)
+    fn token_program_for(&self, mint: &Pubkey) -> Result<TokenProgram> {
+        // Native SOL placeholder mint has no mint account
+        if *mint == solana_sdk::system_program::ID {
+            return Ok(TokenProgram::Spl);
+        }
+        Ok(self.rpc_client.get_mint_token_program(mint)?.program)
+    }
+
+    /// Get associated token account address for user under the mint's token program
+    fn get_associated_token_address(&self, wallet: &Pubkey, mint: &Pubkey) -> Result<Pubkey> {
+        Ok(associated_token_address(
+            wallet,
+            mint,
+            self.token_program_for(mint)?,
+        ))
     }
 
     /// Build the actual Raydium swap instruction
@@ -367,6 +689,7 @@ impl RaydiumSwapBuilder {
         pool_coin_token_account: &Pubkey,
         pool_pc_token_account: &Pubkey,
         amm_authority: &Pubkey,
+        token_program: &Pubkey,
         swap_params: &SwapParams,
     ) -> Result<Instruction> {
         // Raydium CPMM swap instruction accounts
@@ -391,7 +714,7 @@ impl RaydiumSwapBuilder {
         // - coin_vault, pc_vault, vault_signer from Serum market
         let accounts = vec![
             // 0. Token program
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(*token_program, false),
             // 1. AMM ID (pool account)
             AccountMeta::new(*amm_id, false),
             // 2. AMM authority (PDA)
@@ -530,6 +853,8 @@ impl RaydiumSwapBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::captured_fixtures::{pubkey, CapturedFixture};
+    use crate::rpc_client::SolanaRpcClient;
 
     #[test]
     fn test_slippage_calculation() {
@@ -572,4 +897,165 @@ mod tests {
         };
         assert!(builder.validate_swap_params(&zero_out).is_err());
     }
+
+    #[test]
+    fn test_clmm_tick_array_start_index() {
+        // tick_spacing 60 → each array covers 3600 ticks
+        assert_eq!(clmm_tick_array_start_index(0, 60), 0);
+        assert_eq!(clmm_tick_array_start_index(3599, 60), 0);
+        assert_eq!(clmm_tick_array_start_index(3600, 60), 3600);
+        // Negative ticks round toward -inf, not toward zero
+        assert_eq!(clmm_tick_array_start_index(-1, 60), -3600);
+        assert_eq!(clmm_tick_array_start_index(-3600, 60), -3600);
+        assert_eq!(clmm_tick_array_start_index(-3601, 60), -7200);
+        // tick_spacing 1 → 60 ticks per array
+        assert_eq!(clmm_tick_array_start_index(-18_433, 1), -18_480);
+    }
+
+    #[test]
+    fn test_clmm_swap_tick_arrays_follow_direction() {
+        // token0 → token1 moves price down
+        assert_eq!(
+            clmm_swap_tick_array_start_indexes(-18_433, 10, true),
+            vec![-18_600, -19_200, -19_800]
+        );
+        // token1 → token0 moves price up
+        assert_eq!(
+            clmm_swap_tick_array_start_indexes(-18_433, 10, false),
+            vec![-18_600, -18_000, -17_400]
+        );
+    }
+
+    #[test]
+    fn test_clmm_tick_array_pda_seeds() {
+        let program_id: Pubkey = RaydiumSwapBuilder::CLMM_PROGRAM_ID.parse().unwrap();
+        let pool = Pubkey::new_unique();
+
+        // Start index is encoded big-endian (matches on-chain TickArrayState seeds)
+        let expected = Pubkey::find_program_address(
+            &[b"tick_array", pool.as_ref(), &(-18_600i32).to_be_bytes()],
+            &program_id,
+        )
+        .0;
+        assert_eq!(
+            derive_clmm_tick_array(&program_id, &pool, -18_600),
+            expected
+        );
+        assert_ne!(
+            derive_clmm_tick_array(&program_id, &pool, -18_600),
+            derive_clmm_tick_array(&program_id, &pool, -18_000)
+        );
+    }
+
+    #[test]
+    fn test_clmm_swap_instruction_accounts() {
+        let program_id: Pubkey = RaydiumSwapBuilder::CLMM_PROGRAM_ID.parse().unwrap();
+        let pool_address = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        let mut data = vec![0u8; 1544];
+        let pool = ClmmPoolState {
+            amm_config: Pubkey::new_unique(),
+            token_mint_0: Pubkey::new_unique(),
+            token_mint_1: Pubkey::new_unique(),
+            token_vault_0: Pubkey::new_unique(),
+            token_vault_1: Pubkey::new_unique(),
+            observation_key: Pubkey::new_unique(),
+            tick_spacing: 10,
+            tick_current: -18_433,
+        };
+        data[CLMM_AMM_CONFIG_OFFSET..CLMM_AMM_CONFIG_OFFSET + 32]
+            .copy_from_slice(pool.amm_config.as_ref());
+        data[CLMM_TOKEN_MINT_0_OFFSET..CLMM_TOKEN_MINT_0_OFFSET + 32]
+            .copy_from_slice(pool.token_mint_0.as_ref());
+        data[CLMM_TOKEN_MINT_1_OFFSET..CLMM_TOKEN_MINT_1_OFFSET + 32]
+            .copy_from_slice(pool.token_mint_1.as_ref());
+        data[CLMM_TOKEN_VAULT_0_OFFSET..CLMM_TOKEN_VAULT_0_OFFSET + 32]
+            .copy_from_slice(pool.token_vault_0.as_ref());
+        data[CLMM_TOKEN_VAULT_1_OFFSET..CLMM_TOKEN_VAULT_1_OFFSET + 32]
+            .copy_from_slice(pool.token_vault_1.as_ref());
+        data[CLMM_OBSERVATION_KEY_OFFSET..CLMM_OBSERVATION_KEY_OFFSET + 32]
+            .copy_from_slice(pool.observation_key.as_ref());
+        data[CLMM_TICK_SPACING_OFFSET..CLMM_TICK_SPACING_OFFSET + 2]
+            .copy_from_slice(&10u16.to_le_bytes());
+        data[CLMM_TICK_CURRENT_OFFSET..CLMM_TICK_CURRENT_OFFSET + 4]
+            .copy_from_slice(&(-18_433i32).to_le_bytes());
+        assert_eq!(ClmmPoolState::parse(&data).unwrap(), pool);
+
+        let params = SwapParams {
+            amount_in: 1_000_000,
+            minimum_amount_out: 990_000,
+            expected_amount_out: Some(1_000_000),
+            swap_a_to_b: true,
+        };
+        let ix = RaydiumSwapBuilder::build_clmm_swap_ix(
+            &program_id,
+            &pool_address,
+            &pool,
+            &user,
+            &spl_token::id(),
+            &params,
+        );
+
+        assert_eq!(ix.program_id, program_id);
+        assert_eq!(ix.accounts.len(), 12);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[5].pubkey, pool.token_vault_0);
+        assert_eq!(ix.accounts[6].pubkey, pool.token_vault_1);
+        for (i, start) in [-18_600, -19_200, -19_800].iter().enumerate() {
+            assert_eq!(
+                ix.accounts[9 + i].pubkey,
+                derive_clmm_tick_array(&program_id, &pool_address, *start)
+            );
+        }
+        assert_eq!(ix.data.len(), 8 + 8 + 8 + 16 + 1);
+    }
+
+    #[test]
+    #[ignore = "needs fixtures/raydium_clmm_swap.json (scripts/capture_fixture.py --swap)"]
+    fn test_captured_clmm_swap_accounts() {
+        let fixture = CapturedFixture::load("raydium_clmm_swap").unwrap();
+        let pool = ClmmPoolState::parse(fixture.pool_data()).unwrap();
+
+        // Vault mints we read match what the RPC node decoded
+        for (vault, mint) in [
+            (pool.token_vault_0, pool.token_mint_0),
+            (pool.token_vault_1, pool.token_mint_1),
+        ] {
+            assert_eq!(fixture.token_account(&vault).mint, mint.to_string());
+        }
+
+        let swap = fixture.swap();
+        let executed: Vec<Pubkey> = swap
+            .instruction_accounts
+            .iter()
+            .map(|address| pubkey(address).unwrap())
+            .collect();
+        let params = SwapParams {
+            amount_in: swap.amount_in,
+            minimum_amount_out: 0,
+            expected_amount_out: None,
+            swap_a_to_b: pubkey(&swap.vault_in).unwrap() == pool.token_vault_0,
+        };
+        let ix = RaydiumSwapBuilder::build_clmm_swap_ix(
+            &fixture.program,
+            &fixture.pool,
+            &pool,
+            &executed[0],
+            &spl_token::id(),
+            &params,
+        );
+
+        // Pool accounts and the tick array holding the pre-swap price match the
+        // executed `swap`. User token accounts (3, 4) may not be ATAs when the
+        // swap is routed, and later tick arrays skip uninitialized ones via the
+        // pool bitmap, so those aren't compared.
+        for i in [0, 1, 2, 5, 6, 7, 8, 9] {
+            assert_eq!(
+                ix.accounts[i].pubkey, executed[i],
+                "account {} differs from {} (swap_v2 captured?)",
+                i, swap.signature
+            );
+        }
+    }
 }
@@ -0,0 +1,216 @@
+// RPC price feed (ShredStream fallback)
+//
+// When the ShredStream service is Degraded/Down the engine would otherwise go
+// blind. This feed reads the token vaults of registered WSOL-paired pools in
+// batched getMultipleAccounts calls and derives spot prices (SOL per whole
+// token) from the reserve ratio, producing the same `TokenPrice` entries the
+// detectors consume. Only constant-product pools are priced this way -
+// concentrated-liquidity pools (Whirlpools, CLMM, DLMM) trade at their active
+// tick, not at their vault ratio.
+
+use anyhow::Result;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::debug;
+
+use crate::pool_registry::PoolRegistry;
+use crate::rpc_client::SolanaRpcClient;
+use crate::shredstream_client::TokenPrice;
+use crate::types::{DexType, PoolInfo};
+
+/// getMultipleAccounts limit per request
+const MAX_ACCOUNTS_PER_CALL: usize = 100;
+/// SPL token account `amount` field offset (same for Token-2022)
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+/// SPL mint `decimals` field offset (same for Token-2022)
+const MINT_DECIMALS_OFFSET: usize = 44;
+/// `TokenPrice::last_update` marker for RPC-derived prices
+pub const RPC_PRICE_SOURCE: &str = "rpc";
+
+/// Whether a price came from the RPC fallback rather than ShredStream
+pub fn is_rpc_sourced(price: &TokenPrice) -> bool {
+    price.last_update == RPC_PRICE_SOURCE
+}
+
+/// Whether a DEX's spot price is its vault reserve ratio
+fn is_reserve_priced(dex_type: &DexType) -> bool {
+    matches!(
+        dex_type,
+        DexType::RaydiumAmmV4
+            | DexType::RaydiumCpmm
+            | DexType::OrcaLegacy
+            | DexType::PumpSwap
+            | DexType::Saros
+            | DexType::Aldrin
+            | DexType::Cropper
+            | DexType::Fluxbeam
+    )
+}
+
+/// Spot price (SOL per whole token) from constant-product reserves
+///
+/// # Arguments
+/// * `sol_reserve` - WSOL vault balance (lamports)
+/// * `token_reserve` - Token vault balance (base units)
+/// * `token_decimals` - Token mint decimals
+///
+/// # Returns
+/// Price, or None if either side is empty
+pub fn reserve_spot_price(sol_reserve: u64, token_reserve: u64, token_decimals: u8) -> Option<f64> {
+    if sol_reserve == 0 || token_reserve == 0 {
+        return None;
+    }
+    let sol = sol_reserve as f64 / 1_000_000_000.0;
+    let tokens = token_reserve as f64 / 10f64.powi(token_decimals as i32);
+    Some(sol / tokens)
+}
+
+/// Read a little-endian u64 at `offset` (None if the account is missing or short)
+fn read_u64(data: Option<&Vec<u8>>, offset: usize) -> Option<u64> {
+    let bytes = data?.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Spot prices from on-chain pool reserves
+pub struct RpcPriceFeed {
+    /// RPC client for batched account reads
+    rpc_client: Arc<SolanaRpcClient>,
+    /// Source of known pool addresses and vaults
+    pool_registry: Arc<PoolRegistry>,
+    /// Mint -> decimals (immutable, fetched once)
+    decimals: Mutex<HashMap<Pubkey, u8>>,
+}
+
+impl RpcPriceFeed {
+    /// Create new RPC price feed
+    pub fn new(rpc_client: Arc<SolanaRpcClient>, pool_registry: Arc<PoolRegistry>) -> Self {
+        Self {
+            rpc_client,
+            pool_registry,
+            decimals: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fetch accounts in chunks of `MAX_ACCOUNTS_PER_CALL`
+    fn fetch_accounts(&self, keys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut accounts = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(MAX_ACCOUNTS_PER_CALL) {
+            accounts.extend(self.rpc_client.get_multiple_accounts(chunk)?);
+        }
+        Ok(accounts)
+    }
+
+    /// Derive prices for registered WSOL-paired pools
+    ///
+    /// Vault balances (and decimals of mints not seen before) are read in one
+    /// batched pass. Pools with empty or unreadable vaults are skipped.
+    ///
+    /// # Arguments
+    /// * `target_mints` - Only price these mints (None = every registered pool)
+    ///
+    /// # Returns
+    /// Prices labelled with `last_update = RPC_PRICE_SOURCE`; `volume_24h`
+    /// carries the pool's SOL reserve since RPC has no volume data
+    pub fn fetch_prices(&self, target_mints: Option<&HashSet<String>>) -> Result<Vec<TokenPrice>> {
+        let wsol = spl_token::native_mint::id();
+
+        // (short_id, pool, token mint, sol vault, token vault)
+        let pools: Vec<(String, PoolInfo, Pubkey, Pubkey, Pubkey)> = self
+            .pool_registry
+            .pools_paired_with(&wsol)
+            .into_iter()
+            .filter(|(_, pool)| is_reserve_priced(&pool.dex_type))
+            .filter_map(|(short_id, pool)| {
+                let (token_mint, sol_vault, token_vault) = if pool.token_a_mint == wsol {
+                    (pool.token_b_mint, pool.reserve_a, pool.reserve_b)
+                } else {
+                    (pool.token_a_mint, pool.reserve_b, pool.reserve_a)
+                };
+                let targeted =
+                    target_mints.map_or(true, |mints| mints.contains(&token_mint.to_string()));
+                targeted.then_some((short_id, pool, token_mint, sol_vault, token_vault))
+            })
+            .collect();
+        if pools.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let missing_mints: Vec<Pubkey> = {
+            let decimals = self.decimals.lock().unwrap();
+            pools
+                .iter()
+                .map(|(_, _, mint, _, _)| *mint)
+                .filter(|mint| !decimals.contains_key(mint))
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect()
+        };
+
+        // Vault pairs first, then unknown mints - all in one batched read
+        let mut keys: Vec<Pubkey> = pools
+            .iter()
+            .flat_map(|(_, _, _, sol_vault, token_vault)| [*sol_vault, *token_vault])
+            .collect();
+        keys.extend(&missing_mints);
+        let accounts = self.fetch_accounts(&keys)?;
+        let (vaults, mints) = accounts.split_at(pools.len() * 2);
+
+        let mut decimals = self.decimals.lock().unwrap();
+        for (mint, data) in missing_mints.iter().zip(mints) {
+            if let Some(&mint_decimals) = data.as_ref().and_then(|d| d.get(MINT_DECIMALS_OFFSET)) {
+                decimals.insert(*mint, mint_decimals);
+            }
+        }
+
+        let now = Instant::now();
+        let prices: Vec<TokenPrice> = pools
+            .iter()
+            .zip(vaults.chunks(2))
+            .filter_map(|((short_id, pool, token_mint, _, _), vault_data)| {
+                let sol_reserve = read_u64(vault_data[0].as_ref(), TOKEN_ACCOUNT_AMOUNT_OFFSET)?;
+                let token_reserve = read_u64(vault_data[1].as_ref(), TOKEN_ACCOUNT_AMOUNT_OFFSET)?;
+                let price_sol =
+                    reserve_spot_price(sol_reserve, token_reserve, *decimals.get(token_mint)?)?;
+                Some(TokenPrice {
+                    token_mint: token_mint.to_string(),
+                    dex: format!("{}_{}", pool.dex_type.feed_name(), short_id),
+                    price_sol,
+                    last_update: RPC_PRICE_SOURCE.to_string(),
+                    volume_24h: sol_reserve as f64 / 1_000_000_000.0,
+                    pool_address: pool.full_address.to_string(),
+                    last_updated: now,
+                    slot: None,
+                })
+            })
+            .collect();
+
+        debug!(
+            "🛰️ RPC prices: {} of {} pools priced from reserves",
+            prices.len(),
+            pools.len()
+        );
+        Ok(prices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_spot_price() {
+        // 100 SOL against 1,000,000 tokens (6 decimals) = 0.0001 SOL/token
+        let price = reserve_spot_price(100_000_000_000, 1_000_000_000_000, 6).unwrap();
+        assert!((price - 0.0001).abs() < 1e-12);
+
+        // Empty side has no price
+        assert!(reserve_spot_price(0, 1_000, 6).is_none());
+        assert!(reserve_spot_price(1_000, 0, 6).is_none());
+
+        // Concentrated-liquidity pools aren't priced from vaults
+        assert!(is_reserve_priced(&DexType::RaydiumAmmV4));
+        assert!(!is_reserve_priced(&DexType::OrcaWhirlpools));
+    }
+}
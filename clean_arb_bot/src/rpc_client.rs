@@ -7,26 +7,306 @@
 // - Getting pool state information
 
 use anyhow::{Context, Result};
+use governor::{
+    clock::{Clock, DefaultClock},
+    state::{InMemoryState, NotKeyed},
+    Quota, RateLimiter as GovernorRateLimiter,
+};
+use solana_account_decoder::{UiAccount, UiAccountData, UiAccountEncoding};
 use solana_client::rpc_client::RpcClient;
-use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_client::rpc_config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig, RpcTransactionConfig,
+};
+use solana_client::rpc_request::TokenAccountsFilter;
 use solana_sdk::{
-    commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey, signature::Signature,
-    transaction::Transaction,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedTransactionWithStatusMeta, UiTransactionEncoding,
+    UiTransactionTokenBalance,
 };
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::Duration;
+use spl_token::state::Mint;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, trace, warn};
 
+use crate::amm_math::token_account_amount;
+use crate::errors::ArbError;
+use crate::token_program::{MintTokenProgram, TokenProgram};
+
 /// CYCLE-5 FIX: RPC circuit breaker threshold
 /// Halts trading after this many consecutive RPC failures to prevent losses during network issues
 const CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
 
+/// Account data cache defaults
+/// TTL of one slot (~400ms) - pool existence/size doesn't change faster than that
+const DEFAULT_ACCOUNT_CACHE_TTL_MS: u64 = 400;
+const ACCOUNT_CACHE_CAPACITY: usize = 1024;
+
+/// Longest a call queues for an RPC_MAX_RPS token before failing
+/// Short on purpose: a call that waits longer would be acting on stale state anyway
+const RATE_LIMIT_MAX_WAIT: Duration = Duration::from_millis(250);
+
+/// Recent prioritization fees per account set are reused for a few slots
+const PRIORITY_FEE_CACHE_TTL: Duration = Duration::from_secs(2);
+/// getRecentPrioritizationFees accepts at most this many accounts
+const MAX_PRIORITY_FEE_ACCOUNTS: usize = 128;
+/// Default percentile of recent per-slot fees used as our price (PRIORITY_FEE_PERCENTILE)
+const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+
+/// Default retries of a read after a transient failure (RPC_READ_RETRIES)
+pub const DEFAULT_RPC_READ_RETRIES: u32 = 2;
+/// Default backoff before the first retry, doubled per retry (RPC_RETRY_BACKOFF_MS)
+pub const DEFAULT_RPC_RETRY_BACKOFF_MS: u64 = 100;
+/// Upper bound for RPC_READ_RETRIES (backoff doubles, so more would stall a scan for seconds)
+pub const MAX_RPC_READ_RETRIES: u32 = 10;
+
+/// Error fragments of transient failures worth retrying (timeouts, 429, 5xx, dropped connections)
+const RETRIABLE_ERROR_MARKERS: &[&str] = &[
+    "timeout",
+    "timed out",
+    "429",
+    "too many requests",
+    "502",
+    "503",
+    "504",
+    "bad gateway",
+    "service unavailable",
+    "connection",
+    "network",
+];
+
+/// Retry policy for RPC reads (RPC_READ_RETRIES / RPC_RETRY_BACKOFF_MS)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RpcRetryPolicy {
+    /// Retries after the first attempt (0 = fail on the first error)
+    pub max_retries: u32,
+    /// Wait before the first retry; doubles for each further retry
+    pub base_backoff: Duration,
+}
+
+impl Default for RpcRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_RPC_READ_RETRIES,
+            base_backoff: Duration::from_millis(DEFAULT_RPC_RETRY_BACKOFF_MS),
+        }
+    }
+}
+
+impl RpcRetryPolicy {
+    /// Wait before retry number `retry` (0-based): base, 2x base, 4x base, ...
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.base_backoff.saturating_mul(1 << retry.min(16))
+    }
+}
+
+/// Whether an RPC error is transient (timeout, rate limit, 5xx, connection drop)
+///
+/// Anything else - invalid params, bad pubkeys, missing accounts - fails the same
+/// way on every attempt and is returned immediately.
+fn is_retriable_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    RETRIABLE_ERROR_MARKERS
+        .iter()
+        .any(|marker| error.contains(marker))
+}
+
+/// Whether an RPC error means the account does not exist (expected, never retried)
+fn is_not_found_error(error: &str) -> bool {
+    error.contains("AccountNotFound") || error.contains("not found")
+}
+
+/// Outbound RPC calls held back by the RPC_MAX_RPS token bucket (since startup)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RpcRateLimitStats {
+    /// Calls that had to wait for a token
+    pub throttled_calls: u64,
+    /// Calls failed because no token freed up within the queue window
+    pub rejected_calls: u64,
+    /// Total time spent waiting for tokens
+    pub wait_ms: u64,
+}
+
+/// Small LRU cache of account data keyed by pubkey
+#[derive(Debug, Default)]
+struct AccountCache {
+    /// pubkey -> (data, fetched_at, last_used tick)
+    entries: HashMap<Pubkey, (Vec<u8>, Instant, u64)>,
+    tick: u64,
+}
+
+impl AccountCache {
+    /// Return cached data if younger than `ttl` (marks entry as recently used)
+    fn get(&mut self, pubkey: &Pubkey, ttl: Duration) -> Option<Vec<u8>> {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(pubkey) {
+            Some((data, fetched_at, last_used)) if fetched_at.elapsed() < ttl => {
+                *last_used = tick;
+                Some(data.clone())
+            }
+            Some(_) => {
+                self.entries.remove(pubkey);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Insert data, evicting the least recently used entry when full
+    fn insert(&mut self, pubkey: Pubkey, data: Vec<u8>, capacity: usize) {
+        self.tick += 1;
+        if self.entries.len() >= capacity && !self.entries.contains_key(&pubkey) {
+            if let Some(lru) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, _, last_used))| *last_used)
+                .map(|(key, _)| *key)
+            {
+                self.entries.remove(&lru);
+            }
+        }
+        self.entries
+            .insert(pubkey, (data, Instant::now(), self.tick));
+    }
+}
+
+/// Result of an RPC simulateTransaction call
+#[derive(Debug, Clone)]
+pub struct SimulationOutcome {
+    /// Transaction would succeed on-chain
+    pub success: bool,
+    /// Compute units consumed (reported even for failed simulations)
+    pub units_consumed: Option<u64>,
+    /// Program/transaction error if the simulation failed
+    pub error: Option<String>,
+    /// Program logs
+    pub logs: Vec<String>,
+}
+
+/// Token account receiving one swap leg's output, read back after simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegOutputProbe {
+    /// Wallet token account the leg's output lands in
+    pub output_account: Pubkey,
+    /// Amount the rest of the transaction spends from this account (e.g. the
+    /// next leg's exact input) - added back so the balance delta is the leg output
+    pub spent_elsewhere: u64,
+}
+
+/// Simulation outcome plus the decoded output amount of each swap leg
+#[derive(Debug, Clone)]
+pub struct AmountSimulation {
+    pub outcome: SimulationOutcome,
+    /// Actual output per probe, in probe order (None if the simulation failed
+    /// or the account could not be decoded)
+    pub leg_outputs: Vec<Option<u64>>,
+}
+
+/// Output of a leg from its account balance before and after simulation
+///
+/// # Arguments
+/// * `pre` - Balance before the transaction
+/// * `post` - Balance after the simulated transaction
+/// * `spent_elsewhere` - Amount other instructions spent from the same account
+///
+/// # Returns
+/// Amount the leg deposited, or None if the balances are inconsistent
+fn leg_output_amount(pre: u64, post: u64, spent_elsewhere: u64) -> Option<u64> {
+    post.checked_add(spent_elsewhere)?.checked_sub(pre)
+}
+
+/// Lamport change of `account` in a fetched transaction (post minus pre balance)
+///
+/// # Returns
+/// None if the transaction can't be decoded, has no status meta, or doesn't
+/// list `account` among its static keys (signers always are)
+fn lamport_change(transaction: &EncodedTransactionWithStatusMeta, account: &Pubkey) -> Option<i64> {
+    let meta = transaction.meta.as_ref()?;
+    let decoded = transaction.transaction.decode()?;
+    let index = decoded
+        .message
+        .static_account_keys()
+        .iter()
+        .position(|key| key == account)?;
+    let pre = *meta.pre_balances.get(index)?;
+    let post = *meta.post_balances.get(index)?;
+    Some(post as i64 - pre as i64)
+}
+
+/// Change in `owner`'s `mint` token balance in a fetched transaction
+///
+/// # Returns
+/// None if the transaction has no status meta or token balance lists
+fn token_change(
+    transaction: &EncodedTransactionWithStatusMeta,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Option<i128> {
+    let meta = transaction.meta.as_ref()?;
+    let (owner, mint) = (owner.to_string(), mint.to_string());
+    let total = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| match balances {
+        OptionSerializer::Some(balances) => Some(
+            balances
+                .iter()
+                .filter(|balance| {
+                    balance.mint == mint
+                        && matches!(&balance.owner, OptionSerializer::Some(o) if *o == owner)
+                })
+                .filter_map(|balance| balance.ui_token_amount.amount.parse::<u64>().ok())
+                .map(i128::from)
+                .sum::<i128>(),
+        ),
+        _ => None,
+    };
+    Some(total(&meta.post_token_balances)? - total(&meta.pre_token_balances)?)
+}
+
+/// `amount` of a token account returned with `jsonParsed` encoding
+fn parsed_token_amount(data: &UiAccountData) -> Option<u64> {
+    let UiAccountData::Json(account) = data else {
+        return None;
+    };
+    account
+        .parsed
+        .get("info")?
+        .get("tokenAmount")?
+        .get("amount")?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
 /// Wrapper around Solana RPC client with convenience methods for DEX operations
 /// CYCLE-5 FIX: Added circuit breaker to halt trading during sustained RPC failures
 pub struct SolanaRpcClient {
     client: RpcClient,
-    commitment: CommitmentConfig,
+    commitment: CommitmentConfig, // READ_COMMITMENT: account/balance reads (client default)
+    sim_commitment: CommitmentConfig, // SIM_COMMITMENT: simulateTransaction
     consecutive_failures: AtomicU32, // CYCLE-5: Track consecutive RPC failures
+    account_cache: Mutex<AccountCache>,
+    account_cache_ttl: Duration,
+    account_cache_hits: AtomicU64,
+    account_cache_misses: AtomicU64,
+    mint_cache: Mutex<HashMap<Pubkey, Mint>>, // Decimals are immutable - cached for process lifetime
+    mint_program_cache: Mutex<HashMap<Pubkey, MintTokenProgram>>, // Owner program never changes
+    rate_limiter: Option<GovernorRateLimiter<NotKeyed, InMemoryState, DefaultClock>>, // RPC_MAX_RPS (None = unlimited)
+    max_rps: u32,
+    rate_limited_calls: AtomicU64,
+    rate_limit_rejections: AtomicU64,
+    rate_limit_wait_micros: AtomicU64,
+    priority_fee_percentile: u8,
+    priority_fee_cache: Mutex<HashMap<Vec<Pubkey>, (Instant, u64)>>, // Sorted accounts -> fee
+    retry_policy: RpcRetryPolicy, // RPC_READ_RETRIES / RPC_RETRY_BACKOFF_MS
 }
 
 impl SolanaRpcClient {
@@ -40,7 +320,116 @@ impl SolanaRpcClient {
         Self {
             client,
             commitment,
+            sim_commitment: commitment,
             consecutive_failures: AtomicU32::new(0), // CYCLE-5: Initialize circuit breaker
+            account_cache: Mutex::new(AccountCache::default()),
+            account_cache_ttl: Duration::from_millis(DEFAULT_ACCOUNT_CACHE_TTL_MS),
+            account_cache_hits: AtomicU64::new(0),
+            account_cache_misses: AtomicU64::new(0),
+            mint_cache: Mutex::new(HashMap::new()),
+            mint_program_cache: Mutex::new(HashMap::new()),
+            rate_limiter: None,
+            max_rps: 0,
+            rate_limited_calls: AtomicU64::new(0),
+            rate_limit_rejections: AtomicU64::new(0),
+            rate_limit_wait_micros: AtomicU64::new(0),
+            priority_fee_percentile: DEFAULT_PRIORITY_FEE_PERCENTILE,
+            priority_fee_cache: Mutex::new(HashMap::new()),
+            retry_policy: RpcRetryPolicy::default(),
+        }
+    }
+
+    /// Override the account data cache TTL (default: 400ms, one slot)
+    pub fn with_account_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.account_cache_ttl = ttl;
+        self
+    }
+
+    /// Cap outbound calls to `max_rps` requests per second (0 = unlimited)
+    ///
+    /// Applies to every RPC call this client makes. Calls queue for up to
+    /// `RATE_LIMIT_MAX_WAIT` when the bucket is empty, then fail.
+    pub fn with_max_rps(mut self, max_rps: u32) -> Self {
+        self.max_rps = max_rps;
+        self.rate_limiter =
+            NonZeroU32::new(max_rps).map(|rps| GovernorRateLimiter::direct(Quota::per_second(rps)));
+        if self.rate_limiter.is_some() {
+            info!("🚦 RPC rate limit: {} requests/sec", max_rps);
+        }
+        self
+    }
+
+    /// Commitment for account and balance reads (default: confirmed)
+    pub fn with_read_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.client = RpcClient::new_with_commitment(self.client.url(), commitment);
+        self.commitment = commitment;
+        self
+    }
+
+    /// Commitment simulations run against (default: confirmed)
+    ///
+    /// `processed` simulates against the freshest pool state - closest to what
+    /// the transaction will actually execute against.
+    pub fn with_sim_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.sim_commitment = commitment;
+        self
+    }
+
+    /// Percentile of recent per-slot fees `get_prioritization_fee` returns (default: 75)
+    pub fn with_priority_fee_percentile(mut self, percentile: u8) -> Self {
+        self.priority_fee_percentile = percentile.min(100);
+        self
+    }
+
+    /// Retry policy for reads (default: 2 retries, 100ms backoff doubling per retry)
+    pub fn with_retry_policy(mut self, retry_policy: RpcRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Take a token from the RPC_MAX_RPS bucket, waiting briefly if it is empty
+    ///
+    /// # Returns
+    /// `ArbError::RpcError` if no token frees up within `RATE_LIMIT_MAX_WAIT`.
+    /// Throttling is our own doing, so it never counts towards the circuit breaker.
+    fn throttle(&self) -> Result<()> {
+        let Some(ref limiter) = self.rate_limiter else {
+            return Ok(());
+        };
+
+        let started = Instant::now();
+        let mut throttled = false;
+        while let Err(not_until) = limiter.check() {
+            let wait = not_until.wait_time_from(DefaultClock::default().now());
+            if started.elapsed() + wait > RATE_LIMIT_MAX_WAIT {
+                self.rate_limit_rejections.fetch_add(1, Ordering::Relaxed);
+                self.rate_limit_wait_micros
+                    .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+                return Err(ArbError::RpcError(format!(
+                    "rate limit exhausted (RPC_MAX_RPS={}, queued {}ms)",
+                    self.max_rps,
+                    started.elapsed().as_millis()
+                ))
+                .into());
+            }
+            std::thread::sleep(wait);
+            throttled = true;
+        }
+
+        if throttled {
+            self.rate_limited_calls.fetch_add(1, Ordering::Relaxed);
+            self.rate_limit_wait_micros
+                .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// RPC_MAX_RPS throttling since startup
+    pub fn rate_limit_stats(&self) -> RpcRateLimitStats {
+        RpcRateLimitStats {
+            throttled_calls: self.rate_limited_calls.load(Ordering::Relaxed),
+            rejected_calls: self.rate_limit_rejections.load(Ordering::Relaxed),
+            wait_ms: self.rate_limit_wait_micros.load(Ordering::Relaxed) / 1000,
         }
     }
 
@@ -57,10 +446,11 @@ impl SolanaRpcClient {
             error!("   Trading halted to prevent losses during network issues");
             error!("   Manual intervention required - check RPC endpoint and restart bot");
 
-            return Err(anyhow::anyhow!(
-                "RPC circuit breaker tripped after {} consecutive failures. Manual restart required.",
+            return Err(ArbError::RpcError(format!(
+                "circuit breaker tripped after {} consecutive failures. Manual restart required.",
                 failures
-            ));
+            ))
+            .into());
         }
 
         Ok(())
@@ -94,54 +484,179 @@ impl SolanaRpcClient {
         }
     }
 
+    /// Run a read, retrying transient failures per the retry policy
+    ///
+    /// Each attempt takes its own RPC_MAX_RPS token. The circuit breaker counts
+    /// one failure once retries are exhausted (or the error is not retriable),
+    /// not one per attempt; "account not found" never counts.
+    ///
+    /// # Arguments
+    /// * `what` - What is being read, for errors and logs (e.g. "fetch account X")
+    /// * `call` - The RPC call, invoked once per attempt
+    ///
+    /// # Errors
+    /// Returns the last error, or the throttle error if the rate limit is exhausted
+    fn read_with_retry<T, E: std::fmt::Display>(
+        &self,
+        what: &str,
+        mut call: impl FnMut() -> std::result::Result<T, E>,
+    ) -> Result<T> {
+        let mut retry = 0;
+        loop {
+            self.throttle()?;
+            let error = match call() {
+                Ok(value) => {
+                    self.record_success(); // CYCLE-5: Reset circuit breaker on success
+                    return Ok(value);
+                }
+                Err(e) => e.to_string(),
+            };
+            let attempts = retry + 1;
+
+            if is_not_found_error(&error) {
+                // Expected for closed/invalid pools - not an RPC health problem
+                return Err(anyhow::anyhow!("Failed to {}: {}", what, error));
+            }
+            if !is_retriable_error(&error) || retry >= self.retry_policy.max_retries {
+                self.record_failure(); // CYCLE-5: Count once, after retries are exhausted
+                return Err(anyhow::anyhow!(
+                    "Failed to {} after {} attempt(s): {}",
+                    what,
+                    attempts,
+                    error
+                ));
+            }
+
+            let delay = self.retry_policy.backoff(retry);
+            warn!(
+                "⚠️ RPC {} attempt {} failed, retrying in {}ms: {}",
+                what,
+                attempts,
+                delay.as_millis(),
+                error
+            );
+            std::thread::sleep(delay);
+            retry += 1;
+        }
+    }
+
     /// Get recent blockhash (needed for all transactions)
-    /// HIGH-3 FIX: Added retry logic with exponential backoff
+    /// HIGH-3 FIX: Transient failures are retried per RPC_READ_RETRIES
     /// CYCLE-5 FIX: Added circuit breaker tracking
     pub fn get_latest_blockhash(&self) -> Result<Hash> {
         debug!("Fetching latest blockhash...");
+        let blockhash = self.read_with_retry("fetch latest blockhash", || {
+            self.client.get_latest_blockhash()
+        })?;
+        debug!("✅ Got blockhash: {}", blockhash);
+        Ok(blockhash)
+    }
 
-        // Retry up to 3 times with exponential backoff
-        for attempt in 1..=3 {
-            match self.client.get_latest_blockhash() {
-                Ok(blockhash) => {
-                    debug!("✅ Got blockhash: {}", blockhash);
-                    self.record_success(); // CYCLE-5: Reset circuit breaker on success
-                    return Ok(blockhash);
-                }
-                Err(e) => {
-                    // Only retry on transient errors
-                    let is_transient = e.to_string().contains("timeout")
-                        || e.to_string().contains("network")
-                        || e.to_string().contains("connection");
-
-                    if !is_transient || attempt == 3 {
-                        self.record_failure(); // CYCLE-5: Increment circuit breaker on failure
-                        return Err(anyhow::anyhow!(
-                            "Failed to fetch latest blockhash after {} attempts: {}",
-                            attempt,
-                            e
-                        ));
-                    }
-
-                    // Exponential backoff: 100ms, 200ms, 400ms
-                    let delay_ms = 100 * (1 << (attempt - 1));
-                    warn!(
-                        "⚠️ Blockhash fetch attempt {} failed, retrying in {}ms: {}",
-                        attempt, delay_ms, e
-                    );
-                    std::thread::sleep(Duration::from_millis(delay_ms));
+    /// Simulate transaction before sending (critical for safety)
+    ///
+    /// # Arguments
+    /// * `transaction` - Transaction to simulate
+    /// * `commitment` - State to simulate against (None = SIM_COMMITMENT)
+    pub fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<bool> {
+        let simulation = self
+            .simulate_with_accounts(transaction, None, commitment)
+            .map(|(outcome, _)| outcome);
+        match simulation {
+            Ok(outcome) => Ok(outcome.success),
+            Err(e) => {
+                warn!("❌ Failed to simulate transaction: {}", e);
+                // Check for specific RPC errors
+                let error_str = e.to_string();
+                if crate::cached_blockhash::is_blockhash_expired_error(&error_str) {
+                    warn!("   ⏰ Blockhash expired - need to get fresh blockhash");
+                } else if error_str.contains("network") || error_str.contains("connection") {
+                    warn!("   🌐 Network issue - RPC connection problem");
                 }
+                Ok(false)
             }
         }
+    }
 
-        self.record_failure(); // CYCLE-5: Increment on final failure
-        Err(anyhow::anyhow!(
-            "Failed to fetch latest blockhash after retries"
-        ))
+    /// Simulate transaction and return the full outcome (compute units, program error, logs)
+    ///
+    /// # Returns
+    /// * `Ok(SimulationOutcome)` if the RPC answered (success or program error)
+    /// * `Err` if the simulation request itself failed (network, expired blockhash)
+    pub fn simulate_transaction_detailed(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<SimulationOutcome> {
+        self.simulate_with_accounts(transaction, None, None)
+            .map(|(outcome, _)| outcome)
     }
 
-    /// Simulate transaction before sending (critical for safety)
-    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<bool> {
+    /// Simulate transaction and decode the actual output of each swap leg
+    ///
+    /// Reads each probe's token account before the simulation and asks the RPC
+    /// for its post-simulation state, so the leg output is
+    /// `post - pre + spent_elsewhere` (exact-input legs make the spend known).
+    ///
+    /// # Arguments
+    /// * `transaction` - Fully built transaction (swaps + tip)
+    /// * `probes` - One probe per swap leg, in leg order
+    ///
+    /// # Returns
+    /// * `Ok(AmountSimulation)` if the RPC answered (success or program error)
+    /// * `Err` if either RPC request failed
+    pub fn simulate_transaction_with_amounts(
+        &self,
+        transaction: &Transaction,
+        probes: &[LegOutputProbe],
+    ) -> Result<AmountSimulation> {
+        let accounts: Vec<Pubkey> = probes.iter().map(|p| p.output_account).collect();
+
+        // Missing accounts hold nothing yet (ATAs may be created by the transaction)
+        let pre: Vec<u64> = self
+            .get_multiple_accounts(&accounts)?
+            .iter()
+            .map(|data| token_account_amount(data.as_deref()).unwrap_or(0))
+            .collect();
+
+        let accounts_config = RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: accounts.iter().map(Pubkey::to_string).collect(),
+        };
+        let (outcome, post_accounts) =
+            self.simulate_with_accounts(transaction, Some(accounts_config), None)?;
+
+        let leg_outputs = probes
+            .iter()
+            .enumerate()
+            .map(|(i, probe)| {
+                if !outcome.success {
+                    return None;
+                }
+                let post_data = post_accounts.get(i)?.as_ref()?.data.decode();
+                let post = token_account_amount(post_data.as_deref()).ok()?;
+                leg_output_amount(pre[i], post, probe.spent_elsewhere)
+            })
+            .collect();
+
+        Ok(AmountSimulation {
+            outcome,
+            leg_outputs,
+        })
+    }
+
+    /// Run simulateTransaction, optionally returning post-simulation account states
+    ///
+    /// # Arguments
+    /// * `commitment` - State to simulate against (None = SIM_COMMITMENT)
+    fn simulate_with_accounts(
+        &self,
+        transaction: &Transaction,
+        accounts: Option<RpcSimulateTransactionAccountsConfig>,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<(SimulationOutcome, Vec<Option<UiAccount>>)> {
         debug!(
             "Simulating transaction with {} instructions...",
             transaction.message.instructions.len()
@@ -149,79 +664,89 @@ impl SolanaRpcClient {
 
         let config = RpcSimulateTransactionConfig {
             sig_verify: false,
-            commitment: Some(self.commitment),
+            commitment: Some(commitment.unwrap_or(self.sim_commitment)),
+            accounts,
             ..Default::default()
         };
 
-        match self
+        self.throttle()?;
+        let response = self
             .client
             .simulate_transaction_with_config(transaction, config)
-        {
-            Ok(response) => {
-                if let Some(err) = response.value.err {
-                    warn!("❌ Transaction simulation failed: {:?}", err);
-
-                    // Enhanced error analysis
-                    if let Some(logs) = &response.value.logs {
-                        warn!("📋 Failed transaction logs:");
-                        for (i, log) in logs.iter().enumerate() {
-                            if log.contains("Error")
-                                || log.contains("failed")
-                                || log.contains("insufficient")
-                            {
-                                warn!("   [{}] {}", i, log);
-                            }
-                        }
-
-                        // Check for specific common errors
-                        if logs.iter().any(|l| l.contains("insufficient funds")) {
-                            warn!("   💰 INSUFFICIENT FUNDS - wallet needs more SOL or tokens");
-                        }
-                        if logs.iter().any(|l| l.contains("AccountNotFound")) {
-                            warn!("   🔍 ACCOUNT NOT FOUND - likely missing ATA (Associated Token Account)");
-                        }
-                        if logs.iter().any(|l| l.contains("InvalidAccountData")) {
-                            warn!("   ❌ INVALID ACCOUNT DATA - pool address might be wrong");
-                        }
-                        if logs.iter().any(|l| l.contains("slippage")) {
-                            warn!("   📉 SLIPPAGE EXCEEDED - price moved too much");
-                        }
-                    }
+            .context("Simulation request failed")?;
 
-                    return Ok(false);
-                }
+        let logs = response.value.logs.unwrap_or_default();
+        let units_consumed = response.value.units_consumed;
+        let post_accounts = response.value.accounts.unwrap_or_default();
 
-                if let Some(logs) = response.value.logs {
-                    debug!("✅ Simulation successful. Log count: {}", logs.len());
-                    // Only show logs if trace level enabled
-                    if tracing::enabled!(tracing::Level::TRACE) {
-                        for log_entry in &logs {
-                            trace!("   {}", log_entry);
-                        }
+        if let Some(err) = response.value.err {
+            warn!("❌ Transaction simulation failed: {:?}", err);
+
+            // Enhanced error analysis
+            if !logs.is_empty() {
+                warn!("📋 Failed transaction logs:");
+                for (i, log) in logs.iter().enumerate() {
+                    if log.contains("Error")
+                        || log.contains("failed")
+                        || log.contains("insufficient")
+                    {
+                        warn!("   [{}] {}", i, log);
                     }
                 }
 
-                debug!("✅ Transaction simulation succeeded");
-                Ok(true)
-            }
-            Err(e) => {
-                warn!("❌ Failed to simulate transaction: {}", e);
-                // Check for specific RPC errors
-                let error_str = e.to_string();
-                if error_str.contains("blockhash not found") {
-                    warn!("   ⏰ Blockhash expired - need to get fresh blockhash");
-                } else if error_str.contains("network") || error_str.contains("connection") {
-                    warn!("   🌐 Network issue - RPC connection problem");
+                // Check for specific common errors
+                if logs.iter().any(|l| l.contains("insufficient funds")) {
+                    warn!("   💰 INSUFFICIENT FUNDS - wallet needs more SOL or tokens");
                 }
-                Ok(false)
+                if logs.iter().any(|l| l.contains("AccountNotFound")) {
+                    warn!(
+                        "   🔍 ACCOUNT NOT FOUND - likely missing ATA (Associated Token Account)"
+                    );
+                }
+                if logs.iter().any(|l| l.contains("InvalidAccountData")) {
+                    warn!("   ❌ INVALID ACCOUNT DATA - pool address might be wrong");
+                }
+                if logs.iter().any(|l| l.contains("slippage")) {
+                    warn!("   📉 SLIPPAGE EXCEEDED - price moved too much");
+                }
+            }
+
+            return Ok((
+                SimulationOutcome {
+                    success: false,
+                    units_consumed,
+                    error: Some(format!("{:?}", err)),
+                    logs,
+                },
+                post_accounts,
+            ));
+        }
+
+        debug!("✅ Simulation successful. Log count: {}", logs.len());
+        // Only show logs if trace level enabled
+        if tracing::enabled!(tracing::Level::TRACE) {
+            for log_entry in &logs {
+                trace!("   {}", log_entry);
             }
         }
+
+        debug!("✅ Transaction simulation succeeded");
+        Ok((
+            SimulationOutcome {
+                success: true,
+                units_consumed,
+                error: None,
+                logs,
+            },
+            post_accounts,
+        ))
     }
 
     /// Send transaction to blockchain
     pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
         debug!("Sending transaction to blockchain...");
 
+        self.throttle()?;
         let signature = self
             .client
             .send_transaction(transaction)
@@ -231,70 +756,220 @@ impl SolanaRpcClient {
         Ok(signature)
     }
 
+    /// Send versioned (v0) transaction to blockchain (e.g. Jupiter swaps with lookup tables)
+    pub fn send_versioned_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature> {
+        debug!("Sending versioned transaction to blockchain...");
+
+        self.throttle()?;
+        let signature = self
+            .client
+            .send_transaction(transaction)
+            .context("Failed to send versioned transaction")?;
+
+        info!("✅ Versioned transaction sent: {}", signature);
+        Ok(signature)
+    }
+
     /// Get account data (for fetching pool state, token accounts, etc.)
-    /// HIGH-3 FIX: Added retry logic with exponential backoff
+    /// HIGH-3 FIX: Transient failures are retried per RPC_READ_RETRIES
     /// CYCLE-5 FIX: Added circuit breaker tracking
-    pub fn get_account_data(&self, pubkey: &Pubkey) -> Result<Vec<u8>> {
+    ///
+    /// # Arguments
+    /// * `pubkey` - Account to fetch
+    /// * `commitment` - State to read (None = READ_COMMITMENT)
+    pub fn get_account_data(
+        &self,
+        pubkey: &Pubkey,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<Vec<u8>> {
         debug!("Fetching account data for: {}", pubkey);
+        let commitment = commitment.unwrap_or(self.commitment);
 
-        // Retry up to 3 times with exponential backoff
-        for attempt in 1..=3 {
-            match self.client.get_account(pubkey) {
-                Ok(account) => {
-                    debug!("✅ Got {} bytes of account data", account.data.len());
-                    self.record_success(); // CYCLE-5: Reset circuit breaker on success
-                    return Ok(account.data);
+        let response = self
+            .read_with_retry(&format!("fetch account {}", pubkey), || {
+                self.client.get_account_with_commitment(pubkey, commitment)
+            })
+            .map_err(|e| {
+                if is_not_found_error(&e.to_string()) {
+                    anyhow::anyhow!("Account not found: {}", pubkey)
+                } else {
+                    e
                 }
-                Err(e) => {
-                    // Don't retry on "account not found" - that's permanent
-                    let is_not_found = e.to_string().contains("AccountNotFound")
-                        || e.to_string().contains("not found");
-
-                    if is_not_found {
-                        // Don't count "not found" as a failure - it's expected for invalid pools
-                        return Err(anyhow::anyhow!("Account not found: {}", pubkey));
-                    }
+            })?;
+        // Don't count "not found" as a failure - it's expected for invalid pools
+        let account = response
+            .value
+            .ok_or_else(|| anyhow::anyhow!("Account not found: {}", pubkey))?;
+        debug!("✅ Got {} bytes of account data", account.data.len());
+        Ok(account.data)
+    }
 
-                    // Only retry on transient errors
-                    let is_transient = e.to_string().contains("timeout")
-                        || e.to_string().contains("network")
-                        || e.to_string().contains("connection");
-
-                    if !is_transient || attempt == 3 {
-                        self.record_failure(); // CYCLE-5: Increment circuit breaker on failure
-                        return Err(anyhow::anyhow!(
-                            "Failed to fetch account {} after {} attempts: {}",
-                            pubkey,
-                            attempt,
-                            e
-                        ));
-                    }
+    /// Priority fee (micro-lamports/CU) recently paid to write `accounts`
+    ///
+    /// Queries getRecentPrioritizationFees (one fee per recent slot, for
+    /// transactions locking any of the accounts) and returns the configured
+    /// percentile. Results are cached per account set for `PRIORITY_FEE_CACHE_TTL`.
+    ///
+    /// # Arguments
+    /// * `accounts` - Writable accounts the transaction locks (first 128 used)
+    pub fn get_prioritization_fee(&self, accounts: &[Pubkey]) -> Result<u64> {
+        let mut key: Vec<Pubkey> = accounts.to_vec();
+        key.sort();
+        key.dedup();
+        key.truncate(MAX_PRIORITY_FEE_ACCOUNTS);
 
-                    // Exponential backoff: 100ms, 200ms, 400ms
-                    let delay_ms = 100 * (1 << (attempt - 1));
-                    warn!(
-                        "⚠️ Account fetch attempt {} failed, retrying in {}ms: {}",
-                        attempt, delay_ms, e
-                    );
-                    std::thread::sleep(Duration::from_millis(delay_ms));
+        if let Ok(cache) = self.priority_fee_cache.lock() {
+            if let Some((fetched_at, fee)) = cache.get(&key) {
+                if fetched_at.elapsed() < PRIORITY_FEE_CACHE_TTL {
+                    return Ok(*fee);
                 }
             }
         }
 
-        self.record_failure(); // CYCLE-5: Increment on final failure
-        Err(anyhow::anyhow!(
-            "Failed to fetch account data after retries"
-        ))
+        let fees = self
+            .read_with_retry("fetch recent prioritization fees", || {
+                self.client.get_recent_prioritization_fees(&key)
+            })
+            .map_err(|e| ArbError::RpcError(format!("{:#}", e)))?;
+        let fee = fee_percentile(
+            fees.iter().map(|fee| fee.prioritization_fee).collect(),
+            self.priority_fee_percentile,
+        );
+        debug!(
+            "💸 p{} priority fee for {} accounts: {} micro-lamports/CU ({} slots)",
+            self.priority_fee_percentile,
+            key.len(),
+            fee,
+            fees.len()
+        );
+
+        if let Ok(mut cache) = self.priority_fee_cache.lock() {
+            cache.retain(|_, (fetched_at, _)| fetched_at.elapsed() < PRIORITY_FEE_CACHE_TTL);
+            cache.insert(key, (Instant::now(), fee));
+        }
+        Ok(fee)
+    }
+
+    /// Get account data through the TTL cache
+    ///
+    /// For validation paths ("does it exist and is it big enough") where data up to
+    /// one slot old is fine. Swap builders needing live reserves use `get_account_data`.
+    pub fn get_account_data_cached(&self, pubkey: &Pubkey) -> Result<Vec<u8>> {
+        let cached = match self.account_cache.lock() {
+            Ok(mut cache) => cache.get(pubkey, self.account_cache_ttl),
+            Err(poisoned) => poisoned.into_inner().get(pubkey, self.account_cache_ttl),
+        };
+        if let Some(data) = cached {
+            self.account_cache_hits.fetch_add(1, Ordering::Relaxed);
+            trace!("Account cache hit: {}", pubkey);
+            return Ok(data);
+        }
+
+        self.account_cache_misses.fetch_add(1, Ordering::Relaxed);
+        let data = self.get_account_data(pubkey, None)?;
+        self.prime_account_cache(*pubkey, data.clone());
+
+        Ok(data)
+    }
+
+    /// Store account data fetched elsewhere (e.g. a batch) in the TTL cache
+    pub fn prime_account_cache(&self, pubkey: Pubkey, data: Vec<u8>) {
+        match self.account_cache.lock() {
+            Ok(mut cache) => cache.insert(pubkey, data, ACCOUNT_CACHE_CAPACITY),
+            Err(poisoned) => poisoned
+                .into_inner()
+                .insert(pubkey, data, ACCOUNT_CACHE_CAPACITY),
+        }
+    }
+
+    /// Get SPL token mint info (cached after the first fetch)
+    ///
+    /// Used for `decimals`, which never change after mint creation. The cached
+    /// `supply` may be stale - don't rely on it.
+    ///
+    /// # Arguments
+    /// * `mint` - SPL Token or Token-2022 mint address
+    ///
+    /// # Returns
+    /// Unpacked base mint state (Token-2022 extensions are ignored)
+    pub fn get_mint(&self, mint: &Pubkey) -> Result<Mint> {
+        if let Some(cached) = self
+            .mint_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(mint).copied())
+        {
+            return Ok(cached);
+        }
+
+        let data = self.get_account_data(mint, None)?;
+        let info = unpack_mint(&data).context(format!("Account {} is not a token mint", mint))?;
+        debug!("🪙 Mint {} has {} decimals", mint, info.decimals);
+
+        if let Ok(mut cache) = self.mint_cache.lock() {
+            cache.insert(*mint, info);
+        }
+        Ok(info)
+    }
+
+    /// Token program (and transfer fee) of a mint, cached after the first fetch
+    ///
+    /// Transfer fee rates can be changed by the fee authority, but only take effect
+    /// after an epoch boundary - a process-lifetime cache is accurate enough for
+    /// estimation.
+    ///
+    /// # Arguments
+    /// * `mint` - Token mint address
+    ///
+    /// # Returns
+    /// Owning token program and Token-2022 transfer fee config
+    pub fn get_mint_token_program(&self, mint: &Pubkey) -> Result<MintTokenProgram> {
+        if let Some(cached) = self
+            .mint_program_cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(mint).copied())
+        {
+            return Ok(cached);
+        }
+
+        let account = self.read_with_retry(&format!("fetch mint {}", mint), || {
+            self.client.get_account(mint)
+        })?;
+        let info = MintTokenProgram::from_account(&account.owner, &account.data)?;
+        match (info.program, info.transfer_fee) {
+            (TokenProgram::Token2022, Some(fee)) => debug!(
+                "🪙 Mint {} is Token-2022 with transfer fee {}bps → {}bps from epoch {}",
+                mint, fee.older.basis_points, fee.newer.basis_points, fee.newer.epoch
+            ),
+            (TokenProgram::Token2022, None) => debug!("🪙 Mint {} is Token-2022", mint),
+            (TokenProgram::Spl, _) => {}
+        }
+
+        if let Ok(mut cache) = self.mint_program_cache.lock() {
+            cache.insert(*mint, info);
+        }
+        Ok(info)
+    }
+
+    /// Account cache (hits, misses) since startup
+    pub fn account_cache_stats(&self) -> (u64, u64) {
+        (
+            self.account_cache_hits.load(Ordering::Relaxed),
+            self.account_cache_misses.load(Ordering::Relaxed),
+        )
     }
 
     /// Fetch multiple accounts in one RPC call (efficient)
     pub fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
         debug!("Fetching {} accounts in batch...", pubkeys.len());
 
-        let accounts = self
-            .client
-            .get_multiple_accounts(pubkeys)
-            .context("Failed to fetch multiple accounts")?;
+        let accounts = self.read_with_retry("fetch multiple accounts", || {
+            self.client.get_multiple_accounts(pubkeys)
+        })?;
 
         let data: Vec<Option<Vec<u8>>> = accounts
             .into_iter()
@@ -310,7 +985,9 @@ impl SolanaRpcClient {
     /// Check if account exists AND has non-zero data (ghost pool protection)
     /// Returns false if account doesn't exist OR has 0 bytes of data
     pub fn account_exists(&self, pubkey: &Pubkey) -> Result<bool> {
-        match self.client.get_account(pubkey) {
+        match self.read_with_retry(&format!("fetch account {}", pubkey), || {
+            self.client.get_account(pubkey)
+        }) {
             Ok(account) => {
                 // Account exists, but check if it has data
                 if account.data.is_empty() || account.lamports == 0 {
@@ -322,11 +999,10 @@ impl SolanaRpcClient {
             }
             Err(e) => {
                 // Check if it's "account not found" error vs other errors
-                if e.to_string().contains("AccountNotFound") || e.to_string().contains("not found")
-                {
+                if is_not_found_error(&e.to_string()) {
                     Ok(false)
                 } else {
-                    Err(anyhow::anyhow!("Error checking account existence: {}", e))
+                    Err(e.context("Error checking account existence"))
                 }
             }
         }
@@ -334,10 +1010,9 @@ impl SolanaRpcClient {
 
     /// Get account owner (program that owns this account)
     pub fn get_account_owner(&self, pubkey: &Pubkey) -> Result<Pubkey> {
-        let account = self
-            .client
-            .get_account(pubkey)
-            .context(format!("Failed to fetch account {}", pubkey))?;
+        let account = self.read_with_retry(&format!("fetch account {}", pubkey), || {
+            self.client.get_account(pubkey)
+        })?;
 
         Ok(account.owner)
     }
@@ -346,31 +1021,105 @@ impl SolanaRpcClient {
     /// Returns Ok(Some(true)) if confirmed successfully, Ok(Some(false)) if failed, Ok(None) if pending
     pub fn get_transaction_status(&self, signature: &Signature) -> Result<Option<bool>> {
         // Poll blockchain for transaction status
-        match self.client.get_signature_status(signature) {
-            Ok(Some(result)) => {
-                // Transaction found in blockchain
-                match result {
-                    Ok(_) => Ok(Some(true)),   // Confirmed successfully
-                    Err(_) => Ok(Some(false)), // Failed on-chain
-                }
-            }
-            Ok(None) => Ok(None), // Not yet confirmed
-            Err(e) => Err(anyhow::anyhow!("Error checking transaction status: {}", e)),
+        let status = self
+            .read_with_retry(&format!("check transaction status {}", signature), || {
+                self.client.get_signature_status(signature)
+            })?;
+        match status {
+            // Transaction found in blockchain
+            Some(Ok(_)) => Ok(Some(true)),   // Confirmed successfully
+            Some(Err(_)) => Ok(Some(false)), // Failed on-chain
+            None => Ok(None),                // Not yet confirmed
         }
     }
 
+    /// Lamport balance change of `account` caused by a confirmed transaction
+    ///
+    /// Read from the transaction's own pre/post balances, so it is exactly what
+    /// that transaction moved (fees and tips included), even when other
+    /// transactions touch the account in the same window.
+    ///
+    /// # Errors
+    /// Returns error if the transaction is not available at `confirmed` yet, has
+    /// no status meta, or does not reference `account` as a static key
+    pub fn get_transaction_balance_change(
+        &self,
+        signature: &Signature,
+        account: &Pubkey,
+    ) -> Result<i64> {
+        let transaction = self.get_confirmed_transaction(signature)?;
+        lamport_change(&transaction, account)
+            .with_context(|| format!("No balance change for {} in {}", account, signature))
+    }
+
+    /// Change in `owner`'s total `mint` token balance caused by a confirmed transaction
+    ///
+    /// Sums every token account of `mint` owned by `owner` in the transaction's
+    /// pre/post token balances (accounts opened or closed by it count as zero
+    /// on the missing side).
+    ///
+    /// # Errors
+    /// Returns error if the transaction is not available at `confirmed` yet or
+    /// has no token balance meta
+    pub fn get_transaction_token_change(
+        &self,
+        signature: &Signature,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<i128> {
+        let transaction = self.get_confirmed_transaction(signature)?;
+        token_change(&transaction, owner, mint)
+            .with_context(|| format!("No token balances in {}", signature))
+    }
+
+    /// Total `mint` balance across all of `owner`'s token accounts (base units)
+    ///
+    /// # Errors
+    /// Returns error if the accounts can't be fetched or one isn't parsed token data
+    pub fn get_wallet_token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> Result<u64> {
+        let accounts = self.read_with_retry(
+            &format!("fetch {} token accounts of {}", mint, owner),
+            || {
+                self.client
+                    .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+            },
+        )?;
+        accounts.iter().try_fold(0u64, |total, keyed| {
+            let amount = parsed_token_amount(&keyed.account.data)
+                .with_context(|| format!("Unparsed token account {}", keyed.pubkey))?;
+            Ok(total.saturating_add(amount))
+        })
+    }
+
+    /// Fetch a transaction with its status meta at `confirmed` commitment
+    fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<EncodedTransactionWithStatusMeta> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let confirmed = self
+            .read_with_retry(&format!("fetch transaction {}", signature), || {
+                self.client.get_transaction_with_config(signature, config)
+            })?;
+        Ok(confirmed.transaction)
+    }
+
     /// Get balance of an account (in lamports)
     pub fn get_balance(&self, pubkey: &Pubkey) -> Result<u64> {
-        let balance = self
-            .client
-            .get_balance(pubkey)
-            .context(format!("Failed to get balance for {}", pubkey))?;
+        let balance = self.read_with_retry(&format!("get balance for {}", pubkey), || {
+            self.client.get_balance(pubkey)
+        })?;
 
         Ok(balance)
     }
 
     /// Health check - verify RPC connection is working
     pub fn health_check(&self) -> Result<bool> {
+        self.throttle()?;
         match self.client.get_health() {
             Ok(_) => {
                 debug!("✅ RPC health check passed");
@@ -385,15 +1134,145 @@ impl SolanaRpcClient {
 
     /// Get current slot
     pub fn get_slot(&self) -> Result<u64> {
-        let slot = self
-            .client
-            .get_slot()
-            .context("Failed to get current slot")?;
+        let slot = self.read_with_retry("get current slot", || self.client.get_slot())?;
 
         Ok(slot)
     }
 }
 
+/// RPC calls the swap pipeline depends on (pool resolution, DEX builders, executor)
+///
+/// `SolanaRpcClient` is the production implementation; tests substitute an
+/// in-memory client so the pipeline can run without a validator.
+pub trait RpcApi: Send + Sync {
+    /// See [`SolanaRpcClient::check_circuit_breaker`]
+    fn check_circuit_breaker(&self) -> Result<()>;
+    /// See [`SolanaRpcClient::get_latest_blockhash`]
+    fn get_latest_blockhash(&self) -> Result<Hash>;
+    /// See [`SolanaRpcClient::simulate_transaction`]
+    fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<bool>;
+    /// See [`SolanaRpcClient::send_transaction`]
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature>;
+    /// See [`SolanaRpcClient::get_account_data`]
+    fn get_account_data(
+        &self,
+        pubkey: &Pubkey,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<Vec<u8>>;
+    /// See [`SolanaRpcClient::get_prioritization_fee`]
+    fn get_prioritization_fee(&self, accounts: &[Pubkey]) -> Result<u64>;
+    /// See [`SolanaRpcClient::get_account_data_cached`]
+    fn get_account_data_cached(&self, pubkey: &Pubkey) -> Result<Vec<u8>>;
+    /// See [`SolanaRpcClient::prime_account_cache`]
+    fn prime_account_cache(&self, pubkey: Pubkey, data: Vec<u8>);
+    /// See [`SolanaRpcClient::get_mint_token_program`]
+    fn get_mint_token_program(&self, mint: &Pubkey) -> Result<MintTokenProgram>;
+    /// See [`SolanaRpcClient::get_multiple_accounts`]
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>>;
+    /// See [`SolanaRpcClient::account_exists`]
+    fn account_exists(&self, pubkey: &Pubkey) -> Result<bool>;
+    /// See [`SolanaRpcClient::get_account_owner`]
+    fn get_account_owner(&self, pubkey: &Pubkey) -> Result<Pubkey>;
+    /// See [`SolanaRpcClient::get_transaction_status`]
+    fn get_transaction_status(&self, signature: &Signature) -> Result<Option<bool>>;
+    /// See [`SolanaRpcClient::health_check`]
+    fn health_check(&self) -> Result<bool>;
+    /// See [`SolanaRpcClient::get_slot`]
+    fn get_slot(&self) -> Result<u64>;
+}
+
+impl RpcApi for SolanaRpcClient {
+    fn check_circuit_breaker(&self) -> Result<()> {
+        SolanaRpcClient::check_circuit_breaker(self)
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        SolanaRpcClient::get_latest_blockhash(self)
+    }
+
+    fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<bool> {
+        SolanaRpcClient::simulate_transaction(self, transaction, commitment)
+    }
+
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        SolanaRpcClient::send_transaction(self, transaction)
+    }
+
+    fn get_account_data(
+        &self,
+        pubkey: &Pubkey,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<Vec<u8>> {
+        SolanaRpcClient::get_account_data(self, pubkey, commitment)
+    }
+
+    fn get_prioritization_fee(&self, accounts: &[Pubkey]) -> Result<u64> {
+        SolanaRpcClient::get_prioritization_fee(self, accounts)
+    }
+
+    fn get_account_data_cached(&self, pubkey: &Pubkey) -> Result<Vec<u8>> {
+        SolanaRpcClient::get_account_data_cached(self, pubkey)
+    }
+
+    fn prime_account_cache(&self, pubkey: Pubkey, data: Vec<u8>) {
+        SolanaRpcClient::prime_account_cache(self, pubkey, data)
+    }
+
+    fn get_mint_token_program(&self, mint: &Pubkey) -> Result<MintTokenProgram> {
+        SolanaRpcClient::get_mint_token_program(self, mint)
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        SolanaRpcClient::get_multiple_accounts(self, pubkeys)
+    }
+
+    fn account_exists(&self, pubkey: &Pubkey) -> Result<bool> {
+        SolanaRpcClient::account_exists(self, pubkey)
+    }
+
+    fn get_account_owner(&self, pubkey: &Pubkey) -> Result<Pubkey> {
+        SolanaRpcClient::get_account_owner(self, pubkey)
+    }
+
+    fn get_transaction_status(&self, signature: &Signature) -> Result<Option<bool>> {
+        SolanaRpcClient::get_transaction_status(self, signature)
+    }
+
+    fn health_check(&self) -> Result<bool> {
+        SolanaRpcClient::health_check(self)
+    }
+
+    fn get_slot(&self) -> Result<u64> {
+        SolanaRpcClient::get_slot(self)
+    }
+}
+
+/// Nearest-rank `percentile` of `fees` (0 if there are none)
+fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() * percentile.min(100) as usize).div_ceil(100);
+    fees[rank.saturating_sub(1)]
+}
+
+/// Unpack the base `Mint` layout (Token-2022 mints append extensions after it)
+fn unpack_mint(data: &[u8]) -> Result<Mint> {
+    let base = data
+        .get(..Mint::LEN)
+        .ok_or_else(|| anyhow::anyhow!("Mint account too small: {} bytes", data.len()))?;
+    Mint::unpack(base).map_err(|e| anyhow::anyhow!("Invalid mint account: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,5 +1286,364 @@ mod tests {
         assert!(client.commitment.is_confirmed());
     }
 
+    #[test]
+    fn test_rate_limit_queues_then_rejects() {
+        // 20 rps: burst of 20, then one token every 50ms (within the queue window)
+        let client = SolanaRpcClient::new("http://127.0.0.1:1".to_string()).with_max_rps(20);
+        for _ in 0..21 {
+            client.throttle().unwrap();
+        }
+        let stats = client.rate_limit_stats();
+        assert_eq!(stats.throttled_calls, 1);
+        assert_eq!(stats.rejected_calls, 0);
+
+        // 1 rps: the next token is ~1s away, beyond the queue window
+        let client = SolanaRpcClient::new("http://127.0.0.1:1".to_string()).with_max_rps(1);
+        client.throttle().unwrap();
+        let err = client.throttle().unwrap_err();
+        assert!(err.to_string().contains("rate limit exhausted"), "{}", err);
+        assert_eq!(client.rate_limit_stats().rejected_calls, 1);
+
+        // 0 disables the limiter
+        let client = SolanaRpcClient::new("http://127.0.0.1:1".to_string()).with_max_rps(0);
+        for _ in 0..100 {
+            client.throttle().unwrap();
+        }
+        assert_eq!(client.rate_limit_stats(), RpcRateLimitStats::default());
+    }
+
+    /// Answer JSON-RPC calls until `requests` non-getVersion bodies are captured
+    ///
+    /// getVersion (the client's node-version probe) gets a real reply, everything
+    /// else an error - only the requests matter.
+    fn capture_rpc_requests(requests: usize) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for stream in listener.incoming() {
+                let mut reader = BufReader::new(stream.unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8(body).unwrap();
+
+                let reply = if body.contains("\"getVersion\"") {
+                    r#"{"jsonrpc":"2.0","result":{"solana-core":"1.18.0","feature-set":0},"id":1}"#
+                } else {
+                    bodies.push(body);
+                    r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"test"},"id":1}"#
+                };
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    reply.len(),
+                    reply
+                )
+                .unwrap();
+                if bodies.len() == requests {
+                    break;
+                }
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_commitment_passed_through_to_rpc_requests() {
+        let (url, server) = capture_rpc_requests(4);
+        let client = SolanaRpcClient::new(url)
+            .with_sim_commitment(CommitmentConfig::processed())
+            .with_read_commitment(CommitmentConfig::confirmed());
+
+        let transaction = Transaction::new_with_payer(&[], Some(&Pubkey::new_unique()));
+        let account = Pubkey::new_unique();
+
+        // Defaults, then explicit overrides
+        let _ = client.simulate_transaction(&transaction, None);
+        let _ = client.get_account_data(&account, None);
+        let _ = client.simulate_transaction(&transaction, Some(CommitmentConfig::finalized()));
+        let _ = client.get_account_data(&account, Some(CommitmentConfig::processed()));
+
+        let bodies = server.join().unwrap();
+        let commitments: Vec<(String, String)> = bodies
+            .iter()
+            .map(|body| {
+                let request: serde_json::Value = serde_json::from_str(body).unwrap();
+                let config = request["params"]
+                    .as_array()
+                    .and_then(|params| params.last())
+                    .unwrap();
+                (
+                    request["method"].as_str().unwrap().to_string(),
+                    config["commitment"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            commitments,
+            vec![
+                ("simulateTransaction".to_string(), "processed".to_string()),
+                ("getAccountInfo".to_string(), "confirmed".to_string()),
+                ("simulateTransaction".to_string(), "finalized".to_string()),
+                ("getAccountInfo".to_string(), "processed".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fee_percentile_nearest_rank() {
+        let fees: Vec<u64> = (1..=20).map(|slot| slot * 1_000).collect();
+        assert_eq!(fee_percentile(fees.clone(), 75), 15_000);
+        assert_eq!(fee_percentile(fees.clone(), 50), 10_000);
+        assert_eq!(fee_percentile(fees.clone(), 100), 20_000);
+        assert_eq!(fee_percentile(fees, 0), 1_000);
+        // Mostly idle slots keep the median at zero, p75 picks up the contention
+        assert_eq!(fee_percentile(vec![0, 0, 0, 50_000], 50), 0);
+        assert_eq!(fee_percentile(vec![0, 0, 0, 50_000], 75), 0);
+        assert_eq!(fee_percentile(vec![0, 0, 50_000, 80_000], 75), 50_000);
+        assert_eq!(fee_percentile(Vec::new(), 75), 0);
+    }
+
+    #[test]
+    fn test_account_cache_ttl_expiry() {
+        let mut cache = AccountCache::default();
+        let pubkey = Pubkey::new_unique();
+        cache.insert(pubkey, vec![1, 2, 3], 8);
+
+        assert_eq!(
+            cache.get(&pubkey, Duration::from_secs(60)),
+            Some(vec![1, 2, 3])
+        );
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(cache.get(&pubkey, Duration::from_millis(10)), None);
+        // Expired entry is dropped, not just hidden
+        assert_eq!(cache.get(&pubkey, Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn test_account_cache_evicts_least_recently_used() {
+        let mut cache = AccountCache::default();
+        let ttl = Duration::from_secs(60);
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        cache.insert(a, vec![1], 2);
+        cache.insert(b, vec![2], 2);
+        // Touch `a` so `b` becomes least recently used
+        assert!(cache.get(&a, ttl).is_some());
+        cache.insert(c, vec![3], 2);
+
+        assert!(cache.get(&a, ttl).is_some());
+        assert!(cache.get(&b, ttl).is_none());
+        assert!(cache.get(&c, ttl).is_some());
+    }
+
     // Note: Most tests require a live RPC connection and are better suited for integration tests
+
+    #[test]
+    fn test_unpack_mint_with_extensions() {
+        let mint = Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; Mint::LEN];
+        Mint::pack(mint, &mut data).unwrap();
+        assert_eq!(unpack_mint(&data).unwrap().decimals, 6);
+
+        // Token-2022 mint: extension data after the base layout is ignored
+        data.extend_from_slice(&[0u8; 83]);
+        assert_eq!(unpack_mint(&data).unwrap().decimals, 6);
+
+        assert!(unpack_mint(&data[..Mint::LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_leg_output_from_simulated_balances() {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        data[64..72].copy_from_slice(&1_234_567u64.to_le_bytes());
+        assert_eq!(token_account_amount(Some(&data)).ok(), Some(1_234_567));
+        assert!(token_account_amount(Some(&data[..100])).is_err());
+
+        // Intermediate leg: 900 received, 850 immediately spent by the next leg
+        assert_eq!(leg_output_amount(10, 60, 850), Some(900));
+        // Final SOL leg: 1.0 SOL spent by leg 1, 1.01 SOL returned
+        assert_eq!(
+            leg_output_amount(5_000_000_000, 5_010_000_000, 1_000_000_000),
+            Some(1_010_000_000)
+        );
+        // Balance fell by more than the known spend - inconsistent
+        assert_eq!(leg_output_amount(100, 10, 50), None);
+    }
+
+    #[test]
+    fn test_lamport_change_reads_the_transactions_own_balances() {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+        use solana_sdk::{signature::Keypair, signer::Signer, system_instruction};
+
+        let wallet = Keypair::new();
+        let other = Pubkey::new_unique();
+        let transaction = Transaction::new_signed_with_payer(
+            &[system_instruction::transfer(
+                &wallet.pubkey(),
+                &other,
+                1_000,
+            )],
+            Some(&wallet.pubkey()),
+            &[&wallet],
+            Hash::default(),
+        );
+        let encoded = BASE64.encode(bincode::serialize(&transaction).unwrap());
+        let fetched: EncodedTransactionWithStatusMeta = serde_json::from_value(serde_json::json!({
+            "transaction": [encoded, "base64"],
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5_000,
+                "preBalances": [2_000_000, 0, 1],
+                "postBalances": [1_994_000, 1_000, 1],
+            },
+        }))
+        .unwrap();
+
+        // Fee payer paid the transfer plus the fee
+        assert_eq!(lamport_change(&fetched, &wallet.pubkey()), Some(-6_000));
+        assert_eq!(lamport_change(&fetched, &other), Some(1_000));
+        // Not part of this transaction
+        assert_eq!(lamport_change(&fetched, &Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn test_token_change_sums_the_owners_accounts_of_the_mint() {
+        let (wallet, someone_else) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let balance = |index: u8, mint: &Pubkey, owner: &Pubkey, amount: u64| {
+            serde_json::json!({
+                "accountIndex": index,
+                "mint": mint.to_string(),
+                "owner": owner.to_string(),
+                "uiTokenAmount": {
+                    "uiAmount": null,
+                    "decimals": 6,
+                    "amount": amount.to_string(),
+                    "uiAmountString": "",
+                },
+            })
+        };
+        let fetched: EncodedTransactionWithStatusMeta = serde_json::from_value(serde_json::json!({
+            "transaction": ["", "base64"],
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5_000,
+                "preBalances": [],
+                "postBalances": [],
+                "preTokenBalances": [
+                    balance(1, &mint, &wallet, 500),
+                    balance(2, &mint, &someone_else, 9_000),
+                ],
+                // Swap output lands in a second account opened by the transaction
+                "postTokenBalances": [
+                    balance(1, &mint, &wallet, 0),
+                    balance(2, &mint, &someone_else, 8_000),
+                    balance(3, &mint, &wallet, 1_500),
+                    balance(4, &other_mint, &wallet, 7),
+                ],
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(token_change(&fetched, &wallet, &mint), Some(1_000));
+        assert_eq!(token_change(&fetched, &someone_else, &mint), Some(-1_000));
+        assert_eq!(token_change(&fetched, &wallet, &other_mint), Some(7));
+    }
+
+    #[test]
+    fn test_parsed_token_amount() {
+        let data: UiAccountData = serde_json::from_value(serde_json::json!({
+            "program": "spl-token",
+            "parsed": {
+                "type": "account",
+                "info": {
+                    "tokenAmount": { "amount": "1234567", "decimals": 6 },
+                },
+            },
+            "space": 165,
+        }))
+        .unwrap();
+        assert_eq!(parsed_token_amount(&data), Some(1_234_567));
+
+        let binary = UiAccountData::Binary(String::new(), UiAccountEncoding::Base64);
+        assert_eq!(parsed_token_amount(&binary), None);
+    }
+
+    #[test]
+    fn test_transient_read_failures_are_retried() {
+        let client = SolanaRpcClient::new("http://127.0.0.1:1".to_string()).with_retry_policy(
+            RpcRetryPolicy {
+                max_retries: 2,
+                base_backoff: Duration::from_millis(1),
+            },
+        );
+
+        // Mock read: 503, then a timeout, then the account data
+        let mut attempts = 0;
+        let data = client
+            .read_with_retry("fetch account", || {
+                attempts += 1;
+                match attempts {
+                    1 => Err("HTTP status server error (503 Service Unavailable)"),
+                    2 => Err("operation timed out"),
+                    _ => Ok(vec![1u8, 2, 3]),
+                }
+            })
+            .unwrap();
+        assert_eq!(data, vec![1, 2, 3]);
+        assert_eq!(attempts, 3);
+        assert_eq!(client.consecutive_failures.load(Ordering::Relaxed), 0);
+
+        // Exhausted retries count once towards the circuit breaker
+        let mut attempts = 0;
+        let result: Result<()> = client.read_with_retry("fetch account", || {
+            attempts += 1;
+            Err("429 Too Many Requests")
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+        assert_eq!(client.consecutive_failures.load(Ordering::Relaxed), 1);
+
+        // Non-retriable errors fail on the first attempt; missing accounts never count
+        let mut attempts = 0;
+        let result: Result<()> = client.read_with_retry("fetch account", || {
+            attempts += 1;
+            Err("Invalid param: WrongSize")
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+        assert_eq!(client.consecutive_failures.load(Ordering::Relaxed), 2);
+        let result: Result<()> = client.read_with_retry("fetch account", || {
+            Err("AccountNotFound: pubkey=11111111111111111111111111111111")
+        });
+        assert!(result.is_err());
+        assert_eq!(client.consecutive_failures.load(Ordering::Relaxed), 2);
+    }
 }
@@ -0,0 +1,530 @@
+// Saros swap instruction builder
+//
+// Saros AMM (SSwpkEEWHvCXCNWnMYXVW7gCYDXkF4aQMxKdpEqrZks) is a fork of the SPL
+// token-swap program: a constant-product pool whose state account stores both
+// vaults, the LP mint, the fee account and the fee schedule. The pool authority
+// is a PDA seeded by the pool address and the bump stored in the pool state.
+
+use anyhow::{Context, Result};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::amm_math::{constant_product_output, fee_amount, token_account_amount};
+use crate::pool_registry::PoolRegistry;
+use crate::rpc_client::RpcApi;
+use crate::types::{SwapParams, UserSwapAccounts};
+
+/// Token-swap instruction tag for `Swap`
+const SWAP_INSTRUCTION_TAG: u8 = 1;
+
+/// Saros pool state fields needed to build and price a swap
+///
+/// Offsets follow the SPL token-swap `SwapV1` layout (324 bytes):
+/// - byte 0: version
+/// - byte 1: is_initialized
+/// - byte 2: bump_seed (authority PDA bump)
+/// - bytes 3-35: token_program_id
+/// - bytes 35-67: token_a (vault A)
+/// - bytes 67-99: token_b (vault B)
+/// - bytes 99-131: pool_mint (LP mint)
+/// - bytes 131-163: token_a_mint
+/// - bytes 163-195: token_b_mint
+/// - bytes 195-227: pool_fee_account
+/// - bytes 227-259: trade_fee_numerator/denominator, owner_trade_fee_numerator/denominator (u64s)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SarosPoolState {
+    pub bump_seed: u8,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub pool_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub fee_account: Pubkey,
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub owner_trade_fee_numerator: u64,
+    pub owner_trade_fee_denominator: u64,
+}
+
+impl SarosPoolState {
+    /// Minimum account length covering all fields we read
+    const MIN_LEN: usize = 259;
+
+    /// Parse Saros pool account data
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < Self::MIN_LEN {
+            return Err(anyhow::anyhow!(
+                "Saros pool state too short ({} bytes). Expected at least {} bytes.",
+                data.len(),
+                Self::MIN_LEN
+            ));
+        }
+
+        if data[1] == 0 {
+            return Err(anyhow::anyhow!("Saros pool account is not initialized"));
+        }
+
+        let pubkey_at = |offset: usize, name: &str| -> Result<Pubkey> {
+            Pubkey::try_from(&data[offset..offset + 32])
+                .map_err(|_| anyhow::anyhow!("Failed to parse {} pubkey from pool state", name))
+        };
+        let u64_at = |offset: usize| -> Result<u64> {
+            Ok(u64::from_le_bytes(data[offset..offset + 8].try_into()?))
+        };
+
+        Ok(Self {
+            bump_seed: data[2],
+            token_a_vault: pubkey_at(35, "token A vault")?,
+            token_b_vault: pubkey_at(67, "token B vault")?,
+            pool_mint: pubkey_at(99, "pool mint")?,
+            token_a_mint: pubkey_at(131, "token A mint")?,
+            token_b_mint: pubkey_at(163, "token B mint")?,
+            fee_account: pubkey_at(195, "pool fee account")?,
+            trade_fee_numerator: u64_at(227)?,
+            trade_fee_denominator: u64_at(235)?,
+            owner_trade_fee_numerator: u64_at(243)?,
+            owner_trade_fee_denominator: u64_at(251)?,
+        })
+    }
+
+    /// Input left after trade + owner trade fees are taken
+    pub fn amount_after_fees(&self, amount_in: u64) -> u64 {
+        amount_in
+            .saturating_sub(fee_amount(
+                amount_in,
+                self.trade_fee_numerator,
+                self.trade_fee_denominator,
+            ))
+            .saturating_sub(fee_amount(
+                amount_in,
+                self.owner_trade_fee_numerator,
+                self.owner_trade_fee_denominator,
+            ))
+    }
+
+    /// Constant-product output for `amount_in` after trade + owner trade fees
+    pub fn quote(&self, amount_in: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+        constant_product_output(self.amount_after_fees(amount_in), reserve_in, reserve_out)
+    }
+}
+
+/// Saros swap instruction builder
+pub struct SarosSwapBuilder {
+    /// RPC client for fetching pool state
+    rpc_client: Arc<dyn RpcApi>,
+    /// Pool registry for address resolution
+    pool_registry: Arc<PoolRegistry>,
+    /// Saros AMM program ID
+    program_id: Pubkey,
+}
+
+impl SarosSwapBuilder {
+    /// Saros AMM program ID
+    pub const PROGRAM_ID: &'static str = "SSwpkEEWHvCXCNWnMYXVW7gCYDXkF4aQMxKdpEqrZks";
+
+    /// Estimated compute units for one Saros swap (constant-product math)
+    pub fn estimated_compute_units(&self) -> u32 {
+        40_000
+    }
+
+    /// Create new Saros swap builder
+    pub fn new(rpc_client: Arc<dyn RpcApi>, pool_registry: Arc<PoolRegistry>) -> Result<Self> {
+        let program_id = Self::PROGRAM_ID
+            .parse()
+            .context("Failed to parse Saros program ID")?;
+
+        info!("✅ Saros swap builder initialized");
+        info!("   Program ID: {}", Self::PROGRAM_ID);
+
+        Ok(Self {
+            rpc_client,
+            pool_registry,
+            program_id,
+        })
+    }
+
+    /// Build swap instruction for a Saros pool
+    ///
+    /// # Arguments
+    /// * `pool_short_id` - 8-char short pool ID from ShredStream
+    /// * `swap_params` - Swap parameters (amount_in, minimum_amount_out, direction)
+    /// * `user_pubkey` - User's wallet public key
+    ///
+    /// # Returns
+    /// Solana instruction for the swap
+    pub async fn build_swap_instruction(
+        &self,
+        pool_short_id: &str,
+        swap_params: &SwapParams,
+        user_pubkey: &Pubkey,
+    ) -> Result<Instruction> {
+        debug!(
+            "Building Saros swap instruction for pool: {}",
+            pool_short_id
+        );
+
+        // Step 1: Resolve pool address from short ID
+        let pool_address = self
+            .pool_registry
+            .resolve_pool_address(pool_short_id, &crate::types::DexType::Saros)
+            .await
+            .context(format!(
+                "Failed to resolve pool address for {}",
+                pool_short_id
+            ))?;
+
+        debug!(
+            "✅ Resolved pool {} to address: {}",
+            pool_short_id, pool_address
+        );
+
+        // Safety fallback - pools are normally validated by the arbitrage engine
+        self.pool_registry.ensure_pool_valid(pool_short_id).await?;
+
+        // Step 2: Fetch and parse pool state (vaults, mints, fees)
+        let pool = self.fetch_pool_state(&pool_address)?;
+
+        debug!("Token Vault A: {}", pool.token_a_vault);
+        debug!("Token Vault B: {}", pool.token_b_vault);
+
+        // Step 3: Derive pool authority and user token accounts
+        let authority = Self::derive_authority(&pool_address, pool.bump_seed, &self.program_id)?;
+
+        let (mint_in, mint_out) = if swap_params.swap_a_to_b {
+            (pool.token_a_mint, pool.token_b_mint)
+        } else {
+            (pool.token_b_mint, pool.token_a_mint)
+        };
+        let user = UserSwapAccounts {
+            owner: *user_pubkey,
+            token_in: spl_associated_token_account::get_associated_token_address(
+                user_pubkey,
+                &mint_in,
+            ),
+            token_out: spl_associated_token_account::get_associated_token_address(
+                user_pubkey,
+                &mint_out,
+            ),
+        };
+
+        // Step 4: Build instruction
+        let instruction = Self::build_saros_swap_ix(
+            &self.program_id,
+            &pool_address,
+            &authority,
+            &pool,
+            &user,
+            swap_params,
+        );
+
+        info!("✅ Built Saros swap instruction");
+        info!("   Pool: {}", pool_address);
+        info!("   Amount in: {} lamports", swap_params.amount_in);
+        info!(
+            "   Min amount out: {} lamports",
+            swap_params.minimum_amount_out
+        );
+        info!(
+            "   Direction: {}",
+            if swap_params.swap_a_to_b {
+                "A→B"
+            } else {
+                "B→A"
+            }
+        );
+
+        Ok(instruction)
+    }
+
+    /// Fetch and parse Saros pool account
+    fn fetch_pool_state(&self, pool_address: &Pubkey) -> Result<SarosPoolState> {
+        let data = self
+            .rpc_client
+            .get_account_data(pool_address, None)
+            .context("Failed to fetch Saros pool state")?;
+
+        SarosPoolState::parse(&data)
+    }
+
+    /// Derive the pool authority PDA (seeds: [pool, bump_seed])
+    pub fn derive_authority(pool: &Pubkey, bump_seed: u8, program_id: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_program_address(&[pool.as_ref(), &[bump_seed]], program_id)
+            .context("Failed to derive Saros pool authority from stored bump seed")
+    }
+
+    /// Build the raw Saros swap instruction
+    ///
+    /// Account order for token-swap `Swap`:
+    /// 0. [readonly] swap (Pool state account)
+    /// 1. [readonly] authority (Pool authority PDA)
+    /// 2. [signer] user_transfer_authority (User wallet)
+    /// 3. [writable] source (User's input token account)
+    /// 4. [writable] swap_source (Pool vault receiving input)
+    /// 5. [writable] swap_destination (Pool vault sending output)
+    /// 6. [writable] destination (User's output token account)
+    /// 7. [writable] pool_mint (LP mint)
+    /// 8. [writable] pool_fee_account
+    /// 9. [readonly] token_program
+    fn build_saros_swap_ix(
+        program_id: &Pubkey,
+        pool_address: &Pubkey,
+        authority: &Pubkey,
+        pool: &SarosPoolState,
+        user: &UserSwapAccounts,
+        swap_params: &SwapParams,
+    ) -> Instruction {
+        let (swap_source, swap_destination) = if swap_params.swap_a_to_b {
+            (pool.token_a_vault, pool.token_b_vault)
+        } else {
+            (pool.token_b_vault, pool.token_a_vault)
+        };
+
+        let accounts = vec![
+            AccountMeta::new_readonly(*pool_address, false),
+            AccountMeta::new_readonly(*authority, false),
+            AccountMeta::new_readonly(user.owner, true),
+            AccountMeta::new(user.token_in, false),
+            AccountMeta::new(swap_source, false),
+            AccountMeta::new(swap_destination, false),
+            AccountMeta::new(user.token_out, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new(pool.fee_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ];
+
+        // Instruction data: [tag: 1][amount_in: u64][minimum_amount_out: u64]
+        let mut data = Vec::with_capacity(17);
+        data.push(SWAP_INSTRUCTION_TAG);
+        data.extend_from_slice(&swap_params.amount_in.to_le_bytes());
+        data.extend_from_slice(&swap_params.minimum_amount_out.to_le_bytes());
+
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }
+    }
+
+    /// Estimate output amount for a swap (useful for slippage calculation)
+    ///
+    /// Constant product (x*y=k) over current vault balances, after the pool's
+    /// trade and owner fees.
+    ///
+    /// # Returns
+    /// Expected output amount (after fees)
+    pub fn estimate_swap_output(
+        &self,
+        pool_short_id: &str,
+        amount_in: u64,
+        swap_a_to_b: bool,
+    ) -> Result<u64> {
+        debug!("Estimating swap output for Saros pool: {}", pool_short_id);
+
+        let pool_info = self
+            .pool_registry
+            .get_pool(pool_short_id)
+            .ok_or_else(|| anyhow::anyhow!("Pool {} not found", pool_short_id))?;
+
+        let pool = self.fetch_pool_state(&pool_info.full_address)?;
+
+        let vaults = self
+            .rpc_client
+            .get_multiple_accounts(&[pool.token_a_vault, pool.token_b_vault])?;
+        let reserve_a = token_account_amount(vaults.first().and_then(|v| v.as_deref()))
+            .context("Failed to read Saros vault A balance")?;
+        let reserve_b = token_account_amount(vaults.get(1).and_then(|v| v.as_deref()))
+            .context("Failed to read Saros vault B balance")?;
+
+        let (reserve_in, reserve_out) = if swap_a_to_b {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+
+        Ok(pool.quote(amount_in, reserve_in, reserve_out))
+    }
+
+    /// SOL-side reserve of a pool (liquidity depth check before position sizing)
+    ///
+    /// # Returns
+    /// WSOL vault balance in lamports, or error if neither side of the pool is WSOL
+    pub fn sol_reserve(&self, pool_address: &Pubkey) -> Result<u64> {
+        let pool = self.fetch_pool_state(pool_address)?;
+        let sol_vault = if pool.token_a_mint == spl_token::native_mint::id() {
+            pool.token_a_vault
+        } else if pool.token_b_mint == spl_token::native_mint::id() {
+            pool.token_b_vault
+        } else {
+            return Err(anyhow::anyhow!(
+                "Saros pool {} has no WSOL side",
+                pool_address
+            ));
+        };
+
+        let data = self
+            .rpc_client
+            .get_account_data(&sol_vault, None)
+            .context("Failed to fetch Saros SOL vault")?;
+        token_account_amount(Some(&data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::captured_fixtures::{pubkey, CapturedFixture};
+
+    /// SwapV1 pool account laid out like an on-chain Saros pool, with the
+    /// bump seed taken from the canonical authority PDA for `pool_address`
+    fn sample_pool_data(pool_address: &Pubkey, program_id: &Pubkey) -> (Vec<u8>, Vec<Pubkey>) {
+        let mut data = vec![0u8; 324];
+        data[0] = 1; // version
+        data[1] = 1; // is_initialized
+        data[2] = Pubkey::find_program_address(&[pool_address.as_ref()], program_id).1;
+        data[3..35].copy_from_slice(spl_token::id().as_ref());
+
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        for (i, key) in keys.iter().enumerate() {
+            let offset = 35 + i * 32;
+            data[offset..offset + 32].copy_from_slice(key.as_ref());
+        }
+
+        // 0.25% trade fee + 0.05% owner fee
+        data[227..235].copy_from_slice(&25u64.to_le_bytes());
+        data[235..243].copy_from_slice(&10_000u64.to_le_bytes());
+        data[243..251].copy_from_slice(&5u64.to_le_bytes());
+        data[251..259].copy_from_slice(&10_000u64.to_le_bytes());
+
+        (data, keys)
+    }
+
+    #[test]
+    fn test_vault_and_authority_derivation() {
+        let program_id: Pubkey = SarosSwapBuilder::PROGRAM_ID.parse().unwrap();
+        let pool_address = Pubkey::new_unique();
+        let (data, keys) = sample_pool_data(&pool_address, &program_id);
+
+        let pool = SarosPoolState::parse(&data).unwrap();
+        assert_eq!(pool.token_a_vault, keys[0]);
+        assert_eq!(pool.token_b_vault, keys[1]);
+        assert_eq!(pool.pool_mint, keys[2]);
+        assert_eq!(pool.token_a_mint, keys[3]);
+        assert_eq!(pool.token_b_mint, keys[4]);
+        assert_eq!(pool.fee_account, keys[5]);
+
+        // Stored bump reproduces the canonical authority PDA
+        let authority =
+            SarosSwapBuilder::derive_authority(&pool_address, pool.bump_seed, &program_id).unwrap();
+        let (expected, _) = Pubkey::find_program_address(&[pool_address.as_ref()], &program_id);
+        assert_eq!(authority, expected);
+
+        let params = SwapParams {
+            amount_in: 1_000,
+            minimum_amount_out: 900,
+            expected_amount_out: Some(950),
+            swap_a_to_b: true,
+        };
+        let user = UserSwapAccounts {
+            owner: Pubkey::new_unique(),
+            token_in: Pubkey::new_unique(),
+            token_out: Pubkey::new_unique(),
+        };
+        let ix = SarosSwapBuilder::build_saros_swap_ix(
+            &program_id,
+            &pool_address,
+            &authority,
+            &pool,
+            &user,
+            &params,
+        );
+
+        assert_eq!(ix.accounts.len(), 10);
+        assert_eq!(ix.accounts[1].pubkey, authority);
+        assert!(ix.accounts[2].is_signer);
+        assert_eq!(ix.accounts[4].pubkey, pool.token_a_vault);
+        assert_eq!(ix.accounts[5].pubkey, pool.token_b_vault);
+        assert_eq!(ix.data[0], SWAP_INSTRUCTION_TAG);
+        assert_eq!(ix.data.len(), 17);
+
+        // Truncated and uninitialized accounts are rejected
+        assert!(SarosPoolState::parse(&data[..200]).is_err());
+        let mut uninitialized = data.clone();
+        uninitialized[1] = 0;
+        assert!(SarosPoolState::parse(&uninitialized).is_err());
+    }
+
+    #[test]
+    fn test_owner_fee_disabled_by_zero_denominator() {
+        let program_id: Pubkey = SarosSwapBuilder::PROGRAM_ID.parse().unwrap();
+        let (mut data, _) = sample_pool_data(&Pubkey::new_unique(), &program_id);
+
+        // Saros pools that don't collect an owner fee store a 0 denominator
+        data[251..259].copy_from_slice(&0u64.to_le_bytes());
+        let pool = SarosPoolState::parse(&data).unwrap();
+        assert_eq!(pool.owner_trade_fee_numerator, 5);
+        assert_eq!(pool.amount_after_fees(10_000), 9_975);
+        assert_eq!(pool.quote(10_000, 1_000_000, 2_000_000), 19_752);
+    }
+
+    #[test]
+    #[ignore = "needs fixtures/saros_pool_swap.json (scripts/capture_fixture.py --swap)"]
+    fn test_captured_saros_pool_and_swap() {
+        let fixture = CapturedFixture::load("saros_pool_swap").unwrap();
+        let pool = SarosPoolState::parse(fixture.pool_data()).unwrap();
+
+        // Vaults and mints we read match what the RPC node decoded
+        let authority =
+            SarosSwapBuilder::derive_authority(&fixture.pool, pool.bump_seed, &fixture.program)
+                .unwrap();
+        for (vault, mint) in [
+            (pool.token_a_vault, pool.token_a_mint),
+            (pool.token_b_vault, pool.token_b_mint),
+        ] {
+            let account = fixture.token_account(&vault);
+            assert_eq!(account.mint, mint.to_string());
+            assert_eq!(account.owner, authority.to_string());
+        }
+
+        // Our instruction lists the accounts the real swap executed with
+        let swap = fixture.swap();
+        let executed: Vec<Pubkey> = swap
+            .instruction_accounts
+            .iter()
+            .map(|address| pubkey(address).unwrap())
+            .collect();
+        let params = SwapParams {
+            amount_in: swap.amount_in,
+            minimum_amount_out: 0,
+            expected_amount_out: None,
+            swap_a_to_b: pubkey(&swap.vault_in).unwrap() == pool.token_a_vault,
+        };
+        let ix = SarosSwapBuilder::build_saros_swap_ix(
+            &fixture.program,
+            &fixture.pool,
+            &authority,
+            &pool,
+            &UserSwapAccounts {
+                owner: executed[2],
+                token_in: executed[3],
+                token_out: executed[6],
+            },
+            &params,
+        );
+        let built: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(built, executed[..built.len()]);
+
+        // Quote on the pre-swap vault balances vs the amount the vault paid out
+        let out = pool.quote(swap.amount_in, swap.reserve_in, swap.reserve_out);
+        assert!(
+            out.abs_diff(swap.amount_out) <= 1,
+            "estimated {} vs {} out on-chain ({})",
+            out,
+            swap.amount_out,
+            swap.signature
+        );
+    }
+}
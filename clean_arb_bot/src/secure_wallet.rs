@@ -0,0 +1,394 @@
+// Encrypted wallet keystore
+//
+// Lets the bot load its signing key from a passphrase-protected file
+// (WALLET_KEYSTORE) instead of a plaintext base58 WALLET_PRIVATE_KEY.
+// The key is derived with scrypt and the keypair bytes are sealed with
+// AES-256-GCM, so a wrong passphrase or a tampered file fails to decrypt.
+// The passphrase comes from WALLET_PASSPHRASE, or is prompted for without
+// echo (piped stdin is read as one line). Derived keys, passphrases and the
+// decrypted keypair bytes are zeroized when dropped.
+//
+// Create a keystore with `clean_arb_bot create-keystore <path>`.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+use zeroize::Zeroizing;
+
+const KEYSTORE_VERSION: u32 = 1;
+const KDF_SCRYPT: &str = "scrypt";
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// scrypt cost parameters stored alongside the ciphertext
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// log2 of the CPU/memory cost (N)
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // ~32 MiB / a fraction of a second: paid once at startup
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// On-disk keystore format (JSON, binary fields base64-encoded)
+#[derive(Debug, Serialize, Deserialize)]
+struct Keystore {
+    version: u32,
+    kdf: String,
+    kdf_params: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Loader for passphrase-encrypted wallet keystores
+pub struct SecureWallet;
+
+impl SecureWallet {
+    /// Decrypt the keystore at `path` into a signing keypair
+    ///
+    /// # Arguments
+    /// * `path` - Keystore JSON file (see `encrypt_keypair`)
+    /// * `passphrase` - Passphrase the keystore was sealed with
+    ///
+    /// # Returns
+    /// The wallet keypair
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read, is malformed, or the passphrase is wrong
+    pub fn from_keystore(path: &str, passphrase: &str) -> Result<Keypair> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read wallet keystore {}", path))?;
+        Self::decrypt_keystore(&json, passphrase)
+            .with_context(|| format!("Failed to unlock wallet keystore {}", path))
+    }
+
+    /// Seal a keypair into keystore JSON with the default scrypt parameters
+    ///
+    /// # Arguments
+    /// * `keypair` - Wallet keypair to protect
+    /// * `passphrase` - Passphrase required to decrypt it again
+    ///
+    /// # Returns
+    /// Keystore JSON suitable for writing to WALLET_KEYSTORE
+    pub fn encrypt_keypair(keypair: &Keypair, passphrase: &str) -> Result<String> {
+        Self::encrypt_keypair_with_params(keypair, passphrase, KdfParams::default())
+    }
+
+    /// Seal a keypair into keystore JSON with explicit scrypt parameters
+    ///
+    /// # Arguments
+    /// * `keypair` - Wallet keypair to protect
+    /// * `passphrase` - Passphrase required to decrypt it again
+    /// * `params` - scrypt cost parameters (recorded in the keystore)
+    ///
+    /// # Returns
+    /// Keystore JSON
+    pub fn encrypt_keypair_with_params(
+        keypair: &Keypair,
+        passphrase: &str,
+        params: KdfParams,
+    ) -> Result<String> {
+        let salt: [u8; SALT_LEN] = rand::random();
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let key = derive_key(passphrase, &salt, params)?;
+
+        let secret = Zeroizing::new(keypair.to_bytes());
+
+        let cipher = Aes256Gcm::new_from_slice(&key[..])
+            .map_err(|_| anyhow::anyhow!("Invalid keystore key length"))?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), &secret[..])
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt wallet keypair"))?;
+
+        let keystore = Keystore {
+            version: KEYSTORE_VERSION,
+            kdf: KDF_SCRYPT.to_string(),
+            kdf_params: params,
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        serde_json::to_string_pretty(&keystore).context("Failed to serialize wallet keystore")
+    }
+
+    /// Decrypt keystore JSON into a signing keypair
+    ///
+    /// # Arguments
+    /// * `json` - Keystore JSON produced by `encrypt_keypair`
+    /// * `passphrase` - Passphrase the keystore was sealed with
+    ///
+    /// # Returns
+    /// The wallet keypair
+    ///
+    /// # Errors
+    /// Returns error on an unsupported format or a wrong passphrase
+    pub fn decrypt_keystore(json: &str, passphrase: &str) -> Result<Keypair> {
+        let keystore: Keystore =
+            serde_json::from_str(json).context("Malformed wallet keystore JSON")?;
+        if keystore.version != KEYSTORE_VERSION {
+            anyhow::bail!("Unsupported keystore version {}", keystore.version);
+        }
+        if keystore.kdf != KDF_SCRYPT {
+            anyhow::bail!("Unsupported keystore KDF '{}'", keystore.kdf);
+        }
+
+        let salt = BASE64
+            .decode(&keystore.salt)
+            .context("Invalid keystore salt encoding")?;
+        let nonce = BASE64
+            .decode(&keystore.nonce)
+            .context("Invalid keystore nonce encoding")?;
+        let ciphertext = BASE64
+            .decode(&keystore.ciphertext)
+            .context("Invalid keystore ciphertext encoding")?;
+        if nonce.len() != NONCE_LEN {
+            anyhow::bail!("Invalid keystore nonce length {}", nonce.len());
+        }
+
+        let key = derive_key(passphrase, &salt, keystore.kdf_params)?;
+        let cipher = Aes256Gcm::new_from_slice(&key[..])
+            .map_err(|_| anyhow::anyhow!("Invalid keystore key length"))?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+                .map_err(|_| anyhow::anyhow!("Wrong passphrase or corrupted keystore"))?,
+        );
+
+        Keypair::from_bytes(&plaintext).context("Keystore does not contain a valid keypair")
+    }
+
+    /// Write a new keystore for `keypair` to `path`
+    ///
+    /// Refuses to overwrite an existing file; on unix the keystore is created
+    /// readable by the owner only.
+    ///
+    /// # Arguments
+    /// * `path` - Keystore file to create
+    /// * `keypair` - Wallet keypair to protect
+    /// * `passphrase` - Passphrase required to decrypt it again
+    /// * `params` - scrypt cost parameters (recorded in the keystore)
+    ///
+    /// # Errors
+    /// Returns error if `path` already exists or cannot be written
+    pub fn write_keystore(
+        path: &Path,
+        keypair: &Keypair,
+        passphrase: &str,
+        params: KdfParams,
+    ) -> Result<()> {
+        let json = Self::encrypt_keypair_with_params(keypair, passphrase, params)?;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options
+            .open(path)
+            .with_context(|| format!("Failed to create wallet keystore {}", path.display()))?;
+        file.write_all(json.as_bytes())
+            .and_then(|_| file.sync_all())
+            .with_context(|| format!("Failed to write wallet keystore {}", path.display()))
+    }
+
+    /// Interactively create a keystore at `path` (the `create-keystore` command)
+    ///
+    /// The keypair comes from WALLET_PRIVATE_KEY, or is prompted for (base58,
+    /// no echo). The passphrase comes from WALLET_PASSPHRASE, or is prompted
+    /// for twice.
+    ///
+    /// # Arguments
+    /// * `path` - Keystore file to create
+    ///
+    /// # Returns
+    /// Public key of the stored wallet
+    ///
+    /// # Errors
+    /// Returns error on a malformed key, mismatched passphrases, or if `path` exists
+    pub fn create_keystore(path: &Path) -> Result<Pubkey> {
+        if path.exists() {
+            anyhow::bail!(
+                "Wallet keystore {} already exists - refusing to overwrite it",
+                path.display()
+            );
+        }
+
+        let private_key = match std::env::var("WALLET_PRIVATE_KEY") {
+            Ok(key) if !key.is_empty() => Zeroizing::new(key),
+            _ => read_secret("🔑 Wallet private key (base58): ")?,
+        };
+        let bytes = Zeroizing::new(
+            bs58::decode(private_key.trim())
+                .into_vec()
+                .context("Failed to decode wallet private key")?,
+        );
+        let keypair = Keypair::from_bytes(&bytes).context("Failed to parse wallet keypair")?;
+
+        let passphrase = match passphrase_from_env() {
+            Some(passphrase) => passphrase,
+            None => {
+                let passphrase = read_secret("🔐 New keystore passphrase: ")?;
+                if std::io::stdin().is_terminal() {
+                    let confirm = read_secret("🔐 Repeat passphrase: ")?;
+                    if *confirm != *passphrase {
+                        anyhow::bail!("Passphrases do not match");
+                    }
+                }
+                passphrase
+            }
+        };
+
+        Self::write_keystore(path, &keypair, &passphrase, KdfParams::default())?;
+        Ok(keypair.pubkey())
+    }
+
+    /// Keystore passphrase from WALLET_PASSPHRASE, falling back to a prompt
+    ///
+    /// The prompt reads from the terminal without echo; piped stdin is read as
+    /// one line.
+    ///
+    /// # Errors
+    /// Returns error if the passphrase cannot be read or is empty
+    pub fn passphrase_from_env_or_stdin() -> Result<Zeroizing<String>> {
+        match passphrase_from_env() {
+            Some(passphrase) => Ok(passphrase),
+            None => read_secret("🔐 Wallet keystore passphrase: "),
+        }
+    }
+}
+
+/// Non-empty WALLET_PASSPHRASE, if set
+fn passphrase_from_env() -> Option<Zeroizing<String>> {
+    std::env::var("WALLET_PASSPHRASE")
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+        .map(Zeroizing::new)
+}
+
+/// Prompt for a secret without echo, or read one line when stdin is piped
+fn read_secret(prompt: &str) -> Result<Zeroizing<String>> {
+    let secret = if std::io::stdin().is_terminal() {
+        Zeroizing::new(rpassword::prompt_password(prompt).context("Failed to read from terminal")?)
+    } else {
+        eprint!("{}", prompt);
+        std::io::stderr().flush().ok();
+        let mut line = Zeroizing::new(String::new());
+        std::io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .context("Failed to read from stdin")?;
+        Zeroizing::new(line.trim_end_matches(['\r', '\n']).to_string())
+    };
+
+    if secret.is_empty() {
+        anyhow::bail!(
+            "Empty input for '{}' (set WALLET_PASSPHRASE or pipe it on stdin)",
+            prompt.trim()
+        );
+    }
+    Ok(secret)
+}
+
+/// Derive the AES-256 key from a passphrase with scrypt
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    params: KdfParams,
+) -> Result<Zeroizing<[u8; KEY_LEN]>> {
+    let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p, KEY_LEN)
+        .map_err(|e| anyhow::anyhow!("Invalid keystore scrypt parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    scrypt::scrypt(passphrase.as_bytes(), salt, &scrypt_params, &mut key[..])
+        .map_err(|e| anyhow::anyhow!("scrypt key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cheap parameters so the tests don't pay the production KDF cost
+    const TEST_PARAMS: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn test_keystore_round_trip() {
+        let keypair = Keypair::new();
+        let json =
+            SecureWallet::encrypt_keypair_with_params(&keypair, "correct horse", TEST_PARAMS)
+                .unwrap();
+
+        assert!(!json.contains(&bs58::encode(keypair.to_bytes()).into_string()));
+        let restored = SecureWallet::decrypt_keystore(&json, "correct horse").unwrap();
+        assert_eq!(restored.pubkey(), keypair.pubkey());
+        assert_eq!(restored.to_bytes(), keypair.to_bytes());
+
+        let path = std::env::temp_dir().join(format!("keystore_test_{}.json", std::process::id()));
+        std::fs::write(&path, &json).unwrap();
+        let loaded = SecureWallet::from_keystore(path.to_str().unwrap(), "correct horse").unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_wrong_passphrase_or_tampering_fails() {
+        let keypair = Keypair::new();
+        let json =
+            SecureWallet::encrypt_keypair_with_params(&keypair, "correct horse", TEST_PARAMS)
+                .unwrap();
+
+        assert!(SecureWallet::decrypt_keystore(&json, "battery staple").is_err());
+
+        let mut keystore: Keystore = serde_json::from_str(&json).unwrap();
+        let mut ciphertext = BASE64.decode(&keystore.ciphertext).unwrap();
+        ciphertext[0] ^= 0x01;
+        keystore.ciphertext = BASE64.encode(ciphertext);
+        let tampered = serde_json::to_string(&keystore).unwrap();
+        assert!(SecureWallet::decrypt_keystore(&tampered, "correct horse").is_err());
+    }
+
+    #[test]
+    fn test_write_keystore_never_overwrites() {
+        let keypair = Keypair::new();
+        let path =
+            std::env::temp_dir().join(format!("keystore_write_test_{}.json", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        SecureWallet::write_keystore(&path, &keypair, "correct horse", TEST_PARAMS).unwrap();
+        let loaded = SecureWallet::from_keystore(path.to_str().unwrap(), "correct horse").unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let other = Keypair::new();
+        assert!(SecureWallet::write_keystore(&path, &other, "other", TEST_PARAMS).is_err());
+        let still = SecureWallet::from_keystore(path.to_str().unwrap(), "correct horse").unwrap();
+        assert_eq!(still.pubkey(), keypair.pubkey());
+        std::fs::remove_file(&path).ok();
+    }
+}
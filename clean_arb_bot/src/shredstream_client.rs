@@ -30,6 +30,42 @@ pub struct TokenPrice {
     pub last_update: String,
     pub volume_24h: f64,
     pub pool_address: String, // CRITICAL FIX: Full 44-char address for DEX swaps
+    /// When this quote last changed (from `last_update`, else fetch time)
+    #[serde(skip, default = "Instant::now")]
+    pub last_updated: Instant,
+    /// Slot the quote was observed at, when the service reports one
+    #[serde(default)]
+    pub slot: Option<u64>,
+}
+
+impl TokenPrice {
+    /// Age of this quote
+    pub fn age(&self) -> Duration {
+        self.last_updated.elapsed()
+    }
+}
+
+/// Local instant of a quote's service-side `last_update` timestamp
+///
+/// Accepts RFC 3339 or unix seconds/milliseconds. Unparseable values fall back
+/// to `fetched_at`, so an unknown format never makes a quote look stale.
+fn quote_timestamp(last_update: &str, fetched_at: Instant) -> Instant {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let updated_ms = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(last_update.trim()) {
+        dt.timestamp_millis()
+    } else if let Ok(ts) = last_update.trim().parse::<i64>() {
+        // Unix milliseconds have 13 digits, seconds 10
+        if ts > 1_000_000_000_000 {
+            ts
+        } else {
+            ts * 1000
+        }
+    } else {
+        return fetched_at;
+    };
+
+    let age = Duration::from_millis(now_ms.saturating_sub(updated_ms).max(0) as u64);
+    fetched_at.checked_sub(age).unwrap_or(fetched_at)
 }
 
 /// Response from /prices endpoint
@@ -39,12 +75,190 @@ pub struct PricesResponse {
     pub total_tokens: usize,
 }
 
+/// Consecutive fetch failures before the service is considered down
+const DOWN_AFTER_FAILURES: u32 = 5;
+
+/// Time without a successful fetch before the service is considered down
+const DOWN_AFTER_SILENCE: Duration = Duration::from_secs(15);
+
+/// Consecutive successes needed to go from degraded back to connected
+const RECOVERY_SUCCESSES: u32 = 3;
+
+/// Consecutive failed fetches on the active endpoint before failing over to the next
+const FAILOVER_AFTER_FAILURES: u32 = 2;
+
+/// How often a failed-over client retries the primary endpoint
+const PRIMARY_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Connection health of the ShredStream service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShredStreamHealth {
+    /// Fetches succeeding
+    Connected,
+    /// Recent failures, or still recovering from an outage
+    Degraded,
+    /// Unreachable: prices are stale, don't scan on them
+    Down,
+}
+
+impl std::fmt::Display for ShredStreamHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ShredStreamHealth::Connected => "connected",
+            ShredStreamHealth::Degraded => "degraded",
+            ShredStreamHealth::Down => "down",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Health state plus the counters it was derived from (for GET /status)
+#[derive(Debug, Clone, Serialize)]
+pub struct ShredStreamHealthReport {
+    pub state: ShredStreamHealth,
+    pub consecutive_failures: u32,
+    /// None if no fetch has succeeded yet
+    pub last_success_age_secs: Option<u64>,
+    /// Endpoint prices are currently fetched from (SHREDSTREAM_URLS)
+    pub active_endpoint: String,
+}
+
+/// Fetch outcome history driving `ShredStreamHealth`
+#[derive(Debug)]
+struct HealthTracker {
+    consecutive_failures: u32,
+    consecutive_successes: u32,
+    last_success: Option<Instant>,
+    created_at: Instant,
+    /// Went down and hasn't had RECOVERY_SUCCESSES successes since
+    recovering: bool,
+}
+
+impl HealthTracker {
+    fn new(now: Instant) -> Self {
+        Self {
+            consecutive_failures: 0,
+            consecutive_successes: 0,
+            last_success: None,
+            created_at: now,
+            recovering: false,
+        }
+    }
+
+    fn record_success(&mut self, now: Instant) {
+        self.consecutive_failures = 0;
+        self.consecutive_successes += 1;
+        self.last_success = Some(now);
+        if self.consecutive_successes >= RECOVERY_SUCCESSES {
+            self.recovering = false;
+        }
+    }
+
+    fn record_failure(&mut self, now: Instant) {
+        self.consecutive_successes = 0;
+        self.consecutive_failures += 1;
+        if self.state(now) == ShredStreamHealth::Down {
+            self.recovering = true;
+        }
+    }
+
+    /// Down on too many failures in a row or too long without a success;
+    /// degraded while failing or recovering; connected otherwise
+    fn state(&self, now: Instant) -> ShredStreamHealth {
+        let silence = now.duration_since(self.last_success.unwrap_or(self.created_at));
+        if self.consecutive_failures >= DOWN_AFTER_FAILURES
+            || (self.consecutive_failures > 0 && silence >= DOWN_AFTER_SILENCE)
+        {
+            ShredStreamHealth::Down
+        } else if self.consecutive_failures > 0 || self.recovering || self.last_success.is_none() {
+            ShredStreamHealth::Degraded
+        } else {
+            ShredStreamHealth::Connected
+        }
+    }
+}
+
+/// Prioritized ShredStream endpoints with failover (SHREDSTREAM_URLS)
+///
+/// Fetches go to the active endpoint. After FAILOVER_AFTER_FAILURES failed
+/// fetches in a row it moves to the next one in the list (wrapping). While
+/// failed over, the primary is retried every PRIMARY_RETRY_INTERVAL and
+/// becomes active again on its first success.
+#[derive(Debug)]
+struct EndpointFailover {
+    urls: Vec<String>,
+    active: usize,
+    active_failures: u32,
+    /// Endpoint of the latest fetch (what a caller-side timeout is charged to)
+    last_attempt: usize,
+    last_primary_retry: Instant,
+}
+
+impl EndpointFailover {
+    fn new(urls: Vec<String>, now: Instant) -> Self {
+        Self {
+            urls,
+            active: 0,
+            active_failures: 0,
+            last_attempt: 0,
+            last_primary_retry: now,
+        }
+    }
+
+    fn active_url(&self) -> &str {
+        &self.urls[self.active]
+    }
+
+    /// Endpoint index to fetch from: the primary when a retry is due, else the active one
+    fn next_attempt(&mut self, now: Instant) -> usize {
+        self.last_attempt = if self.active != 0
+            && now.duration_since(self.last_primary_retry) >= PRIMARY_RETRY_INTERVAL
+        {
+            self.last_primary_retry = now;
+            0
+        } else {
+            self.active
+        };
+        self.last_attempt
+    }
+
+    fn record_success(&mut self, index: usize) {
+        if index != self.active {
+            info!(
+                "🔁 ShredStream primary recovered - switching back to {}",
+                self.urls[index]
+            );
+            self.active = index;
+        }
+        self.active_failures = 0;
+    }
+
+    fn record_failure(&mut self, index: usize, now: Instant) {
+        // A failed primary retry doesn't count against the endpoint in use
+        if index != self.active {
+            return;
+        }
+        self.active_failures += 1;
+        if self.active_failures >= FAILOVER_AFTER_FAILURES && self.urls.len() > 1 {
+            let next = (self.active + 1) % self.urls.len();
+            warn!(
+                "🔁 ShredStream endpoint {} failed {} times - failing over to {}",
+                self.urls[self.active], self.active_failures, self.urls[next]
+            );
+            self.active = next;
+            self.active_failures = 0;
+            self.last_primary_retry = now;
+        }
+    }
+}
+
 /// Client for ShredStream service REST API
 /// CYCLE-7: Enhanced with rate limiting (Grok recommendation)
 /// OPTIMIZED: Lock-free concurrent cache with staleness detection
 pub struct ShredStreamClient {
-    /// Service endpoint URL
-    service_url: String,
+    /// Service endpoint URLs, primary first
+    endpoints: EndpointFailover,
     /// HTTP client
     client: reqwest::Client,
     /// Cached prices by token_mint + dex (concurrent access)
@@ -57,6 +271,10 @@ pub struct ShredStreamClient {
     last_fetch: Option<Instant>,
     /// Cache TTL in seconds (prices older than this are stale)
     cache_ttl_secs: u64,
+    /// Connection health (consecutive failures, last success)
+    health: HealthTracker,
+    /// Highest slot reported by any quote (None until the service sends slots)
+    latest_slot: Option<u64>,
 }
 
 impl ShredStreamClient {
@@ -78,15 +296,71 @@ impl ShredStreamClient {
         let rate_limiter = GovernorRateLimiter::direct(quota);
 
         Self {
-            service_url,
+            endpoints: EndpointFailover::new(vec![service_url], Instant::now()),
             client,
             price_cache: Arc::new(DashMap::new()),
             rate_limiter,
             last_fetch: None,
             cache_ttl_secs: 5, // 5 second cache TTL (prices are fresh for 5s)
+            health: HealthTracker::new(Instant::now()),
+            latest_slot: None,
+        }
+    }
+
+    /// Fail over across a prioritized endpoint list (primary first)
+    ///
+    /// An empty list is ignored so there is always an endpoint to fetch from
+    pub fn with_endpoints(mut self, endpoints: Vec<String>) -> Self {
+        if endpoints.is_empty() {
+            warn!(
+                "⚠️ Empty ShredStream endpoint list ignored - keeping {}",
+                self.active_endpoint()
+            );
+            return self;
+        }
+        if endpoints.len() > 1 {
+            info!("📡 ShredStream endpoints (failover order):");
+            for (i, endpoint) in endpoints.iter().enumerate() {
+                info!("   {}. {}", i + 1, endpoint);
+            }
+        }
+        self.endpoints = EndpointFailover::new(endpoints, Instant::now());
+        self
+    }
+
+    /// Endpoint prices are currently fetched from
+    pub fn active_endpoint(&self) -> &str {
+        self.endpoints.active_url()
+    }
+
+    /// Highest slot seen on the feed, used as the current slot for staleness checks
+    pub fn latest_slot(&self) -> Option<u64> {
+        self.latest_slot
+    }
+
+    /// Current connection health
+    pub fn health(&self) -> ShredStreamHealth {
+        self.health.state(Instant::now())
+    }
+
+    /// Connection health with its underlying counters
+    pub fn health_report(&self) -> ShredStreamHealthReport {
+        ShredStreamHealthReport {
+            state: self.health(),
+            consecutive_failures: self.health.consecutive_failures,
+            last_success_age_secs: self.health.last_success.map(|t| t.elapsed().as_secs()),
+            active_endpoint: self.active_endpoint().to_string(),
         }
     }
 
+    /// Count a fetch that failed outside `fetch_prices` (e.g. caller's timeout)
+    pub fn record_fetch_failure(&mut self) {
+        let now = Instant::now();
+        self.health.record_failure(now);
+        self.endpoints
+            .record_failure(self.endpoints.last_attempt, now);
+    }
+
     /// Check if we need to fetch new prices (cache staleness check)
     /// OPTIMIZATION: Skip fetching if cache is still fresh
     pub fn needs_update(&self) -> bool {
@@ -118,7 +392,8 @@ impl ShredStreamClient {
         let fetch_start = std::time::Instant::now();
 
         // CRITICAL FIX: Endpoint is /prices not /api/prices
-        let url = format!("{}/prices", self.service_url);
+        let endpoint = self.endpoints.next_attempt(Instant::now());
+        let url = format!("{}/prices", self.endpoints.urls[endpoint]);
 
         // CYCLE-7: Timeout guard to protect against network jitter (5s for all retries)
         // Prevents hanging during network issues (Grok recommendation)
@@ -177,6 +452,8 @@ impl ShredStreamClient {
             Ok(retry_result) => retry_result,
             Err(_) => {
                 warn!("⚠️ ShredStream fetch timed out after 5s (network jitter protection)");
+                self.health.record_failure(Instant::now());
+                self.endpoints.record_failure(endpoint, Instant::now());
                 return Err(anyhow::anyhow!("Fetch timeout exceeded"));
             }
         };
@@ -188,7 +465,9 @@ impl ShredStreamClient {
                 let fetched_count = prices_response.prices.len();
 
                 // OPTIMIZATION: Batch update using concurrent DashMap
-                for price in prices_response.prices {
+                for mut price in prices_response.prices {
+                    price.last_updated = quote_timestamp(&price.last_update, now);
+                    self.latest_slot = self.latest_slot.max(price.slot);
                     let cache_key = format!("{}_{}", price.token_mint, price.dex);
                     let cached_price = CachedPrice {
                         data: price,
@@ -199,6 +478,8 @@ impl ShredStreamClient {
 
                 // Update last fetch timestamp
                 self.last_fetch = Some(now);
+                self.health.record_success(now);
+                self.endpoints.record_success(endpoint);
 
                 // CYCLE-6: Log fetch performance
                 let fetch_duration = fetch_start.elapsed();
@@ -213,6 +494,8 @@ impl ShredStreamClient {
             }
             Err(e) => {
                 warn!("❌ Failed to fetch prices after retries: {}", e);
+                self.health.record_failure(Instant::now());
+                self.endpoints.record_failure(endpoint, Instant::now());
                 Err(anyhow::anyhow!(
                     "ShredStream service unavailable after retries: {}",
                     e
@@ -221,6 +504,29 @@ impl ShredStreamClient {
         }
     }
 
+    /// Insert prices from another source (RPC fallback) into the cache
+    ///
+    /// Detectors read them like ShredStream prices. Connection health and the
+    /// fetch TTL are untouched, so ShredStream keeps being retried.
+    ///
+    /// # Returns
+    /// Number of prices inserted
+    pub fn merge_prices(&self, prices: Vec<TokenPrice>) -> usize {
+        let now = Instant::now();
+        let count = prices.len();
+        for price in prices {
+            let cache_key = format!("{}_{}", price.token_mint, price.dex);
+            self.price_cache.insert(
+                cache_key,
+                CachedPrice {
+                    data: price,
+                    cached_at: now,
+                },
+            );
+        }
+        count
+    }
+
     /// Get price for specific token on specific DEX
     pub fn get_price(&self, token_mint: &str, dex: &str) -> Option<f64> {
         let cache_key = format!("{}_{}", token_mint, dex);
@@ -258,3 +564,127 @@ impl ShredStreamClient {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_timestamp_formats() {
+        let now = Instant::now();
+        let five_secs_ago = chrono::Utc::now() - chrono::Duration::seconds(5);
+
+        let rfc3339 = quote_timestamp(&five_secs_ago.to_rfc3339(), now);
+        assert!((now - rfc3339).as_millis().abs_diff(5000) < 500);
+
+        let millis = quote_timestamp(&five_secs_ago.timestamp_millis().to_string(), now);
+        assert!((now - millis).as_millis().abs_diff(5000) < 500);
+
+        let secs = quote_timestamp(&five_secs_ago.timestamp().to_string(), now);
+        assert!((now - secs).as_millis().abs_diff(5000) < 1500);
+
+        // Unknown format / future timestamps never look stale
+        assert_eq!(quote_timestamp("", now), now);
+        let future = chrono::Utc::now() + chrono::Duration::seconds(60);
+        assert_eq!(quote_timestamp(&future.to_rfc3339(), now), now);
+    }
+
+    #[test]
+    fn test_health_degrades_then_goes_down() {
+        let start = Instant::now();
+        let mut tracker = HealthTracker::new(start);
+        assert_eq!(tracker.state(start), ShredStreamHealth::Degraded); // Not yet connected
+
+        tracker.record_success(start);
+        tracker.record_success(start);
+        tracker.record_success(start);
+        assert_eq!(tracker.state(start), ShredStreamHealth::Connected);
+
+        tracker.record_failure(start);
+        assert_eq!(tracker.state(start), ShredStreamHealth::Degraded);
+
+        // A single failure long after the last success is already an outage
+        assert_eq!(
+            tracker.state(start + DOWN_AFTER_SILENCE),
+            ShredStreamHealth::Down
+        );
+
+        for _ in 1..DOWN_AFTER_FAILURES {
+            tracker.record_failure(start);
+        }
+        assert_eq!(tracker.state(start), ShredStreamHealth::Down);
+    }
+
+    #[test]
+    fn test_health_recovers_after_consecutive_successes() {
+        let start = Instant::now();
+        let mut tracker = HealthTracker::new(start);
+        for _ in 0..DOWN_AFTER_FAILURES {
+            tracker.record_failure(start);
+        }
+        assert_eq!(tracker.state(start), ShredStreamHealth::Down);
+
+        // First success after an outage only degrades; flapping restarts recovery
+        tracker.record_success(start);
+        assert_eq!(tracker.state(start), ShredStreamHealth::Degraded);
+        tracker.record_failure(start);
+        tracker.record_success(start);
+        tracker.record_success(start);
+        assert_eq!(tracker.state(start), ShredStreamHealth::Degraded);
+
+        tracker.record_success(start);
+        assert_eq!(tracker.state(start), ShredStreamHealth::Connected);
+        assert_eq!(tracker.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_failover_to_healthy_secondary_and_back() {
+        let start = Instant::now();
+        let urls = vec![
+            "http://primary:8080".to_string(),
+            "http://secondary:8080".to_string(),
+        ];
+        let mut endpoints = EndpointFailover::new(urls, start);
+        let mut health = HealthTracker::new(start);
+
+        // One fetch cycle: the primary fails unless up, the secondary always answers
+        let fetch = |endpoints: &mut EndpointFailover,
+                     health: &mut HealthTracker,
+                     primary_up: bool,
+                     now: Instant| {
+            let endpoint = endpoints.next_attempt(now);
+            if endpoint == 1 || primary_up {
+                endpoints.record_success(endpoint);
+                health.record_success(now);
+            } else {
+                endpoints.record_failure(endpoint, now);
+                health.record_failure(now);
+            }
+            endpoint
+        };
+
+        assert_eq!(fetch(&mut endpoints, &mut health, false, start), 0);
+        assert_eq!(endpoints.active_url(), "http://primary:8080");
+        assert_eq!(fetch(&mut endpoints, &mut health, false, start), 0);
+        assert_eq!(endpoints.active_url(), "http://secondary:8080");
+
+        // Secondary keeps the feed connected
+        for _ in 0..RECOVERY_SUCCESSES {
+            assert_eq!(fetch(&mut endpoints, &mut health, false, start), 1);
+        }
+        assert_eq!(health.state(start), ShredStreamHealth::Connected);
+
+        // A failed primary retry doesn't knock the secondary off
+        let retry = start + PRIMARY_RETRY_INTERVAL;
+        assert_eq!(fetch(&mut endpoints, &mut health, false, retry), 0);
+        assert_eq!(endpoints.active_url(), "http://secondary:8080");
+        assert_eq!(fetch(&mut endpoints, &mut health, false, retry), 1);
+        assert_eq!(health.state(retry), ShredStreamHealth::Connected);
+
+        // Primary recovers: the next retry switches back and stays there
+        let later = retry + PRIMARY_RETRY_INTERVAL;
+        assert_eq!(fetch(&mut endpoints, &mut health, true, later), 0);
+        assert_eq!(endpoints.active_url(), "http://primary:8080");
+        assert_eq!(fetch(&mut endpoints, &mut health, true, later), 0);
+    }
+}
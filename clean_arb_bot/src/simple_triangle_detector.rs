@@ -1,46 +1,135 @@
+use anyhow::{bail, Result};
 use std::collections::HashMap;
 use tracing::{debug, info};
 
 use crate::shredstream_client::TokenPrice;
 
+/// Anchor tokens triangles may start and end in: (symbol, mint, decimals)
+const KNOWN_ANCHORS: &[(&str, &str, u8)] = &[
+    ("SOL", "So11111111111111111111111111111111111111112", 9),
+    ("USDC", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", 6),
+    ("USDT", "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", 6),
+];
+
+/// Default TRIANGLE_ANCHORS (SOL-anchored cycles only)
+pub const DEFAULT_TRIANGLE_ANCHORS: &str = "SOL";
+
+/// Token a triangle starts and ends in (SOL → A → B → SOL, USDC → A → B → USDC)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriangleAnchor {
+    pub symbol: String,
+    pub mint: String,
+    pub decimals: u8, // Converts anchor units ↔ base units for execution
+}
+
+impl TriangleAnchor {
+    /// Parse a comma-separated anchor list (TRIANGLE_ANCHORS, e.g. "SOL,USDC,USDT")
+    ///
+    /// # Errors
+    /// Returns error for an unknown symbol or an empty list
+    pub fn parse_list(value: &str) -> Result<Vec<Self>> {
+        let mut anchors: Vec<Self> = Vec::new();
+        for symbol in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let Some((symbol, mint, decimals)) = KNOWN_ANCHORS
+                .iter()
+                .find(|(known, _, _)| known.eq_ignore_ascii_case(symbol))
+            else {
+                bail!(
+                    "Unknown triangle anchor '{}' (supported: SOL, USDC, USDT)",
+                    symbol
+                );
+            };
+            if anchors.iter().all(|anchor| anchor.mint != *mint) {
+                anchors.push(Self {
+                    symbol: symbol.to_string(),
+                    mint: mint.to_string(),
+                    decimals: *decimals,
+                });
+            }
+        }
+
+        if anchors.is_empty() {
+            bail!("TRIANGLE_ANCHORS must list at least one anchor token");
+        }
+        Ok(anchors)
+    }
+
+    /// Whether this anchor is wrapped SOL (quotes are already in SOL)
+    pub fn is_sol(&self) -> bool {
+        self.mint == KNOWN_ANCHORS[0].1
+    }
+}
+
 /// Simple triangle opportunity detected from ShredStream data
 #[derive(Debug, Clone)]
 pub struct SimpleTriangleOpportunity {
+    pub anchor: TriangleAnchor,
     pub token_a_mint: String,
     pub token_b_mint: String,
-    pub dex_1: String, // SOL → TokenA
+    pub dex_1: String, // Anchor → TokenA
     pub dex_2: String, // TokenA → TokenB
-    pub dex_3: String, // TokenB → SOL
+    pub dex_3: String, // TokenB → Anchor
 
     // GHOST POOL FIX: Full 44-char pool addresses from ShredStream
-    pub pool_1_address: String, // Full address for SOL → TokenA pool
-    pub pool_3_address: String, // Full address for TokenB → SOL pool
+    pub pool_1_address: String, // Full address for Anchor → TokenA pool
+    pub pool_3_address: String, // Full address for TokenB → Anchor pool
+
+    pub input_amount: f64,     // Position in anchor units (e.g. USDC)
+    pub profit: f64,           // NET profit in anchor units
+    pub anchor_price_sol: f64, // SOL per anchor unit at detection (1.0 for SOL)
 
-    pub profit_sol: f64,
+    pub profit_sol: f64, // NET profit, SOL equivalent (stats, limits)
     pub profit_percentage: f64,
-    pub input_amount_sol: f64,
+    pub input_amount_sol: f64, // Position, SOL equivalent
+}
+
+impl SimpleTriangleOpportunity {
+    /// Position in the anchor's base units (lamports for SOL, 1e-6 for USDC)
+    pub fn input_base_units(&self) -> u64 {
+        (self.input_amount * 10f64.powi(self.anchor.decimals as i32)) as u64
+    }
+
+    /// SOL value of an anchor base-unit amount (negative for an outflow), at the detection price
+    pub fn base_units_to_sol(&self, base_units: i128) -> f64 {
+        base_units as f64 / 10f64.powi(self.anchor.decimals as i32) * self.anchor_price_sol
+    }
+}
+
+/// Middle token of a triangle (A or B) with its SOL quotes
+#[derive(Clone, Copy)]
+struct TriangleToken<'a> {
+    mint: &'a str,
+    prices: &'a [&'a TokenPrice],
 }
 
 /// Simple triangle detector using only ShredStream price data
-/// Detects: SOL → TokenA → TokenB → SOL
+/// Detects: Anchor → TokenA → TokenB → Anchor, for each configured anchor
 pub struct SimpleTriangleDetector {
-    sol_mint: String,
+    anchors: Vec<TriangleAnchor>,
 }
 
 impl SimpleTriangleDetector {
-    pub fn new() -> Self {
-        Self {
-            sol_mint: "So11111111111111111111111111111111111111112".to_string(),
-        }
+    /// Create a detector for cycles anchored on `anchors` (TRIANGLE_ANCHORS)
+    pub fn new(anchors: Vec<TriangleAnchor>) -> Self {
+        Self { anchors }
     }
 
     /// Find triangle opportunities from ShredStream prices
-    /// Strategy: Find pairs where SOL → A → B → SOL is profitable
+    /// Strategy: Find pairs where Anchor → A → B → Anchor is profitable
+    ///
+    /// Feed quotes are in SOL; a non-SOL anchor is valued at the median of its
+    /// own quotes, and anchors without quotes are skipped.
+    ///
+    /// # Arguments
+    /// * `prices` - ShredStream quotes (SOL per token)
+    /// * `capital_sol_for` - Position size in SOL for a token path (per-token
+    ///   override or the global max), converted to anchor units
+    /// * `total_fees_sol` - JITO tip + gas + compute for a gross profit in SOL
     pub fn find_opportunities(
         &self,
         prices: &HashMap<String, TokenPrice>,
-        capital_sol: f64,
-        config: &crate::config::Config,
+        capital_sol_for: &(dyn Fn(&[&str]) -> f64 + Sync),
+        total_fees_sol: &(dyn Fn(f64) -> f64 + Sync),
     ) -> Vec<SimpleTriangleOpportunity> {
         let mut opportunities = Vec::new();
 
@@ -76,44 +165,67 @@ impl SimpleTriangleDetector {
         let tokens_with_sol_pairs: Vec<&String> = token_prices.keys().collect();
 
         debug!(
-            "🔍 Scanning {} tokens for triangle paths",
-            tokens_with_sol_pairs.len()
+            "🔍 Scanning {} tokens for triangle paths ({} anchors)",
+            tokens_with_sol_pairs.len(),
+            self.anchors.len()
         );
 
-        // Try all combinations: SOL → TokenA → TokenB → SOL
-        for (i, token_a_mint) in tokens_with_sol_pairs.iter().enumerate() {
-            if *token_a_mint == &self.sol_mint {
-                continue;
-            }
-
-            // Check first 500 tokens (increased from 100 to find more opportunities)
-            if i >= 500 {
-                break;
-            }
-
-            let token_a_prices = &token_prices[*token_a_mint];
+        for anchor in &self.anchors {
+            let anchor_price_sol = if anchor.is_sol() {
+                1.0
+            } else {
+                match token_prices.get(&anchor.mint).and_then(|q| median_price(q)) {
+                    Some(price) => price,
+                    None => {
+                        debug!("⚠️ No SOL quote for anchor {} - skipping", anchor.symbol);
+                        continue;
+                    }
+                }
+            };
 
-            for token_b_mint in &tokens_with_sol_pairs {
-                if token_b_mint == token_a_mint || *token_b_mint == &self.sol_mint {
+            // Try all combinations: Anchor → TokenA → TokenB → Anchor
+            for (i, token_a_mint) in tokens_with_sol_pairs.iter().enumerate() {
+                if **token_a_mint == anchor.mint {
                     continue;
                 }
 
-                let token_b_prices = &token_prices[*token_b_mint];
-
-                // Try to find a profitable path
-                if let Some(opp) = self.calculate_triangle_profit(
-                    token_a_mint,
-                    token_b_mint,
-                    token_a_prices,
-                    token_b_prices,
-                    capital_sol,
-                    config,
-                ) {
-                    opportunities.push(opp);
-
-                    // Limit to 50 opportunities (increased to see more)
-                    if opportunities.len() >= 50 {
-                        return opportunities;
+                // Check first 500 tokens (increased from 100 to find more opportunities)
+                if i >= 500 {
+                    break;
+                }
+
+                let token_a_prices = &token_prices[*token_a_mint];
+
+                for token_b_mint in &tokens_with_sol_pairs {
+                    if token_b_mint == token_a_mint || **token_b_mint == anchor.mint {
+                        continue;
+                    }
+
+                    let token_b_prices = &token_prices[*token_b_mint];
+                    let capital_sol =
+                        capital_sol_for(&[token_a_mint.as_str(), token_b_mint.as_str()]);
+
+                    // Try to find a profitable path
+                    if let Some(opp) = self.calculate_triangle_profit(
+                        anchor,
+                        anchor_price_sol,
+                        TriangleToken {
+                            mint: token_a_mint,
+                            prices: token_a_prices,
+                        },
+                        TriangleToken {
+                            mint: token_b_mint,
+                            prices: token_b_prices,
+                        },
+                        capital_sol / anchor_price_sol,
+                        total_fees_sol,
+                    ) {
+                        opportunities.push(opp);
+
+                        // Limit to 50 opportunities (increased to see more)
+                        if opportunities.len() >= 50 {
+                            return opportunities;
+                        }
                     }
                 }
             }
@@ -126,40 +238,65 @@ impl SimpleTriangleDetector {
         opportunities
     }
 
-    /// Calculate profit for SOL → TokenA → TokenB → SOL
+    /// Calculate profit for Anchor → TokenA → TokenB → Anchor
+    ///
+    /// Amounts and profit are in anchor units; only the fee model (SOL) is
+    /// converted. The inferred A → B leg (routed by Jupiter) is valued at the
+    /// best quoted rates: A at its highest quote, B at its lowest.
     fn calculate_triangle_profit(
         &self,
-        token_a_mint: &str,
-        token_b_mint: &str,
-        token_a_prices: &[&TokenPrice],
-        token_b_prices: &[&TokenPrice],
-        capital_sol: f64,
-        config: &crate::config::Config,
+        anchor: &TriangleAnchor,
+        anchor_price_sol: f64,
+        token_a: TriangleToken,
+        token_b: TriangleToken,
+        capital: f64,
+        total_fees_sol: &(dyn Fn(f64) -> f64 + Sync),
     ) -> Option<SimpleTriangleOpportunity> {
-        // Try all combinations of DEXs
-        for price_a in token_a_prices {
-            for price_b in token_b_prices {
-                // Step 1: SOL → TokenA
+        let best_a_sol = token_a
+            .prices
+            .iter()
+            .map(|p| p.price_sol)
+            .fold(0.0, f64::max);
+        let best_b_sol = token_b
+            .prices
+            .iter()
+            .map(|p| p.price_sol)
+            .filter(|p| *p > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        if best_a_sol <= 0.0 || !best_b_sol.is_finite() || capital <= 0.0 {
+            return None;
+        }
+
+        // Try all combinations of DEXs, keep the most profitable
+        let mut best: Option<SimpleTriangleOpportunity> = None;
+        for price_a in token_a.prices {
+            for price_b in token_b.prices {
+                if price_a.price_sol <= 0.0 || price_b.price_sol <= 0.0 {
+                    continue;
+                }
+
+                // Step 1: Anchor → TokenA
                 let fee_1 = 0.003; // 0.3% typical DEX fee
-                let token_a_amount = (capital_sol * (1.0 - fee_1)) / price_a.price_sol;
+                let token_a_amount =
+                    (capital * anchor_price_sol * (1.0 - fee_1)) / price_a.price_sol;
 
                 // Step 2: TokenA → TokenB
-                // We need to know the TokenA/TokenB price
-                // Approximate: value_in_sol / price_b
-                let token_a_value_sol = token_a_amount * price_a.price_sol;
+                // We don't know the TokenA/TokenB pool - infer it from the best SOL quotes
+                let token_a_value_sol = token_a_amount * best_a_sol;
                 let fee_2 = 0.003;
-                let token_b_amount = (token_a_value_sol * (1.0 - fee_2)) / price_b.price_sol;
+                let token_b_amount = (token_a_value_sol * (1.0 - fee_2)) / best_b_sol;
 
-                // Step 3: TokenB → SOL
+                // Step 3: TokenB → Anchor
                 let fee_3 = 0.003;
-                let sol_received = token_b_amount * price_b.price_sol * (1.0 - fee_3);
+                let anchor_received =
+                    token_b_amount * price_b.price_sol * (1.0 - fee_3) / anchor_price_sol;
 
-                // Gross profit after DEX fees (0.9% total)
-                let gross_profit = sol_received - capital_sol;
-                let profit_pct = (gross_profit / capital_sol) * 100.0;
+                // Gross profit after DEX fees (0.9% total), in anchor units
+                let gross_profit = anchor_received - capital;
+                let profit_pct = (gross_profit / capital) * 100.0;
 
-                // Calculate total fees (JITO tip + gas + compute)
-                let total_fees = config.calculate_total_fees(gross_profit);
+                // Calculate total fees (JITO tip + gas + compute), paid in SOL
+                let total_fees = total_fees_sol(gross_profit * anchor_price_sol) / anchor_price_sol;
 
                 // Calculate net profit after ALL fees
                 let net_profit = gross_profit - total_fees;
@@ -172,15 +309,22 @@ impl SimpleTriangleDetector {
 
                 // Check if profitable with required margin and realistic
                 // Cap at 5% to avoid fake/manipulated spreads (real arbs are 0.5-3%)
-                if net_profit >= min_acceptable && profit_pct < 5.0 && gross_profit > 0.0 {
+                let beats_best = best.as_ref().map_or(true, |b| net_profit > b.profit);
+                if net_profit >= min_acceptable
+                    && profit_pct < 5.0
+                    && gross_profit > 0.0
+                    && beats_best
+                {
                     debug!(
-                        "✅ Triangle profitable: Gross={:.6} SOL, Fees={:.6} SOL, Net={:.6} SOL, Min Required={:.6} SOL (fees + 0.5% gross)",
-                        gross_profit, total_fees, net_profit, min_acceptable
+                        "✅ Triangle profitable: Gross={:.6} {}, Fees={:.6} {}, Net={:.6} {}, Min Required={:.6} {} (fees + 0.5% gross)",
+                        gross_profit, anchor.symbol, total_fees, anchor.symbol,
+                        net_profit, anchor.symbol, min_acceptable, anchor.symbol
                     );
 
-                    return Some(SimpleTriangleOpportunity {
-                        token_a_mint: token_a_mint.to_string(),
-                        token_b_mint: token_b_mint.to_string(),
+                    best = Some(SimpleTriangleOpportunity {
+                        anchor: anchor.clone(),
+                        token_a_mint: token_a.mint.to_string(),
+                        token_b_mint: token_b.mint.to_string(),
                         dex_1: price_a.dex.clone(),
                         dex_2: "Inferred".to_string(), // We don't know actual A→B DEX
                         dex_3: price_b.dex.clone(),
@@ -189,14 +333,117 @@ impl SimpleTriangleDetector {
                         pool_1_address: price_a.pool_address.clone(), // Full 44-char address
                         pool_3_address: price_b.pool_address.clone(), // Full 44-char address
 
-                        profit_sol: net_profit, // Store NET profit (after all fees)
+                        input_amount: capital,
+                        profit: net_profit, // Store NET profit (after all fees)
+                        anchor_price_sol,
+
+                        profit_sol: net_profit * anchor_price_sol,
                         profit_percentage: profit_pct,
-                        input_amount_sol: capital_sol,
+                        input_amount_sol: capital * anchor_price_sol,
                     });
                 }
             }
         }
 
-        None
+        best
+    }
+}
+
+/// Median SOL price across a token's quotes (robust to one bad pool)
+fn median_price(quotes: &[&TokenPrice]) -> Option<f64> {
+    let mut prices: Vec<f64> = quotes
+        .iter()
+        .map(|q| q.price_sol)
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .collect();
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort_by(|a, b| a.total_cmp(b));
+    Some(prices[prices.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn quote(token_mint: &str, dex: &str, price_sol: f64) -> (String, TokenPrice) {
+        (
+            format!("{}_{}", dex, token_mint),
+            TokenPrice {
+                token_mint: token_mint.to_string(),
+                dex: dex.to_string(),
+                price_sol,
+                last_update: String::new(),
+                volume_24h: 100.0,
+                pool_address: format!("{}Pool", dex),
+                last_updated: Instant::now(),
+                slot: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_parse_anchor_list() {
+        let anchors = TriangleAnchor::parse_list("sol, USDC,usdc").unwrap();
+        assert_eq!(anchors.len(), 2);
+        assert!(anchors[0].is_sol());
+        assert_eq!(
+            (anchors[1].symbol.as_str(), anchors[1].decimals),
+            ("USDC", 6)
+        );
+
+        assert!(TriangleAnchor::parse_list("SOL,EUR").is_err());
+        assert!(TriangleAnchor::parse_list(" ").is_err());
+    }
+
+    #[test]
+    fn test_usdc_anchored_triangle_in_usdc_units() {
+        // USDC at 0.005 SOL ($200 SOL); TokenA cheap on Orca, TokenB rich on Raydium
+        let prices: HashMap<String, TokenPrice> = [
+            quote(USDC, "Orca_Whirlpools", 0.005),
+            quote(USDC, "Raydium_AMM_V4", 0.005),
+            quote("TokenA", "Orca_Whirlpools", 0.0100),
+            quote("TokenA", "Raydium_AMM_V4", 0.0102),
+            quote("TokenB", "Orca_Whirlpools", 0.0200),
+            quote("TokenB", "Raydium_AMM_V4", 0.0203),
+        ]
+        .into_iter()
+        .collect();
+
+        let usdc_only = TriangleAnchor::parse_list("USDC").unwrap();
+        let detector = SimpleTriangleDetector::new(usdc_only);
+        let opportunities = detector.find_opportunities(&prices, &|_| 1.0, &|_| 0.0001);
+
+        let opp = opportunities
+            .iter()
+            .find(|o| o.token_a_mint == "TokenA" && o.token_b_mint == "TokenB")
+            .expect("USDC → TokenA → TokenB → USDC");
+        assert_eq!(opp.anchor.mint, USDC);
+        assert_eq!(
+            (opp.dex_1.as_str(), opp.dex_3.as_str()),
+            ("Orca_Whirlpools", "Raydium_AMM_V4")
+        );
+
+        // 1 SOL of capital is sized as 200 USDC, executed as 200_000_000 base units
+        assert!((opp.input_amount - 200.0).abs() < 1e-9);
+        assert_eq!(opp.input_base_units(), 200_000_000);
+        assert!((opp.input_amount_sol - 1.0).abs() < 1e-9);
+
+        // Profit is in USDC; its SOL equivalent uses the anchor price, and the
+        // 0.0001 SOL of fees cost 0.02 USDC
+        let gross = 200.0 * (1.02 * 1.015 * 0.997f64.powi(3) - 1.0);
+        assert!((opp.profit - (gross - 0.02)).abs() < 1e-6, "{}", opp.profit);
+        assert!((opp.profit_sol - opp.profit * 0.005).abs() < 1e-12);
+        assert!((opp.base_units_to_sol(opp.input_base_units().into()) - 1.0).abs() < 1e-9);
+        assert!((opp.base_units_to_sol(-i128::from(opp.input_base_units())) + 1.0).abs() < 1e-9);
+
+        // USDC itself is never an intermediate of a USDC-anchored cycle
+        assert!(opportunities
+            .iter()
+            .all(|o| o.token_a_mint != USDC && o.token_b_mint != USDC));
     }
 }
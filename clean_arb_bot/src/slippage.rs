@@ -5,6 +5,14 @@
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 
+/// Bounds for dynamic swap slippage tolerance (basis points)
+pub const MIN_DYNAMIC_SLIPPAGE_BPS: u16 = 10; // 0.1% - tight enough for stable pairs
+pub const MAX_DYNAMIC_SLIPPAGE_BPS: u16 = 500; // 5% - beyond this the trade isn't arbitrage
+
+/// Weights applied to volatility / spread when sizing slippage tolerance
+const VOLATILITY_WEIGHT: f64 = 0.5;
+const SPREAD_WEIGHT: f64 = 0.25;
+
 /// Calculate expected slippage based on market price and volatility
 ///
 /// # Arguments
@@ -122,6 +130,141 @@ pub fn calculate_slippage_percentage(expected_price: Decimal, actual_price: Deci
     slippage_pct.to_f64().unwrap_or(0.0)
 }
 
+/// Cross-DEX price dispersion as a volatility proxy
+///
+/// # Returns
+/// * `(max - min) / mean` as a percentage, or `None` with fewer than 2 valid prices
+pub fn price_dispersion_pct(prices: &[f64]) -> Option<f64> {
+    let valid: Vec<f64> = prices
+        .iter()
+        .copied()
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .collect();
+    if valid.len() < 2 {
+        return None;
+    }
+
+    let min = valid.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = valid.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = valid.iter().sum::<f64>() / valid.len() as f64;
+
+    Some((max - min) / mean * 100.0)
+}
+
+/// Dynamic slippage tolerance for a swap based on volatility and spread
+///
+/// # Arguments
+/// * `volatility_pct` - Token volatility percentage (None if no data)
+/// * `spread_pct` - Observed arbitrage spread percentage
+/// * `default_bps` - Configured fallback when volatility data is unavailable
+///
+/// # Returns
+/// * Slippage tolerance as a fraction (e.g., 0.005 for 0.5%), clamped to
+///   [MIN_DYNAMIC_SLIPPAGE_BPS, MAX_DYNAMIC_SLIPPAGE_BPS] - never 0
+pub fn dynamic_slippage_tolerance(
+    volatility_pct: Option<f64>,
+    spread_pct: f64,
+    default_bps: u16,
+) -> f64 {
+    let min = MIN_DYNAMIC_SLIPPAGE_BPS as f64 / 10_000.0;
+    let max = MAX_DYNAMIC_SLIPPAGE_BPS as f64 / 10_000.0;
+    let default = (default_bps as f64 / 10_000.0).clamp(min, max);
+
+    let volatility = match volatility_pct {
+        Some(v) if v.is_finite() && v > 0.0 => v,
+        _ => return default,
+    };
+    let spread = if spread_pct.is_finite() {
+        spread_pct.max(0.0)
+    } else {
+        0.0
+    };
+
+    // Normalized price (1.0) so the max deviation is directly a fraction
+    calculate_max_slippage(
+        Decimal::ONE,
+        volatility * VOLATILITY_WEIGHT,
+        spread * SPREAD_WEIGHT,
+    )
+    .to_f64()
+    .map(|tolerance| tolerance.clamp(min, max))
+    .unwrap_or(default)
+}
+
+/// Constant-product price impact of trading into a pool
+///
+/// # Arguments
+/// * `amount_in` - Trade size (lamports)
+/// * `reserve_in` - Pool's SOL-side reserve (lamports)
+///
+/// # Returns
+/// * Impact as a percentage: `amount / (reserve + amount) * 100` (100 for an empty pool)
+pub fn price_impact_pct(amount_in: u64, reserve_in: u64) -> f64 {
+    let total = reserve_in as f64 + amount_in as f64;
+    if total <= 0.0 {
+        return 100.0;
+    }
+    amount_in as f64 / total * 100.0
+}
+
+/// Largest trade into a pool that keeps constant-product price impact at or below a cap
+///
+/// Inverse of `price_impact_pct`: `reserve * p / (100 - p)`
+pub fn max_size_for_price_impact(reserve_in: u64, max_impact_pct: f64) -> u64 {
+    if max_impact_pct.is_nan() || max_impact_pct <= 0.0 {
+        return 0;
+    }
+    if max_impact_pct >= 100.0 {
+        return u64::MAX;
+    }
+    (reserve_in as f64 * max_impact_pct / (100.0 - max_impact_pct)) as u64
+}
+
+/// Slippage tolerance for one swap leg, widened by that pool's price impact
+///
+/// A deep pool barely moves, so the leg keeps `base_bps`; a shallow pool adds
+/// the impact the trade itself causes, so thin legs (typically a triangle's
+/// middle leg) get more room than the SOL entry/exit legs.
+///
+/// # Arguments
+/// * `base_bps` - Tolerance for a leg with negligible impact (or unknown depth)
+/// * `trade_lamports` - Trade size in SOL terms
+/// * `sol_reserve` - Pool's SOL-side reserve (None if depth couldn't be read)
+/// * `max_bps` - Cap on the widened tolerance (never tightens below `base_bps`)
+///
+/// # Returns
+/// * Tolerance in basis points
+pub fn leg_slippage_bps(
+    base_bps: u64,
+    trade_lamports: u64,
+    sol_reserve: Option<u64>,
+    max_bps: u64,
+) -> u64 {
+    let Some(reserve) = sol_reserve else {
+        return base_bps;
+    };
+    let impact_bps = (price_impact_pct(trade_lamports, reserve) * 100.0).ceil() as u64;
+    base_bps
+        .saturating_add(impact_bps)
+        .min(max_bps.max(base_bps))
+}
+
+/// Shrink a position so it stays under the price-impact cap in every pool it trades
+///
+/// # Arguments
+/// * `position_lamports` - Desired position size
+/// * `reserves` - SOL-side reserve of each pool (buy and sell)
+/// * `max_impact_pct` - Per-pool price impact cap
+///
+/// # Returns
+/// * `position_lamports`, or less if a pool is too shallow for it
+pub fn depth_capped_position(position_lamports: u64, reserves: &[u64], max_impact_pct: f64) -> u64 {
+    reserves
+        .iter()
+        .map(|reserve| max_size_for_price_impact(*reserve, max_impact_pct))
+        .fold(position_lamports, u64::min)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +293,82 @@ mod tests {
         assert!(!is_slippage_acceptable(expected, actual_bad, 5.0, 2.0));
     }
 
+    #[test]
+    fn test_dynamic_slippage_falls_back_to_default() {
+        // No volatility data → configured default (never 0)
+        assert!((dynamic_slippage_tolerance(None, 2.0, 50) - 0.005).abs() < 1e-9);
+        assert!((dynamic_slippage_tolerance(Some(f64::NAN), 2.0, 50) - 0.005).abs() < 1e-9);
+        assert!((dynamic_slippage_tolerance(Some(0.0), 2.0, 75) - 0.0075).abs() < 1e-9);
+        assert!(dynamic_slippage_tolerance(None, 2.0, 0) > 0.0);
+    }
+
+    #[test]
+    fn test_dynamic_slippage_scales_and_clamps() {
+        // Stable pair: 0.1% dispersion, 0.4% spread → 0.05% + 0.1% = 0.15%
+        let stable = dynamic_slippage_tolerance(Some(0.1), 0.4, 50);
+        assert!((stable - 0.0015).abs() < 1e-9);
+
+        // Volatile memecoin: 4% dispersion, 2% spread → 2% + 0.5% = 2.5%
+        let volatile = dynamic_slippage_tolerance(Some(4.0), 2.0, 50);
+        assert!((volatile - 0.025).abs() < 1e-9);
+
+        // Extreme volatility is capped
+        assert!((dynamic_slippage_tolerance(Some(80.0), 10.0, 50) - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_price_dispersion() {
+        assert_eq!(price_dispersion_pct(&[1.0]), None);
+        assert_eq!(price_dispersion_pct(&[1.0, f64::NAN, 0.0]), None);
+        let dispersion = price_dispersion_pct(&[0.99, 1.01]).unwrap();
+        assert!((dispersion - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_deep_pool_keeps_full_position() {
+        // 0.5 SOL into 1000 SOL of depth: ~0.05% impact, well under a 1% cap
+        let position = 500_000_000;
+        let reserves = [1_000_000_000_000, 800_000_000_000];
+        assert_eq!(depth_capped_position(position, &reserves, 1.0), position);
+        assert!(price_impact_pct(position, reserves[0]) < 0.1);
+    }
+
+    #[test]
+    fn test_shallow_pool_caps_position() {
+        // 0.2 SOL of depth can only take ~0.00202 SOL at 1% impact
+        let capped = depth_capped_position(500_000_000, &[1_000_000_000_000, 200_000_000], 1.0);
+        assert_eq!(capped, max_size_for_price_impact(200_000_000, 1.0));
+        assert!((capped as f64 - 2_020_202.0).abs() < 1.0);
+        assert!((price_impact_pct(capped, 200_000_000) - 1.0).abs() < 1e-3);
+
+        // Uncapped, the same trade would move the price ~71%
+        assert!(price_impact_pct(500_000_000, 200_000_000) > 70.0);
+        assert_eq!(depth_capped_position(500_000_000, &[0], 1.0), 0);
+    }
+
+    #[test]
+    fn test_shallow_middle_leg_gets_wider_slippage() {
+        let position = 500_000_000;
+        // Entry leg in a 1000 SOL pool: ~0.05% impact
+        let entry = leg_slippage_bps(100, position, Some(1_000_000_000_000), 300);
+        // Middle leg in a 50 SOL pool: ~1% impact
+        let middle = leg_slippage_bps(100, position, Some(50_000_000_000), 300);
+        assert_eq!(entry, 105);
+        assert_eq!(middle, 200);
+        assert!(middle > entry);
+
+        // Capped for very thin pools, base when depth is unknown
+        assert_eq!(
+            leg_slippage_bps(100, position, Some(1_000_000_000), 300),
+            300
+        );
+        assert_eq!(leg_slippage_bps(100, position, None, 300), 100);
+        assert_eq!(
+            leg_slippage_bps(400, position, Some(1_000_000_000), 300),
+            400
+        );
+    }
+
     #[test]
     fn test_slippage_percentage() {
         let expected = dec!(1.0);
@@ -0,0 +1,215 @@
+// Historical spread analyzer
+//
+// Records every fresh cross-DEX spread seen per token together with the
+// cost-derived break-even spread (ArbitrageCosts) at that moment, then
+// recommends the lowest min-spread at which most observed spreads would have
+// been profitable. Tokens that throw off many false spreads get a higher bar.
+//
+// Advisory by default - AUTO_TUNE_SPREAD=true feeds the recommendations into
+// the engine's per-token min spread.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Samples a token needs inside the window before a recommendation is made
+const MIN_SAMPLES_FOR_RECOMMENDATION: usize = 30;
+
+/// Cap on samples kept per token (oldest dropped first)
+const MAX_SAMPLES_PER_TOKEN: usize = 5_000;
+
+/// Share of spreads at/above the recommended min spread that must have been profitable
+const TARGET_PROFITABLE_FRACTION: f64 = 0.8;
+
+/// One observed spread and the break-even spread after costs at that time
+#[derive(Debug, Clone, Copy)]
+struct SpreadSample {
+    observed_at: Instant,
+    spread_pct: f64,
+    required_spread_pct: f64,
+}
+
+impl SpreadSample {
+    fn profitable(&self) -> bool {
+        self.spread_pct >= self.required_spread_pct
+    }
+}
+
+/// Spread distribution and recommended min spread for one token
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadRecommendation {
+    pub token_mint: String,
+    pub samples: usize,
+    pub median_spread_pct: f64,
+    pub p90_spread_pct: f64,
+    pub profitable_fraction: f64, // Share of all samples that cleared costs
+    pub recommended_min_spread_pct: Option<f64>, // None = no threshold reaches the target
+}
+
+/// Rolling per-token window of observed spreads
+pub struct SpreadAnalyzer {
+    window: Duration,
+    samples: HashMap<String, VecDeque<SpreadSample>>,
+}
+
+impl SpreadAnalyzer {
+    /// Create an analyzer keeping `window` of history per token
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Record an observed spread for `token_mint`
+    ///
+    /// # Arguments
+    /// * `spread_pct` - Sell-over-buy spread in percent (non-positive spreads are ignored)
+    /// * `required_spread_pct` - Break-even spread after all costs for the same pairing
+    /// * `now` - Observation time
+    pub fn record(
+        &mut self,
+        token_mint: &str,
+        spread_pct: f64,
+        required_spread_pct: f64,
+        now: Instant,
+    ) {
+        if !(spread_pct.is_finite() && spread_pct > 0.0 && required_spread_pct.is_finite()) {
+            return;
+        }
+
+        let samples = self.samples.entry(token_mint.to_string()).or_default();
+        samples.push_back(SpreadSample {
+            observed_at: now,
+            spread_pct,
+            required_spread_pct,
+        });
+        if samples.len() > MAX_SAMPLES_PER_TOKEN {
+            samples.pop_front();
+        }
+    }
+
+    /// Drop samples older than the window, then recommend a min spread per token
+    ///
+    /// # Returns
+    /// One entry per token with enough samples, most-observed token first
+    pub fn recommendations(&mut self, now: Instant) -> Vec<SpreadRecommendation> {
+        let window = self.window;
+        self.samples.retain(|_, samples| {
+            while samples
+                .front()
+                .is_some_and(|sample| now.duration_since(sample.observed_at) > window)
+            {
+                samples.pop_front();
+            }
+            !samples.is_empty()
+        });
+
+        let mut recommendations: Vec<SpreadRecommendation> = self
+            .samples
+            .iter()
+            .filter(|(_, samples)| samples.len() >= MIN_SAMPLES_FOR_RECOMMENDATION)
+            .map(|(token_mint, samples)| recommend(token_mint, samples))
+            .collect();
+        recommendations.sort_by(|a, b| {
+            b.samples
+                .cmp(&a.samples)
+                .then_with(|| a.token_mint.cmp(&b.token_mint))
+        });
+        recommendations
+    }
+}
+
+/// Summarize one token's samples
+///
+/// The recommended min spread is the LOWEST threshold where at least
+/// `TARGET_PROFITABLE_FRACTION` of the spreads at or above it cleared costs.
+fn recommend(token_mint: &str, samples: &VecDeque<SpreadSample>) -> SpreadRecommendation {
+    let mut sorted: Vec<SpreadSample> = samples.iter().copied().collect();
+    sorted.sort_by(|a, b| {
+        b.spread_pct
+            .partial_cmp(&a.spread_pct)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    // Walk from the widest spread down; a threshold only counts at the end of a tie
+    let mut profitable = 0usize;
+    let mut recommended_min_spread_pct = None;
+    for (index, sample) in sorted.iter().enumerate() {
+        if sample.profitable() {
+            profitable += 1;
+        }
+        let end_of_tie = sorted
+            .get(index + 1)
+            .is_none_or(|next| next.spread_pct < sample.spread_pct);
+        if end_of_tie && profitable as f64 / (index + 1) as f64 >= TARGET_PROFITABLE_FRACTION {
+            recommended_min_spread_pct = Some(sample.spread_pct);
+        }
+    }
+
+    // Nearest-rank percentiles on the ascending order
+    let count = sorted.len();
+    let percentile = |pct: usize| sorted[count - 1 - (count * pct / 100).min(count - 1)].spread_pct;
+
+    SpreadRecommendation {
+        token_mint: token_mint.to_string(),
+        samples: count,
+        median_spread_pct: percentile(50),
+        p90_spread_pct: percentile(90),
+        profitable_fraction: profitable as f64 / count as f64,
+        recommended_min_spread_pct,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommends_lowest_reliably_profitable_spread() {
+        let now = Instant::now();
+        let mut analyzer = SpreadAnalyzer::new(Duration::from_secs(3600));
+
+        // Break-even is 0.5%: 0.1-0.4% spreads are false, 0.6%+ clear costs
+        for _ in 0..10 {
+            for spread in [0.1, 0.2, 0.3, 0.4, 0.6, 0.8, 1.0] {
+                analyzer.record("MINT", spread, 0.5, now);
+            }
+        }
+        // Below the sample floor - no recommendation
+        for _ in 0..5 {
+            analyzer.record("RARE", 2.0, 0.5, now);
+        }
+
+        let recommendations = analyzer.recommendations(now);
+        assert_eq!(recommendations.len(), 1);
+        let mint = &recommendations[0];
+        assert_eq!(mint.samples, 70);
+        assert!((mint.profitable_fraction - 30.0 / 70.0).abs() < 1e-9);
+        assert_eq!(mint.median_spread_pct, 0.4);
+        assert_eq!(mint.p90_spread_pct, 1.0);
+        // 0.6%+ is 100% profitable; adding 0.4% drops to 75% (< 80%)
+        assert_eq!(mint.recommended_min_spread_pct, Some(0.6));
+    }
+
+    #[test]
+    fn test_old_samples_leave_the_window() {
+        let start = Instant::now();
+        let mut analyzer = SpreadAnalyzer::new(Duration::from_secs(60));
+        for _ in 0..MIN_SAMPLES_FOR_RECOMMENDATION {
+            analyzer.record("MINT", 0.2, 0.5, start);
+        }
+        // Ignored: no spread, or nonsense break-even
+        analyzer.record("MINT", -0.3, 0.5, start);
+        analyzer.record("MINT", 0.3, f64::INFINITY, start);
+
+        let fresh = analyzer.recommendations(start);
+        assert_eq!(fresh[0].samples, MIN_SAMPLES_FOR_RECOMMENDATION);
+        // Never profitable - nothing to recommend
+        assert_eq!(fresh[0].recommended_min_spread_pct, None);
+
+        assert!(analyzer
+            .recommendations(start + Duration::from_secs(61))
+            .is_empty());
+        assert!(analyzer.samples.is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+// Maximum realistic spread per token tier
+//
+// A 5% spread between two deep stablecoin pools is bad price data; the same
+// 5% on a fresh memecoin is a plausible opportunity. SPREAD_TIERS replaces the
+// old flat 50% cap with one cap per tier. Grammar - comma-separated entries:
+//
+// - `stable:<pct>`          - stablecoins and liquid-staking tokens (`stable:2`)
+// - `<min_volume_sol>:<pct>` - pairs whose thinner pool trades at least this
+//                              much SOL per 24h (`1000:10`)
+//
+// A pairing uses the stable cap if its token is a known stable/LST mint,
+// otherwise the highest volume tier its thinner pool qualifies for. Pools
+// below every volume tier get the loosest (lowest-volume) tier's cap.
+
+use anyhow::{bail, Context, Result};
+use std::fmt;
+
+/// Default SPREAD_TIERS
+pub const DEFAULT_SPREAD_TIERS: &str = "stable:2,1000:10,100:25,0:50";
+
+/// Stablecoins and liquid-staking tokens: deep, pegged, spreads stay tiny
+const STABLE_MINTS: &[&str] = &[
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", // USDC
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", // USDT
+    "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So",  // mSOL
+    "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn", // JitoSOL
+];
+
+/// Cap applied when SPREAD_TIERS lists no volume tier
+const FALLBACK_MAX_SPREAD_PCT: f64 = 50.0;
+
+/// Spread cap for pairings whose thinner pool trades at least `min_volume_sol`/24h
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeTier {
+    pub min_volume_sol: f64,
+    pub max_spread_pct: f64,
+}
+
+/// Per-tier maximum realistic spread (SPREAD_TIERS)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadTiers {
+    stable_max_spread_pct: Option<f64>,
+    volume_tiers: Vec<VolumeTier>, // Sorted by min_volume_sol, highest first
+}
+
+impl Default for SpreadTiers {
+    fn default() -> Self {
+        Self::parse(DEFAULT_SPREAD_TIERS).expect("default SPREAD_TIERS parses")
+    }
+}
+
+impl SpreadTiers {
+    /// Parse a SPREAD_TIERS value (see module docs for the grammar)
+    ///
+    /// # Errors
+    /// Returns error on an empty value, malformed entry or non-positive cap
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut stable_max_spread_pct = None;
+        let mut volume_tiers = Vec::new();
+
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (tier, cap) = entry.split_once(':').with_context(|| {
+                format!(
+                    "Invalid spread tier '{}': expected stable:<pct> or <min_volume_sol>:<pct>",
+                    entry
+                )
+            })?;
+            let max_spread_pct: f64 = cap.trim().parse().with_context(|| {
+                format!("Invalid max spread '{}' in tier '{}'", cap.trim(), entry)
+            })?;
+            if !max_spread_pct.is_finite() || max_spread_pct <= 0.0 {
+                bail!(
+                    "Invalid max spread {}% in tier '{}': must be > 0",
+                    max_spread_pct,
+                    entry
+                );
+            }
+
+            let tier = tier.trim();
+            if tier.eq_ignore_ascii_case("stable") {
+                stable_max_spread_pct = Some(max_spread_pct);
+                continue;
+            }
+            let min_volume_sol: f64 = tier.parse().with_context(|| {
+                format!(
+                    "Invalid spread tier '{}': expected 'stable' or a 24h volume in SOL",
+                    tier
+                )
+            })?;
+            if !min_volume_sol.is_finite() || min_volume_sol < 0.0 {
+                bail!(
+                    "Invalid tier volume {} SOL in '{}': must be >= 0",
+                    min_volume_sol,
+                    entry
+                );
+            }
+            volume_tiers.push(VolumeTier {
+                min_volume_sol,
+                max_spread_pct,
+            });
+        }
+
+        if stable_max_spread_pct.is_none() && volume_tiers.is_empty() {
+            bail!("SPREAD_TIERS is empty");
+        }
+        volume_tiers.sort_by(|a, b| {
+            b.min_volume_sol
+                .partial_cmp(&a.min_volume_sol)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Ok(Self {
+            stable_max_spread_pct,
+            volume_tiers,
+        })
+    }
+
+    /// Maximum realistic spread for one pairing
+    ///
+    /// # Arguments
+    /// * `token_mint` - Token being arbitraged
+    /// * `buy_volume_sol` - 24h volume of the buy pool (SOL)
+    /// * `sell_volume_sol` - 24h volume of the sell pool (SOL)
+    ///
+    /// # Returns
+    /// Spread percentage above which the pairing is treated as bad price data
+    pub fn max_spread_pct(
+        &self,
+        token_mint: &str,
+        buy_volume_sol: f64,
+        sell_volume_sol: f64,
+    ) -> f64 {
+        if let Some(stable_cap) = self.stable_max_spread_pct {
+            if STABLE_MINTS.contains(&token_mint) {
+                return stable_cap;
+            }
+        }
+
+        // The thinner pool decides how far the price can plausibly lag
+        let volume_sol = buy_volume_sol.min(sell_volume_sol);
+        self.volume_tiers
+            .iter()
+            .find(|tier| volume_sol >= tier.min_volume_sol)
+            .or_else(|| self.volume_tiers.last())
+            .map(|tier| tier.max_spread_pct)
+            .unwrap_or(FALLBACK_MAX_SPREAD_PCT)
+    }
+}
+
+impl fmt::Display for SpreadTiers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(stable_cap) = self.stable_max_spread_pct {
+            parts.push(format!("stable:{}", stable_cap));
+        }
+        for tier in &self.volume_tiers {
+            parts.push(format!("{}:{}", tier.min_volume_sol, tier.max_spread_pct));
+        }
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const MEMECOIN: &str = "MemeMint111111111111111111111111111111111111";
+
+    #[test]
+    fn test_stable_false_spread_rejected_memecoin_spread_passes() {
+        let tiers = SpreadTiers::default();
+
+        // 5% between two deep USDC pools is bad data
+        let stable_cap = tiers.max_spread_pct(USDC, 50_000.0, 40_000.0);
+        assert_eq!(stable_cap, 2.0);
+        assert!(5.0 > stable_cap);
+
+        // 5% on a thin fresh memecoin is plausible
+        let meme_cap = tiers.max_spread_pct(MEMECOIN, 40.0, 500.0);
+        assert_eq!(meme_cap, 50.0);
+        assert!(5.0 <= meme_cap);
+
+        // Deep memecoin pools: thinner pool (2,000 SOL) selects the 1000 tier
+        assert_eq!(tiers.max_spread_pct(MEMECOIN, 2_000.0, 9_000.0), 10.0);
+        assert_eq!(tiers.max_spread_pct(MEMECOIN, 150.0, 9_000.0), 25.0);
+    }
+
+    #[test]
+    fn test_parse_sorts_tiers_and_rejects_bad_entries() {
+        let tiers = SpreadTiers::parse("100:20, 1000:5").unwrap();
+        assert_eq!(tiers.to_string(), "1000:5,100:20");
+        // No stable tier: USDC is classified by volume like any token
+        assert_eq!(tiers.max_spread_pct(USDC, 50_000.0, 50_000.0), 5.0);
+        // Below every tier: loosest cap
+        assert_eq!(tiers.max_spread_pct(MEMECOIN, 10.0, 10.0), 20.0);
+
+        // Stable-only table falls back to the flat cap for everything else
+        let stable_only = SpreadTiers::parse("stable:1").unwrap();
+        assert_eq!(
+            stable_only.max_spread_pct(MEMECOIN, 10.0, 10.0),
+            FALLBACK_MAX_SPREAD_PCT
+        );
+
+        assert_eq!(SpreadTiers::default().to_string(), DEFAULT_SPREAD_TIERS);
+        assert!(SpreadTiers::parse("").is_err());
+        assert!(SpreadTiers::parse("stable").is_err());
+        assert!(SpreadTiers::parse("stable:0").is_err());
+        assert!(SpreadTiers::parse("deep:5").is_err());
+        assert!(SpreadTiers::parse("-5:10").is_err());
+    }
+}
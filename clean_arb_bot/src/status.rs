@@ -0,0 +1,125 @@
+// Live engine status endpoint
+//
+// The engine owns its stats by value and runs on a single task, so the HTTP
+// server never touches engine state directly. Instead the engine publishes a
+// JSON snapshot (`ArbitrageEngine::status_json`) once per loop iteration and
+// the server only reads the latest snapshot.
+// Opt-in via STATUS_PORT - serves JSON on GET /status
+
+use anyhow::Result;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+/// Latest published engine status, shared between the engine and the HTTP task
+#[derive(Debug)]
+pub struct StatusBoard {
+    snapshot: RwLock<Value>,
+}
+
+impl Default for StatusBoard {
+    fn default() -> Self {
+        Self {
+            snapshot: RwLock::new(json!({ "status": "starting" })),
+        }
+    }
+}
+
+impl StatusBoard {
+    /// Create new status board (reports "starting" until the first publish)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the current snapshot
+    pub fn publish(&self, status: Value) {
+        match self.snapshot.write() {
+            Ok(mut snapshot) => *snapshot = status,
+            Err(poisoned) => *poisoned.into_inner() = status,
+        }
+    }
+
+    /// Latest published snapshot
+    pub fn snapshot(&self) -> Value {
+        match self.snapshot.read() {
+            Ok(snapshot) => snapshot.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Spawn HTTP server exposing `GET /status` on `addr`
+    ///
+    /// # Arguments
+    /// * `addr` - Socket address to bind (e.g. 127.0.0.1:9091)
+    ///
+    /// # Returns
+    /// Handle to the server task (runs until the runtime shuts down)
+    ///
+    /// # Errors
+    /// Returns error if the address cannot be bound
+    pub fn serve(self: Arc<Self>, addr: SocketAddr) -> Result<JoinHandle<()>> {
+        let make_service = make_service_fn(move |_conn| {
+            let board = self.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let board = board.clone();
+                    async move { Ok::<_, Infallible>(board.handle_request(&req)) }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&addr)?.serve(make_service);
+        info!("🩺 Engine status available at http://{}/status", addr);
+
+        Ok(tokio::spawn(async move {
+            if let Err(e) = server.await {
+                error!("❌ Status server failed: {}", e);
+            }
+        }))
+    }
+
+    /// Route a status request (only GET /status is served)
+    fn handle_request(&self, req: &Request<Body>) -> Response<Body> {
+        if req.method() == Method::GET && req.uri().path() == "/status" {
+            Response::builder()
+                .header("Content-Type", "application/json")
+                .body(Body::from(self.snapshot().to_string()))
+                .unwrap_or_else(|_| Response::new(Body::empty()))
+        } else {
+            let mut response = Response::new(Body::from("Not Found"));
+            *response.status_mut() = StatusCode::NOT_FOUND;
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_status_serves_latest_snapshot() {
+        let board = StatusBoard::new();
+        assert_eq!(board.snapshot()["status"], "starting");
+
+        board.publish(json!({ "status": "running", "stats": { "opportunities_detected": 7 } }));
+
+        let request = Request::get("/status").body(Body::empty()).unwrap();
+        let response = board.handle_request(&request);
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let parsed: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(parsed["stats"]["opportunities_detected"], 7);
+
+        let request = Request::get("/metrics").body(Body::empty()).unwrap();
+        assert_eq!(
+            board.handle_request(&request).status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,155 @@
+// Structured logging (LOG_FORMAT)
+//
+// - text: human-readable emoji lines (default, unchanged)
+// - json: one JSON object per line for log pipelines (Loki, Datadog, ...)
+//
+// In json mode the engine also emits typed execution events under the
+// `arb_events` target: `opportunity_detected`, then one `trade` event per
+// outcome (submitted, landed, failed, rejected with its reason). Pipelines can
+// filter on `target == "arb_events"` and aggregate on fields instead of
+// regex-parsing messages. Text mode drops these events so the console isn't
+// doubled up with the emoji lines that describe the same thing.
+//
+// LOG_FORMAT is read straight from the environment because the subscriber is
+// installed before the rest of the config is loaded.
+
+use anyhow::{bail, Result};
+use std::fmt;
+use tracing::info;
+
+use crate::trade_log::{TradeOutcome, TradeRecord};
+
+/// Tracing target carrying the typed execution events
+pub const EVENT_TARGET: &str = "arb_events";
+
+/// Base filter shared by both formats
+const BASE_FILTER: &str = "info,clean_arb_bot=debug";
+
+/// Log line encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// Parse a LOG_FORMAT value (`text` or `json`, case-insensitive)
+    ///
+    /// # Errors
+    /// Returns error for any other value
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => bail!("Invalid LOG_FORMAT '{}': expected text or json", other),
+        }
+    }
+
+    /// Read LOG_FORMAT from the environment (default: text)
+    ///
+    /// # Errors
+    /// Returns error if LOG_FORMAT is set to an unknown value
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("LOG_FORMAT") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Env filter for this format (text drops the `arb_events` target)
+    fn filter(self) -> String {
+        match self {
+            Self::Text => format!("{},{}=off", BASE_FILTER, EVENT_TARGET),
+            Self::Json => BASE_FILTER.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Install the global tracing subscriber
+///
+/// # Arguments
+/// * `format` - Text (emoji lines) or JSON (one object per line, fields flattened)
+pub fn init(format: LogFormat) {
+    let builder = tracing_subscriber::fmt().with_env_filter(format.filter());
+    match format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().flatten_event(true).init(),
+    }
+}
+
+/// Emit an `opportunity_detected` event
+///
+/// # Arguments
+/// * `token` - Token mint (or triangle path)
+/// * `dexes` - DEX route, e.g. `Orca→Raydium`
+/// * `spread_pct` - Detected spread in percent
+/// * `profit_sol` - Estimated profit before execution
+pub fn opportunity_detected(token: &str, dexes: &str, spread_pct: f64, profit_sol: f64) {
+    info!(
+        target: EVENT_TARGET,
+        event = "opportunity_detected",
+        token,
+        dexes,
+        spread_pct,
+        profit_sol,
+        "opportunity detected"
+    );
+}
+
+/// Emit a `trade` event for a logged trade outcome
+///
+/// # Arguments
+/// * `trade` - Trade as written to the CSV trade log
+/// * `outcome` - Submitted / landed / failed / rejected / paper
+/// * `reason` - Why the trade was rejected, when known
+pub fn trade_event(trade: &TradeRecord, outcome: TradeOutcome, reason: Option<&str>) {
+    info!(
+        target: EVENT_TARGET,
+        event = "trade",
+        token = %trade.token_mint,
+        dexes = %route(trade),
+        buy_pool = %trade.buy_pool,
+        sell_pool = %trade.sell_pool,
+        spread_pct = trade.spread_pct,
+        position_size_sol = trade.position_size_sol,
+        profit_sol = trade.estimated_profit_sol,
+        realized_profit_sol = trade.realized_profit_sol,
+        jito_tip_lamports = trade.jito_tip_lamports,
+        tx_id = trade.tx_id.as_deref(),
+        outcome = outcome.label(),
+        reason,
+        "trade {}",
+        outcome.label()
+    );
+}
+
+/// DEX route field: `buy→sell`
+fn route(trade: &TradeRecord) -> String {
+    format!("{}→{}", trade.buy_dex, trade.sell_dex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_parse_and_filter() {
+        assert_eq!(LogFormat::parse(" JSON ").unwrap(), LogFormat::Json);
+        assert_eq!(LogFormat::parse("text").unwrap(), LogFormat::default());
+        assert!(LogFormat::parse("yaml").is_err());
+
+        // Typed events only reach the JSON output
+        assert!(LogFormat::Text.filter().ends_with("arb_events=off"));
+        assert!(!LogFormat::Json.filter().contains("arb_events"));
+    }
+}
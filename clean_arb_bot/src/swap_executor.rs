@@ -9,29 +9,97 @@
 // Provides simple API for arbitrage engine
 
 use anyhow::{Context, Result};
+use dashmap::DashMap;
 use solana_sdk::{
-    compute_budget::ComputeBudgetInstruction, hash::Hash, instruction::Instruction, pubkey::Pubkey,
-    signature::Signature, signer::Signer, transaction::Transaction,
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    transaction::{Transaction, VersionedTransaction},
 };
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+use crate::dex_filter::DexFilter;
+use crate::errors::{ArbError, ArbResult};
 use crate::jito_bundle_client::JitoBundleClient;
+use crate::jito_tip_monitor::JitoTipFloor;
+use crate::metrics::LatencyBreakdown;
+use crate::tip_strategy::TipStrategy;
 use crate::{
+    aldrin::AldrinSwapBuilder,
+    crema::CremaSwapBuilder,
+    fluxbeam::FluxbeamSwapBuilder,
     humidifi::HumidiFiSwapBuilder,
+    lifinity::LifinitySwapBuilder,
     meteora::MeteoraSwapBuilder,
     orca::OrcaSwapBuilder,
     pool_registry::PoolRegistry,
     pumpswap::PumpSwapSwapBuilder,
     raydium::RaydiumSwapBuilder,
-    rpc_client::SolanaRpcClient,
+    rpc_client::RpcApi,
+    saros::SarosSwapBuilder,
+    token_program::MintTokenProgram,
     types::{DexType, SwapParams},
 };
 
+/// Max time to wait for a submitted JITO bundle to land before giving up
+const BUNDLE_LANDING_TIMEOUT_SECS: u64 = 10;
+
+/// Leg count accepted by `build_leg_bundle_with_tip` (JITO caps bundles at 5 transactions)
+pub const MIN_SPLIT_BUNDLE_LEGS: usize = 2;
+pub const MAX_SPLIT_BUNDLE_LEGS: usize = 4;
+/// Smallest tip transfer Jito accepts (per transaction)
+const MIN_TIP_LAMPORTS: u64 = 1_000;
+
+/// Congestion reference points (EMA p50 landed tip, lamports)
+/// At/below the calm level we pay the min CU price, at/above the congested level the max
+const CALM_TIP_FLOOR_LAMPORTS: f64 = 1_000.0;
+const CONGESTED_TIP_FLOOR_LAMPORTS: f64 = 1_000_000.0;
+
+/// Compute units assumed for an instruction no DEX builder claims (old flat per-swap figure)
+const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 100_000;
+/// Compute units for a system transfer (the JITO tip, WSOL funding)
+const TRANSFER_COMPUTE_UNITS: u32 = 300;
+/// Compute units for an idempotent ATA create (WSOL setup)
+const ATA_CREATE_COMPUTE_UNITS: u32 = 25_000;
+/// Compute units for an SPL Token instruction (sync_native, close_account)
+const TOKEN_INSTRUCTION_COMPUTE_UNITS: u32 = 5_000;
+/// Safety buffer added on top of the summed per-leg estimate
+const COMPUTE_UNIT_BUFFER_PCT: u32 = 20;
+/// Solana per-transaction compute unit ceiling
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u32 = 1_400_000;
+/// Cached compute budget pairs before the cache is flushed (congestion
+/// scaling and per-account fees keep producing new prices)
+const MAX_CACHED_COMPUTE_BUDGETS: usize = 256;
+
+/// Set-price + set-limit compute budget instructions, in transaction order
+fn build_compute_budget(compute_unit_price: u64, compute_limit: u32) -> [Instruction; 2] {
+    [
+        ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_limit),
+    ]
+}
+
+/// JITO tip transfer carried inside a swap transaction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JitoTip {
+    /// Tip amount (minimum 1000 lamports)
+    pub lamports: u64,
+    /// Jito tip account receiving the transfer
+    pub account: Pubkey,
+}
+
 /// High-level swap executor that coordinates all swap operations
 pub struct SwapExecutor {
     /// RPC client for blockchain operations
-    rpc_client: Arc<SolanaRpcClient>,
+    rpc_client: Arc<dyn RpcApi>,
     /// Pool registry for address lookups
     pool_registry: Arc<PoolRegistry>,
     /// Meteora swap builder
@@ -42,6 +110,16 @@ pub struct SwapExecutor {
     pumpswap_builder: PumpSwapSwapBuilder,
     /// Raydium swap builder
     raydium_builder: RaydiumSwapBuilder,
+    /// Lifinity swap builder
+    lifinity_builder: LifinitySwapBuilder,
+    /// Saros swap builder
+    saros_builder: SarosSwapBuilder,
+    /// Crema swap builder
+    crema_builder: CremaSwapBuilder,
+    /// Aldrin swap builder
+    aldrin_builder: AldrinSwapBuilder,
+    /// Fluxbeam swap builder
+    fluxbeam_builder: FluxbeamSwapBuilder,
     /// HumidiFi swap builder
     humidifi_builder: Option<HumidiFiSwapBuilder>,
     /// JITO bundle client for atomic execution (optional)
@@ -50,12 +128,25 @@ pub struct SwapExecutor {
     compute_unit_price: u64,
     /// Default compute unit limit
     compute_unit_limit: u32,
+    /// Congestion scaling bounds for compute_unit_price (micro-lamports per CU)
+    compute_unit_price_min: u64,
+    compute_unit_price_max: u64,
+    /// Per-DEX compute unit estimates that replace the builders' own (DEX_COMPUTE_UNITS)
+    compute_unit_overrides: HashMap<DexType, u32>,
+    /// Price each transaction from recent fees on the pools it writes (PRIORITY_FEE_PERCENTILE)
+    priority_fee_from_accounts: bool,
+    /// Tip policy when no JITO client is attached (TIP_STRATEGY)
+    tip_strategy: TipStrategy,
+    /// DEXs swaps may be built for (ENABLED_DEXES / DISABLED_DEXES)
+    dex_filter: DexFilter,
+    /// Built compute budget instructions keyed by (price, limit)
+    compute_budget_cache: DashMap<(u64, u32), [Instruction; 2]>,
 }
 
 impl SwapExecutor {
     /// Create new swap executor
     pub fn new(
-        rpc_client: Arc<SolanaRpcClient>,
+        rpc_client: Arc<dyn RpcApi>,
         pool_registry: Arc<PoolRegistry>,
         jito_client: Option<Arc<JitoBundleClient>>,
     ) -> Result<Self> {
@@ -71,6 +162,21 @@ impl SwapExecutor {
         // Initialize Raydium builder
         let raydium_builder = RaydiumSwapBuilder::new(rpc_client.clone(), pool_registry.clone())?;
 
+        // Initialize Lifinity builder
+        let lifinity_builder = LifinitySwapBuilder::new(rpc_client.clone(), pool_registry.clone())?;
+
+        // Initialize Saros builder
+        let saros_builder = SarosSwapBuilder::new(rpc_client.clone(), pool_registry.clone())?;
+
+        // Initialize Crema builder
+        let crema_builder = CremaSwapBuilder::new(rpc_client.clone(), pool_registry.clone())?;
+
+        // Initialize Aldrin builder
+        let aldrin_builder = AldrinSwapBuilder::new(rpc_client.clone(), pool_registry.clone())?;
+
+        // Initialize Fluxbeam builder
+        let fluxbeam_builder = FluxbeamSwapBuilder::new(rpc_client.clone(), pool_registry.clone())?;
+
         // Initialize HumidiFi builder (may fail if program ID is incorrect)
         let humidifi_builder = match HumidiFiSwapBuilder::new() {
             Ok(builder) => {
@@ -85,7 +191,7 @@ impl SwapExecutor {
 
         info!("✅ Swap executor initialized");
         info!(
-            "   DEX support: Meteora DLMM/DAMM V2, Orca Whirlpools, Raydium CPMM, PumpSwap{}",
+            "   DEX support: Meteora DLMM/DAMM V2, Orca Whirlpools, Raydium CPMM/CLMM, PumpSwap, Lifinity, Saros, Crema, Aldrin, Fluxbeam{}",
             if humidifi_builder.is_some() {
                 ", HumidiFi"
             } else {
@@ -108,10 +214,22 @@ impl SwapExecutor {
             orca_builder,
             pumpswap_builder,
             raydium_builder,
+            lifinity_builder,
+            saros_builder,
+            crema_builder,
+            aldrin_builder,
+            fluxbeam_builder,
             humidifi_builder,
             jito_client,
             compute_unit_price: 1000, // 1000 micro-lamports (0.001 lamports per CU)
             compute_unit_limit: 200_000, // 200k compute units
+            compute_unit_price_min: 1000,
+            compute_unit_price_max: 100_000,
+            compute_unit_overrides: HashMap::new(),
+            priority_fee_from_accounts: false,
+            tip_strategy: TipStrategy::default(),
+            dex_filter: DexFilter::default(),
+            compute_budget_cache: DashMap::new(),
         })
     }
 
@@ -186,7 +304,7 @@ impl SwapExecutor {
         // CYCLE-7: MANDATORY SIMULATION (Grok recommendation)
         // Catches failed swaps without cost - bulletproof safety
         info!("🧪 Simulating transaction before execution...");
-        let sim_result = self.rpc_client.simulate_transaction(&transaction)?;
+        let sim_result = self.rpc_client.simulate_transaction(&transaction, None)?;
 
         if !sim_result {
             return Err(anyhow::anyhow!(
@@ -239,9 +357,8 @@ impl SwapExecutor {
     /// Execute a triangle arbitrage (3 swaps atomically)
     ///
     /// # Arguments
-    /// * `leg1` - First swap (e.g., SOL → TokenA)
-    /// * `leg2` - Second swap (e.g., TokenA → TokenB)
-    /// * `leg3` - Third swap (e.g., TokenB → SOL)
+    /// * `input_mint` - Mint the first leg spends (must be native SOL)
+    /// * `legs` - Swaps in route order (e.g., SOL → TokenA → TokenB → SOL)
     /// * `wallet` - User's wallet
     /// * `use_jito` - If true, submit via JITO bundle for MEV protection
     ///
@@ -249,12 +366,12 @@ impl SwapExecutor {
     /// Transaction signature or bundle ID
     pub async fn execute_triangle<T: Signer>(
         &self,
-        leg1: (&DexType, &str, &SwapParams),
-        leg2: (&DexType, &str, &SwapParams),
-        leg3: (&DexType, &str, &SwapParams),
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
         wallet: &T,
         use_jito: bool,
     ) -> Result<String> {
+        let [leg1, leg2, leg3] = legs;
         info!("🔺 Executing triangle arbitrage");
         info!("   Leg 1: {:?} pool {}", leg1.0, leg1.1);
         info!("   Leg 2: {:?} pool {}", leg2.0, leg2.1);
@@ -275,15 +392,23 @@ impl SwapExecutor {
 
         debug!("✅ Built all 3 swap instructions");
 
+        // Wrap the SOL input, unwrap the SOL output
+        let instructions = sol_route_instructions(
+            &user_pubkey,
+            input_mint,
+            leg1.2.amount_in,
+            vec![ix1, ix2, ix3],
+        )?;
+
         // Get recent blockhash
         let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
 
         // Build transaction with all swaps
-        let transaction = self.build_transaction(vec![ix1, ix2, ix3], wallet, recent_blockhash)?;
+        let transaction = self.build_transaction(instructions, wallet, recent_blockhash)?;
 
         // Simulate first
         info!("🧪 Simulating triangle transaction...");
-        let sim_result = self.rpc_client.simulate_transaction(&transaction)?;
+        let sim_result = self.rpc_client.simulate_transaction(&transaction, None)?;
 
         if !sim_result {
             return Err(anyhow::anyhow!(
@@ -295,25 +420,48 @@ impl SwapExecutor {
         info!("✅ Triangle simulation passed");
 
         // Execute via JITO bundle or regular transaction
-        if use_jito && self.jito_client.is_some() {
-            info!("💎 Submitting via JITO bundle for MEV protection...");
+        self.submit_transaction(transaction, use_jito).await
+    }
 
-            // TODO: Use JITO client to submit bundle
-            // let bundle_id = self.jito_client.as_ref().unwrap()
-            //     .submit_bundle(&transaction)
-            //     .await?;
+    /// Submit a signed transaction via JITO bundle (if requested and available) or RPC
+    ///
+    /// # Returns
+    /// Bundle ID (JITO path) or transaction signature (RPC path, once confirmed)
+    ///
+    /// # Errors
+    /// JITO path returns `BundleNotLandedError` if the bundle was accepted but
+    /// did not land within `BUNDLE_LANDING_TIMEOUT_SECS`, and a plain error if
+    /// the block engine rejected the submission. RPC path returns error if the
+    /// transaction failed on-chain or never confirmed
+    async fn submit_transaction(&self, transaction: Transaction, use_jito: bool) -> Result<String> {
+        if let (true, Some(jito_client)) = (use_jito, &self.jito_client) {
+            info!("💎 Submitting via JITO bundle for MEV protection...");
 
-            warn!("⚠️ JITO bundle submission not yet wired up");
-            warn!("   Falling back to regular transaction");
+            let bundle_id = jito_client
+                .submit_bundle_safe(vec![transaction])
+                .await
+                .context("JITO bundle submission rejected")?;
+            info!("📤 Triangle bundle submitted: {}", bundle_id);
 
-            let signature = self.rpc_client.send_transaction(&transaction)?;
-            info!("✅ Triangle transaction sent: {}", signature);
+            jito_client
+                .wait_for_landing(&bundle_id, Duration::from_secs(BUNDLE_LANDING_TIMEOUT_SECS))
+                .await?;
+            info!("✅ Triangle bundle landed: {}", bundle_id);
 
-            Ok(signature.to_string())
+            Ok(bundle_id)
         } else {
             // Regular transaction
             let signature = self.rpc_client.send_transaction(&transaction)?;
-            info!("✅ Triangle transaction sent: {}", signature);
+            info!("📤 Triangle transaction sent: {}", signature);
+
+            // Never report a trade that was only sent - wait for it to confirm
+            if !self.confirm_transaction(&signature).await? {
+                return Err(anyhow::anyhow!(
+                    "Triangle transaction failed or did not confirm: {}",
+                    signature
+                ));
+            }
+            info!("✅ Triangle transaction confirmed: {}", signature);
 
             Ok(signature.to_string())
         }
@@ -322,11 +470,11 @@ impl SwapExecutor {
     /// Build triangle transaction without submitting (for queue-based JITO submission)
     pub async fn build_triangle_transaction<T: Signer>(
         &self,
-        leg1: (&DexType, &str, &SwapParams),
-        leg2: (&DexType, &str, &SwapParams),
-        leg3: (&DexType, &str, &SwapParams),
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
         wallet: &T,
     ) -> Result<Transaction> {
+        let [leg1, leg2, leg3] = legs;
         let user_pubkey = wallet.pubkey();
 
         // Build all three swap instructions (async for pool resolution)
@@ -340,11 +488,19 @@ impl SwapExecutor {
             .build_swap_instruction(leg3.0, leg3.1, leg3.2, &user_pubkey)
             .await?;
 
+        // Wrap the SOL input, unwrap the SOL output
+        let instructions = sol_route_instructions(
+            &user_pubkey,
+            input_mint,
+            leg1.2.amount_in,
+            vec![ix1, ix2, ix3],
+        )?;
+
         // Get recent blockhash
         let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
 
         // Build and return transaction
-        let transaction = self.build_transaction(vec![ix1, ix2, ix3], wallet, recent_blockhash)?;
+        let transaction = self.build_transaction(instructions, wallet, recent_blockhash)?;
 
         Ok(transaction)
     }
@@ -358,9 +514,8 @@ impl SwapExecutor {
     /// transaction that is running the MEV strategy"
     ///
     /// # Arguments
-    /// * `leg1` - First swap parameters
-    /// * `leg2` - Second swap parameters
-    /// * `leg3` - Third swap parameters
+    /// * `input_mint` - Mint the first leg spends (must be native SOL)
+    /// * `legs` - Swap parameters in route order
     /// * `wallet` - User's wallet (signer)
     /// * `tip_lamports` - Tip amount (minimum 1000 lamports)
     /// * `tip_account` - Jito tip account pubkey
@@ -369,57 +524,254 @@ impl SwapExecutor {
     /// Complete signed transaction ready for JITO bundle submission
     pub async fn build_triangle_with_tip<T: Signer>(
         &self,
-        leg1: (&DexType, &str, &SwapParams),
-        leg2: (&DexType, &str, &SwapParams),
-        leg3: (&DexType, &str, &SwapParams),
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
         wallet: &T,
         tip_lamports: u64,
         tip_account: &Pubkey,
     ) -> Result<Transaction> {
-        let user_pubkey = wallet.pubkey();
+        self.build_triangle_with_tip_timed(
+            input_mint,
+            legs,
+            wallet,
+            JitoTip {
+                lamports: tip_lamports,
+                account: *tip_account,
+            },
+            &mut LatencyBreakdown::default(),
+        )
+        .await
+    }
+
+    /// Same as `build_triangle_with_tip`, recording instruction-build and signing time
+    ///
+    /// # Arguments
+    /// * `tip` - Tip transfer included inside the transaction
+    /// * `latency` - Breakdown to fill `instruction_build` and `signing` on
+    ///
+    /// # Returns
+    /// Complete signed transaction ready for JITO bundle submission
+    pub async fn build_triangle_with_tip_timed<T: Signer>(
+        &self,
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
+        wallet: &T,
+        tip: JitoTip,
+        latency: &mut LatencyBreakdown,
+    ) -> Result<Transaction> {
+        let build_start = Instant::now();
+        let all_instructions = self
+            .build_triangle_instructions(input_mint, legs, &wallet.pubkey(), tip)
+            .await?;
+        latency.instruction_build = Some(build_start.elapsed());
 
+        info!("🔒 SECURE: Tip included IN swap transaction (prevents unbundling)");
+
+        // Get recent blockhash
+        let signing_start = Instant::now();
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+
+        // Build transaction with all instructions atomically
+        let transaction = self.build_transaction(all_instructions, wallet, recent_blockhash)?;
+        latency.signing = Some(signing_start.elapsed());
+
+        info!(
+            "✅ Built SECURE transaction: 3 swaps + 1 tip = {} total instructions",
+            transaction.message.instructions.len()
+        );
+
+        Ok(transaction)
+    }
+
+    /// Build triangle transaction with JITO tip as a v0 message using address lookup tables
+    ///
+    /// Legacy transactions top out around 35 accounts; 3 swaps + tip + compute budget
+    /// already bump against that ceiling. ALT-resident accounts cost 1 byte instead of 32.
+    /// Tip stays INSIDE the transaction exactly like `build_triangle_with_tip`.
+    ///
+    /// # Arguments
+    /// * `input_mint` - Mint the first leg spends (must be native SOL)
+    /// * `legs` - Swap parameters in route order
+    /// * `wallet` - User's wallet (signer)
+    /// * `tip` - Tip transfer included inside the transaction
+    /// * `address_lookup_tables` - Fetched ALT accounts to compile against
+    ///
+    /// # Returns
+    /// Complete signed versioned transaction ready for JITO bundle submission
+    pub async fn build_triangle_with_tip_versioned<T: Signer>(
+        &self,
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
+        wallet: &T,
+        tip: JitoTip,
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction> {
+        let all_instructions = self
+            .build_triangle_instructions(input_mint, legs, &wallet.pubkey(), tip)
+            .await?;
+
+        info!("🔒 SECURE: Tip included IN swap transaction (prevents unbundling)");
+
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let transaction = self.build_versioned_transaction(
+            all_instructions,
+            wallet,
+            recent_blockhash,
+            address_lookup_tables,
+        )?;
+
+        info!(
+            "✅ Built SECURE v0 transaction: 3 swaps + 1 tip, {} lookup tables",
+            address_lookup_tables.len()
+        );
+
+        Ok(transaction)
+    }
+
+    /// Build the 3 swap instructions + JITO tip transfer for a triangle
+    ///
+    /// Swaps are wrapped in WSOL setup/teardown (see `sol_route_instructions`);
+    /// the WSOL close precedes the tip so the unwrapped proceeds can pay it.
+    async fn build_triangle_instructions(
+        &self,
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
+        user_pubkey: &Pubkey,
+        tip: JitoTip,
+    ) -> Result<Vec<Instruction>> {
+        let [leg1, leg2, leg3] = legs;
         // Build all three swap instructions (async for pool resolution)
         let ix1 = self
-            .build_swap_instruction(leg1.0, leg1.1, leg1.2, &user_pubkey)
+            .build_swap_instruction(leg1.0, leg1.1, leg1.2, user_pubkey)
             .await?;
         let ix2 = self
-            .build_swap_instruction(leg2.0, leg2.1, leg2.2, &user_pubkey)
+            .build_swap_instruction(leg2.0, leg2.1, leg2.2, user_pubkey)
             .await?;
         let ix3 = self
-            .build_swap_instruction(leg3.0, leg3.1, leg3.2, &user_pubkey)
+            .build_swap_instruction(leg3.0, leg3.1, leg3.2, user_pubkey)
             .await?;
 
         info!("✅ Built all 3 swap instructions");
 
         // Build JITO tip instruction
         let tip_ix =
-            solana_sdk::system_instruction::transfer(&user_pubkey, tip_account, tip_lamports);
+            solana_sdk::system_instruction::transfer(user_pubkey, &tip.account, tip.lamports);
 
         info!(
             "✅ Built JITO tip instruction: {} lamports (0.{:06} SOL) to {}",
-            tip_lamports,
-            tip_lamports / 1000,
-            tip_account
+            tip.lamports,
+            tip.lamports / 1000,
+            tip.account
         );
 
         // SECURITY FIX (2025-10-08): Combine swap instructions + tip
         // Note: build_transaction() will add compute budget instructions automatically
-        let all_instructions = vec![ix1, ix2, ix3, tip_ix];
+        let mut instructions = sol_route_instructions(
+            user_pubkey,
+            input_mint,
+            leg1.2.amount_in,
+            vec![ix1, ix2, ix3],
+        )?;
+        instructions.push(tip_ix);
+        Ok(instructions)
+    }
 
-        info!("🔒 SECURE: Tip included IN swap transaction (prevents unbundling)");
+    /// Build one signed transaction per leg for a multi-transaction JITO bundle
+    ///
+    /// JITO executes a bundle all-or-nothing, so splitting legs keeps atomicity while
+    /// giving each swap its own account and compute budget. WSOL is wrapped in the
+    /// first transaction and closed in the last, ahead of that leg's tip.
+    ///
+    /// Every leg carries an equal share of the tip (remainder on the last), as Jito
+    /// asks of every transaction running the strategy: no leg is a tipless swap that
+    /// could be lifted out of an uncled bundle and landed on its own. The trade-off
+    /// is that the tip is no longer conditional on the whole route: a leg replayed
+    /// alone still pays its share, and each leg spends one extra transfer.
+    ///
+    /// # Arguments
+    /// * `input_mint` - Mint the first leg spends (must be native SOL)
+    /// * `legs` - Swap legs in execution order (2-4)
+    /// * `wallet` - User's wallet (signer)
+    /// * `tip_lamports` - Total tip across all legs (minimum 1000 lamports per leg)
+    /// * `tip_account` - Jito tip account pubkey
+    ///
+    /// # Returns
+    /// Signed transactions sharing one blockhash, ready for a single bundle
+    ///
+    /// # Errors
+    /// Returns error if the leg count is out of range or a leg's tip share would
+    /// fall below Jito's minimum
+    pub async fn build_leg_bundle_with_tip<T: Signer>(
+        &self,
+        input_mint: &Pubkey,
+        legs: &[(&DexType, &str, &SwapParams)],
+        wallet: &T,
+        tip_lamports: u64,
+        tip_account: &Pubkey,
+    ) -> Result<Vec<Transaction>> {
+        if !(MIN_SPLIT_BUNDLE_LEGS..=MAX_SPLIT_BUNDLE_LEGS).contains(&legs.len()) {
+            return Err(anyhow::anyhow!(
+                "split bundle needs {}-{} legs (got {})",
+                MIN_SPLIT_BUNDLE_LEGS,
+                MAX_SPLIT_BUNDLE_LEGS,
+                legs.len()
+            ));
+        }
+        let tip_share = tip_lamports / legs.len() as u64;
+        if tip_share < MIN_TIP_LAMPORTS {
+            return Err(anyhow::anyhow!(
+                "split bundle tip {} lamports leaves {} per leg (minimum {})",
+                tip_lamports,
+                tip_share,
+                MIN_TIP_LAMPORTS
+            ));
+        }
+        require_native_input(input_mint)?;
 
-        // Get recent blockhash
-        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let user_pubkey = wallet.pubkey();
+        let mut per_leg = Vec::with_capacity(legs.len());
+        for (dex_type, pool_short_id, swap_params) in legs {
+            let ix = self
+                .build_swap_instruction(dex_type, pool_short_id, swap_params, &user_pubkey)
+                .await?;
+            per_leg.push(vec![ix]);
+        }
+        if let Some(first) = per_leg.first_mut() {
+            let mut wrap = wrap_sol_instructions(&user_pubkey, legs[0].2.amount_in)?;
+            wrap.append(first);
+            *first = wrap;
+        }
+        if let Some(last) = per_leg.last_mut() {
+            last.push(unwrap_sol_instruction(&user_pubkey)?);
+        }
+        let last_leg = per_leg.len() - 1;
+        for (i, instructions) in per_leg.iter_mut().enumerate() {
+            let share = if i == last_leg {
+                tip_lamports - tip_share * last_leg as u64
+            } else {
+                tip_share
+            };
+            instructions.push(solana_sdk::system_instruction::transfer(
+                &user_pubkey,
+                tip_account,
+                share,
+            ));
+        }
 
-        // Build transaction with all instructions atomically
-        let transaction = self.build_transaction(all_instructions, wallet, recent_blockhash)?;
+        // One blockhash for the whole bundle so the resigner refreshes them together
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let transactions = per_leg
+            .into_iter()
+            .map(|instructions| self.build_transaction(instructions, wallet, recent_blockhash))
+            .collect::<Result<Vec<_>>>()?;
 
         info!(
-            "✅ Built SECURE transaction: 3 swaps + 1 tip = {} total instructions",
-            transaction.message.instructions.len()
+            "✅ Built SECURE {}-transaction bundle: tip split across every leg ({} lamports)",
+            transactions.len(),
+            tip_lamports
         );
 
-        Ok(transaction)
+        Ok(transactions)
     }
 
     /// Build triangle transaction with PROFIT-BASED JITO tip (RECOMMENDED)
@@ -430,9 +782,8 @@ impl SwapExecutor {
     /// - Maximum: 20% of expected profit
     ///
     /// # Arguments
-    /// * `leg1` - First swap parameters
-    /// * `leg2` - Second swap parameters
-    /// * `leg3` - Third swap parameters
+    /// * `input_mint` - Mint the first leg spends (must be native SOL)
+    /// * `legs` - Swap parameters in route order
     /// * `wallet` - User's wallet (signer)
     /// * `expected_profit_lamports` - Expected profit from arbitrage
     /// * `tip_account` - Jito tip account pubkey
@@ -444,18 +795,18 @@ impl SwapExecutor {
     /// ```ignore
     /// // Arbitrage with 0.5 SOL expected profit
     /// let tx = swap_executor.build_triangle_with_profit_based_tip(
-    ///     leg1, leg2, leg3,
+    ///     &spl_token::native_mint::id(),
+    ///     [leg1, leg2, leg3],
     ///     &wallet,
     ///     500_000_000, // 0.5 SOL expected profit
     ///     &tip_account,
     /// ).await?;
-    /// // Tip will be: 50,000,000 lamports (10% of 0.5 SOL = 0.05 SOL)
+    /// // Default TIP_STRATEGY: 50,000,000 lamports (10% of 0.5 SOL beats the floor)
     /// ```
     pub async fn build_triangle_with_profit_based_tip<T: Signer>(
         &self,
-        leg1: (&DexType, &str, &SwapParams),
-        leg2: (&DexType, &str, &SwapParams),
-        leg3: (&DexType, &str, &SwapParams),
+        input_mint: &Pubkey,
+        legs: [(&DexType, &str, &SwapParams); 3],
         wallet: &T,
         expected_profit_lamports: u64,
         tip_account: &Pubkey,
@@ -464,9 +815,8 @@ impl SwapExecutor {
         let tip_lamports = if let Some(jito_client) = &self.jito_client {
             jito_client.calculate_optimal_tip_with_profit(Some(expected_profit_lamports))
         } else {
-            // Fallback if no JITO client: 10% of profit, min 100k lamports
-            let tip = (expected_profit_lamports as f64 * 0.10) as u64;
-            tip.max(100_000)
+            self.tip_strategy
+                .tip_lamports(expected_profit_lamports, None)
         };
 
         info!("💰 Profit-based tip calculation:");
@@ -486,7 +836,7 @@ impl SwapExecutor {
         );
 
         // Build transaction with calculated tip
-        self.build_triangle_with_tip(leg1, leg2, leg3, wallet, tip_lamports, tip_account)
+        self.build_triangle_with_tip(input_mint, legs, wallet, tip_lamports, tip_account)
             .await
     }
 
@@ -497,7 +847,15 @@ impl SwapExecutor {
         pool_short_id: &str,
         swap_params: &SwapParams,
         user_pubkey: &Pubkey,
-    ) -> Result<Instruction> {
+    ) -> ArbResult<Instruction> {
+        // Refuse disabled DEXs before any pool resolution or RPC work
+        if !self.dex_filter.is_enabled(dex_type) {
+            return Err(ArbError::Rejected(format!(
+                "{:?} is disabled (ENABLED_DEXES / DISABLED_DEXES)",
+                dex_type
+            )));
+        }
+
         match dex_type {
             // Meteora variants (all use same builder)
             DexType::MeteoraDammV1 | DexType::MeteoraDammV2 | DexType::MeteoraDlmm => {
@@ -514,11 +872,15 @@ impl SwapExecutor {
                     .await
             }
 
-            // Raydium variants (all use same builder)
-            DexType::RaydiumAmmV4
-            | DexType::RaydiumClmm
-            | DexType::RaydiumCpmm
-            | DexType::RaydiumStable => {
+            // Raydium CLMM needs tick-array accounts for the current price
+            DexType::RaydiumClmm => {
+                self.raydium_builder
+                    .build_clmm_swap_instruction(pool_short_id, swap_params, user_pubkey)
+                    .await
+            }
+
+            // Other Raydium variants (share the AMM/CPMM builder)
+            DexType::RaydiumAmmV4 | DexType::RaydiumCpmm | DexType::RaydiumStable => {
                 self.raydium_builder
                     .build_swap_instruction(pool_short_id, swap_params, user_pubkey)
                     .await
@@ -530,10 +892,9 @@ impl SwapExecutor {
                     .pool_registry
                     .resolve_pool_address(pool_short_id, dex_type)
                     .await
-                    .context(format!(
-                        "Failed to resolve PumpSwap pool address for {}",
-                        pool_short_id
-                    ))?;
+                    .map_err(|e| {
+                        ArbError::PoolResolution(format!("{} (PumpSwap): {}", pool_short_id, e))
+                    })?;
 
                 // Fetch pool info from on-chain data
                 let pool_info = self
@@ -551,6 +912,41 @@ impl SwapExecutor {
                 )
             }
 
+            // Lifinity oracle-based AMM
+            DexType::Lifinity => self
+                .lifinity_builder
+                .build_swap_instruction(pool_short_id, swap_params, user_pubkey)
+                .await
+                .map_err(ArbError::from),
+
+            // Saros constant-product AMM
+            DexType::Saros => self
+                .saros_builder
+                .build_swap_instruction(pool_short_id, swap_params, user_pubkey)
+                .await
+                .map_err(ArbError::from),
+
+            // Crema concentrated liquidity (CLMM)
+            DexType::Crema => self
+                .crema_builder
+                .build_swap_instruction(pool_short_id, swap_params, user_pubkey)
+                .await
+                .map_err(ArbError::from),
+
+            // Aldrin constant-product AMM
+            DexType::Aldrin => self
+                .aldrin_builder
+                .build_swap_instruction(pool_short_id, swap_params, user_pubkey)
+                .await
+                .map_err(ArbError::from),
+
+            // Fluxbeam Token-2022-native constant-product AMM
+            DexType::Fluxbeam => self
+                .fluxbeam_builder
+                .build_swap_instruction(pool_short_id, swap_params, user_pubkey)
+                .await
+                .map_err(ArbError::from),
+
             // HumidiFi dark pool
             DexType::HumidiFi => {
                 debug!(
@@ -569,10 +965,9 @@ impl SwapExecutor {
                     .pool_registry
                     .resolve_pool_address(pool_short_id, dex_type)
                     .await
-                    .context(format!(
-                        "Failed to resolve HumidiFi pool address for {}",
-                        pool_short_id
-                    ))?;
+                    .map_err(|e| {
+                        ArbError::PoolResolution(format!("{} (HumidiFi): {}", pool_short_id, e))
+                    })?;
 
                 // TODO: Fetch actual token mints from pool account data (like Meteora/Orca do)
                 // For now, use common token mints (SOL/USDC) since HumidiFi primarily deals with these pairs
@@ -602,21 +997,13 @@ impl SwapExecutor {
                     .await?;
 
                 // Return first instruction (should be single swap instruction)
-                instructions
-                    .into_iter()
-                    .next()
-                    .ok_or_else(|| anyhow::anyhow!("HumidiFi builder returned no instructions"))
+                instructions.into_iter().next().ok_or_else(|| {
+                    ArbError::Rejected("HumidiFi builder returned no instructions".to_string())
+                })
             }
 
             // Not yet implemented DEXes - gracefully skip
-            DexType::Jupiter
-            | DexType::Serum
-            | DexType::Aldrin
-            | DexType::Saros
-            | DexType::Crema
-            | DexType::Cropper
-            | DexType::Lifinity
-            | DexType::Fluxbeam => {
+            DexType::Jupiter | DexType::Serum | DexType::Cropper => {
                 warn!(
                     "⚠️ DEX {:?} not yet implemented - skipping opportunity on pool {}",
                     dex_type, pool_short_id
@@ -625,7 +1012,10 @@ impl SwapExecutor {
                     "   To enable this DEX, implement builder in src/{}.rs",
                     format!("{:?}", dex_type).to_lowercase()
                 );
-                Err(anyhow::anyhow!("DEX {:?} implementation pending", dex_type))
+                Err(ArbError::Rejected(format!(
+                    "DEX {:?} implementation pending",
+                    dex_type
+                )))
             }
         }
     }
@@ -637,31 +1027,7 @@ impl SwapExecutor {
         wallet: &T,
         recent_blockhash: Hash,
     ) -> Result<Transaction> {
-        let mut instructions = Vec::new();
-
-        // HIGH FIX: Dynamic compute budget based on swap complexity
-        let estimated_cu = match swap_instructions.len() {
-            1 => 100_000, // Single swap
-            2 => 200_000, // 2-leg arbitrage
-            3 => 300_000, // Triangle arbitrage
-            _ => 400_000, // Complex multi-hop
-        };
-
-        // Add 20% safety buffer
-        let compute_limit = (estimated_cu as f64 * 1.2) as u32;
-
-        debug!(
-            "Estimated compute units: {} (with 20% buffer: {})",
-            estimated_cu, compute_limit
-        );
-
-        // Add compute budget instructions first
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
-            self.compute_unit_price,
-        ));
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
-            compute_limit,
-        ));
+        let mut instructions = self.compute_budget_instructions(&swap_instructions);
 
         // Add swap instructions
         instructions.extend(swap_instructions);
@@ -680,12 +1046,248 @@ impl SwapExecutor {
         Ok(transaction)
     }
 
+    /// Build and sign a v0 transaction, compiling accounts against `address_lookup_tables`
+    fn build_versioned_transaction<T: Signer>(
+        &self,
+        swap_instructions: Vec<Instruction>,
+        wallet: &T,
+        recent_blockhash: Hash,
+        address_lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction> {
+        let mut instructions = self.compute_budget_instructions(&swap_instructions);
+        instructions.extend(swap_instructions);
+
+        let message = v0::Message::try_compile(
+            &wallet.pubkey(),
+            &instructions,
+            address_lookup_tables,
+            recent_blockhash,
+        )
+        .context("Failed to compile v0 message")?;
+
+        let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), &[wallet])
+            .context("Failed to sign versioned transaction")?;
+
+        debug!(
+            "✅ Built v0 transaction with {} instructions",
+            instructions.len()
+        );
+
+        Ok(transaction)
+    }
+
+    /// Compute budget instructions (price + limit) sized for `swap_instructions`
+    fn compute_budget_instructions(&self, swap_instructions: &[Instruction]) -> Vec<Instruction> {
+        let compute_limit = self.estimate_compute_unit_limit(swap_instructions);
+        let compute_unit_price = self.compute_unit_price_for(swap_instructions);
+
+        // Compute budget instructions go first
+        self.cached_compute_budget(compute_unit_price, compute_limit)
+            .to_vec()
+    }
+
+    /// Compute budget pair for (price, limit), built once and then served from cache
+    ///
+    /// The instructions are deterministic given price and limit, so rebuilding
+    /// them for every transaction is wasted work on the hot path.
+    fn cached_compute_budget(
+        &self,
+        compute_unit_price: u64,
+        compute_limit: u32,
+    ) -> [Instruction; 2] {
+        let key = (compute_unit_price, compute_limit);
+        if let Some(cached) = self.compute_budget_cache.get(&key) {
+            return cached.clone();
+        }
+
+        let pair = build_compute_budget(compute_unit_price, compute_limit);
+        if self.compute_budget_cache.len() >= MAX_CACHED_COMPUTE_BUDGETS {
+            self.compute_budget_cache.clear();
+        }
+        self.compute_budget_cache.insert(key, pair.clone());
+        pair
+    }
+
+    /// Pre-build compute budget instructions for common (price, limit) pairs
+    ///
+    /// Call at startup so the first trades don't pay for building them
+    /// (COMPUTE_BUDGET_WARM_PAIRS).
+    ///
+    /// # Arguments
+    /// * `pairs` - (micro-lamports per CU, compute unit limit); empty warms the
+    ///   executor's default price and limit
+    ///
+    /// # Returns
+    /// Number of pairs now cached
+    pub fn warm_compute_budget_cache(&self, pairs: &[(u64, u32)]) -> usize {
+        let defaults = [(self.compute_unit_price, self.compute_unit_limit)];
+        let pairs = if pairs.is_empty() {
+            &defaults[..]
+        } else {
+            pairs
+        };
+        for &(price, limit) in pairs {
+            self.cached_compute_budget(price, limit);
+        }
+        debug!("Warmed {} compute budget pair(s): {:?}", pairs.len(), pairs);
+        self.compute_budget_cache.len()
+    }
+
+    /// Compute unit limit for a transaction carrying `instructions`
+    ///
+    /// HIGH FIX: Sums each leg's DEX estimate (DLMM bin walks and CLMM tick
+    /// crossings cost far more than a Raydium V4 swap) plus a 20% buffer,
+    /// instead of a flat figure per instruction count.
+    ///
+    /// # Returns
+    /// Buffered estimate, capped at the 1.4M CU transaction ceiling
+    fn estimate_compute_unit_limit(&self, instructions: &[Instruction]) -> u32 {
+        let estimated_cu = instructions
+            .iter()
+            .map(|ix| {
+                if ix.program_id == solana_sdk::system_program::id() {
+                    TRANSFER_COMPUTE_UNITS
+                } else if ix.program_id == spl_associated_token_account::id() {
+                    ATA_CREATE_COMPUTE_UNITS
+                } else if ix.program_id == spl_token::id() {
+                    TOKEN_INSTRUCTION_COMPUTE_UNITS
+                } else {
+                    dex_type_for_program(&ix.program_id)
+                        .map_or(DEFAULT_INSTRUCTION_COMPUTE_UNITS, |dex_type| {
+                            self.leg_compute_units(&dex_type)
+                        })
+                }
+            })
+            .sum::<u32>()
+            .max(DEFAULT_INSTRUCTION_COMPUTE_UNITS);
+
+        let compute_limit = (estimated_cu.saturating_mul(100 + COMPUTE_UNIT_BUFFER_PCT) / 100)
+            .min(MAX_TRANSACTION_COMPUTE_UNITS);
+
+        debug!(
+            "Estimated compute units: {} (with {}% buffer: {})",
+            estimated_cu, COMPUTE_UNIT_BUFFER_PCT, compute_limit
+        );
+
+        compute_limit
+    }
+
+    /// Estimated compute units for one swap on `dex_type` (config override first)
+    fn leg_compute_units(&self, dex_type: &DexType) -> u32 {
+        if let Some(units) = self.compute_unit_overrides.get(dex_type) {
+            return *units;
+        }
+
+        match dex_type {
+            DexType::MeteoraDammV1 | DexType::MeteoraDammV2 | DexType::MeteoraDlmm => {
+                self.meteora_builder.estimated_compute_units(dex_type)
+            }
+            DexType::OrcaWhirlpools | DexType::OrcaLegacy => {
+                self.orca_builder.estimated_compute_units(dex_type)
+            }
+            DexType::RaydiumAmmV4
+            | DexType::RaydiumClmm
+            | DexType::RaydiumCpmm
+            | DexType::RaydiumStable => self.raydium_builder.estimated_compute_units(dex_type),
+            DexType::PumpSwap => self.pumpswap_builder.estimated_compute_units(),
+            DexType::Lifinity => self.lifinity_builder.estimated_compute_units(),
+            DexType::Saros => self.saros_builder.estimated_compute_units(),
+            DexType::Crema => self.crema_builder.estimated_compute_units(),
+            DexType::Aldrin => self.aldrin_builder.estimated_compute_units(),
+            DexType::Fluxbeam => self.fluxbeam_builder.estimated_compute_units(),
+            DexType::HumidiFi => self
+                .humidifi_builder
+                .as_ref()
+                .map_or(DEFAULT_INSTRUCTION_COMPUTE_UNITS, |builder| {
+                    builder.estimated_compute_units()
+                }),
+            _ => DEFAULT_INSTRUCTION_COMPUTE_UNITS,
+        }
+    }
+
+    /// Replace per-DEX compute unit estimates (DEX_COMPUTE_UNITS)
+    pub fn set_compute_unit_overrides(&mut self, overrides: HashMap<DexType, u32>) {
+        debug!("Set {} per-DEX compute unit overrides", overrides.len());
+        self.compute_unit_overrides = overrides;
+    }
+
     /// Set compute unit price (micro-lamports per compute unit)
     pub fn set_compute_unit_price(&mut self, price: u64) {
         self.compute_unit_price = price;
         debug!("Set compute unit price: {} micro-lamports", price);
     }
 
+    /// Set min/max compute unit price used by congestion scaling
+    pub fn set_compute_unit_price_bounds(&mut self, min: u64, max: u64) {
+        self.compute_unit_price_min = min;
+        self.compute_unit_price_max = max.max(min);
+        debug!(
+            "Set compute unit price bounds: {}-{} micro-lamports",
+            self.compute_unit_price_min, self.compute_unit_price_max
+        );
+    }
+
+    /// Scale compute unit price with JITO tip floor congestion
+    ///
+    /// Full blocks deprioritize low-fee transactions even inside a bundle,
+    /// so the priority fee follows the same congestion signal as our tips.
+    pub fn set_compute_unit_price_from_congestion(&mut self, tip_floor: &JitoTipFloor) {
+        let price = compute_unit_price_for_congestion(
+            tip_floor,
+            self.compute_unit_price_min,
+            self.compute_unit_price_max,
+        );
+        if price != self.compute_unit_price {
+            self.set_compute_unit_price(price);
+        }
+    }
+
+    /// Price transactions from recent prioritization fees on the accounts they write
+    ///
+    /// Falls back to the congestion-scaled price when the RPC lookup fails.
+    pub fn set_priority_fee_from_accounts(&mut self, enabled: bool) {
+        self.priority_fee_from_accounts = enabled;
+        debug!("Per-account priority fees: {}", enabled);
+    }
+
+    /// Set the tip policy used when no JITO client is attached
+    pub fn set_tip_strategy(&mut self, tip_strategy: TipStrategy) {
+        debug!("Tip strategy: {}", tip_strategy);
+        self.tip_strategy = tip_strategy;
+    }
+
+    /// Restrict swap building to enabled DEXs (ENABLED_DEXES / DISABLED_DEXES)
+    pub fn set_dex_filter(&mut self, dex_filter: DexFilter) {
+        debug!("Enabled DEXs: {}", dex_filter);
+        self.dex_filter = dex_filter;
+    }
+
+    /// Compute unit price for a transaction carrying `instructions`
+    ///
+    /// Uses recent fees on the swap legs' writable accounts (clamped to the
+    /// COMPUTE_UNIT_PRICE_MIN/MAX bounds), so contended pools get a higher price
+    /// than quiet ones. Otherwise the congestion-scaled default.
+    fn compute_unit_price_for(&self, instructions: &[Instruction]) -> u64 {
+        if !self.priority_fee_from_accounts {
+            return self.compute_unit_price;
+        }
+        let accounts = contended_accounts(instructions);
+        if accounts.is_empty() {
+            return self.compute_unit_price;
+        }
+
+        match self.rpc_client.get_prioritization_fee(&accounts) {
+            Ok(fee) => fee.clamp(self.compute_unit_price_min, self.compute_unit_price_max),
+            Err(e) => {
+                debug!(
+                    "Priority fee lookup failed, using {} micro-lamports: {}",
+                    self.compute_unit_price, e
+                );
+                self.compute_unit_price
+            }
+        }
+    }
+
     /// Set compute unit limit
     pub fn set_compute_unit_limit(&mut self, limit: u32) {
         self.compute_unit_limit = limit;
@@ -693,12 +1295,73 @@ impl SwapExecutor {
     }
 
     /// Estimate swap output (for slippage calculation)
+    ///
+    /// Token-2022 transfer fees are withheld both when the input reaches the pool
+    /// vault and when the output reaches our account, so they are deducted on
+    /// either side of the pool's own curve estimate.
     pub fn estimate_swap_output(
         &self,
         dex_type: &DexType,
         pool_short_id: &str,
         amount_in: u64,
         swap_a_to_b: bool,
+    ) -> Result<u64> {
+        let (mint_in, mint_out) = self.leg_mint_token_programs(pool_short_id, swap_a_to_b);
+
+        let pool_amount_in = mint_in.amount_after_transfer_fee(amount_in);
+        let pool_amount_out =
+            self.estimate_pool_output(dex_type, pool_short_id, pool_amount_in, swap_a_to_b)?;
+        let amount_out = mint_out.amount_after_transfer_fee(pool_amount_out);
+
+        if pool_amount_in != amount_in || amount_out != pool_amount_out {
+            debug!(
+                "🪙 Token-2022 transfer fees on {}: in {} → {}, out {} → {}",
+                pool_short_id, amount_in, pool_amount_in, pool_amount_out, amount_out
+            );
+        }
+        Ok(amount_out)
+    }
+
+    /// Token programs of a leg's input and output mints
+    ///
+    /// Falls back to classic SPL Token (no transfer fee) for unknown pools,
+    /// native SOL, or mints that cannot be fetched.
+    fn leg_mint_token_programs(
+        &self,
+        pool_short_id: &str,
+        swap_a_to_b: bool,
+    ) -> (MintTokenProgram, MintTokenProgram) {
+        let Some(pool) = self.pool_registry.get_pool(pool_short_id) else {
+            return (MintTokenProgram::SPL, MintTokenProgram::SPL);
+        };
+
+        let lookup = |mint: &Pubkey| {
+            if *mint == solana_sdk::system_program::ID {
+                return MintTokenProgram::SPL;
+            }
+            self.rpc_client
+                .get_mint_token_program(mint)
+                .unwrap_or_else(|e| {
+                    debug!("Token program lookup failed for {}: {}", mint, e);
+                    MintTokenProgram::SPL
+                })
+        };
+
+        let (mint_in, mint_out) = if swap_a_to_b {
+            (&pool.token_a_mint, &pool.token_b_mint)
+        } else {
+            (&pool.token_b_mint, &pool.token_a_mint)
+        };
+        (lookup(mint_in), lookup(mint_out))
+    }
+
+    /// Pool curve output for an amount that has already reached the pool
+    fn estimate_pool_output(
+        &self,
+        dex_type: &DexType,
+        pool_short_id: &str,
+        amount_in: u64,
+        swap_a_to_b: bool,
     ) -> Result<u64> {
         match dex_type {
             // Meteora variants (all use same builder)
@@ -707,11 +1370,9 @@ impl SwapExecutor {
                 .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b),
 
             // Orca variants
-            DexType::OrcaWhirlpools | DexType::OrcaLegacy => {
-                // Conservative estimate for Orca (1% slippage)
-                warn!("⚠️ Orca output estimation not yet implemented - using 1% slippage estimate");
-                Ok(amount_in * 99 / 100)
-            }
+            DexType::OrcaWhirlpools | DexType::OrcaLegacy => self
+                .orca_builder
+                .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b),
 
             // Raydium variants (all use same builder)
             DexType::RaydiumAmmV4
@@ -723,9 +1384,45 @@ impl SwapExecutor {
             }
 
             DexType::PumpSwap => {
-                // Conservative estimate for PumpSwap (1% slippage)
-                warn!("⚠️ PumpSwap output estimation not yet implemented - using 1% slippage estimate");
-                Ok(amount_in * 99 / 100)
+                let pool_address = self
+                    .pool_registry
+                    .get_pool(pool_short_id)
+                    .map(|pool| pool.full_address)
+                    .ok_or_else(|| anyhow::anyhow!("Pool {} not found", pool_short_id))?;
+
+                // Read curve reserves from on-chain vaults
+                let pool_info = self
+                    .pumpswap_builder
+                    .fetch_pool_info(&pool_address)
+                    .context("Failed to fetch PumpSwap pool info")?;
+
+                self.pumpswap_builder
+                    .estimate_swap_output(&pool_info, amount_in, swap_a_to_b)
+            }
+
+            DexType::Lifinity => {
+                self.lifinity_builder
+                    .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b)
+            }
+
+            DexType::Saros => {
+                self.saros_builder
+                    .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b)
+            }
+
+            DexType::Crema => {
+                self.crema_builder
+                    .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b)
+            }
+
+            DexType::Aldrin => {
+                self.aldrin_builder
+                    .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b)
+            }
+
+            DexType::Fluxbeam => {
+                self.fluxbeam_builder
+                    .estimate_swap_output(pool_short_id, amount_in, swap_a_to_b)
             }
 
             DexType::HumidiFi => {
@@ -735,14 +1432,7 @@ impl SwapExecutor {
             }
 
             // Not yet implemented DEXes - conservative estimate
-            DexType::Jupiter
-            | DexType::Serum
-            | DexType::Aldrin
-            | DexType::Saros
-            | DexType::Crema
-            | DexType::Cropper
-            | DexType::Lifinity
-            | DexType::Fluxbeam => {
+            DexType::Jupiter | DexType::Serum | DexType::Cropper => {
                 warn!(
                     "⚠️ DEX {:?} output estimation not implemented - using 1% slippage estimate",
                     dex_type
@@ -752,6 +1442,35 @@ impl SwapExecutor {
         }
     }
 
+    /// SOL-side reserve of a pool, for sizing positions against liquidity depth
+    ///
+    /// Supported for constant-product pools whose vault balances are the real
+    /// depth (PumpSwap, Lifinity, Saros, Aldrin, Fluxbeam). Concentrated-liquidity and not yet
+    /// parsed pool types return an error - callers treat depth as unknown.
+    ///
+    /// # Arguments
+    /// * `dex_type` - DEX the pool belongs to
+    /// * `pool_address` - Full pool address
+    ///
+    /// # Returns
+    /// WSOL reserve in lamports
+    pub fn pool_sol_reserve(&self, dex_type: &DexType, pool_address: &Pubkey) -> Result<u64> {
+        match dex_type {
+            DexType::PumpSwap => self
+                .pumpswap_builder
+                .fetch_pool_info(pool_address)
+                .map(|pool| pool.quote_reserve),
+            DexType::Lifinity => self.lifinity_builder.sol_reserve(pool_address),
+            DexType::Saros => self.saros_builder.sol_reserve(pool_address),
+            DexType::Aldrin => self.aldrin_builder.sol_reserve(pool_address),
+            DexType::Fluxbeam => self.fluxbeam_builder.sol_reserve(pool_address),
+            _ => Err(anyhow::anyhow!(
+                "Pool depth not available for {:?}",
+                dex_type
+            )),
+        }
+    }
+
     /// Calculate recommended minimum output with slippage tolerance
     ///
     /// # Arguments
@@ -816,9 +1535,147 @@ impl SwapExecutor {
     }
 }
 
+/// Map JITO tip floor congestion to a compute unit price in [min, max]
+///
+/// Uses the EMA p50 landed tip (smoothed, less spiky than p95/p99) on a log scale,
+/// since tips span several orders of magnitude between calm and congested markets.
+pub fn compute_unit_price_for_congestion(tip_floor: &JitoTipFloor, min: u64, max: u64) -> u64 {
+    let ema_p50_lamports = (tip_floor.ema_p50 * 1_000_000_000.0).max(1.0);
+    let congestion = ((ema_p50_lamports.log10() - CALM_TIP_FLOOR_LAMPORTS.log10())
+        / (CONGESTED_TIP_FLOOR_LAMPORTS.log10() - CALM_TIP_FLOOR_LAMPORTS.log10()))
+    .clamp(0.0, 1.0);
+
+    let max = max.max(min);
+    min + ((max - min) as f64 * congestion).round() as u64
+}
+
+/// Wrap `lamports` of SOL into the wallet's WSOL ATA
+///
+/// Creates the ATA if missing (idempotent, so an existing WSOL account is reused),
+/// transfers the lamports in and syncs the token balance with them.
+fn wrap_sol_instructions(user_pubkey: &Pubkey, lamports: u64) -> Result<Vec<Instruction>> {
+    let wsol_account = spl_associated_token_account::get_associated_token_address(
+        user_pubkey,
+        &spl_token::native_mint::id(),
+    );
+    Ok(vec![
+        spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            user_pubkey,
+            user_pubkey,
+            &spl_token::native_mint::id(),
+            &spl_token::id(),
+        ),
+        solana_sdk::system_instruction::transfer(user_pubkey, &wsol_account, lamports),
+        spl_token::instruction::sync_native(&spl_token::id(), &wsol_account)
+            .context("Failed to build WSOL sync_native instruction")?,
+    ])
+}
+
+/// Close the wallet's WSOL ATA, returning its balance and rent as native SOL
+fn unwrap_sol_instruction(user_pubkey: &Pubkey) -> Result<Instruction> {
+    let wsol_account = spl_associated_token_account::get_associated_token_address(
+        user_pubkey,
+        &spl_token::native_mint::id(),
+    );
+    spl_token::instruction::close_account(
+        &spl_token::id(),
+        &wsol_account,
+        user_pubkey,
+        user_pubkey,
+        &[],
+    )
+    .context("Failed to build WSOL close_account instruction")
+}
+
+/// Only routes spending native SOL can be wrapped into WSOL
+fn require_native_input(input_mint: &Pubkey) -> Result<()> {
+    if *input_mint != spl_token::native_mint::id() {
+        return Err(anyhow::anyhow!(
+            "route starts from {} - only native SOL input is wrapped",
+            input_mint
+        ));
+    }
+    Ok(())
+}
+
+/// Surround a SOL → ... → SOL route's swaps with WSOL setup and teardown
+///
+/// DEX programs only move SPL tokens, so the first leg spends WSOL and the last
+/// leg pays out WSOL. Without the close, proceeds stay wrapped and the ATA rent
+/// is never reclaimed.
+///
+/// # Arguments
+/// * `input_mint` - Mint the first leg spends
+/// * `sol_in_lamports` - First leg's input amount to wrap
+/// * `swap_instructions` - Swap instructions in route order
+///
+/// # Returns
+/// Create ATA, fund, sync_native, swaps..., close_account
+///
+/// # Errors
+/// Returns error if the route doesn't start from native SOL: wrapping would
+/// fund WSOL the first leg never spends
+fn sol_route_instructions(
+    user_pubkey: &Pubkey,
+    input_mint: &Pubkey,
+    sol_in_lamports: u64,
+    swap_instructions: Vec<Instruction>,
+) -> Result<Vec<Instruction>> {
+    require_native_input(input_mint)?;
+    let mut instructions = wrap_sol_instructions(user_pubkey, sol_in_lamports)?;
+    instructions.extend(swap_instructions);
+    instructions.push(unwrap_sol_instruction(user_pubkey)?);
+    Ok(instructions)
+}
+
+/// Writable, non-signer accounts of the swap instructions (pools, vaults, tick arrays)
+///
+/// These are what other transactions contend with; tips, WSOL setup and the
+/// wallet itself are left out.
+fn contended_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = instructions
+        .iter()
+        .filter(|ix| dex_type_for_program(&ix.program_id).is_some())
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|meta| meta.is_writable && !meta.is_signer)
+        .map(|meta| meta.pubkey)
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+    accounts
+}
+
+/// DEX whose program an instruction calls, for per-leg compute unit estimates
+///
+/// # Returns
+/// `None` for programs no swap builder targets (tip transfers, ATA creation, ...)
+fn dex_type_for_program(program_id: &Pubkey) -> Option<DexType> {
+    let dex_type = match program_id.to_string().as_str() {
+        MeteoraSwapBuilder::PROGRAM_ID => DexType::MeteoraDlmm,
+        MeteoraSwapBuilder::DAMM_V1_PROGRAM_ID => DexType::MeteoraDammV1,
+        MeteoraSwapBuilder::DAMM_V2_PROGRAM_ID => DexType::MeteoraDammV2,
+        OrcaSwapBuilder::WHIRLPOOLS_PROGRAM_ID => DexType::OrcaWhirlpools,
+        OrcaSwapBuilder::LEGACY_PROGRAM_ID => DexType::OrcaLegacy,
+        RaydiumSwapBuilder::AMM_V4_PROGRAM_ID => DexType::RaydiumAmmV4,
+        RaydiumSwapBuilder::CLMM_PROGRAM_ID => DexType::RaydiumClmm,
+        RaydiumSwapBuilder::CPMM_PROGRAM_ID => DexType::RaydiumCpmm,
+        RaydiumSwapBuilder::STABLE_PROGRAM_ID => DexType::RaydiumStable,
+        crate::pumpswap::PUMPSWAP_PROGRAM_ID => DexType::PumpSwap,
+        LifinitySwapBuilder::PROGRAM_ID => DexType::Lifinity,
+        SarosSwapBuilder::PROGRAM_ID => DexType::Saros,
+        CremaSwapBuilder::PROGRAM_ID => DexType::Crema,
+        AldrinSwapBuilder::PROGRAM_ID => DexType::Aldrin,
+        FluxbeamSwapBuilder::PROGRAM_ID => DexType::Fluxbeam,
+        crate::humidifi::HUMIDIFI_PROGRAM_ID => DexType::HumidiFi,
+        _ => return None,
+    };
+    Some(dex_type)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rpc_client::SolanaRpcClient;
 
     #[test]
     fn test_min_output_calculation() {
@@ -858,4 +1715,477 @@ mod tests {
         assert_eq!(executor.compute_unit_price, 1000);
         assert_eq!(executor.compute_unit_limit, 200_000);
     }
+
+    #[test]
+    fn test_compute_budget_cache_reuses_rebuilt_pairs() {
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let executor = SwapExecutor::new(rpc_client, pool_registry, None).unwrap();
+
+        // Empty list warms the default pair; explicit pairs are all cached
+        assert_eq!(executor.warm_compute_budget_cache(&[]), 1);
+        assert_eq!(
+            executor.warm_compute_budget_cache(&[(1_000, 240_000), (5_000, 480_000)]),
+            3
+        );
+
+        // Cached pairs are identical to freshly built ones
+        for (price, limit) in [(1_000, 200_000), (1_000, 240_000), (5_000, 480_000)] {
+            assert_eq!(
+                executor.cached_compute_budget(price, limit),
+                build_compute_budget(price, limit)
+            );
+        }
+
+        // Repeat lookups are served from the cache, not rebuilt: a planted
+        // entry comes back as-is and no new entries appear
+        let planted = build_compute_budget(7, 7);
+        executor
+            .compute_budget_cache
+            .insert((5_000, 480_000), planted.clone());
+        for _ in 0..3 {
+            assert_eq!(executor.cached_compute_budget(5_000, 480_000), planted);
+        }
+        assert_eq!(executor.compute_budget_cache.len(), 3);
+
+        // A new pair is built once, then reused
+        let fresh = executor.cached_compute_budget(9_000, 300_000);
+        assert_eq!(fresh, build_compute_budget(9_000, 300_000));
+        assert_eq!(executor.compute_budget_cache.len(), 4);
+        assert_eq!(executor.cached_compute_budget(9_000, 300_000), fresh);
+        assert_eq!(executor.compute_budget_cache.len(), 4);
+    }
+
+    #[test]
+    #[ignore = "timing benchmark: cargo test --release -- --ignored compute_budget_cache_lookup"]
+    fn test_compute_budget_cache_lookup_stays_fast() {
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let executor = SwapExecutor::new(rpc_client, pool_registry, None).unwrap();
+        executor.warm_compute_budget_cache(&[(5_000, 480_000)]);
+
+        // Cached lookup vs rebuilding the pair every time
+        const ITERATIONS: u32 = 100_000;
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            std::hint::black_box(build_compute_budget(std::hint::black_box(5_000), 480_000));
+        }
+        let rebuilt = start.elapsed() / ITERATIONS;
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            std::hint::black_box(
+                executor.cached_compute_budget(std::hint::black_box(5_000), 480_000),
+            );
+        }
+        let cached = start.elapsed() / ITERATIONS;
+        println!(
+            "compute budget per call: rebuilt {:?}, cached {:?}",
+            rebuilt, cached
+        );
+
+        // Every transaction build takes this path: keep it within a few
+        // microseconds and without growing the cache
+        assert!(
+            cached < Duration::from_micros(5),
+            "cached compute budget lookup took {:?} per call",
+            cached
+        );
+        assert_eq!(executor.compute_budget_cache.len(), 1);
+    }
+
+    /// Minimal JSON-RPC server standing in for the JITO block engine
+    ///
+    /// Answers `sendBundle` with a fixed bundle ID and `getInflightBundleStatuses`
+    /// with a Landed status, recording every method it receives
+    fn spawn_mock_jito() -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let methods = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = methods.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => break,
+                };
+
+                // Read headers + body (Content-Length delimited)
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = stream.read(&mut buf).unwrap_or(0);
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let content_length = text[..header_end]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= header_end + 4 + content_length {
+                            break;
+                        }
+                    }
+                }
+
+                let text = String::from_utf8_lossy(&request).to_string();
+                let body = if text.contains("sendBundle") {
+                    seen.lock().unwrap().push("sendBundle".to_string());
+                    r#"{"jsonrpc":"2.0","id":1,"result":"mock-bundle-id"}"#.to_string()
+                } else {
+                    seen.lock()
+                        .unwrap()
+                        .push("getInflightBundleStatuses".to_string());
+                    r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":[{"bundle_id":"mock-bundle-id","status":"Landed","landed_slot":42}]}}"#.to_string()
+                };
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (url, methods)
+    }
+
+    #[test]
+    fn test_compute_unit_price_scales_with_congestion() {
+        let tip_floor = |ema_p50_sol: f64| JitoTipFloor {
+            ema_p50: ema_p50_sol,
+            ..Default::default()
+        };
+
+        // Low congestion: at/below calm tip floor → min price
+        assert_eq!(
+            compute_unit_price_for_congestion(&tip_floor(0.000001), 1000, 100_000),
+            1000
+        );
+        assert_eq!(
+            compute_unit_price_for_congestion(&tip_floor(0.0), 1000, 100_000),
+            1000
+        );
+
+        // Medium congestion: ~31.6k lamports is halfway on the log scale
+        let medium = compute_unit_price_for_congestion(&tip_floor(0.0000316), 1000, 100_000);
+        assert!((45_000..=55_000).contains(&medium), "medium = {}", medium);
+
+        // High congestion: at/above congested tip floor → max price
+        assert_eq!(
+            compute_unit_price_for_congestion(&tip_floor(0.001), 1000, 100_000),
+            100_000
+        );
+        assert_eq!(
+            compute_unit_price_for_congestion(&tip_floor(0.05), 1000, 100_000),
+            100_000
+        );
+    }
+
+    #[test]
+    fn test_versioned_triangle_with_alt_fits_packet() {
+        use solana_sdk::instruction::AccountMeta;
+        use solana_sdk::packet::PACKET_DATA_SIZE;
+        use solana_sdk::signature::Keypair;
+
+        let wallet = Keypair::new();
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let executor = SwapExecutor::new(rpc_client, pool_registry, None).unwrap();
+
+        // 3 swaps with 12 pool-specific accounts each (pool, vaults, tick arrays, ...)
+        let pool_accounts: Vec<Vec<Pubkey>> = (0..3)
+            .map(|_| (0..12).map(|_| Pubkey::new_unique()).collect())
+            .collect();
+        let token_program = Pubkey::new_unique();
+        let mut instructions: Vec<Instruction> = pool_accounts
+            .iter()
+            .map(|accounts| {
+                let mut metas = vec![
+                    AccountMeta::new_readonly(wallet.pubkey(), true),
+                    AccountMeta::new_readonly(token_program, false),
+                ];
+                metas.extend(accounts.iter().map(|a| AccountMeta::new(*a, false)));
+                Instruction::new_with_bytes(Pubkey::new_unique(), &[0u8; 24], metas)
+            })
+            .collect();
+        instructions.push(solana_sdk::system_instruction::transfer(
+            &wallet.pubkey(),
+            &Pubkey::new_unique(),
+            100_000,
+        ));
+
+        // Legacy encoding blows past the packet limit
+        let legacy = executor
+            .build_transaction(instructions.clone(), &wallet, Hash::default())
+            .unwrap();
+        assert!(bincode::serialize(&legacy).unwrap().len() > PACKET_DATA_SIZE);
+
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: pool_accounts.into_iter().flatten().collect(),
+        };
+        let versioned = executor
+            .build_versioned_transaction(instructions, &wallet, Hash::default(), &[lookup_table])
+            .unwrap();
+
+        assert!(bincode::serialize(&versioned).unwrap().len() <= PACKET_DATA_SIZE);
+        assert!(versioned.verify_with_results().iter().all(|ok| *ok));
+    }
+
+    #[test]
+    fn test_dlmm_legs_request_more_compute_than_raydium() {
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let mut executor = SwapExecutor::new(rpc_client, pool_registry, None).unwrap();
+
+        let swap = |program_id: &str| {
+            Instruction::new_with_bytes(program_id.parse().unwrap(), &[0u8; 24], vec![])
+        };
+        let tip = solana_sdk::system_instruction::transfer(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            100_000,
+        );
+
+        let dlmm_triangle = vec![
+            swap(MeteoraSwapBuilder::PROGRAM_ID),
+            swap(MeteoraSwapBuilder::PROGRAM_ID),
+            swap(RaydiumSwapBuilder::AMM_V4_PROGRAM_ID),
+            tip.clone(),
+        ];
+        let raydium_triangle = vec![
+            swap(RaydiumSwapBuilder::AMM_V4_PROGRAM_ID),
+            swap(RaydiumSwapBuilder::AMM_V4_PROGRAM_ID),
+            swap(RaydiumSwapBuilder::AMM_V4_PROGRAM_ID),
+            tip,
+        ];
+
+        let dlmm_limit = executor.estimate_compute_unit_limit(&dlmm_triangle);
+        let raydium_limit = executor.estimate_compute_unit_limit(&raydium_triangle);
+        assert!(dlmm_limit > raydium_limit);
+
+        // Limit lands in the compute budget instruction (discriminator 2 + u32 LE)
+        let budget = executor.compute_budget_instructions(&dlmm_triangle);
+        assert_eq!(budget[1].data[0], 2);
+        assert_eq!(
+            u32::from_le_bytes(budget[1].data[1..5].try_into().unwrap()),
+            dlmm_limit
+        );
+
+        // Config override replaces the builder's estimate
+        executor.set_compute_unit_overrides(HashMap::from([(DexType::RaydiumAmmV4, 400_000)]));
+        assert!(executor.estimate_compute_unit_limit(&raydium_triangle) > dlmm_limit);
+        assert_eq!(
+            executor.estimate_compute_unit_limit(&raydium_triangle),
+            MAX_TRANSACTION_COMPUTE_UNITS
+        );
+    }
+
+    #[test]
+    fn test_sol_triangle_wraps_before_and_closes_after_swaps() {
+        use solana_sdk::signature::Keypair;
+
+        let wallet = Keypair::new();
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let executor = SwapExecutor::new(rpc_client, pool_registry, None).unwrap();
+
+        // SOL → TokenA → TokenB → SOL, then the tip (as build_triangle_instructions)
+        let swaps: Vec<Instruction> = [
+            MeteoraSwapBuilder::PROGRAM_ID,
+            RaydiumSwapBuilder::AMM_V4_PROGRAM_ID,
+            OrcaSwapBuilder::WHIRLPOOLS_PROGRAM_ID,
+        ]
+        .iter()
+        .map(|program_id| {
+            Instruction::new_with_bytes(program_id.parse().unwrap(), &[0u8; 24], vec![])
+        })
+        .collect();
+        let mut instructions = sol_route_instructions(
+            &wallet.pubkey(),
+            &spl_token::native_mint::id(),
+            500_000_000,
+            swaps.clone(),
+        )
+        .unwrap();
+        instructions.push(solana_sdk::system_instruction::transfer(
+            &wallet.pubkey(),
+            &Pubkey::new_unique(),
+            100_000,
+        ));
+
+        let transaction = executor
+            .build_transaction(instructions, &wallet, Hash::default())
+            .unwrap();
+        let message = &transaction.message;
+        let programs: Vec<Pubkey> = message
+            .instructions
+            .iter()
+            .map(|ix| *ix.program_id(&message.account_keys))
+            .collect();
+
+        let compute_budget = solana_sdk::compute_budget::id();
+        let system = solana_sdk::system_program::id();
+        assert_eq!(
+            programs,
+            vec![
+                compute_budget,
+                compute_budget,
+                spl_associated_token_account::id(),
+                system,          // fund WSOL
+                spl_token::id(), // sync_native
+                swaps[0].program_id,
+                swaps[1].program_id,
+                swaps[2].program_id,
+                spl_token::id(), // close_account after the final leg
+                system,          // tip
+            ]
+        );
+
+        // sync_native = 17, close_account = 9; close pays the wallet back
+        assert_eq!(message.instructions[4].data, vec![17]);
+        assert_eq!(message.instructions[8].data, vec![9]);
+        let wsol_account = spl_associated_token_account::get_associated_token_address(
+            &wallet.pubkey(),
+            &spl_token::native_mint::id(),
+        );
+        let close_accounts: Vec<Pubkey> = message.instructions[8]
+            .accounts
+            .iter()
+            .map(|index| message.account_keys[*index as usize])
+            .collect();
+        assert_eq!(
+            close_accounts,
+            vec![wsol_account, wallet.pubkey(), wallet.pubkey()]
+        );
+    }
+
+    #[test]
+    fn test_non_sol_route_is_not_wrapped() {
+        let wallet = Pubkey::new_unique();
+        let usdc: Pubkey = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            .parse()
+            .unwrap();
+
+        // Wrapping would fund WSOL that a USDC-spending first leg never touches
+        let err = sol_route_instructions(&wallet, &usdc, 500_000_000, vec![]).unwrap_err();
+        assert!(err.to_string().contains(&usdc.to_string()));
+    }
+
+    #[test]
+    fn test_contended_accounts_are_writable_pool_accounts() {
+        use solana_sdk::instruction::AccountMeta;
+
+        let wallet = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let config_account = Pubkey::new_unique();
+        let swap = |accounts: Vec<AccountMeta>| {
+            Instruction::new_with_bytes(
+                RaydiumSwapBuilder::AMM_V4_PROGRAM_ID.parse().unwrap(),
+                &[0u8; 17],
+                accounts,
+            )
+        };
+
+        let instructions = vec![
+            swap(vec![
+                AccountMeta::new(wallet, true),
+                AccountMeta::new(pool, false),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(config_account, false),
+            ]),
+            swap(vec![
+                AccountMeta::new(wallet, true),
+                AccountMeta::new(pool, false),
+            ]),
+            // Tip: writable, but not a pool we contend on
+            solana_sdk::system_instruction::transfer(&wallet, &Pubkey::new_unique(), 100_000),
+        ];
+
+        let mut expected = vec![pool, vault];
+        expected.sort();
+        assert_eq!(contended_accounts(&instructions), expected);
+    }
+
+    #[tokio::test]
+    async fn test_jito_bundle_path_taken_when_enabled() {
+        use solana_sdk::signature::Keypair;
+
+        let (mock_url, methods) = spawn_mock_jito();
+
+        let wallet = Arc::new(Keypair::new());
+        let jito_client = Arc::new(
+            JitoBundleClient::new_with_keypair_ref(String::new(), String::new(), wallet.clone())
+                .with_endpoints(vec![mock_url]),
+        );
+
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let executor = SwapExecutor::new(rpc_client, pool_registry, Some(jito_client)).unwrap();
+
+        let transaction = executor
+            .build_transaction(vec![], wallet.as_ref(), Hash::default())
+            .unwrap();
+
+        let result = executor
+            .submit_transaction(transaction, true)
+            .await
+            .unwrap();
+
+        assert_eq!(result, "mock-bundle-id");
+        let methods = methods.lock().unwrap();
+        assert_eq!(methods.first().map(String::as_str), Some("sendBundle"));
+        assert!(methods.iter().any(|m| m == "getInflightBundleStatuses"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_dex_refused_before_pool_resolution() {
+        let rpc_client = Arc::new(SolanaRpcClient::new(
+            "https://api.mainnet-beta.solana.com".to_string(),
+        ));
+        let pool_registry = Arc::new(PoolRegistry::new(rpc_client.clone()));
+        let mut executor = SwapExecutor::new(rpc_client, pool_registry, None).unwrap();
+        executor.set_dex_filter(DexFilter::parse(None, Some("PumpSwap")).unwrap());
+
+        let swap_params = SwapParams {
+            amount_in: 1_000_000,
+            minimum_amount_out: 1,
+            expected_amount_out: None,
+            swap_a_to_b: true,
+        };
+        let result = executor
+            .build_swap_instruction(
+                &DexType::PumpSwap,
+                "8sLbNZoA",
+                &swap_params,
+                &Pubkey::new_unique(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ArbError::Rejected(_))));
+    }
 }
@@ -0,0 +1,210 @@
+// Test doubles for running the swap pipeline without a validator or JITO
+//
+// - MockRpcClient: in-memory accounts, a fixed blockhash, simulations that
+//   always pass, and sent transactions that report as confirmed
+// - MockJitoSubmitter: records every bundle the engine queues
+//
+// Both plug into the same seams production uses (`RpcApi`, `BundleSink`), so a
+// test can feed a price map through pool resolution, instruction building and
+// bundle submission, then assert on the exact transactions that came out.
+
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::jito_submitter::BundleSink;
+use crate::rpc_client::RpcApi;
+use crate::token_program::MintTokenProgram;
+
+/// Size of an SPL token account (enough for the `amount` field at offset 64)
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// On-chain account served by `MockRpcClient`
+#[derive(Debug, Clone)]
+struct MockAccount {
+    owner: Pubkey,
+    data: Vec<u8>,
+}
+
+/// In-memory `RpcApi`: serves preset accounts, never touches the network
+pub struct MockRpcClient {
+    accounts: Mutex<HashMap<Pubkey, MockAccount>>,
+    blockhash: Hash,
+    sent: Mutex<Vec<Transaction>>,
+}
+
+impl Default for MockRpcClient {
+    fn default() -> Self {
+        Self {
+            accounts: Mutex::new(HashMap::new()),
+            blockhash: Hash::new_unique(),
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl MockRpcClient {
+    /// Serve `data` for `address`, owned by `owner`
+    pub fn set_account(&self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        self.accounts
+            .lock()
+            .unwrap()
+            .insert(address, MockAccount { owner, data });
+    }
+
+    /// Serve a classic SPL Token mint
+    pub fn set_mint(&self, mint: Pubkey) {
+        self.set_account(
+            mint,
+            spl_token::id(),
+            vec![0u8; spl_token::state::Mint::LEN],
+        );
+    }
+
+    /// Serve an SPL token account holding `amount`
+    pub fn set_token_account(&self, address: Pubkey, amount: u64) {
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        self.set_account(address, spl_token::id(), data);
+    }
+
+    /// Blockhash every built transaction is signed against
+    pub fn blockhash(&self) -> Hash {
+        self.blockhash
+    }
+
+    fn account(&self, address: &Pubkey) -> Result<MockAccount> {
+        self.accounts
+            .lock()
+            .unwrap()
+            .get(address)
+            .cloned()
+            .ok_or_else(|| anyhow!("Mock account {} not found", address))
+    }
+}
+
+impl RpcApi for MockRpcClient {
+    fn check_circuit_breaker(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_latest_blockhash(&self) -> Result<Hash> {
+        Ok(self.blockhash)
+    }
+
+    fn simulate_transaction(
+        &self,
+        _transaction: &Transaction,
+        _commitment: Option<CommitmentConfig>,
+    ) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        self.sent.lock().unwrap().push(transaction.clone());
+        Ok(transaction.signatures.first().copied().unwrap_or_default())
+    }
+
+    fn get_account_data(
+        &self,
+        pubkey: &Pubkey,
+        _commitment: Option<CommitmentConfig>,
+    ) -> Result<Vec<u8>> {
+        Ok(self.account(pubkey)?.data)
+    }
+
+    fn get_prioritization_fee(&self, _accounts: &[Pubkey]) -> Result<u64> {
+        Ok(0)
+    }
+
+    fn get_account_data_cached(&self, pubkey: &Pubkey) -> Result<Vec<u8>> {
+        Ok(self.account(pubkey)?.data)
+    }
+
+    fn prime_account_cache(&self, _pubkey: Pubkey, _data: Vec<u8>) {}
+
+    fn get_mint_token_program(&self, mint: &Pubkey) -> Result<MintTokenProgram> {
+        let account = self.account(mint)?;
+        MintTokenProgram::from_account(&account.owner, &account.data)
+    }
+
+    fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Vec<u8>>>> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.account(pubkey).ok().map(|account| account.data))
+            .collect())
+    }
+
+    fn account_exists(&self, pubkey: &Pubkey) -> Result<bool> {
+        Ok(self.account(pubkey).is_ok())
+    }
+
+    fn get_account_owner(&self, pubkey: &Pubkey) -> Result<Pubkey> {
+        Ok(self.account(pubkey)?.owner)
+    }
+
+    fn get_transaction_status(&self, signature: &Signature) -> Result<Option<bool>> {
+        let sent = self.sent.lock().unwrap();
+        Ok(sent
+            .iter()
+            .any(|tx| tx.signatures.first() == Some(signature))
+            .then_some(true))
+    }
+
+    fn health_check(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn get_slot(&self) -> Result<u64> {
+        Ok(1)
+    }
+}
+
+/// Bundle queued through `MockJitoSubmitter`
+#[derive(Debug, Clone)]
+pub struct SubmittedBundle {
+    pub transactions: Vec<VersionedTransaction>,
+    pub description: String,
+    pub expected_profit_sol: f64,
+    pub dedup_key: String,
+}
+
+/// `BundleSink` that records bundles instead of sending them to JITO
+#[derive(Default)]
+pub struct MockJitoSubmitter {
+    bundles: Mutex<Vec<SubmittedBundle>>,
+}
+
+impl MockJitoSubmitter {
+    /// Bundles queued so far, in order
+    pub fn bundles(&self) -> Vec<SubmittedBundle> {
+        self.bundles.lock().unwrap().clone()
+    }
+}
+
+impl BundleSink for MockJitoSubmitter {
+    fn submit_bundle(
+        &self,
+        transactions: Vec<VersionedTransaction>,
+        description: String,
+        expected_profit_sol: f64,
+        dedup_key: String,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.bundles.lock().unwrap().push(SubmittedBundle {
+            transactions,
+            description,
+            expected_profit_sol,
+            dedup_key,
+        });
+        Box::pin(async { Ok(()) })
+    }
+}
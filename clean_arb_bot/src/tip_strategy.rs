@@ -0,0 +1,237 @@
+// JITO tip policy
+//
+// Every place that sizes a JITO tip (cost calculation, bundle submission,
+// fee screening in the detectors) asks one `TipStrategy`, configured via
+// TIP_STRATEGY. Grammar - one component, or a comma-separated list whose
+// largest tip wins:
+//
+// - `fixed:<lamports>`  - constant tip (`fixed:100000`)
+// - `percent:<bps>`     - share of expected gross profit (`percent:1000` = 10%)
+// - `floor:<multiplier>` - landed-tip 99th percentile × multiplier (`floor:1.1`)
+//
+// Default `floor:1.1,percent:1000`: beat 99% of landed bundles by 10%, or
+// give up 10% of profit when that is more.
+
+use crate::jito_tip_monitor::JitoTipFloor;
+use anyhow::{bail, Context, Result};
+use std::fmt;
+
+/// Default TIP_STRATEGY
+pub const DEFAULT_TIP_STRATEGY: &str = "floor:1.1,percent:1000";
+
+/// Market-spike cap on floor-following tips (same as `JitoTipFloor::competitive_tip_99`)
+const MAX_FLOOR_TIP_LAMPORTS: u64 = 3_000_000; // 0.003 SOL
+
+/// How a JITO tip is sized from expected profit and the landed-tip floor
+#[derive(Debug, Clone, PartialEq)]
+pub enum TipStrategy {
+    /// Constant tip in lamports
+    Fixed(u64),
+    /// Basis points of expected gross profit
+    PercentOfProfit(u32),
+    /// Landed-tip 99th percentile × multiplier, capped at 0.003 SOL
+    FollowFloor { multiplier: f64 },
+    /// Largest tip of the listed strategies
+    Max(Vec<TipStrategy>),
+}
+
+impl Default for TipStrategy {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TIP_STRATEGY).expect("default TIP_STRATEGY parses")
+    }
+}
+
+impl TipStrategy {
+    /// Parse a TIP_STRATEGY value (see module docs for the grammar)
+    ///
+    /// # Errors
+    /// Returns error on an empty value, unknown kind or invalid number
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut strategies = value
+            .split(',')
+            .map(str::trim)
+            .filter(|component| !component.is_empty())
+            .map(Self::parse_component)
+            .collect::<Result<Vec<_>>>()?;
+
+        match strategies.len() {
+            0 => bail!("TIP_STRATEGY is empty"),
+            1 => Ok(strategies.remove(0)),
+            _ => Ok(Self::Max(strategies)),
+        }
+    }
+
+    fn parse_component(component: &str) -> Result<Self> {
+        let (kind, amount) = component.split_once(':').with_context(|| {
+            format!(
+                "Invalid tip strategy '{}': expected fixed:<lamports>, percent:<bps> or floor:<multiplier>",
+                component
+            )
+        })?;
+        let amount = amount.trim();
+
+        match kind.trim().to_lowercase().as_str() {
+            "fixed" => Ok(Self::Fixed(amount.parse().with_context(|| {
+                format!("Invalid fixed tip '{}': must be lamports", amount)
+            })?)),
+            "percent" => Ok(Self::PercentOfProfit(amount.parse().with_context(
+                || format!("Invalid percent tip '{}': must be basis points", amount),
+            )?)),
+            "floor" => {
+                let multiplier: f64 = amount.parse().with_context(|| {
+                    format!("Invalid floor multiplier '{}': must be a number", amount)
+                })?;
+                if !multiplier.is_finite() || multiplier <= 0.0 {
+                    bail!("Invalid floor multiplier {}: must be > 0", multiplier);
+                }
+                Ok(Self::FollowFloor { multiplier })
+            }
+            other => bail!(
+                "Unknown tip strategy '{}': expected fixed, percent or floor",
+                other
+            ),
+        }
+    }
+
+    /// Tip for a trade expected to make `expected_profit_lamports` gross
+    ///
+    /// # Arguments
+    /// * `expected_profit_lamports` - Expected gross profit
+    /// * `tip_floor` - Live landed-tip percentiles (conservative defaults if None)
+    ///
+    /// # Returns
+    /// Tip in lamports
+    pub fn tip_lamports(
+        &self,
+        expected_profit_lamports: u64,
+        tip_floor: Option<&JitoTipFloor>,
+    ) -> u64 {
+        match self {
+            Self::Fixed(lamports) => *lamports,
+            Self::PercentOfProfit(bps) => {
+                (expected_profit_lamports as u128 * *bps as u128 / 10_000) as u64
+            }
+            Self::FollowFloor { multiplier } => {
+                let p99 = match tip_floor {
+                    Some(floor) => floor.p99_lamports(),
+                    None => JitoTipFloor::default().p99_lamports(),
+                };
+                ((p99 as f64 * multiplier) as u64).min(MAX_FLOOR_TIP_LAMPORTS)
+            }
+            Self::Max(strategies) => strategies
+                .iter()
+                .map(|strategy| strategy.tip_lamports(expected_profit_lamports, tip_floor))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Share of gross profit always paid as tip (0.10 for `percent:1000`)
+    ///
+    /// Used to solve for the gross profit that leaves a target net.
+    pub fn profit_share(&self) -> f64 {
+        match self {
+            Self::Fixed(_) | Self::FollowFloor { .. } => 0.0,
+            Self::PercentOfProfit(bps) => *bps as f64 / 10_000.0,
+            Self::Max(strategies) => strategies
+                .iter()
+                .map(TipStrategy::profit_share)
+                .fold(0.0, f64::max),
+        }
+    }
+}
+
+impl fmt::Display for TipStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(lamports) => write!(f, "fixed:{}", lamports),
+            Self::PercentOfProfit(bps) => write!(f, "percent:{}", bps),
+            Self::FollowFloor { multiplier } => write!(f, "floor:{}", multiplier),
+            Self::Max(strategies) => {
+                let parts: Vec<String> = strategies.iter().map(|s| s.to_string()).collect();
+                write!(f, "{}", parts.join(","))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floor_with_p99(p99_sol: f64) -> JitoTipFloor {
+        JitoTipFloor {
+            p99: p99_sol,
+            ..JitoTipFloor::default()
+        }
+    }
+
+    #[test]
+    fn test_fixed_ignores_profit_and_floor() {
+        let strategy = TipStrategy::Fixed(100_000);
+        assert_eq!(strategy.tip_lamports(0, None), 100_000);
+        assert_eq!(
+            strategy.tip_lamports(5_000_000_000, Some(&floor_with_p99(0.01))),
+            100_000
+        );
+        assert_eq!(TipStrategy::parse("fixed:100000").unwrap(), strategy);
+    }
+
+    #[test]
+    fn test_percent_of_profit_in_basis_points() {
+        let strategy = TipStrategy::PercentOfProfit(1_000);
+        assert_eq!(strategy.tip_lamports(10_000_000, None), 1_000_000);
+        assert_eq!(strategy.tip_lamports(0, None), 0);
+        assert_eq!(
+            TipStrategy::PercentOfProfit(250).tip_lamports(10_000_000, None),
+            250_000
+        );
+        assert_eq!(TipStrategy::parse("percent:1000").unwrap(), strategy);
+    }
+
+    #[test]
+    fn test_follow_floor_scales_p99_with_spike_cap() {
+        let strategy = TipStrategy::FollowFloor { multiplier: 1.1 };
+        // 0.001 SOL p99 → 1.1M
+        assert_eq!(
+            strategy.tip_lamports(0, Some(&floor_with_p99(0.001))),
+            1_100_000
+        );
+        // 0.1 SOL spike → capped at 0.003 SOL
+        assert_eq!(
+            strategy.tip_lamports(0, Some(&floor_with_p99(0.1))),
+            MAX_FLOOR_TIP_LAMPORTS
+        );
+        // No live data: conservative default floor
+        assert_eq!(
+            strategy.tip_lamports(0, None),
+            strategy.tip_lamports(0, Some(&JitoTipFloor::default()))
+        );
+        assert_eq!(TipStrategy::parse("floor:1.1").unwrap(), strategy);
+        assert!(TipStrategy::parse("floor:0").is_err());
+    }
+
+    #[test]
+    fn test_max_takes_largest_component() {
+        let strategy = TipStrategy::default();
+        assert_eq!(
+            strategy,
+            TipStrategy::Max(vec![
+                TipStrategy::FollowFloor { multiplier: 1.1 },
+                TipStrategy::PercentOfProfit(1_000),
+            ])
+        );
+        let floor = floor_with_p99(0.001);
+
+        // Small profit: floor (1.1M) beats 10% (100k)
+        assert_eq!(strategy.tip_lamports(1_000_000, Some(&floor)), 1_100_000);
+        // Large profit: 10% (50M) beats floor
+        assert_eq!(strategy.tip_lamports(500_000_000, Some(&floor)), 50_000_000);
+        assert_eq!(strategy.profit_share(), 0.10);
+        assert_eq!(strategy.to_string(), DEFAULT_TIP_STRATEGY);
+
+        assert!(TipStrategy::parse("").is_err());
+        assert!(TipStrategy::parse("fixed:abc").is_err());
+        assert!(TipStrategy::parse("bribe:5").is_err());
+    }
+}
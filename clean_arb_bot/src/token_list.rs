@@ -0,0 +1,229 @@
+// Target-token allowlist / denylist
+//
+// Loaded from TOKEN_LIST_FILE (JSON, or TOML when the path ends in `.toml`)
+// and hot-reloaded whenever the file's mtime changes, so the tracked mints can
+// be edited without restarting. When no file is configured the allowlist falls
+// back to the comma-separated TARGET_TOKENS env var.
+//
+// File format (both keys optional):
+//   { "allow": ["<mint>", ...], "deny": ["<mint>", ...] }
+//   allow = ["<mint>"]        # TOML
+//   deny = ["<mint>"]
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::{info, warn};
+
+/// On-disk token list
+#[derive(Debug, Default, Deserialize)]
+struct TokenListFile {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+/// Mints to trade (allowlist) and mints never to trade (denylist)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenList {
+    /// Only these mints are traded (None = every mint)
+    pub allow: Option<HashSet<String>>,
+    /// Never traded, even if allowlisted
+    pub deny: HashSet<String>,
+}
+
+impl TokenList {
+    /// Build allowlist from a comma-separated TARGET_TOKENS value
+    pub fn from_target_tokens(value: Option<&str>) -> Self {
+        let allow = value.map(|s| {
+            s.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect::<HashSet<_>>()
+        });
+
+        Self {
+            allow,
+            deny: HashSet::new(),
+        }
+    }
+
+    /// Parse a token list file (TOML if the path ends in `.toml`, JSON otherwise)
+    pub fn parse(path: &Path, contents: &str) -> Result<Self> {
+        let file: TokenListFile = if path.extension().and_then(|e| e.to_str()) == Some("toml") {
+            toml::from_str(contents).context("Failed to parse token list TOML")?
+        } else {
+            serde_json::from_str(contents).context("Failed to parse token list JSON")?
+        };
+
+        let clean = |mints: Vec<String>| -> HashSet<String> {
+            mints
+                .into_iter()
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty())
+                .collect()
+        };
+
+        let allow = clean(file.allow);
+        Ok(Self {
+            allow: if allow.is_empty() { None } else { Some(allow) },
+            deny: clean(file.deny),
+        })
+    }
+
+    /// Whether a mint passes both lists
+    pub fn is_allowed(&self, mint: &str) -> bool {
+        !self.deny.contains(mint) && self.allow.as_ref().map_or(true, |a| a.contains(mint))
+    }
+}
+
+/// Token list backed by a file, reloaded when its mtime changes
+pub struct TokenListWatcher {
+    /// TOKEN_LIST_FILE path (None = TARGET_TOKENS fallback)
+    path: Option<PathBuf>,
+    /// Last successfully loaded list and the mtime it was read at
+    state: Mutex<(Option<SystemTime>, TokenList)>,
+}
+
+impl TokenListWatcher {
+    /// Create watcher for `path`, or the TARGET_TOKENS fallback when `None`
+    ///
+    /// # Errors
+    /// Returns error if the file is set but cannot be read or parsed at startup
+    pub fn new(path: Option<&str>) -> Result<Self> {
+        let Some(path) = path.map(PathBuf::from) else {
+            let list =
+                TokenList::from_target_tokens(std::env::var("TARGET_TOKENS").ok().as_deref());
+            return Ok(Self {
+                path: None,
+                state: Mutex::new((None, list)),
+            });
+        };
+
+        let mtime = Self::modified(&path)?;
+        let list = Self::load(&path)?;
+        info!(
+            "🎯 Token list loaded from {}: {} allowed, {} denied",
+            path.display(),
+            list.allow.as_ref().map_or(0, |a| a.len()),
+            list.deny.len()
+        );
+
+        Ok(Self {
+            path: Some(path),
+            state: Mutex::new((Some(mtime), list)),
+        })
+    }
+
+    /// Current token list, reloading the file first if it changed
+    ///
+    /// A file that fails to read or parse keeps the previous list active.
+    pub fn current(&self) -> TokenList {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if let Some(path) = &self.path {
+            match Self::modified(path) {
+                Ok(mtime) if state.0 != Some(mtime) => match Self::load(path) {
+                    Ok(list) => {
+                        info!(
+                            "🔄 Token list reloaded from {}: {} allowed, {} denied",
+                            path.display(),
+                            list.allow.as_ref().map_or(0, |a| a.len()),
+                            list.deny.len()
+                        );
+                        *state = (Some(mtime), list);
+                    }
+                    Err(e) => {
+                        warn!("⚠️ Token list reload failed, keeping previous list: {}", e);
+                        // Don't retry until the file changes again
+                        state.0 = Some(mtime);
+                    }
+                },
+                Ok(_) => {}
+                Err(e) => warn!("⚠️ Token list file unavailable: {}", e),
+            }
+        }
+
+        state.1.clone()
+    }
+
+    fn modified(path: &Path) -> Result<SystemTime> {
+        std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .with_context(|| format!("Failed to stat token list file {}", path.display()))
+    }
+
+    fn load(path: &Path) -> Result<TokenList> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read token list file {}", path.display()))?;
+        TokenList::parse(path, &contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_json_and_toml() {
+        let json = TokenList::parse(
+            Path::new("tokens.json"),
+            r#"{ "allow": ["MintA", "MintB"], "deny": ["MintB"] }"#,
+        )
+        .unwrap();
+        assert!(json.is_allowed("MintA"));
+        assert!(!json.is_allowed("MintB")); // denylist wins
+        assert!(!json.is_allowed("MintC")); // not allowlisted
+
+        let toml = TokenList::parse(Path::new("tokens.toml"), "deny = [\"Scam\"]\n").unwrap();
+        assert_eq!(toml.allow, None);
+        assert!(toml.is_allowed("Anything"));
+        assert!(!toml.is_allowed("Scam"));
+    }
+
+    #[test]
+    fn test_target_tokens_fallback() {
+        let list = TokenList::from_target_tokens(Some("MintA, MintB,,"));
+        assert_eq!(list.allow.as_ref().map(|a| a.len()), Some(2));
+        assert!(list.is_allowed("MintB"));
+        assert!(!list.is_allowed("MintC"));
+
+        assert!(TokenList::from_target_tokens(None).is_allowed("MintC"));
+    }
+
+    #[test]
+    fn test_reloads_when_mtime_changes() {
+        let path =
+            std::env::temp_dir().join(format!("token_list_test_{}.json", std::process::id()));
+        std::fs::write(&path, r#"{ "deny": ["MintA"] }"#).unwrap();
+
+        let watcher = TokenListWatcher::new(path.to_str()).unwrap();
+        assert!(!watcher.current().is_allowed("MintA"));
+
+        std::fs::write(&path, r#"{ "deny": ["MintB"] }"#).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5))
+            .unwrap();
+
+        let list = watcher.current();
+        assert!(list.is_allowed("MintA"));
+        assert!(!list.is_allowed("MintB"));
+
+        // Broken edits keep the last good list
+        std::fs::write(&path, "not json").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert!(!watcher.current().is_allowed("MintB"));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
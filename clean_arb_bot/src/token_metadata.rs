@@ -0,0 +1,239 @@
+// Token symbol / decimals registry
+//
+// Resolves mints to human-readable symbols for logs and the trade log. Seeded
+// with the common tokens; TOKEN_METADATA_SOURCE optionally backfills it at
+// startup from a token list - a local file or an HTTP(S) URL such as the
+// Jupiter token API. Both the Solana token-list format
+// (`{"tokens": [{"address", "symbol", "decimals"}, ...]}`) and a bare array of
+// the same objects (Jupiter) are accepted.
+//
+// Unknown mints fall back to the first 8 characters of the mint.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::{debug, info};
+
+/// Tokens known without any backfill: (mint, symbol, decimals)
+const SEED_TOKENS: &[(&str, &str, u8)] = &[
+    ("So11111111111111111111111111111111111111112", "SOL", 9),
+    ("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "USDC", 6),
+    ("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", "USDT", 6),
+    ("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263", "BONK", 5),
+    ("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", "JUP", 6),
+    ("EKpQGSJtjMFqKZ9KQanSqYXRcF8fBopzLHYxdM65zcjm", "WIF", 6),
+    ("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R", "RAY", 6),
+    ("HZ1JovNiVvGrGNiiYvEozEVgZ58xaU3RKwX8eACQBCt3", "PYTH", 6),
+    ("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So", "mSOL", 9),
+    ("J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn", "JitoSOL", 9),
+];
+
+/// Characters of the mint shown when the symbol is unknown
+const FALLBACK_MINT_CHARS: usize = 8;
+
+/// Symbol and decimals of one mint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// One token as listed in a token list file or API response
+#[derive(Debug, Deserialize)]
+struct TokenListEntry {
+    address: String,
+    symbol: String,
+    decimals: u8,
+}
+
+/// Token list document: Solana token-list object or bare array (Jupiter)
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TokenListDocument {
+    Wrapped { tokens: Vec<TokenListEntry> },
+    Bare(Vec<TokenListEntry>),
+}
+
+/// Mint → symbol/decimals lookups shared by the engine and the trade log
+pub struct TokenMetadataRegistry {
+    tokens: RwLock<HashMap<String, TokenMetadata>>,
+}
+
+impl Default for TokenMetadataRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenMetadataRegistry {
+    /// Create a registry seeded with the common tokens
+    pub fn new() -> Self {
+        let tokens = SEED_TOKENS
+            .iter()
+            .map(|(mint, symbol, decimals)| {
+                (
+                    mint.to_string(),
+                    TokenMetadata {
+                        symbol: symbol.to_string(),
+                        decimals: *decimals,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            tokens: RwLock::new(tokens),
+        }
+    }
+
+    /// Metadata for `mint`, if known
+    #[allow(dead_code)] // Swap math reads decimals on-chain; logs only need symbols
+    pub fn get(&self, mint: &str) -> Option<TokenMetadata> {
+        self.read().get(mint).cloned()
+    }
+
+    /// Display label for `mint`: its symbol, or the truncated mint when unknown
+    pub fn label(&self, mint: &str) -> String {
+        match self.read().get(mint) {
+            Some(metadata) => metadata.symbol.clone(),
+            None => mint.get(..FALLBACK_MINT_CHARS).unwrap_or(mint).to_string(),
+        }
+    }
+
+    /// Labels for a `/`-separated list of mints (e.g. a triangle's intermediates)
+    pub fn label_path(&self, mints: &str) -> String {
+        mints
+            .split('/')
+            .map(|mint| self.label(mint))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Backfill from a token list file or HTTP(S) URL
+    ///
+    /// Seeded entries are kept; every other listed mint is added or updated.
+    ///
+    /// # Arguments
+    /// * `source` - Local path, or `http://` / `https://` URL
+    ///
+    /// # Returns
+    /// Number of mints added or updated
+    ///
+    /// # Errors
+    /// Returns error if the list cannot be fetched, read or parsed
+    pub async fn backfill(&self, source: &str) -> Result<usize> {
+        let contents = if source.starts_with("http://") || source.starts_with("https://") {
+            reqwest::get(source)
+                .await
+                .with_context(|| format!("Failed to fetch token list {}", source))?
+                .error_for_status()
+                .with_context(|| format!("Token list request failed: {}", source))?
+                .text()
+                .await
+                .with_context(|| format!("Failed to read token list {}", source))?
+        } else {
+            std::fs::read_to_string(source)
+                .with_context(|| format!("Failed to read token list {}", source))?
+        };
+
+        let count = self.backfill_from_str(&contents)?;
+        info!("🏷️ Token metadata: {} mints loaded from {}", count, source);
+        Ok(count)
+    }
+
+    /// Backfill from token list JSON (see module docs for accepted formats)
+    fn backfill_from_str(&self, contents: &str) -> Result<usize> {
+        let entries = match serde_json::from_str(contents).context("Invalid token list JSON")? {
+            TokenListDocument::Wrapped { tokens } => tokens,
+            TokenListDocument::Bare(tokens) => tokens,
+        };
+
+        let mut tokens = self.write();
+        let mut count = 0;
+        for entry in entries {
+            if entry.symbol.trim().is_empty()
+                || SEED_TOKENS
+                    .iter()
+                    .any(|(mint, _, _)| *mint == entry.address)
+            {
+                continue;
+            }
+            tokens.insert(
+                entry.address,
+                TokenMetadata {
+                    symbol: entry.symbol.trim().to_string(),
+                    decimals: entry.decimals,
+                },
+            );
+            count += 1;
+        }
+        debug!(
+            "🏷️ Token metadata registry now holds {} mints",
+            tokens.len()
+        );
+        Ok(count)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, TokenMetadata>> {
+        match self.tokens.read() {
+            Ok(tokens) => tokens,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, TokenMetadata>> {
+        match self.tokens.write() {
+            Ok(tokens) => tokens,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_seeded_backfilled_and_unknown_mints() {
+        let registry = TokenMetadataRegistry::new();
+        assert_eq!(
+            registry.label("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263"),
+            "BONK"
+        );
+        assert_eq!(
+            registry.label("UnknownMint1111111111111111111111"),
+            "UnknownM"
+        );
+        assert_eq!(registry.label("SOL"), "SOL");
+
+        // Solana token-list format; seeded USDC must not be overridden
+        let wrapped = r#"{"tokens": [
+            {"address": "NewMint11111111111111111111111111", "symbol": "NEW", "decimals": 7},
+            {"address": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "symbol": "FAKE", "decimals": 0}
+        ]}"#;
+        assert_eq!(registry.backfill_from_str(wrapped).unwrap(), 1);
+        assert_eq!(
+            registry.get("NewMint11111111111111111111111111"),
+            Some(TokenMetadata {
+                symbol: "NEW".to_string(),
+                decimals: 7
+            })
+        );
+        assert_eq!(
+            registry.label("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+            "USDC"
+        );
+
+        // Jupiter bare-array format, extra fields ignored
+        let bare = r#"[{"address": "Other111111111111111111111111111", "symbol": "OTH",
+            "decimals": 9, "name": "Other", "tags": ["verified"]}]"#;
+        assert_eq!(registry.backfill_from_str(bare).unwrap(), 1);
+        assert_eq!(
+            registry.label_path(
+                "NewMint11111111111111111111111111/Other111111111111111111111111111/Zzzzzzzzzzzz"
+            ),
+            "NEW/OTH/Zzzzzzzz"
+        );
+    }
+}
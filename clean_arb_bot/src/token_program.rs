@@ -0,0 +1,313 @@
+// Token program detection (SPL Token vs Token-2022)
+//
+// Associated token accounts are derived from (wallet, token program, mint), so a
+// Token-2022 mint gets a different ATA than the classic derivation assumes, and
+// swap instructions must pass the mint's owning program. Token-2022 mints may
+// also carry a transfer fee extension that skims every transfer into or out of
+// a pool; output estimates have to account for it or the trade looks profitable
+// on paper and loses on-chain.
+
+use anyhow::Result;
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
+
+/// Token-2022 program ID
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Byte after the padded base mint marking a Token-2022 account as a mint
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// TLV extension type of `TransferFeeConfig`
+const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+
+/// Size of the `TransferFeeConfig` extension value
+const TRANSFER_FEE_CONFIG_LEN: usize = 108;
+
+/// Offset of `older_transfer_fee` inside `TransferFeeConfig`
+/// (config authority 32 + withdraw authority 32 + withheld amount 8)
+const OLDER_TRANSFER_FEE_OFFSET: usize = 72;
+
+/// Size of one serialized `TransferFee` (epoch u64, maximum_fee u64, basis_points u16)
+const TRANSFER_FEE_LEN: usize = 18;
+
+/// Program that owns a token mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenProgram {
+    /// Classic SPL Token program
+    Spl,
+    /// Token-2022 (token extensions) program
+    Token2022,
+}
+
+impl TokenProgram {
+    /// Detect the token program from a mint account's owner
+    ///
+    /// # Errors
+    /// Returns error if the owner is neither token program (not a mint)
+    pub fn from_owner(owner: &Pubkey) -> Result<Self> {
+        if *owner == spl_token::id() {
+            Ok(TokenProgram::Spl)
+        } else if *owner == TOKEN_2022_PROGRAM_ID {
+            Ok(TokenProgram::Token2022)
+        } else {
+            Err(anyhow::anyhow!(
+                "Account owned by {} is not a token mint",
+                owner
+            ))
+        }
+    }
+
+    /// Program ID to pass in instruction account metas
+    pub fn id(self) -> Pubkey {
+        match self {
+            TokenProgram::Spl => spl_token::id(),
+            TokenProgram::Token2022 => TOKEN_2022_PROGRAM_ID,
+        }
+    }
+}
+
+/// One transfer fee schedule entry (Token-2022 `TransferFee`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFee {
+    /// First epoch this fee applies to
+    pub epoch: u64,
+    /// Fee cap per transfer, in token base units
+    pub maximum_fee: u64,
+    pub basis_points: u16,
+}
+
+impl TransferFee {
+    /// Fee withheld from a transfer of `amount` (rounded up, capped at `maximum_fee`)
+    pub fn fee(&self, amount: u64) -> u64 {
+        if self.basis_points == 0 || amount == 0 {
+            return 0;
+        }
+        let raw = (amount as u128 * self.basis_points as u128).div_ceil(10_000);
+        raw.min(self.maximum_fee as u128) as u64
+    }
+}
+
+/// Token-2022 transfer fee extension (the schedule switches to `newer` at its epoch)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    pub older: TransferFee,
+    pub newer: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Worst-case fee for a transfer of `amount`
+    ///
+    /// Uses the larger of the two schedules so estimates stay conservative
+    /// around a fee change without having to track the current epoch.
+    pub fn max_fee(&self, amount: u64) -> u64 {
+        self.older.fee(amount).max(self.newer.fee(amount))
+    }
+}
+
+/// Token program and transfer fee of a mint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintTokenProgram {
+    pub program: TokenProgram,
+    /// Transfer fee extension (Token-2022 only)
+    pub transfer_fee: Option<TransferFeeConfig>,
+}
+
+impl MintTokenProgram {
+    /// Classic SPL Token mint (no extensions)
+    pub const SPL: Self = Self {
+        program: TokenProgram::Spl,
+        transfer_fee: None,
+    };
+
+    /// Decode from a mint account's owner and data
+    ///
+    /// # Arguments
+    /// * `owner` - Program owning the mint account
+    /// * `data` - Raw mint account data
+    ///
+    /// # Errors
+    /// Returns error if the account is not owned by a token program
+    pub fn from_account(owner: &Pubkey, data: &[u8]) -> Result<Self> {
+        let program = TokenProgram::from_owner(owner)?;
+        let transfer_fee = match program {
+            TokenProgram::Spl => None,
+            TokenProgram::Token2022 => parse_transfer_fee_config(data),
+        };
+        Ok(Self {
+            program,
+            transfer_fee,
+        })
+    }
+
+    /// Amount left after the transfer fee is withheld (unchanged without the extension)
+    pub fn amount_after_transfer_fee(&self, amount: u64) -> u64 {
+        match self.transfer_fee {
+            Some(config) => amount.saturating_sub(config.max_fee(amount)),
+            None => amount,
+        }
+    }
+}
+
+/// Associated token account of `wallet` for `mint` under the mint's token program
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, program: TokenProgram) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        wallet,
+        mint,
+        &program.id(),
+    )
+}
+
+/// Token program for instructions that only accept the classic SPL Token program
+///
+/// Whirlpool `swap`, Raydium AMM v4 and CLMM `swap` and Lifinity v1 `swap`
+/// hard-code the legacy program (Token-2022 pools need `swap_v2` variants where
+/// they exist), so a Token-2022 leg would only revert on-chain - fail it before
+/// building the bundle instead.
+///
+/// # Errors
+/// Returns error if either mint belongs to Token-2022
+pub fn require_spl_token_program(
+    mint_a: (&Pubkey, TokenProgram),
+    mint_b: (&Pubkey, TokenProgram),
+) -> Result<Pubkey> {
+    for (mint, program) in [mint_a, mint_b] {
+        if program == TokenProgram::Token2022 {
+            return Err(anyhow::anyhow!(
+                "Token-2022 mint {} is not supported by this swap instruction (needs swap_v2)",
+                mint
+            ));
+        }
+    }
+    Ok(spl_token::id())
+}
+
+/// Parse the `TransferFeeConfig` extension from Token-2022 mint data
+///
+/// Layout: base mint, zero padding up to the token account length, one account
+/// type byte, then TLV entries (type u16, length u16, value).
+fn parse_transfer_fee_config(data: &[u8]) -> Option<TransferFeeConfig> {
+    let account_type_offset = spl_token::state::Account::LEN;
+    if data.get(account_type_offset) != Some(&ACCOUNT_TYPE_MINT) {
+        return None;
+    }
+
+    let mut offset = account_type_offset + 1;
+    while offset + 4 <= data.len() {
+        let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let length = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let value = data.get(offset + 4..offset + 4 + length)?;
+
+        if extension_type == EXTENSION_TRANSFER_FEE_CONFIG {
+            if length < TRANSFER_FEE_CONFIG_LEN {
+                return None;
+            }
+            return Some(TransferFeeConfig {
+                older: parse_transfer_fee(&value[OLDER_TRANSFER_FEE_OFFSET..])?,
+                newer: parse_transfer_fee(&value[OLDER_TRANSFER_FEE_OFFSET + TRANSFER_FEE_LEN..])?,
+            });
+        }
+        // Type 0 marks the end of initialized extensions
+        if extension_type == 0 {
+            return None;
+        }
+        offset += 4 + length;
+    }
+    None
+}
+
+/// Decode one serialized `TransferFee`
+fn parse_transfer_fee(data: &[u8]) -> Option<TransferFee> {
+    let data = data.get(..TRANSFER_FEE_LEN)?;
+    Some(TransferFee {
+        epoch: u64::from_le_bytes(data[0..8].try_into().ok()?),
+        maximum_fee: u64::from_le_bytes(data[8..16].try_into().ok()?),
+        basis_points: u16::from_le_bytes(data[16..18].try_into().ok()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Token-2022 mint account data with a transfer fee extension
+    fn token_2022_mint_with_fee(older: TransferFee, newer: TransferFee) -> Vec<u8> {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        data.push(ACCOUNT_TYPE_MINT);
+        data.extend_from_slice(&EXTENSION_TRANSFER_FEE_CONFIG.to_le_bytes());
+        data.extend_from_slice(&(TRANSFER_FEE_CONFIG_LEN as u16).to_le_bytes());
+        data.extend_from_slice(&[0u8; OLDER_TRANSFER_FEE_OFFSET]);
+        for fee in [older, newer] {
+            data.extend_from_slice(&fee.epoch.to_le_bytes());
+            data.extend_from_slice(&fee.maximum_fee.to_le_bytes());
+            data.extend_from_slice(&fee.basis_points.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_token_2022_ata_derivation() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let spl_ata = associated_token_address(&wallet, &mint, TokenProgram::Spl);
+        let token_2022_ata = associated_token_address(&wallet, &mint, TokenProgram::Token2022);
+
+        // Classic derivation matches the SPL helper, Token-2022 derives a different address
+        assert_eq!(
+            spl_ata,
+            spl_associated_token_account::get_associated_token_address(&wallet, &mint)
+        );
+        assert_ne!(spl_ata, token_2022_ata);
+
+        // ATA seeds: [wallet, token program, mint] under the associated token program
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                wallet.as_ref(),
+                TOKEN_2022_PROGRAM_ID.as_ref(),
+                mint.as_ref(),
+            ],
+            &spl_associated_token_account::id(),
+        );
+        assert_eq!(token_2022_ata, expected);
+
+        assert_eq!(
+            TokenProgram::from_owner(&TOKEN_2022_PROGRAM_ID).unwrap(),
+            TokenProgram::Token2022
+        );
+        assert!(TokenProgram::from_owner(&Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn test_transfer_fee_extension() {
+        let older = TransferFee {
+            epoch: 0,
+            maximum_fee: 1_000,
+            basis_points: 100, // 1%
+        };
+        let newer = TransferFee {
+            epoch: 600,
+            maximum_fee: 5_000,
+            basis_points: 50,
+        };
+        let data = token_2022_mint_with_fee(older, newer);
+        let mint = MintTokenProgram::from_account(&TOKEN_2022_PROGRAM_ID, &data).unwrap();
+
+        let config = mint.transfer_fee.unwrap();
+        assert_eq!(config.older, older);
+        assert_eq!(config.newer, newer);
+
+        // 1% of 50_000 = 500 (older wins), rounded up for odd amounts, capped at 1_000
+        assert_eq!(mint.amount_after_transfer_fee(50_000), 49_500);
+        assert_eq!(older.fee(101), 2);
+        assert_eq!(older.fee(10_000_000), 1_000);
+        assert_eq!(config.max_fee(10_000_000), 5_000);
+
+        // Classic mints and extension-less Token-2022 mints pass amounts through
+        assert_eq!(
+            MintTokenProgram::SPL.amount_after_transfer_fee(50_000),
+            50_000
+        );
+        let plain = MintTokenProgram::from_account(&TOKEN_2022_PROGRAM_ID, &[0u8; 82]).unwrap();
+        assert_eq!(plain.transfer_fee, None);
+    }
+}
@@ -0,0 +1,156 @@
+// Token mint safety check
+//
+// Rejects tokens whose mint still has a live mint authority (supply can be
+// inflated under us) or freeze authority (our token account can be frozen
+// mid-arbitrage). Verdicts are cached per mint for the life of the process.
+// ALLOW_MINTABLE_TOKENS=true disables the check.
+
+use anyhow::{Context, Result};
+use solana_sdk::{program_option::COption, pubkey::Pubkey};
+use spl_token::state::Mint;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+use crate::rpc_client::SolanaRpcClient;
+
+/// Issuer-controlled stablecoins keep both authorities by design; trading them is intended
+const TRUSTED_MINTS: &[&str] = &[
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", // USDC
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB", // USDT
+];
+
+/// Why a mint failed the safety check (None = safe)
+///
+/// # Arguments
+/// * `mint` - Unpacked mint state
+///
+/// # Returns
+/// Description of the live authority, or None if both are revoked
+pub fn mint_safety_issue(mint: &Mint) -> Option<String> {
+    match (mint.mint_authority, mint.freeze_authority) {
+        (COption::Some(mint_auth), _) => Some(format!("live mint authority {}", mint_auth)),
+        (_, COption::Some(freeze_auth)) => Some(format!("live freeze authority {}", freeze_auth)),
+        _ => None,
+    }
+}
+
+/// Cached per-mint safety verdicts
+pub struct TokenSafetyChecker {
+    /// RPC client for fetching mint accounts
+    rpc_client: Arc<SolanaRpcClient>,
+    /// Skip the check entirely (ALLOW_MINTABLE_TOKENS=true)
+    allow_mintable: bool,
+    /// Mint -> failure reason (None = safe)
+    verdicts: Mutex<HashMap<Pubkey, Option<String>>>,
+}
+
+impl TokenSafetyChecker {
+    /// Create new checker
+    pub fn new(rpc_client: Arc<SolanaRpcClient>, allow_mintable: bool) -> Self {
+        if allow_mintable {
+            warn!("⚠️ ALLOW_MINTABLE_TOKENS=true - mint/freeze authority check disabled");
+        }
+
+        Self {
+            rpc_client,
+            allow_mintable,
+            verdicts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check a token mint before trading it
+    ///
+    /// # Arguments
+    /// * `mint` - Base58 mint address
+    ///
+    /// # Returns
+    /// Ok if safe to trade; Err naming the live authority otherwise.
+    /// RPC failures are returned as errors but not cached, so the mint is retried.
+    pub fn check(&self, mint: &str) -> Result<()> {
+        if self.allow_mintable || TRUSTED_MINTS.contains(&mint) {
+            return Ok(());
+        }
+
+        let mint_pubkey: Pubkey = mint
+            .parse()
+            .context(format!("Invalid token mint address: {}", mint))?;
+
+        let cached = self
+            .verdicts
+            .lock()
+            .ok()
+            .and_then(|verdicts| verdicts.get(&mint_pubkey).cloned());
+
+        let verdict = match cached {
+            Some(verdict) => verdict,
+            None => {
+                let state = self.rpc_client.get_mint(&mint_pubkey)?;
+                let verdict = mint_safety_issue(&state);
+                match &verdict {
+                    Some(issue) => warn!("🚫 Unsafe token {}: {}", mint, issue),
+                    None => debug!("✅ Token {} has no live mint/freeze authority", mint),
+                }
+                if let Ok(mut verdicts) = self.verdicts.lock() {
+                    verdicts.insert(mint_pubkey, verdict.clone());
+                }
+                verdict
+            }
+        };
+
+        match verdict {
+            Some(issue) => Err(anyhow::anyhow!(
+                "Token {} failed safety check: {}",
+                mint,
+                issue
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mint(mint_authority: COption<Pubkey>, freeze_authority: COption<Pubkey>) -> Mint {
+        Mint {
+            mint_authority,
+            supply: 1_000_000_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority,
+        }
+    }
+
+    #[test]
+    fn test_freeze_authority_rejected() {
+        let frozen = mint(COption::None, COption::Some(Pubkey::new_unique()));
+        let issue = mint_safety_issue(&frozen).unwrap();
+        assert!(issue.contains("freeze authority"));
+
+        let mintable = mint(COption::Some(Pubkey::new_unique()), COption::None);
+        assert!(mint_safety_issue(&mintable)
+            .unwrap()
+            .contains("mint authority"));
+    }
+
+    #[test]
+    fn test_revoked_authorities_pass() {
+        let revoked = mint(COption::None, COption::None);
+        assert_eq!(mint_safety_issue(&revoked), None);
+    }
+
+    #[test]
+    fn test_allow_mintable_and_trusted_mints_skip_rpc() {
+        // Unreachable RPC: these checks must not need it
+        let rpc = Arc::new(SolanaRpcClient::new("http://127.0.0.1:1".to_string()));
+
+        let permissive = TokenSafetyChecker::new(rpc.clone(), true);
+        assert!(permissive.check(&Pubkey::new_unique().to_string()).is_ok());
+
+        let strict = TokenSafetyChecker::new(rpc, false);
+        assert!(strict.check(TRUSTED_MINTS[0]).is_ok());
+        assert!(strict.check("not-a-mint").is_err());
+    }
+}
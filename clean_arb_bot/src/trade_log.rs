@@ -0,0 +1,229 @@
+// CSV trade log
+//
+// Appends one row per trade outcome to TRADE_LOG_PATH for offline analysis:
+// cost-guard rejections, submitted bundles, and their final landed/failed
+// result (with realized profit once known). A bundle therefore produces a
+// `submitted` row followed by a `landed` or `failed` row sharing its ID.
+// Rows are flushed as they are written; a mutex serializes concurrent writers.
+// The token_symbol column resolves each mint through the TokenMetadataRegistry.
+
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::token_metadata::TokenMetadataRegistry;
+
+const HEADER: &str =
+    "timestamp,token_mint,token_symbol,buy_dex,sell_dex,buy_pool,sell_pool,spread_pct,\
+position_size_sol,estimated_profit_sol,realized_profit_sol,jito_tip_lamports,tx_id,outcome";
+
+/// Final (or interim) state of a logged trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeOutcome {
+    /// Simulated in paper trading mode
+    Paper,
+    /// Queued for JITO, landing not yet known
+    Submitted,
+    /// Confirmed on-chain
+    Landed,
+    /// Sent (or simulated) but did not succeed
+    Failed,
+    /// Dropped by a pre-execution guard
+    Rejected,
+}
+
+impl TradeOutcome {
+    /// Label written to the `outcome` column
+    pub fn label(self) -> &'static str {
+        match self {
+            TradeOutcome::Paper => "paper",
+            TradeOutcome::Submitted => "submitted",
+            TradeOutcome::Landed => "landed",
+            TradeOutcome::Failed => "failed",
+            TradeOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+/// One trade as written to the log (outcome and timestamp are added at write time)
+#[derive(Debug, Clone, Default)]
+pub struct TradeRecord {
+    pub token_mint: String,
+    pub buy_dex: String,
+    pub sell_dex: String,
+    pub buy_pool: String,
+    pub sell_pool: String,
+    pub spread_pct: f64,
+    pub position_size_sol: f64,
+    pub estimated_profit_sol: f64,
+    pub realized_profit_sol: Option<f64>,
+    pub jito_tip_lamports: Option<u64>,
+    /// Bundle ID, transaction signature(s) or dedup key
+    pub tx_id: Option<String>,
+}
+
+impl TradeRecord {
+    /// Render as a CSV row (no trailing newline)
+    fn to_csv_row(&self, timestamp: &str, token_symbol: &str, outcome: TradeOutcome) -> String {
+        [
+            timestamp.to_string(),
+            csv_field(&self.token_mint),
+            csv_field(token_symbol),
+            csv_field(&self.buy_dex),
+            csv_field(&self.sell_dex),
+            csv_field(&self.buy_pool),
+            csv_field(&self.sell_pool),
+            format!("{:.4}", self.spread_pct),
+            format!("{:.9}", self.position_size_sol),
+            format!("{:.9}", self.estimated_profit_sol),
+            self.realized_profit_sol
+                .map(|p| format!("{:.9}", p))
+                .unwrap_or_default(),
+            self.jito_tip_lamports
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            self.tx_id.as_deref().map(csv_field).unwrap_or_default(),
+            outcome.label().to_string(),
+        ]
+        .join(",")
+    }
+}
+
+/// Quote a field if it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Append-only CSV trade log shared by all execution paths
+pub struct TradeLogger {
+    file: Mutex<File>,
+    token_metadata: Arc<TokenMetadataRegistry>,
+}
+
+impl TradeLogger {
+    /// Open (or create) the log at `path`, writing the header if the file is empty
+    ///
+    /// # Arguments
+    /// * `path` - CSV file to append to
+    /// * `token_metadata` - Resolves the token_symbol column
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be opened or the header cannot be written
+    pub fn open(path: &str, token_metadata: Arc<TokenMetadataRegistry>) -> Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open trade log {}", path))?;
+
+        let len = file
+            .metadata()
+            .with_context(|| format!("Failed to stat trade log {}", path))?
+            .len();
+        if len == 0 {
+            writeln!(file, "{}", HEADER).context("Failed to write trade log header")?;
+            file.flush().context("Failed to flush trade log header")?;
+        }
+
+        info!("🧾 Trade log: {}", path);
+        Ok(Self {
+            file: Mutex::new(file),
+            token_metadata,
+        })
+    }
+
+    /// Append one row and flush
+    ///
+    /// Write failures are logged, never returned - the trade log must not
+    /// interfere with execution.
+    pub fn log(&self, record: &TradeRecord, outcome: TradeOutcome) {
+        let row = record.to_csv_row(
+            &chrono::Utc::now().to_rfc3339(),
+            &self.token_metadata.label_path(&record.token_mint),
+            outcome,
+        );
+        let mut file = match self.file.lock() {
+            Ok(file) => file,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Err(e) = writeln!(file, "{}", row).and_then(|_| file.flush()) {
+            warn!("⚠️ Failed to write trade log row: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_row_escapes_and_blank_optionals() {
+        let record = TradeRecord {
+            token_mint: "MintA".to_string(),
+            buy_dex: "Raydium_AMM, v4".to_string(),
+            sell_dex: "Orca \"Whirlpool\"".to_string(),
+            spread_pct: 1.5,
+            ..Default::default()
+        };
+        let row = record.to_csv_row("T", "A", TradeOutcome::Rejected);
+        assert_eq!(
+            row,
+            "T,MintA,A,\"Raydium_AMM, v4\",\"Orca \"\"Whirlpool\"\"\",,,1.5000,\
+0.000000000,0.000000000,,,,rejected"
+        );
+        assert_eq!(row.split(',').count(), HEADER.split(',').count() + 1); // one quoted comma
+    }
+
+    #[test]
+    fn test_concurrent_writes_produce_whole_rows() {
+        let path = std::env::temp_dir().join(format!("trade_log_test_{}.csv", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let token_metadata = Arc::new(TokenMetadataRegistry::new());
+        let logger =
+            Arc::new(TradeLogger::open(path.to_str().unwrap(), token_metadata.clone()).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let logger = logger.clone();
+                std::thread::spawn(move || {
+                    let record = TradeRecord {
+                        token_mint: format!(
+                            "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263/Mint{}",
+                            i
+                        ),
+                        realized_profit_sol: Some(0.001),
+                        jito_tip_lamports: Some(10_000),
+                        tx_id: Some(format!("sig{}", i)),
+                        ..Default::default()
+                    };
+                    for _ in 0..25 {
+                        logger.log(&record, TradeOutcome::Landed);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Reopening an existing log must not repeat the header
+        drop(TradeLogger::open(path.to_str().unwrap(), token_metadata).unwrap());
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(lines.len(), 101);
+        assert!(lines[1..]
+            .iter()
+            .all(|l| l.split(',').count() == 14 && l.ends_with(",landed")));
+        assert!(lines[1].contains(",BONK/Mint"));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -1,32 +1,39 @@
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, info}; // CYCLE-6: Parallel processing
 
 use crate::dex_registry::DexRegistry;
 use crate::shredstream_client::TokenPrice;
 
+/// Wrapped SOL mint, the only anchor of cross-DEX cycles
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
 /// Triangle arbitrage opportunity (e.g., SOL → TokenA → TokenB → SOL)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TriangleOpportunity {
     pub path: Vec<String>, // [SOL, TokenA, TokenB, SOL]
     pub dexs: Vec<String>, // [DEX1, DEX2, DEX3]
     pub prices: Vec<f64>,  // [price1, price2, price3]
     pub estimated_profit_sol: f64,
     pub profit_percentage: f64,
+    pub position_cap_lamports: Option<u64>, // Depth-capped size (2-leg pairs), None = full position
 }
 
 /// Triangle arbitrage detector
+///
+/// Cross-DEX pairs are SOL-anchored whatever TRIANGLE_ANCHORS lists: quotes are
+/// priced in SOL and the bundle path these pairs execute through only wraps
+/// native SOL. USDC/USDT anchors apply to `SimpleTriangleDetector`, whose
+/// cycles route through Jupiter.
 pub struct TriangleArbitrage {
     dex_registry: DexRegistry,
-    sol_mint: String,
 }
 
 impl TriangleArbitrage {
     pub fn new() -> Self {
         Self {
             dex_registry: DexRegistry::new(),
-            // Wrapped SOL mint address
-            sol_mint: "So11111111111111111111111111111111111111112".to_string(),
         }
     }
 
@@ -113,11 +120,14 @@ impl TriangleArbitrage {
     /// Strategy: SOL → TokenA on DEX1 (cheap) → sell TokenA on DEX2 (expensive) → SOL
     /// This is the REAL arbitrage that exists on Solana
     /// CYCLE-6: Optimized with Rayon parallel processing (Grok recommendation)
+    ///
+    /// `capital_sol_for` gives the position size for a token (per-token override
+    /// or the global max).
     pub fn find_opportunities(
         &self,
         prices: &HashMap<String, TokenPrice>,
         config: &crate::config::Config,
-        capital_sol: f64,
+        capital_sol_for: &(dyn Fn(&[&str]) -> f64 + Sync),
     ) -> Vec<TriangleOpportunity> {
         // CYCLE-6: Performance benchmark timing
         let triangle_start = std::time::Instant::now();
@@ -141,7 +151,7 @@ impl TriangleArbitrage {
             .par_iter() // Parallel processing across CPU cores
             .filter_map(|(token_mint, token_price_list)| {
                 // Skip SOL itself
-                if token_mint == &self.sol_mint {
+                if token_mint == SOL_MINT {
                     return None;
                 }
 
@@ -160,6 +170,7 @@ impl TriangleArbitrage {
 
                 // Find opportunities for this token
                 let mut token_opps = Vec::new();
+                let capital_sol = capital_sol_for(&[token_mint.as_str()]);
 
                 // Try all pairs of DEXs for this token (using filtered prices)
                 for i in 0..filtered_prices.len() {
@@ -349,15 +360,12 @@ impl TriangleArbitrage {
 
         if profit_sol > 0.0 {
             Some(TriangleOpportunity {
-                path: vec![
-                    "SOL".to_string(),
-                    token_mint[..8].to_string(),
-                    "SOL".to_string(),
-                ],
+                path: vec!["SOL".to_string(), token_mint.to_string(), "SOL".to_string()],
                 dexs: vec![buy_dex.clone(), sell_dex.clone()],
                 prices: vec![buy_price, sell_price],
                 estimated_profit_sol: profit_sol,
                 profit_percentage,
+                position_cap_lamports: None,
             })
         } else {
             None
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// Type of DEX
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DexType {
     // Meteora variants
     MeteoraDammV1, // Meteora DAMM V1 (older version)
@@ -46,7 +46,7 @@ pub struct PoolInfo {
 }
 
 /// Swap parameters
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapParams {
     pub amount_in: u64,
     pub minimum_amount_out: u64,
@@ -54,6 +54,26 @@ pub struct SwapParams {
     pub swap_a_to_b: bool,                // true = A→B, false = B→A
 }
 
+/// Wallet-side accounts of a swap: the signer and its input/output token accounts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UserSwapAccounts {
+    /// Wallet signing the transfer out of `token_in`
+    pub owner: Pubkey,
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+}
+
+impl UserSwapAccounts {
+    /// (token A account, token B account), for instructions that list user accounts by pool side
+    pub fn by_side(&self, swap_a_to_b: bool) -> (Pubkey, Pubkey) {
+        if swap_a_to_b {
+            (self.token_in, self.token_out)
+        } else {
+            (self.token_out, self.token_in)
+        }
+    }
+}
+
 impl DexType {
     /// Parse from DEX string like "Meteora_DAMM_V2_81vA2wJx"
     pub fn from_dex_string(dex_str: &str) -> anyhow::Result<Self> {
@@ -108,6 +128,58 @@ impl DexType {
     }
 }
 
+impl DexType {
+    /// Every DEX type, in declaration order
+    pub const ALL: [DexType; 19] = [
+        DexType::MeteoraDammV1,
+        DexType::MeteoraDammV2,
+        DexType::MeteoraDlmm,
+        DexType::OrcaWhirlpools,
+        DexType::OrcaLegacy,
+        DexType::RaydiumAmmV4,
+        DexType::RaydiumClmm,
+        DexType::RaydiumCpmm,
+        DexType::RaydiumStable,
+        DexType::PumpSwap,
+        DexType::Jupiter,
+        DexType::Serum,
+        DexType::Aldrin,
+        DexType::Saros,
+        DexType::Crema,
+        DexType::Cropper,
+        DexType::Lifinity,
+        DexType::Fluxbeam,
+        DexType::HumidiFi,
+    ];
+
+    /// Canonical price feed prefix (inverse of `from_dex_string`)
+    ///
+    /// Combined with a short pool ID as "{prefix}_{short_id}", e.g. "Raydium_AMM_V4_58oQChx4"
+    pub fn feed_name(&self) -> &'static str {
+        match self {
+            DexType::MeteoraDammV1 => "Meteora_DAMM_V1",
+            DexType::MeteoraDammV2 => "Meteora_DAMM_V2",
+            DexType::MeteoraDlmm => "Meteora_DLMM",
+            DexType::OrcaWhirlpools => "Orca_Whirlpools",
+            DexType::OrcaLegacy => "Orca_Legacy",
+            DexType::RaydiumAmmV4 => "Raydium_AMM_V4",
+            DexType::RaydiumClmm => "Raydium_CLMM",
+            DexType::RaydiumCpmm => "Raydium_CPMM",
+            DexType::RaydiumStable => "Raydium_Stable",
+            DexType::PumpSwap => "PumpSwap",
+            DexType::Jupiter => "Jupiter",
+            DexType::Serum => "Serum",
+            DexType::Aldrin => "Aldrin",
+            DexType::Saros => "Saros",
+            DexType::Crema => "Crema",
+            DexType::Cropper => "Cropper",
+            DexType::Lifinity => "Lifinity",
+            DexType::Fluxbeam => "Fluxbeam",
+            DexType::HumidiFi => "HumidiFi",
+        }
+    }
+}
+
 /// Extract short pool ID from DEX string
 pub fn extract_pool_id(dex_str: &str) -> anyhow::Result<String> {
     let parts: Vec<&str> = dex_str.split('_').collect();